@@ -0,0 +1,83 @@
+//! Benchmark of the extraction of messages from a `XREAD` reply, comparing the previous extraction, which cloned every message, with the current one, which moves them out of the reply.
+//!
+//! Run it with `cargo bench --bench unwrap_stream_read_reply`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use redsumer::redis::{StreamId, StreamReadReply, UnwrapStreamReadReply, Value};
+
+/// Stream name of the benchmark reply.
+const KEY: &str = "bench-stream";
+
+/// Number of times each extraction is measured.
+const ITERATIONS: u32 = 200;
+
+/// Build a `XREAD` reply with *count* messages of 4 fields.
+fn build_reply(count: usize) -> StreamReadReply {
+    let messages: Vec<Value> = (0..count)
+        .map(|i| {
+            Value::Array(vec![
+                Value::BulkString(format!("{i}-0").into_bytes()),
+                Value::Array(
+                    (0..4)
+                        .flat_map(|f| {
+                            vec![
+                                Value::BulkString(format!("field-{f}").into_bytes()),
+                                Value::BulkString(vec![b'x'; 64]),
+                            ]
+                        })
+                        .collect(),
+                ),
+            ])
+        })
+        .collect();
+
+    redsumer::redis::from_redis_value(&Value::Array(vec![Value::Array(vec![
+        Value::BulkString(KEY.as_bytes().to_vec()),
+        Value::Array(messages),
+    ])]))
+    .expect("Invalid XREAD reply")
+}
+
+/// Extract the messages cloning them, as the previous implementation did.
+fn unwrap_by_key_cloning(reply: &StreamReadReply) -> Vec<StreamId> {
+    let mut ids: Vec<StreamId> = Vec::new();
+    for stream in reply.keys.iter() {
+        if stream.key.eq(KEY) {
+            ids.extend(stream.ids.to_owned());
+        }
+    }
+    ids
+}
+
+/// Measure the mean time of *extract* over fresh replies of *count* messages.
+fn measure<F>(count: usize, extract: F) -> Duration
+where
+    F: Fn(StreamReadReply) -> Vec<StreamId>,
+{
+    let mut total: Duration = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let reply: StreamReadReply = build_reply(count);
+        let started_at: Instant = Instant::now();
+        black_box(extract(black_box(reply)));
+        total += started_at.elapsed();
+    }
+
+    total / ITERATIONS
+}
+
+fn main() {
+    for count in [100, 1_000, 10_000] {
+        let cloning: Duration = measure(count, |reply| unwrap_by_key_cloning(&reply));
+        let moving: Duration = measure(count, |reply| reply.unwrap_by_key(&KEY));
+
+        println!(
+            "unwrap_by_key/{count}: cloning {cloning:?}, moving {moving:?} ({:.1}x faster)",
+            cloning.as_secs_f64() / moving.as_secs_f64().max(f64::EPSILON)
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::{
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+    time::Duration,
+};
+
+#[cfg(feature = "blocking")]
+use std::task::{Context, Poll, Waker};
+
+use redis::ErrorKind;
+#[allow(unused_imports)]
+use tracing::{error, Span};
+
+#[allow(unused_imports)]
+use super::result::{RedsumerError, RedsumerResult};
+
+/// A boxed future returned by a [`Runtime`].
+pub type RuntimeFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A boxed blocking task given to a [`Runtime`].
+pub type BlockingTask = Box<dyn FnOnce() + Send>;
+
+/// An async runtime where redsumer runs its blocking Redis commands and its waits, so it can be used with async-std, smol or any other runtime without Tokio.
+///
+/// Redsumer works without a registered runtime: with the `tokio` feature, enabled by default, inside a Tokio runtime, Tokio is used. Otherwise, blocking commands and waits are run in the current task, so they stall the executor of any other async runtime. Registering the runtime of the application with [`set_runtime`] reuses its blocking thread pool and its timers instead, like `smol::unblock` and `smol::Timer`, or `async_std::task::spawn_blocking` and `async_std::task::sleep`.
+pub trait Runtime: Debug + Send + Sync {
+    /// Run a blocking *task* in a thread where blocking is allowed.
+    ///
+    /// # Arguments:
+    /// - **task**: The blocking task to run.
+    ///
+    /// # Returns:
+    /// A [`RuntimeFuture`] resolved when the task has finished, or when it has panicked.
+    fn spawn_blocking(&self, task: BlockingTask) -> RuntimeFuture;
+
+    /// Wait for the given *duration* with a timer of the runtime.
+    ///
+    /// # Arguments:
+    /// - **duration**: The time to wait.
+    ///
+    /// # Returns:
+    /// A [`RuntimeFuture`] resolved when the duration has elapsed.
+    fn sleep(&self, duration: Duration) -> RuntimeFuture;
+}
+
+/// The [`Runtime`] registered with [`set_runtime`].
+static RUNTIME: OnceLock<Arc<dyn Runtime>> = OnceLock::new();
+
+/// Register the async [`Runtime`] used by all producers and consumers to run their blocking Redis commands and their waits. It must be called once, at startup, before any producer or consumer is used.
+///
+/// # Arguments:
+/// - **runtime**: The runtime to register.
+///
+/// # Returns:
+/// A [`RedsumerResult`] with the unit type. If a runtime was already registered, a [`RedsumerError`] is returned and the registered runtime is kept.
+pub fn set_runtime(runtime: Arc<dyn Runtime>) -> RedsumerResult<()> {
+    RUNTIME.set(runtime).map_err(|runtime| {
+        error!("A runtime was already registered, ignoring {:?}", runtime);
+        RedsumerError::from((
+            ErrorKind::ClientError,
+            "Runtime already set",
+            "a runtime can be registered only once".to_string(),
+        ))
+    })
+}
+
+/// Run a synchronous Redis *command* in the blocking thread pool of the given *runtime*, inside the current tracing span.
+async fn run_blocking_on<T, F>(runtime: &dyn Runtime, command: F) -> RedsumerResult<T>
+where
+    F: FnOnce() -> RedsumerResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let result: Arc<Mutex<Option<RedsumerResult<T>>>> = Arc::new(Mutex::new(None));
+
+    let task_result: Arc<Mutex<Option<RedsumerResult<T>>>> = result.to_owned();
+    let span: Span = Span::current();
+    runtime
+        .spawn_blocking(Box::new(move || {
+            let output: RedsumerResult<T> = span.in_scope(command);
+            *task_result.lock().unwrap_or_else(|e| e.into_inner()) = Some(output);
+        }))
+        .await;
+
+    let output: Option<RedsumerResult<T>> = result.lock().unwrap_or_else(|e| e.into_inner()).take();
+    output.unwrap_or_else(|| {
+        error!("Blocking Redis command did not finish");
+        Err(RedsumerError::from((
+            ErrorKind::ClientError,
+            "Blocking task failed",
+            "command did not finish".to_string(),
+        )))
+    })
+}
+
+/// Run a synchronous Redis *command* without stalling the async runtime.
+///
+/// If a [`Runtime`] is registered with [`set_runtime`], the command is run in its blocking thread pool. With the `tokio` feature, enabled by default, the command is run in the blocking thread pool of the current Tokio runtime with `spawn_blocking`, inside the current tracing span, so a long blocking read does not stall other tasks on the same worker. Without the feature, or outside a Tokio runtime, the command is run in the current task.
+///
+/// # Arguments:
+/// - **command**: The synchronous command to run.
+///
+/// # Returns:
+/// A [`RedsumerResult`] with the result of the command. If the blocking task panics or is cancelled, a [`RedsumerError`] is returned.
+pub async fn run_blocking<T, F>(command: F) -> RedsumerResult<T>
+where
+    F: FnOnce() -> RedsumerResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    if let Some(runtime) = RUNTIME.get() {
+        return run_blocking_on(runtime.as_ref(), command).await;
+    }
+
+    #[cfg(feature = "tokio")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        let span: Span = Span::current();
+        return handle
+            .spawn_blocking(move || span.in_scope(command))
+            .await
+            .unwrap_or_else(|e| {
+                error!("Error running blocking Redis command: {:?}", e);
+                Err(RedsumerError::from((
+                    ErrorKind::ClientError,
+                    "Blocking task failed",
+                    e.to_string(),
+                )))
+            });
+    }
+
+    command()
+}
+
+/// Wait for the given *duration* without stalling the async runtime.
+///
+/// If a [`Runtime`] is registered with [`set_runtime`], it is a timer of the runtime. With the `tokio` feature, inside a Tokio runtime, it is a Tokio timer. Otherwise the current thread sleeps.
+///
+/// # Arguments:
+/// - **duration**: The time to wait.
+///
+/// # Returns:
+/// A [`RedsumerResult`] with the unit type.
+pub async fn sleep(duration: Duration) -> RedsumerResult<()> {
+    if duration.is_zero() {
+        return Ok(());
+    }
+
+    if let Some(runtime) = RUNTIME.get() {
+        runtime.sleep(duration).await;
+        return Ok(());
+    }
+
+    #[cfg(feature = "tokio")]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        tokio::time::sleep(duration).await;
+        return Ok(());
+    }
+
+    thread::sleep(duration);
+    Ok(())
+}
+
+/// A waker that unparks the thread blocked by [`block_on`].
+#[cfg(feature = "blocking")]
+struct ThreadWaker(thread::Thread);
+
+#[cfg(feature = "blocking")]
+impl std::task::Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a *future* to completion in the current thread, parking the thread while the future is pending.
+///
+/// It does not need an async runtime, since the futures of redsumer run their Redis commands and their waits in the current thread outside a Tokio runtime. It must not be called from an async task, since it blocks the thread until the future completes.
+///
+/// # Arguments:
+/// - **future**: The future to run.
+///
+/// # Returns:
+/// The output of the future.
+#[cfg(feature = "blocking")]
+pub fn block_on<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    let mut future = std::pin::pin!(future);
+    let waker: Waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context: Context<'_> = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod test_block_on {
+    use super::*;
+
+    #[test]
+    fn test_block_on_ready() {
+        // Run a future that is ready:
+        let result: RedsumerResult<usize> = block_on(run_blocking(|| Ok(1)));
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_block_on_pending() {
+        // Run a future that waits in another thread:
+        let result: RedsumerResult<()> = block_on(sleep(Duration::from_millis(10)));
+
+        // Verify the result:
+        assert!(result.is_ok());
+    }
+}
+
+#[cfg(test)]
+mod test_runtime {
+    use super::*;
+
+    /// A runtime that runs blocking tasks in a new thread and waits with a blocking task.
+    #[derive(Debug)]
+    struct ThreadRuntime;
+
+    impl Runtime for ThreadRuntime {
+        fn spawn_blocking(&self, task: BlockingTask) -> RuntimeFuture {
+            Box::pin(async move {
+                let _ = thread::spawn(task).join();
+            })
+        }
+
+        fn sleep(&self, duration: Duration) -> RuntimeFuture {
+            self.spawn_blocking(Box::new(move || thread::sleep(duration)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_blocking_on_ok() {
+        // Run a command in the runtime:
+        let result: RedsumerResult<usize> = run_blocking_on(&ThreadRuntime, || Ok(1)).await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_blocking_on_error() {
+        // Run a failing command in the runtime:
+        let result: RedsumerResult<usize> = run_blocking_on(&ThreadRuntime, || {
+            Err(RedsumerError::from((ErrorKind::IoError, "Connection lost")))
+        })
+        .await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::IoError);
+    }
+
+    #[tokio::test]
+    async fn test_run_blocking_on_panic() {
+        // Run a panicking command in the runtime:
+        let result: RedsumerResult<usize> =
+            run_blocking_on(&ThreadRuntime, || panic!("command panicked")).await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+}
+
+#[cfg(test)]
+mod test_run_blocking {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_blocking_ok() {
+        // Run a command:
+        let result: RedsumerResult<usize> = run_blocking(|| Ok(1)).await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_blocking_error() {
+        // Run a failing command:
+        let result: RedsumerResult<usize> =
+            run_blocking(|| Err(RedsumerError::from((ErrorKind::IoError, "Connection lost"))))
+                .await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::IoError);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_run_blocking_yields() {
+        // Run a command that waits while another task runs on the same thread:
+        let (result, other) = tokio::join!(
+            run_blocking(|| {
+                thread::sleep(Duration::from_millis(50));
+                Ok(1)
+            }),
+            async { 2 }
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(other, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sleep() {
+        // Wait for a short time:
+        let start: std::time::Instant = std::time::Instant::now();
+        let result: RedsumerResult<()> = sleep(Duration::from_millis(20)).await;
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(start.elapsed().ge(&Duration::from_millis(20)));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_run_blocking_panic() {
+        // Run a panicking command:
+        let result: RedsumerResult<usize> = run_blocking(|| panic!("command panicked")).await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+}
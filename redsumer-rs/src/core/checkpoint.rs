@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use redis::{pipe, Commands, ToRedisArgs};
+use tracing::{debug, error};
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+
+/// Replace the fields of a checkpoint hash atomically, so fields that are not given anymore are removed.
+fn save_checkpoint<C, K>(conn: &mut C, key: K, fields: &[(&str, String)]) -> RedsumerResult<()>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    match pipe()
+        .atomic()
+        .del(&key)
+        .ignore()
+        .hset_multiple(&key, fields)
+        .ignore()
+        .query::<()>(conn)
+    {
+        Ok(()) => {
+            debug!("The checkpoint was saved");
+            Ok(())
+        }
+        Err(e) => {
+            error!("Error saving checkpoint: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Get the fields of a checkpoint hash. It is empty if the checkpoint does not exist.
+fn load_checkpoint<C, K>(conn: &mut C, key: K) -> RedsumerResult<HashMap<String, String>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    match conn.hgetall::<_, HashMap<String, String>>(key) {
+        Ok(fields) => {
+            debug!("Total checkpoint fields found: {}", fields.len());
+            Ok(fields)
+        }
+        Err(e) => {
+            error!("Error loading checkpoint: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// A trait that bundles methods to store consumer checkpoints in Redis hashes.
+pub trait CheckpointCommands {
+    /// Replace the fields of a checkpoint hash atomically.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the checkpoint hash, which must implement the `ToRedisArgs` trait.
+    /// - **fields**: The fields of the checkpoint with their values.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` if the checkpoint was saved. If an error occurs, the function will return an error result.
+    fn save_checkpoint<K>(&mut self, key: K, fields: &[(&str, String)]) -> RedsumerResult<()>
+    where
+        K: ToRedisArgs;
+
+    /// Get the fields of a checkpoint hash.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the checkpoint hash, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the fields of the checkpoint, which is empty if the checkpoint does not exist. If an error occurs, the function will return an error result.
+    fn load_checkpoint<K>(&mut self, key: K) -> RedsumerResult<HashMap<String, String>>
+    where
+        K: ToRedisArgs;
+}
+
+impl<C> CheckpointCommands for C
+where
+    C: Commands,
+{
+    fn save_checkpoint<K>(&mut self, key: K, fields: &[(&str, String)]) -> RedsumerResult<()>
+    where
+        K: ToRedisArgs,
+    {
+        save_checkpoint(self, key, fields)
+    }
+
+    fn load_checkpoint<K>(&mut self, key: K) -> RedsumerResult<HashMap<String, String>>
+    where
+        K: ToRedisArgs,
+    {
+        load_checkpoint(self, key)
+    }
+}
+
+#[cfg(test)]
+mod test_checkpoints {
+    use redis::{cmd, ErrorKind, Pipeline, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build the `MULTI`/`EXEC` transaction to replace the checkpoint fields.
+    fn transaction(key: &str, fields: &[(&str, String)]) -> Pipeline {
+        let mut pipeline: Pipeline = pipe();
+        pipeline
+            .atomic()
+            .add_command(cmd("DEL").arg(key).to_owned())
+            .ignore()
+            .add_command(cmd("HMSET").arg(key).arg(fields).to_owned())
+            .ignore();
+        pipeline
+    }
+
+    #[test]
+    fn test_save_checkpoint_ok() {
+        // Define the key and fields:
+        let key: &str = "checkpoint";
+        let fields: Vec<(&str, String)> = vec![("next_id_to_claim", "1-0".to_string())];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::with_values(
+            transaction(key, &fields),
+            Ok(vec![Value::Array(vec![Value::Int(1), Value::Okay])]),
+        )]);
+
+        // Save the checkpoint:
+        let result: RedsumerResult<()> = conn.save_checkpoint(key, &fields);
+
+        // Verify the result:
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_save_checkpoint_error() {
+        // Define the key and fields:
+        let key: &str = "checkpoint";
+        let fields: Vec<(&str, String)> = vec![("next_id_to_claim", "1-0".to_string())];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::with_values::<_, Value>(
+                transaction(key, &fields),
+                Err(RedisError::from((ErrorKind::ResponseError, "Error"))),
+            )]);
+
+        // Save the checkpoint:
+        let result: RedsumerResult<()> = conn.save_checkpoint(key, &fields);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+
+    #[test]
+    fn test_load_checkpoint_ok() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("HGETALL").arg("checkpoint"),
+                Ok(Value::Array(vec![
+                    Value::BulkString(b"next_id_to_claim".to_vec()),
+                    Value::BulkString(b"1-0".to_vec()),
+                ])),
+            )]);
+
+        // Load the checkpoint:
+        let result: RedsumerResult<HashMap<String, String>> = conn.load_checkpoint("checkpoint");
+
+        // Verify the result:
+        assert_eq!(
+            result.unwrap(),
+            HashMap::from([("next_id_to_claim".to_string(), "1-0".to_string())])
+        );
+    }
+}
@@ -1,20 +1,69 @@
-use std::fmt::Debug;
-
-use redis::{Client, ConnectionAddr, ConnectionInfo, ProtocolVersion, RedisConnectionInfo};
-
+use std::{
+    fmt::Debug,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use redis::{
+    cmd, Client, Connection, ConnectionAddr, ConnectionInfo, ConnectionLike, ErrorKind,
+    ProtocolVersion, RedisConnectionInfo, RedisResult, Value,
+};
+use serde::{Deserialize, Serialize, Serializer};
+use tracing::warn;
+
+use super::env::{env_lookup, get_var, require_var, VarLookup};
 #[allow(unused_imports)]
 use super::result::{RedsumerError, RedsumerResult};
 
+/// Default Redis server port used when `REDIS_PORT` is not set.
+pub const DEFAULT_PORT: u16 = 6379;
+
+/// Default Redis database used when `REDIS_DB` is not set.
+pub const DEFAULT_DB: i64 = 0;
+
+/// Separator between the namespace and the name of a key.
+pub const NAMESPACE_SEPARATOR: &str = ":";
+
+/// Get the key of *name* in *namespace*, with the `<namespace>:<name>` format. If there is no namespace, *name* is returned as it is.
+pub(crate) fn namespaced_key(namespace: Option<&str>, name: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}{NAMESPACE_SEPARATOR}{name}"),
+        None => name.to_owned(),
+    }
+}
+
+/// Get the name of *key* without *namespace*. If the key is not in the namespace, it is returned as it is.
+pub(crate) fn strip_namespace(namespace: Option<&str>, key: String) -> String {
+    match namespace {
+        Some(namespace) => key
+            .strip_prefix(namespace)
+            .and_then(|name| name.strip_prefix(NAMESPACE_SEPARATOR))
+            .map(str::to_owned)
+            .unwrap_or(key),
+        None => key,
+    }
+}
+
 /// Communication protocol to be used by the client. It is an alias for [`ProtocolVersion`].
 pub type CommunicationProtocol = ProtocolVersion;
 
 /// To hold credentials to authenticate in Redis.
 ///
 /// This credentials are used to authenticate in Redis when server requires it. If server does not require it, you set it to `None`.
-#[derive(Clone)]
+///
+/// Credentials can be an ACL user with its password ([`new`](ClientCredentials::new)), a password without user for the legacy `AUTH <password>` ([`password_only`](ClientCredentials::password_only)), or an access token issued by a managed Redis offering ([`token`](ClientCredentials::token)).
+///
+/// When serialized, the password is redacted, so serialized credentials are only useful for diagnostics.
+#[derive(Clone, Deserialize)]
 pub struct ClientCredentials {
-    /// User to authenticate in Redis service.
-    user: String,
+    /// User to authenticate in Redis service. If it is `None`, the server authenticates the `default` user with the password.
+    #[serde(default)]
+    user: Option<String>,
 
     /// Password to authenticate in Redis service.
     password: String,
@@ -22,8 +71,8 @@ pub struct ClientCredentials {
 
 impl ClientCredentials {
     /// Get *user*
-    fn get_user(&self) -> &str {
-        &self.user
+    fn get_user(&self) -> Option<&str> {
+        self.user.as_deref()
     }
 
     /// Get *password*
@@ -41,18 +90,301 @@ impl ClientCredentials {
     /// A new instance of [`ClientCredentials`].
     pub fn new(user: &str, password: &str) -> ClientCredentials {
         ClientCredentials {
-            user: user.to_owned(),
+            user: Some(user.to_owned()),
+            password: password.to_owned(),
+        }
+    }
+
+    /// Build a new instance of [`ClientCredentials`] without user, to authenticate with the legacy `AUTH <password>` command of servers without ACL users.
+    ///
+    /// # Arguments:
+    /// - **password**: The password to authenticate in Redis service.
+    ///
+    /// # Returns:
+    /// A new instance of [`ClientCredentials`].
+    pub fn password_only(password: &str) -> ClientCredentials {
+        ClientCredentials {
+            user: None,
             password: password.to_owned(),
         }
     }
+
+    /// Build a new instance of [`ClientCredentials`] with an access token, like the IAM tokens of managed Redis offerings. The token is sent as the password of `AUTH`.
+    ///
+    /// # Arguments:
+    /// - **user**: The username the token was issued for, if the offering requires it.
+    /// - **token**: The access token to authenticate in Redis service.
+    ///
+    /// # Returns:
+    /// A new instance of [`ClientCredentials`].
+    pub fn token(user: Option<&str>, token: &str) -> ClientCredentials {
+        ClientCredentials {
+            user: user.map(str::to_owned),
+            password: token.to_owned(),
+        }
+    }
 }
 
 impl Debug for ClientCredentials {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ClientCredentials")
-            .field("user", &self.user)
-            .field("password", &"****")
-            .finish()
+        let mut debug = f.debug_struct("ClientCredentials");
+        if let Some(user) = &self.user {
+            debug.field("user", user);
+        }
+        debug.field("password", &"****").finish()
+    }
+}
+
+impl Serialize for ClientCredentials {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct RedactedClientCredentials<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            user: Option<&'a str>,
+            password: &'a str,
+        }
+
+        RedactedClientCredentials {
+            user: self.get_user(),
+            password: "****",
+        }
+        .serialize(serializer)
+    }
+}
+
+/// (De)serialize a [`CommunicationProtocol`] as `RESP2` or `RESP3`.
+mod protocol_serde {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::CommunicationProtocol;
+
+    pub fn serialize<S>(protocol: &CommunicationProtocol, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match protocol {
+            CommunicationProtocol::RESP2 => "RESP2",
+            CommunicationProtocol::RESP3 => "RESP3",
+        })
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<CommunicationProtocol, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let protocol: String = String::deserialize(deserializer)?;
+
+        match protocol.to_uppercase().as_str() {
+            "RESP2" => Ok(CommunicationProtocol::RESP2),
+            "RESP3" => Ok(CommunicationProtocol::RESP3),
+            p => Err(D::Error::custom(format!("unsupported protocol {p}"))),
+        }
+    }
+}
+
+/// Default value of *port* when it is not given in a deserialized [`ClientArgs`].
+fn default_port() -> u16 {
+    DEFAULT_PORT
+}
+
+/// Default value of *db* when it is not given in a deserialized [`ClientArgs`].
+fn default_db() -> i64 {
+    DEFAULT_DB
+}
+
+/// Default value of *protocol* when it is not given in a deserialized [`ClientArgs`].
+fn default_protocol() -> CommunicationProtocol {
+    CommunicationProtocol::RESP2
+}
+
+/// The address of a Redis server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Endpoint {
+    /// Host of the Redis server.
+    host: String,
+
+    /// Port of the Redis server.
+    #[serde(default = "default_port")]
+    port: u16,
+}
+
+impl Endpoint {
+    /// Get *host*.
+    pub fn get_host(&self) -> &str {
+        &self.host
+    }
+
+    /// Get *port*.
+    pub fn get_port(&self) -> u16 {
+        self.port
+    }
+
+    /// Create a new instance of [`Endpoint`].
+    ///
+    /// # Arguments:
+    /// - **host**: Host of the Redis server.
+    /// - **port**: Port of the Redis server.
+    ///
+    /// # Returns:
+    /// A new instance of [`Endpoint`].
+    pub fn new(host: &str, port: u16) -> Endpoint {
+        Endpoint {
+            host: host.to_owned(),
+            port,
+        }
+    }
+}
+
+/// Parse an endpoint with the `<host>[:<port>]` format. If the port is not given, [`DEFAULT_PORT`] is used.
+impl FromStr for Endpoint {
+    type Err = RedsumerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid endpoint",
+                format!("{s} does not match the <host>[:<port>] format"),
+            ))
+        };
+
+        let (host, port) = match s.trim().rsplit_once(':') {
+            Some((host, port)) => (host, port.parse::<u16>().map_err(|_| invalid())?),
+            None => (s.trim(), DEFAULT_PORT),
+        };
+
+        match host.is_empty() {
+            true => Err(invalid()),
+            false => Ok(Endpoint::new(host, port)),
+        }
+    }
+}
+
+/// Default delay between the retries to connect to the Redis server.
+pub const DEFAULT_CONNECT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// (De)serialize an optional [`Duration`] as a number of `milliseconds`.
+mod optional_duration_milliseconds {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match duration {
+            Some(duration) => serializer.serialize_some(&(duration.as_millis() as u64)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<u64>::deserialize(deserializer).map(|millis| millis.map(Duration::from_millis))
+    }
+}
+
+/// Options of the sockets of the connections to the Redis server, to avoid half-open connections silently stalling long blocking reads like `XREADGROUP`.
+///
+/// They are applied to the connections opened by a [`NamedClient`], a [`FailoverClient`] or a [`RotatingClient`](crate::client::RotatingClient). TCP keepalive is always enabled with the default settings of the operating system, and `TCP_NODELAY` is enabled with the `tcp_nodelay` feature.
+///
+/// Timeouts are serialized in `milliseconds`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SocketOptions {
+    /// Max time to establish a connection. If it is `None`, the timeout of the operating system is used.
+    #[serde(with = "optional_duration_milliseconds")]
+    connect_timeout: Option<Duration>,
+
+    /// Max time to wait for a reply. If it is `None`, replies are awaited indefinitely.
+    #[serde(with = "optional_duration_milliseconds")]
+    read_timeout: Option<Duration>,
+
+    /// Max time to wait for a command to be written. If it is `None`, writes are awaited indefinitely.
+    #[serde(with = "optional_duration_milliseconds")]
+    write_timeout: Option<Duration>,
+
+    /// Number of retries when a connection can not be established.
+    connect_retries: usize,
+
+    /// Delay between the retries to connect.
+    #[serde(with = "crate::redsumer::consumer::duration_milliseconds")]
+    connect_retry_delay: Duration,
+}
+
+impl SocketOptions {
+    /// Get *connect timeout*.
+    pub fn get_connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// Get *read timeout*.
+    pub fn get_read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Get *write timeout*.
+    pub fn get_write_timeout(&self) -> Option<Duration> {
+        self.write_timeout
+    }
+
+    /// Get *connect retries*.
+    pub fn get_connect_retries(&self) -> usize {
+        self.connect_retries
+    }
+
+    /// Get *connect retry delay*.
+    pub fn get_connect_retry_delay(&self) -> Duration {
+        self.connect_retry_delay
+    }
+
+    /// Create a new instance of [`SocketOptions`] without timeouts nor retries.
+    pub fn new() -> SocketOptions {
+        SocketOptions::default()
+    }
+
+    /// Set the max *timeout* to establish a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> SocketOptions {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the max *timeout* to wait for a reply. It must be longer than the block time of the consumers, otherwise their blocking reads fail.
+    pub fn read_timeout(mut self, timeout: Duration) -> SocketOptions {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the max *timeout* to wait for a command to be written.
+    pub fn write_timeout(mut self, timeout: Duration) -> SocketOptions {
+        self.write_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the number of *retries* when a connection can not be established, waiting *delay* between them.
+    pub fn connect_retries(mut self, retries: usize, delay: Duration) -> SocketOptions {
+        self.connect_retries = retries;
+        self.connect_retry_delay = delay;
+        self
+    }
+}
+
+/// Options without timeouts nor retries, and a retry delay of [`DEFAULT_CONNECT_RETRY_DELAY`].
+impl Default for SocketOptions {
+    fn default() -> Self {
+        SocketOptions {
+            connect_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+            connect_retries: 0,
+            connect_retry_delay: DEFAULT_CONNECT_RETRY_DELAY,
+        }
     }
 }
 
@@ -63,22 +395,48 @@ impl Debug for ClientCredentials {
 /// `redis://[<user>][:<password>@]<host>:<port>/<db>`
 ///
 /// *user* and *password* are optional. If you don't need to authenticate in Redis, you can ignore them. *port* and *db* are mandatory for the connection. Another connection URL formats are not implemented yet.
-#[derive(Debug, Clone)]
+///
+/// It can be loaded from any format supported by [serde](https://docs.rs/serde). If *port*, *db* or *protocol* are not given, [`DEFAULT_PORT`], [`DEFAULT_DB`] and `RESP2` are used.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClientArgs {
     /// Credentials to authenticate in Redis.
+    #[serde(default)]
     credentials: Option<ClientCredentials>,
 
     /// Host to connect to Redis.
     host: String,
 
     /// Redis server port.
+    #[serde(default = "default_port")]
     port: u16,
 
     /// Redis database number.
+    #[serde(default = "default_db")]
     db: i64,
 
     /// Redis protocol version to communicate with the server.
+    #[serde(default = "default_protocol", with = "protocol_serde")]
     protocol: CommunicationProtocol,
+
+    /// Namespace prepended to the keys of the streams, to isolate tenants in the same Redis server.
+    #[serde(default)]
+    namespace: Option<String>,
+
+    /// Name of the connections, set with `CLIENT SETNAME` to identify them in `CLIENT LIST`.
+    #[serde(default)]
+    client_name: Option<String>,
+
+    /// Replica used by the inspection APIs, to offload reads from the primary.
+    #[serde(default)]
+    replica: Option<Endpoint>,
+
+    /// Endpoints tried in order when the connection to the host fails.
+    #[serde(default)]
+    failover_endpoints: Vec<Endpoint>,
+
+    /// Options of the sockets of the connections.
+    #[serde(default)]
+    socket_options: SocketOptions,
 }
 
 impl ClientArgs {
@@ -107,6 +465,152 @@ impl ClientArgs {
         self.protocol
     }
 
+    /// Get *namespace*.
+    pub fn get_namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Set the namespace prepended to the keys of the streams.
+    ///
+    /// Consumers, producers and the other instances built with these arguments use the key `<namespace>:<stream_name>` for every stream name they are given, including archive and replay streams, so multi-tenant deployments can isolate tenants without threading prefixes through every call.
+    ///
+    /// # Arguments:
+    /// - **namespace**: The namespace of the keys, like the tenant ID.
+    ///
+    /// # Returns:
+    /// The [`ClientArgs`] instance with the namespace set.
+    pub fn with_namespace(mut self, namespace: &str) -> ClientArgs {
+        self.namespace = Some(namespace.to_owned());
+        self
+    }
+
+    /// Get *client name*.
+    pub fn get_client_name(&self) -> Option<&str> {
+        self.client_name.as_deref()
+    }
+
+    /// Set the name of the connections, like `redsumer:<service>:<consumer_name>`, so they can be identified in `CLIENT LIST` on the server.
+    ///
+    /// The name is set with `CLIENT SETNAME` on every connection opened by a [`NamedClient`] or a [`RotatingClient`](crate::client::RotatingClient) built with these arguments. The [`Client`] built from the arguments alone does not set it. The name can not contain spaces, otherwise the server rejects the connections.
+    ///
+    /// # Arguments:
+    /// - **client_name**: The name of the connections.
+    ///
+    /// # Returns:
+    /// The [`ClientArgs`] instance with the client name set.
+    pub fn with_client_name(mut self, client_name: &str) -> ClientArgs {
+        self.client_name = Some(client_name.to_owned());
+        self
+    }
+
+    /// Get *replica* endpoint.
+    pub fn get_replica(&self) -> Option<&Endpoint> {
+        self.replica.as_ref()
+    }
+
+    /// Set a replica of the Redis server to offload heavy inspection workloads from the primary.
+    ///
+    /// The reads of the [`StreamInspector`](crate::inspector::StreamInspector), like `peek`, and the read-only methods of the [`Admin`](crate::admin::Admin), like `discover_streams`, are sent to the replica, while writes and consumers group reads stay on the primary. The replica is authenticated with the same credentials and database. Since replication is asynchronous, the replica can lag behind the primary.
+    ///
+    /// # Arguments:
+    /// - **host**: Host of the replica.
+    /// - **port**: Port of the replica.
+    ///
+    /// # Returns:
+    /// The [`ClientArgs`] instance with the replica set.
+    pub fn with_replica(mut self, host: &str, port: u16) -> ClientArgs {
+        self.replica = Some(Endpoint::new(host, port));
+        self
+    }
+
+    /// Get *failover endpoints*.
+    pub fn get_failover_endpoints(&self) -> &[Endpoint] {
+        &self.failover_endpoints
+    }
+
+    /// Get all the endpoints in the order they are tried: the host and port first, and then the failover endpoints.
+    pub fn get_endpoints(&self) -> Vec<Endpoint> {
+        let mut endpoints: Vec<Endpoint> = vec![Endpoint::new(self.get_host(), self.get_port())];
+        endpoints.extend_from_slice(self.get_failover_endpoints());
+        endpoints
+    }
+
+    /// Set the endpoints tried in order when the connection to the host fails, like the replicas that can be promoted when the primary is down. It provides basic high availability without a full Sentinel setup.
+    ///
+    /// The endpoints are used by a [`FailoverClient`] built with these arguments. The [`Client`] built from the arguments alone only connects to the host.
+    ///
+    /// # Arguments:
+    /// - **endpoints**: The failover endpoints, in the order they are tried.
+    ///
+    /// # Returns:
+    /// The [`ClientArgs`] instance with the failover endpoints set.
+    pub fn with_failover_endpoints(mut self, endpoints: Vec<Endpoint>) -> ClientArgs {
+        self.failover_endpoints = endpoints;
+        self
+    }
+
+    /// Get *socket options*.
+    pub fn get_socket_options(&self) -> &SocketOptions {
+        &self.socket_options
+    }
+
+    /// Set the options of the sockets of the connections, like timeouts and connection retries.
+    ///
+    /// The options are applied by a [`NamedClient`], a [`FailoverClient`] or a [`RotatingClient`](crate::client::RotatingClient) built with these arguments. The [`Client`] built from the arguments alone does not apply them.
+    ///
+    /// # Arguments:
+    /// - **options**: The [`SocketOptions`].
+    ///
+    /// # Returns:
+    /// The [`ClientArgs`] instance with the socket options set.
+    pub fn with_socket_options(mut self, options: SocketOptions) -> ClientArgs {
+        self.socket_options = options;
+        self
+    }
+
+    /// Build a new [`Client`] instance for the replica, or for the primary if no replica is set.
+    pub(crate) fn build_replica(&self) -> RedsumerResult<Client> {
+        match self.get_replica() {
+            Some(replica) => self.build_for(replica.get_host(), replica.get_port()),
+            None => self.build(),
+        }
+    }
+
+    /// Build a new [`Client`] instance for the server at *host* and *port*, with the credentials, database and protocol of the arguments.
+    fn build_for(&self, host: &str, port: u16) -> RedsumerResult<Client> {
+        let addr: ConnectionAddr = ConnectionAddr::Tcp(String::from(host), port);
+
+        let username: Option<String> = self
+            .get_credentials()
+            .to_owned()
+            .and_then(|c| c.get_user().map(str::to_string));
+
+        let password: Option<String> = self
+            .get_credentials()
+            .to_owned()
+            .map(|c| c.get_password().to_string());
+
+        let redis: RedisConnectionInfo = RedisConnectionInfo {
+            db: self.get_db(),
+            username,
+            password,
+            protocol: self.get_protocol(),
+        };
+
+        Ok(Client::open(ConnectionInfo { addr, redis })?)
+    }
+
+    /// Get the key of the stream *name* in the namespace, with the `<namespace>:<name>` format. If no namespace is set, *name* is returned as it is.
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the stream.
+    ///
+    /// # Returns:
+    /// The key of the stream in Redis.
+    pub fn get_key(&self, name: &str) -> String {
+        namespaced_key(self.get_namespace(), name)
+    }
+
     /// Create a new instance of [`ClientArgs`].
     ///
     /// # Arguments:
@@ -131,10 +635,142 @@ impl ClientArgs {
             port,
             db,
             protocol,
+            namespace: None,
+            client_name: None,
+            replica: None,
+            failover_endpoints: Vec::new(),
+            socket_options: SocketOptions::default(),
         }
     }
 }
 
+impl ClientArgs {
+    /// Create a new instance of [`ClientArgs`] from the environment variables of the process.
+    ///
+    /// The following variables are read:
+    ///
+    /// - **REDIS_HOST**: Host to connect to Redis. It is mandatory.
+    /// - **REDIS_PORT**: Redis server port. Default value is [`DEFAULT_PORT`].
+    /// - **REDIS_DB**: Redis database. Default value is [`DEFAULT_DB`].
+    /// - **REDIS_USER** and **REDIS_PASSWORD**: Credentials to authenticate in Redis. If only **REDIS_PASSWORD** is set, the credentials have no user, see [`ClientCredentials::password_only`]. **REDIS_USER** can not be set without **REDIS_PASSWORD**.
+    /// - **REDIS_PROTOCOL**: Redis protocol version, `RESP2` or `RESP3`. Default value is `RESP2`.
+    /// - **REDIS_NAMESPACE**: Namespace prepended to the keys of the streams. See [`with_namespace`](ClientArgs::with_namespace). By default, no namespace is set.
+    /// - **REDIS_CLIENT_NAME**: Name of the connections. See [`with_client_name`](ClientArgs::with_client_name). By default, no name is set.
+    /// - **REDIS_REPLICA_HOST** and **REDIS_REPLICA_PORT**: Replica used by the inspection APIs. See [`with_replica`](ClientArgs::with_replica). The port defaults to the port of the primary. By default, no replica is set.
+    /// - **REDIS_FAILOVER_ENDPOINTS**: Comma separated failover endpoints with the `<host>[:<port>]` format. See [`with_failover_endpoints`](ClientArgs::with_failover_endpoints). By default, no failover endpoints are set.
+    /// - **REDIS_CONNECT_TIMEOUT**, **REDIS_READ_TIMEOUT** and **REDIS_WRITE_TIMEOUT** (in `milliseconds`), **REDIS_CONNECT_RETRIES** and **REDIS_CONNECT_RETRY_DELAY** (in `milliseconds`): Options of the sockets. See [`with_socket_options`](ClientArgs::with_socket_options). By default, there are no timeouts nor retries.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a new instance of [`ClientArgs`]. If a mandatory variable is missing or a value is invalid, a [`RedsumerError`] is returned.
+    pub fn from_env() -> RedsumerResult<ClientArgs> {
+        Self::from_lookup(&env_lookup)
+    }
+
+    /// Create a new instance of [`ClientArgs`] from the variables given by *lookup*.
+    fn from_lookup<L>(lookup: &L) -> RedsumerResult<ClientArgs>
+    where
+        L: VarLookup,
+    {
+        let credentials: Option<ClientCredentials> = match (
+            get_var::<String, _>(lookup, "REDIS_USER")?,
+            get_var::<String, _>(lookup, "REDIS_PASSWORD")?,
+        ) {
+            (Some(user), Some(password)) => Some(ClientCredentials::new(&user, &password)),
+            (None, Some(password)) => Some(ClientCredentials::password_only(&password)),
+            (None, None) => None,
+            (Some(_), None) => {
+                return Err(RedsumerError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "Invalid environment variable",
+                    "REDIS_USER requires REDIS_PASSWORD".to_string(),
+                )))
+            }
+        };
+
+        let protocol: CommunicationProtocol = match get_var::<String, _>(lookup, "REDIS_PROTOCOL")?
+            .as_deref()
+        {
+            None => CommunicationProtocol::RESP2,
+            Some(p) if p.eq_ignore_ascii_case("RESP2") || p.eq("2") => CommunicationProtocol::RESP2,
+            Some(p) if p.eq_ignore_ascii_case("RESP3") || p.eq("3") => CommunicationProtocol::RESP3,
+            Some(p) => {
+                return Err(RedsumerError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "Invalid environment variable",
+                    format!("REDIS_PROTOCOL: unsupported protocol {p}"),
+                )))
+            }
+        };
+
+        let mut args: ClientArgs = ClientArgs::new(
+            credentials,
+            &require_var::<String, _>(lookup, "REDIS_HOST")?,
+            get_var(lookup, "REDIS_PORT")?.unwrap_or(DEFAULT_PORT),
+            get_var(lookup, "REDIS_DB")?.unwrap_or(DEFAULT_DB),
+            protocol,
+        );
+
+        if let Some(namespace) = get_var::<String, _>(lookup, "REDIS_NAMESPACE")? {
+            args = args.with_namespace(&namespace);
+        }
+
+        if let Some(client_name) = get_var::<String, _>(lookup, "REDIS_CLIENT_NAME")? {
+            args = args.with_client_name(&client_name);
+        }
+
+        let replica_port: Option<u16> = get_var(lookup, "REDIS_REPLICA_PORT")?;
+        match get_var::<String, _>(lookup, "REDIS_REPLICA_HOST")? {
+            Some(host) => {
+                let port: u16 = replica_port.unwrap_or(args.get_port());
+                args = args.with_replica(&host, port);
+            }
+            None if replica_port.is_some() => {
+                return Err(RedsumerError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "Invalid environment variable",
+                    "REDIS_REPLICA_PORT requires REDIS_REPLICA_HOST".to_string(),
+                )))
+            }
+            None => {}
+        }
+
+        if let Some(endpoints) = get_var::<String, _>(lookup, "REDIS_FAILOVER_ENDPOINTS")? {
+            args = args.with_failover_endpoints(
+                endpoints
+                    .split(',')
+                    .filter(|endpoint| !endpoint.trim().is_empty())
+                    .map(Endpoint::from_str)
+                    .collect::<RedsumerResult<Vec<Endpoint>>>()?,
+            );
+        }
+
+        let mut options: SocketOptions = SocketOptions::new();
+        if let Some(millis) = get_var(lookup, "REDIS_CONNECT_TIMEOUT")? {
+            options = options.connect_timeout(Duration::from_millis(millis));
+        }
+
+        if let Some(millis) = get_var(lookup, "REDIS_READ_TIMEOUT")? {
+            options = options.read_timeout(Duration::from_millis(millis));
+        }
+
+        if let Some(millis) = get_var(lookup, "REDIS_WRITE_TIMEOUT")? {
+            options = options.write_timeout(Duration::from_millis(millis));
+        }
+
+        if let Some(retries) = get_var(lookup, "REDIS_CONNECT_RETRIES")? {
+            let delay: Duration = get_var(lookup, "REDIS_CONNECT_RETRY_DELAY")?
+                .map_or(DEFAULT_CONNECT_RETRY_DELAY, Duration::from_millis);
+            options = options.connect_retries(retries, delay);
+        }
+        args = args.with_socket_options(options);
+
+        Ok(args)
+    }
+}
+
 /// To build a new instance of [`Client`].
 pub trait RedisClientBuilder {
     /// Build a new instance of [`Client`].
@@ -149,27 +785,411 @@ pub trait RedisClientBuilder {
 
 impl RedisClientBuilder for ClientArgs {
     fn build(&self) -> RedsumerResult<Client> {
-        let addr: ConnectionAddr =
-            ConnectionAddr::Tcp(String::from(self.get_host()), self.get_port());
+        self.build_for(self.get_host(), self.get_port())
+    }
+}
 
-        let username: Option<String> = self
-            .get_credentials()
-            .to_owned()
-            .map(|c| c.get_user().to_string());
+/// Open a new connection with *client*, retrying it and applying the timeouts of *options*, and set its *name* with `CLIENT SETNAME`, if it is given.
+pub(crate) fn connect(
+    client: &Client,
+    name: Option<&str>,
+    options: &SocketOptions,
+) -> RedisResult<Connection> {
+    let mut attempt: usize = 0;
+    let mut connection: Connection = loop {
+        let result: RedisResult<Connection> = match options.get_connect_timeout() {
+            Some(timeout) => client.get_connection_with_timeout(timeout),
+            None => client.get_connection(),
+        };
 
-        let password: Option<String> = self
-            .get_credentials()
-            .to_owned()
-            .map(|c| c.get_password().to_string());
+        match result {
+            Ok(connection) => break connection,
+            Err(e) if is_unreachable(&e) && attempt.lt(&options.get_connect_retries()) => {
+                attempt += 1;
+                warn!(
+                    "Error connecting to Redis, retry {attempt} of {}: {e}",
+                    options.get_connect_retries()
+                );
+                thread::sleep(options.get_connect_retry_delay());
+            }
+            Err(e) => return Err(e),
+        }
+    };
 
-        let redis: RedisConnectionInfo = RedisConnectionInfo {
-            db: self.get_db(),
-            username,
-            password,
-            protocol: self.get_protocol(),
+    connection.set_read_timeout(options.get_read_timeout())?;
+    connection.set_write_timeout(options.get_write_timeout())?;
+
+    if let Some(name) = name {
+        cmd("CLIENT")
+            .arg("SETNAME")
+            .arg(name)
+            .query::<()>(&mut connection)?;
+    }
+
+    Ok(connection)
+}
+
+/// A Redis client that names every connection it opens with `CLIENT SETNAME`, so the connections of a service can be identified in `CLIENT LIST` during incidents. The [socket options](ClientArgs::with_socket_options) of the arguments are applied to the connections.
+///
+/// It can be given to [`Consumer::from_client`](crate::consumer::Consumer::from_client) and [`Producer::from_client`](crate::producer::Producer::from_client) like any other [`StreamConnection`](crate::client::StreamConnection).
+#[derive(Debug, Clone)]
+pub struct NamedClient {
+    /// Redis client to open the connections.
+    client: Client,
+
+    /// Name of the connections.
+    name: String,
+
+    /// Options of the sockets of the connections.
+    options: SocketOptions,
+}
+
+impl NamedClient {
+    /// Get **name** of the connections.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Build a new [`NamedClient`] instance with the [client name](ClientArgs::with_client_name) of *args*.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments with a client name.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the new [`NamedClient`] instance. If *args* have no client name or the connection string is invalid, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs) -> RedsumerResult<NamedClient> {
+        let name: String = match args.get_client_name() {
+            Some(name) => name.to_owned(),
+            None => {
+                return Err(RedsumerError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "Invalid client arguments",
+                    "a client name is required".to_string(),
+                )))
+            }
         };
 
-        Client::open(ConnectionInfo { addr, redis })
+        Ok(NamedClient {
+            client: args.build()?,
+            name,
+            options: args.get_socket_options().to_owned(),
+        })
+    }
+}
+
+impl ConnectionLike for NamedClient {
+    fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
+        connect(&self.client, Some(&self.name), &self.options)?.req_packed_command(cmd)
+    }
+
+    fn req_packed_commands(
+        &mut self,
+        cmd: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> RedisResult<Vec<Value>> {
+        connect(&self.client, Some(&self.name), &self.options)?
+            .req_packed_commands(cmd, offset, count)
+    }
+
+    fn get_db(&self) -> i64 {
+        self.client.get_db()
+    }
+
+    fn check_connection(&mut self) -> bool {
+        match connect(&self.client, Some(&self.name), &self.options) {
+            Ok(mut connection) => connection.check_connection(),
+            Err(_) => false,
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        match connect(&self.client, Some(&self.name), &self.options) {
+            Ok(connection) => connection.is_open(),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Verify if *error* means the server could not be reached, so another endpoint can be tried.
+fn is_unreachable(error: &redis::RedisError) -> bool {
+    error.is_io_error() || error.is_connection_refusal() || error.is_timeout()
+}
+
+/// A Redis client that connects to the first reachable endpoint of an ordered list, for basic high availability without a full Sentinel setup.
+///
+/// Every command opens a new connection, with the [socket options](ClientArgs::with_socket_options) of the arguments. If the endpoint of the last connection can not be reached, the next endpoints are tried in order, and the first reachable one is used by the next commands. Host names are resolved again on every connection, so DNS records updated during a failover are followed. If an endpoint rejects a write because it is a read-only replica, the next endpoints are tried too, since the write was not applied. The connections are named with the [client name](ClientArgs::with_client_name) of the arguments.
+///
+/// It can be given to [`Consumer::from_client`](crate::consumer::Consumer::from_client) and [`Producer::from_client`](crate::producer::Producer::from_client) like any other [`StreamConnection`](crate::client::StreamConnection). Clones share the current endpoint.
+#[derive(Debug, Clone)]
+pub struct FailoverClient {
+    /// Endpoints in the order they are tried.
+    endpoints: Arc<Vec<Endpoint>>,
+
+    /// Redis clients of the endpoints, in the same order.
+    clients: Arc<Vec<Client>>,
+
+    /// Position of the endpoint of the last connection.
+    current: Arc<AtomicUsize>,
+
+    /// Name of the connections.
+    name: Option<String>,
+
+    /// Options of the sockets of the connections.
+    options: SocketOptions,
+}
+
+impl FailoverClient {
+    /// Get the **current endpoint**, where the next connection is tried first.
+    pub fn get_current_endpoint(&self) -> &Endpoint {
+        &self.endpoints[self.current.load(Ordering::SeqCst)]
+    }
+
+    /// Build a new [`FailoverClient`] instance with the [endpoints](ClientArgs::get_endpoints) of *args*.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments, with the failover endpoints.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the new [`FailoverClient`] instance. If the connection string of an endpoint is invalid, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs) -> RedsumerResult<FailoverClient> {
+        let endpoints: Vec<Endpoint> = args.get_endpoints();
+        let clients: Vec<Client> = endpoints
+            .iter()
+            .map(|endpoint| args.build_for(endpoint.get_host(), endpoint.get_port()))
+            .collect::<RedsumerResult<Vec<Client>>>()?;
+
+        Ok(FailoverClient {
+            endpoints: Arc::new(endpoints),
+            clients: Arc::new(clients),
+            current: Arc::default(),
+            name: args.get_client_name().map(str::to_owned),
+            options: args.get_socket_options().to_owned(),
+        })
+    }
+
+    /// Run a *command* on a new connection to the first reachable endpoint, starting from the current one.
+    fn with_connection<T, F>(&self, mut command: F) -> RedisResult<T>
+    where
+        F: FnMut(&mut Connection) -> RedisResult<T>,
+    {
+        let start: usize = self.current.load(Ordering::SeqCst);
+        let total: usize = self.clients.len();
+
+        let mut last_error: Option<redis::RedisError> = None;
+        for offset in 0..total {
+            let position: usize = (start + offset) % total;
+            let endpoint: &Endpoint = &self.endpoints[position];
+
+            let result: RedisResult<T> =
+                connect(&self.clients[position], self.name.as_deref(), &self.options)
+                    .and_then(|mut connection| command(&mut connection));
+
+            match result {
+                Err(e) if is_unreachable(&e) || e.kind().eq(&ErrorKind::ReadOnly) => {
+                    warn!(
+                        "Redis endpoint {}:{} is not available: {e}",
+                        endpoint.get_host(),
+                        endpoint.get_port()
+                    );
+                    last_error = Some(e);
+                }
+                result => {
+                    if position.ne(&start) {
+                        warn!(
+                            "Failing over to Redis endpoint {}:{}",
+                            endpoint.get_host(),
+                            endpoint.get_port()
+                        );
+                        self.current.store(position, Ordering::SeqCst);
+                    }
+                    return result;
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            redis::RedisError::from((ErrorKind::InvalidClientConfig, "No endpoints"))
+        }))
+    }
+}
+
+impl ConnectionLike for FailoverClient {
+    fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
+        self.with_connection(|connection| connection.req_packed_command(cmd))
+    }
+
+    fn req_packed_commands(
+        &mut self,
+        cmd: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> RedisResult<Vec<Value>> {
+        self.with_connection(|connection| connection.req_packed_commands(cmd, offset, count))
+    }
+
+    fn get_db(&self) -> i64 {
+        self.clients[0].get_db()
+    }
+
+    fn check_connection(&mut self) -> bool {
+        self.with_connection(|connection| Ok(connection.check_connection()))
+            .unwrap_or(false)
+    }
+
+    fn is_open(&self) -> bool {
+        self.with_connection(|connection| Ok(connection.is_open()))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod test_socket_options {
+    use std::time::Instant;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_socket_options_serde() {
+        // Deserialize the options:
+        let options: SocketOptions = serde_json::from_value(json!({
+            "read_timeout": 30000,
+            "connect_retries": 2
+        }))
+        .unwrap();
+
+        // Verify the result:
+        assert_eq!(
+            options,
+            SocketOptions::new()
+                .read_timeout(Duration::from_secs(30))
+                .connect_retries(2, DEFAULT_CONNECT_RETRY_DELAY)
+        );
+        assert_eq!(
+            serde_json::to_value(&options).unwrap()["read_timeout"],
+            json!(30000)
+        );
+    }
+
+    #[test]
+    fn test_connect_retries() {
+        // Define an unreachable server and the options:
+        let client: Client = Client::open("redis://127.0.0.1:1/0").unwrap();
+        let options: SocketOptions = SocketOptions::new()
+            .connect_timeout(Duration::from_millis(100))
+            .connect_retries(2, Duration::from_millis(20));
+
+        // Connect to the server:
+        let started: Instant = Instant::now();
+        let result: RedisResult<Connection> = connect(&client, None, &options);
+
+        // Verify the result:
+        assert!(is_unreachable(&result.err().unwrap()));
+        assert!(started.elapsed().ge(&Duration::from_millis(40)));
+    }
+}
+
+#[cfg(test)]
+mod test_failover_client {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_from_str() {
+        // Verify valid endpoints:
+        assert_eq!(
+            "redis-1:6380".parse::<Endpoint>().unwrap(),
+            Endpoint::new("redis-1", 6380)
+        );
+        assert_eq!(
+            " redis-2 ".parse::<Endpoint>().unwrap(),
+            Endpoint::new("redis-2", DEFAULT_PORT)
+        );
+
+        // Verify invalid endpoints:
+        assert!("redis:port".parse::<Endpoint>().is_err());
+        assert!(":6379".parse::<Endpoint>().is_err());
+    }
+
+    #[test]
+    fn test_failover_client_endpoints() {
+        // Create a new instance of FailoverClient:
+        let client: FailoverClient = FailoverClient::new(
+            &ClientArgs::new(None, "primary", 6379, 4, CommunicationProtocol::RESP2)
+                .with_failover_endpoints(vec![Endpoint::new("replica", 6380)]),
+        )
+        .unwrap();
+
+        // Verify the result:
+        assert_eq!(
+            client.endpoints.as_slice(),
+            &[
+                Endpoint::new("primary", 6379),
+                Endpoint::new("replica", 6380)
+            ]
+        );
+        assert_eq!(
+            client.get_current_endpoint(),
+            &Endpoint::new("primary", 6379)
+        );
+        assert_eq!(client.get_db(), 4);
+    }
+
+    #[test]
+    fn test_failover_client_all_endpoints_unreachable() {
+        // Create a new instance of FailoverClient with unreachable endpoints:
+        let mut client: FailoverClient = FailoverClient::new(
+            &ClientArgs::new(None, "127.0.0.1", 1, 0, CommunicationProtocol::RESP2)
+                .with_failover_endpoints(vec![Endpoint::new("127.0.0.1", 2)]),
+        )
+        .unwrap();
+
+        // Run a command:
+        let result: RedisResult<Value> = client.req_command(&cmd("PING"));
+
+        // Verify the result:
+        assert!(is_unreachable(&result.unwrap_err()));
+        assert_eq!(
+            client.get_current_endpoint(),
+            &Endpoint::new("127.0.0.1", 1)
+        );
+        assert!(!client.check_connection());
+    }
+}
+
+#[cfg(test)]
+mod test_named_client {
+    use super::*;
+
+    #[test]
+    fn test_named_client_new() {
+        // Create new instances of NamedClient with and without client name:
+        let args: ClientArgs =
+            ClientArgs::new(None, "127.0.0.1", 1, 3, CommunicationProtocol::RESP2);
+        let unnamed: RedsumerResult<NamedClient> = NamedClient::new(&args);
+        let named: NamedClient =
+            NamedClient::new(&args.with_client_name("redsumer:orders:consumer-1")).unwrap();
+
+        // Verify the result:
+        assert_eq!(unnamed.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+        assert_eq!(named.get_name(), "redsumer:orders:consumer-1");
+        assert_eq!(named.get_db(), 3);
+    }
+
+    #[test]
+    fn test_named_client_unreachable() {
+        // Create a new instance of NamedClient for an unreachable server:
+        let mut client: NamedClient = NamedClient::new(
+            &ClientArgs::new(None, "127.0.0.1", 1, 0, CommunicationProtocol::RESP2)
+                .with_client_name("redsumer"),
+        )
+        .unwrap();
+
+        // Verify the result:
+        assert!(client.req_command(&cmd("PING")).is_err());
+        assert!(!client.check_connection());
+        assert!(!client.is_open());
     }
 }
 
@@ -187,7 +1207,7 @@ mod test_client_credentials {
         let credentials: ClientCredentials = ClientCredentials::new(user, password);
 
         // Verify if the user and password are correct:
-        assert_eq!(credentials.get_user(), user);
+        assert_eq!(credentials.get_user(), Some(user));
         assert_eq!(credentials.get_password(), password);
     }
 
@@ -226,6 +1246,33 @@ mod test_client_credentials {
             cloned_credentials.get_password()
         );
     }
+
+    #[test]
+    fn test_client_credentials_password_only() {
+        // Create a new instance of ClientCredentials without user:
+        let credentials: ClientCredentials = ClientCredentials::password_only("password");
+
+        // Verify if the credentials are correct:
+        assert!(credentials.get_user().is_none());
+        assert_eq!(credentials.get_password(), "password");
+        assert_eq!(
+            format!("{:?}", credentials),
+            "ClientCredentials { password: \"****\" }"
+        );
+    }
+
+    #[test]
+    fn test_client_credentials_token() {
+        // Create new instances of ClientCredentials with a token:
+        let with_user: ClientCredentials = ClientCredentials::token(Some("app"), "token");
+        let without_user: ClientCredentials = ClientCredentials::token(None, "token");
+
+        // Verify if the credentials are correct:
+        assert_eq!(with_user.get_user(), Some("app"));
+        assert_eq!(with_user.get_password(), "token");
+        assert!(without_user.get_user().is_none());
+        assert_eq!(without_user.get_password(), "token");
+    }
 }
 
 #[cfg(test)]
@@ -258,7 +1305,10 @@ mod test_client_args {
 
         // Verify if the args are correct:
         assert!(args.get_credentials().is_some());
-        assert_eq!(args.get_credentials().to_owned().unwrap().get_user(), user);
+        assert_eq!(
+            args.get_credentials().to_owned().unwrap().get_user(),
+            Some(user)
+        );
         assert_eq!(
             args.get_credentials().to_owned().unwrap().get_password(),
             password
@@ -293,7 +1343,7 @@ mod test_client_args {
         let args: ClientArgs = ClientArgs::new(Some(credentials), host, port, db, protocol_version);
 
         // Verify if the debug is correct:
-        assert_eq!(format!("{:?}", args), "ClientArgs { credentials: Some(ClientCredentials { user: \"user\", password: \"****\" }), host: \"localhost\", port: 6379, db: 1, protocol: RESP2 }");
+        assert_eq!(format!("{:?}", args), "ClientArgs { credentials: Some(ClientCredentials { user: \"user\", password: \"****\" }), host: \"localhost\", port: 6379, db: 1, protocol: RESP2, namespace: None, client_name: None, replica: None, failover_endpoints: [], socket_options: SocketOptions { connect_timeout: None, read_timeout: None, write_timeout: None, connect_retries: 0, connect_retry_delay: 100ms } }");
     }
 
     #[test]
@@ -341,6 +1391,266 @@ mod test_client_args {
         assert_eq!(args.get_db(), cloned_args.get_db());
         assert_eq!(args.get_protocol(), cloned_args.get_protocol());
     }
+
+    #[test]
+    fn test_client_args_namespace() {
+        // Create new instances of ClientArgs with and without namespace:
+        let args: ClientArgs =
+            ClientArgs::new(None, "localhost", 6379, 0, CommunicationProtocol::RESP2);
+        let namespaced: ClientArgs = args.to_owned().with_namespace("tenant");
+
+        // Verify the keys:
+        assert_eq!(args.get_key("stream"), "stream");
+        assert_eq!(namespaced.get_namespace(), Some("tenant"));
+        assert_eq!(namespaced.get_key("stream"), "tenant:stream");
+    }
+}
+
+#[cfg(test)]
+mod test_client_args_from_env {
+    use std::collections::HashMap;
+
+    use redis::ErrorKind;
+
+    use super::*;
+
+    /// Build a lookup function from a list of variables.
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        move |k: &str| vars.get(k).cloned()
+    }
+
+    #[test]
+    fn test_client_args_from_env_defaults() {
+        // Create a new instance of ClientArgs with only the host:
+        let args: ClientArgs =
+            ClientArgs::from_lookup(&lookup(&[("REDIS_HOST", "redis")])).unwrap();
+
+        // Verify if the args are correct:
+        assert!(args.get_credentials().is_none());
+        assert_eq!(args.get_host(), "redis");
+        assert_eq!(args.get_port(), DEFAULT_PORT);
+        assert_eq!(args.get_db(), DEFAULT_DB);
+        assert_eq!(args.get_protocol(), CommunicationProtocol::RESP2);
+        assert!(args.get_namespace().is_none());
+    }
+
+    #[test]
+    fn test_client_args_from_env_all_variables() {
+        // Create a new instance of ClientArgs with all variables:
+        let args: ClientArgs = ClientArgs::from_lookup(&lookup(&[
+            ("REDIS_HOST", "redis"),
+            ("REDIS_PORT", "6380"),
+            ("REDIS_DB", "2"),
+            ("REDIS_USER", "user"),
+            ("REDIS_PASSWORD", "password"),
+            ("REDIS_PROTOCOL", "resp3"),
+            ("REDIS_NAMESPACE", "tenant"),
+            ("REDIS_CLIENT_NAME", "redsumer:orders:consumer-1"),
+            ("REDIS_REPLICA_HOST", "redis-replica"),
+            ("REDIS_FAILOVER_ENDPOINTS", "redis-2:6381, redis-3"),
+            ("REDIS_CONNECT_TIMEOUT", "1000"),
+            ("REDIS_READ_TIMEOUT", "30000"),
+            ("REDIS_CONNECT_RETRIES", "3"),
+        ]))
+        .unwrap();
+
+        // Verify if the args are correct:
+        assert_eq!(
+            args.get_credentials().to_owned().unwrap().get_user(),
+            Some("user")
+        );
+        assert_eq!(
+            args.get_credentials().to_owned().unwrap().get_password(),
+            "password"
+        );
+        assert_eq!(args.get_port(), 6380);
+        assert_eq!(args.get_db(), 2);
+        assert_eq!(args.get_protocol(), CommunicationProtocol::RESP3);
+        assert_eq!(args.get_namespace(), Some("tenant"));
+        assert_eq!(args.get_client_name(), Some("redsumer:orders:consumer-1"));
+        assert_eq!(
+            args.get_replica(),
+            Some(&Endpoint::new("redis-replica", 6380))
+        );
+        assert_eq!(
+            args.get_endpoints(),
+            vec![
+                Endpoint::new("redis", 6380),
+                Endpoint::new("redis-2", 6381),
+                Endpoint::new("redis-3", DEFAULT_PORT)
+            ]
+        );
+        assert_eq!(
+            args.get_socket_options(),
+            &SocketOptions::new()
+                .connect_timeout(Duration::from_secs(1))
+                .read_timeout(Duration::from_secs(30))
+                .connect_retries(3, DEFAULT_CONNECT_RETRY_DELAY)
+        );
+    }
+
+    #[test]
+    fn test_client_args_from_env_password_only() {
+        // Create a new instance of ClientArgs with a password without user:
+        let args: ClientArgs = ClientArgs::from_lookup(&lookup(&[
+            ("REDIS_HOST", "redis"),
+            ("REDIS_PASSWORD", "password"),
+        ]))
+        .unwrap();
+
+        // Verify if the credentials are correct:
+        let credentials: ClientCredentials = args.get_credentials().to_owned().unwrap();
+        assert!(credentials.get_user().is_none());
+        assert_eq!(credentials.get_password(), "password");
+    }
+
+    #[test]
+    fn test_client_args_from_env_errors() {
+        // Verify a missing host:
+        assert!(ClientArgs::from_lookup(&lookup(&[])).is_err());
+
+        // Verify an invalid port:
+        let result: RedsumerResult<ClientArgs> =
+            ClientArgs::from_lookup(&lookup(&[("REDIS_HOST", "redis"), ("REDIS_PORT", "abc")]));
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+
+        // Verify a user without password:
+        assert!(ClientArgs::from_lookup(&lookup(&[
+            ("REDIS_HOST", "redis"),
+            ("REDIS_USER", "user")
+        ]))
+        .is_err());
+
+        // Verify a replica port without host:
+        assert!(ClientArgs::from_lookup(&lookup(&[
+            ("REDIS_HOST", "redis"),
+            ("REDIS_REPLICA_PORT", "6380")
+        ]))
+        .is_err());
+
+        // Verify an unsupported protocol:
+        assert!(ClientArgs::from_lookup(&lookup(&[
+            ("REDIS_HOST", "redis"),
+            ("REDIS_PROTOCOL", "RESP1")
+        ]))
+        .is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_client_args_serde {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[test]
+    fn test_client_args_serialize_redacts_password() {
+        // Create a new instance of ClientArgs:
+        let args: ClientArgs = ClientArgs::new(
+            Some(ClientCredentials::new("user", "password")),
+            "localhost",
+            6379,
+            1,
+            CommunicationProtocol::RESP3,
+        );
+
+        // Serialize the args:
+        let value: Value = serde_json::to_value(&args).unwrap();
+
+        // Verify the password is redacted:
+        assert_eq!(
+            value,
+            json!({
+                "credentials": {"user": "user", "password": "****"},
+                "host": "localhost",
+                "port": 6379,
+                "db": 1,
+                "protocol": "RESP3",
+                "namespace": null,
+                "client_name": null,
+                "replica": null,
+                "failover_endpoints": [],
+                "socket_options": {
+                    "connect_timeout": null,
+                    "read_timeout": null,
+                    "write_timeout": null,
+                    "connect_retries": 0,
+                    "connect_retry_delay": 100
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_client_args_deserialize_ok() {
+        // Deserialize the args:
+        let args: ClientArgs = serde_json::from_value(json!({
+            "credentials": {"user": "user", "password": "password"},
+            "host": "redis",
+            "protocol": "resp3"
+        }))
+        .unwrap();
+
+        // Verify if the args are correct:
+        assert_eq!(
+            args.get_credentials().to_owned().unwrap().get_user(),
+            Some("user")
+        );
+        assert_eq!(
+            args.get_credentials().to_owned().unwrap().get_password(),
+            "password"
+        );
+        assert_eq!(args.get_host(), "redis");
+        assert_eq!(args.get_port(), DEFAULT_PORT);
+        assert_eq!(args.get_db(), DEFAULT_DB);
+        assert_eq!(args.get_protocol(), CommunicationProtocol::RESP3);
+    }
+
+    #[test]
+    fn test_client_args_serde_password_only() {
+        // Create a new instance of ClientArgs with a password without user:
+        let args: ClientArgs = ClientArgs::new(
+            Some(ClientCredentials::password_only("password")),
+            "localhost",
+            6379,
+            1,
+            CommunicationProtocol::RESP2,
+        );
+
+        // Serialize the args and deserialize credentials without user:
+        let value: Value = serde_json::to_value(&args).unwrap();
+        let deserialized: ClientArgs = serde_json::from_value(json!({
+            "credentials": {"password": "password"},
+            "host": "redis"
+        }))
+        .unwrap();
+
+        // Verify the result:
+        assert_eq!(value["credentials"], json!({"password": "****"}));
+        assert!(deserialized
+            .get_credentials()
+            .to_owned()
+            .unwrap()
+            .get_user()
+            .is_none());
+    }
+
+    #[test]
+    fn test_client_args_deserialize_error() {
+        // Verify a missing host:
+        assert!(serde_json::from_value::<ClientArgs>(json!({"port": 6379})).is_err());
+
+        // Verify an unsupported protocol:
+        assert!(serde_json::from_value::<ClientArgs>(
+            json!({"host": "redis", "protocol": "RESP1"})
+        )
+        .is_err());
+    }
 }
 
 #[cfg(test)]
@@ -377,4 +1687,58 @@ mod test_redis_client_builder {
         // Verify if the client is correct:
         assert!(client_result.is_ok());
     }
+
+    #[test]
+    fn test_redis_client_builder_replica() {
+        // Create new instances of ClientArgs with and without replica:
+        let args: ClientArgs = ClientArgs::new(
+            Some(ClientCredentials::new("user", "password")),
+            "primary",
+            6379,
+            2,
+            CommunicationProtocol::RESP2,
+        );
+        let with_replica: ClientArgs = args.to_owned().with_replica("replica", 6380);
+
+        // Build the replica clients:
+        let primary: Client = args.build_replica().unwrap();
+        let replica: Client = with_replica.build_replica().unwrap();
+
+        // Verify the connection info:
+        assert_eq!(
+            primary.get_connection_info().addr,
+            ConnectionAddr::Tcp("primary".to_string(), 6379)
+        );
+        assert_eq!(
+            replica.get_connection_info().addr,
+            ConnectionAddr::Tcp("replica".to_string(), 6380)
+        );
+        assert_eq!(replica.get_connection_info().redis.db, 2);
+        assert_eq!(
+            replica.get_connection_info().redis.username.as_deref(),
+            Some("user")
+        );
+    }
+
+    #[test]
+    fn test_redis_client_builder_ok_with_password_only() {
+        // Create a new instance of ClientArgs with a password without user:
+        let args: ClientArgs = ClientArgs::new(
+            Some(ClientCredentials::password_only("password")),
+            "mylocalhost",
+            6377,
+            16,
+            CommunicationProtocol::RESP2,
+        );
+
+        // Build a new instance of Client:
+        let client: Client = args.build().unwrap();
+
+        // Verify the connection info:
+        assert!(client.get_connection_info().redis.username.is_none());
+        assert_eq!(
+            client.get_connection_info().redis.password.as_deref(),
+            Some("password")
+        );
+    }
 }
@@ -1,5 +1,10 @@
-use redis::{Commands, ErrorKind, RedisError, RedisResult};
-use tracing::{debug, error};
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+use redis::{cmd, Commands, ConnectionLike, ErrorKind, InfoDict, RedisError, RedisResult};
+use tracing::{debug, error, warn};
 
 #[allow(unused_imports)]
 use crate::core::result::{RedsumerError, RedsumerResult};
@@ -38,14 +43,261 @@ where
     C: Commands,
 {
     fn ping(&mut self) -> RedsumerResult<String> {
-        ping(self)
+        ping(self).map_err(RedsumerError::ConnectionError)
+    }
+}
+
+/// Version of the Redis server, as reported by the `redis_version` field of `INFO server`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ServerVersion {
+    /// Major version.
+    major: u32,
+
+    /// Minor version.
+    minor: u32,
+
+    /// Patch version.
+    patch: u32,
+}
+
+impl ServerVersion {
+    /// Get **major** version.
+    pub fn get_major(&self) -> u32 {
+        self.major
+    }
+
+    /// Get **minor** version.
+    pub fn get_minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Get **patch** version.
+    pub fn get_patch(&self) -> u32 {
+        self.patch
+    }
+
+    /// Build a new [`ServerVersion`] instance.
+    ///
+    /// # Arguments:
+    /// - **major**: Major version.
+    /// - **minor**: Minor version.
+    /// - **patch**: Patch version.
+    ///
+    /// # Returns:
+    /// A new instance of [`ServerVersion`].
+    pub fn new(major: u32, minor: u32, patch: u32) -> ServerVersion {
+        ServerVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl Display for ServerVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Parse a version like `6.0.9`. Missing minor and patch versions are `0`.
+impl FromStr for ServerVersion {
+    type Err = RedsumerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(3, '.').map(|part| {
+            part.parse::<u32>().map_err(|_| {
+                RedsumerError::DeserializationError(format!(
+                    "Invalid server version, expected <major>.<minor>.<patch>, got: {s}"
+                ))
+            })
+        });
+
+        Ok(ServerVersion::new(
+            parts.next().unwrap_or(Ok(0))?,
+            parts.next().unwrap_or(Ok(0))?,
+            parts.next().unwrap_or(Ok(0))?,
+        ))
+    }
+}
+
+/// A feature of Redis streams that is not available in every server version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerFeature {
+    /// `XAUTOCLAIM`, available since Redis 6.2.
+    AutoClaim,
+
+    /// The `NOMKSTREAM` option of `XADD`, available since Redis 6.2.
+    NoMkStream,
+
+    /// The `MINID` trimming strategy, available since Redis 6.2.
+    TrimMinId,
+
+    /// The `lag` and `entries-read` fields of `XINFO GROUPS`, available since Redis 7.0.
+    GroupLag,
+
+    /// `XACKDEL`, available since Redis 8.2.
+    AckDel,
+}
+
+impl ServerFeature {
+    /// Get the min [`ServerVersion`] that supports the feature.
+    pub fn get_min_version(&self) -> ServerVersion {
+        match self {
+            ServerFeature::AutoClaim | ServerFeature::NoMkStream | ServerFeature::TrimMinId => {
+                ServerVersion::new(6, 2, 0)
+            }
+            ServerFeature::GroupLag => ServerVersion::new(7, 0, 0),
+            ServerFeature::AckDel => ServerVersion::new(8, 2, 0),
+        }
     }
 }
 
+impl Display for ServerFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ServerFeature::AutoClaim => write!(f, "XAUTOCLAIM"),
+            ServerFeature::NoMkStream => write!(f, "XADD NOMKSTREAM"),
+            ServerFeature::TrimMinId => write!(f, "MINID trimming"),
+            ServerFeature::GroupLag => write!(f, "consumers group lag"),
+            ServerFeature::AckDel => write!(f, "XACKDEL"),
+        }
+    }
+}
+
+/// Capabilities of the Redis server, detected from its version when a producer or a consumer is created, to pick the commands it supports.
+///
+/// If the version could not be detected, like on servers that disable `INFO`, every feature is assumed to be supported and the server reports the commands it does not know.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ServerCapabilities {
+    /// Version of the server. It is `None` if it could not be detected.
+    version: Option<ServerVersion>,
+}
+
+impl ServerCapabilities {
+    /// Get **version** of the server. It is `None` if it could not be detected.
+    pub fn get_version(&self) -> Option<ServerVersion> {
+        self.version
+    }
+
+    /// Build a new [`ServerCapabilities`] instance.
+    ///
+    /// # Arguments:
+    /// - **version**: The version of the server, or `None` if it is unknown.
+    ///
+    /// # Returns:
+    /// A new instance of [`ServerCapabilities`].
+    pub fn new(version: Option<ServerVersion>) -> ServerCapabilities {
+        ServerCapabilities { version }
+    }
+
+    /// Verify if the server supports a *feature*. It is `true` if the server version is unknown.
+    pub fn supports(&self, feature: ServerFeature) -> bool {
+        match self.version {
+            Some(version) => version.ge(&feature.get_min_version()),
+            None => true,
+        }
+    }
+
+    /// Verify that the server supports a *feature*.
+    ///
+    /// # Arguments:
+    /// - **feature**: The required [`ServerFeature`].
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` if the feature is supported. Otherwise, a [`RedsumerError`] with the version required by the feature.
+    pub fn require(&self, feature: ServerFeature) -> RedsumerResult<()> {
+        match (self.supports(feature), self.version) {
+            (false, Some(version)) => {
+                error!("{feature} is not supported by Redis server {version}");
+                Err(RedsumerError::from((
+                    ErrorKind::ClientError,
+                    "Unsupported server feature",
+                    format!(
+                        "{feature} requires Redis {} or later, but the server version is {version}",
+                        feature.get_min_version()
+                    ),
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn get_server_version<C>(c: &mut C) -> RedsumerResult<ServerVersion>
+where
+    C: Commands,
+{
+    let info: InfoDict = cmd("INFO").arg("server").query(c).map_err(|e| {
+        error!("Error getting the server information: {:?}", e);
+        e
+    })?;
+
+    match info.get::<String>("redis_version") {
+        Some(version) => {
+            debug!("Redis server version: {version}");
+            version.parse()
+        }
+        None => {
+            error!("The server information does not include the redis_version field");
+            Err(RedsumerError::DeserializationError(
+                "The server information does not include the redis_version field".to_owned(),
+            ))
+        }
+    }
+}
+
+/// A trait to get information about the Redis server.
+pub trait ServerCommands {
+    /// Get the version of the Redis server, to select the commands it supports.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the [`ServerVersion`]. If `INFO server` fails or its reply does not include a valid `redis_version` field, a [`RedsumerError`] is returned.
+    fn get_server_version(&mut self) -> RedsumerResult<ServerVersion>;
+
+    /// Get the [`ServerCapabilities`] of the Redis server from its version. If the version can not be detected, the error is logged and the capabilities of an unknown version are returned, so servers that disable `INFO` are still supported.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// The [`ServerCapabilities`] of the server.
+    fn get_server_capabilities(&mut self) -> ServerCapabilities;
+}
+
+impl<C> ServerCommands for C
+where
+    C: Commands,
+{
+    fn get_server_version(&mut self) -> RedsumerResult<ServerVersion> {
+        get_server_version(self)
+    }
+
+    fn get_server_capabilities(&mut self) -> ServerCapabilities {
+        match get_server_version(self) {
+            Ok(version) => ServerCapabilities::new(Some(version)),
+            Err(e) => {
+                warn!("The server version could not be detected, every feature is assumed to be supported: {:?}", e);
+                ServerCapabilities::default()
+            }
+        }
+    }
+}
+
+/// A connection to the Redis server that [`Consumer`](crate::consumer::Consumer) and [`Producer`](crate::producer::Producer) can be built on.
+///
+/// It is implemented by every cloneable [`ConnectionLike`] that can be shared between threads, like [`redis::Client`], pooled connections, cluster clients or `MockRedisConnection` in tests. Clones must reach the same server, since a clone is taken for every command.
+pub trait StreamConnection: ConnectionLike + Clone + Send + Sync + 'static {}
+
+impl<C> StreamConnection for C where C: ConnectionLike + Clone + Send + Sync + 'static {}
+
 #[cfg(test)]
 mod test_connection {
-    use redis::Client;
-    use redis_test::MockRedisConnection;
+    use redis::{Client, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
 
@@ -71,7 +323,100 @@ mod test_connection {
         let ping_result: RedsumerResult<String> = client.ping();
 
         // Verify the connection to the server:
-        assert!(ping_result.is_err());
-        assert_eq!(ping_result.unwrap_err().to_string(), "The connection to the Redis server could not be verified. Please verify the client configuration or server availability- ClientError");
+        let error: RedsumerError = ping_result.unwrap_err();
+        assert!(error.is_connection_error());
+        assert_eq!(error.to_string(), "connection error: The connection to the Redis server could not be verified. Please verify the client configuration or server availability- ClientError");
+    }
+
+    #[test]
+    fn test_server_version_parse() {
+        // Verify the result:
+        assert_eq!(
+            "7.2.4".parse::<ServerVersion>().unwrap(),
+            ServerVersion::new(7, 2, 4)
+        );
+        assert_eq!(
+            "6".parse::<ServerVersion>().unwrap(),
+            ServerVersion::new(6, 0, 0)
+        );
+        assert!("6.x".parse::<ServerVersion>().is_err());
+        assert!(ServerVersion::new(6, 0, 9).lt(&ServerVersion::new(6, 2, 0)));
+    }
+
+    #[test]
+    fn test_server_capabilities() {
+        // Define the capabilities of Redis 7.0 and of an unknown version:
+        let capabilities: ServerCapabilities =
+            ServerCapabilities::new(Some(ServerVersion::new(7, 0, 15)));
+        let unknown: ServerCapabilities = ServerCapabilities::default();
+
+        // Verify the result:
+        assert!(capabilities.supports(ServerFeature::AutoClaim));
+        assert!(capabilities.supports(ServerFeature::GroupLag));
+        assert!(!capabilities.supports(ServerFeature::AckDel));
+        assert!(capabilities.require(ServerFeature::TrimMinId).is_ok());
+        assert_eq!(
+            capabilities.require(ServerFeature::AckDel).unwrap_err().to_string(),
+            "Unsupported server feature - ClientError: XACKDEL requires Redis 8.2.0 or later, but the server version is 7.0.15"
+        );
+        assert!(unknown.supports(ServerFeature::AckDel));
+        assert!(unknown.require(ServerFeature::AckDel).is_ok());
+    }
+
+    #[test]
+    fn test_get_server_version_ok() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Ok(Value::BulkString(
+                    b"# Server\r\nredis_version:6.0.9\r\nredis_mode:standalone\r\n".to_vec(),
+                )),
+            )]);
+
+        // Get the server version:
+        let result: RedsumerResult<ServerVersion> = conn.get_server_version();
+
+        // Verify the result:
+        assert_eq!(result.unwrap().to_string(), "6.0.9");
+    }
+
+    #[test]
+    fn test_get_server_version_missing_field() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Ok(Value::BulkString(b"# Server\r\n".to_vec())),
+            )]);
+
+        // Get the server version:
+        let result: RedsumerResult<ServerVersion> = conn.get_server_version();
+
+        // Verify the result:
+        assert!(matches!(
+            result.unwrap_err(),
+            RedsumerError::DeserializationError(_)
+        ));
+    }
+
+    #[test]
+    fn test_get_server_capabilities_unknown() {
+        // Create a mock connection that does not reply to INFO:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "unknown command",
+                ))),
+            )]);
+
+        // Get the server capabilities:
+        let capabilities: ServerCapabilities = conn.get_server_capabilities();
+
+        // Verify the result:
+        assert_eq!(capabilities.get_version(), None);
+        assert!(capabilities.supports(ServerFeature::AutoClaim));
     }
 }
@@ -0,0 +1,375 @@
+use std::time::Duration;
+
+use redis::{cmd, Commands, ToRedisArgs};
+use tracing::{debug, error};
+
+#[allow(unused_imports)]
+use crate::core::{
+    result::{RedsumerError, RedsumerResult},
+    scripting::{ScriptCommands, RELEASE_LEASE_SCRIPT, RENEW_LEASE_SCRIPT},
+};
+
+/// Remove the expired members of a membership set, register or renew a member until *expires_at* and get the live members.
+fn renew_membership<C, K, M>(
+    conn: &mut C,
+    key: K,
+    member: M,
+    now: i64,
+    expires_at: i64,
+) -> RedsumerResult<Vec<String>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    M: ToRedisArgs,
+{
+    conn.zrembyscore::<_, _, _, usize>(&key, "-inf", now)
+        .and_then(|expired| {
+            debug!("Total expired members removed: {expired}");
+            conn.zadd::<_, _, _, usize>(&key, member, expires_at)
+        })
+        .and_then(|_| conn.zrange::<_, Vec<String>>(&key, 0, -1))
+        .map_err(|e| {
+            error!("Error renewing membership: {:?}", e);
+            e.into()
+        })
+}
+
+/// Remove a member from a membership set.
+fn leave_membership<C, K, M>(conn: &mut C, key: K, member: M) -> RedsumerResult<bool>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    M: ToRedisArgs,
+{
+    match conn.zrem::<_, _, bool>(key, member) {
+        Ok(removed) => {
+            debug!("The member was removed: {removed}");
+            Ok(removed)
+        }
+        Err(e) => {
+            error!("Error removing member: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Acquire a lease for *owner*, or renew it if it is already owned by *owner*. The owner is checked and the lease renewed atomically with a script.
+fn acquire_lease<C, K>(conn: &mut C, key: K, owner: &str, ttl: Duration) -> RedsumerResult<bool>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    let ttl: u64 = ttl.as_millis() as u64;
+
+    let acquired: Option<String> = cmd("SET")
+        .arg(&key)
+        .arg(owner)
+        .arg("NX")
+        .arg("PX")
+        .arg(ttl)
+        .query(conn)?;
+
+    if acquired.is_some() {
+        debug!("The lease was acquired");
+        return Ok(true);
+    }
+
+    match conn.run_script::<_, _, bool>(&RENEW_LEASE_SCRIPT, &[&key], &[owner, &ttl.to_string()])? {
+        true => {
+            debug!("The lease was renewed");
+            Ok(true)
+        }
+        false => {
+            debug!("The lease is owned by another member");
+            Ok(false)
+        }
+    }
+}
+
+/// Release a lease if it is owned by *owner*, checking the owner and deleting the lease atomically with a script.
+fn release_lease<C, K>(conn: &mut C, key: K, owner: &str) -> RedsumerResult<bool>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    match conn.run_script::<_, _, bool>(&RELEASE_LEASE_SCRIPT, &[&key], &[owner])? {
+        true => {
+            debug!("The lease was released");
+            Ok(true)
+        }
+        false => {
+            debug!("The lease is not owned by the member");
+            Ok(false)
+        }
+    }
+}
+
+/// A trait that bundles methods to coordinate members through membership sets and leases stored in Redis.
+pub trait CoordinationCommands {
+    /// Remove the expired members of a membership set, register or renew a member and get the live members.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the membership set, which must implement the `ToRedisArgs` trait.
+    /// - **member**: The member to register, which must implement the `ToRedisArgs` trait.
+    /// - **now**: The current Unix time in milliseconds. Members that expired before it are removed.
+    /// - **expires_at**: The Unix time in milliseconds when the membership of *member* expires.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the live members. If an error occurs, the function will return an error result.
+    fn renew_membership<K, M>(
+        &mut self,
+        key: K,
+        member: M,
+        now: i64,
+        expires_at: i64,
+    ) -> RedsumerResult<Vec<String>>
+    where
+        K: ToRedisArgs,
+        M: ToRedisArgs;
+
+    /// Remove a member from a membership set.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the membership set, which must implement the `ToRedisArgs` trait.
+    /// - **member**: The member to remove, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a boolean value indicating if the member was removed. If an error occurs, the function will return an error result.
+    fn leave_membership<K, M>(&mut self, key: K, member: M) -> RedsumerResult<bool>
+    where
+        K: ToRedisArgs,
+        M: ToRedisArgs;
+
+    /// Acquire a lease with a time to live, or renew it if it is already owned by *owner*.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the lease, which must implement the `ToRedisArgs` trait.
+    /// - **owner**: The owner of the lease.
+    /// - **ttl**: The time to live of the lease.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a boolean value indicating if *owner* holds the lease. If an error occurs, the function will return an error result.
+    fn acquire_lease<K>(&mut self, key: K, owner: &str, ttl: Duration) -> RedsumerResult<bool>
+    where
+        K: ToRedisArgs;
+
+    /// Release a lease if it is owned by *owner*.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the lease, which must implement the `ToRedisArgs` trait.
+    /// - **owner**: The owner of the lease.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a boolean value indicating if the lease was released. If an error occurs, the function will return an error result.
+    fn release_lease<K>(&mut self, key: K, owner: &str) -> RedsumerResult<bool>
+    where
+        K: ToRedisArgs;
+}
+
+impl<C> CoordinationCommands for C
+where
+    C: Commands,
+{
+    fn renew_membership<K, M>(
+        &mut self,
+        key: K,
+        member: M,
+        now: i64,
+        expires_at: i64,
+    ) -> RedsumerResult<Vec<String>>
+    where
+        K: ToRedisArgs,
+        M: ToRedisArgs,
+    {
+        renew_membership(self, key, member, now, expires_at)
+    }
+
+    fn leave_membership<K, M>(&mut self, key: K, member: M) -> RedsumerResult<bool>
+    where
+        K: ToRedisArgs,
+        M: ToRedisArgs,
+    {
+        leave_membership(self, key, member)
+    }
+
+    fn acquire_lease<K>(&mut self, key: K, owner: &str, ttl: Duration) -> RedsumerResult<bool>
+    where
+        K: ToRedisArgs,
+    {
+        acquire_lease(self, key, owner, ttl)
+    }
+
+    fn release_lease<K>(&mut self, key: K, owner: &str) -> RedsumerResult<bool>
+    where
+        K: ToRedisArgs,
+    {
+        release_lease(self, key, owner)
+    }
+}
+
+#[cfg(test)]
+mod test_renew_membership {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_renew_membership_ok() {
+        // Define the key and member:
+        let key: &str = "members";
+        let member: &str = "instance-1";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, i64>(
+                cmd("ZREMRANGEBYSCORE").arg(key).arg("-inf").arg(1000),
+                Ok(1),
+            ),
+            MockCmd::new::<_, i64>(cmd("ZADD").arg(key).arg(31000).arg(member), Ok(1)),
+            MockCmd::new::<_, Value>(
+                cmd("ZRANGE").arg(key).arg(0).arg(-1),
+                Ok(Value::Array(vec![
+                    Value::BulkString(b"instance-2".to_vec()),
+                    Value::BulkString(member.as_bytes().to_vec()),
+                ])),
+            ),
+        ]);
+
+        // Renew the membership:
+        let result: RedsumerResult<Vec<String>> = conn.renew_membership(key, member, 1000, 31000);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), vec!["instance-2", "instance-1"]);
+    }
+
+    #[test]
+    fn test_leave_membership_ok() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("ZREM").arg("members").arg("instance-1"),
+            Ok(1),
+        )]);
+
+        // Leave the membership:
+        let result: RedsumerResult<bool> = conn.leave_membership("members", "instance-1");
+
+        // Verify the result:
+        assert!(result.unwrap());
+    }
+}
+
+#[cfg(test)]
+mod test_leases {
+    use redis::{cmd, Cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build the `EVALSHA` command to renew the lease of *owner*.
+    fn renew(owner: &str) -> Cmd {
+        cmd("EVALSHA")
+            .arg(RENEW_LEASE_SCRIPT.get_hash())
+            .arg(1)
+            .arg("lease")
+            .arg(owner)
+            .arg("30000")
+            .to_owned()
+    }
+
+    /// Build the `EVALSHA` command to release the lease of *owner*.
+    fn release(owner: &str) -> Cmd {
+        cmd("EVALSHA")
+            .arg(RELEASE_LEASE_SCRIPT.get_hash())
+            .arg(1)
+            .arg("lease")
+            .arg(owner)
+            .to_owned()
+    }
+
+    #[test]
+    fn test_acquire_free_lease() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("SET")
+                    .arg("lease")
+                    .arg("instance-1")
+                    .arg("NX")
+                    .arg("PX")
+                    .arg(30000),
+                Ok("OK"),
+            )]);
+
+        // Acquire the lease:
+        let result: RedsumerResult<bool> =
+            conn.acquire_lease("lease", "instance-1", Duration::from_secs(30));
+
+        // Verify the result:
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_renew_owned_lease() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("SET")
+                    .arg("lease")
+                    .arg("instance-1")
+                    .arg("NX")
+                    .arg("PX")
+                    .arg(30000),
+                Ok(Value::Nil),
+            ),
+            MockCmd::new::<_, i64>(renew("instance-1"), Ok(1)),
+        ]);
+
+        // Acquire the lease:
+        let result: RedsumerResult<bool> =
+            conn.acquire_lease("lease", "instance-1", Duration::from_secs(30));
+
+        // Verify the result:
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_acquire_lease_owned_by_another_member() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("SET")
+                    .arg("lease")
+                    .arg("instance-1")
+                    .arg("NX")
+                    .arg("PX")
+                    .arg(30000),
+                Ok(Value::Nil),
+            ),
+            MockCmd::new::<_, i64>(renew("instance-1"), Ok(0)),
+        ]);
+
+        // Acquire the lease:
+        let result: RedsumerResult<bool> =
+            conn.acquire_lease("lease", "instance-1", Duration::from_secs(30));
+
+        // Verify the result:
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn test_release_lease() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, i64>(release("instance-1"), Ok(1)),
+            MockCmd::new::<_, i64>(release("instance-1"), Ok(0)),
+        ]);
+
+        // Release the lease:
+        assert!(conn.release_lease("lease", "instance-1").unwrap());
+
+        // Verify a lease owned by another member is not released:
+        assert!(!conn.release_lease("lease", "instance-1").unwrap());
+    }
+}
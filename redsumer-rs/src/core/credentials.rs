@@ -0,0 +1,402 @@
+use std::{
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use redis::{Client, Connection, ConnectionLike, ErrorKind, RedisResult, Value};
+#[allow(unused_imports)]
+use tracing::{debug, error, warn};
+
+#[allow(unused_imports)]
+use super::result::{RedsumerError, RedsumerResult};
+use super::{
+    blocking::run_blocking,
+    client::{connect, ClientArgs, ClientCredentials, RedisClientBuilder},
+};
+
+/// A boxed future returned by a [`CredentialsProvider`].
+pub type CredentialsFuture<'a> =
+    Pin<Box<dyn Future<Output = RedsumerResult<ClientCredentials>> + Send + 'a>>;
+
+/// A provider of credentials that expire periodically, like Elasticache IAM tokens or passwords issued by Vault.
+///
+/// It is called by a [`RotatingClient`] when it is created, when it is refreshed, and when a connection is rejected because the credentials expired.
+pub trait CredentialsProvider: Debug + Send + Sync {
+    /// Fetch the current credentials.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`CredentialsFuture`] resolved with the current [`ClientCredentials`]. Otherwise, a [`RedsumerError`] is returned.
+    fn fetch(&self) -> CredentialsFuture<'_>;
+}
+
+/// A Redis client whose credentials are fetched from a [`CredentialsProvider`].
+///
+/// Every command opens a new connection with the latest credentials, named with the [client name](ClientArgs::with_client_name) and with the [socket options](ClientArgs::with_socket_options) of its arguments. The provider is never awaited in the thread of a command: if the server rejects a connection with an authentication error, the credentials are marked as expired and the command fails. With the `tokio` feature inside a Tokio runtime, the credentials are then refreshed in a task of the runtime, so the next commands use them. Otherwise, or to retry the command right away, run it with [`run`](RotatingClient::run), which refreshes expired credentials before running the command and retries it once after an authentication error.
+///
+/// It can be given to [`Consumer::from_client`](crate::consumer::Consumer::from_client) and [`Producer::from_client`](crate::producer::Producer::from_client) like any other [`StreamConnection`](crate::client::StreamConnection). Clones share the credentials.
+#[derive(Debug, Clone)]
+pub struct RotatingClient {
+    /// Arguments to build the client. Their credentials are replaced by the fetched ones.
+    args: ClientArgs,
+
+    /// Provider of the credentials.
+    provider: Arc<dyn CredentialsProvider>,
+
+    /// Client built with the latest credentials.
+    client: Arc<RwLock<Client>>,
+
+    /// If `true`, a connection was rejected with the latest credentials, so they must be refreshed.
+    expired: Arc<AtomicBool>,
+}
+
+impl RotatingClient {
+    /// Get **args**.
+    pub fn get_args(&self) -> &ClientArgs {
+        &self.args
+    }
+
+    /// Build a new [`RotatingClient`] instance, fetching its first credentials from *provider*.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build the client. Their credentials are ignored.
+    /// - **provider**: The provider of the credentials.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the new [`RotatingClient`] instance. If the credentials can not be fetched or the connection string is invalid, a [`RedsumerError`] is returned.
+    pub async fn new(
+        args: &ClientArgs,
+        provider: Arc<dyn CredentialsProvider>,
+    ) -> RedsumerResult<RotatingClient> {
+        let client: Client = Self::build_client(args, provider.fetch().await?)?;
+
+        Ok(RotatingClient {
+            args: args.to_owned(),
+            provider,
+            client: Arc::new(RwLock::new(client)),
+            expired: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Verify if a connection was rejected with the latest credentials, so they must be refreshed.
+    pub fn is_expired(&self) -> bool {
+        self.expired.load(Ordering::SeqCst)
+    }
+
+    /// Fetch the credentials again, so the next connections use them.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` if the credentials were refreshed. Otherwise, a [`RedsumerError`] is returned and the previous credentials are kept.
+    pub async fn refresh(&self) -> RedsumerResult<()> {
+        let client: Client = Self::build_client(&self.args, self.provider.fetch().await?)?;
+        *self.client.write().unwrap_or_else(|e| e.into_inner()) = client;
+        self.expired.store(false, Ordering::SeqCst);
+
+        debug!("Redis credentials were refreshed");
+        Ok(())
+    }
+
+    /// Fetch the credentials again if a connection was rejected with the latest ones, so the next connections use the new ones.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `true` if the credentials were expired and they were refreshed, or `false` if they were not expired. Otherwise, a [`RedsumerError`] is returned and the previous credentials are kept.
+    pub async fn reconnect(&self) -> RedsumerResult<bool> {
+        if !self.is_expired() {
+            return Ok(false);
+        }
+
+        self.refresh().await?;
+        Ok(true)
+    }
+
+    /// Run a synchronous *command* with the client in a blocking thread, refreshing the credentials before it if they are expired. If the command fails with an authentication error, the credentials are refreshed and the command is run once more.
+    ///
+    /// # Arguments:
+    /// - **command**: The synchronous command to run with the client.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the result of the command. If the credentials can not be refreshed or the command fails, a [`RedsumerError`] is returned.
+    pub async fn run<T, F>(&self, command: F) -> RedsumerResult<T>
+    where
+        F: FnOnce(&mut RotatingClient) -> RedsumerResult<T> + Clone + Send + 'static,
+        T: Send + 'static,
+    {
+        self.reconnect().await?;
+
+        let mut client: RotatingClient = self.to_owned();
+        let first: F = command.to_owned();
+        match run_blocking(move || first(&mut client)).await {
+            Err(e) if e.kind().eq(&ErrorKind::AuthenticationFailed) => {
+                self.reconnect().await?;
+
+                let mut client: RotatingClient = self.to_owned();
+                run_blocking(move || command(&mut client)).await
+            }
+            result => result,
+        }
+    }
+
+    /// Mark the credentials as expired after a connection was rejected, scheduling their refresh unless a refresh is already pending.
+    fn expire(&self) {
+        if !self.expired.swap(true, Ordering::SeqCst) {
+            self.spawn_refresh();
+        }
+    }
+
+    /// Refresh the expired credentials in a task of the current Tokio runtime, if there is one.
+    #[cfg(feature = "tokio")]
+    fn spawn_refresh(&self) {
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let client: RotatingClient = self.to_owned();
+            handle.spawn(async move {
+                if let Err(e) = client.reconnect().await {
+                    error!("Error refreshing Redis credentials: {:?}", e);
+                }
+            });
+        }
+    }
+
+    /// Without the `tokio` feature, expired credentials are refreshed by [`reconnect`](RotatingClient::reconnect) or [`run`](RotatingClient::run).
+    #[cfg(not(feature = "tokio"))]
+    fn spawn_refresh(&self) {}
+
+    /// Build a client from *args* with the given *credentials*.
+    fn build_client(args: &ClientArgs, credentials: ClientCredentials) -> RedsumerResult<Client> {
+        ClientArgs::new(
+            Some(credentials),
+            args.get_host(),
+            args.get_port(),
+            args.get_db(),
+            args.get_protocol(),
+        )
+        .build()
+    }
+
+    /// Get the client built with the latest credentials.
+    fn get_client(&self) -> Client {
+        self.client
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .to_owned()
+    }
+
+    /// Run a *command* on a new connection. If the connection is rejected with an authentication error, the credentials are marked as expired and the error is returned.
+    fn with_credentials<T, F>(&self, command: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Connection) -> RedisResult<T>,
+    {
+        let mut connection: Connection = match connect(
+            &self.get_client(),
+            self.args.get_client_name(),
+            self.args.get_socket_options(),
+        ) {
+            Err(e) if e.kind().eq(&ErrorKind::AuthenticationFailed) => {
+                warn!("Redis authentication failed, the credentials are expired: {e}");
+                self.expire();
+                return Err(e);
+            }
+            result => result?,
+        };
+
+        command(&mut connection)
+    }
+}
+
+impl ConnectionLike for RotatingClient {
+    fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
+        self.with_credentials(|connection| connection.req_packed_command(cmd))
+    }
+
+    fn req_packed_commands(
+        &mut self,
+        cmd: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> RedisResult<Vec<Value>> {
+        self.with_credentials(|connection| connection.req_packed_commands(cmd, offset, count))
+    }
+
+    fn get_db(&self) -> i64 {
+        self.args.get_db()
+    }
+
+    fn check_connection(&mut self) -> bool {
+        self.with_credentials(|connection| Ok(connection.check_connection()))
+            .unwrap_or(false)
+    }
+
+    fn is_open(&self) -> bool {
+        self.with_credentials(|connection| Ok(connection.is_open()))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod test_rotating_client {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::core::client::CommunicationProtocol;
+
+    /// A provider that issues a new token on every fetch, or fails.
+    #[derive(Debug, Default)]
+    struct TokenProvider {
+        fetches: AtomicUsize,
+        fail: bool,
+    }
+
+    impl CredentialsProvider for TokenProvider {
+        fn fetch(&self) -> CredentialsFuture<'_> {
+            Box::pin(async move {
+                if self.fail {
+                    return Err(RedsumerError::from((
+                        ErrorKind::AuthenticationFailed,
+                        "Token not issued",
+                    )));
+                }
+
+                let fetches: usize = self.fetches.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok(ClientCredentials::token(
+                    Some("app"),
+                    &format!("token-{fetches}"),
+                ))
+            })
+        }
+    }
+
+    /// Build the args of an unreachable server.
+    fn args() -> ClientArgs {
+        ClientArgs::new(
+            Some(ClientCredentials::new("ignored", "ignored")),
+            "127.0.0.1",
+            1,
+            2,
+            CommunicationProtocol::RESP2,
+        )
+    }
+
+    /// Get the password of the client built with the latest credentials.
+    fn password(client: &RotatingClient) -> Option<String> {
+        client
+            .get_client()
+            .get_connection_info()
+            .redis
+            .password
+            .to_owned()
+    }
+
+    #[tokio::test]
+    async fn test_rotating_client_new_and_refresh() {
+        // Define the provider:
+        let provider: Arc<TokenProvider> = Arc::new(TokenProvider::default());
+
+        // Create the client and refresh its credentials:
+        let client: RotatingClient = RotatingClient::new(&args(), provider.to_owned())
+            .await
+            .unwrap();
+        let first: Option<String> = password(&client);
+        client.refresh().await.unwrap();
+
+        // Verify the result:
+        assert_eq!(first.as_deref(), Some("token-1"));
+        assert_eq!(password(&client).as_deref(), Some("token-2"));
+        assert_eq!(
+            client
+                .get_client()
+                .get_connection_info()
+                .redis
+                .username
+                .as_deref(),
+            Some("app")
+        );
+        assert_eq!(ConnectionLike::get_db(&client), 2);
+        assert_eq!(provider.fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rotating_client_provider_error() {
+        // Define a failing provider:
+        let provider: Arc<TokenProvider> = Arc::new(TokenProvider {
+            fail: true,
+            ..Default::default()
+        });
+
+        // Create the client:
+        let result: RedsumerResult<RotatingClient> = RotatingClient::new(&args(), provider).await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::AuthenticationFailed);
+    }
+
+    #[tokio::test]
+    async fn test_rotating_client_connection_error_keeps_credentials() {
+        // Define the provider:
+        let provider: Arc<TokenProvider> = Arc::new(TokenProvider::default());
+
+        // Create the client and run a command on an unreachable server:
+        let mut client: RotatingClient = RotatingClient::new(&args(), provider.to_owned())
+            .await
+            .unwrap();
+        let result: RedisResult<Value> = client.req_command(&redis::cmd("PING"));
+
+        // Verify the result:
+        assert!(result.is_err());
+        assert!(!client.check_connection());
+        assert!(!client.is_expired());
+        assert_eq!(provider.fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rotating_client_reconnect() {
+        // Define the provider:
+        let provider: Arc<TokenProvider> = Arc::new(TokenProvider::default());
+
+        // Create the client and reconnect it with valid credentials:
+        let client: RotatingClient = RotatingClient::new(&args(), provider.to_owned())
+            .await
+            .unwrap();
+        assert!(!client.reconnect().await.unwrap());
+
+        // Expire the credentials and reconnect it:
+        client.expired.store(true, Ordering::SeqCst);
+        assert!(client.reconnect().await.unwrap());
+
+        // Verify the result:
+        assert!(!client.is_expired());
+        assert_eq!(password(&client).as_deref(), Some("token-2"));
+        assert_eq!(provider.fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rotating_client_run_refreshes_expired_credentials() {
+        // Define the provider:
+        let provider: Arc<TokenProvider> = Arc::new(TokenProvider::default());
+
+        // Create the client, expire its credentials and run a command on an unreachable server:
+        let client: RotatingClient = RotatingClient::new(&args(), provider.to_owned())
+            .await
+            .unwrap();
+        client.expired.store(true, Ordering::SeqCst);
+        let result: RedsumerResult<Value> = client
+            .run(|client| Ok(client.req_command(&redis::cmd("PING"))?))
+            .await;
+
+        // Verify the credentials were refreshed before the command:
+        assert!(result.unwrap_err().is_connection_error());
+        assert!(!client.is_expired());
+        assert_eq!(provider.fetches.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,116 @@
+use std::str::FromStr;
+
+use redis::ErrorKind;
+use tracing::error;
+
+#[allow(unused_imports)]
+use super::result::{RedsumerError, RedsumerResult};
+
+/// A function to look up the value of a variable by its name.
+pub trait VarLookup: Fn(&str) -> Option<String> {}
+
+impl<F> VarLookup for F where F: Fn(&str) -> Option<String> {}
+
+/// Look up a variable in the process environment.
+pub fn env_lookup(key: &str) -> Option<String> {
+    std::env::var(key).ok()
+}
+
+/// Build the name of a variable with the given prefix. If the prefix is empty, the name is returned as it is.
+pub fn prefixed(prefix: &str, name: &str) -> String {
+    match prefix.is_empty() {
+        true => name.to_owned(),
+        false => format!("{}_{}", prefix.trim_end_matches('_'), name),
+    }
+}
+
+/// Get an optional variable and parse it into `T`.
+///
+/// # Arguments:
+/// - **lookup**: A function to look up the variable value.
+/// - **key**: The variable name.
+///
+/// # Returns:
+/// A [`RedsumerResult`] with `None` if the variable is not set, otherwise `Some` with the parsed value. If the value can not be parsed, a [`RedsumerError`] is returned.
+pub fn get_var<T, L>(lookup: &L, key: &str) -> RedsumerResult<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+    L: VarLookup,
+{
+    match lookup(key) {
+        Some(value) => value.trim().parse::<T>().map(Some).map_err(|e| {
+            error!("Error parsing environment variable {key}: {e}");
+            RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid environment variable",
+                format!("{key}: {e}"),
+            ))
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Get a mandatory variable and parse it into `T`.
+///
+/// # Arguments:
+/// - **lookup**: A function to look up the variable value.
+/// - **key**: The variable name.
+///
+/// # Returns:
+/// A [`RedsumerResult`] with the parsed value. If the variable is not set or it can not be parsed, a [`RedsumerError`] is returned.
+pub fn require_var<T, L>(lookup: &L, key: &str) -> RedsumerResult<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+    L: VarLookup,
+{
+    get_var(lookup, key)?.ok_or_else(|| {
+        error!("Environment variable {key} is not set");
+        RedsumerError::from((
+            ErrorKind::InvalidClientConfig,
+            "Missing environment variable",
+            key.to_owned(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test_env {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_prefixed() {
+        // Verify the variable names:
+        assert_eq!(prefixed("", "STREAM"), "STREAM");
+        assert_eq!(prefixed("APP", "STREAM"), "APP_STREAM");
+        assert_eq!(prefixed("APP_", "STREAM"), "APP_STREAM");
+    }
+
+    #[test]
+    fn test_get_var() {
+        // Define the variables:
+        let vars: HashMap<&str, &str> = HashMap::from([("PORT", " 6379 "), ("BAD", "abc")]);
+        let lookup = |k: &str| vars.get(k).map(|v| v.to_string());
+
+        // Verify the result:
+        assert_eq!(get_var::<u16, _>(&lookup, "PORT").unwrap(), Some(6379));
+        assert_eq!(get_var::<u16, _>(&lookup, "MISSING").unwrap(), None);
+        assert!(get_var::<u16, _>(&lookup, "BAD").is_err());
+    }
+
+    #[test]
+    fn test_require_var() {
+        // Define the variables:
+        let vars: HashMap<&str, &str> = HashMap::from([("HOST", "redis")]);
+        let lookup = |k: &str| vars.get(k).map(|v| v.to_string());
+
+        // Verify the result:
+        assert_eq!(require_var::<String, _>(&lookup, "HOST").unwrap(), "redis");
+
+        let error: RedsumerError = require_var::<String, _>(&lookup, "MISSING").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidClientConfig);
+    }
+}
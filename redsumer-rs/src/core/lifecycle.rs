@@ -0,0 +1,300 @@
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::{Arc, Mutex},
+};
+
+use redis::{ConnectionLike, RedisError, RedisResult, Value};
+use tracing::{info, warn};
+
+/// An event of the connectivity of a [`MonitoredClient`] to the Redis server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The first command reached the server.
+    Connected,
+
+    /// A command could not reach the server, or its connection was dropped.
+    Disconnected {
+        /// Description of the connection error.
+        error: String,
+    },
+
+    /// A command is trying to reach the server again after a disconnection.
+    ReconnectAttempt {
+        /// Number of the attempt since the disconnection, starting at 1.
+        attempt: usize,
+    },
+
+    /// A command reached the server again after a disconnection.
+    ReconnectSuccess {
+        /// Number of attempts it took to reach the server again.
+        attempts: usize,
+    },
+}
+
+/// A callback fired for every [`ConnectionEvent`].
+pub type ConnectionEventCallback = Arc<dyn Fn(&ConnectionEvent) + Send + Sync>;
+
+/// Connectivity state of a [`MonitoredClient`].
+#[derive(Debug, Default)]
+struct ConnectionState {
+    /// Whether the last command reached the server. It is `None` before the first command.
+    connected: Option<bool>,
+
+    /// Reconnect attempts since the last disconnection.
+    attempts: usize,
+}
+
+/// Verify if *error* means the server could not be reached or the connection was lost.
+fn is_connection_error(error: &RedisError) -> bool {
+    error.is_io_error()
+        || error.is_connection_refusal()
+        || error.is_connection_dropped()
+        || error.is_timeout()
+}
+
+/// A connection that tracks the connectivity to the Redis server from the results of its commands and fires [`ConnectionEvent`]s when it changes, so applications can log or alert on flapping connections and gate their readiness on connectivity.
+///
+/// It wraps any other connection, like a [`Client`](redis::Client), a [`FailoverClient`](crate::client::FailoverClient) or a [`RotatingClient`](crate::client::RotatingClient), and it can be given to [`Consumer::from_client`](crate::consumer::Consumer::from_client) and [`Producer::from_client`](crate::producer::Producer::from_client) like any other [`StreamConnection`](crate::client::StreamConnection). Clones share the connectivity state and the callbacks.
+///
+/// Callbacks are called in the thread of the command, so they should be cheap and must not block.
+#[derive(Clone)]
+pub struct MonitoredClient<C> {
+    /// The wrapped connection.
+    inner: C,
+
+    /// Connectivity state, shared by clones.
+    state: Arc<Mutex<ConnectionState>>,
+
+    /// Callbacks fired for every event.
+    callbacks: Vec<ConnectionEventCallback>,
+}
+
+impl<C> MonitoredClient<C> {
+    /// Get the wrapped connection.
+    pub fn get_inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Build a new [`MonitoredClient`] instance without callbacks.
+    ///
+    /// # Arguments:
+    /// - **inner**: The connection to monitor.
+    ///
+    /// # Returns:
+    /// A new instance of [`MonitoredClient`].
+    pub fn new(inner: C) -> Self {
+        MonitoredClient {
+            inner,
+            state: Arc::default(),
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Add a callback fired for every [`ConnectionEvent`].
+    pub fn on_event<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ConnectionEvent) + Send + Sync + 'static,
+    {
+        self.callbacks.push(Arc::new(callback));
+        self
+    }
+
+    /// Verify if the last command reached the server. It is `false` before the first command.
+    pub fn is_connected(&self) -> bool {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .connected
+            .unwrap_or(false)
+    }
+
+    /// Fire an *event* to every callback.
+    fn fire(&self, event: ConnectionEvent) {
+        match &event {
+            ConnectionEvent::Disconnected { error } => warn!("Disconnected from Redis: {error}"),
+            ConnectionEvent::ReconnectAttempt { attempt } => {
+                info!("Reconnecting to Redis, attempt {attempt}")
+            }
+            ConnectionEvent::ReconnectSuccess { attempts } => {
+                info!("Reconnected to Redis after {attempts} attempts")
+            }
+            ConnectionEvent::Connected => info!("Connected to Redis"),
+        }
+
+        for callback in &self.callbacks {
+            callback(&event);
+        }
+    }
+
+    /// Run a *command* on the wrapped connection, updating the connectivity state with its result.
+    fn observe<T, F>(&mut self, command: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut C) -> RedisResult<T>,
+    {
+        let attempt: Option<usize> = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            match state.connected {
+                Some(false) => {
+                    state.attempts += 1;
+                    Some(state.attempts)
+                }
+                _ => None,
+            }
+        };
+        if let Some(attempt) = attempt {
+            self.fire(ConnectionEvent::ReconnectAttempt { attempt });
+        }
+
+        let result: RedisResult<T> = command(&mut self.inner);
+
+        let event: Option<ConnectionEvent> = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            match (&result, state.connected) {
+                (Err(e), Some(true) | None) if is_connection_error(e) => {
+                    state.connected = Some(false);
+                    state.attempts = 0;
+                    Some(ConnectionEvent::Disconnected {
+                        error: e.to_string(),
+                    })
+                }
+                (Err(e), Some(false)) if is_connection_error(e) => None,
+                (_, None) => {
+                    state.connected = Some(true);
+                    Some(ConnectionEvent::Connected)
+                }
+                (_, Some(false)) => {
+                    state.connected = Some(true);
+                    Some(ConnectionEvent::ReconnectSuccess {
+                        attempts: std::mem::take(&mut state.attempts),
+                    })
+                }
+                (_, Some(true)) => None,
+            }
+        };
+        if let Some(event) = event {
+            self.fire(event);
+        }
+
+        result
+    }
+}
+
+impl<C> Debug for MonitoredClient<C>
+where
+    C: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("MonitoredClient")
+            .field("inner", &self.inner)
+            .field("connected", &self.is_connected())
+            .field("callbacks", &self.callbacks.len())
+            .finish()
+    }
+}
+
+impl<C> ConnectionLike for MonitoredClient<C>
+where
+    C: ConnectionLike,
+{
+    fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
+        self.observe(|inner| inner.req_packed_command(cmd))
+    }
+
+    fn req_packed_commands(
+        &mut self,
+        cmd: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> RedisResult<Vec<Value>> {
+        self.observe(|inner| inner.req_packed_commands(cmd, offset, count))
+    }
+
+    fn get_db(&self) -> i64 {
+        self.inner.get_db()
+    }
+
+    fn check_connection(&mut self) -> bool {
+        self.inner.check_connection()
+    }
+
+    fn is_open(&self) -> bool {
+        self.inner.is_open()
+    }
+}
+
+#[cfg(test)]
+mod test_monitored_client {
+    use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+
+    use redis::{cmd, ErrorKind};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `PING` mock command with the given result.
+    fn ping(result: RedisResult<&str>) -> MockCmd {
+        MockCmd::new(cmd("PING"), result)
+    }
+
+    /// Build a connection refused error.
+    fn refused() -> RedisError {
+        RedisError::from(IoError::from(IoErrorKind::ConnectionRefused))
+    }
+
+    #[test]
+    fn test_monitored_client_events() {
+        // Create a monitored mock connection that collects its events:
+        let events: Arc<Mutex<Vec<ConnectionEvent>>> = Arc::default();
+        let collected: Arc<Mutex<Vec<ConnectionEvent>>> = events.to_owned();
+        let mut client: MonitoredClient<MockRedisConnection> =
+            MonitoredClient::new(MockRedisConnection::new(vec![
+                ping(Ok("PONG")),
+                ping(Err(refused())),
+                ping(Err(refused())),
+                ping(Err(RedisError::from((ErrorKind::ResponseError, "Error")))),
+                ping(Ok("PONG")),
+            ]))
+            .on_event(move |event| collected.lock().unwrap().push(event.to_owned()));
+
+        // Run the commands:
+        let mut connected: Vec<bool> = Vec::new();
+        for _ in 0..5 {
+            let _ = client.req_command(&cmd("PING"));
+            connected.push(client.is_connected());
+        }
+
+        // Verify the result:
+        assert_eq!(connected, vec![true, false, false, true, true]);
+        assert_eq!(
+            events.lock().unwrap().as_slice(),
+            &[
+                ConnectionEvent::Connected,
+                ConnectionEvent::Disconnected {
+                    error: refused().to_string()
+                },
+                ConnectionEvent::ReconnectAttempt { attempt: 1 },
+                ConnectionEvent::ReconnectAttempt { attempt: 2 },
+                ConnectionEvent::ReconnectSuccess { attempts: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monitored_client_clones_share_state() {
+        // Create a monitored mock connection and a clone:
+        let mut client: MonitoredClient<MockRedisConnection> =
+            MonitoredClient::new(MockRedisConnection::new(vec![ping(Err(refused()))]));
+        let clone: MonitoredClient<MockRedisConnection> = client.to_owned();
+
+        // Run a command that can not reach the server:
+        let result: RedisResult<Value> = client.req_command(&cmd("PING"));
+
+        // Verify the result:
+        assert!(result.is_err());
+        assert!(!clone.is_connected());
+        assert_eq!(
+            format!("{:?}", MonitoredClient::new(0)),
+            "MonitoredClient { inner: 0, connected: false, callbacks: 0 }"
+        );
+    }
+}
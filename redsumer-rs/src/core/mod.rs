@@ -1,4 +1,12 @@
+pub mod blocking;
+pub mod checkpoint;
 pub mod client;
 pub mod connection;
+pub mod coordination;
+pub mod credentials;
+pub mod env;
+pub mod lifecycle;
 pub mod result;
+pub mod scripting;
 pub mod streams;
+pub mod value;
@@ -1,7 +1,246 @@
-use redis::RedisError;
+use redis::{ErrorKind, RedisError};
+use thiserror::Error;
 
-/// Error type for *redsumer* operations, it is an alias for [`RedisError`].
-pub type RedsumerError = RedisError;
+/// Error type for *redsumer* operations.
+///
+/// Errors returned by the Redis server or the Redis client are classified into the variants below, so they can be handled without matching error messages. Any other error is kept as it is in [`RedsumerError::Backend`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RedsumerError {
+    /// The connection to the Redis server could not be established, was dropped or timed out.
+    #[error("connection error: {0}")]
+    ConnectionError(#[source] RedisError),
+
+    /// The stream does not exist.
+    #[error("stream does not exist: {0}")]
+    StreamNotFound(String),
+
+    /// The consumers group already exists in the stream.
+    #[error("consumers group already exists: {0}")]
+    GroupAlreadyExists(#[source] RedisError),
+
+    /// Pending messages could not be claimed.
+    #[error("claim failed: {0}")]
+    ClaimFailed(#[source] RedisError),
+
+    /// A value could not be deserialized, like a message field or an ID.
+    #[error("deserialization error: {0}")]
+    DeserializationError(String),
+
+    /// A message was rejected by a validator before it was produced.
+    #[error("message validation failed: {0}")]
+    ValidationError(String),
+
+    /// Any other error returned by the Redis server or the Redis client.
+    #[error(transparent)]
+    Backend(RedisError),
+}
+
+/// Class of a [`RedsumerError`], to decide whether the failed operation can be retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// The error is transient, like a connection loss or a failover, so the operation can succeed if it is retried later.
+    Retryable,
+
+    /// The error is permanent, like an invalid configuration or an invalid message, so retrying the operation fails again.
+    Fatal,
+}
+
+impl RedsumerError {
+    /// Get the [`RedisError`] that caused the error, if any.
+    pub fn get_backend_error(&self) -> Option<&RedisError> {
+        match self {
+            RedsumerError::ConnectionError(e)
+            | RedsumerError::GroupAlreadyExists(e)
+            | RedsumerError::ClaimFailed(e)
+            | RedsumerError::Backend(e) => Some(e),
+            RedsumerError::StreamNotFound(_)
+            | RedsumerError::DeserializationError(_)
+            | RedsumerError::ValidationError(_) => None,
+        }
+    }
+
+    /// Get the [`ErrorKind`] of the error. It is the kind of the [`RedisError`] that caused the error, or the closest one for errors raised by *redsumer*.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            RedsumerError::StreamNotFound(_) => ErrorKind::ClientError,
+            RedsumerError::DeserializationError(_) => ErrorKind::TypeError,
+            RedsumerError::ValidationError(_) => ErrorKind::ClientError,
+            RedsumerError::ConnectionError(e)
+            | RedsumerError::GroupAlreadyExists(e)
+            | RedsumerError::ClaimFailed(e)
+            | RedsumerError::Backend(e) => e.kind(),
+        }
+    }
+
+    /// Get the error code returned by the Redis server, like `NOGROUP` or `BUSYGROUP`, if any.
+    pub fn code(&self) -> Option<&str> {
+        self.get_backend_error().and_then(RedisError::code)
+    }
+
+    /// Verify if the connection to the Redis server could not be established, was dropped or timed out.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, RedsumerError::ConnectionError(_))
+    }
+
+    /// Get the [`ErrorClass`] of the error.
+    ///
+    /// Connection errors, missing streams, failed claims and transient server states, like a cluster failover or a server loading its dataset, are [`ErrorClass::Retryable`]. Any other error is [`ErrorClass::Fatal`].
+    pub fn get_class(&self) -> ErrorClass {
+        match self {
+            RedsumerError::ConnectionError(_)
+            | RedsumerError::StreamNotFound(_)
+            | RedsumerError::ClaimFailed(_) => ErrorClass::Retryable,
+            RedsumerError::GroupAlreadyExists(_)
+            | RedsumerError::DeserializationError(_)
+            | RedsumerError::ValidationError(_) => ErrorClass::Fatal,
+            RedsumerError::Backend(e) => match e.kind() {
+                ErrorKind::BusyLoadingError
+                | ErrorKind::TryAgain
+                | ErrorKind::ClusterDown
+                | ErrorKind::MasterDown
+                | ErrorKind::ReadOnly
+                | ErrorKind::Moved
+                | ErrorKind::Ask
+                | ErrorKind::IoError
+                | ErrorKind::ClusterConnectionNotFound => ErrorClass::Retryable,
+                _ => ErrorClass::Fatal,
+            },
+        }
+    }
+
+    /// Verify if the failed operation can be retried. See [`get_class`](RedsumerError::get_class).
+    pub fn is_retryable(&self) -> bool {
+        self.get_class().eq(&ErrorClass::Retryable)
+    }
+}
+
+/// Classify a [`RedisError`] into a [`RedsumerError`].
+impl From<RedisError> for RedsumerError {
+    fn from(error: RedisError) -> Self {
+        if error.is_io_error()
+            || error.is_connection_refusal()
+            || error.is_connection_dropped()
+            || error.is_timeout()
+        {
+            return RedsumerError::ConnectionError(error);
+        }
+
+        match error.code() {
+            Some("BUSYGROUP") => RedsumerError::GroupAlreadyExists(error),
+            _ => RedsumerError::Backend(error),
+        }
+    }
+}
+
+/// Build a [`RedsumerError`] from an [`ErrorKind`] and a description, like a [`RedisError`].
+impl From<(ErrorKind, &'static str)> for RedsumerError {
+    fn from(error: (ErrorKind, &'static str)) -> Self {
+        RedsumerError::from(RedisError::from(error))
+    }
+}
+
+/// Build a [`RedsumerError`] from an [`ErrorKind`], a description and a detail, like a [`RedisError`].
+impl From<(ErrorKind, &'static str, String)> for RedsumerError {
+    fn from(error: (ErrorKind, &'static str, String)) -> Self {
+        RedsumerError::from(RedisError::from(error))
+    }
+}
+
+/// Convert an I/O error into a [`RedsumerError::ConnectionError`].
+impl From<std::io::Error> for RedsumerError {
+    fn from(error: std::io::Error) -> Self {
+        RedsumerError::from(RedisError::from(error))
+    }
+}
+
+/// Convert a [`RedsumerError`] into a [`RedisError`], to return it from Redis traits like [`FromRedisValue`](redis::FromRedisValue). Errors raised by *redsumer* keep their message.
+impl From<RedsumerError> for RedisError {
+    fn from(error: RedsumerError) -> Self {
+        match error {
+            RedsumerError::ConnectionError(e)
+            | RedsumerError::GroupAlreadyExists(e)
+            | RedsumerError::ClaimFailed(e)
+            | RedsumerError::Backend(e) => e,
+            e => RedisError::from((e.kind(), "Redsumer error", e.to_string())),
+        }
+    }
+}
 
 /// Result type for *redsumer* operations.
 pub type RedsumerResult<T> = Result<T, RedsumerError>;
+
+#[cfg(test)]
+mod test_redsumer_error {
+    use std::io;
+
+    use redis::{parse_redis_value, Value};
+
+    use super::*;
+
+    #[test]
+    fn test_redsumer_error_from_connection_error() {
+        // Define the error:
+        let error: RedsumerError =
+            RedsumerError::from(io::Error::new(io::ErrorKind::ConnectionRefused, "refused"));
+
+        // Verify the result:
+        assert!(error.is_connection_error());
+        assert_eq!(error.kind(), ErrorKind::IoError);
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_redsumer_error_from_busygroup() {
+        // Define the error:
+        let error: RedsumerError = RedsumerError::from(
+            parse_redis_value(b"-BUSYGROUP Consumer Group name already exists\r\n")
+                .and_then(Value::extract_error)
+                .unwrap_err(),
+        );
+
+        // Verify the result:
+        assert!(matches!(error, RedsumerError::GroupAlreadyExists(_)));
+        assert_eq!(error.code(), Some("BUSYGROUP"));
+        assert_eq!(error.get_class(), ErrorClass::Fatal);
+    }
+
+    #[test]
+    fn test_redsumer_error_from_response_error() {
+        // Define the error:
+        let error: RedsumerError = RedsumerError::from((ErrorKind::ResponseError, "XACK Error"));
+
+        // Verify the result:
+        assert!(matches!(error, RedsumerError::Backend(_)));
+        assert_eq!(error.kind(), ErrorKind::ResponseError);
+        assert!(!error.is_connection_error());
+    }
+
+    #[test]
+    fn test_redsumer_error_without_backend_error() {
+        // Define the errors:
+        let not_found: RedsumerError = RedsumerError::StreamNotFound("stream".to_string());
+        let invalid: RedsumerError = RedsumerError::DeserializationError("invalid".to_string());
+
+        // Verify the result:
+        assert!(not_found.get_backend_error().is_none());
+        assert_eq!(not_found.to_string(), "stream does not exist: stream");
+        assert_eq!(invalid.kind(), ErrorKind::TypeError);
+        assert_eq!(invalid.code(), None);
+    }
+
+    #[test]
+    fn test_redsumer_error_class() {
+        // Define the errors:
+        let loading: RedsumerError =
+            RedsumerError::from((ErrorKind::BusyLoadingError, "Loading dataset"));
+        let no_script: RedsumerError = RedsumerError::from((ErrorKind::NoScriptError, "NOSCRIPT"));
+
+        // Verify the result:
+        assert_eq!(loading.get_class(), ErrorClass::Retryable);
+        assert_eq!(no_script.get_class(), ErrorClass::Fatal);
+        assert!(RedsumerError::StreamNotFound("stream".to_string()).is_retryable());
+        assert!(!RedsumerError::DeserializationError("invalid".to_string()).is_retryable());
+        assert!(!RedsumerError::ValidationError("invalid".to_string()).is_retryable());
+    }
+}
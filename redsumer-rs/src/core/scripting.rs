@@ -0,0 +1,314 @@
+use std::sync::LazyLock;
+
+use redis::{cmd, Cmd, Commands, ErrorKind, FromRedisValue, Script, ToRedisArgs};
+use tracing::{debug, error, warn};
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+
+/// A Lua script with its source and its SHA1 digest, which is used to run it with `EVALSHA` without sending the source every time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LuaScript {
+    /// Source code of the script.
+    source: String,
+
+    /// SHA1 digest of the source code, in hexadecimal.
+    hash: String,
+}
+
+impl LuaScript {
+    /// Get **source**.
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get **hash**.
+    pub fn get_hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Build a new [`LuaScript`] instance, computing the SHA1 digest of its *source*.
+    ///
+    /// # Arguments:
+    /// - **source**: The Lua source code of the script.
+    ///
+    /// # Returns:
+    /// A new instance of [`LuaScript`].
+    pub fn new(source: &str) -> Self {
+        LuaScript {
+            source: source.to_owned(),
+            hash: Script::new(source).get_hash().to_owned(),
+        }
+    }
+}
+
+/// Release a lease if it is owned by an owner. `KEYS[1]` is the lease and `ARGV[1]` the owner.
+pub static RELEASE_LEASE_SCRIPT: LazyLock<LuaScript> = LazyLock::new(|| {
+    LuaScript::new(
+        r"if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0",
+    )
+});
+
+/// Renew a lease if it is owned by an owner. `KEYS[1]` is the lease, `ARGV[1]` the owner and `ARGV[2]` the time to live in milliseconds.
+pub static RENEW_LEASE_SCRIPT: LazyLock<LuaScript> = LazyLock::new(|| {
+    LuaScript::new(
+        r"if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0",
+    )
+});
+
+/// Load a script in the script cache of the server with `SCRIPT LOAD`, so it can be run by its SHA1 digest.
+fn load_script<C>(conn: &mut C, script: &LuaScript) -> RedsumerResult<String>
+where
+    C: Commands,
+{
+    match cmd("SCRIPT")
+        .arg("LOAD")
+        .arg(script.get_source())
+        .query::<String>(conn)
+    {
+        Ok(hash) if hash.eq(script.get_hash()) => {
+            debug!("Script loaded successfully: {hash}");
+            Ok(hash)
+        }
+        Ok(hash) => {
+            error!(
+                "Script loaded with an unexpected hash: {hash}, expected: {}",
+                script.get_hash()
+            );
+            Err(RedsumerError::from((
+                ErrorKind::ClientError,
+                "Unexpected script hash",
+                hash,
+            )))
+        }
+        Err(e) => {
+            error!("Error loading script: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Run a script by its SHA1 digest with `EVALSHA`. If it is not in the script cache of the server, like after a restart or a failover, it is loaded and run again.
+fn run_script<C, K, A, T>(
+    conn: &mut C,
+    script: &LuaScript,
+    keys: &[K],
+    args: &[A],
+) -> RedsumerResult<T>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    A: ToRedisArgs,
+    T: FromRedisValue,
+{
+    let keys: Vec<Vec<u8>> = keys.iter().flat_map(|key| key.to_redis_args()).collect();
+
+    let mut evalsha: Cmd = cmd("EVALSHA");
+    evalsha
+        .arg(script.get_hash())
+        .arg(keys.len())
+        .arg(&keys)
+        .arg(args);
+
+    match evalsha.query::<T>(conn) {
+        Err(e) if e.kind().eq(&ErrorKind::NoScriptError) => {
+            warn!(
+                "Script {} not found in the script cache, loading it",
+                script.get_hash()
+            );
+            load_script(conn, script)?;
+            evalsha.query::<T>(conn).map_err(|e| {
+                error!("Error running script: {:?}", e);
+                e.into()
+            })
+        }
+        Err(e) => {
+            error!("Error running script: {:?}", e);
+            Err(e.into())
+        }
+        Ok(reply) => {
+            debug!("Script run successfully: {}", script.get_hash());
+            Ok(reply)
+        }
+    }
+}
+
+/// A trait that bundles methods to run Lua scripts, used by atomic operations that can not be expressed with a single command.
+pub trait ScriptCommands {
+    /// Load a script in the script cache of the server with `SCRIPT LOAD`.
+    ///
+    /// # Arguments:
+    /// - **script**: The script to load.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the SHA1 digest of the script. If an error occurs, the function will return an error result.
+    fn load_script(&mut self, script: &LuaScript) -> RedsumerResult<String>;
+
+    /// Run a script with `EVALSHA`, loading it first if it is not in the script cache of the server.
+    ///
+    /// # Arguments:
+    /// - **script**: The script to run.
+    /// - **keys**: The keys accessed by the script, available as *KEYS*. Each one must implement the `ToRedisArgs` trait.
+    /// - **args**: The arguments of the script, available as *ARGV*. Each one must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the reply of the script. If an error occurs, the function will return an error result.
+    fn run_script<K, A, T>(
+        &mut self,
+        script: &LuaScript,
+        keys: &[K],
+        args: &[A],
+    ) -> RedsumerResult<T>
+    where
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+        T: FromRedisValue;
+}
+
+impl<C> ScriptCommands for C
+where
+    C: Commands,
+{
+    fn load_script(&mut self, script: &LuaScript) -> RedsumerResult<String> {
+        load_script(self, script)
+    }
+
+    fn run_script<K, A, T>(
+        &mut self,
+        script: &LuaScript,
+        keys: &[K],
+        args: &[A],
+    ) -> RedsumerResult<T>
+    where
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+        T: FromRedisValue,
+    {
+        run_script(self, script, keys, args)
+    }
+}
+
+#[cfg(test)]
+mod test_scripts {
+    use redis::{RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build an `EVALSHA` command of *script* with a single key and argument.
+    fn evalsha(script: &LuaScript, key: &str, arg: &str) -> Cmd {
+        cmd("EVALSHA")
+            .arg(script.get_hash())
+            .arg(1)
+            .arg(key)
+            .arg(arg)
+            .to_owned()
+    }
+
+    #[test]
+    fn test_load_script_ok() {
+        // Define the script:
+        let script: LuaScript = LuaScript::new("return 1");
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("SCRIPT").arg("LOAD").arg("return 1"),
+                Ok(script.get_hash()),
+            )]);
+
+        // Load the script:
+        let result: RedsumerResult<String> = conn.load_script(&script);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), script.get_hash());
+    }
+
+    #[test]
+    fn test_load_script_unexpected_hash() {
+        // Define the script:
+        let script: LuaScript = LuaScript::new("return 1");
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("SCRIPT").arg("LOAD").arg("return 1"),
+                Ok("unexpected"),
+            )]);
+
+        // Load the script:
+        let result: RedsumerResult<String> = conn.load_script(&script);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+
+    #[test]
+    fn test_run_script_cached() {
+        // Define the script:
+        let script: LuaScript = LuaScript::new("return ARGV[1]");
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                evalsha(&script, "key", "value"),
+                Ok("value"),
+            )]);
+
+        // Run the script:
+        let result: RedsumerResult<String> = conn.run_script(&script, &["key"], &["value"]);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), "value");
+    }
+
+    #[test]
+    fn test_run_script_noscript_fallback() {
+        // Define the script:
+        let script: LuaScript = LuaScript::new("return ARGV[1]");
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                evalsha(&script, "key", "value"),
+                Err(RedisError::from((ErrorKind::NoScriptError, "NOSCRIPT"))),
+            ),
+            MockCmd::new::<_, &str>(
+                cmd("SCRIPT").arg("LOAD").arg("return ARGV[1]"),
+                Ok(script.get_hash()),
+            ),
+            MockCmd::new::<_, &str>(evalsha(&script, "key", "value"), Ok("value")),
+        ]);
+
+        // Run the script:
+        let result: RedsumerResult<String> = conn.run_script(&script, &["key"], &["value"]);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), "value");
+    }
+
+    #[test]
+    fn test_run_script_error() {
+        // Define the script:
+        let script: LuaScript = LuaScript::new("return ARGV[1]");
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                evalsha(&script, "key", "value"),
+                Err(RedisError::from((ErrorKind::ResponseError, "Script Error"))),
+            )]);
+
+        // Run the script:
+        let result: RedsumerResult<String> = conn.run_script(&script, &["key"], &["value"]);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
@@ -1,32 +1,44 @@
+use std::{
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
 use redis::{
+    cmd, pipe,
     streams::{
-        StreamAutoClaimOptions, StreamAutoClaimReply, StreamId, StreamPendingCountReply,
-        StreamReadOptions, StreamReadReply,
+        StreamAutoClaimOptions, StreamAutoClaimReply, StreamClaimOptions, StreamClaimReply,
+        StreamId, StreamInfoConsumer, StreamInfoConsumersReply, StreamInfoGroup,
+        StreamInfoGroupsReply, StreamPendingCountReply, StreamPendingReply, StreamReadOptions,
+        StreamReadReply,
     },
-    Commands, ErrorKind, RedisError, RedisResult, ToRedisArgs,
+    Cmd, Commands, ErrorKind, Pipeline, RedisResult, ToRedisArgs,
 };
 use tracing::{debug, error, warn};
 
 #[allow(unused_imports)]
 use crate::core::{
     result::{RedsumerError, RedsumerResult},
-    streams::types::{
-        LastDeliveredMilliseconds, LatestPendingMessageId, NextIdToClaim, TotalTimesDelivered,
+    streams::{
+        reader::next_id,
+        types::{
+            Id, LastDeliveredMilliseconds, LatestPendingMessageId, MessageOwnership, NextIdToClaim,
+            PendingAndClaimedMessages, TotalTimesDelivered,
+        },
     },
 };
 
 pub const BEGINNING_OF_TIME_ID: &str = "0-0";
 
 /// Get StreamIds from a StreamReadReply by key.
-trait UnwrapStreamReadReply<K> {
-    /// Unwrap StreamReadReply by key into a `Vec<StreamId>`.
+pub trait UnwrapStreamReadReply<K> {
+    /// Unwrap StreamReadReply by key into a `Vec<StreamId>`, moving the messages out of the reply instead of cloning them.
     ///
     /// # Arguments:
     /// - **key**: A key to filter the StreamReadReply.
     ///
     /// # Returns:
     /// A `Vec<StreamId>` with the StreamIds found.
-    fn unwrap_by_key(&self, key: &K) -> Vec<StreamId>
+    fn unwrap_by_key(self, key: &K) -> Vec<StreamId>
     where
         K: ToString;
 }
@@ -35,16 +47,17 @@ impl<K> UnwrapStreamReadReply<K> for StreamReadReply
 where
     K: ToString,
 {
-    fn unwrap_by_key(&self, key: &K) -> Vec<StreamId> {
+    fn unwrap_by_key(self, key: &K) -> Vec<StreamId> {
+        let key: String = key.to_string();
         let mut ids: Vec<StreamId> = Vec::new();
 
-        for stream in self.keys.iter() {
-            match stream.key.eq(&key.to_string()) {
-                true => ids.extend(stream.ids.to_owned()),
+        for stream in self.keys.into_iter() {
+            match stream.key.eq(&key) {
+                true if ids.is_empty() => ids = stream.ids,
+                true => ids.extend(stream.ids),
                 false => warn!(
                     "An unexpected stream name found while extracting the key {}: {}. ",
-                    &key.to_string(),
-                    stream.key,
+                    &key, stream.key,
                 ),
             };
         }
@@ -53,30 +66,36 @@ where
     }
 }
 
+/// Get the name of a stream *key*, to report it in errors.
+fn key_name<K>(key: &K) -> String
+where
+    K: ToRedisArgs,
+{
+    key.to_redis_args()
+        .iter()
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
 /// Verify if a stream exists in Redis Stream service.
 fn verify_if_stream_exists<C, K>(conn: &mut C, key: K) -> RedsumerResult<()>
 where
     C: Commands,
     K: ToRedisArgs,
 {
-    match conn.exists::<_, bool>(key) {
+    match conn.exists::<_, bool>(&key) {
         Ok(true) => {
             debug!("The stream already exists");
             Ok(())
         }
         Ok(false) => {
             error!("The stream does not exist");
-            Err(RedisError::from((
-                ErrorKind::ClientError,
-                "Stream does not exist",
-            )))
+            Err(RedsumerError::StreamNotFound(key_name(&key)))
         }
         Err(e) => {
             error!("Error verifying if stream exists: {:?}", e);
-            Err(RedisError::from((
-                ErrorKind::ClientError,
-                "Error verifying if stream exists",
-            )))
+            Err(e.into())
         }
     }
 }
@@ -189,7 +208,7 @@ fn claim_pending_messages<C, K, G, N, ID>(
     min_idle_time: usize,
     next_id_to_claim: ID,
     count: usize,
-) -> RedisResult<(Vec<StreamId>, NextIdToClaim)>
+) -> RedsumerResult<(Vec<StreamId>, NextIdToClaim)>
 where
     C: Commands,
     K: ToRedisArgs,
@@ -207,14 +226,105 @@ where
                     min_idle_time,
                     next_id_to_claim,
                     StreamAutoClaimOptions::default().count(count),
-                )?;
-
-            Ok((reply.claimed.to_owned(), reply.next_stream_id.to_owned()))
+                )
+                .map_err(|e| match RedsumerError::from(e) {
+                    RedsumerError::Backend(e) => {
+                        error!("Error claiming pending messages: {:?}", e);
+                        RedsumerError::ClaimFailed(e)
+                    }
+                    e => e,
+                })?;
+
+            Ok((reply.claimed, reply.next_stream_id))
         }
         false => Ok((Vec::new(), BEGINNING_OF_TIME_ID.to_owned())),
     }
 }
 
+/// Claim pending messages from a stream with `XPENDING` and `XCLAIM`, for servers without `XAUTOCLAIM`, which requires Redis 6.2 or later.
+///
+/// A page of *count* entries of the group pending list is read from *next_id_to_claim*, and the entries idle for at least *min_idle_time* are claimed. The next ID to claim is the ID right after the page, or `0-0` when the end of the pending list is reached, as `XAUTOCLAIM` does.
+fn claim_pending_messages_with_xclaim<C, K, G, N, ID>(
+    conn: &mut C,
+    key: &K,
+    group: &G,
+    consumer: &N,
+    min_idle_time: usize,
+    next_id_to_claim: ID,
+    count: usize,
+) -> RedsumerResult<(Vec<StreamId>, NextIdToClaim)>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    N: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    if count.eq(&0) {
+        return Ok((Vec::new(), BEGINNING_OF_TIME_ID.to_owned()));
+    }
+
+    let reply: StreamPendingCountReply = conn
+        .xpending_count::<_, _, _, _, _, StreamPendingCountReply>(
+            key,
+            group,
+            next_id_to_claim,
+            "+",
+            count,
+        )
+        .map_err(|e| {
+            error!("Error getting pending messages to claim: {:?}", e);
+            e
+        })?;
+
+    let next_id_to_claim: NextIdToClaim = match reply.ids.last() {
+        Some(last) if reply.ids.len().ge(&count) => match next_id(&last.id.parse()?) {
+            Some(id) => id.to_string(),
+            None => BEGINNING_OF_TIME_ID.to_owned(),
+        },
+        _ => BEGINNING_OF_TIME_ID.to_owned(),
+    };
+
+    let ids: Vec<&str> = reply
+        .ids
+        .iter()
+        .filter(|m| m.last_delivered_ms.ge(&min_idle_time))
+        .map(|m| m.id.as_str())
+        .collect();
+
+    let claimed: Vec<StreamId> = claim_messages(conn, key, group, consumer, min_idle_time, &ids)?;
+
+    Ok((claimed, next_id_to_claim))
+}
+
+/// Get the ownership of a message from the `XPENDING` *reply* of a single message Id, comparing the owner of the entry with *consumer_name*.
+fn get_ownership(
+    reply: &StreamPendingCountReply,
+    consumer_name: &str,
+) -> (
+    bool,
+    Option<LastDeliveredMilliseconds>,
+    Option<TotalTimesDelivered>,
+) {
+    match reply.ids.first() {
+        Some(m) if m.consumer.eq(consumer_name) => {
+            debug!("The message is still in the consumer pending list");
+            (true, Some(m.last_delivered_ms), Some(m.times_delivered))
+        }
+        Some(m) => {
+            debug!(
+                "The message is in the pending list of another consumer: {}",
+                m.consumer
+            );
+            (false, None, None)
+        }
+        None => {
+            debug!("The message is not in the consumer pending list");
+            (false, None, None)
+        }
+    }
+}
+
 /// Verify if a message is still in the consumer pending list.
 fn is_still_mine<C, K, G, CN, ID>(
     conn: &mut C,
@@ -231,9 +341,10 @@ where
     C: Commands,
     K: ToRedisArgs,
     G: ToRedisArgs,
-    CN: ToRedisArgs,
+    CN: ToRedisArgs + ToString,
     ID: ToRedisArgs,
 {
+    let consumer_name: String = consumer.to_string();
     let reply: StreamPendingCountReply = conn
         .xpending_consumer_count::<_, _, _, _, _, _, StreamPendingCountReply>(
             key, group, &id, &id, 1, consumer,
@@ -241,22 +352,58 @@ where
 
     if reply.ids.len().gt(&1) {
         error!("More than one pending message found searching for a single message Id");
-        return Err(RedisError::from((
+        return Err(RedsumerError::from((
             ErrorKind::ClientError,
-            "Error verifying if stream exists",
+            "More than one pending message found",
         )));
     }
 
-    match reply.ids.first() {
-        Some(m) => {
-            debug!("The message is still in the consumer pending list");
-            Ok((true, Some(m.last_delivered_ms), Some(m.times_delivered)))
-        }
-        None => {
-            debug!("The message is not in the consumer pending list");
-            Ok((false, None, None))
-        }
+    Ok(get_ownership(&reply, &consumer_name))
+}
+
+/// Verify if a set of messages are still in the consumer pending list, sending one `XPENDING` command per Id in a single pipeline.
+fn are_still_mine<C, K, G, CN, ID>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    consumer: CN,
+    ids: &[ID],
+) -> RedsumerResult<Vec<MessageOwnership>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    CN: ToRedisArgs + ToString,
+    ID: ToRedisArgs,
+{
+    if ids.is_empty() {
+        debug!("No messages to verify");
+        return Ok(Vec::new());
+    }
+
+    let consumer_name: String = consumer.to_string();
+    let mut pipeline: Pipeline = pipe();
+    for id in ids {
+        pipeline.xpending_consumer_count(&key, &group, id, id, 1, &consumer);
+    }
+
+    let replies: Vec<StreamPendingCountReply> = pipeline.query(conn).map_err(|e| {
+        error!("Error verifying the pending messages: {:?}", e);
+        RedsumerError::from(e)
+    })?;
+
+    if replies.iter().any(|reply| reply.ids.len().gt(&1)) {
+        error!("More than one pending message found searching for a single message Id");
+        return Err(RedsumerError::from((
+            ErrorKind::ClientError,
+            "More than one pending message found",
+        )));
     }
+
+    Ok(replies
+        .iter()
+        .map(|reply| get_ownership(reply, &consumer_name))
+        .collect())
 }
 
 /// Ack a message in a consumer group.
@@ -278,288 +425,3306 @@ where
         }
         Err(e) => {
             error!("Error acknowledging message: {:?}", e);
-            Err(e)
+            Err(e.into())
         }
     }
 }
 
-/// A trait that bundles methods for consuming messages from a Redis stream
-pub trait ConsumerCommands<K>
+/// Remove a consumer from a consumer group.
+fn remove_consumer<C, K, G, CN>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    consumer: CN,
+) -> RedsumerResult<usize>
 where
+    C: Commands,
     K: ToRedisArgs,
+    G: ToRedisArgs,
+    CN: ToRedisArgs,
 {
-    /// Verify if a stream exists in Redis Stream service.
-    ///
-    /// # Arguments:
-    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
-    ///
-    /// # Returns:
-    /// A [`RedsumerResult`] with the result of the operation.
-    /// If the stream exists, the function will return a success result.
-    /// If the stream does not exist, the function will return an error result.
-    /// If an error occurs, the function will return an error result.
-    fn verify_if_stream_exists(&mut self, key: K) -> RedsumerResult<()>;
-
-    /// Create a consumer group in a Redis stream.
-    ///
-    /// # Arguments:
-    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
-    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
-    /// - **since_id**: The ID of the message to start consuming, which must implement the `ToRedisArgs` trait.
-    ///
-    /// # Returns:
-    /// A [`RedsumerResult`] with the result of the operation.
-    /// If the consumer group already exists, the function will return a success result with a `false` value.
-    /// If the consumer group does not exist, the function will create it and return a success result with a `true` value.
-    /// If an error occurs, the function will return an error result.
-    fn create_consumer_group<G, ID>(
-        &mut self,
-        key: K,
-        group: G,
-        since_id: ID,
-    ) -> RedsumerResult<bool>
-    where
-        G: ToRedisArgs,
-        ID: ToRedisArgs;
-
-    /// Read new messages from a stream.
-    ///
-    /// # Arguments:
-    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
-    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
-    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
-    /// - **count**: The number of messages to read.
-    /// - **block**: The time to block waiting for new messages.
-    ///
-    /// # Returns:
-    /// A [`RedisResult`] with a vector of [`StreamId`]s.
-    /// If the operation is successful, the function will return a vector of [`StreamId`]s.
-    /// If an error occurs, the function will return an error result.
-    fn read_new_messages<G, N>(
-        &mut self,
-        key: &K,
-        group: &G,
-        consumer: &N,
-        count: usize,
-        block: usize,
-    ) -> RedisResult<Vec<StreamId>>
-    where
-        G: ToRedisArgs,
-        N: ToRedisArgs;
-
-    /// Read pending messages from a stream.
-    ///
-    /// # Arguments:
-    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
-    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
-    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
-    /// - **latest_pending_message_id**: The ID of the latest pending message, which must implement the `ToRedisArgs` trait.
-    /// - **count**: The number of messages to read.
-    ///
-    /// # Returns:
-    /// A [`RedisResult`] with a tuple of a vector of [`StreamId`]s and the latest pending message ID.
-    /// If the operation is successful, the function will return a tuple with a vector of [`StreamId`]s and the latest pending message ID.
-    /// If an error occurs, the function will return an error result.
-    fn read_pending_messages<G, N, ID>(
-        &mut self,
-        key: &K,
-        group: &G,
-        consumer: &N,
-        latest_pending_message_id: ID,
-        count: usize,
-    ) -> RedisResult<(Vec<StreamId>, LatestPendingMessageId)>
-    where
-        G: ToRedisArgs,
-        N: ToRedisArgs,
-        ID: ToRedisArgs;
-
-    /// Claim pending messages from a stream.
-    ///
-    /// # Arguments:
-    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
-    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
-    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
-    /// - **min_idle_time**: The minimum idle time in milliseconds.
-    /// - **next_id_to_claim**: The next ID to claim, which must implement the `ToRedisArgs` trait.
-    /// - **count**: The number of messages to claim.
-    ///
-    /// # Returns:
-    /// A [`RedisResult`] with a tuple of a vector of [`StreamId`]s and the next ID to claim.
-    /// If the operation is successful, the function will return a tuple with a vector of [`StreamId`]s and the next ID to claim.
-    /// If an error occurs, the function will return an error result.
-    fn claim_pending_messages<G, N, ID>(
-        &mut self,
-        key: &K,
-        group: &G,
-        consumer: &N,
-        min_idle_time: usize,
-        next_id_to_claim: ID,
-        count: usize,
-    ) -> RedisResult<(Vec<StreamId>, NextIdToClaim)>
-    where
-        G: ToRedisArgs,
-        N: ToRedisArgs,
-        ID: ToRedisArgs;
-
-    /// Verify if a message is still in the consumer pending list.
-    ///
-    /// # Arguments:
-    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
-    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
-    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
-    /// - **id**: The ID of the message to verify, which must implement the `ToRedisArgs` trait.
-    ///
-    /// # Returns:
-    /// A [`RedsumerResult`] with a boolean value. If the message is still in the consumer pending list, the function will return `true`. If the message is not in the consumer pending list, the function will return `false`. If an error occurs, the function will return an error result.
-    fn is_still_mine<G, CN, ID>(
-        &mut self,
-        key: K,
-        group: G,
-        consumer: CN,
-        id: ID,
-    ) -> RedsumerResult<(
-        bool,
-        Option<LastDeliveredMilliseconds>,
-        Option<TotalTimesDelivered>,
-    )>
-    where
-        G: ToRedisArgs,
-        CN: ToRedisArgs,
-        ID: ToRedisArgs;
-
-    /// Acknowledge a message in a consumer group.
-    ///
-    /// # Arguments:
-    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
-    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
-    /// - **id**: The ID of the message to acknowledge, which must implement the `ToRedisArgs` trait.
-    ///
-    /// # Returns:
-    /// A [`RedsumerResult`] with a boolean value. If the message was successfully acknowledged, the function will return `true`. If the message was not acknowledged, the function will return `false`. If an error occurs, the function will return an error result.
-    fn ack<G, ID>(&mut self, key: K, group: G, id: ID) -> RedsumerResult<bool>
-    where
-        G: ToRedisArgs,
-        ID: ToRedisArgs;
+    match conn.xgroup_delconsumer::<_, _, _, usize>(key, group, consumer) {
+        Ok(pending_messages) => {
+            debug!(
+                "The consumer was removed from the consumers group with {pending_messages} pending messages"
+            );
+            Ok(pending_messages)
+        }
+        Err(e) => {
+            error!("Error removing consumer: {:?}", e);
+            Err(e.into())
+        }
+    }
 }
 
-impl<C, K> ConsumerCommands<K> for C
+/// Destroy a consumer group in a stream.
+fn destroy_consumer_group<C, K, G>(conn: &mut C, key: K, group: G) -> RedsumerResult<bool>
 where
     C: Commands,
-    K: ToRedisArgs + ToString,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
 {
-    fn verify_if_stream_exists(&mut self, key: K) -> RedsumerResult<()>
-    where
-        K: ToRedisArgs,
-    {
-        verify_if_stream_exists(self, key)
+    match conn.xgroup_destroy::<_, _, bool>(key, group) {
+        Ok(true) => {
+            debug!("The consumers group was successfully destroyed");
+            Ok(true)
+        }
+        Ok(false) => {
+            debug!("The consumers group does not exist");
+            Ok(false)
+        }
+        Err(e) => {
+            error!("Error destroying consumer group: {:?}", e);
+            Err(e.into())
+        }
     }
+}
+
+/// Set the last delivered ID of a consumer group.
+fn set_consumer_group_id<C, K, G, ID>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    id: ID,
+    entries_read: Option<usize>,
+) -> RedsumerResult<()>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    let mut command: Cmd = cmd("XGROUP");
+    command.arg("SETID").arg(key).arg(group).arg(id);
+
+    if let Some(entries_read) = entries_read {
+        command.arg("ENTRIESREAD").arg(entries_read);
+    }
+
+    match command.query::<()>(conn) {
+        Ok(()) => {
+            debug!("The consumers group position was successfully set");
+            Ok(())
+        }
+        Err(e) => {
+            error!("Error setting consumer group position: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Get the information of a consumer group in a stream.
+fn get_consumer_group_info<C, K, G>(
+    conn: &mut C,
+    key: K,
+    group: G,
+) -> RedsumerResult<StreamInfoGroup>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToString,
+{
+    let reply: StreamInfoGroupsReply = conn.xinfo_groups(key)?;
+
+    match reply
+        .groups
+        .into_iter()
+        .find(|g| g.name.eq(&group.to_string()))
+    {
+        Some(g) => {
+            debug!("The consumers group information was found");
+            Ok(g)
+        }
+        None => {
+            error!("The consumers group does not exist");
+            Err(RedsumerError::from((
+                ErrorKind::ClientError,
+                "Consumer group does not exist",
+            )))
+        }
+    }
+}
+
+/// Get the summary of the pending messages of a consumers group in a stream.
+fn get_pending_messages_summary<C, K, G>(
+    conn: &mut C,
+    key: K,
+    group: G,
+) -> RedsumerResult<StreamPendingReply>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+{
+    match conn.xpending::<_, _, StreamPendingReply>(key, group) {
+        Ok(reply) => {
+            debug!("Pending messages summary was read successfully");
+            Ok(reply)
+        }
+        Err(e) => {
+            error!("Error reading pending messages summary: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Create a consumer group in a stream, creating the stream if it does not exist.
+fn create_consumer_group_and_stream<C, K, G, ID>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    since_id: ID,
+) -> RedisResult<bool>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    match conn.xgroup_create_mkstream::<_, _, _, String>(key, group, since_id) {
+        Ok(_) => {
+            debug!("The consumers group was successfully created");
+            Ok(true)
+        }
+        Err(e) => {
+            if e.to_string().contains("BUSYGROUP") {
+                debug!("The consumer group already exists");
+                Ok(false)
+            } else {
+                error!("Error creating consumer group: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Initial interval to poll a stream while waiting for it to exist.
+const WAIT_FOR_STREAM_INITIAL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Max interval to poll a stream while waiting for it to exist.
+const WAIT_FOR_STREAM_MAX_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Wait until a stream exists, polling it with exponential backoff up to *max_wait*.
+fn wait_for_stream<C, K>(conn: &mut C, key: K, max_wait: Duration) -> RedsumerResult<()>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    let started_at: Instant = Instant::now();
+    let mut interval: Duration = WAIT_FOR_STREAM_INITIAL_INTERVAL;
+
+    loop {
+        match conn.exists::<_, bool>(&key) {
+            Ok(true) => {
+                debug!("The stream already exists");
+                return Ok(());
+            }
+            Ok(false) => {
+                let remaining: Duration = max_wait.saturating_sub(started_at.elapsed());
+                if remaining.is_zero() {
+                    error!("The stream does not exist after waiting {:?}", max_wait);
+                    return Err(RedsumerError::StreamNotFound(key_name(&key)));
+                }
+
+                debug!("The stream does not exist yet, waiting {:?}", interval);
+                sleep(interval.min(remaining));
+                interval = interval.saturating_mul(2).min(WAIT_FOR_STREAM_MAX_INTERVAL);
+            }
+            Err(e) => {
+                error!("Error verifying if stream exists: {:?}", e);
+                return Err(e.into());
+            }
+        }
+    }
+}
+
+/// Prefix of the keys where the consumer heartbeats are stored.
+const HEARTBEAT_KEY_PREFIX: &str = "redsumer:heartbeat";
+
+/// Get the key where the heartbeat of a consumer is stored.
+fn heartbeat_key<K, G, CN>(key: &K, group: &G, consumer: &CN) -> String
+where
+    K: ToString,
+    G: ToString,
+    CN: ToString,
+{
+    format!(
+        "{HEARTBEAT_KEY_PREFIX}:{}:{}:{}",
+        key.to_string(),
+        group.to_string(),
+        consumer.to_string()
+    )
+}
+
+/// Get the information of the consumers of a consumer group in a stream.
+fn get_consumers_info<C, K, G>(
+    conn: &mut C,
+    key: K,
+    group: G,
+) -> RedsumerResult<Vec<StreamInfoConsumer>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+{
+    match conn.xinfo_consumers::<_, _, StreamInfoConsumersReply>(key, group) {
+        Ok(reply) => {
+            debug!("Total consumers found: {}", reply.consumers.len());
+            Ok(reply.consumers)
+        }
+        Err(e) => {
+            error!("Error reading consumers information: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Send the heartbeat of a consumer, setting its heartbeat key with the given time to live.
+fn send_heartbeat<C, K, G, CN>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    consumer: CN,
+    ttl: Duration,
+) -> RedsumerResult<()>
+where
+    C: Commands,
+    K: ToString,
+    G: ToString,
+    CN: ToString,
+{
+    match conn.pset_ex::<_, _, ()>(
+        heartbeat_key(&key, &group, &consumer),
+        1,
+        ttl.as_millis() as u64,
+    ) {
+        Ok(()) => {
+            debug!("The consumer heartbeat was sent");
+            Ok(())
+        }
+        Err(e) => {
+            error!("Error sending consumer heartbeat: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Get the names of the consumers of a consumer group whose heartbeat expired.
+fn get_dead_consumers<C, K, G>(conn: &mut C, key: K, group: G) -> RedsumerResult<Vec<String>>
+where
+    C: Commands,
+    K: ToRedisArgs + ToString,
+    G: ToRedisArgs + ToString,
+{
+    let mut dead_consumers: Vec<String> = Vec::new();
+
+    for consumer in get_consumers_info(conn, &key, &group)? {
+        if !conn.exists::<_, bool>(heartbeat_key(&key, &group, &consumer.name))? {
+            dead_consumers.push(consumer.name);
+        }
+    }
+
+    debug!("Total dead consumers found: {}", dead_consumers.len());
+
+    Ok(dead_consumers)
+}
+
+/// Remove the consumers of a consumer group that are idle for at least *min_idle_time* and have no pending messages.
+fn remove_idle_consumers<C, K, G>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    min_idle_time: Duration,
+) -> RedsumerResult<Vec<String>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+{
+    let mut removed_consumers: Vec<String> = Vec::new();
+
+    for consumer in get_consumers_info(conn, &key, &group)? {
+        if consumer.pending.eq(&0) && (consumer.idle as u128).ge(&min_idle_time.as_millis()) {
+            remove_consumer(conn, &key, &group, &consumer.name)?;
+            removed_consumers.push(consumer.name);
+        }
+    }
+
+    debug!("Total idle consumers removed: {}", removed_consumers.len());
+
+    Ok(removed_consumers)
+}
+
+/// Get the ID of the first message of a stream that is not acknowledged by every consumer group, which is the lowest ID between the first pending message of each group, or the message after its last delivered ID if it has no pending messages. If the stream has no consumer groups, `None` is returned.
+fn get_first_unacknowledged_id<C, K>(conn: &mut C, key: K) -> RedsumerResult<Option<Id>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    let reply: StreamInfoGroupsReply = conn.xinfo_groups(&key)?;
+
+    let mut first_unacknowledged: Option<Id> = None;
+    for group in reply.groups {
+        let id: Option<Id> = match get_pending_messages_summary(conn, &key, &group.name)? {
+            StreamPendingReply::Data(data) => Some(data.start_id.parse()?),
+            StreamPendingReply::Empty => next_id(&group.last_delivered_id.parse()?),
+        };
+
+        first_unacknowledged = match (first_unacknowledged, id) {
+            (Some(current), Some(id)) => Some(current.min(id)),
+            (current, id) => current.or(id),
+        };
+    }
+
+    debug!("First unacknowledged message ID: {first_unacknowledged:?}");
+
+    Ok(first_unacknowledged)
+}
+
+/// Read pending messages and claim pending messages from a stream in a single pipeline.
+#[allow(clippy::too_many_arguments)]
+fn read_pending_and_claim_messages<C, K, G, N, PID, CID>(
+    conn: &mut C,
+    key: &K,
+    group: &G,
+    consumer: &N,
+    latest_pending_message_id: PID,
+    pending_count: usize,
+    min_idle_time: usize,
+    next_id_to_claim: CID,
+    claim_count: usize,
+) -> RedisResult<PendingAndClaimedMessages>
+where
+    C: Commands,
+    K: ToRedisArgs + ToString,
+    G: ToRedisArgs,
+    N: ToRedisArgs,
+    PID: ToRedisArgs,
+    CID: ToRedisArgs,
+{
+    let (pending_reply, claim_reply): (StreamReadReply, StreamAutoClaimReply) = pipe()
+        .xread_options(
+            &[key],
+            &[latest_pending_message_id],
+            &StreamReadOptions::default()
+                .group(group, consumer)
+                .count(pending_count),
+        )
+        .xautoclaim_options(
+            key,
+            group,
+            consumer,
+            min_idle_time,
+            next_id_to_claim,
+            StreamAutoClaimOptions::default().count(claim_count),
+        )
+        .query(conn)
+        .map_err(|e| {
+            error!("Error reading pending and claiming messages: {:?}", e);
+            e
+        })?;
+
+    let pending_messages: Vec<StreamId> = pending_reply.unwrap_by_key(key);
+    let latest_pending_message_id: String = match pending_messages.last() {
+        Some(s) => s.id.to_owned(),
+        None => BEGINNING_OF_TIME_ID.to_owned(),
+    };
+
+    debug!(
+        "Total pending messages found: {}, total claimed messages: {}",
+        pending_messages.len(),
+        claim_reply.claimed.len()
+    );
+
+    Ok((
+        (pending_messages, latest_pending_message_id),
+        (claim_reply.claimed, claim_reply.next_stream_id),
+    ))
+}
+
+/// Renew the ownership of pending messages of a consumer, resetting their idle time so they are not claimed by other consumers.
+fn renew_messages<C, K, G, CN, ID>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    consumer: CN,
+    min_idle_time: usize,
+    ids: &[ID],
+) -> RedsumerResult<Vec<String>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    CN: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    if ids.is_empty() {
+        debug!("No messages to renew");
+        return Ok(Vec::new());
+    }
+
+    match conn.xclaim_options::<_, _, _, _, _, Vec<String>>(
+        key,
+        group,
+        consumer,
+        min_idle_time,
+        ids,
+        StreamClaimOptions::default().with_justid(),
+    ) {
+        Ok(renewed) => {
+            debug!("Total messages renewed: {}", renewed.len());
+            Ok(renewed)
+        }
+        Err(e) => {
+            error!("Error renewing messages: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Release pending messages of a consumer, setting their idle time so they can be claimed by other consumers right away.
+fn release_messages<C, K, G, CN, ID>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    consumer: CN,
+    idle_time: usize,
+    ids: &[ID],
+) -> RedsumerResult<Vec<String>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    CN: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    if ids.is_empty() {
+        debug!("No messages to release");
+        return Ok(Vec::new());
+    }
+
+    match conn.xclaim_options::<_, _, _, _, _, Vec<String>>(
+        key,
+        group,
+        consumer,
+        0,
+        ids,
+        StreamClaimOptions::default().idle(idle_time).with_justid(),
+    ) {
+        Ok(released) => {
+            debug!("Total messages released: {}", released.len());
+            Ok(released)
+        }
+        Err(e) => {
+            error!("Error releasing messages: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Read new messages from several streams with a single `XREADGROUP`, getting the messages of every stream with its key.
+fn read_new_messages_from_streams<C, K, G, N>(
+    conn: &mut C,
+    keys: &[K],
+    group: &G,
+    consumer: &N,
+    count: usize,
+    block: usize,
+) -> RedsumerResult<Vec<(String, Vec<StreamId>)>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    N: ToRedisArgs,
+{
+    if keys.is_empty() || count.eq(&0) {
+        debug!("No streams or messages to read");
+        return Ok(Vec::new());
+    }
+
+    match conn.xread_options::<_, _, StreamReadReply>(
+        keys,
+        &vec![">"; keys.len()],
+        &StreamReadOptions::default()
+            .group(group, consumer)
+            .count(count)
+            .block(block),
+    ) {
+        Ok(reply) => {
+            let messages: Vec<(String, Vec<StreamId>)> = reply
+                .keys
+                .into_iter()
+                .filter(|stream| !stream.ids.is_empty())
+                .map(|stream| (stream.key, stream.ids))
+                .collect();
+            debug!("Total streams with new messages: {}", messages.len());
+            Ok(messages)
+        }
+        Err(e) => {
+            error!("Error reading new messages from streams: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Read the pending messages of a consumer from several streams with a single `XREADGROUP`, after the given *ids*, getting the messages of every stream with its key.
+fn read_pending_messages_from_streams<C, K, G, N>(
+    conn: &mut C,
+    keys: &[K],
+    ids: &[String],
+    group: &G,
+    consumer: &N,
+    count: usize,
+) -> RedsumerResult<Vec<(String, Vec<StreamId>)>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    N: ToRedisArgs,
+{
+    if keys.is_empty() || count.eq(&0) {
+        debug!("No streams or messages to read");
+        return Ok(Vec::new());
+    }
+
+    match conn.xread_options::<_, _, StreamReadReply>(
+        keys,
+        ids,
+        &StreamReadOptions::default()
+            .group(group, consumer)
+            .count(count),
+    ) {
+        Ok(reply) => {
+            let messages: Vec<(String, Vec<StreamId>)> = reply
+                .keys
+                .into_iter()
+                .map(|stream| (stream.key, stream.ids))
+                .collect();
+            debug!(
+                "Total streams read for pending messages: {}",
+                messages.len()
+            );
+            Ok(messages)
+        }
+        Err(e) => {
+            error!("Error reading pending messages from streams: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Get the IDs of the pending messages of a consumer, from *start* to *end*, with an idle time greater than or equal to *min_idle_time*.
+#[allow(clippy::too_many_arguments)]
+fn get_consumer_pending_ids<C, K, G, CN, S, E>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    consumer: CN,
+    min_idle_time: usize,
+    start: S,
+    end: E,
+    count: usize,
+) -> RedsumerResult<Vec<String>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    CN: ToRedisArgs,
+    S: ToRedisArgs,
+    E: ToRedisArgs,
+{
+    if count.eq(&0) {
+        debug!("No pending messages to get");
+        return Ok(Vec::new());
+    }
+
+    match cmd("XPENDING")
+        .arg(key)
+        .arg(group)
+        .arg("IDLE")
+        .arg(min_idle_time)
+        .arg(start)
+        .arg(end)
+        .arg(count)
+        .arg(consumer)
+        .query::<StreamPendingCountReply>(conn)
+    {
+        Ok(reply) => {
+            debug!(
+                "Total pending messages of the consumer: {}",
+                reply.ids.len()
+            );
+            Ok(reply.ids.into_iter().map(|m| m.id).collect())
+        }
+        Err(e) => {
+            error!(
+                "Error getting the pending messages of the consumer: {:?}",
+                e
+            );
+            Err(e.into())
+        }
+    }
+}
+
+/// Claim pending messages by ID with `XCLAIM`, whatever their owner is.
+fn claim_messages<C, K, G, CN, ID>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    consumer: CN,
+    min_idle_time: usize,
+    ids: &[ID],
+) -> RedsumerResult<Vec<StreamId>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    CN: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    if ids.is_empty() {
+        debug!("No messages to claim");
+        return Ok(Vec::new());
+    }
+
+    let reply: StreamClaimReply = conn
+        .xclaim::<_, _, _, _, _, StreamClaimReply>(key, group, consumer, min_idle_time, ids)
+        .map_err(|e| match RedsumerError::from(e) {
+            RedsumerError::Backend(e) => {
+                error!("Error claiming messages: {:?}", e);
+                RedsumerError::ClaimFailed(e)
+            }
+            e => e,
+        })?;
+
+    debug!("Total messages claimed: {}", reply.ids.len());
+
+    Ok(reply.ids)
+}
+
+/// Get the IDs of the pending messages of a consumers group from *start* to *end*, with the consumer that owns every message.
+fn get_group_pending_ids<C, K, G, S, E>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    start: S,
+    end: E,
+    count: usize,
+) -> RedsumerResult<Vec<(String, String)>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    S: ToRedisArgs,
+    E: ToRedisArgs,
+{
+    if count.eq(&0) {
+        debug!("No pending messages to get");
+        return Ok(Vec::new());
+    }
+
+    match conn
+        .xpending_count::<_, _, _, _, _, StreamPendingCountReply>(key, group, start, end, count)
+    {
+        Ok(reply) => {
+            debug!("Total pending messages of the group: {}", reply.ids.len());
+            Ok(reply.ids.into_iter().map(|m| (m.id, m.consumer)).collect())
+        }
+        Err(e) => {
+            error!("Error getting the pending messages of the group: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Add messages to the pending list of a consumer with `XCLAIM ... FORCE JUSTID`, even if they are not pending in the consumers group.
+fn force_claim_messages<C, K, G, CN, ID>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    consumer: CN,
+    ids: &[ID],
+) -> RedsumerResult<Vec<String>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    CN: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    if ids.is_empty() {
+        debug!("No messages to claim");
+        return Ok(Vec::new());
+    }
+
+    match conn.xclaim_options::<_, _, _, _, _, Vec<String>>(
+        key,
+        group,
+        consumer,
+        0,
+        ids,
+        StreamClaimOptions::default().with_force().with_justid(),
+    ) {
+        Ok(claimed) => {
+            debug!("Total messages claimed: {}", claimed.len());
+            Ok(claimed)
+        }
+        Err(e) => {
+            error!("Error claiming messages: {:?}", e);
+            Err(RedsumerError::ClaimFailed(e))
+        }
+    }
+}
+
+/// Ack a set of messages in a consumer group, reporting which of them were acknowledged. `XACK` only replies with a count, so one `XPENDING` command per Id is sent right before it, in the same `MULTI`/`EXEC` transaction: a message is acknowledged only if it was pending in the group.
+fn ack_many_detailed<C, K, G, ID>(
+    conn: &mut C,
+    key: K,
+    group: G,
+    ids: &[ID],
+) -> RedsumerResult<Vec<bool>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    if ids.is_empty() {
+        debug!("No messages to acknowledge");
+        return Ok(Vec::new());
+    }
+
+    let mut pipeline: Pipeline = pipe();
+    pipeline.atomic();
+    for id in ids {
+        pipeline.xpending_count(&key, &group, id, id, 1);
+    }
+    pipeline.xack(&key, &group, ids).ignore();
+
+    let replies: Vec<StreamPendingCountReply> = pipeline.query(conn).map_err(|e| {
+        error!("Error acknowledging messages: {:?}", e);
+        RedsumerError::from(e)
+    })?;
+
+    let acked: Vec<bool> = replies.iter().map(|reply| !reply.ids.is_empty()).collect();
+
+    debug!(
+        "Total messages acknowledged: {} of {}",
+        acked.iter().filter(|acked| **acked).count(),
+        ids.len()
+    );
+
+    Ok(acked)
+}
+
+/// Ack a set of messages in a consumer group with a single `XACK` command.
+fn ack_many<C, K, G, ID>(conn: &mut C, key: K, group: G, ids: &[ID]) -> RedsumerResult<usize>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    G: ToRedisArgs,
+    ID: ToRedisArgs,
+{
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    match conn.xack::<_, _, _, usize>(key, group, ids) {
+        Ok(acked) => {
+            debug!("{acked} of {} messages were acknowledged", ids.len());
+            Ok(acked)
+        }
+        Err(e) => {
+            error!("Error acknowledging messages: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// A trait that bundles methods for consuming messages from a Redis stream
+pub trait ConsumerCommands<K>
+where
+    K: ToRedisArgs,
+{
+    /// Verify if a stream exists in Redis Stream service.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the result of the operation.
+    /// If the stream exists, the function will return a success result.
+    /// If the stream does not exist, the function will return an error result.
+    /// If an error occurs, the function will return an error result.
+    fn verify_if_stream_exists(&mut self, key: K) -> RedsumerResult<()>;
+
+    /// Create a consumer group in a Redis stream.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **since_id**: The ID of the message to start consuming, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the result of the operation.
+    /// If the consumer group already exists, the function will return a success result with a `false` value.
+    /// If the consumer group does not exist, the function will create it and return a success result with a `true` value.
+    /// If an error occurs, the function will return an error result.
+    fn create_consumer_group<G, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        since_id: ID,
+    ) -> RedsumerResult<bool>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Read new messages from a stream.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The number of messages to read.
+    /// - **block**: The time to block waiting for new messages.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a vector of [`StreamId`]s.
+    /// If the operation is successful, the function will return a vector of [`StreamId`]s.
+    /// If an error occurs, the function will return an error result.
+    fn read_new_messages<G, N>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        count: usize,
+        block: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs;
+
+    /// Read pending messages from a stream.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
+    /// - **latest_pending_message_id**: The ID of the latest pending message, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The number of messages to read.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a tuple of a vector of [`StreamId`]s and the latest pending message ID.
+    /// If the operation is successful, the function will return a tuple with a vector of [`StreamId`]s and the latest pending message ID.
+    /// If an error occurs, the function will return an error result.
+    fn read_pending_messages<G, N, ID>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        latest_pending_message_id: ID,
+        count: usize,
+    ) -> RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Claim pending messages from a stream.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
+    /// - **min_idle_time**: The minimum idle time in milliseconds.
+    /// - **next_id_to_claim**: The next ID to claim, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The number of messages to claim.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a tuple of a vector of [`StreamId`]s and the next ID to claim.
+    /// If the operation is successful, the function will return a tuple with a vector of [`StreamId`]s and the next ID to claim.
+    /// If the server rejects the claim, a [`RedsumerError::ClaimFailed`] is returned.
+    fn claim_pending_messages<G, N, ID>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        min_idle_time: usize,
+        next_id_to_claim: ID,
+        count: usize,
+    ) -> RedsumerResult<(Vec<StreamId>, NextIdToClaim)>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Claim pending messages from a stream with `XPENDING` and `XCLAIM`, for servers without `XAUTOCLAIM`, like Redis versions before 6.2. The messages are claimed as in [`ConsumerCommands::claim_pending_messages`], with one more round trip.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
+    /// - **min_idle_time**: The minimum idle time in milliseconds.
+    /// - **next_id_to_claim**: The next ID to claim, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The number of pending messages to scan.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a tuple of a vector of [`StreamId`]s and the next ID to claim, which is `0-0` when the end of the pending list is reached. If the server rejects the claim, a [`RedsumerError::ClaimFailed`] is returned.
+    fn claim_pending_messages_with_xclaim<G, N, ID>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        min_idle_time: usize,
+        next_id_to_claim: ID,
+        count: usize,
+    ) -> RedsumerResult<(Vec<StreamId>, NextIdToClaim)>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Verify if a message is still in the consumer pending list. The owner of the `XPENDING` entry is compared with the consumer name, so the message is only reported as owned by its current consumer.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` and `ToString` traits.
+    /// - **id**: The ID of the message to verify, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a boolean value, the milliseconds elapsed since the message was last delivered and the number of times it was delivered. If the message is still in the consumer pending list, the function will return `true` with the delivery metadata. If the message is not in the consumer pending list, the function will return `false` without metadata. If an error occurs, the function will return an error result.
+    fn is_still_mine<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        id: ID,
+    ) -> RedsumerResult<(
+        bool,
+        Option<LastDeliveredMilliseconds>,
+        Option<TotalTimesDelivered>,
+    )>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs + ToString,
+        ID: ToRedisArgs;
+
+    /// Verify if a set of messages are still in the consumer pending list, checking all of them in one round trip. The ownership of each message is verified as in [`ConsumerCommands::is_still_mine`].
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` and `ToString` traits.
+    /// - **ids**: The IDs of the messages to verify, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the ownership and delivery metadata of each message, in the same order as *ids*. If an error occurs, the function will return an error result.
+    fn are_still_mine<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<MessageOwnership>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs + ToString,
+        ID: ToRedisArgs;
+
+    /// Remove a consumer from a consumer group.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: The name of the consumer to remove, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the number of pending messages the consumer had before it was removed. If an error occurs, the function will return an error result.
+    fn remove_consumer<G, CN>(&mut self, key: K, group: G, consumer: CN) -> RedsumerResult<usize>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs;
+
+    /// Destroy a consumer group in a Redis stream.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a boolean value. If the consumer group was destroyed, the function will return `true`. If the consumer group does not exist, the function will return `false`. If an error occurs, the function will return an error result.
+    fn destroy_consumer_group<G>(&mut self, key: K, group: G) -> RedsumerResult<bool>
+    where
+        G: ToRedisArgs;
+
+    /// Set the last delivered ID of a consumer group, so the next new messages delivered to the group are the ones after that ID.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **id**: The new last delivered ID, which must implement the `ToRedisArgs` trait.
+    /// - **entries_read**: An optional value for the group entries read counter, used by Redis >= 7.0 to compute the group lag.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the result of the operation. If an error occurs, the function will return an error result.
+    fn set_consumer_group_id<G, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        id: ID,
+        entries_read: Option<usize>,
+    ) -> RedsumerResult<()>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Get the information of a consumer group in a Redis stream, as reported by `XINFO GROUPS`.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToString` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a [`StreamInfoGroup`]. If the consumer group does not exist or an error occurs, the function will return an error result.
+    fn get_consumer_group_info<G>(&mut self, key: K, group: G) -> RedsumerResult<StreamInfoGroup>
+    where
+        G: ToString;
+
+    /// Get the summary of the pending messages of a consumers group in a Redis stream, as reported by `XPENDING` without range arguments.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a [`StreamPendingReply`], which contains the total number of pending messages and the number of pending messages per consumer. If an error occurs, the function will return an error result.
+    fn get_pending_messages_summary<G>(
+        &mut self,
+        key: K,
+        group: G,
+    ) -> RedsumerResult<StreamPendingReply>
+    where
+        G: ToRedisArgs;
+
+    /// Create a consumer group in a Redis stream with `MKSTREAM`, so the stream is created if it does not exist.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **since_id**: The ID of the message to start consuming, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a boolean value indicating if the consumer group was created. If the consumer group already exists, the function will return `false`. If an error occurs, the function will return an error result.
+    fn create_consumer_group_and_stream<G, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        since_id: ID,
+    ) -> RedsumerResult<bool>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Wait until a stream exists in Redis, polling it with exponential backoff.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **max_wait**: The max time to wait for the stream.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the result of the operation.
+    /// If the stream exists before *max_wait* elapses, the function will return a success result.
+    /// If the stream does not exist after *max_wait*, or an error occurs, the function will return an error result.
+    fn wait_for_stream(&mut self, key: K, max_wait: Duration) -> RedsumerResult<()>;
+
+    /// Send the heartbeat of a consumer, setting a key with a time to live that is refreshed on every call.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToString` trait.
+    /// - **group**: A consumers group, which must implement the `ToString` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToString` trait.
+    /// - **ttl**: The time to live of the heartbeat.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the result of the operation. If an error occurs, the function will return an error result.
+    fn send_heartbeat<G, CN>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        ttl: Duration,
+    ) -> RedsumerResult<()>
+    where
+        G: ToString,
+        CN: ToString;
+
+    /// Get the names of the consumers of a consumer group whose heartbeat expired or was never sent.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` and `ToString` traits.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the names of the dead consumers. If an error occurs, the function will return an error result.
+    fn get_dead_consumers<G>(&mut self, key: K, group: G) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs + ToString;
+
+    /// Remove the consumers of a consumer group that are idle for at least *min_idle_time* and have no pending messages.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **min_idle_time**: The min idle time of the consumers to remove.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the names of the removed consumers. If an error occurs, the function will return an error result.
+    fn remove_idle_consumers<G>(
+        &mut self,
+        key: K,
+        group: G,
+        min_idle_time: Duration,
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs;
+
+    /// Get the ID of the first message of a Redis stream that is not acknowledged by every consumers group. Messages with a lower ID were delivered to and acknowledged by all the groups.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the ID of the first unacknowledged message, or `None` if the stream has no consumers groups. If an error occurs, the function will return an error result.
+    fn get_first_unacknowledged_id(&mut self, key: K) -> RedsumerResult<Option<Id>>;
+
+    /// Read pending messages and claim pending messages from a Redis stream in a single pipeline, saving a round trip compared to [`read_pending_messages`](ConsumerCommands::read_pending_messages) followed by [`claim_pending_messages`](ConsumerCommands::claim_pending_messages).
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` and `ToString` traits.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
+    /// - **latest_pending_message_id**: The latest pending message ID, which must implement the `ToRedisArgs` trait.
+    /// - **pending_count**: The number of pending messages to read, which must be greater than `0`.
+    /// - **min_idle_time**: The min idle time in milliseconds of the messages to claim.
+    /// - **next_id_to_claim**: The next ID to claim, which must implement the `ToRedisArgs` trait.
+    /// - **claim_count**: The number of messages to claim, which must be greater than `0`.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the pending messages and the latest pending message ID, and the claimed messages and the next ID to claim. If an error occurs, the function will return an error result.
+    #[allow(clippy::too_many_arguments)]
+    fn read_pending_and_claim_messages<G, N, PID, CID>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        latest_pending_message_id: PID,
+        pending_count: usize,
+        min_idle_time: usize,
+        next_id_to_claim: CID,
+        claim_count: usize,
+    ) -> RedsumerResult<PendingAndClaimedMessages>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+        PID: ToRedisArgs,
+        CID: ToRedisArgs;
+
+    /// Renew the ownership of pending messages of a consumer with `XCLAIM ... JUSTID`, resetting their idle time so they are not claimed by other consumers.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: The consumer that keeps the messages, which must implement the `ToRedisArgs` trait.
+    /// - **min_idle_time**: The min idle time in milliseconds of the messages to renew. Messages with a lower idle time are not renewed.
+    /// - **ids**: The IDs of the messages to renew, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the renewed messages. Messages that are not pending anymore are not renewed. If an error occurs, the function will return an error result.
+    fn renew_messages<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        min_idle_time: usize,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Release pending messages of a consumer with `XCLAIM ... IDLE ... JUSTID`, setting their idle time so they can be claimed by other consumers right away. The messages are kept in the pending list of the consumer until they are claimed.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: The consumer that keeps the messages, which must implement the `ToRedisArgs` trait.
+    /// - **idle_time**: The idle time in milliseconds set to the messages. It must be greater than or equal to the min idle time used by other consumers to claim messages.
+    /// - **ids**: The IDs of the messages to release, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the released messages. Messages that are not pending anymore are not released. If an error occurs, the function will return an error result.
+    fn release_messages<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        idle_time: usize,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Read new messages from several Redis streams with a single `XREADGROUP`. The consumers group must exist in every stream.
+    ///
+    /// # Arguments:
+    /// - **keys**: The stream keys, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The max number of new messages to read from every stream.
+    /// - **block**: The time in milliseconds to wait for new messages to arrive in any stream.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the key and the new messages of every stream where messages were found. If there are no keys, no messages are read. If an error occurs, the function will return an error result.
+    fn read_new_messages_from_streams<G, N>(
+        &mut self,
+        keys: &[K],
+        group: &G,
+        consumer: &N,
+        count: usize,
+        block: usize,
+    ) -> RedsumerResult<Vec<(String, Vec<StreamId>)>>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs;
+
+    /// Read the pending messages of a consumer from several Redis streams with a single `XREADGROUP`, to recover the messages delivered to it before a restart. The consumers group must exist in every stream.
+    ///
+    /// # Arguments:
+    /// - **keys**: The stream keys, which must implement the `ToRedisArgs` trait.
+    /// - **ids**: The ID after which the pending messages are read in every stream, in the same order as the keys. Use `0-0` to read them from the beginning.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: A consumer name, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The max number of pending messages to read from every stream.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the key and the pending messages of every stream, even when no messages were found, so the streams without pending messages can be told apart. If there are no keys, no messages are read. If an error occurs, the function will return an error result.
+    fn read_pending_messages_from_streams<G, N>(
+        &mut self,
+        keys: &[K],
+        ids: &[String],
+        group: &G,
+        consumer: &N,
+        count: usize,
+    ) -> RedsumerResult<Vec<(String, Vec<StreamId>)>>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs;
+
+    /// Get the IDs of the pending messages of a specific consumer with `XPENDING ... IDLE`, from *start* to *end*. It requires Redis 6.2 or later.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: The consumer that owns the messages, which must implement the `ToRedisArgs` trait.
+    /// - **min_idle_time**: The min idle time in milliseconds of the messages. Use `0` to get all of them.
+    /// - **start**: The first ID of the range, like `-`, which must implement the `ToRedisArgs` trait.
+    /// - **end**: The last ID of the range, like `+`, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The max number of IDs to get.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the pending messages of the consumer, in ascending order. If the count is `0`, no IDs are read. If an error occurs, the function will return an error result.
+    #[allow(clippy::too_many_arguments)]
+    fn get_consumer_pending_ids<G, CN, S, E>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        min_idle_time: usize,
+        start: S,
+        end: E,
+        count: usize,
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        S: ToRedisArgs,
+        E: ToRedisArgs;
+
+    /// Claim pending messages by ID with `XCLAIM`, transferring them to a consumer whatever their owner is.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: The consumer that claims the messages, which must implement the `ToRedisArgs` trait.
+    /// - **min_idle_time**: The min idle time in milliseconds of the messages to claim. Messages with a lower idle time are not claimed.
+    /// - **ids**: The IDs of the messages to claim, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the claimed messages. Messages that are not pending anymore are not claimed. If there are no IDs, no messages are claimed. If an error occurs, the function will return an error result.
+    fn claim_messages<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        min_idle_time: usize,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Get the IDs of the pending messages of a consumers group with `XPENDING`, from *start* to *end*, with the consumer that owns every message.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **start**: The first ID of the range, like `-`, which must implement the `ToRedisArgs` trait.
+    /// - **end**: The last ID of the range, like `+`, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The max number of IDs to get.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the ID and the consumer name of every pending message, in ascending order. If the count is `0`, no IDs are read. If an error occurs, the function will return an error result.
+    fn get_group_pending_ids<G, S, E>(
+        &mut self,
+        key: K,
+        group: G,
+        start: S,
+        end: E,
+        count: usize,
+    ) -> RedsumerResult<Vec<(String, String)>>
+    where
+        G: ToRedisArgs,
+        S: ToRedisArgs,
+        E: ToRedisArgs;
+
+    /// Add messages to the pending list of a consumer with `XCLAIM ... FORCE JUSTID`, creating the pending entries if the messages are not pending in the consumers group yet.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **consumer**: The consumer that receives the messages, which must implement the `ToRedisArgs` trait.
+    /// - **ids**: The IDs of the messages, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the claimed messages. Messages deleted from the stream are not claimed. If there are no IDs, no messages are claimed. If an error occurs, the function will return an error result.
+    fn force_claim_messages<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        ID: ToRedisArgs;
+}
+
+impl<C, K> ConsumerCommands<K> for C
+where
+    C: Commands,
+    K: ToRedisArgs + ToString,
+{
+    fn verify_if_stream_exists(&mut self, key: K) -> RedsumerResult<()>
+    where
+        K: ToRedisArgs,
+    {
+        verify_if_stream_exists(self, key)
+    }
+
+    fn create_consumer_group<G, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        since_id: ID,
+    ) -> RedsumerResult<bool>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        Ok(create_consumer_group(self, key, group, since_id)?)
+    }
+
+    fn read_new_messages<G, N>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        count: usize,
+        block: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+    {
+        Ok(read_new_messages(self, key, group, consumer, count, block)?)
+    }
+
+    fn read_pending_messages<G, N, ID>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        latest_pending_message_id: ID,
+        count: usize,
+    ) -> RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        Ok(read_pending_messages(
+            self,
+            key,
+            group,
+            consumer,
+            latest_pending_message_id,
+            count,
+        )?)
+    }
+
+    fn claim_pending_messages<G, N, ID>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        min_idle_time: usize,
+        next_id_to_claim: ID,
+        count: usize,
+    ) -> RedsumerResult<(Vec<StreamId>, NextIdToClaim)>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        claim_pending_messages(
+            self,
+            key,
+            group,
+            consumer,
+            min_idle_time,
+            next_id_to_claim,
+            count,
+        )
+    }
+
+    fn claim_pending_messages_with_xclaim<G, N, ID>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        min_idle_time: usize,
+        next_id_to_claim: ID,
+        count: usize,
+    ) -> RedsumerResult<(Vec<StreamId>, NextIdToClaim)>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        claim_pending_messages_with_xclaim(
+            self,
+            key,
+            group,
+            consumer,
+            min_idle_time,
+            next_id_to_claim,
+            count,
+        )
+    }
+
+    fn is_still_mine<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        id: ID,
+    ) -> RedsumerResult<(
+        bool,
+        Option<LastDeliveredMilliseconds>,
+        Option<TotalTimesDelivered>,
+    )>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs + ToString,
+        ID: ToRedisArgs,
+    {
+        is_still_mine(self, key, group, consumer, id)
+    }
+
+    fn are_still_mine<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<MessageOwnership>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs + ToString,
+        ID: ToRedisArgs,
+    {
+        are_still_mine(self, key, group, consumer, ids)
+    }
+
+    fn remove_consumer<G, CN>(&mut self, key: K, group: G, consumer: CN) -> RedsumerResult<usize>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+    {
+        remove_consumer(self, key, group, consumer)
+    }
+
+    fn destroy_consumer_group<G>(&mut self, key: K, group: G) -> RedsumerResult<bool>
+    where
+        G: ToRedisArgs,
+    {
+        destroy_consumer_group(self, key, group)
+    }
+
+    fn set_consumer_group_id<G, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        id: ID,
+        entries_read: Option<usize>,
+    ) -> RedsumerResult<()>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        set_consumer_group_id(self, key, group, id, entries_read)
+    }
+
+    fn get_consumer_group_info<G>(&mut self, key: K, group: G) -> RedsumerResult<StreamInfoGroup>
+    where
+        G: ToString,
+    {
+        get_consumer_group_info(self, key, group)
+    }
+
+    fn get_pending_messages_summary<G>(
+        &mut self,
+        key: K,
+        group: G,
+    ) -> RedsumerResult<StreamPendingReply>
+    where
+        G: ToRedisArgs,
+    {
+        get_pending_messages_summary(self, key, group)
+    }
+
+    fn create_consumer_group_and_stream<G, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        since_id: ID,
+    ) -> RedsumerResult<bool>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        Ok(create_consumer_group_and_stream(
+            self, key, group, since_id,
+        )?)
+    }
+
+    fn wait_for_stream(&mut self, key: K, max_wait: Duration) -> RedsumerResult<()> {
+        wait_for_stream(self, key, max_wait)
+    }
+
+    fn send_heartbeat<G, CN>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        ttl: Duration,
+    ) -> RedsumerResult<()>
+    where
+        G: ToString,
+        CN: ToString,
+    {
+        send_heartbeat(self, key, group, consumer, ttl)
+    }
+
+    fn get_dead_consumers<G>(&mut self, key: K, group: G) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs + ToString,
+    {
+        get_dead_consumers(self, key, group)
+    }
+
+    fn remove_idle_consumers<G>(
+        &mut self,
+        key: K,
+        group: G,
+        min_idle_time: Duration,
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+    {
+        remove_idle_consumers(self, key, group, min_idle_time)
+    }
+
+    fn get_first_unacknowledged_id(&mut self, key: K) -> RedsumerResult<Option<Id>> {
+        get_first_unacknowledged_id(self, key)
+    }
+
+    fn read_pending_and_claim_messages<G, N, PID, CID>(
+        &mut self,
+        key: &K,
+        group: &G,
+        consumer: &N,
+        latest_pending_message_id: PID,
+        pending_count: usize,
+        min_idle_time: usize,
+        next_id_to_claim: CID,
+        claim_count: usize,
+    ) -> RedsumerResult<PendingAndClaimedMessages>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+        PID: ToRedisArgs,
+        CID: ToRedisArgs,
+    {
+        Ok(read_pending_and_claim_messages(
+            self,
+            key,
+            group,
+            consumer,
+            latest_pending_message_id,
+            pending_count,
+            min_idle_time,
+            next_id_to_claim,
+            claim_count,
+        )?)
+    }
+
+    fn renew_messages<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        min_idle_time: usize,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        renew_messages(self, key, group, consumer, min_idle_time, ids)
+    }
+
+    fn release_messages<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        idle_time: usize,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        release_messages(self, key, group, consumer, idle_time, ids)
+    }
+
+    fn read_new_messages_from_streams<G, N>(
+        &mut self,
+        keys: &[K],
+        group: &G,
+        consumer: &N,
+        count: usize,
+        block: usize,
+    ) -> RedsumerResult<Vec<(String, Vec<StreamId>)>>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+    {
+        read_new_messages_from_streams(self, keys, group, consumer, count, block)
+    }
+
+    fn read_pending_messages_from_streams<G, N>(
+        &mut self,
+        keys: &[K],
+        ids: &[String],
+        group: &G,
+        consumer: &N,
+        count: usize,
+    ) -> RedsumerResult<Vec<(String, Vec<StreamId>)>>
+    where
+        G: ToRedisArgs,
+        N: ToRedisArgs,
+    {
+        read_pending_messages_from_streams(self, keys, ids, group, consumer, count)
+    }
+
+    fn get_consumer_pending_ids<G, CN, S, E>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        min_idle_time: usize,
+        start: S,
+        end: E,
+        count: usize,
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        S: ToRedisArgs,
+        E: ToRedisArgs,
+    {
+        get_consumer_pending_ids(self, key, group, consumer, min_idle_time, start, end, count)
+    }
+
+    fn claim_messages<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        min_idle_time: usize,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        claim_messages(self, key, group, consumer, min_idle_time, ids)
+    }
+
+    fn get_group_pending_ids<G, S, E>(
+        &mut self,
+        key: K,
+        group: G,
+        start: S,
+        end: E,
+        count: usize,
+    ) -> RedsumerResult<Vec<(String, String)>>
+    where
+        G: ToRedisArgs,
+        S: ToRedisArgs,
+        E: ToRedisArgs,
+    {
+        get_group_pending_ids(self, key, group, start, end, count)
+    }
+
+    fn force_claim_messages<G, CN, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        consumer: CN,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<String>>
+    where
+        G: ToRedisArgs,
+        CN: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        force_claim_messages(self, key, group, consumer, ids)
+    }
+}
+
+/// A trait that bundles the commands to acknowledge messages by their raw IDs in a consumers group.
+///
+/// It is not exported: messages are acked through [`Consumer::ack`](crate::consumer::Consumer::ack) and the other methods taking an [`AckToken`](crate::consumer::AckToken), so IDs consumed from another stream or group can not be acked by mistake.
+pub(crate) trait AckCommands<K>
+where
+    K: ToRedisArgs,
+{
+    /// Acknowledge a message in a consumer group.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **id**: The ID of the message to acknowledge, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a boolean value. If the message was successfully acknowledged, the function will return `true`. If the message was not acknowledged, the function will return `false`. If an error occurs, the function will return an error result.
+    fn ack<G, ID>(&mut self, key: K, group: G, id: ID) -> RedsumerResult<bool>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Acknowledge a set of messages in a consumer group in one round trip, reporting which specific messages were acknowledged, unlike `XACK` that only replies with a count.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **ids**: The IDs of the messages to acknowledge, which must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a boolean value per message, in the same order as *ids*. A message is not acknowledged when it was not pending in the consumers group, for example because it was already acknowledged. If an error occurs, the function will return an error result.
+    fn ack_many_detailed<G, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<bool>>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs;
+
+    /// Acknowledge a set of messages in a consumer group with a single `XACK` command.
+    ///
+    /// # Arguments:
+    /// - **key**: A stream key, which must implement the `ToRedisArgs` trait.
+    /// - **group**: A consumers group, which must implement the `ToRedisArgs` trait.
+    /// - **ids**: The IDs of the messages to acknowledge. Each one must implement the `ToRedisArgs` trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the number of messages acknowledged, which excludes the messages that were not pending. If an error occurs, the function will return an error result.
+    fn ack_many<G, ID>(&mut self, key: K, group: G, ids: &[ID]) -> RedsumerResult<usize>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs;
+}
+
+impl<C, K> AckCommands<K> for C
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    fn ack<G, ID>(&mut self, key: K, group: G, id: ID) -> RedsumerResult<bool>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        ack(self, key, group, id)
+    }
+
+    fn ack_many_detailed<G, ID>(
+        &mut self,
+        key: K,
+        group: G,
+        ids: &[ID],
+    ) -> RedsumerResult<Vec<bool>>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        ack_many_detailed(self, key, group, ids)
+    }
+
+    fn ack_many<G, ID>(&mut self, key: K, group: G, ids: &[ID]) -> RedsumerResult<usize>
+    where
+        G: ToRedisArgs,
+        ID: ToRedisArgs,
+    {
+        ack_many(self, key, group, ids)
+    }
+}
+
+#[cfg(test)]
+mod test_create_consumer_group {
+    use redis::{cmd, ErrorKind, RedisError};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_create_non_existent_consumer_group() {
+        // Define the key, group, and since_id:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let since_id: &str = "0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg(key)
+                    .arg(group)
+                    .arg(since_id),
+                Ok("Ok"),
+            )]);
+
+        // Create the consumer group:
+        let result: RedsumerResult<bool> = conn.create_consumer_group(key, group, since_id);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(result.unwrap())
+    }
+
+    #[test]
+    fn test_create_existent_consumer_group() {
+        // Define the key, group, and since_id:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let since_id: &str = "0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg(key)
+                    .arg(group)
+                    .arg(since_id),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "BUSYGROUP Consumer Group name already exists",
+                ))),
+            )]);
+
+        // Create the consumer group:
+        let result: RedsumerResult<bool> = conn.create_consumer_group(key, group, since_id);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(!result.unwrap())
+    }
+
+    #[test]
+    fn test_create_consumer_group_error() {
+        // Define the key, group, and since_id:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let since_id: &str = "0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg(key)
+                    .arg(group)
+                    .arg(since_id),
+                Err(RedisError::from((ErrorKind::ResponseError, "XGROUP Error"))),
+            )]);
+
+        // Create the consumer group:
+        let result: RedsumerResult<bool> = conn.create_consumer_group(key, group, since_id);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_verify_if_stream_exists {
+    use redis::{cmd, ErrorKind, RedisError};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_verify_if_stream_exists() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, i64>(cmd("EXISTS").arg(key), Ok(1))]);
+
+        // Verify if the stream exists:
+        let result: RedsumerResult<()> = conn.verify_if_stream_exists(key);
+
+        // Verify the result:
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_verify_if_stream_does_not_exist() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, i64>(cmd("EXISTS").arg(key), Ok(0))]);
+
+        // Verify if the stream exists:
+        let result: RedsumerResult<()> = conn.verify_if_stream_exists(key);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_verify_if_stream_exists_error() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("EXISTS").arg(key),
+            Err(RedisError::from((ErrorKind::ResponseError, "EXISTS Error"))),
+        )]);
+
+        // Verify if the stream exists:
+        let result: RedsumerResult<()> = conn.verify_if_stream_exists(key);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_read_new_messages {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_read_new_messages_with_zero_count() {
+        // Define the key, group, consumer, count, and block:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let count: usize = 0;
+        let block: usize = 1;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Read new messages:
+        let result: RedsumerResult<Vec<StreamId>> =
+            conn.read_new_messages(&key, &group, &consumer, count, block);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_new_messages_ok() {
+        // Define the key, group, and consumer:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let count: usize = 2;
+        let block: usize = 1;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREADGROUP")
+                    .arg(
+                        &StreamReadOptions::default()
+                            .group(group, consumer)
+                            .count(count)
+                            .block(block),
+                    )
+                    .arg("STREAMS")
+                    .arg(&[key])
+                    .arg(&[">"]),
+                Ok(Value::Array(vec![Value::Map(vec![
+                    (
+                        Value::SimpleString("my-key".to_string()),
+                        Value::Array(vec![Value::Map(vec![(
+                            Value::SimpleString("1-0".to_string()),
+                            Value::Array(vec![
+                                Value::SimpleString("code".to_string()),
+                                Value::Int(1),
+                            ]),
+                        )])]),
+                    ),
+                    (
+                        Value::SimpleString("fake-key".to_string()),
+                        Value::Array(vec![Value::Map(vec![(
+                            Value::SimpleString("666-0".to_string()),
+                            Value::Array(vec![
+                                Value::SimpleString("code".to_string()),
+                                Value::Int(666),
+                            ]),
+                        )])]),
+                    ),
+                ])])),
+            )]);
+
+        // Consume messages:
+        let result: RedsumerResult<Vec<StreamId>> =
+            conn.read_new_messages(&key, &group, &consumer, count, block);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        // Verify the messages:
+        let messages: Vec<StreamId> = result.unwrap();
+        assert!(messages.len().eq(&1));
+
+        assert!(messages[0].id.eq("1-0"));
+        assert!(messages[0].map.get("code").unwrap().eq(&Value::Int(1)));
+    }
+
+    #[test]
+    fn test_read_new_messages_error() {
+        // Define the key, group, and consumer:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let count: usize = 2;
+        let block: usize = 1;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREADGROUP")
+                    .arg(
+                        &StreamReadOptions::default()
+                            .group(group, consumer)
+                            .count(count)
+                            .block(block),
+                    )
+                    .arg("STREAMS")
+                    .arg(&[key])
+                    .arg(&[">"]),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "XREADGROUP Error",
+                ))),
+            )]);
+
+        // Consume messages:
+        let result: RedsumerResult<Vec<StreamId>> =
+            conn.read_new_messages(&key, &group, &consumer, count, block);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_read_pending_messages {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_read_pending_messages_with_zero_count() {
+        // Define the key, group, consumer, latest_pending_message_id, and count:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let latest_pending_message_id: &str = "0-0";
+        let count: usize = 0;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Read pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)> =
+            conn.read_pending_messages(&key, &group, &consumer, latest_pending_message_id, count);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let (messages, next_id_to_claim): (Vec<StreamId>, LatestPendingMessageId) = result.unwrap();
+        assert!(messages.is_empty());
+        assert!(next_id_to_claim.eq(BEGINNING_OF_TIME_ID));
+    }
+
+    #[test]
+    fn test_read_pending_messages_empty() {
+        // Define the key, group, consumer, latest_pending_message_id, and count:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let latest_pending_message_id = "0-0";
+        let count = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREADGROUP")
+                    .arg(
+                        &StreamReadOptions::default()
+                            .group(group, consumer)
+                            .count(count),
+                    )
+                    .arg("STREAMS")
+                    .arg(&[key])
+                    .arg(&[latest_pending_message_id]),
+                Ok(Value::Array(vec![Value::Map(vec![])])),
+            )]);
+
+        // Read pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)> =
+            conn.read_pending_messages(&key, &group, &consumer, latest_pending_message_id, count);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let (messages, next_id_to_claim): (Vec<StreamId>, LatestPendingMessageId) = result.unwrap();
+        assert!(messages.len().eq(&0));
+        assert!(next_id_to_claim.eq(BEGINNING_OF_TIME_ID));
+    }
+
+    #[test]
+    fn test_read_pending_messages_ok() {
+        // Define the key, group, consumer, latest_pending_message_id, and count:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let latest_pending_message_id = "0-0";
+        let count = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREADGROUP")
+                    .arg(
+                        &StreamReadOptions::default()
+                            .group(group, consumer)
+                            .count(count),
+                    )
+                    .arg("STREAMS")
+                    .arg(&[key])
+                    .arg(&[latest_pending_message_id]),
+                Ok(Value::Array(vec![Value::Map(vec![
+                    (
+                        Value::SimpleString("my-key".to_string()),
+                        Value::Array(vec![Value::Map(vec![(
+                            Value::SimpleString("1-0".to_string()),
+                            Value::Array(vec![
+                                Value::SimpleString("code".to_string()),
+                                Value::Int(1),
+                            ]),
+                        )])]),
+                    ),
+                    (
+                        Value::SimpleString("fake-key".to_string()),
+                        Value::Array(vec![Value::Map(vec![(
+                            Value::SimpleString("666-0".to_string()),
+                            Value::Array(vec![
+                                Value::SimpleString("code".to_string()),
+                                Value::Int(666),
+                            ]),
+                        )])]),
+                    ),
+                ])])),
+            )]);
+
+        // Read pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)> =
+            conn.read_pending_messages(&key, &group, &consumer, latest_pending_message_id, count);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let (messages, next_id_to_claim): (Vec<StreamId>, LatestPendingMessageId) = result.unwrap();
+        assert!(messages.len().eq(&1));
+
+        assert!(messages[0].id.eq("1-0"));
+        assert!(messages[0].map.get("code").unwrap().eq(&Value::Int(1)));
+
+        assert!(next_id_to_claim.eq("1-0"));
+    }
+
+    #[test]
+    fn test_read_pending_messages_error() {
+        // Define the key, group, consumer, latest_pending_message_id, and count:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let latest_pending_message_id = "0-0";
+        let count = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREADGROUP")
+                    .arg(
+                        &StreamReadOptions::default()
+                            .group(group, consumer)
+                            .count(count),
+                    )
+                    .arg("STREAMS")
+                    .arg(&[key])
+                    .arg(&[latest_pending_message_id]),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "XREADGROUP Error",
+                ))),
+            )]);
+
+        // Read pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)> =
+            conn.read_pending_messages(&key, &group, &consumer, latest_pending_message_id, count);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_claim_pending_messages {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_claim_pending_messages_with_zero_count() {
+        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let min_idle_time = 1000;
+        let next_id_to_claim = "0-0";
+        let count = 0;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Claim pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, NextIdToClaim)> = conn.claim_pending_messages(
+            &key,
+            &group,
+            &consumer,
+            min_idle_time,
+            next_id_to_claim,
+            count,
+        );
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let (messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) = result.unwrap();
+        assert!(messages.is_empty());
+        assert!(next_id_to_claim.eq(BEGINNING_OF_TIME_ID));
+    }
+
+    #[test]
+    fn test_claim_pending_messages_empty() {
+        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let min_idle_time = 1000;
+        let next_id_to_claim = "0-0";
+        let count = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XAUTOCLAIM")
+                    .arg(key)
+                    .arg(group)
+                    .arg(consumer)
+                    .arg(min_idle_time)
+                    .arg(next_id_to_claim)
+                    .arg(&StreamAutoClaimOptions::default().count(count)),
+                Ok(Value::Array(vec![
+                    Value::SimpleString("0-0".to_string()),
+                    Value::Array(vec![]),
+                    Value::Array(vec![]),
+                ])),
+            )]);
+
+        // Claim pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, NextIdToClaim)> = conn.claim_pending_messages(
+            &key,
+            &group,
+            &consumer,
+            min_idle_time,
+            next_id_to_claim,
+            count,
+        );
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let (messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) = result.unwrap();
+        assert!(messages.len().eq(&0));
+        assert!(next_id_to_claim.eq(BEGINNING_OF_TIME_ID));
+    }
+
+    #[test]
+    fn test_claim_pending_messages_ok() {
+        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let min_idle_time = 1000;
+        let next_id_to_claim = "0-0";
+        let count = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XAUTOCLAIM")
+                    .arg(key)
+                    .arg(group)
+                    .arg(consumer)
+                    .arg(min_idle_time)
+                    .arg(next_id_to_claim)
+                    .arg(&StreamAutoClaimOptions::default().count(count)),
+                Ok(Value::Array(vec![
+                    Value::SimpleString("1-0".to_string()),
+                    Value::Array(vec![Value::Array(vec![
+                        Value::SimpleString("1-0".to_string()),
+                        Value::Array(vec![Value::SimpleString("code".to_string()), Value::Int(1)]),
+                    ])]),
+                    Value::Array(vec![]),
+                ])),
+            )]);
+
+        // Claim pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, NextIdToClaim)> = conn.claim_pending_messages(
+            &key,
+            &group,
+            &consumer,
+            min_idle_time,
+            next_id_to_claim,
+            count,
+        );
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let (messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) = result.unwrap();
+        assert!(messages.len().eq(&1));
+
+        assert!(messages[0].id.eq("1-0"));
+        assert!(messages[0].map.get("code").unwrap().eq(&Value::Int(1)));
+
+        assert!(next_id_to_claim.eq("1-0"));
+    }
+
+    #[test]
+    fn test_claim_pending_messages_error() {
+        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let min_idle_time = 1000;
+        let next_id_to_claim = "0-0";
+        let count = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XAUTOCLAIM")
+                    .arg(key)
+                    .arg(group)
+                    .arg(consumer)
+                    .arg(min_idle_time)
+                    .arg(next_id_to_claim)
+                    .arg(&StreamAutoClaimOptions::default().count(count)),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "XAUTOCLAIM Error",
+                ))),
+            )]);
+
+        // Claim pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, NextIdToClaim)> = conn.claim_pending_messages(
+            &key,
+            &group,
+            &consumer,
+            min_idle_time,
+            next_id_to_claim,
+            count,
+        );
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_claim_pending_messages_with_xclaim {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `XPENDING` entry with the given *id* and *idle* time.
+    fn pending_entry(id: &str, idle: i64) -> Value {
+        Value::Array(vec![
+            Value::BulkString(id.as_bytes().to_vec()),
+            Value::BulkString(b"other-consumer".to_vec()),
+            Value::Int(idle),
+            Value::Int(1),
+        ])
+    }
+
+    #[test]
+    fn test_claim_pending_messages_with_xclaim_full_page() {
+        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let min_idle_time: usize = 1000;
+        let count: usize = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg(key)
+                    .arg(group)
+                    .arg("0-0")
+                    .arg("+")
+                    .arg(count),
+                Ok(Value::Array(vec![
+                    pending_entry("1-0", 500),
+                    pending_entry("2-0", 2000),
+                ])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg(key)
+                    .arg(group)
+                    .arg(consumer)
+                    .arg(min_idle_time)
+                    .arg(&["2-0"]),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"2-0".to_vec()),
+                    Value::Array(vec![
+                        Value::BulkString(b"field".to_vec()),
+                        Value::BulkString(b"value".to_vec()),
+                    ]),
+                ])])),
+            ),
+        ]);
+
+        // Claim pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, NextIdToClaim)> = conn
+            .claim_pending_messages_with_xclaim(
+                &key,
+                &group,
+                &consumer,
+                min_idle_time,
+                "0-0",
+                count,
+            );
+
+        // Verify the result:
+        let (messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) = result.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, "2-0");
+        assert_eq!(next_id_to_claim, "2-1");
+    }
+
+    #[test]
+    fn test_claim_pending_messages_with_xclaim_end_of_list() {
+        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let count: usize = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg(key)
+                    .arg(group)
+                    .arg("3-0")
+                    .arg("+")
+                    .arg(count),
+                Ok(Value::Array(vec![pending_entry("3-0", 500)])),
+            )]);
+
+        // Claim pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, NextIdToClaim)> =
+            conn.claim_pending_messages_with_xclaim(&key, &group, &consumer, 1000, "3-0", count);
+
+        // Verify the result:
+        let (messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) = result.unwrap();
+        assert!(messages.is_empty());
+        assert_eq!(next_id_to_claim, BEGINNING_OF_TIME_ID);
+    }
+
+    #[test]
+    fn test_claim_pending_messages_with_xclaim_error() {
+        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let count: usize = 2;
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg(key)
+                    .arg(group)
+                    .arg("0-0")
+                    .arg("+")
+                    .arg(count),
+                Err(RedisError::from((ErrorKind::ResponseError, "Error"))),
+            )]);
+
+        // Claim pending messages:
+        let result: RedsumerResult<(Vec<StreamId>, NextIdToClaim)> =
+            conn.claim_pending_messages_with_xclaim(&key, &group, &consumer, 1000, "0-0", count);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
+
+#[cfg(test)]
+mod test_if_is_still_mine {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_is_still_mine_true() {
+        // Define the key, group, consumer, and id:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let id = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg(key)
+                    .arg(group)
+                    .arg(&[id])
+                    .arg(&[id])
+                    .arg(1)
+                    .arg(consumer),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"1526984818136-0".to_vec()),
+                    Value::BulkString(b"my-consumer".to_vec()),
+                    Value::Int(196415),
+                    Value::Int(5),
+                ])])),
+            )]);
+
+        // Verify if the message is still in the consumer pending list:
+        let result: RedsumerResult<(
+            bool,
+            Option<LastDeliveredMilliseconds>,
+            Option<TotalTimesDelivered>,
+        )> = conn.is_still_mine(key, group, consumer, id);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let reply: (
+            bool,
+            Option<LastDeliveredMilliseconds>,
+            Option<TotalTimesDelivered>,
+        ) = result.unwrap();
+
+        assert!(reply.0);
+        assert!(reply.1.is_some());
+        assert!(reply.1.unwrap().eq(&196415));
+        assert!(reply.2.is_some());
+        assert!(reply.2.unwrap().eq(&5));
+    }
+
+    #[test]
+    fn test_is_still_mine_false() {
+        // Define the key, group, consumer, and id:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let id = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg(key)
+                    .arg(group)
+                    .arg(&[id])
+                    .arg(&[id])
+                    .arg(1)
+                    .arg(consumer),
+                Ok(Value::Array(vec![])),
+            )]);
+
+        // Verify if the message is still in the consumer pending list:
+        let result: RedsumerResult<(
+            bool,
+            Option<LastDeliveredMilliseconds>,
+            Option<TotalTimesDelivered>,
+        )> = conn.is_still_mine(key, group, consumer, id);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let reply: (
+            bool,
+            Option<LastDeliveredMilliseconds>,
+            Option<TotalTimesDelivered>,
+        ) = result.unwrap();
+
+        assert!(!reply.0);
+        assert!(reply.1.is_none());
+        assert!(reply.2.is_none());
+    }
+
+    #[test]
+    fn test_is_still_mine_other_consumer() {
+        // Define the key, group, consumer, and id:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+        let id = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg(key)
+                    .arg(group)
+                    .arg(&[id])
+                    .arg(&[id])
+                    .arg(1)
+                    .arg(consumer),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"1-0".to_vec()),
+                    Value::BulkString(b"other-consumer".to_vec()),
+                    Value::Int(1000),
+                    Value::Int(2),
+                ])])),
+            )]);
+
+        // Verify if the message is still in the consumer pending list:
+        let result: RedsumerResult<(
+            bool,
+            Option<LastDeliveredMilliseconds>,
+            Option<TotalTimesDelivered>,
+        )> = conn.is_still_mine(key, group, consumer, id);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), (false, None, None));
+    }
+
+    #[test]
+    fn test_is_still_mine_error() {
+        // Define the key, group, consumer, and id:
+        let key = "my-key";
+        let group = "my-group";
+        let consumer = "my-consumer";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg(key)
+                    .arg(group)
+                    .arg(&["1-0"])
+                    .arg(&["1-0"])
+                    .arg(1)
+                    .arg(consumer),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "XPENDING Error",
+                ))),
+            )]);
+
+        // Verify if the message is still in the consumer pending list:
+        let result: RedsumerResult<(
+            bool,
+            Option<LastDeliveredMilliseconds>,
+            Option<TotalTimesDelivered>,
+        )> = conn.is_still_mine(key, group, consumer, "1-0");
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_ack {
+    use redis::{cmd, ErrorKind, RedisError};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_ack_ok_true() {
+        // Define the key, group, and id:
+        let key = "my-key";
+        let group = "my-group";
+        let id: &str = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("XACK").arg(key).arg(group).arg(&[id]),
+            Ok(1),
+        )]);
+
+        // Acknowledge the message:
+        let result: RedsumerResult<bool> = conn.ack(key, group, id);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_ack_ok_false() {
+        // Define the key, group, and id:
+        let key = "my-key";
+        let group = "my-group";
+        let id = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("XACK").arg(key).arg(group).arg(&[id]),
+            Ok(0),
+        )]);
+
+        // Acknowledge the message:
+        let result: RedsumerResult<bool> = conn.ack(key, group, id);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn test_ack_error() {
+        // Define the key, group, and id:
+        let key = "my-key";
+        let group = "my-group";
+        let id = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("XACK").arg(key).arg(group).arg(&[id]),
+            Err(RedisError::from((ErrorKind::ResponseError, "XACK Error"))),
+        )]);
+
+        // Acknowledge the message:
+        let result: RedsumerResult<bool> = conn.ack(key, group, id);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_remove_consumer {
+    use redis::{cmd, ErrorKind, RedisError};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_remove_consumer_ok() {
+        // Define the key, group, and consumer:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("XGROUP")
+                .arg("DELCONSUMER")
+                .arg(key)
+                .arg(group)
+                .arg(consumer),
+            Ok(2),
+        )]);
+
+        // Remove the consumer:
+        let result: RedsumerResult<usize> = conn.remove_consumer(key, group, consumer);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_remove_consumer_error() {
+        // Define the key, group, and consumer:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("XGROUP")
+                .arg("DELCONSUMER")
+                .arg(key)
+                .arg(group)
+                .arg(consumer),
+            Err(RedisError::from((
+                ErrorKind::ResponseError,
+                "NOGROUP No such consumer group",
+            ))),
+        )]);
+
+        // Remove the consumer:
+        let result: RedsumerResult<usize> = conn.remove_consumer(key, group, consumer);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_destroy_consumer_group {
+    use redis::{cmd, ErrorKind, RedisError};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_destroy_existent_consumer_group() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("XGROUP").arg("DESTROY").arg(key).arg(group),
+            Ok(1),
+        )]);
+
+        // Destroy the consumer group:
+        let result: RedsumerResult<bool> = conn.destroy_consumer_group(key, group);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_destroy_non_existent_consumer_group() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("XGROUP").arg("DESTROY").arg(key).arg(group),
+            Ok(0),
+        )]);
+
+        // Destroy the consumer group:
+        let result: RedsumerResult<bool> = conn.destroy_consumer_group(key, group);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn test_destroy_consumer_group_error() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("XGROUP").arg("DESTROY").arg(key).arg(group),
+            Err(RedisError::from((ErrorKind::ResponseError, "XGROUP Error"))),
+        )]);
+
+        // Destroy the consumer group:
+        let result: RedsumerResult<bool> = conn.destroy_consumer_group(key, group);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_set_consumer_group_id {
+    use redis::{cmd, ErrorKind, RedisError};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_set_consumer_group_id_ok() {
+        // Define the key, group, and id:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let id: &str = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("XGROUP").arg("SETID").arg(key).arg(group).arg(id),
+                Ok("OK"),
+            )]);
+
+        // Set the consumer group position:
+        let result: RedsumerResult<()> = conn.set_consumer_group_id(key, group, id, None);
+
+        // Verify the result:
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_set_consumer_group_id_with_entries_read_ok() {
+        // Define the key, group, and id:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let id: &str = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("XGROUP")
+                    .arg("SETID")
+                    .arg(key)
+                    .arg(group)
+                    .arg(id)
+                    .arg("ENTRIESREAD")
+                    .arg(5),
+                Ok("OK"),
+            )]);
+
+        // Set the consumer group position:
+        let result: RedsumerResult<()> = conn.set_consumer_group_id(key, group, id, Some(5));
+
+        // Verify the result:
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_set_consumer_group_id_error() {
+        // Define the key, group, and id:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let id: &str = "1-0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("XGROUP").arg("SETID").arg(key).arg(group).arg(id),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "NOGROUP No such consumer group",
+                ))),
+            )]);
+
+        // Set the consumer group position:
+        let result: RedsumerResult<()> = conn.set_consumer_group_id(key, group, id, None);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
 
-    fn create_consumer_group<G, ID>(
-        &mut self,
-        key: K,
-        group: G,
-        since_id: ID,
-    ) -> RedsumerResult<bool>
-    where
-        G: ToRedisArgs,
-        ID: ToRedisArgs,
-    {
-        create_consumer_group(self, key, group, since_id)
+#[cfg(test)]
+mod test_get_consumer_group_info {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `XINFO GROUPS` reply item for a group.
+    fn group_info(name: &str, lag: Value) -> Value {
+        Value::Array(vec![
+            Value::BulkString(b"name".to_vec()),
+            Value::BulkString(name.as_bytes().to_vec()),
+            Value::BulkString(b"consumers".to_vec()),
+            Value::Int(2),
+            Value::BulkString(b"pending".to_vec()),
+            Value::Int(3),
+            Value::BulkString(b"last-delivered-id".to_vec()),
+            Value::BulkString(b"10-0".to_vec()),
+            Value::BulkString(b"entries-read".to_vec()),
+            Value::Int(10),
+            Value::BulkString(b"lag".to_vec()),
+            lag,
+        ])
     }
 
-    fn read_new_messages<G, N>(
-        &mut self,
-        key: &K,
-        group: &G,
-        consumer: &N,
-        count: usize,
-        block: usize,
-    ) -> RedisResult<Vec<StreamId>>
-    where
-        G: ToRedisArgs,
-        N: ToRedisArgs,
-    {
-        read_new_messages(self, key, group, consumer, count, block)
+    #[test]
+    fn test_get_consumer_group_info_ok() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("GROUPS").arg(key),
+                Ok(Value::Array(vec![
+                    group_info("other-group", Value::Nil),
+                    group_info(group, Value::Int(4)),
+                ])),
+            )]);
+
+        // Get the consumer group information:
+        let result: RedsumerResult<StreamInfoGroup> = conn.get_consumer_group_info(key, group);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let info: StreamInfoGroup = result.unwrap();
+        assert_eq!(info.name, group);
+        assert_eq!(info.pending, 3);
+        assert_eq!(info.entries_read, Some(10));
+        assert_eq!(info.lag, Some(4));
     }
 
-    fn read_pending_messages<G, N, ID>(
-        &mut self,
-        key: &K,
-        group: &G,
-        consumer: &N,
-        latest_pending_message_id: ID,
-        count: usize,
-    ) -> RedisResult<(Vec<StreamId>, LatestPendingMessageId)>
-    where
-        G: ToRedisArgs,
-        N: ToRedisArgs,
-        ID: ToRedisArgs,
-    {
-        read_pending_messages(self, key, group, consumer, latest_pending_message_id, count)
+    #[test]
+    fn test_get_consumer_group_info_not_found() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("GROUPS").arg(key),
+                Ok(Value::Array(vec![group_info("other-group", Value::Nil)])),
+            )]);
+
+        // Get the consumer group information:
+        let result: RedsumerResult<StreamInfoGroup> = conn.get_consumer_group_info(key, group);
+
+        // Verify the result:
+        assert!(result.is_err());
     }
 
-    fn claim_pending_messages<G, N, ID>(
-        &mut self,
-        key: &K,
-        group: &G,
-        consumer: &N,
-        min_idle_time: usize,
-        next_id_to_claim: ID,
-        count: usize,
-    ) -> RedisResult<(Vec<StreamId>, NextIdToClaim)>
-    where
-        G: ToRedisArgs,
-        N: ToRedisArgs,
-        ID: ToRedisArgs,
-    {
-        claim_pending_messages(
-            self,
-            key,
-            group,
-            consumer,
-            min_idle_time,
-            next_id_to_claim,
-            count,
-        )
+    #[test]
+    fn test_get_consumer_group_info_error() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("GROUPS").arg(key),
+                Err(RedisError::from((ErrorKind::ResponseError, "no such key"))),
+            )]);
+
+        // Get the consumer group information:
+        let result: RedsumerResult<StreamInfoGroup> = conn.get_consumer_group_info(key, group);
+
+        // Verify the result:
+        assert!(result.is_err());
     }
+}
 
-    fn is_still_mine<G, CN, ID>(
-        &mut self,
-        key: K,
-        group: G,
-        consumer: CN,
-        id: ID,
-    ) -> RedsumerResult<(
-        bool,
-        Option<LastDeliveredMilliseconds>,
-        Option<TotalTimesDelivered>,
-    )>
-    where
-        G: ToRedisArgs,
-        CN: ToRedisArgs,
-        ID: ToRedisArgs,
-    {
-        is_still_mine(self, key, group, consumer, id)
+#[cfg(test)]
+mod test_get_pending_messages_summary {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_get_pending_messages_summary_with_data() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING").arg(key).arg(group),
+                Ok(Value::Array(vec![
+                    Value::Int(3),
+                    Value::BulkString(b"1-0".to_vec()),
+                    Value::BulkString(b"5-0".to_vec()),
+                    Value::Array(vec![
+                        Value::Array(vec![
+                            Value::BulkString(b"consumer-1".to_vec()),
+                            Value::BulkString(b"2".to_vec()),
+                        ]),
+                        Value::Array(vec![
+                            Value::BulkString(b"consumer-2".to_vec()),
+                            Value::BulkString(b"1".to_vec()),
+                        ]),
+                    ]),
+                ])),
+            )]);
+
+        // Get the pending messages summary:
+        let result: RedsumerResult<StreamPendingReply> =
+            conn.get_pending_messages_summary(key, group);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let reply: StreamPendingReply = result.unwrap();
+        assert_eq!(reply.count(), 3);
+
+        match reply {
+            StreamPendingReply::Data(data) => {
+                assert_eq!(data.consumers.len(), 2);
+                assert_eq!(data.consumers[0].name, "consumer-1");
+                assert_eq!(data.consumers[0].pending, 2);
+            }
+            StreamPendingReply::Empty => panic!("Expected pending messages"),
+        }
     }
 
-    fn ack<G, ID>(&mut self, key: K, group: G, id: ID) -> RedsumerResult<bool>
-    where
-        G: ToRedisArgs,
-        ID: ToRedisArgs,
-    {
-        ack(self, key, group, id)
+    #[test]
+    fn test_get_pending_messages_summary_empty() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING").arg(key).arg(group),
+                Ok(Value::Array(vec![
+                    Value::Int(0),
+                    Value::Nil,
+                    Value::Nil,
+                    Value::Nil,
+                ])),
+            )]);
+
+        // Get the pending messages summary:
+        let result: RedsumerResult<StreamPendingReply> =
+            conn.get_pending_messages_summary(key, group);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_get_pending_messages_summary_error() {
+        // Define the key and group:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING").arg(key).arg(group),
+                Err(RedisError::from((ErrorKind::ResponseError, "NOGROUP"))),
+            )]);
+
+        // Get the pending messages summary:
+        let result: RedsumerResult<StreamPendingReply> =
+            conn.get_pending_messages_summary(key, group);
+
+        // Verify the result:
+        assert!(result.is_err());
     }
 }
 
 #[cfg(test)]
-mod test_create_consumer_group {
+mod test_create_consumer_group_and_stream {
     use redis::{cmd, ErrorKind, RedisError};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
 
     #[test]
-    fn test_create_non_existent_consumer_group() {
+    fn test_create_consumer_group_and_stream_ok() {
         // Define the key, group, and since_id:
         let key: &str = "my-key";
         let group: &str = "my-group";
@@ -572,12 +3737,14 @@ mod test_create_consumer_group {
                     .arg("CREATE")
                     .arg(key)
                     .arg(group)
-                    .arg(since_id),
+                    .arg(since_id)
+                    .arg("MKSTREAM"),
                 Ok("Ok"),
             )]);
 
         // Create the consumer group:
-        let result: RedsumerResult<bool> = conn.create_consumer_group(key, group, since_id);
+        let result: RedsumerResult<bool> =
+            conn.create_consumer_group_and_stream(key, group, since_id);
 
         // Verify the result:
         assert!(result.is_ok());
@@ -585,238 +3752,658 @@ mod test_create_consumer_group {
     }
 
     #[test]
-    fn test_create_existent_consumer_group() {
-        // Define the key, group, and since_id:
+    fn test_create_existent_consumer_group_and_stream() {
+        // Define the key, group, and since_id:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let since_id: &str = "0";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg(key)
+                    .arg(group)
+                    .arg(since_id)
+                    .arg("MKSTREAM"),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "BUSYGROUP Consumer Group name already exists",
+                ))),
+            )]);
+
+        // Create the consumer group:
+        let result: RedsumerResult<bool> =
+            conn.create_consumer_group_and_stream(key, group, since_id);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(!result.unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test_wait_for_stream {
+    use redis::cmd;
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_wait_for_stream_ok() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection where the stream is created after the first poll:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, i64>(cmd("EXISTS").arg(key), Ok(0)),
+            MockCmd::new::<_, i64>(cmd("EXISTS").arg(key), Ok(1)),
+        ]);
+
+        // Wait for the stream:
+        let result: RedsumerResult<()> = conn.wait_for_stream(key, Duration::from_secs(1));
+
+        // Verify the result:
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_wait_for_stream_timeout() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection where the stream does not exist:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, i64>(cmd("EXISTS").arg(key), Ok(0)),
+            MockCmd::new::<_, i64>(cmd("EXISTS").arg(key), Ok(0)),
+        ]);
+
+        // Wait for the stream:
+        let result: RedsumerResult<()> = conn.wait_for_stream(key, Duration::from_millis(50));
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_heartbeats {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `XINFO CONSUMERS` reply item for a consumer.
+    fn consumer_info(name: &str, pending: i64, idle: i64) -> Value {
+        Value::Array(vec![
+            Value::BulkString(b"name".to_vec()),
+            Value::BulkString(name.as_bytes().to_vec()),
+            Value::BulkString(b"pending".to_vec()),
+            Value::Int(pending),
+            Value::BulkString(b"idle".to_vec()),
+            Value::Int(idle),
+        ])
+    }
+
+    #[test]
+    fn test_send_heartbeat_ok() {
+        // Define the key, group and consumer:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+                cmd("PSETEX")
+                    .arg("redsumer:heartbeat:my-key:my-group:my-consumer")
+                    .arg(30_000)
+                    .arg(1),
+                Ok("OK"),
+            )]);
+
+        // Send the heartbeat:
+        let result: RedsumerResult<()> =
+            conn.send_heartbeat(key, group, consumer, Duration::from_secs(30));
+
+        // Verify the result:
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_get_dead_consumers_ok() {
+        // Define the key and group:
         let key: &str = "my-key";
         let group: &str = "my-group";
-        let since_id: &str = "0";
 
         // Create a mock connection:
-        let mut conn: MockRedisConnection =
-            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
-                cmd("XGROUP")
-                    .arg("CREATE")
-                    .arg(key)
-                    .arg(group)
-                    .arg(since_id),
-                Err(RedisError::from((
-                    ErrorKind::ResponseError,
-                    "BUSYGROUP Consumer Group name already exists",
-                ))),
-            )]);
-
-        // Create the consumer group:
-        let result: RedsumerResult<bool> = conn.create_consumer_group(key, group, since_id);
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("CONSUMERS").arg(key).arg(group),
+                Ok(Value::Array(vec![
+                    consumer_info("alive", 1, 100),
+                    consumer_info("dead", 2, 90_000),
+                ])),
+            ),
+            MockCmd::new::<_, i64>(
+                cmd("EXISTS").arg("redsumer:heartbeat:my-key:my-group:alive"),
+                Ok(1),
+            ),
+            MockCmd::new::<_, i64>(
+                cmd("EXISTS").arg("redsumer:heartbeat:my-key:my-group:dead"),
+                Ok(0),
+            ),
+        ]);
+
+        // Get the dead consumers:
+        let result: RedsumerResult<Vec<String>> = conn.get_dead_consumers(key, group);
 
         // Verify the result:
         assert!(result.is_ok());
-        assert!(!result.unwrap())
+        assert_eq!(result.unwrap(), vec!["dead".to_string()]);
+    }
+}
+
+#[cfg(test)]
+mod test_remove_idle_consumers {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `XINFO CONSUMERS` reply item for a consumer.
+    fn consumer_info(name: &str, pending: i64, idle: i64) -> Value {
+        Value::Array(vec![
+            Value::BulkString(b"name".to_vec()),
+            Value::BulkString(name.as_bytes().to_vec()),
+            Value::BulkString(b"pending".to_vec()),
+            Value::Int(pending),
+            Value::BulkString(b"idle".to_vec()),
+            Value::Int(idle),
+        ])
     }
 
     #[test]
-    fn test_create_consumer_group_error() {
-        // Define the key, group, and since_id:
+    fn test_remove_idle_consumers_ok() {
+        // Define the key and group:
         let key: &str = "my-key";
         let group: &str = "my-group";
-        let since_id: &str = "0";
 
         // Create a mock connection:
-        let mut conn: MockRedisConnection =
-            MockRedisConnection::new(vec![MockCmd::new::<_, &str>(
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("CONSUMERS").arg(key).arg(group),
+                Ok(Value::Array(vec![
+                    consumer_info("active", 0, 100),
+                    consumer_info("idle-with-pending", 3, 90_000),
+                    consumer_info("idle", 0, 90_000),
+                ])),
+            ),
+            MockCmd::new::<_, i64>(
                 cmd("XGROUP")
-                    .arg("CREATE")
+                    .arg("DELCONSUMER")
                     .arg(key)
                     .arg(group)
-                    .arg(since_id),
-                Err(RedisError::from((ErrorKind::ResponseError, "XGROUP Error"))),
-            )]);
+                    .arg("idle"),
+                Ok(0),
+            ),
+        ]);
 
-        // Create the consumer group:
-        let result: RedsumerResult<bool> = conn.create_consumer_group(key, group, since_id);
+        // Remove the idle consumers:
+        let result: RedsumerResult<Vec<String>> =
+            conn.remove_idle_consumers(key, group, Duration::from_secs(60));
 
         // Verify the result:
-        assert!(result.is_err());
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), vec!["idle".to_string()]);
     }
 }
 
 #[cfg(test)]
-mod test_verify_if_stream_exists {
-    use redis::{cmd, ErrorKind, RedisError};
+mod test_get_first_unacknowledged_id {
+    use redis::{cmd, Value};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
 
+    /// Build a `XINFO GROUPS` reply item for a group.
+    fn group_info(name: &str, pending: i64, last_delivered_id: &str) -> Value {
+        Value::Array(vec![
+            Value::BulkString(b"name".to_vec()),
+            Value::BulkString(name.as_bytes().to_vec()),
+            Value::BulkString(b"consumers".to_vec()),
+            Value::Int(1),
+            Value::BulkString(b"pending".to_vec()),
+            Value::Int(pending),
+            Value::BulkString(b"last-delivered-id".to_vec()),
+            Value::BulkString(last_delivered_id.as_bytes().to_vec()),
+        ])
+    }
+
     #[test]
-    fn test_verify_if_stream_exists() {
+    fn test_get_first_unacknowledged_id_ok() {
         // Define the key:
         let key: &str = "my-key";
 
         // Create a mock connection:
-        let mut conn: MockRedisConnection =
-            MockRedisConnection::new(vec![MockCmd::new::<_, i64>(cmd("EXISTS").arg(key), Ok(1))]);
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("GROUPS").arg(key),
+                Ok(Value::Array(vec![
+                    group_info("group-1", 0, "10-0"),
+                    group_info("group-2", 2, "12-0"),
+                ])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XPENDING").arg(key).arg("group-1"),
+                Ok(Value::Array(vec![
+                    Value::Int(0),
+                    Value::Nil,
+                    Value::Nil,
+                    Value::Nil,
+                ])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XPENDING").arg(key).arg("group-2"),
+                Ok(Value::Array(vec![
+                    Value::Int(2),
+                    Value::BulkString(b"7-0".to_vec()),
+                    Value::BulkString(b"12-0".to_vec()),
+                    Value::Array(vec![Value::Array(vec![
+                        Value::BulkString(b"consumer-1".to_vec()),
+                        Value::BulkString(b"2".to_vec()),
+                    ])]),
+                ])),
+            ),
+        ]);
 
-        // Verify if the stream exists:
-        let result: RedsumerResult<()> = conn.verify_if_stream_exists(key);
+        // Get the first unacknowledged message ID:
+        let result: RedsumerResult<Option<Id>> = conn.get_first_unacknowledged_id(key);
 
         // Verify the result:
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Some(Id::new(7, 0)));
     }
 
     #[test]
-    fn test_verify_if_stream_does_not_exist() {
+    fn test_get_first_unacknowledged_id_without_groups() {
         // Define the key:
         let key: &str = "my-key";
 
         // Create a mock connection:
         let mut conn: MockRedisConnection =
-            MockRedisConnection::new(vec![MockCmd::new::<_, i64>(cmd("EXISTS").arg(key), Ok(0))]);
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("GROUPS").arg(key),
+                Ok(Value::Array(vec![])),
+            )]);
 
-        // Verify if the stream exists:
-        let result: RedsumerResult<()> = conn.verify_if_stream_exists(key);
+        // Get the first unacknowledged message ID:
+        let result: RedsumerResult<Option<Id>> = conn.get_first_unacknowledged_id(key);
 
         // Verify the result:
-        assert!(result.is_err());
+        assert_eq!(result.unwrap(), None);
+    }
+}
+
+#[cfg(test)]
+mod test_unwrap_stream_read_reply {
+    use redis::streams::StreamKey;
+
+    use super::*;
+
+    /// Build a stream of a `XREAD` reply with messages with the given IDs.
+    fn stream_key(key: &str, ids: &[&str]) -> StreamKey {
+        StreamKey {
+            key: key.to_string(),
+            ids: ids
+                .iter()
+                .map(|id| StreamId {
+                    id: id.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+        }
     }
 
     #[test]
-    fn test_verify_if_stream_exists_error() {
-        // Define the key:
+    fn test_unwrap_by_key() {
+        // Define the reply:
+        let reply: StreamReadReply = StreamReadReply {
+            keys: vec![
+                stream_key("my-key", &["1-0", "2-0"]),
+                stream_key("other-key", &["3-0"]),
+            ],
+        };
+
+        // Unwrap the reply:
+        let ids: Vec<String> = reply
+            .unwrap_by_key(&"my-key")
+            .into_iter()
+            .map(|message| message.id)
+            .collect();
+
+        // Verify the result:
+        assert_eq!(ids, vec!["1-0", "2-0"]);
+    }
+
+    #[test]
+    fn test_unwrap_by_key_not_found() {
+        // Define the reply:
+        let reply: StreamReadReply = StreamReadReply {
+            keys: vec![stream_key("other-key", &["3-0"])],
+        };
+
+        // Verify the result:
+        assert!(reply.unwrap_by_key(&"my-key").is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_read_pending_and_claim_messages {
+    use redis::{cmd, pipe, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_read_pending_and_claim_messages_ok() {
+        // Define the key, group and consumer:
         let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
 
         // Create a mock connection:
-        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
-            cmd("EXISTS").arg(key),
-            Err(RedisError::from((ErrorKind::ResponseError, "EXISTS Error"))),
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::with_values(
+            pipe()
+                .add_command(
+                    cmd("XREADGROUP")
+                        .arg("GROUP")
+                        .arg(group)
+                        .arg(consumer)
+                        .arg("COUNT")
+                        .arg(2)
+                        .arg("STREAMS")
+                        .arg(key)
+                        .arg("0-0")
+                        .to_owned(),
+                )
+                .add_command(
+                    cmd("XAUTOCLAIM")
+                        .arg(key)
+                        .arg(group)
+                        .arg(consumer)
+                        .arg(1000)
+                        .arg("0-0")
+                        .arg("COUNT")
+                        .arg(3)
+                        .to_owned(),
+                ),
+            Ok(vec![
+                Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(key.as_bytes().to_vec()),
+                    Value::Array(vec![Value::Array(vec![
+                        Value::BulkString(b"1-0".to_vec()),
+                        Value::Array(vec![
+                            Value::BulkString(b"field".to_vec()),
+                            Value::BulkString(b"value".to_vec()),
+                        ]),
+                    ])]),
+                ])]),
+                Value::Array(vec![
+                    Value::BulkString(b"5-0".to_vec()),
+                    Value::Array(vec![Value::Array(vec![
+                        Value::BulkString(b"2-0".to_vec()),
+                        Value::Array(vec![
+                            Value::BulkString(b"field".to_vec()),
+                            Value::BulkString(b"value".to_vec()),
+                        ]),
+                    ])]),
+                    Value::Array(vec![]),
+                ]),
+            ]),
         )]);
 
-        // Verify if the stream exists:
-        let result: RedsumerResult<()> = conn.verify_if_stream_exists(key);
+        // Read pending and claim messages:
+        let result: RedsumerResult<PendingAndClaimedMessages> =
+            conn.read_pending_and_claim_messages(&key, &group, &consumer, "0-0", 2, 1000, "0-0", 3);
 
         // Verify the result:
-        assert!(result.is_err());
+        let ((pending, latest_pending_message_id), (claimed, next_id_to_claim)) = result.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(latest_pending_message_id, "1-0");
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, "2-0");
+        assert_eq!(next_id_to_claim, "5-0");
     }
 }
 
 #[cfg(test)]
-mod test_read_new_messages {
-    use redis::{cmd, Value};
+mod test_renew_messages {
+    use redis::{cmd, ErrorKind, RedisError, Value};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
 
     #[test]
-    fn test_read_new_messages_with_zero_count() {
-        // Define the key, group, consumer, count, and block:
+    fn test_renew_messages_ok() {
+        // Define the key, group, consumer and ids:
         let key: &str = "my-key";
         let group: &str = "my-group";
         let consumer: &str = "my-consumer";
-        let count: usize = 0;
-        let block: usize = 1;
+        let ids: [&str; 2] = ["1-0", "2-0"];
 
         // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg(key)
+                    .arg(group)
+                    .arg(consumer)
+                    .arg(1000)
+                    .arg(&ids)
+                    .arg("JUSTID"),
+                Ok(Value::Array(vec![Value::BulkString(b"1-0".to_vec())])),
+            )]);
+
+        // Renew the messages:
+        let result: RedsumerResult<Vec<String>> =
+            conn.renew_messages(key, group, consumer, 1000, &ids);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), vec!["1-0".to_string()]);
+    }
+
+    #[test]
+    fn test_renew_messages_without_ids() {
+        // Create a mock connection without commands:
         let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
 
-        // Read new messages:
-        let result: RedisResult<Vec<StreamId>> =
-            conn.read_new_messages(&key, &group, &consumer, count, block);
+        // Renew no messages:
+        let result: RedsumerResult<Vec<String>> = conn.renew_messages(
+            "my-key",
+            "my-group",
+            "my-consumer",
+            1000,
+            &Vec::<String>::new(),
+        );
 
         // Verify the result:
-        assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
 
     #[test]
-    fn test_read_new_messages_ok() {
-        // Define the key, group, and consumer:
-        let key: &str = "my-key";
-        let group: &str = "my-group";
-        let consumer: &str = "my-consumer";
-        let count: usize = 2;
-        let block: usize = 1;
-
+    fn test_renew_messages_error() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XREADGROUP")
-                    .arg(
-                        &StreamReadOptions::default()
-                            .group(group, consumer)
-                            .count(count)
-                            .block(block),
-                    )
-                    .arg("STREAMS")
-                    .arg(&[key])
-                    .arg(&[">"]),
-                Ok(Value::Array(vec![Value::Map(vec![
-                    (
-                        Value::SimpleString("my-key".to_string()),
-                        Value::Array(vec![Value::Map(vec![(
-                            Value::SimpleString("1-0".to_string()),
-                            Value::Array(vec![
-                                Value::SimpleString("code".to_string()),
-                                Value::Int(1),
-                            ]),
-                        )])]),
-                    ),
-                    (
-                        Value::SimpleString("fake-key".to_string()),
-                        Value::Array(vec![Value::Map(vec![(
-                            Value::SimpleString("666-0".to_string()),
-                            Value::Array(vec![
-                                Value::SimpleString("code".to_string()),
-                                Value::Int(666),
-                            ]),
-                        )])]),
-                    ),
-                ])])),
+                cmd("XCLAIM")
+                    .arg("my-key")
+                    .arg("my-group")
+                    .arg("my-consumer")
+                    .arg(1000)
+                    .arg(&["1-0"])
+                    .arg("JUSTID"),
+                Err(RedisError::from((ErrorKind::ResponseError, "XCLAIM Error"))),
             )]);
 
-        // Consume messages:
-        let result: RedsumerResult<Vec<StreamId>> =
-            conn.read_new_messages(&key, &group, &consumer, count, block);
+        // Renew the messages:
+        let result: RedsumerResult<Vec<String>> =
+            conn.renew_messages("my-key", "my-group", "my-consumer", 1000, &["1-0"]);
 
         // Verify the result:
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
 
-        // Verify the messages:
-        let messages: Vec<StreamId> = result.unwrap();
-        assert!(messages.len().eq(&1));
+#[cfg(test)]
+mod test_release_messages {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
 
-        assert!(messages[0].id.eq("1-0"));
-        assert!(messages[0].map.get("code").unwrap().eq(&Value::Int(1)));
+    use super::*;
+
+    #[test]
+    fn test_release_messages_ok() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg("my-key")
+                    .arg("my-group")
+                    .arg("my-consumer")
+                    .arg(0)
+                    .arg(&["1-0"])
+                    .arg("IDLE")
+                    .arg(60000)
+                    .arg("JUSTID"),
+                Ok(Value::Array(vec![Value::BulkString(b"1-0".to_vec())])),
+            )]);
+
+        // Release the messages:
+        let result: RedsumerResult<Vec<String>> =
+            conn.release_messages("my-key", "my-group", "my-consumer", 60000, &["1-0"]);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), vec!["1-0".to_string()]);
     }
 
     #[test]
-    fn test_read_new_messages_error() {
-        // Define the key, group, and consumer:
-        let key: &str = "my-key";
-        let group: &str = "my-group";
-        let consumer: &str = "my-consumer";
-        let count: usize = 2;
-        let block: usize = 1;
+    fn test_release_messages_error() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg("my-key")
+                    .arg("my-group")
+                    .arg("my-consumer")
+                    .arg(0)
+                    .arg(&["1-0"])
+                    .arg("IDLE")
+                    .arg(60000)
+                    .arg("JUSTID"),
+                Err(RedisError::from((ErrorKind::ResponseError, "XCLAIM Error"))),
+            )]);
+
+        // Release the messages:
+        let result: RedsumerResult<Vec<String>> =
+            conn.release_messages("my-key", "my-group", "my-consumer", 60000, &["1-0"]);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
 
+#[cfg(test)]
+mod test_read_new_messages_from_streams {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build the `XREADGROUP` command of two streams.
+    fn xreadgroup() -> Cmd {
+        cmd("XREADGROUP")
+            .arg("GROUP")
+            .arg("group")
+            .arg("consumer")
+            .arg("BLOCK")
+            .arg(10)
+            .arg("COUNT")
+            .arg(5)
+            .arg("STREAMS")
+            .arg("stream-1")
+            .arg("stream-2")
+            .arg(">")
+            .arg(">")
+            .to_owned()
+    }
+
+    #[test]
+    fn test_read_new_messages_from_streams_ok() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XREADGROUP")
-                    .arg(
-                        &StreamReadOptions::default()
-                            .group(group, consumer)
-                            .count(count)
-                            .block(block),
-                    )
-                    .arg("STREAMS")
-                    .arg(&[key])
-                    .arg(&[">"]),
-                Err(RedisError::from((
-                    ErrorKind::ResponseError,
-                    "XREADGROUP Error",
-                ))),
+                xreadgroup(),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"stream-2".to_vec()),
+                    Value::Array(vec![Value::Array(vec![
+                        Value::BulkString(b"1-0".to_vec()),
+                        Value::Array(vec![
+                            Value::BulkString(b"field".to_vec()),
+                            Value::BulkString(b"value".to_vec()),
+                        ]),
+                    ])]),
+                ])])),
+            )]);
+
+        // Read new messages from the streams:
+        let result: RedsumerResult<Vec<(String, Vec<StreamId>)>> = conn
+            .read_new_messages_from_streams(
+                &["stream-1", "stream-2"],
+                &"group",
+                &"consumer",
+                5,
+                10,
+            );
+
+        // Verify the result:
+        let messages: Vec<(String, Vec<StreamId>)> = result.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, "stream-2");
+        assert_eq!(messages[0].1[0].id, "1-0");
+    }
+
+    #[test]
+    fn test_read_new_messages_from_streams_without_keys() {
+        // Create a mock connection without commands:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Read new messages without streams:
+        let result: RedsumerResult<Vec<(String, Vec<StreamId>)>> =
+            conn.read_new_messages_from_streams(&Vec::<&str>::new(), &"group", &"consumer", 5, 10);
+
+        // Verify the result:
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_new_messages_from_streams_error() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                xreadgroup(),
+                Err(RedisError::from((ErrorKind::ResponseError, "NOGROUP"))),
             )]);
 
-        // Consume messages:
-        let result: RedsumerResult<Vec<StreamId>> =
-            conn.read_new_messages(&key, &group, &consumer, count, block);
+        // Read new messages from the streams:
+        let result: RedsumerResult<Vec<(String, Vec<StreamId>)>> = conn
+            .read_new_messages_from_streams(
+                &["stream-1", "stream-2"],
+                &"group",
+                &"consumer",
+                5,
+                10,
+            );
 
         // Verify the result:
         assert!(result.is_err());
@@ -824,544 +4411,669 @@ mod test_read_new_messages {
 }
 
 #[cfg(test)]
-mod test_read_pending_messages {
-    use redis::{cmd, Value};
+mod test_read_pending_messages_from_streams {
+    use redis::{cmd, ErrorKind, RedisError, Value};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
 
-    #[test]
-    fn test_read_pending_messages_with_zero_count() {
-        // Define the key, group, consumer, latest_pending_message_id, and count:
-        let key: &str = "my-key";
-        let group: &str = "my-group";
-        let consumer: &str = "my-consumer";
-        let latest_pending_message_id: &str = "0-0";
-        let count: usize = 0;
+    /// Build the `XREADGROUP` command of the pending messages of two streams.
+    fn xreadgroup() -> Cmd {
+        cmd("XREADGROUP")
+            .arg("GROUP")
+            .arg("group")
+            .arg("consumer")
+            .arg("COUNT")
+            .arg(5)
+            .arg("STREAMS")
+            .arg("stream-1")
+            .arg("stream-2")
+            .arg("0-0")
+            .arg("3-0")
+            .to_owned()
+    }
 
+    #[test]
+    fn test_read_pending_messages_from_streams_ok() {
         // Create a mock connection:
-        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
-
-        // Read pending messages:
-        let result: RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)> =
-            conn.read_pending_messages(&key, &group, &consumer, latest_pending_message_id, count);
-
-        // Verify the result:
-        assert!(result.is_ok());
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                xreadgroup(),
+                Ok(Value::Array(vec![
+                    Value::Array(vec![
+                        Value::BulkString(b"stream-1".to_vec()),
+                        Value::Array(vec![Value::Array(vec![
+                            Value::BulkString(b"1-0".to_vec()),
+                            Value::Array(vec![
+                                Value::BulkString(b"field".to_vec()),
+                                Value::BulkString(b"value".to_vec()),
+                            ]),
+                        ])]),
+                    ]),
+                    Value::Array(vec![
+                        Value::BulkString(b"stream-2".to_vec()),
+                        Value::Array(vec![]),
+                    ]),
+                ])),
+            )]);
 
-        let (messages, next_id_to_claim): (Vec<StreamId>, LatestPendingMessageId) = result.unwrap();
-        assert!(messages.is_empty());
-        assert!(next_id_to_claim.eq(BEGINNING_OF_TIME_ID));
+        // Read the pending messages from the streams:
+        let result: RedsumerResult<Vec<(String, Vec<StreamId>)>> = conn
+            .read_pending_messages_from_streams(
+                &["stream-1", "stream-2"],
+                &["0-0".to_string(), "3-0".to_string()],
+                &"group",
+                &"consumer",
+                5,
+            );
+
+        // Verify the streams without pending messages are kept:
+        let messages: Vec<(String, Vec<StreamId>)> = result.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0, "stream-1");
+        assert_eq!(messages[0].1[0].id, "1-0");
+        assert_eq!(messages[1].0, "stream-2");
+        assert!(messages[1].1.is_empty());
     }
 
     #[test]
-    fn test_read_pending_messages_empty() {
-        // Define the key, group, consumer, latest_pending_message_id, and count:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let latest_pending_message_id = "0-0";
-        let count = 2;
-
+    fn test_read_pending_messages_from_streams_error() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XREADGROUP")
-                    .arg(
-                        &StreamReadOptions::default()
-                            .group(group, consumer)
-                            .count(count),
-                    )
-                    .arg("STREAMS")
-                    .arg(&[key])
-                    .arg(&[latest_pending_message_id]),
-                Ok(Value::Array(vec![Value::Map(vec![])])),
+                xreadgroup(),
+                Err(RedisError::from((ErrorKind::ResponseError, "NOGROUP"))),
             )]);
 
-        // Read pending messages:
-        let result: RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)> =
-            conn.read_pending_messages(&key, &group, &consumer, latest_pending_message_id, count);
+        // Read the pending messages from the streams:
+        let result: RedsumerResult<Vec<(String, Vec<StreamId>)>> = conn
+            .read_pending_messages_from_streams(
+                &["stream-1", "stream-2"],
+                &["0-0".to_string(), "3-0".to_string()],
+                &"group",
+                &"consumer",
+                5,
+            );
 
         // Verify the result:
-        assert!(result.is_ok());
+        assert!(result.is_err());
+    }
+}
 
-        let (messages, next_id_to_claim): (Vec<StreamId>, LatestPendingMessageId) = result.unwrap();
-        assert!(messages.len().eq(&0));
-        assert!(next_id_to_claim.eq(BEGINNING_OF_TIME_ID));
+#[cfg(test)]
+mod test_get_consumer_pending_ids {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `XPENDING` command of *consumer* with the given *min_idle_time*.
+    fn xpending(consumer: &str, min_idle_time: usize) -> Cmd {
+        cmd("XPENDING")
+            .arg("my-key")
+            .arg("my-group")
+            .arg("IDLE")
+            .arg(min_idle_time)
+            .arg("-")
+            .arg("+")
+            .arg(10)
+            .arg(consumer)
+            .to_owned()
     }
 
     #[test]
-    fn test_read_pending_messages_ok() {
-        // Define the key, group, consumer, latest_pending_message_id, and count:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let latest_pending_message_id = "0-0";
-        let count = 2;
-
+    fn test_get_consumer_pending_ids_ok() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XREADGROUP")
-                    .arg(
-                        &StreamReadOptions::default()
-                            .group(group, consumer)
-                            .count(count),
-                    )
-                    .arg("STREAMS")
-                    .arg(&[key])
-                    .arg(&[latest_pending_message_id]),
-                Ok(Value::Array(vec![Value::Map(vec![
-                    (
-                        Value::SimpleString("my-key".to_string()),
-                        Value::Array(vec![Value::Map(vec![(
-                            Value::SimpleString("1-0".to_string()),
-                            Value::Array(vec![
-                                Value::SimpleString("code".to_string()),
-                                Value::Int(1),
-                            ]),
-                        )])]),
-                    ),
-                    (
-                        Value::SimpleString("fake-key".to_string()),
-                        Value::Array(vec![Value::Map(vec![(
-                            Value::SimpleString("666-0".to_string()),
-                            Value::Array(vec![
-                                Value::SimpleString("code".to_string()),
-                                Value::Int(666),
-                            ]),
-                        )])]),
-                    ),
-                ])])),
+                xpending("dead-consumer", 60000),
+                Ok(Value::Array(vec![
+                    Value::Array(vec![
+                        Value::BulkString(b"1-0".to_vec()),
+                        Value::BulkString(b"dead-consumer".to_vec()),
+                        Value::Int(90000),
+                        Value::Int(1),
+                    ]),
+                    Value::Array(vec![
+                        Value::BulkString(b"2-0".to_vec()),
+                        Value::BulkString(b"dead-consumer".to_vec()),
+                        Value::Int(80000),
+                        Value::Int(2),
+                    ]),
+                ])),
             )]);
 
-        // Read pending messages:
-        let result: RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)> =
-            conn.read_pending_messages(&key, &group, &consumer, latest_pending_message_id, count);
+        // Get the pending messages of the consumer:
+        let result: RedsumerResult<Vec<String>> = conn.get_consumer_pending_ids(
+            "my-key",
+            "my-group",
+            "dead-consumer",
+            60000,
+            "-",
+            "+",
+            10,
+        );
 
         // Verify the result:
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), vec!["1-0", "2-0"]);
+    }
 
-        let (messages, next_id_to_claim): (Vec<StreamId>, LatestPendingMessageId) = result.unwrap();
-        assert!(messages.len().eq(&1));
+    #[test]
+    fn test_get_consumer_pending_ids_with_zero_count() {
+        // Create a mock connection without commands:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
 
-        assert!(messages[0].id.eq("1-0"));
-        assert!(messages[0].map.get("code").unwrap().eq(&Value::Int(1)));
+        // Get the pending messages of the consumer:
+        let result: RedsumerResult<Vec<String>> =
+            conn.get_consumer_pending_ids("my-key", "my-group", "dead-consumer", 0, "-", "+", 0);
 
-        assert!(next_id_to_claim.eq("1-0"));
+        // Verify the result:
+        assert!(result.unwrap().is_empty());
     }
 
     #[test]
-    fn test_read_pending_messages_error() {
-        // Define the key, group, consumer, latest_pending_message_id, and count:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let latest_pending_message_id = "0-0";
-        let count = 2;
-
+    fn test_get_consumer_pending_ids_error() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XREADGROUP")
-                    .arg(
-                        &StreamReadOptions::default()
-                            .group(group, consumer)
-                            .count(count),
-                    )
-                    .arg("STREAMS")
-                    .arg(&[key])
-                    .arg(&[latest_pending_message_id]),
+                xpending("dead-consumer", 0),
                 Err(RedisError::from((
                     ErrorKind::ResponseError,
-                    "XREADGROUP Error",
+                    "XPENDING Error",
                 ))),
             )]);
 
-        // Read pending messages:
-        let result: RedsumerResult<(Vec<StreamId>, LatestPendingMessageId)> =
-            conn.read_pending_messages(&key, &group, &consumer, latest_pending_message_id, count);
+        // Get the pending messages of the consumer:
+        let result: RedsumerResult<Vec<String>> =
+            conn.get_consumer_pending_ids("my-key", "my-group", "dead-consumer", 0, "-", "+", 10);
 
         // Verify the result:
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
     }
 }
 
 #[cfg(test)]
-mod test_claim_pending_messages {
-    use redis::{cmd, Value};
+mod test_claim_messages {
+    use redis::{cmd, ErrorKind, RedisError, Value};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
 
     #[test]
-    fn test_claim_pending_messages_with_zero_count() {
-        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let min_idle_time = 1000;
-        let next_id_to_claim = "0-0";
-        let count = 0;
+    fn test_claim_messages_ok() {
+        // Define the key, group, consumer and ids:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let ids: [&str; 2] = ["1-0", "2-0"];
 
         // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg(key)
+                    .arg(group)
+                    .arg(consumer)
+                    .arg(0)
+                    .arg(&ids),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"1-0".to_vec()),
+                    Value::Array(vec![
+                        Value::BulkString(b"field".to_vec()),
+                        Value::BulkString(b"value".to_vec()),
+                    ]),
+                ])])),
+            )]);
+
+        // Claim the messages:
+        let result: RedsumerResult<Vec<StreamId>> =
+            conn.claim_messages(key, group, consumer, 0, &ids);
+
+        // Verify the result:
+        let messages: Vec<StreamId> = result.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, "1-0");
+    }
+
+    #[test]
+    fn test_claim_messages_without_ids() {
+        // Create a mock connection without commands:
         let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
 
-        // Claim pending messages:
-        let result: RedisResult<(Vec<StreamId>, NextIdToClaim)> = conn.claim_pending_messages(
-            &key,
-            &group,
-            &consumer,
-            min_idle_time,
-            next_id_to_claim,
-            count,
+        // Claim no messages:
+        let result: RedsumerResult<Vec<StreamId>> = conn.claim_messages(
+            "my-key",
+            "my-group",
+            "my-consumer",
+            0,
+            &Vec::<String>::new(),
         );
 
         // Verify the result:
-        assert!(result.is_ok());
-
-        let (messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) = result.unwrap();
-        assert!(messages.is_empty());
-        assert!(next_id_to_claim.eq(BEGINNING_OF_TIME_ID));
+        assert!(result.unwrap().is_empty());
     }
 
     #[test]
-    fn test_claim_pending_messages_empty() {
-        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let min_idle_time = 1000;
-        let next_id_to_claim = "0-0";
-        let count = 2;
-
+    fn test_claim_messages_error() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XAUTOCLAIM")
-                    .arg(key)
-                    .arg(group)
-                    .arg(consumer)
-                    .arg(min_idle_time)
-                    .arg(next_id_to_claim)
-                    .arg(&StreamAutoClaimOptions::default().count(count)),
-                Ok(Value::Array(vec![
-                    Value::SimpleString("0-0".to_string()),
-                    Value::Array(vec![]),
-                    Value::Array(vec![]),
-                ])),
+                cmd("XCLAIM")
+                    .arg("my-key")
+                    .arg("my-group")
+                    .arg("my-consumer")
+                    .arg(0)
+                    .arg(&["1-0"]),
+                Err(RedisError::from((ErrorKind::ResponseError, "XCLAIM Error"))),
             )]);
 
-        // Claim pending messages:
-        let result: RedisResult<(Vec<StreamId>, NextIdToClaim)> = conn.claim_pending_messages(
-            &key,
-            &group,
-            &consumer,
-            min_idle_time,
-            next_id_to_claim,
-            count,
-        );
+        // Claim the messages:
+        let result: RedsumerResult<Vec<StreamId>> =
+            conn.claim_messages("my-key", "my-group", "my-consumer", 0, &["1-0"]);
 
         // Verify the result:
-        assert!(result.is_ok());
-
-        let (messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) = result.unwrap();
-        assert!(messages.len().eq(&0));
-        assert!(next_id_to_claim.eq(BEGINNING_OF_TIME_ID));
+        assert!(matches!(result.unwrap_err(), RedsumerError::ClaimFailed(_)));
     }
+}
 
-    #[test]
-    fn test_claim_pending_messages_ok() {
-        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let min_idle_time = 1000;
-        let next_id_to_claim = "0-0";
-        let count = 2;
+#[cfg(test)]
+mod test_get_group_pending_ids {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
 
+    #[test]
+    fn test_get_group_pending_ids_ok() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XAUTOCLAIM")
-                    .arg(key)
-                    .arg(group)
-                    .arg(consumer)
-                    .arg(min_idle_time)
-                    .arg(next_id_to_claim)
-                    .arg(&StreamAutoClaimOptions::default().count(count)),
-                Ok(Value::Array(vec![
-                    Value::SimpleString("1-0".to_string()),
-                    Value::Array(vec![Value::Array(vec![
-                        Value::SimpleString("1-0".to_string()),
-                        Value::Array(vec![Value::SimpleString("code".to_string()), Value::Int(1)]),
-                    ])]),
-                    Value::Array(vec![]),
+                cmd("XPENDING")
+                    .arg("my-key")
+                    .arg("my-group")
+                    .arg("-")
+                    .arg("+")
+                    .arg(10),
+                Ok(Value::Array(vec![
+                    Value::Array(vec![
+                        Value::BulkString(b"1-0".to_vec()),
+                        Value::BulkString(b"consumer-1".to_vec()),
+                        Value::Int(1000),
+                        Value::Int(1),
+                    ]),
+                    Value::Array(vec![
+                        Value::BulkString(b"2-0".to_vec()),
+                        Value::BulkString(b"consumer-2".to_vec()),
+                        Value::Int(1000),
+                        Value::Int(1),
+                    ]),
                 ])),
             )]);
 
-        // Claim pending messages:
-        let result: RedisResult<(Vec<StreamId>, NextIdToClaim)> = conn.claim_pending_messages(
-            &key,
-            &group,
-            &consumer,
-            min_idle_time,
-            next_id_to_claim,
-            count,
-        );
+        // Get the pending messages of the group:
+        let result: RedsumerResult<Vec<(String, String)>> =
+            conn.get_group_pending_ids("my-key", "my-group", "-", "+", 10);
 
         // Verify the result:
-        assert!(result.is_ok());
-
-        let (messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) = result.unwrap();
-        assert!(messages.len().eq(&1));
-
-        assert!(messages[0].id.eq("1-0"));
-        assert!(messages[0].map.get("code").unwrap().eq(&Value::Int(1)));
-
-        assert!(next_id_to_claim.eq("1-0"));
+        assert_eq!(
+            result.unwrap(),
+            vec![
+                ("1-0".to_string(), "consumer-1".to_string()),
+                ("2-0".to_string(), "consumer-2".to_string()),
+            ]
+        );
     }
 
     #[test]
-    fn test_claim_pending_messages_error() {
-        // Define the key, group, consumer, min_idle_time, next_id_to_claim, and count:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let min_idle_time = 1000;
-        let next_id_to_claim = "0-0";
-        let count = 2;
-
+    fn test_get_group_pending_ids_error() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XAUTOCLAIM")
-                    .arg(key)
-                    .arg(group)
-                    .arg(consumer)
-                    .arg(min_idle_time)
-                    .arg(next_id_to_claim)
-                    .arg(&StreamAutoClaimOptions::default().count(count)),
+                cmd("XPENDING")
+                    .arg("my-key")
+                    .arg("my-group")
+                    .arg("-")
+                    .arg("+")
+                    .arg(10),
                 Err(RedisError::from((
                     ErrorKind::ResponseError,
-                    "XAUTOCLAIM Error",
+                    "XPENDING Error",
                 ))),
             )]);
 
-        // Claim pending messages:
-        let result: RedisResult<(Vec<StreamId>, NextIdToClaim)> = conn.claim_pending_messages(
-            &key,
-            &group,
-            &consumer,
-            min_idle_time,
-            next_id_to_claim,
-            count,
-        );
+        // Get the pending messages of the group:
+        let result: RedsumerResult<Vec<(String, String)>> =
+            conn.get_group_pending_ids("my-key", "my-group", "-", "+", 10);
 
         // Verify the result:
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
     }
 }
 
 #[cfg(test)]
-mod test_if_is_still_mine {
-    use redis::{cmd, Value};
+mod test_force_claim_messages {
+    use redis::{cmd, ErrorKind, RedisError, Value};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
 
     #[test]
-    fn test_is_still_mine_true() {
-        // Define the key, group, consumer, and id:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let id = "1-0";
+    fn test_force_claim_messages_ok() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg("my-key")
+                    .arg("my-group")
+                    .arg("my-consumer")
+                    .arg(0)
+                    .arg(&["1-0", "2-0"])
+                    .arg("FORCE")
+                    .arg("JUSTID"),
+                Ok(Value::Array(vec![
+                    Value::BulkString(b"1-0".to_vec()),
+                    Value::BulkString(b"2-0".to_vec()),
+                ])),
+            )]);
+
+        // Claim the messages:
+        let result: RedsumerResult<Vec<String>> =
+            conn.force_claim_messages("my-key", "my-group", "my-consumer", &["1-0", "2-0"]);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), vec!["1-0", "2-0"]);
+    }
 
+    #[test]
+    fn test_force_claim_messages_error() {
         // Create a mock connection:
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
-                cmd("XPENDING")
-                    .arg(key)
-                    .arg(group)
-                    .arg(&[id])
-                    .arg(&[id])
-                    .arg(1)
-                    .arg(consumer),
-                Ok(Value::Array(vec![Value::Array(vec![
-                    Value::BulkString(b"1526984818136-0".to_vec()),
-                    Value::BulkString(b"consumer-123".to_vec()),
-                    Value::Int(196415),
-                    Value::Int(5),
-                ])])),
+                cmd("XCLAIM")
+                    .arg("my-key")
+                    .arg("my-group")
+                    .arg("my-consumer")
+                    .arg(0)
+                    .arg(&["1-0"])
+                    .arg("FORCE")
+                    .arg("JUSTID"),
+                Err(RedisError::from((ErrorKind::ResponseError, "XCLAIM Error"))),
             )]);
 
-        // Verify if the message is still in the consumer pending list:
-        let result: RedsumerResult<(
-            bool,
-            Option<LastDeliveredMilliseconds>,
-            Option<TotalTimesDelivered>,
-        )> = conn.is_still_mine(key, group, consumer, id);
+        // Claim the messages:
+        let result: RedsumerResult<Vec<String>> =
+            conn.force_claim_messages("my-key", "my-group", "my-consumer", &["1-0"]);
 
         // Verify the result:
-        assert!(result.is_ok());
+        assert!(matches!(result.unwrap_err(), RedsumerError::ClaimFailed(_)));
+    }
+}
 
-        let reply: (
-            bool,
-            Option<LastDeliveredMilliseconds>,
-            Option<TotalTimesDelivered>,
-        ) = result.unwrap();
+#[cfg(test)]
+mod test_are_still_mine {
+    use redis::{cmd, pipe, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
 
-        assert!(reply.0);
-        assert!(reply.1.is_some());
-        assert!(reply.1.unwrap().eq(&196415));
-        assert!(reply.2.is_some());
-        assert!(reply.2.unwrap().eq(&5));
+    use super::*;
+
+    /// Build a `XPENDING` reply with a single entry owned by *consumer*.
+    fn entry(id: &str, consumer: &str) -> Value {
+        Value::Array(vec![Value::Array(vec![
+            Value::BulkString(id.as_bytes().to_vec()),
+            Value::BulkString(consumer.as_bytes().to_vec()),
+            Value::Int(1000),
+            Value::Int(2),
+        ])])
     }
 
     #[test]
-    fn test_is_still_mine_false() {
-        // Define the key, group, consumer, and id:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
-        let id = "1-0";
+    fn test_are_still_mine_ok() {
+        // Define the key, group, consumer, and ids:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let ids: [&str; 3] = ["1-0", "2-0", "3-0"];
 
         // Create a mock connection:
-        let mut conn: MockRedisConnection =
-            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+        let mut pipeline: Pipeline = pipe();
+        for id in ids {
+            pipeline.add_command(
                 cmd("XPENDING")
                     .arg(key)
                     .arg(group)
-                    .arg(&[id])
-                    .arg(&[id])
+                    .arg(id)
+                    .arg(id)
                     .arg(1)
-                    .arg(consumer),
-                Ok(Value::Array(vec![])),
-            )]);
+                    .arg(consumer)
+                    .to_owned(),
+            );
+        }
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::with_values(
+            pipeline,
+            Ok(vec![
+                entry("1-0", consumer),
+                entry("2-0", "other-consumer"),
+                Value::Array(vec![]),
+            ]),
+        )]);
 
-        // Verify if the message is still in the consumer pending list:
-        let result: RedsumerResult<(
-            bool,
-            Option<LastDeliveredMilliseconds>,
-            Option<TotalTimesDelivered>,
-        )> = conn.is_still_mine(key, group, consumer, id);
+        // Verify if the messages are still in the consumer pending list:
+        let result: RedsumerResult<Vec<MessageOwnership>> =
+            conn.are_still_mine(key, group, consumer, &ids);
 
         // Verify the result:
-        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            vec![
+                (true, Some(1000), Some(2)),
+                (false, None, None),
+                (false, None, None)
+            ]
+        );
+    }
 
-        let reply: (
-            bool,
-            Option<LastDeliveredMilliseconds>,
-            Option<TotalTimesDelivered>,
-        ) = result.unwrap();
+    #[test]
+    fn test_are_still_mine_empty() {
+        // Define the key, group and consumer:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
 
-        assert!(!reply.0);
-        assert!(reply.1.is_none());
-        assert!(reply.2.is_none());
+        // Create a mock connection without commands:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Verify an empty set of messages:
+        let result = conn.are_still_mine::<_, _, &str>(key, group, consumer, &[]);
+
+        // Verify the result:
+        assert!(result.unwrap().is_empty());
     }
 
     #[test]
-    fn test_is_still_mine_error() {
+    fn test_are_still_mine_error() {
         // Define the key, group, consumer, and id:
-        let key = "my-key";
-        let group = "my-group";
-        let consumer = "my-consumer";
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let consumer: &str = "my-consumer";
+        let id: &str = "1-0";
 
         // Create a mock connection:
-        let mut conn: MockRedisConnection =
-            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::with_values(
+            pipe().add_command(
                 cmd("XPENDING")
                     .arg(key)
                     .arg(group)
-                    .arg(&["1-0"])
-                    .arg(&["1-0"])
+                    .arg(id)
+                    .arg(id)
                     .arg(1)
-                    .arg(consumer),
-                Err(RedisError::from((
-                    ErrorKind::ResponseError,
-                    "XPENDING Error",
-                ))),
-            )]);
+                    .arg(consumer)
+                    .to_owned(),
+            ),
+            Err::<Vec<Value>, RedisError>(RedisError::from((
+                ErrorKind::ResponseError,
+                "XPENDING Error",
+            ))),
+        )]);
 
         // Verify if the message is still in the consumer pending list:
-        let result: RedsumerResult<(
-            bool,
-            Option<LastDeliveredMilliseconds>,
-            Option<TotalTimesDelivered>,
-        )> = conn.is_still_mine(key, group, consumer, "1-0");
+        let result = conn.are_still_mine(key, group, consumer, &[id]);
 
         // Verify the result:
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
     }
 }
 
 #[cfg(test)]
-mod test_ack {
-    use redis::cmd;
+mod test_ack_many_detailed {
+    use redis::{cmd, pipe, ErrorKind, RedisError, Value};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
 
+    /// Build the `MULTI`/`EXEC` transaction to ack *ids*.
+    fn transaction(key: &str, group: &str, ids: &[&str]) -> Pipeline {
+        let mut pipeline: Pipeline = pipe();
+        pipeline.atomic();
+        for id in ids {
+            pipeline.add_command(
+                cmd("XPENDING")
+                    .arg(key)
+                    .arg(group)
+                    .arg(id)
+                    .arg(id)
+                    .arg(1)
+                    .to_owned(),
+            );
+        }
+        pipeline
+            .add_command(cmd("XACK").arg(key).arg(group).arg(ids).to_owned())
+            .ignore();
+        pipeline
+    }
+
     #[test]
-    fn test_ack_ok_true() {
-        // Define the key, group, and id:
-        let key = "my-key";
-        let group = "my-group";
-        let id: &str = "1-0";
+    fn test_ack_many_detailed_ok() {
+        // Define the key, group, and ids:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let ids: [&str; 2] = ["1-0", "2-0"];
 
         // Create a mock connection:
-        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
-            cmd("XACK").arg(key).arg(group).arg(&[id]),
-            Ok(1),
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::with_values(
+            transaction(key, group, &ids),
+            Ok(vec![Value::Array(vec![
+                Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"1-0".to_vec()),
+                    Value::BulkString(b"my-consumer".to_vec()),
+                    Value::Int(1000),
+                    Value::Int(1),
+                ])]),
+                Value::Array(vec![]),
+                Value::Int(1),
+            ])]),
         )]);
 
-        // Acknowledge the message:
-        let result: RedsumerResult<bool> = conn.ack(key, group, id);
+        // Acknowledge the messages:
+        let result: RedsumerResult<Vec<bool>> = conn.ack_many_detailed(key, group, &ids);
 
         // Verify the result:
-        assert!(result.is_ok());
-        assert!(result.unwrap());
+        assert_eq!(result.unwrap(), vec![true, false]);
     }
 
     #[test]
-    fn test_ack_ok_false() {
-        // Define the key, group, and id:
-        let key = "my-key";
-        let group = "my-group";
-        let id = "1-0";
+    fn test_ack_many_detailed_empty() {
+        // Create a mock connection without commands:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Acknowledge an empty set of messages:
+        let result: RedsumerResult<Vec<bool>> =
+            conn.ack_many_detailed::<_, &str>("my-key", "my-group", &[]);
+
+        // Verify the result:
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ack_many_detailed_error() {
+        // Define the key, group, and ids:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let ids: [&str; 1] = ["1-0"];
 
         // Create a mock connection:
-        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
-            cmd("XACK").arg(key).arg(group).arg(&[id]),
-            Ok(0),
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::with_values(
+            transaction(key, group, &ids),
+            Err::<Vec<Value>, RedisError>(RedisError::from((
+                ErrorKind::ResponseError,
+                "XACK Error",
+            ))),
         )]);
 
-        // Acknowledge the message:
-        let result: RedsumerResult<bool> = conn.ack(key, group, id);
+        // Acknowledge the messages:
+        let result: RedsumerResult<Vec<bool>> = conn.ack_many_detailed(key, group, &ids);
 
         // Verify the result:
-        assert!(result.is_ok());
-        assert!(!result.unwrap());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
     }
+}
+
+#[cfg(test)]
+mod test_ack_many {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
 
     #[test]
-    fn test_ack_error() {
-        // Define the key, group, and id:
-        let key = "my-key";
-        let group = "my-group";
-        let id = "1-0";
+    fn test_ack_many_ok() {
+        // Define the key, group, and ids:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let ids: [&str; 3] = ["1-0", "2-0", "3-0"];
 
         // Create a mock connection:
         let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
-            cmd("XACK").arg(key).arg(group).arg(&[id]),
-            Err(RedisError::from((ErrorKind::ResponseError, "XACK Error"))),
+            cmd("XACK").arg(key).arg(group).arg(&ids),
+            Ok(2),
         )]);
 
-        // Acknowledge the message:
-        let result: RedsumerResult<bool> = conn.ack(key, group, id);
+        // Acknowledge the messages:
+        let result: RedsumerResult<usize> = conn.ack_many(key, group, &ids);
 
         // Verify the result:
-        assert!(result.is_err());
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_ack_many_without_ids() {
+        // Create a mock connection without commands:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Acknowledge no messages:
+        let result: RedsumerResult<usize> = conn.ack_many::<_, &str>("my-key", "my-group", &[]);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_ack_many_error() {
+        // Define the key, group, and ids:
+        let key: &str = "my-key";
+        let group: &str = "my-group";
+        let ids: [&str; 1] = ["1-0"];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XACK").arg(key).arg(group).arg(&ids),
+                Err(RedisError::from((ErrorKind::ResponseError, "Error"))),
+            )]);
+
+        // Acknowledge the messages:
+        let result: RedsumerResult<usize> = conn.ack_many(key, group, &ids);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
     }
 }
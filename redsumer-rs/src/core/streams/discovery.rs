@@ -0,0 +1,140 @@
+use std::collections::BTreeSet;
+
+use redis::{cmd, Commands, ScanOptions};
+use tracing::{debug, error};
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+
+/// Number of keys requested to the server per `SCAN` iteration when streams are discovered.
+pub const DISCOVERY_SCAN_COUNT: usize = 100;
+
+/// Get the keys of type stream matching a glob *pattern*, iterating the whole keyspace with `SCAN`. Keys are sorted and deduplicated, since `SCAN` can return a key more than once.
+fn discover_streams<C>(conn: &mut C, pattern: &str) -> RedsumerResult<Vec<String>>
+where
+    C: Commands,
+{
+    let mut streams: BTreeSet<String> = BTreeSet::new();
+    let mut cursor: u64 = 0;
+
+    loop {
+        let (next_cursor, keys): (u64, Vec<String>) = cmd("SCAN")
+            .cursor_arg(cursor)
+            .arg(
+                ScanOptions::default()
+                    .with_pattern(pattern)
+                    .with_count(DISCOVERY_SCAN_COUNT)
+                    .with_type("stream"),
+            )
+            .query(conn)
+            .map_err(|e| {
+                error!("Error discovering streams: {:?}", e);
+                RedsumerError::from(e)
+            })?;
+
+        streams.extend(keys);
+
+        if next_cursor.eq(&0) {
+            break;
+        }
+        cursor = next_cursor;
+    }
+
+    debug!("Total streams discovered: {}", streams.len());
+
+    Ok(streams.into_iter().collect())
+}
+
+/// A trait that bundles methods to discover the streams of a Redis server.
+pub trait DiscoveryCommands {
+    /// Get the keys of type stream matching a glob pattern, like `meters:*:events`, using `SCAN` with the `MATCH` and `TYPE` options. It does not block the server, unlike `KEYS`, but keys created or deleted while the scan runs may be reported or not.
+    ///
+    /// # Arguments:
+    /// - **pattern**: The glob pattern of the keys.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the keys of the streams found, sorted and without duplicates. If an error occurs, the function will return an error result.
+    fn discover_streams(&mut self, pattern: &str) -> RedsumerResult<Vec<String>>;
+}
+
+impl<C> DiscoveryCommands for C
+where
+    C: Commands,
+{
+    fn discover_streams(&mut self, pattern: &str) -> RedsumerResult<Vec<String>> {
+        discover_streams(self, pattern)
+    }
+}
+
+#[cfg(test)]
+mod test_discover_streams {
+    use redis::{ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `SCAN` command from *cursor* with the discovery options.
+    fn scan(cursor: u64, pattern: &str) -> redis::Cmd {
+        cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(DISCOVERY_SCAN_COUNT)
+            .arg("TYPE")
+            .arg("stream")
+            .to_owned()
+    }
+
+    /// Build a `SCAN` reply with the next *cursor* and *keys*.
+    fn reply(cursor: &str, keys: &[&str]) -> Value {
+        Value::Array(vec![
+            Value::BulkString(cursor.as_bytes().to_vec()),
+            Value::Array(
+                keys.iter()
+                    .map(|key| Value::BulkString(key.as_bytes().to_vec()))
+                    .collect(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_discover_streams_ok() {
+        // Define the pattern:
+        let pattern: &str = "meters:*:events";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                scan(0, pattern),
+                Ok(reply("7", &["meters:2:events", "meters:1:events"])),
+            ),
+            MockCmd::new::<_, Value>(scan(7, pattern), Ok(reply("0", &["meters:1:events"]))),
+        ]);
+
+        // Discover the streams:
+        let result: RedsumerResult<Vec<String>> = conn.discover_streams(pattern);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), vec!["meters:1:events", "meters:2:events"]);
+    }
+
+    #[test]
+    fn test_discover_streams_error() {
+        // Define the pattern:
+        let pattern: &str = "meters:*:events";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                scan(0, pattern),
+                Err(RedisError::from((ErrorKind::ResponseError, "SCAN Error"))),
+            )]);
+
+        // Discover the streams:
+        let result: RedsumerResult<Vec<String>> = conn.discover_streams(pattern);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
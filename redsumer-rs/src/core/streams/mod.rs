@@ -1,3 +1,5 @@
 pub mod consumer;
+pub mod discovery;
 pub mod producer;
+pub mod reader;
 pub mod types;
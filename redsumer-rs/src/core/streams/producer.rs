@@ -1,18 +1,32 @@
-use redis::{Commands, FromRedisValue, RedisResult, ToRedisArgs};
+use std::time::Duration;
+
+use redis::{
+    from_redis_value, pipe,
+    streams::{StreamAddOptions, StreamId, StreamTrimOptions},
+    Commands, FromRedisValue, Pipeline, RedisResult, ToRedisArgs,
+};
 use tracing::{debug, error};
 
 #[allow(unused_imports)]
-use crate::core::result::{RedsumerError, RedsumerResult};
+use crate::core::{
+    result::{RedsumerError, RedsumerResult},
+    streams::types::Id,
+};
 
 /// Produce a message to a Redis stream from a map. To set the ID of the message, this method use the value "*" to indicate that Redis should generate a new ID with the current timestamp.
-fn produce_from_map<C, K, M, ID>(c: &mut C, key: K, map: M) -> RedisResult<ID>
+fn produce_from_map<C, K, M, ID>(
+    c: &mut C,
+    key: K,
+    map: M,
+    options: &StreamAddOptions,
+) -> RedisResult<ID>
 where
     C: Commands,
     K: ToRedisArgs,
     M: ToRedisArgs,
     ID: FromRedisValue,
 {
-    match c.xadd_map(key, "*", map) {
+    match c.xadd_options(key, "*", map, options) {
         Ok(id) => {
             debug!("Message produced successfully");
             Ok(id)
@@ -24,8 +38,48 @@ where
     }
 }
 
+/// Produce a message to a Redis stream from a map and wait until it is acknowledged by *replicas* replicas or *timeout* elapses. `WAIT` is sent in the same pipeline as `XADD`, since it only waits for the writes of its own connection.
+fn produce_and_wait<C, K, M>(
+    c: &mut C,
+    key: K,
+    map: M,
+    options: &StreamAddOptions,
+    replicas: usize,
+    timeout: Duration,
+) -> RedsumerResult<(Id, usize)>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    M: ToRedisArgs,
+{
+    match pipe()
+        .xadd_options(key, "*", map, options)
+        .cmd("WAIT")
+        .arg(replicas)
+        .arg(timeout.as_millis() as u64)
+        .query::<(Id, usize)>(c)
+    {
+        Ok((id, acknowledged)) => {
+            debug!("Message produced successfully and acknowledged by {acknowledged} replicas");
+            Ok((id, acknowledged))
+        }
+        Err(e) => {
+            error!(
+                "Error producing message with replication confirmation: {:?}",
+                e
+            );
+            Err(e.into())
+        }
+    }
+}
+
 /// Produce a message to a Redis stream from a list of items. To set the ID of the message, this method use the value "*" to indicate that Redis should generate a new ID with the current timestamp.
-fn produce_from_items<C, K, F, V, ID>(c: &mut C, key: K, items: &[(F, V)]) -> RedisResult<ID>
+fn produce_from_items<C, K, F, V, ID>(
+    c: &mut C,
+    key: K,
+    items: &[(F, V)],
+    options: &StreamAddOptions,
+) -> RedisResult<ID>
 where
     C: Commands,
     K: ToRedisArgs,
@@ -33,7 +87,7 @@ where
     V: ToRedisArgs,
     ID: FromRedisValue,
 {
-    match c.xadd(key, "*", items) {
+    match c.xadd_options(key, "*", items, options) {
         Ok(id) => {
             debug!("Message produced successfully");
             Ok(id)
@@ -45,6 +99,118 @@ where
     }
 }
 
+/// Produce a message to a Redis stream from a list of items with an explicit ID, which must be greater than the ID of the latest message in the stream.
+fn produce_from_items_with_id<C, K, F, V>(
+    c: &mut C,
+    key: K,
+    id: &Id,
+    items: &[(F, V)],
+    options: &StreamAddOptions,
+) -> RedsumerResult<Id>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    F: ToRedisArgs,
+    V: ToRedisArgs,
+{
+    match c.xadd_options(key, id, items, options) {
+        Ok(id) => {
+            debug!("Message produced successfully with explicit ID");
+            Ok(id)
+        }
+        Err(e) => {
+            error!("Error producing message with explicit ID {id}: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Produce a batch of messages to a Redis stream in a single pipeline. Redis generates the ID of every message.
+fn produce_batch<C, K, M>(
+    c: &mut C,
+    key: K,
+    messages: &[M],
+    options: &StreamAddOptions,
+) -> RedsumerResult<Vec<Id>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    M: ToRedisArgs,
+{
+    if messages.is_empty() {
+        debug!("No messages to produce");
+        return Ok(Vec::new());
+    }
+
+    let mut pipeline: Pipeline = pipe();
+    for message in messages.iter() {
+        pipeline.xadd_options(&key, "*", message, options);
+    }
+
+    match pipeline.query::<Vec<Id>>(c) {
+        Ok(ids) => {
+            debug!("Total messages produced in batch: {}", ids.len());
+            Ok(ids)
+        }
+        Err(e) => {
+            error!("Error producing batch of messages: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Trim a Redis stream according to the strategy of *options*.
+fn trim_stream<C, K>(c: &mut C, key: K, options: &StreamTrimOptions) -> RedsumerResult<usize>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    match c.xtrim_options::<_, usize>(key, options) {
+        Ok(trimmed) => {
+            debug!("Total messages trimmed: {trimmed}");
+            Ok(trimmed)
+        }
+        Err(e) => {
+            error!("Error trimming stream: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Set a time to live of *seconds* on a Redis stream, refreshing it if it was already set.
+fn expire_stream<C, K>(c: &mut C, key: K, seconds: u64) -> RedsumerResult<bool>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    match c.expire::<_, bool>(key, seconds as i64) {
+        Ok(true) => {
+            debug!("Stream expires in {seconds} seconds");
+            Ok(true)
+        }
+        Ok(false) => {
+            debug!("The stream does not exist, no time to live was set");
+            Ok(false)
+        }
+        Err(e) => {
+            error!("Error setting the stream time to live: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Get the fields of a consumed message as a list of items sorted by field, so it can be produced again in another stream.
+pub fn message_to_items(message: &StreamId) -> RedsumerResult<Vec<(String, Vec<u8>)>> {
+    let mut items: Vec<(String, Vec<u8>)> = message
+        .map
+        .iter()
+        .map(|(field, value)| Ok((field.to_owned(), from_redis_value::<Vec<u8>>(value)?)))
+        .collect::<RedsumerResult<_>>()?;
+    items.sort();
+
+    Ok(items)
+}
+
 /// A trait that bundles methods for producing messages in a Redis stream
 pub trait ProducerCommands {
     /// Produce a message to a Redis stream from a map.
@@ -52,10 +218,39 @@ pub trait ProducerCommands {
     /// # Arguments:
     /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
     /// - **map**: A map with the message fields and values, which must implement the `ToRedisArgs` trait.
+    /// - **options**: Options to set the *NOMKSTREAM* flag and the trimming strategy of the `XADD` command.
     ///
     /// # Returns:
     /// A [`RedsumerResult`] with the message ID if the message was produced successfully. Otherwise, a [`RedsumerError`] is returned.
-    fn produce_from_map<K, M>(&mut self, key: K, map: M) -> RedsumerResult<String>
+    fn produce_from_map<K, M>(
+        &mut self,
+        key: K,
+        map: M,
+        options: &StreamAddOptions,
+    ) -> RedsumerResult<Id>
+    where
+        K: ToRedisArgs,
+        M: ToRedisArgs;
+
+    /// Produce a message to a Redis stream from a map and wait for its replication with `WAIT`, for workloads that can not tolerate losing acknowledged messages on failover.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
+    /// - **map**: A map with the message fields and values, which must implement the `ToRedisArgs` trait.
+    /// - **options**: Options to set the *NOMKSTREAM* flag and the trimming strategy of the `XADD` command.
+    /// - **replicas**: The number of replicas that must acknowledge the message.
+    /// - **timeout**: The max time to wait for the replicas. If it is zero, `WAIT` blocks until the replicas acknowledge the message.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the message ID and the number of replicas that acknowledged it, which may be lower than *replicas* if *timeout* elapsed. Otherwise, a [`RedsumerError`] is returned.
+    fn produce_and_wait<K, M>(
+        &mut self,
+        key: K,
+        map: M,
+        options: &StreamAddOptions,
+        replicas: usize,
+        timeout: Duration,
+    ) -> RedsumerResult<(Id, usize)>
     where
         K: ToRedisArgs,
         M: ToRedisArgs;
@@ -65,35 +260,173 @@ pub trait ProducerCommands {
     /// # Arguments:
     ///  - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
     /// - **items**: A list of tuples with the message fields and values, which must implement the `ToRedisArgs` trait.
+    /// - **options**: Options to set the *NOMKSTREAM* flag and the trimming strategy of the `XADD` command.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the message ID if the message was produced successfully. Otherwise, a [`RedsumerError`] is returned.
+    fn produce_from_items<K, F, V>(
+        &mut self,
+        key: K,
+        items: &[(F, V)],
+        options: &StreamAddOptions,
+    ) -> RedsumerResult<Id>
+    where
+        K: ToRedisArgs,
+        F: ToRedisArgs,
+        V: ToRedisArgs;
+
+    /// Produce a message to a Redis stream from a list of items with an explicit ID.
+    ///
+    /// # Arguments:
+    ///  - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
+    /// - **id**: The ID of the message. It must be greater than the ID of the latest message in the stream.
+    /// - **items**: A list of tuples with the message fields and values, which must implement the `ToRedisArgs` trait.
+    /// - **options**: Options to set the *NOMKSTREAM* flag and the trimming strategy of the `XADD` command.
     ///
     /// # Returns:
     /// A [`RedsumerResult`] with the message ID if the message was produced successfully. Otherwise, a [`RedsumerError`] is returned.
-    fn produce_from_items<K, F, V>(&mut self, key: K, items: &[(F, V)]) -> RedsumerResult<String>
+    fn produce_from_items_with_id<K, F, V>(
+        &mut self,
+        key: K,
+        id: &Id,
+        items: &[(F, V)],
+        options: &StreamAddOptions,
+    ) -> RedsumerResult<Id>
     where
         K: ToRedisArgs,
         F: ToRedisArgs,
         V: ToRedisArgs;
+
+    /// Produce a batch of messages to a Redis stream in a single pipeline, saving a round trip per message.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
+    /// - **messages**: The messages to produce, each one with its fields and values, which must implement the `ToRedisArgs` trait.
+    /// - **options**: Options to set the *NOMKSTREAM* flag and the trimming strategy of every `XADD` command.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the produced messages, in the same order. If an error occurs, a [`RedsumerError`] is returned and messages of the batch before the failing one may have been produced.
+    fn produce_batch<K, M>(
+        &mut self,
+        key: K,
+        messages: &[M],
+        options: &StreamAddOptions,
+    ) -> RedsumerResult<Vec<Id>>
+    where
+        K: ToRedisArgs,
+        M: ToRedisArgs;
+
+    /// Trim a Redis stream, evicting its oldest messages.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
+    /// - **options**: Options to set the trimming strategy of the `XTRIM` command.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the number of messages evicted. Otherwise, a [`RedsumerError`] is returned.
+    fn trim_stream<K>(&mut self, key: K, options: &StreamTrimOptions) -> RedsumerResult<usize>
+    where
+        K: ToRedisArgs;
+
+    /// Set a time to live on a Redis stream with the `EXPIRE` command, refreshing it if it was already set. When it elapses, Redis deletes the stream.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
+    /// - **seconds**: The time to live of the stream, in seconds.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `true` if the time to live was set, or `false` if the stream does not exist. Otherwise, a [`RedsumerError`] is returned.
+    fn expire_stream<K>(&mut self, key: K, seconds: u64) -> RedsumerResult<bool>
+    where
+        K: ToRedisArgs;
 }
 
 impl<C> ProducerCommands for C
 where
     C: Commands,
 {
-    fn produce_from_map<K, M>(&mut self, key: K, map: M) -> RedsumerResult<String>
+    fn produce_from_map<K, M>(
+        &mut self,
+        key: K,
+        map: M,
+        options: &StreamAddOptions,
+    ) -> RedsumerResult<Id>
+    where
+        K: ToRedisArgs,
+        M: ToRedisArgs,
+    {
+        Ok(produce_from_map(self, key, map, options)?)
+    }
+
+    fn produce_and_wait<K, M>(
+        &mut self,
+        key: K,
+        map: M,
+        options: &StreamAddOptions,
+        replicas: usize,
+        timeout: Duration,
+    ) -> RedsumerResult<(Id, usize)>
     where
         K: ToRedisArgs,
         M: ToRedisArgs,
     {
-        produce_from_map(self, key, map)
+        produce_and_wait(self, key, map, options, replicas, timeout)
     }
 
-    fn produce_from_items<K, F, V>(&mut self, key: K, items: &[(F, V)]) -> RedsumerResult<String>
+    fn produce_from_items<K, F, V>(
+        &mut self,
+        key: K,
+        items: &[(F, V)],
+        options: &StreamAddOptions,
+    ) -> RedsumerResult<Id>
     where
         K: ToRedisArgs,
         F: ToRedisArgs,
         V: ToRedisArgs,
     {
-        produce_from_items(self, key, items)
+        Ok(produce_from_items(self, key, items, options)?)
+    }
+
+    fn produce_from_items_with_id<K, F, V>(
+        &mut self,
+        key: K,
+        id: &Id,
+        items: &[(F, V)],
+        options: &StreamAddOptions,
+    ) -> RedsumerResult<Id>
+    where
+        K: ToRedisArgs,
+        F: ToRedisArgs,
+        V: ToRedisArgs,
+    {
+        produce_from_items_with_id(self, key, id, items, options)
+    }
+
+    fn produce_batch<K, M>(
+        &mut self,
+        key: K,
+        messages: &[M],
+        options: &StreamAddOptions,
+    ) -> RedsumerResult<Vec<Id>>
+    where
+        K: ToRedisArgs,
+        M: ToRedisArgs,
+    {
+        produce_batch(self, key, messages, options)
+    }
+
+    fn trim_stream<K>(&mut self, key: K, options: &StreamTrimOptions) -> RedsumerResult<usize>
+    where
+        K: ToRedisArgs,
+    {
+        trim_stream(self, key, options)
+    }
+
+    fn expire_stream<K>(&mut self, key: K, seconds: u64) -> RedsumerResult<bool>
+    where
+        K: ToRedisArgs,
+    {
+        expire_stream(self, key, seconds)
     }
 }
 
@@ -101,7 +434,7 @@ where
 mod test_produce_from_map {
     use std::collections::BTreeMap;
 
-    use redis::{cmd, ErrorKind, Value};
+    use redis::{cmd, ErrorKind, RedisError, Value};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
@@ -123,7 +456,8 @@ mod test_produce_from_map {
             )]);
 
         // Produce the message:
-        let result: RedsumerResult<String> = conn.produce_from_map(key, map);
+        let result: RedsumerResult<Id> =
+            conn.produce_from_map(key, map, &StreamAddOptions::default());
 
         // Verify the result:
         assert!(result.is_ok());
@@ -142,7 +476,7 @@ mod test_produce_from_map {
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
                 cmd("XADD").arg(key).arg("*").arg(map.to_owned()),
-                Err(RedsumerError::from((
+                Err(RedisError::from((
                     ErrorKind::ResponseError,
                     "XADD Error",
                     "XADD command failed".to_string(),
@@ -150,16 +484,119 @@ mod test_produce_from_map {
             )]);
 
         // Produce the message:
-        let result: RedsumerResult<String> = conn.produce_from_map(key, map);
+        let result: RedsumerResult<Id> =
+            conn.produce_from_map(key, map, &StreamAddOptions::default());
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_produce_from_items_with_id {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_produce_from_items_with_id_ok() {
+        // Define the key, ID and items:
+        let key: &str = "my-key";
+        let id: Id = Id::new(1526919030474, 55);
+        let items: Vec<(&str, &str)> = vec![("field", "value")];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XADD").arg(key).arg("1526919030474-55").arg(&items),
+                Ok(Value::SimpleString("1526919030474-55".to_string())),
+            )]);
+
+        // Produce the message:
+        let result: RedsumerResult<Id> =
+            conn.produce_from_items_with_id(key, &id, &items, &StreamAddOptions::default());
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), id);
+    }
+
+    #[test]
+    fn test_produce_from_items_with_lower_id() {
+        // Define the key, ID and items:
+        let key: &str = "my-key";
+        let id: Id = Id::new(1, 0);
+        let items: Vec<(&str, &str)> = vec![("field", "value")];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XADD").arg(key).arg("1-0").arg(&items),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "ERR The ID specified in XADD is equal or smaller than the target stream top item",
+                ))),
+            )]);
+
+        // Produce the message:
+        let result: RedsumerResult<Id> =
+            conn.produce_from_items_with_id(key, &id, &items, &StreamAddOptions::default());
 
         // Verify the result:
         assert!(result.is_err());
     }
 }
 
+#[cfg(test)]
+mod test_produce_with_options {
+    use redis::{
+        cmd,
+        streams::{StreamTrimStrategy, StreamTrimmingMode},
+        Value,
+    };
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_produce_from_items_with_options_ok() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Define the items:
+        let items: Vec<(&str, &str)> = vec![("field", "value")];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XADD")
+                    .arg(key)
+                    .arg("NOMKSTREAM")
+                    .arg("MAXLEN")
+                    .arg("~")
+                    .arg(100)
+                    .arg("*")
+                    .arg(&items),
+                Ok(Value::SimpleString("1-0".to_string())),
+            )]);
+
+        // Produce the message:
+        let result: RedsumerResult<Id> = conn.produce_from_items(
+            key,
+            &items,
+            &StreamAddOptions::default()
+                .nomkstream()
+                .trim(StreamTrimStrategy::maxlen(StreamTrimmingMode::Approx, 100)),
+        );
+
+        // Verify the result:
+        assert!(result.is_ok());
+    }
+}
+
 #[cfg(test)]
 mod test_produce_from_items {
-    use redis::{cmd, ErrorKind, Value};
+    use redis::{cmd, ErrorKind, RedisError, Value};
     use redis_test::{MockCmd, MockRedisConnection};
 
     use super::*;
@@ -180,7 +617,8 @@ mod test_produce_from_items {
             )]);
 
         // Produce the message:
-        let result: RedsumerResult<String> = conn.produce_from_items(key, &items);
+        let result: RedsumerResult<Id> =
+            conn.produce_from_items(key, &items, &StreamAddOptions::default());
 
         // Verify the result:
         assert!(result.is_ok());
@@ -198,7 +636,7 @@ mod test_produce_from_items {
         let mut conn: MockRedisConnection =
             MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
                 cmd("XADD").arg(key).arg("*").arg(&items),
-                Err(RedsumerError::from((
+                Err(RedisError::from((
                     ErrorKind::ResponseError,
                     "XADD Error",
                     "XADD command failed".to_string(),
@@ -206,9 +644,252 @@ mod test_produce_from_items {
             )]);
 
         // Produce the message:
-        let result: RedsumerResult<String> = conn.produce_from_items(key, &items);
+        let result: RedsumerResult<Id> =
+            conn.produce_from_items(key, &items, &StreamAddOptions::default());
 
         // Verify the result:
         assert!(result.is_err());
     }
 }
+
+#[cfg(test)]
+mod test_message_to_items {
+    use std::collections::HashMap;
+
+    use redis::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_message_to_items_ok() {
+        // Define a consumed message:
+        let message: StreamId = StreamId {
+            id: "1-0".to_string(),
+            map: HashMap::from([
+                ("b".to_string(), Value::BulkString(b"2".to_vec())),
+                ("a".to_string(), Value::BulkString(b"1".to_vec())),
+            ]),
+        };
+
+        // Verify the result:
+        assert_eq!(
+            message_to_items(&message).unwrap(),
+            vec![
+                ("a".to_string(), b"1".to_vec()),
+                ("b".to_string(), b"2".to_vec())
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_trim_stream {
+    use redis::{cmd, streams::StreamTrimmingMode, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_trim_stream_by_min_id() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XTRIM").arg(key).arg("MINID").arg("=").arg("5-0"),
+                Ok(Value::Int(3)),
+            )]);
+
+        // Trim the stream:
+        let result: RedsumerResult<usize> = conn.trim_stream(
+            key,
+            &StreamTrimOptions::minid(StreamTrimmingMode::Exact, "5-0"),
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), 3);
+    }
+}
+
+#[cfg(test)]
+mod test_produce_batch {
+    use redis::{cmd, pipe, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_produce_batch_ok() {
+        // Define the key and the messages:
+        let key: &str = "my-key";
+        let messages: Vec<Vec<(&str, &str)>> = vec![vec![("a", "1")], vec![("b", "2")]];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::with_values(
+            pipe()
+                .add_command(cmd("XADD").arg(key).arg("*").arg("a").arg("1").to_owned())
+                .add_command(cmd("XADD").arg(key).arg("*").arg("b").arg("2").to_owned()),
+            Ok(vec![
+                Value::BulkString(b"1-0".to_vec()),
+                Value::BulkString(b"1-1".to_vec()),
+            ]),
+        )]);
+
+        // Produce the batch:
+        let result: RedsumerResult<Vec<Id>> =
+            conn.produce_batch(key, &messages, &StreamAddOptions::default());
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), vec![Id::new(1, 0), Id::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_produce_batch_empty() {
+        // Create a mock connection without commands:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Produce an empty batch:
+        let result: RedsumerResult<Vec<Id>> = conn.produce_batch(
+            "my-key",
+            &Vec::<Vec<(&str, &str)>>::new(),
+            &StreamAddOptions::default(),
+        );
+
+        // Verify the result:
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_produce_batch_error() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::with_values::<_, Value>(
+                pipe().add_command(
+                    cmd("XADD")
+                        .arg("my-key")
+                        .arg("*")
+                        .arg("a")
+                        .arg("1")
+                        .to_owned(),
+                ),
+                Err(RedisError::from((ErrorKind::ResponseError, "XADD Error"))),
+            )]);
+
+        // Produce the batch:
+        let result: RedsumerResult<Vec<Id>> =
+            conn.produce_batch("my-key", &[vec![("a", "1")]], &StreamAddOptions::default());
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
+
+#[cfg(test)]
+mod test_produce_and_wait {
+    use redis::{cmd, pipe, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_produce_and_wait_ok() {
+        // Define the key and the message:
+        let key: &str = "my-key";
+        let message: Vec<(&str, &str)> = vec![("a", "1")];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::with_values(
+            pipe()
+                .add_command(cmd("XADD").arg(key).arg("*").arg("a").arg("1").to_owned())
+                .add_command(cmd("WAIT").arg(2).arg(500).to_owned()),
+            Ok(vec![Value::BulkString(b"1-0".to_vec()), Value::Int(1)]),
+        )]);
+
+        // Produce the message and wait for its replication:
+        let result: RedsumerResult<(Id, usize)> = conn.produce_and_wait(
+            key,
+            &message,
+            &StreamAddOptions::default(),
+            2,
+            Duration::from_millis(500),
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), (Id::new(1, 0), 1));
+    }
+
+    #[test]
+    fn test_produce_and_wait_error() {
+        // Define the key and the message:
+        let key: &str = "my-key";
+        let message: Vec<(&str, &str)> = vec![("a", "1")];
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::with_values::<_, Value>(
+                pipe()
+                    .add_command(cmd("XADD").arg(key).arg("*").arg("a").arg("1").to_owned())
+                    .add_command(cmd("WAIT").arg(1).arg(0).to_owned()),
+                Err(RedisError::from((ErrorKind::ResponseError, "WAIT Error"))),
+            )]);
+
+        // Produce the message and wait for its replication:
+        let result: RedsumerResult<(Id, usize)> = conn.produce_and_wait(
+            key,
+            &message,
+            &StreamAddOptions::default(),
+            1,
+            Duration::ZERO,
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
+
+#[cfg(test)]
+mod test_expire_stream {
+    use redis::{cmd, ErrorKind, RedisError};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_expire_stream_ok() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, i64>(cmd("EXPIRE").arg(key).arg(60), Ok(1)),
+            MockCmd::new::<_, i64>(cmd("EXPIRE").arg(key).arg(60), Ok(0)),
+        ]);
+
+        // Set the time to live of an existing and a missing stream:
+        let existing: RedsumerResult<bool> = conn.expire_stream(key, 60);
+        let missing: RedsumerResult<bool> = conn.expire_stream(key, 60);
+
+        // Verify the result:
+        assert!(existing.unwrap());
+        assert!(!missing.unwrap());
+    }
+
+    #[test]
+    fn test_expire_stream_error() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+            cmd("EXPIRE").arg(key).arg(60),
+            Err(RedisError::from((ErrorKind::ResponseError, "EXPIRE Error"))),
+        )]);
+
+        // Set the time to live of the stream:
+        let result: RedsumerResult<bool> = conn.expire_stream(key, 60);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
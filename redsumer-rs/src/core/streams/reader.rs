@@ -0,0 +1,580 @@
+use redis::{
+    streams::{StreamId, StreamRangeReply, StreamReadOptions, StreamReadReply},
+    Commands, ToRedisArgs,
+};
+use tracing::{debug, error};
+
+#[allow(unused_imports)]
+use crate::core::{
+    result::{RedsumerError, RedsumerResult},
+    streams::{consumer::UnwrapStreamReadReply, types::Id},
+};
+
+/// The smallest ID of a stream range.
+pub const RANGE_START: &str = "-";
+
+/// The greatest ID of a stream range.
+pub const RANGE_END: &str = "+";
+
+/// Read up to *count* messages of a stream between *start* and *end*, both inclusive, in ascending order.
+fn read_range<C, K, S, E>(
+    conn: &mut C,
+    key: K,
+    start: S,
+    end: E,
+    count: usize,
+) -> RedsumerResult<Vec<StreamId>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    S: ToRedisArgs,
+    E: ToRedisArgs,
+{
+    match conn.xrange_count::<_, _, _, _, StreamRangeReply>(key, start, end, count) {
+        Ok(reply) => {
+            debug!("Total messages read from range: {}", reply.ids.len());
+            Ok(reply.ids)
+        }
+        Err(e) => {
+            error!("Error reading messages from range: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Read up to *count* messages of a stream between *end* and *start*, both inclusive, in descending order.
+fn read_rev_range<C, K, E, S>(
+    conn: &mut C,
+    key: K,
+    end: E,
+    start: S,
+    count: usize,
+) -> RedsumerResult<Vec<StreamId>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+    E: ToRedisArgs,
+    S: ToRedisArgs,
+{
+    match conn.xrevrange_count::<_, _, _, _, StreamRangeReply>(key, end, start, count) {
+        Ok(reply) => {
+            debug!(
+                "Total messages read from reverse range: {}",
+                reply.ids.len()
+            );
+            Ok(reply.ids)
+        }
+        Err(e) => {
+            error!("Error reading messages from reverse range: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Get the ID right after *id*, or `None` if *id* is the greatest possible ID.
+pub fn next_id(id: &Id) -> Option<Id> {
+    match id.get_sequence().checked_add(1) {
+        Some(sequence) => Some(Id::new(id.get_milliseconds(), sequence)),
+        None => id
+            .get_milliseconds()
+            .checked_add(1)
+            .map(|milliseconds| Id::new(milliseconds, 0)),
+    }
+}
+
+/// Get the ID right before *id*, or `None` if *id* is `0-0`.
+pub fn previous_id(id: &Id) -> Option<Id> {
+    match id.get_sequence().checked_sub(1) {
+        Some(sequence) => Some(Id::new(id.get_milliseconds(), sequence)),
+        None => id
+            .get_milliseconds()
+            .checked_sub(1)
+            .map(|milliseconds| Id::new(milliseconds, u64::MAX)),
+    }
+}
+
+/// Read all the messages of a stream between *start* and *end*, both inclusive, in ascending order, paging through the range with up to *page_size* messages per request.
+fn read_range_pages<C, K>(
+    conn: &mut C,
+    key: K,
+    start: Id,
+    end: Id,
+    page_size: usize,
+) -> RedsumerResult<Vec<StreamId>>
+where
+    C: Commands,
+    K: ToRedisArgs,
+{
+    let mut messages: Vec<StreamId> = Vec::new();
+    let mut start: Id = start;
+
+    loop {
+        let page: Vec<StreamId> = read_range(conn, &key, start, end, page_size)?;
+        let page_len: usize = page.len();
+
+        let next: Option<Id> = match page.last() {
+            Some(last) => next_id(&last.id.parse::<Id>()?),
+            None => None,
+        };
+        messages.extend(page);
+
+        match next {
+            Some(next) if page_len.ge(&page_size) && next.le(&end) => start = next,
+            _ => break,
+        }
+    }
+
+    debug!("Total messages read from paged range: {}", messages.len());
+
+    Ok(messages)
+}
+
+/// Read up to *count* messages of a stream with an ID greater than *since_id*, waiting up to *block* milliseconds for them to arrive.
+fn read_messages<C, K, ID>(
+    conn: &mut C,
+    key: &K,
+    since_id: ID,
+    count: usize,
+    block: usize,
+) -> RedsumerResult<Vec<StreamId>>
+where
+    C: Commands,
+    K: ToRedisArgs + ToString,
+    ID: ToRedisArgs,
+{
+    let mut options: StreamReadOptions = StreamReadOptions::default().count(count);
+    if block.gt(&0) {
+        options = options.block(block);
+    }
+
+    match conn.xread_options::<_, _, StreamReadReply>(&[key], &[since_id], &options) {
+        Ok(reply) => {
+            let messages: Vec<StreamId> = reply.unwrap_by_key(key);
+            debug!("Total messages read: {}", messages.len());
+            Ok(messages)
+        }
+        Err(e) => {
+            error!("Error reading messages: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// A trait that bundles methods for reading messages from a Redis stream without a consumers group.
+pub trait ReaderCommands {
+    /// Read up to *count* messages of a stream between *start* and *end*, both inclusive, in ascending order, using `XRANGE`.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
+    /// - **start**: The ID to start reading from, which must implement the `ToRedisArgs` trait. Use [`RANGE_START`] to read from the beginning of the stream.
+    /// - **end**: The ID to end reading at, which must implement the `ToRedisArgs` trait. Use [`RANGE_END`] to read until the end of the stream.
+    /// - **count**: The max number of messages to read.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the messages read. Otherwise, a [`RedsumerError`] is returned.
+    fn read_range<K, S, E>(
+        &mut self,
+        key: K,
+        start: S,
+        end: E,
+        count: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        K: ToRedisArgs,
+        S: ToRedisArgs,
+        E: ToRedisArgs;
+
+    /// Read up to *count* messages of a stream between *end* and *start*, both inclusive, in descending order, using `XREVRANGE`.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
+    /// - **end**: The ID to start reading from backwards, which must implement the `ToRedisArgs` trait. Use [`RANGE_END`] to read from the end of the stream.
+    /// - **start**: The ID to end reading at, which must implement the `ToRedisArgs` trait. Use [`RANGE_START`] to read until the beginning of the stream.
+    /// - **count**: The max number of messages to read.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the messages read, from the latest to the oldest. Otherwise, a [`RedsumerError`] is returned.
+    fn read_rev_range<K, E, S>(
+        &mut self,
+        key: K,
+        end: E,
+        start: S,
+        count: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        K: ToRedisArgs,
+        E: ToRedisArgs,
+        S: ToRedisArgs;
+
+    /// Read all the messages of a stream between *start* and *end*, both inclusive, in ascending order, paging through the range with `XRANGE`.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` trait.
+    /// - **start**: The ID to start reading from.
+    /// - **end**: The ID to end reading at.
+    /// - **page_size**: The max number of messages to read per request.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with all the messages read. Otherwise, a [`RedsumerError`] is returned.
+    fn read_range_pages<K>(
+        &mut self,
+        key: K,
+        start: Id,
+        end: Id,
+        page_size: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        K: ToRedisArgs;
+
+    /// Read up to *count* messages of a stream with an ID greater than *since_id*, using `XREAD` without a consumers group.
+    ///
+    /// # Arguments:
+    /// - **key**: The key of the Redis stream, which must implement the `ToRedisArgs` and `ToString` traits.
+    /// - **since_id**: The ID to read after, which must implement the `ToRedisArgs` trait.
+    /// - **count**: The max number of messages to read.
+    /// - **block**: The time in milliseconds to wait for messages to arrive. If it is `0`, the command does not block.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the messages read. Otherwise, a [`RedsumerError`] is returned.
+    fn read_messages<K, ID>(
+        &mut self,
+        key: &K,
+        since_id: ID,
+        count: usize,
+        block: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        K: ToRedisArgs + ToString,
+        ID: ToRedisArgs;
+}
+
+impl<C> ReaderCommands for C
+where
+    C: Commands,
+{
+    fn read_range<K, S, E>(
+        &mut self,
+        key: K,
+        start: S,
+        end: E,
+        count: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        K: ToRedisArgs,
+        S: ToRedisArgs,
+        E: ToRedisArgs,
+    {
+        read_range(self, key, start, end, count)
+    }
+
+    fn read_rev_range<K, E, S>(
+        &mut self,
+        key: K,
+        end: E,
+        start: S,
+        count: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        K: ToRedisArgs,
+        E: ToRedisArgs,
+        S: ToRedisArgs,
+    {
+        read_rev_range(self, key, end, start, count)
+    }
+
+    fn read_range_pages<K>(
+        &mut self,
+        key: K,
+        start: Id,
+        end: Id,
+        page_size: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        K: ToRedisArgs,
+    {
+        read_range_pages(self, key, start, end, page_size)
+    }
+
+    fn read_messages<K, ID>(
+        &mut self,
+        key: &K,
+        since_id: ID,
+        count: usize,
+        block: usize,
+    ) -> RedsumerResult<Vec<StreamId>>
+    where
+        K: ToRedisArgs + ToString,
+        ID: ToRedisArgs,
+    {
+        read_messages(self, key, since_id, count, block)
+    }
+}
+
+#[cfg(test)]
+mod test_read_range {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a stream message reply item.
+    fn message(id: &str, field: &str, value: &str) -> Value {
+        Value::Array(vec![
+            Value::BulkString(id.as_bytes().to_vec()),
+            Value::Array(vec![
+                Value::BulkString(field.as_bytes().to_vec()),
+                Value::BulkString(value.as_bytes().to_vec()),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_read_range_ok() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XRANGE")
+                    .arg(key)
+                    .arg(RANGE_START)
+                    .arg(RANGE_END)
+                    .arg("COUNT")
+                    .arg(2),
+                Ok(Value::Array(vec![
+                    message("1-0", "key", "value-1"),
+                    message("2-0", "key", "value-2"),
+                ])),
+            )]);
+
+        // Read the range:
+        let result: RedsumerResult<Vec<StreamId>> = conn.read_range(key, RANGE_START, RANGE_END, 2);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let messages: Vec<StreamId> = result.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].id, "1-0");
+        assert_eq!(messages[1].id, "2-0");
+    }
+
+    #[test]
+    fn test_read_range_error() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XRANGE")
+                    .arg(key)
+                    .arg("1-0")
+                    .arg(RANGE_END)
+                    .arg("COUNT")
+                    .arg(10),
+                Err(RedisError::from((ErrorKind::ResponseError, "Error"))),
+            )]);
+
+        // Read the range:
+        let result: RedsumerResult<Vec<StreamId>> = conn.read_range(key, "1-0", RANGE_END, 10);
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_read_rev_range {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_read_rev_range_ok() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREVRANGE")
+                    .arg(key)
+                    .arg(RANGE_END)
+                    .arg(RANGE_START)
+                    .arg("COUNT")
+                    .arg(1),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"2-0".to_vec()),
+                    Value::Array(vec![
+                        Value::BulkString(b"key".to_vec()),
+                        Value::BulkString(b"value".to_vec()),
+                    ]),
+                ])])),
+            )]);
+
+        // Read the reverse range:
+        let result: RedsumerResult<Vec<StreamId>> =
+            conn.read_rev_range(key, RANGE_END, RANGE_START, 1);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let messages: Vec<StreamId> = result.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, "2-0");
+    }
+}
+
+#[cfg(test)]
+mod test_read_range_pages {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a stream range reply with the given IDs.
+    fn messages(ids: &[&str]) -> Value {
+        Value::Array(
+            ids.iter()
+                .map(|id| {
+                    Value::Array(vec![
+                        Value::BulkString(id.as_bytes().to_vec()),
+                        Value::Array(vec![
+                            Value::BulkString(b"key".to_vec()),
+                            Value::BulkString(b"value".to_vec()),
+                        ]),
+                    ])
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_read_range_pages_ok() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection with two pages:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("XRANGE")
+                    .arg(key)
+                    .arg("1-0")
+                    .arg("9-0")
+                    .arg("COUNT")
+                    .arg(2),
+                Ok(messages(&["1-0", "2-5"])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XRANGE")
+                    .arg(key)
+                    .arg("2-6")
+                    .arg("9-0")
+                    .arg("COUNT")
+                    .arg(2),
+                Ok(messages(&["3-0"])),
+            ),
+        ]);
+
+        // Read the paged range:
+        let result: RedsumerResult<Vec<StreamId>> =
+            conn.read_range_pages(key, Id::new(1, 0), Id::new(9, 0), 2);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let ids: Vec<String> = result.unwrap().into_iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec!["1-0", "2-5", "3-0"]);
+    }
+
+    #[test]
+    fn test_next_id() {
+        // Verify the result:
+        assert_eq!(next_id(&Id::new(1, 0)), Some(Id::new(1, 1)));
+        assert_eq!(next_id(&Id::new(1, u64::MAX)), Some(Id::new(2, 0)));
+        assert_eq!(next_id(&Id::new(u64::MAX, u64::MAX)), None);
+    }
+
+    #[test]
+    fn test_previous_id() {
+        // Verify the result:
+        assert_eq!(previous_id(&Id::new(1, 1)), Some(Id::new(1, 0)));
+        assert_eq!(previous_id(&Id::new(2, 0)), Some(Id::new(1, u64::MAX)));
+        assert_eq!(previous_id(&Id::default()), None);
+    }
+}
+
+#[cfg(test)]
+mod test_read_messages {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_read_messages_ok() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREAD")
+                    .arg("COUNT")
+                    .arg(10)
+                    .arg("STREAMS")
+                    .arg(key)
+                    .arg("1-0"),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(key.as_bytes().to_vec()),
+                    Value::Array(vec![Value::Array(vec![
+                        Value::BulkString(b"2-0".to_vec()),
+                        Value::Array(vec![
+                            Value::BulkString(b"key".to_vec()),
+                            Value::BulkString(b"value".to_vec()),
+                        ]),
+                    ])]),
+                ])])),
+            )]);
+
+        // Read the messages:
+        let result: RedsumerResult<Vec<StreamId>> = conn.read_messages(&key, "1-0", 10, 0);
+
+        // Verify the result:
+        assert!(result.is_ok());
+
+        let messages: Vec<StreamId> = result.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, "2-0");
+    }
+
+    #[test]
+    fn test_read_messages_blocking_timeout() {
+        // Define the key:
+        let key: &str = "my-key";
+
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREAD")
+                    .arg("BLOCK")
+                    .arg(100)
+                    .arg("COUNT")
+                    .arg(10)
+                    .arg("STREAMS")
+                    .arg(key)
+                    .arg("1-0"),
+                Ok(Value::Nil),
+            )]);
+
+        // Read the messages:
+        let result: RedsumerResult<Vec<StreamId>> = conn.read_messages(&key, "1-0", 10, 100);
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+    }
+}
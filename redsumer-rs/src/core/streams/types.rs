@@ -1,14 +1,300 @@
-/// Stream message identifier. It is used to identify any message in a stream.
-pub type Id = String;
+use std::{fmt::Display, str::FromStr};
+
+use redis::{
+    from_redis_value, streams::StreamId, FromRedisValue, RedisResult, RedisWrite, ToRedisArgs,
+    Value,
+};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use time::OffsetDateTime;
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+
+/// Stream entry identifier, with the `<milliseconds>-<sequence>` format used by Redis. It is used to identify any message in a stream.
+///
+/// Entry IDs are ordered first by their *milliseconds* part and then by their *sequence* part, in the same way Redis does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EntryId {
+    /// Unix time in milliseconds when the entry was added to the stream.
+    milliseconds: u64,
+
+    /// Sequence number to distinguish entries added in the same millisecond.
+    sequence: u64,
+}
+
+impl EntryId {
+    /// Get *milliseconds*.
+    pub fn get_milliseconds(&self) -> u64 {
+        self.milliseconds
+    }
+
+    /// Get *sequence*.
+    pub fn get_sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Get the timestamp when the entry was added to the stream.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with an [`OffsetDateTime`] in UTC. If the *milliseconds* part is out of the supported date range, a [`RedsumerError`] is returned.
+    pub fn timestamp(&self) -> RedsumerResult<OffsetDateTime> {
+        OffsetDateTime::from_unix_timestamp_nanos(i128::from(self.milliseconds) * 1_000_000)
+            .map_err(|e| {
+                RedsumerError::DeserializationError(format!(
+                    "Invalid stream entry ID, timestamp out of range: {e}"
+                ))
+            })
+    }
+
+    /// Build a new instance of [`EntryId`].
+    ///
+    /// # Arguments:
+    /// - **milliseconds**: Unix time in milliseconds.
+    /// - **sequence**: Sequence number within the given millisecond.
+    ///
+    /// # Returns:
+    /// A new instance of [`EntryId`].
+    pub fn new(milliseconds: u64, sequence: u64) -> EntryId {
+        EntryId {
+            milliseconds,
+            sequence,
+        }
+    }
+
+    /// Build a new instance of [`EntryId`] from a timestamp, with *sequence* equal to `0`. It is useful to build range queries.
+    ///
+    /// # Arguments:
+    /// - **timestamp**: The timestamp to convert. Sub-millisecond precision is truncated.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a new instance of [`EntryId`]. If the timestamp is before the Unix epoch, a [`RedsumerError`] is returned.
+    pub fn from_timestamp(timestamp: OffsetDateTime) -> RedsumerResult<EntryId> {
+        let milliseconds: i128 = timestamp.unix_timestamp_nanos() / 1_000_000;
+
+        match u64::try_from(milliseconds) {
+            Ok(milliseconds) => Ok(EntryId::new(milliseconds, 0)),
+            Err(_) => Err(RedsumerError::DeserializationError(format!(
+                "Invalid stream entry ID, timestamp before Unix epoch: {timestamp}"
+            ))),
+        }
+    }
+}
+
+impl Display for EntryId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.milliseconds, self.sequence)
+    }
+}
+
+impl FromStr for EntryId {
+    type Err = RedsumerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || {
+            RedsumerError::DeserializationError(format!(
+                "Invalid stream entry ID, expected <milliseconds>-<sequence>, got: {s}"
+            ))
+        };
+
+        // Both parts must be plain digits, since integer parsing also accepts a leading sign:
+        let parse = |part: &str| match !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit())
+        {
+            true => part.parse::<u64>().map_err(|_| error()),
+            false => Err(error()),
+        };
+
+        let (milliseconds, sequence) = s.split_once('-').ok_or_else(error)?;
+
+        Ok(EntryId::new(parse(milliseconds)?, parse(sequence)?))
+    }
+}
+
+/// Convert a `&str` into an [`EntryId`] instance.
+impl TryFrom<&str> for EntryId {
+    type Error = RedsumerError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Convert a [`String`] into an [`EntryId`] instance.
+impl TryFrom<String> for EntryId {
+    type Error = RedsumerError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl ToRedisArgs for EntryId {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.to_string().as_bytes())
+    }
+}
+
+impl FromRedisValue for EntryId {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        Ok(from_redis_value::<String>(v)?.parse()?)
+    }
+}
+
+/// Serialize an [`EntryId`] with the `<milliseconds>-<sequence>` format.
+impl Serialize for EntryId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserialize an [`EntryId`] from the `<milliseconds>-<sequence>` format.
+impl<'de> Deserialize<'de> for EntryId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Stream message identifier. It is an alias for [`EntryId`].
+pub type Id = EntryId;
 
 /// Represents the latest message ID that is pending to be processed. It is used to the read pending messages operation.
-pub type LatestPendingMessageId = Id;
+pub type LatestPendingMessageId = String;
 
 /// Represents the next message ID to claim. It is used to the claim messages operation.
-pub type NextIdToClaim = Id;
+pub type NextIdToClaim = String;
 
 /// Represents the total time in milliseconds that elapsed since the last message was delivered to the consumer.
 pub type LastDeliveredMilliseconds = usize;
 
 /// Represents the total number of times that a message was delivered to any consumer in the group.
 pub type TotalTimesDelivered = usize;
+
+/// Represents the pending messages with the latest pending message ID, and the claimed messages with the next ID to claim, when both are read in a single pipeline.
+pub type PendingAndClaimedMessages = (
+    (Vec<StreamId>, LatestPendingMessageId),
+    (Vec<StreamId>, NextIdToClaim),
+);
+
+/// Represents the ownership of a pending message: whether it belongs to the consumer, and its delivery metadata when it does.
+pub type MessageOwnership = (
+    bool,
+    Option<LastDeliveredMilliseconds>,
+    Option<TotalTimesDelivered>,
+);
+
+#[cfg(test)]
+mod test_entry_id {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_entry_id_parse_ok() {
+        // Parse a valid entry ID:
+        let id: RedsumerResult<EntryId> = "1526919030474-55".parse();
+
+        // Verify the result:
+        assert!(id.is_ok());
+        assert_eq!(id.as_ref().unwrap().get_milliseconds(), 1526919030474);
+        assert_eq!(id.as_ref().unwrap().get_sequence(), 55);
+        assert_eq!(id.unwrap().to_string(), "1526919030474-55");
+    }
+
+    #[test]
+    fn test_entry_id_parse_error() {
+        // Verify invalid entry IDs:
+        assert!("1526919030474".parse::<EntryId>().is_err());
+        assert!("abc-1".parse::<EntryId>().is_err());
+        assert!("1-abc".parse::<EntryId>().is_err());
+        assert!("-1".parse::<EntryId>().is_err());
+        assert!(EntryId::try_from("*").is_err());
+    }
+
+    #[test]
+    fn test_entry_id_parse_sign_error() {
+        // Verify entry IDs with signed parts:
+        assert!("+1-+2".parse::<EntryId>().is_err());
+        assert!("+1-2".parse::<EntryId>().is_err());
+        assert!("1-+2".parse::<EntryId>().is_err());
+        assert!("1-".parse::<EntryId>().is_err());
+    }
+
+    #[test]
+    fn test_entry_id_ordering() {
+        // Define entry IDs:
+        let a: EntryId = EntryId::new(10, 5);
+        let b: EntryId = EntryId::new(10, 6);
+        let c: EntryId = EntryId::new(11, 0);
+
+        // Verify the ordering is numeric and not lexicographic:
+        assert!(a < b);
+        assert!(b < c);
+        assert!(EntryId::new(9, 0) < EntryId::new(10, 0));
+        assert_eq!(EntryId::default(), EntryId::new(0, 0));
+    }
+
+    #[test]
+    fn test_entry_id_timestamp() {
+        // Define an entry ID:
+        let id: EntryId = EntryId::new(1_700_000_000_123, 2);
+
+        // Verify the timestamp:
+        assert_eq!(
+            id.timestamp().unwrap(),
+            datetime!(2023-11-14 22:13:20.123 UTC)
+        );
+        assert!(EntryId::new(u64::MAX, 0).timestamp().is_err());
+    }
+
+    #[test]
+    fn test_entry_id_from_timestamp() {
+        // Build an entry ID from a timestamp:
+        let id: RedsumerResult<EntryId> =
+            EntryId::from_timestamp(datetime!(2023-11-14 22:13:20.123456 UTC));
+
+        // Verify the result:
+        assert!(id.is_ok());
+        assert_eq!(id.unwrap(), EntryId::new(1_700_000_000_123, 0));
+        assert!(EntryId::from_timestamp(datetime!(1969-12-31 23:59:59 UTC)).is_err());
+    }
+
+    #[test]
+    fn test_entry_id_serde() {
+        // Serialize an entry ID:
+        assert_eq!(
+            serde_json::to_string(&EntryId::new(1, 2)).unwrap(),
+            "\"1-2\""
+        );
+
+        // Deserialize an entry ID:
+        assert_eq!(
+            serde_json::from_str::<EntryId>("\"3-4\"").unwrap(),
+            EntryId::new(3, 4)
+        );
+        assert!(serde_json::from_str::<EntryId>("\"3\"").is_err());
+    }
+
+    #[test]
+    fn test_entry_id_redis_value() {
+        // Convert a Redis value into an entry ID:
+        let id: RedisResult<EntryId> =
+            EntryId::from_redis_value(&Value::BulkString(b"1-2".to_vec()));
+
+        // Verify the result:
+        assert_eq!(id.unwrap(), EntryId::new(1, 2));
+        assert_eq!(EntryId::new(1, 2).to_redis_args(), vec![b"1-2".to_vec()]);
+    }
+}
@@ -0,0 +1,143 @@
+use redis::{from_redis_value, Value};
+use serde_json::Value as JsonValue;
+use tracing::error;
+
+#[allow(unused_imports)]
+use super::result::{RedsumerError, RedsumerResult};
+
+/// Convert a Redis [`Value`] into a [`JsonValue`].
+fn to_json_value(value: &Value) -> RedsumerResult<JsonValue> {
+    let raw: String = from_redis_value(value)?;
+
+    serde_json::from_str::<JsonValue>(&raw).map_err(|e| {
+        error!("Error parsing Redis value as JSON: {:?}", e);
+        RedsumerError::DeserializationError(format!("Value is not a valid JSON document: {e}"))
+    })
+}
+
+/// Convert a Redis [`Value`] into an optional [`JsonValue`]. A [`Value::Nil`] is converted into `None`.
+fn to_optional_json_value(value: &Value) -> RedsumerResult<Option<JsonValue>> {
+    match value {
+        Value::Nil => Ok(None),
+        v => to_json_value(v).map(Some),
+    }
+}
+
+/// A trait to handle Redis values as semi-structured data.
+pub trait ValueHandler {
+    /// Convert a Redis value into a [`serde_json::Value`].
+    ///
+    /// Useful to inspect message fields that contain a JSON document without declaring a full struct type up front.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a [`serde_json::Value`]. If the value can not be read as a string or it is not a valid JSON document, a [`RedsumerError`] is returned.
+    fn to_json_value(&self) -> RedsumerResult<JsonValue>;
+
+    /// Convert a Redis value into an optional [`serde_json::Value`].
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `None` if the value is [`Value::Nil`], otherwise `Some` with a [`serde_json::Value`]. If the value is not a valid JSON document, a [`RedsumerError`] is returned.
+    fn to_optional_json_value(&self) -> RedsumerResult<Option<JsonValue>>;
+}
+
+impl ValueHandler for Value {
+    fn to_json_value(&self) -> RedsumerResult<JsonValue> {
+        to_json_value(self)
+    }
+
+    fn to_optional_json_value(&self) -> RedsumerResult<Option<JsonValue>> {
+        to_optional_json_value(self)
+    }
+}
+
+#[cfg(test)]
+mod test_to_json_value {
+    use redis::ErrorKind;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_to_json_value_from_bulk_string_ok() {
+        // Define a JSON document as bulk string:
+        let value: Value = Value::BulkString(br#"{"id": 1, "tags": ["a", "b"]}"#.to_vec());
+
+        // Convert the value:
+        let result: RedsumerResult<JsonValue> = value.to_json_value();
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), json!({"id": 1, "tags": ["a", "b"]}));
+    }
+
+    #[test]
+    fn test_to_json_value_from_int_ok() {
+        // Define an integer value:
+        let value: Value = Value::Int(42);
+
+        // Convert the value:
+        let result: RedsumerResult<JsonValue> = value.to_json_value();
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), json!(42));
+    }
+
+    #[test]
+    fn test_to_json_value_invalid_document() {
+        // Define an invalid JSON document:
+        let value: Value = Value::BulkString(b"{not-json".to_vec());
+
+        // Convert the value:
+        let result: RedsumerResult<JsonValue> = value.to_json_value();
+
+        // Verify the result:
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::TypeError);
+    }
+
+    #[test]
+    fn test_to_json_value_from_nil() {
+        // Convert a nil value:
+        let result: RedsumerResult<JsonValue> = Value::Nil.to_json_value();
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_to_optional_json_value {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_to_optional_json_value_from_nil() {
+        // Convert a nil value:
+        let result: RedsumerResult<Option<JsonValue>> = Value::Nil.to_optional_json_value();
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_to_optional_json_value_some() {
+        // Define a JSON document as simple string:
+        let value: Value = Value::SimpleString(r#"{"ok": true}"#.to_string());
+
+        // Convert the value:
+        let result: RedsumerResult<Option<JsonValue>> = value.to_optional_json_value();
+
+        // Verify the result:
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Some(json!({"ok": true})));
+    }
+}
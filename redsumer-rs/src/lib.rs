@@ -76,6 +76,8 @@
 //! Create a new consumer instance and consume messages from stream:
 //!
 //! ```rust,no_run
+//! use std::time::Duration;
+//!
 //! use redsumer::prelude::*;
 //! use redsumer::redis::StreamId;
 //!
@@ -88,12 +90,11 @@
 //!     let stream_name: &str = "my-stream";
 //!     let group_name: &str = "group-name";
 //!     let consumer_name: &str = "consumer";
-//!     let initial_stream_id: &str = "0-0";
 //!     let min_idle_time_milliseconds: usize = 1000;
 //!     let new_messages_count: usize = 3;
 //!     let pending_messages_count: usize = 2;
 //!     let claimed_messages_count: usize = 1;
-//!     let block: usize = 5;
+//!     let block: Duration = Duration::from_secs(5);
 //!
 //!     let args: ClientArgs = ClientArgs::new(
 //!         credentials,
@@ -123,7 +124,7 @@
 //!     let consumer_result: RedsumerResult<Consumer> = Consumer::new(
 //!         args,
 //!         config,
-//!         Some(initial_stream_id.to_string()),
+//!         StartPosition::Beginning,
 //!     );
 //!
 //!     let mut consumer: Consumer = consumer_result.unwrap_or_else(|error| {
@@ -135,8 +136,8 @@
 //!             panic!("Error consuming messages from stream: {:?}", error);
 //!         });
 //!
-//!         for message in consume_reply.get_messages() {
-//!             if consumer.is_still_mine(&message.id).unwrap_or_else(|error| {
+//!         for (message, token) in consume_reply.get_messages().iter().zip(consume_reply.get_ack_tokens()) {
+//!             if consumer.is_still_mine(token.get_id()).unwrap_or_else(|error| {
 //!                 panic!(
 //!                     "Error checking if message is still in consumer pending list: {:?}", error
 //!                 );
@@ -145,7 +146,7 @@
 //!                 println!("Processing message: {:?}", message);
 //!                 // ...
 //!
-//!                 let ack_reply: AckMessageReply = consumer.ack(&message.id).await.unwrap_or_else(|error| {
+//!                 let ack_reply: AckMessageReply = consumer.ack(token).await.unwrap_or_else(|error| {
 //!                     panic!("Error acknowledging message: {:?}", error);
 //!                 });
 //!
@@ -160,15 +161,32 @@
 //!
 //! In this example, the [consume](consumer::Consumer::consume) method is called in a loop to consume messages from the stream.
 //! The [consume](consumer::Consumer::consume) method returns a vector of [StreamId](redis::StreamId) instances. Each [StreamId](redis::StreamId) instance represents a message in the stream.
+//! Every message comes with an [AckToken](consumer::AckToken), which holds the message [EntryId](consumer::EntryId) and ties it to the stream, consumers group and consumer it was consumed by.
 //! The [is_still_mine](consumer::Consumer::is_still_mine) method is used to check if the message is still in the consumer pending list.
-//! If it is, the message is processed and then acknowledged using the [ack](consumer::Consumer::ack) method.
+//! If it is, the message is processed and then acknowledged using the [ack](consumer::Consumer::ack) method, which only accepts the [AckToken](consumer::AckToken) of the message.
 //! The [ack](consumer::Consumer::ack) method returns a boolean indicating if the message was successfully acknowledged.
 //!
 //! The main objective of this message consumption strategy is to minimize the possibility that two or more consumers from the same consumer group operating simultaneously consume the same message at the same time.
 //! Knowing that it is a complex problem with no definitive solution, including business logic in the message processing instance will always improve results.
 //!
+//! The consumer configuration can also be created with the [ConsumerConfigBuilder](consumer::ConsumerConfigBuilder), which only requires the stream, group and consumer names and uses sensible defaults for the remaining options:
+//!
+//! ```rust
+//! use redsumer::prelude::*;
+//!
+//! let config: ConsumerConfig = ConsumerConfig::builder("my-stream", "group-name", "consumer")
+//!     .new_messages_count(3)
+//!     .min_idle_time(1000)
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
 //! Take a look at the [examples](https://github.com/enerBit/redsumer-rs/tree/main/examples) directory to see more use cases.
 //!
+//! #### Low-level commands:
+//!
+//! The [low_level] module exposes the command traits used by the producer and the consumer, like [ConsumerCommands](low_level::ConsumerCommands) and [ProducerCommands](low_level::ProducerCommands), so they can be driven on your own connections, like cluster connections or mocks.
+//!
 //! #### Utilities from [redis] crate:
 //!
 //! The [redis] module provides utilities from the [redis](https://docs.rs/redis) crate. You can use these utilities to interact with Redis values and errors.
@@ -177,6 +195,10 @@
 //!
 //! The [Value](redis::Value) enum represents a Redis value. It can be converted to a specific type using the [from_redis_value](redis::from_redis_value) function. This function can be imported from the [redis] module.
 //!
+//! #### Inspect a [Value](redis::Value) as JSON:
+//!
+//! When a message field contains a JSON document, the [ValueHandler](value::ValueHandler) trait allows you to convert it into a [JsonValue](value::JsonValue) with [to_json_value](value::ValueHandler::to_json_value) or [to_optional_json_value](value::ValueHandler::to_optional_json_value), without declaring a full struct type up front.
+//!
 //! ## Contributing
 //!
 //! We welcome contributions to **redsumer** project. Here are some ways you can contribute:
@@ -186,45 +208,272 @@
 //! - **Pull Requests**: If you've fixed a bug or implemented a new feature, we'd love to see your work! Please submit a pull request. Make sure your code follows the existing style and all tests pass.
 //!
 //! Thank you for your interest in improving **redsumer**!
+// The mocked commands of the tests pass the stream options by reference, like the commands under test:
+#![cfg_attr(test, allow(clippy::needless_borrows_for_generic_args))]
+// No minimum Rust version is declared, so `%` is used instead of `is_multiple_of`, stable since Rust 1.87:
+#![allow(clippy::manual_is_multiple_of)]
 mod core;
 mod redsumer;
 
+pub mod admin {
+    //! Resources to manage Redis streams and consumers groups.
+    pub use super::redsumer::admin::{
+        Admin, ArchiveReply, DestroyGroupReply, MigrateGroupReply, TransferReply,
+    };
+}
+
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    //! Synchronous producers and consumers for applications without an async runtime.
+    pub use super::redsumer::blocking::{BlockingConsumer, BlockingProducer};
+}
+
+pub mod checkpoint {
+    //! Resources to save and restore the position of consumers across restarts.
+    pub use super::redsumer::checkpoint::{
+        Checkpoint, CheckpointStore, RedisCheckpointStore, DEFAULT_CHECKPOINT_KEY_PREFIX,
+    };
+}
+
 pub mod client {
     //! Resources to manage the Redis client.
-    pub use super::core::client::{ClientArgs, ClientCredentials, CommunicationProtocol};
+    pub use super::core::client::{
+        ClientArgs, ClientCredentials, CommunicationProtocol, Endpoint, FailoverClient,
+        NamedClient, SocketOptions, DEFAULT_CONNECT_RETRY_DELAY, DEFAULT_DB, DEFAULT_PORT,
+    };
+    pub use super::core::connection::{
+        ServerCapabilities, ServerFeature, ServerVersion, StreamConnection,
+    };
+    pub use super::core::credentials::{CredentialsFuture, CredentialsProvider, RotatingClient};
+    pub use super::core::lifecycle::{ConnectionEvent, ConnectionEventCallback, MonitoredClient};
+}
+
+pub mod clock {
+    //! Resources to control the time source of consumers, like in deterministic tests.
+    pub use super::redsumer::clock::{Clock, ClockFuture, ManualClock, SystemClock};
 }
 
 pub mod consumer {
     //! Resources to consume messages from a Redis stream.
-    pub use super::core::streams::types::{Id, LastDeliveredMilliseconds, TotalTimesDelivered};
+    pub use super::core::streams::types::{
+        EntryId, Id, LastDeliveredMilliseconds, TotalTimesDelivered,
+    };
     pub use super::redsumer::consumer::{
-        AckMessageReply, ClaimMessagesOptions, ConsumeMessagesReply, Consumer, ConsumerConfig,
-        IsStillMineReply, ReadNewMessagesOptions, ReadPendingMessagesOptions,
+        AckDeadlineAction, AckDeadlineCallback, AckManyReply, AckMessageReply, AckToken,
+        ClaimCallback, ClaimFilter, ClaimMessagesOptions, ClaimMode, ConsumeMessagesReply,
+        Consumer, ConsumerCallbacks, ConsumerConfig, ConsumerConfigBuilder, ConsumerSnapshot,
+        ConsumerStats, ErrorCallback, GroupLag, IdleStrategy, IsStillMineReply, MessageCallback,
+        MessagesKind, ReadNewMessagesOptions, ReadPendingMessagesOptions, RemoveConsumerReply,
+        StartPosition, DEFAULT_BLOCK, DEFAULT_MESSAGES_COUNT, DEFAULT_MIN_IDLE_TIME,
+    };
+    pub use super::redsumer::guard::{GuardDropAction, MessageGuard};
+    pub use super::redsumer::handler::{
+        decode, HandleMessagesReply, HandlerOutcome, MessageHandler, MessageMeta,
     };
+    pub use super::redsumer::traits::MessageSource;
+}
+
+pub mod coordinator {
+    //! Resources to coordinate the assignment of shards between consumer instances.
+    pub use super::redsumer::coordinator::{
+        Assignment, Coordinator, CoordinatorConfig, DEFAULT_LEASE_TTL,
+    };
+}
+
+pub mod dump {
+    //! Resources to import messages from a JSON Lines dump.
+    pub use super::redsumer::dump::{DumpedMessage, ImportIdMode, ImportSummary};
+}
+
+pub mod dynamic {
+    //! Resources to consume messages from the streams matching a pattern, discovered at runtime.
+    pub use super::redsumer::dynamic::{
+        DynamicConsumeReply, DynamicConsumer, MembershipCallback, MembershipEvent,
+        DEFAULT_RESCAN_INTERVAL,
+    };
+}
+
+pub mod envelope {
+    //! Resources to write and read standard message headers alongside the business payload.
+    pub use super::redsumer::envelope::{
+        Envelope, Headers, CONTENT_TYPE_HEADER, CORRELATION_ID_HEADER, MESSAGE_ID_HEADER,
+        PRODUCED_AT_HEADER, RESERVED_HEADERS, SCHEMA_VERSION_HEADER,
+    };
+}
+
+pub mod inspector {
+    //! Resources to inspect stream contents without a consumers group.
+    pub use super::redsumer::inspector::StreamInspector;
+}
+
+pub mod interceptor {
+    //! Resources to intercept producer and consumer operations.
+    pub use super::redsumer::interceptor::{Interceptor, MessageFields};
+}
+
+pub mod low_level {
+    //! Low-level command traits that drive Redis streams on any connection implementing [`Commands`](::redis::Commands), like a cluster connection, a pooled connection or a mock, without the high-level [`Producer`](crate::producer::Producer) and [`Consumer`](crate::consumer::Consumer).
+    //!
+    //! Every trait is implemented for all the types implementing [`Commands`](::redis::Commands), so importing it is enough to call its methods on a connection. The methods run a single command or pipeline and do not apply retries, interceptors, metrics or cursors, which remain responsibility of the caller. Messages are not acked by raw ID here: they are acked with the [`AckToken`](crate::consumer::AckToken) issued by the consumer that consumed them.
+    //!
+    //! ```rust
+    //! use redis::cmd;
+    //! use redis_test::{MockCmd, MockRedisConnection};
+    //! use redsumer::low_level::ConsumerCommands;
+    //!
+    //! let mut conn = MockRedisConnection::new(vec![MockCmd::new::<_, i64>(
+    //!     cmd("EXISTS").arg("my-stream"),
+    //!     Ok(1),
+    //! )]);
+    //!
+    //! assert!(conn.verify_if_stream_exists("my-stream").is_ok());
+    //! ```
+    pub use super::core::checkpoint::CheckpointCommands;
+    pub use super::core::connection::ServerCommands;
+    pub use super::core::coordination::CoordinationCommands;
+    pub use super::core::scripting::ScriptCommands;
+    pub use super::core::streams::consumer::{ConsumerCommands, BEGINNING_OF_TIME_ID};
+    pub use super::core::streams::discovery::DiscoveryCommands;
+    pub use super::core::streams::producer::ProducerCommands;
+    pub use super::core::streams::reader::{ReaderCommands, RANGE_END, RANGE_START};
+    pub use super::core::streams::types::{
+        LatestPendingMessageId, MessageOwnership, NextIdToClaim, PendingAndClaimedMessages,
+    };
+}
+
+pub mod metrics {
+    //! Resources to instrument producers and consumers.
+    pub use super::redsumer::metrics::{
+        MetricsRecorder, NoopMetricsRecorder, Operation, RateMeter, SlowOperationThresholds,
+    };
+}
+
+pub mod mirror {
+    //! Resources to mirror a stream between Redis servers.
+    pub use super::redsumer::mirror::Mirror;
+}
+
+pub mod notifier {
+    //! Resources to be notified of new messages with RESP3 push messages.
+    pub use super::redsumer::notifier::{NotifierEvent, StreamNotifier};
+}
+
+pub mod priority {
+    //! Resources to consume several streams with priorities.
+    pub use super::redsumer::priority::{PriorityConsumeReply, PriorityConsumer, Scheduling};
 }
 
 pub mod producer {
     //! Resources to produce messages in a Redis stream.
-    pub use super::core::streams::types::Id;
-    pub use super::redsumer::producer::{ProduceMessageReply, Producer, ProducerConfig};
+    pub use super::core::streams::types::{EntryId, Id};
+    pub use super::redsumer::buffer::{
+        BufferedProducer, DEFAULT_BUFFER_SIZE, DEFAULT_FLUSH_INTERVAL,
+    };
+    pub use super::redsumer::producer::{
+        BulkProduceSummary, FieldCodec, ProduceMessageReply, Producer, ProducerConfig,
+        ProducerConfigBuilder, TrimPolicy, TrimmingPolicy, WaitPolicy, DEFAULT_MAX_RETRIES,
+        DEFAULT_RETRY_DELAY,
+    };
+    pub use super::redsumer::traits::MessageSink;
+}
+
+pub mod propagation {
+    //! Resources to propagate the W3C trace context through message fields.
+    pub use super::redsumer::propagation::{
+        TraceContext, TraceContextExt, TraceContextPropagator, TRACEPARENT_FIELD, TRACESTATE_FIELD,
+    };
+}
+
+pub mod reader {
+    //! Resources to read messages from a Redis stream without a consumers group.
+    pub use super::redsumer::reader::StreamReader;
+}
+
+pub mod replayer {
+    //! Resources to replay historical messages of a Redis stream.
+    pub use super::redsumer::replayer::{
+        ProgressCallback, ReplayProgress, Replayer, DEFAULT_REPLAY_PAGE_SIZE,
+    };
 }
 
 pub mod redis {
     //! Utilities from [redis] crate.
-    pub use redis::streams::StreamId;
+    pub use super::core::streams::consumer::UnwrapStreamReadReply;
+    pub use redis::streams::{StreamId, StreamReadReply};
     pub use redis::{from_redis_value, ErrorKind, FromRedisValue, RedisError, ToRedisArgs, Value};
 }
 
+pub mod schema {
+    //! Declarative schemas to validate consumed messages.
+    pub use super::redsumer::schema::{FieldType, MessageSchema, SchemaViolation, ViolationKind};
+}
+
+pub mod scripting {
+    //! Resources to run Lua scripts for custom atomic operations.
+    pub use super::core::scripting::LuaScript;
+    pub use super::redsumer::scripting::ScriptRegistry;
+}
+
+pub mod testing {
+    //! Resources to test applications without a Redis server.
+    #[cfg(feature = "test-harness")]
+    pub use super::redsumer::harness::{RedisTestHarness, DEFAULT_REDIS_IMAGE};
+    pub use super::redsumer::testing::{InMemoryConsumer, InMemoryStream};
+}
+
+pub mod validation {
+    //! Resources to validate messages before they are produced.
+    pub use super::redsumer::validation::{MaxSizeValidator, RequiredFieldsValidator, Validator};
+}
+
+pub mod versioning {
+    //! Resources to deserialize messages of several schema versions.
+    pub use super::redsumer::versioning::{DeserializerFn, VersionedDeserializer};
+}
+
+pub mod value {
+    //! Utilities to handle Redis values as semi-structured data.
+    pub use super::core::value::ValueHandler;
+    pub use serde_json::Value as JsonValue;
+}
+
+pub mod runtime {
+    //! Resources to run redsumer on any async runtime.
+    pub use super::core::blocking::{set_runtime, BlockingTask, Runtime, RuntimeFuture};
+}
+
 pub mod results {
     //! The result types used in redsumer.
-    pub use super::core::result::{RedsumerError, RedsumerResult};
+    pub use super::core::result::{ErrorClass, RedsumerError, RedsumerResult};
 }
 
 pub mod prelude {
     //! A global import for crate resources.
+    pub use super::admin::*;
+    #[cfg(feature = "blocking")]
+    pub use super::blocking::*;
+    pub use super::checkpoint::*;
     pub use super::client::*;
+    pub use super::clock::*;
     pub use super::consumer::*;
+    pub use super::coordinator::*;
+    pub use super::dump::*;
+    pub use super::envelope::*;
+    pub use super::inspector::*;
+    pub use super::interceptor::*;
+    pub use super::metrics::*;
+    pub use super::mirror::*;
+    pub use super::notifier::*;
     pub use super::producer::*;
+    pub use super::propagation::*;
+    pub use super::reader::*;
     pub use super::redis::*;
+    pub use super::replayer::*;
     pub use super::results::*;
+    pub use super::runtime::*;
+    pub use super::schema::*;
+    pub use super::scripting::*;
+    pub use super::validation::*;
+    pub use super::value::*;
+    pub use super::versioning::*;
 }
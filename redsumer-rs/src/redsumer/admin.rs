@@ -0,0 +1,928 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use redis::{
+    streams::{StreamAddOptions, StreamId, StreamTrimOptions, StreamTrimmingMode},
+    Client, Commands, ErrorKind,
+};
+use tracing::{debug, info};
+
+#[allow(unused_imports)]
+use crate::core::{
+    client::{namespaced_key, strip_namespace, ClientArgs, RedisClientBuilder},
+    connection::VerifyConnection,
+    result::{RedsumerError, RedsumerResult},
+    streams::{
+        consumer::ConsumerCommands,
+        discovery::DiscoveryCommands,
+        producer::{message_to_items, ProducerCommands},
+        reader::{next_id, previous_id, ReaderCommands, RANGE_END, RANGE_START},
+        types::Id,
+    },
+};
+
+/// Number of messages read per request when acknowledged messages are archived.
+const ARCHIVE_PAGE_SIZE: usize = 100;
+
+/// Number of pending messages transferred per request between consumers.
+const TRANSFER_PAGE_SIZE: usize = 100;
+
+/// A reply to destroy a consumers group.
+#[derive(Debug, Clone)]
+pub struct DestroyGroupReply {
+    /// A boolean value indicating if the consumers group was destroyed.
+    was_destroyed: bool,
+}
+
+impl DestroyGroupReply {
+    /// Get **was destroyed**. If the consumers group did not exist, it is `false`.
+    pub fn was_destroyed(&self) -> bool {
+        self.was_destroyed
+    }
+}
+
+/// Convert a boolean value into a [`DestroyGroupReply`] instance.
+impl From<bool> for DestroyGroupReply {
+    fn from(was_destroyed: bool) -> Self {
+        DestroyGroupReply { was_destroyed }
+    }
+}
+
+/// A reply to archive the acknowledged messages of a stream.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveReply {
+    /// Number of messages copied to the archive stream.
+    archived: usize,
+
+    /// Number of messages deleted from the stream.
+    deleted: usize,
+}
+
+impl ArchiveReply {
+    /// Get the number of **archived** messages, copied to the archive stream.
+    pub fn get_archived(&self) -> usize {
+        self.archived
+    }
+
+    /// Get the number of **deleted** messages from the stream. It is `0` if the messages were not deleted after copying them.
+    pub fn get_deleted(&self) -> usize {
+        self.deleted
+    }
+}
+
+/// A reply to transfer the pending messages of a consumer to another one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferReply {
+    /// Number of messages transferred to the target consumer.
+    transferred: usize,
+}
+
+impl TransferReply {
+    /// Get the number of **transferred** messages to the target consumer.
+    pub fn get_transferred(&self) -> usize {
+        self.transferred
+    }
+}
+
+/// A reply to migrate a consumers group.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrateGroupReply {
+    /// Last delivered ID of the old consumers group, where the new consumers group was created.
+    last_delivered_id: String,
+
+    /// Number of pending messages copied to the new consumers group.
+    copied: usize,
+}
+
+impl MigrateGroupReply {
+    /// Get the **last delivered id** of the old consumers group, where the new consumers group was created.
+    pub fn get_last_delivered_id(&self) -> &str {
+        &self.last_delivered_id
+    }
+
+    /// Get the number of pending messages **copied** to the new consumers group. It is `0` if the pending messages were not copied.
+    pub fn get_copied(&self) -> usize {
+        self.copied
+    }
+}
+
+/// Create a new consumers group at the last delivered ID of an old one and optionally copy the pending messages of the old group, page by page, keeping the consumer that owns every message.
+fn migrate_consumer_group<C>(
+    conn: &mut C,
+    stream_name: &str,
+    old_group_name: &str,
+    new_group_name: &str,
+    copy_pending: bool,
+) -> RedsumerResult<MigrateGroupReply>
+where
+    C: Commands,
+{
+    let mut reply: MigrateGroupReply = MigrateGroupReply {
+        last_delivered_id: conn
+            .get_consumer_group_info(stream_name, old_group_name)?
+            .last_delivered_id,
+        copied: 0,
+    };
+
+    if !conn.create_consumer_group(stream_name, new_group_name, &reply.last_delivered_id)? {
+        return Err(RedsumerError::from((
+            ErrorKind::ClientError,
+            "Consumer group already exists",
+            format!("Group {new_group_name} already exists in stream {stream_name}"),
+        )));
+    }
+
+    debug!(
+        "Consumers group {new_group_name} created at {}",
+        reply.last_delivered_id
+    );
+
+    let mut next: Option<Id> = match copy_pending {
+        true => Some(Id::default()),
+        false => None,
+    };
+    while let Some(start) = next {
+        let page: Vec<(String, String)> = conn.get_group_pending_ids(
+            stream_name,
+            old_group_name,
+            start,
+            RANGE_END,
+            TRANSFER_PAGE_SIZE,
+        )?;
+
+        let mut ids_by_consumer: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (id, consumer) in page.iter() {
+            ids_by_consumer
+                .entry(consumer.as_str())
+                .or_default()
+                .push(id.as_str());
+        }
+        for (consumer, ids) in ids_by_consumer.iter() {
+            reply.copied += conn
+                .force_claim_messages(stream_name, new_group_name, consumer, ids)?
+                .len();
+        }
+
+        next = match (page.len().ge(&TRANSFER_PAGE_SIZE), page.last()) {
+            (true, Some((latest, _))) => next_id(&latest.parse()?),
+            _ => None,
+        };
+    }
+
+    info!(
+        "Consumers group {old_group_name} migrated to {new_group_name} at {}, total pending messages copied: {}",
+        reply.last_delivered_id, reply.copied
+    );
+
+    Ok(reply)
+}
+
+/// Transfer all the pending messages of a consumer to another one, page by page, calling *on_progress* with the total transferred after every page.
+fn transfer_pending_messages<C, F>(
+    conn: &mut C,
+    stream_name: &str,
+    group_name: &str,
+    from_consumer: &str,
+    to_consumer: &str,
+    mut on_progress: F,
+) -> RedsumerResult<TransferReply>
+where
+    C: Commands,
+    F: FnMut(usize),
+{
+    let mut reply: TransferReply = TransferReply::default();
+
+    let mut next: Option<Id> = Some(Id::default());
+    while let Some(start) = next {
+        let page: Vec<String> = conn.get_consumer_pending_ids(
+            stream_name,
+            group_name,
+            from_consumer,
+            0,
+            start,
+            RANGE_END,
+            TRANSFER_PAGE_SIZE,
+        )?;
+
+        // `XCLAIM ... JUSTID` transfers the messages without reading their fields:
+        reply.transferred += conn
+            .renew_messages(stream_name, group_name, to_consumer, 0, &page)?
+            .len();
+        debug!(
+            "Total messages transferred from {from_consumer} to {to_consumer}: {}",
+            reply.transferred
+        );
+        on_progress(reply.transferred);
+
+        next = match (page.len().ge(&TRANSFER_PAGE_SIZE), page.last()) {
+            (true, Some(latest)) => next_id(&latest.parse()?),
+            _ => None,
+        };
+    }
+
+    info!(
+        "Total messages transferred from {from_consumer} to {to_consumer}: {}",
+        reply.transferred
+    );
+
+    Ok(reply)
+}
+
+/// Copy the messages of a stream acknowledged by all its consumers groups to an archive stream, after the latest message archived, and optionally delete them from the stream.
+fn archive_acknowledged_messages<C>(
+    conn: &mut C,
+    stream_name: &str,
+    archive_stream_name: &str,
+    delete: bool,
+) -> RedsumerResult<ArchiveReply>
+where
+    C: Commands,
+{
+    let mut reply: ArchiveReply = ArchiveReply::default();
+
+    let first_unacknowledged: Id = match conn.get_first_unacknowledged_id(stream_name)? {
+        Some(id) => id,
+        None => {
+            debug!("Stream {stream_name} has no consumers groups");
+            return Ok(reply);
+        }
+    };
+    let end: Id = match previous_id(&first_unacknowledged) {
+        Some(end) => end,
+        None => return Ok(reply),
+    };
+
+    let mut next: Option<Id> = match conn
+        .read_rev_range(archive_stream_name, RANGE_END, RANGE_START, 1)?
+        .first()
+    {
+        Some(latest) => next_id(&latest.id.parse()?),
+        None => Some(Id::default()),
+    };
+
+    while let Some(start) = next.filter(|id| id.le(&end)) {
+        let page: Vec<StreamId> = conn.read_range(stream_name, start, end, ARCHIVE_PAGE_SIZE)?;
+
+        let mut latest: Option<Id> = None;
+        for message in page.iter() {
+            latest = Some(conn.produce_from_items_with_id(
+                archive_stream_name,
+                &message.id.parse()?,
+                &message_to_items(message)?,
+                &StreamAddOptions::default(),
+            )?);
+            reply.archived += 1;
+        }
+
+        next = match page.len().ge(&ARCHIVE_PAGE_SIZE) {
+            true => latest.as_ref().and_then(next_id),
+            false => None,
+        };
+    }
+
+    if delete {
+        reply.deleted = conn.trim_stream(
+            stream_name,
+            &StreamTrimOptions::minid(StreamTrimmingMode::Exact, first_unacknowledged.to_string()),
+        )?;
+    }
+
+    info!(
+        "Total messages archived: {}, deleted: {}",
+        reply.archived, reply.deleted
+    );
+
+    Ok(reply)
+}
+
+/// An administration interface of Redis Streams. It is responsible for managing streams and consumers groups, regardless of any consumer or producer configuration, which is useful in test suites and decommissioning scripts.
+///
+/// If a namespace is set in the [`ClientArgs`], every stream name given to the admin, including archive streams, is prefixed with it. If a [replica](ClientArgs::with_replica) is set, [`discover_streams`](Admin::discover_streams) and [`get_dead_consumers`](Admin::get_dead_consumers) read from it.
+#[derive(Debug, Clone)]
+pub struct Admin {
+    /// Redis client to interact with Redis server.
+    client: Client,
+
+    /// Redis client of the read-only methods, connected to the replica if it is set.
+    replica: Client,
+
+    /// Namespace prepended to the keys of the streams.
+    namespace: Option<String>,
+}
+
+impl Admin {
+    /// Get [`Client`].
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get [`Client`] of the read-only methods.
+    fn get_replica(&self) -> &Client {
+        &self.replica
+    }
+
+    /// Get the key of the stream *name* in the namespace.
+    fn get_key(&self, name: &str) -> String {
+        namespaced_key(self.namespace.as_deref(), name)
+    }
+
+    /// Get the name of the stream *key*, without the namespace.
+    fn get_name(&self, key: String) -> String {
+        strip_namespace(self.namespace.as_deref(), key)
+    }
+
+    /// Build a new [`Admin`] instance.
+    ///
+    /// Before creating a new admin, the following validations are performed:
+    ///
+    /// - If connection string is invalid, a [`RedsumerError`] is returned.
+    /// - If connection to Redis server, or to its replica if it is set, can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`Admin`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs) -> RedsumerResult<Admin> {
+        debug!("Creating a new admin instance by: {:?}", args);
+
+        let mut client: Client = args.build()?;
+        client.ping()?;
+
+        let mut replica: Client = args.build_replica()?;
+        if args.get_replica().is_some() {
+            replica.ping()?;
+        }
+
+        info!("Admin instance created successfully and it is ready to be used");
+
+        Ok(Admin {
+            client,
+            replica,
+            namespace: args.get_namespace().map(str::to_owned),
+        })
+    }
+
+    /// Discover the streams whose name matches a glob *pattern*, like `meters:*:events`, so dynamic consumers can find per-entity streams at runtime.
+    ///
+    /// The keyspace is iterated with `SCAN`, filtering keys of type stream, so the server is not blocked like with `KEYS`. Streams created or deleted while the scan runs may be reported or not. If a namespace is set, the pattern is matched inside the namespace and the names are returned without it.
+    ///
+    /// # Arguments:
+    /// - **pattern**: The glob pattern of the stream names.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the names of the streams found, sorted and without duplicates. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn discover_streams(&self, pattern: &str) -> RedsumerResult<Vec<String>> {
+        debug!("Discovering streams matching pattern {pattern}");
+
+        Ok(self
+            .get_replica()
+            .to_owned()
+            .discover_streams(&self.get_key(pattern))?
+            .into_iter()
+            .map(|key| self.get_name(key))
+            .collect())
+    }
+
+    /// Destroy a consumers group.
+    ///
+    /// All the consumers of the group and their pending messages are deleted, even if there are active consumers.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream.
+    /// - **group_name**: The name of the consumers group to destroy.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`DestroyGroupReply`] if successful. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn destroy_group(
+        &self,
+        stream_name: &str,
+        group_name: &str,
+    ) -> RedsumerResult<DestroyGroupReply> {
+        info!("Destroying consumers group {group_name} in stream {stream_name}");
+
+        self.get_client()
+            .to_owned()
+            .destroy_consumer_group(self.get_key(stream_name), group_name)
+            .map(DestroyGroupReply::from)
+    }
+
+    /// Get the consumers of a consumers group whose heartbeat expired.
+    ///
+    /// Consumers send heartbeats when they are built with [`Consumer::with_heartbeat`](crate::consumer::Consumer::with_heartbeat). A consumer registered in the group without a live heartbeat is considered dead, unlike an idle consumer that keeps sending heartbeats while no messages arrive, so its pending messages can be claimed and the consumer can be removed safely. Consumers that never sent a heartbeat are reported as dead too.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream.
+    /// - **group_name**: The name of the consumers group.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the names of the dead consumers. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn get_dead_consumers(
+        &self,
+        stream_name: &str,
+        group_name: &str,
+    ) -> RedsumerResult<Vec<String>> {
+        debug!("Getting dead consumers of group {group_name} in stream {stream_name}");
+
+        self.get_replica()
+            .to_owned()
+            .get_dead_consumers(self.get_key(stream_name), group_name)
+    }
+
+    /// Remove the consumers of a consumers group that are idle beyond *min_idle_time* and have no pending messages.
+    ///
+    /// It keeps the `XINFO CONSUMERS` output from growing unboundedly in autoscaled environments, where consumer names are usually unique per instance. Consumers with pending messages are kept, so no message is lost from the group pending list.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream.
+    /// - **group_name**: The name of the consumers group.
+    /// - **min_idle_time**: The min idle time of the consumers to remove.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the names of the removed consumers. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn remove_idle_consumers(
+        &self,
+        stream_name: &str,
+        group_name: &str,
+        min_idle_time: Duration,
+    ) -> RedsumerResult<Vec<String>> {
+        info!("Removing idle consumers of group {group_name} in stream {stream_name}");
+
+        self.get_client().to_owned().remove_idle_consumers(
+            self.get_key(stream_name),
+            group_name,
+            min_idle_time,
+        )
+    }
+
+    /// Archive the messages of a stream that were acknowledged by all its consumers groups, copying them to an archive stream with their original IDs. Optionally, the archived messages are deleted from the stream, so it stays small while the full history remains queryable in the archive stream.
+    ///
+    /// It is intended to be run periodically as a maintenance task. Messages are copied after the latest message of the archive stream, so an interrupted run is resumed by the next one without duplicates. Therefore, the archive stream must only be written by this task. If the stream has no consumers groups, no message is considered acknowledged.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream.
+    /// - **archive_stream_name**: The name of the archive stream.
+    /// - **delete**: If `true`, the archived messages are deleted from the stream with `XTRIM`.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing an [`ArchiveReply`] if successful. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn archive_acknowledged_messages(
+        &self,
+        stream_name: &str,
+        archive_stream_name: &str,
+        delete: bool,
+    ) -> RedsumerResult<ArchiveReply> {
+        info!("Archiving acknowledged messages of stream {stream_name} into {archive_stream_name}");
+
+        archive_acknowledged_messages(
+            &mut self.get_client().to_owned(),
+            &self.get_key(stream_name),
+            &self.get_key(archive_stream_name),
+            delete,
+        )
+    }
+
+    /// Transfer all the pending messages of a consumer to another consumer of the same consumers group, useful to decommission a consumer in a controlled way before removing it.
+    ///
+    /// The pending list of the source consumer is read in pages with `XPENDING`, and every page is claimed by the target consumer with `XCLAIM`, whatever the idle time of the messages is. It requires Redis 6.2 or later. The messages are delivered again to the target consumer as pending messages, so the source consumer should be stopped before the transfer.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream.
+    /// - **group_name**: The name of the consumers group.
+    /// - **from_consumer**: The name of the consumer whose pending messages are transferred.
+    /// - **to_consumer**: The name of the consumer that receives the pending messages.
+    /// - **on_progress**: A function called after every page with the total number of messages transferred so far.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`TransferReply`] if successful. If an error occurs, a [`RedsumerError`] is returned, and the messages transferred before the error stay in the target consumer.
+    pub async fn transfer_pending<F>(
+        &self,
+        stream_name: &str,
+        group_name: &str,
+        from_consumer: &str,
+        to_consumer: &str,
+        on_progress: F,
+    ) -> RedsumerResult<TransferReply>
+    where
+        F: FnMut(usize),
+    {
+        info!("Transferring pending messages of consumer {from_consumer} to {to_consumer} in group {group_name} of stream {stream_name}");
+
+        transfer_pending_messages(
+            &mut self.get_client().to_owned(),
+            &self.get_key(stream_name),
+            group_name,
+            from_consumer,
+            to_consumer,
+            on_progress,
+        )
+    }
+
+    /// Migrate a consumers group to a new one, so a group can be renamed or split without losing its position in the stream.
+    ///
+    /// The new consumers group is created at the last delivered ID of the old one, read with `XINFO GROUPS`, so it starts consuming after the latest message delivered to the old group. Optionally, the pending messages of the old group are copied to the new one with `XCLAIM ... FORCE`, keeping the consumer that owns every message, so they are delivered again as pending messages by the consumers of the new group. Delivery counts are not copied. The old consumers group is not modified, so it can be destroyed with [`destroy_group`](Admin::destroy_group) once the new one is in use.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream.
+    /// - **old_group_name**: The name of the consumers group to migrate.
+    /// - **new_group_name**: The name of the new consumers group. It must not exist.
+    /// - **copy_pending**: If `true`, the pending messages of the old group are copied to the new one.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`MigrateGroupReply`] if successful. If the old group does not exist, the new group already exists or another error occurs, a [`RedsumerError`] is returned.
+    pub async fn migrate_group(
+        &self,
+        stream_name: &str,
+        old_group_name: &str,
+        new_group_name: &str,
+        copy_pending: bool,
+    ) -> RedsumerResult<MigrateGroupReply> {
+        info!("Migrating consumers group {old_group_name} to {new_group_name} in stream {stream_name}");
+
+        migrate_consumer_group(
+            &mut self.get_client().to_owned(),
+            &self.get_key(stream_name),
+            old_group_name,
+            new_group_name,
+            copy_pending,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_destroy_group_reply {
+    use super::*;
+
+    #[test]
+    fn test_destroy_group_reply() {
+        // Create new DestroyGroupReply instance:
+        let reply: DestroyGroupReply = DestroyGroupReply::from(true);
+
+        // Verify the result:
+        assert!(reply.was_destroyed());
+    }
+}
+
+#[cfg(test)]
+mod test_admin_namespace {
+    use super::*;
+
+    #[test]
+    fn test_admin_stream_names() {
+        // Build admins without connecting to the Redis server:
+        let client: Client = Client::open("redis://127.0.0.1:1/0").unwrap();
+        let admin: Admin = Admin {
+            client: client.to_owned(),
+            replica: client.to_owned(),
+            namespace: None,
+        };
+        let namespaced: Admin = Admin {
+            client: client.to_owned(),
+            replica: client,
+            namespace: Some("tenant".to_string()),
+        };
+
+        // Verify the keys and names of the streams:
+        assert_eq!(admin.get_key("stream"), "stream");
+        assert_eq!(admin.get_name("stream".to_string()), "stream");
+        assert_eq!(namespaced.get_key("meters:*"), "tenant:meters:*");
+        assert_eq!(
+            namespaced.get_name("tenant:meters:1".to_string()),
+            "meters:1"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_archive_acknowledged_messages {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `XRANGE` reply item for a message.
+    fn message(id: &str) -> Value {
+        Value::Array(vec![
+            Value::BulkString(id.as_bytes().to_vec()),
+            Value::Array(vec![
+                Value::BulkString(b"field".to_vec()),
+                Value::BulkString(b"value".to_vec()),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_archive_and_delete_acknowledged_messages() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("GROUPS").arg("stream"),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"name".to_vec()),
+                    Value::BulkString(b"group".to_vec()),
+                    Value::BulkString(b"consumers".to_vec()),
+                    Value::Int(1),
+                    Value::BulkString(b"pending".to_vec()),
+                    Value::Int(0),
+                    Value::BulkString(b"last-delivered-id".to_vec()),
+                    Value::BulkString(b"3-0".to_vec()),
+                ])])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XPENDING").arg("stream").arg("group"),
+                Ok(Value::Array(vec![
+                    Value::Int(0),
+                    Value::Nil,
+                    Value::Nil,
+                    Value::Nil,
+                ])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XREVRANGE")
+                    .arg("archive")
+                    .arg("+")
+                    .arg("-")
+                    .arg("COUNT")
+                    .arg(1),
+                Ok(Value::Array(vec![])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XRANGE")
+                    .arg("stream")
+                    .arg("0-0")
+                    .arg("3-0")
+                    .arg("COUNT")
+                    .arg(ARCHIVE_PAGE_SIZE),
+                Ok(Value::Array(vec![message("1-0"), message("3-0")])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XADD")
+                    .arg("archive")
+                    .arg("1-0")
+                    .arg("field")
+                    .arg("value"),
+                Ok(Value::BulkString(b"1-0".to_vec())),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XADD")
+                    .arg("archive")
+                    .arg("3-0")
+                    .arg("field")
+                    .arg("value"),
+                Ok(Value::BulkString(b"3-0".to_vec())),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XTRIM").arg("stream").arg("MINID").arg("=").arg("3-1"),
+                Ok(Value::Int(2)),
+            ),
+        ]);
+
+        // Archive the acknowledged messages:
+        let result: RedsumerResult<ArchiveReply> =
+            archive_acknowledged_messages(&mut conn, "stream", "archive", true);
+
+        // Verify the result:
+        let reply: ArchiveReply = result.unwrap();
+        assert_eq!(reply.get_archived(), 2);
+        assert_eq!(reply.get_deleted(), 2);
+    }
+
+    #[test]
+    fn test_archive_without_groups() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XINFO").arg("GROUPS").arg("stream"),
+                Ok(Value::Array(vec![])),
+            )]);
+
+        // Archive the acknowledged messages:
+        let result: RedsumerResult<ArchiveReply> =
+            archive_acknowledged_messages(&mut conn, "stream", "archive", true);
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), ArchiveReply::default());
+    }
+}
+
+#[cfg(test)]
+mod test_transfer_pending_messages {
+    use redis::{cmd, ErrorKind, RedisError, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `XPENDING` reply item for a message of *consumer*.
+    fn pending(id: &str, consumer: &str) -> Value {
+        Value::Array(vec![
+            Value::BulkString(id.as_bytes().to_vec()),
+            Value::BulkString(consumer.as_bytes().to_vec()),
+            Value::Int(1000),
+            Value::Int(1),
+        ])
+    }
+
+    #[test]
+    fn test_transfer_pending_messages_ok() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg("stream")
+                    .arg("group")
+                    .arg("IDLE")
+                    .arg(0)
+                    .arg("0-0")
+                    .arg("+")
+                    .arg(TRANSFER_PAGE_SIZE)
+                    .arg("old-consumer"),
+                Ok(Value::Array(vec![
+                    pending("1-0", "old-consumer"),
+                    pending("2-0", "old-consumer"),
+                ])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg("stream")
+                    .arg("group")
+                    .arg("new-consumer")
+                    .arg(0)
+                    .arg(&["1-0", "2-0"])
+                    .arg("JUSTID"),
+                Ok(Value::Array(vec![
+                    Value::BulkString(b"1-0".to_vec()),
+                    Value::BulkString(b"2-0".to_vec()),
+                ])),
+            ),
+        ]);
+
+        // Transfer the pending messages:
+        let mut progress: Vec<usize> = Vec::new();
+        let result: RedsumerResult<TransferReply> = transfer_pending_messages(
+            &mut conn,
+            "stream",
+            "group",
+            "old-consumer",
+            "new-consumer",
+            |transferred| progress.push(transferred),
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap().get_transferred(), 2);
+        assert_eq!(progress, vec![2]);
+    }
+
+    #[test]
+    fn test_transfer_pending_messages_error() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg("stream")
+                    .arg("group")
+                    .arg("IDLE")
+                    .arg(0)
+                    .arg("0-0")
+                    .arg("+")
+                    .arg(TRANSFER_PAGE_SIZE)
+                    .arg("old-consumer"),
+                Err(RedisError::from((
+                    ErrorKind::ResponseError,
+                    "XPENDING Error",
+                ))),
+            )]);
+
+        // Transfer the pending messages:
+        let result: RedsumerResult<TransferReply> = transfer_pending_messages(
+            &mut conn,
+            "stream",
+            "group",
+            "old-consumer",
+            "new-consumer",
+            |_| {},
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ResponseError);
+    }
+}
+
+#[cfg(test)]
+mod test_migrate_consumer_group {
+    use redis::{cmd, ErrorKind, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    /// Build a `XINFO GROUPS` command and its reply with the *old-group* at the given *last_delivered_id*.
+    fn xinfo_groups(last_delivered_id: &str) -> MockCmd {
+        MockCmd::new::<_, Value>(
+            cmd("XINFO").arg("GROUPS").arg("stream"),
+            Ok(Value::Array(vec![Value::Array(vec![
+                Value::BulkString(b"name".to_vec()),
+                Value::BulkString(b"old-group".to_vec()),
+                Value::BulkString(b"consumers".to_vec()),
+                Value::Int(2),
+                Value::BulkString(b"pending".to_vec()),
+                Value::Int(2),
+                Value::BulkString(b"last-delivered-id".to_vec()),
+                Value::BulkString(last_delivered_id.as_bytes().to_vec()),
+            ])])),
+        )
+    }
+
+    /// Build a `XPENDING` reply item for a message of *consumer*.
+    fn pending(id: &str, consumer: &str) -> Value {
+        Value::Array(vec![
+            Value::BulkString(id.as_bytes().to_vec()),
+            Value::BulkString(consumer.as_bytes().to_vec()),
+            Value::Int(1000),
+            Value::Int(1),
+        ])
+    }
+
+    #[test]
+    fn test_migrate_consumer_group_with_pending_messages() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            xinfo_groups("3-0"),
+            MockCmd::new::<_, Value>(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg("stream")
+                    .arg("new-group")
+                    .arg("3-0"),
+                Ok(Value::Okay),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XPENDING")
+                    .arg("stream")
+                    .arg("old-group")
+                    .arg("0-0")
+                    .arg("+")
+                    .arg(TRANSFER_PAGE_SIZE),
+                Ok(Value::Array(vec![
+                    pending("1-0", "consumer-2"),
+                    pending("2-0", "consumer-1"),
+                ])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg("stream")
+                    .arg("new-group")
+                    .arg("consumer-1")
+                    .arg(0)
+                    .arg(&["2-0"])
+                    .arg("FORCE")
+                    .arg("JUSTID"),
+                Ok(Value::Array(vec![Value::BulkString(b"2-0".to_vec())])),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XCLAIM")
+                    .arg("stream")
+                    .arg("new-group")
+                    .arg("consumer-2")
+                    .arg(0)
+                    .arg(&["1-0"])
+                    .arg("FORCE")
+                    .arg("JUSTID"),
+                Ok(Value::Array(vec![Value::BulkString(b"1-0".to_vec())])),
+            ),
+        ]);
+
+        // Migrate the consumers group:
+        let result: RedsumerResult<MigrateGroupReply> =
+            migrate_consumer_group(&mut conn, "stream", "old-group", "new-group", true);
+
+        // Verify the result:
+        let reply: MigrateGroupReply = result.unwrap();
+        assert_eq!(reply.get_last_delivered_id(), "3-0");
+        assert_eq!(reply.get_copied(), 2);
+    }
+
+    #[test]
+    fn test_migrate_consumer_group_already_exists() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection = MockRedisConnection::new(vec![
+            xinfo_groups("3-0"),
+            MockCmd::new::<_, Value>(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg("stream")
+                    .arg("new-group")
+                    .arg("3-0"),
+                Err(redis::RedisError::from((
+                    ErrorKind::ExtensionError,
+                    "BUSYGROUP",
+                    "Consumer Group name already exists".to_string(),
+                ))),
+            ),
+        ]);
+
+        // Migrate the consumers group:
+        let result: RedsumerResult<MigrateGroupReply> =
+            migrate_consumer_group(&mut conn, "stream", "old-group", "new-group", false);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+}
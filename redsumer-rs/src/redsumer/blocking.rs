@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use redis::ToRedisArgs;
+
+#[allow(unused_imports)]
+use crate::core::{
+    blocking::block_on,
+    client::ClientArgs,
+    result::{RedsumerError, RedsumerResult},
+    streams::types::Id,
+};
+use crate::redsumer::consumer::{
+    AckManyReply, AckMessageReply, AckToken, ClaimFilter, ConsumeMessagesReply, Consumer,
+    ConsumerConfig, IsStillMineReply, StartPosition,
+};
+use crate::redsumer::producer::{ProduceMessageReply, Producer, ProducerConfig};
+
+/// A synchronous wrapper of [`Producer`], for CLIs and applications without an async runtime.
+///
+/// Every method blocks the current thread until the operation completes, so it must not be called from an async task.
+#[derive(Debug, Clone)]
+pub struct BlockingProducer {
+    /// The wrapped async producer.
+    producer: Producer,
+}
+
+impl BlockingProducer {
+    /// Build a new [`BlockingProducer`] instance, with the same validations as [`Producer::new`].
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new Redis client.
+    /// - **config**: Producer configuration parameters.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with the new [`BlockingProducer`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs, config: &ProducerConfig) -> RedsumerResult<Self> {
+        Producer::new(args, config).map(Self::from)
+    }
+
+    /// Get the wrapped [`Producer`].
+    pub fn get_producer(&self) -> &Producer {
+        &self.producer
+    }
+
+    /// Produce a new message in the stream from a map, see [`Producer::produce_from_map`].
+    ///
+    /// # Arguments:
+    /// - **map**: A map with the message to be produced. It must implement the [`ToRedisArgs`] trait.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with a [`ProduceMessageReply`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn produce_from_map<M>(&self, map: M) -> RedsumerResult<ProduceMessageReply>
+    where
+        M: ToRedisArgs,
+    {
+        block_on(self.producer.produce_from_map(map))
+    }
+
+    /// Produce a new message in the stream from a list of items, see [`Producer::produce_from_items`].
+    ///
+    /// # Arguments:
+    /// - **items**: A list of items with the message to be produced. Each item is a tuple with the field and the value. Both must implement the [`ToRedisArgs`] trait.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with a [`ProduceMessageReply`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn produce_from_items<F, V>(
+        &self,
+        items: Vec<(F, V)>,
+    ) -> RedsumerResult<ProduceMessageReply>
+    where
+        F: ToRedisArgs,
+        V: ToRedisArgs,
+    {
+        block_on(self.producer.produce_from_items(items))
+    }
+}
+
+/// Convert a [`Producer`] into a [`BlockingProducer`] instance.
+impl From<Producer> for BlockingProducer {
+    fn from(producer: Producer) -> Self {
+        BlockingProducer { producer }
+    }
+}
+
+/// A synchronous wrapper of [`Consumer`], for CLIs and applications without an async runtime.
+///
+/// Every method blocks the current thread until the operation completes, so it must not be called from an async task. Reads with a block time wait in the current thread, as they do in the async API without a Tokio runtime.
+#[derive(Debug, Clone)]
+pub struct BlockingConsumer {
+    /// The wrapped async consumer.
+    consumer: Consumer,
+}
+
+impl BlockingConsumer {
+    /// Build a new [`BlockingConsumer`] instance, with the same validations as [`Consumer::new`].
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new Redis client.
+    /// - **config**: Consumer configuration parameters.
+    /// - **start_position**: The [`StartPosition`] of the stream where a new consumers group starts consuming messages.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with the new [`BlockingConsumer`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(
+        args: ClientArgs,
+        config: ConsumerConfig,
+        start_position: StartPosition,
+    ) -> RedsumerResult<Self> {
+        Consumer::new(args, config, start_position).map(Self::from)
+    }
+
+    /// Get the wrapped [`Consumer`], for example to pause it or to get its statistics.
+    pub fn get_consumer(&self) -> &Consumer {
+        &self.consumer
+    }
+
+    /// Consume messages from the stream, see [`Consumer::consume`].
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] containing a [`ConsumeMessagesReply`]. If an error occurs, a [`RedsumerError`] is returned.
+    pub fn consume(&mut self) -> RedsumerResult<ConsumeMessagesReply> {
+        block_on(self.consumer.consume())
+    }
+
+    /// Ack a message by its *token*, see [`Consumer::ack`].
+    ///
+    /// # Arguments:
+    /// - **token**: The [`AckToken`] of the message to ack, issued by [`consume`](BlockingConsumer::consume).
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] containing an [`AckMessageReply`]. If the token was issued by another consumer or an error occurs, a [`RedsumerError`] is returned.
+    pub fn ack(&self, token: &AckToken) -> RedsumerResult<AckMessageReply> {
+        block_on(self.consumer.ack(token))
+    }
+
+    /// Ack a set of messages by their *tokens*, reporting which of them were acked, see [`Consumer::ack_many_detailed`].
+    ///
+    /// # Arguments:
+    /// - **tokens**: The [`AckToken`]s of the messages to ack, issued by [`consume`](BlockingConsumer::consume).
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] containing an [`AckManyReply`]. If a token was issued by another consumer or an error occurs, a [`RedsumerError`] is returned.
+    pub fn ack_many_detailed(&self, tokens: &[AckToken]) -> RedsumerResult<AckManyReply> {
+        block_on(self.consumer.ack_many_detailed(tokens))
+    }
+
+    /// Ack every message in a consumed *reply*, see [`Consumer::ack_all`].
+    ///
+    /// # Arguments:
+    /// - **reply**: The [`ConsumeMessagesReply`] whose messages were processed.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] containing an [`AckManyReply`]. If no token was issued for a message of the reply or an error occurs, a [`RedsumerError`] is returned.
+    pub fn ack_all(&self, reply: &ConsumeMessagesReply) -> RedsumerResult<AckManyReply> {
+        block_on(self.consumer.ack_all(reply))
+    }
+
+    /// Mark a consumed message as processed, see [`Consumer::mark_processed`].
+    ///
+    /// # Arguments:
+    /// - **token**: The [`AckToken`] of the message.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `true` if the message is tracked by auto-commit. Otherwise, `false` is returned. If the token was issued for another stream, consumers group or consumer, a [`RedsumerError`] is returned.
+    pub fn mark_processed(&self, token: &AckToken) -> RedsumerResult<bool> {
+        self.consumer.mark_processed(token)
+    }
+
+    /// Ack the processed messages up to the highest contiguously processed ID, see [`Consumer::commit`].
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with the number of messages acked. If an error occurs, a [`RedsumerError`] is returned.
+    pub fn commit(&self) -> RedsumerResult<usize> {
+        block_on(self.consumer.commit())
+    }
+
+    /// Verify if a message by *id* is still in the consumer pending list, see [`Consumer::is_still_mine`].
+    ///
+    /// # Arguments:
+    /// - **id**: The ID of the message to verify.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] containing an [`IsStillMineReply`]. If an error occurs, a [`RedsumerError`] is returned.
+    pub fn is_still_mine(&self, id: &Id) -> RedsumerResult<IsStillMineReply> {
+        self.consumer.is_still_mine(id)
+    }
+
+    /// Verify if a set of messages by *ids* are still in the consumer pending list, see [`Consumer::is_still_mine_many`].
+    ///
+    /// # Arguments:
+    /// - **ids**: The IDs of the messages to verify.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] containing a map from each id to its [`IsStillMineReply`]. If an error occurs, a [`RedsumerError`] is returned.
+    pub fn is_still_mine_many(&self, ids: &[Id]) -> RedsumerResult<HashMap<Id, IsStillMineReply>> {
+        self.consumer.is_still_mine_many(ids)
+    }
+
+    /// Take over pending messages of a specific consumer by *name*, see [`Consumer::claim_from`].
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the consumer that owns the messages.
+    /// - **filter**: The [`ClaimFilter`] of the messages to claim.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] containing a [`ConsumeMessagesReply`] with the claimed messages. If an error occurs, a [`RedsumerError`] is returned.
+    pub fn claim_from(
+        &self,
+        name: &str,
+        filter: ClaimFilter,
+    ) -> RedsumerResult<ConsumeMessagesReply> {
+        block_on(self.consumer.claim_from(name, filter))
+    }
+}
+
+/// Convert a [`Consumer`] into a [`BlockingConsumer`] instance.
+impl From<Consumer> for BlockingConsumer {
+    fn from(consumer: Consumer) -> Self {
+        BlockingConsumer { consumer }
+    }
+}
+
+#[cfg(test)]
+mod test_blocking_consumer {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_blocking_consumer_errors() {
+        // Create a new blocking consumer with an unreachable server:
+        let mut consumer: BlockingConsumer = BlockingConsumer::from(Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        ));
+
+        // Consume and ack messages:
+        assert!(consumer.consume().is_err());
+        assert!(consumer
+            .ack(&AckToken::new(
+                EntryId::new(1, 0),
+                "stream",
+                "group",
+                "consumer"
+            ))
+            .is_err());
+
+        // Verify the result:
+        assert_eq!(consumer.get_consumer().stats().get_failed_acks(), 1);
+    }
+
+    #[test]
+    fn test_blocking_consumer_paused() {
+        // Create a new paused blocking consumer:
+        let mut consumer: BlockingConsumer = BlockingConsumer::from(Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        ));
+        consumer.get_consumer().pause();
+
+        // Consume without reaching the server:
+        let reply: ConsumeMessagesReply = consumer.consume().unwrap();
+
+        // Verify the result:
+        assert!(reply.not_found());
+    }
+}
@@ -0,0 +1,253 @@
+use std::time::{Duration, Instant};
+
+use redis::{ErrorKind, ToRedisArgs};
+use tracing::{debug, warn};
+
+#[allow(unused_imports)]
+use crate::core::{
+    result::{RedsumerError, RedsumerResult},
+    streams::types::Id,
+};
+use crate::redsumer::producer::Producer;
+
+/// Default max number of messages buffered before they are flushed.
+pub const DEFAULT_BUFFER_SIZE: usize = 100;
+
+/// Default max time between flushes.
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A producer that accumulates messages in memory and produces them in pipelined batches, trading a small latency budget for a much higher throughput.
+///
+/// Buffered messages are flushed when the buffer reaches its *max size*, when the *flush interval* elapsed since the latest flush, or explicitly with [`flush`](BufferedProducer::flush). The time trigger is checked when a message is buffered and with [`flush_if_due`](BufferedProducer::flush_if_due), which can be called periodically from a timer while no messages arrive. Messages still buffered when the producer is dropped are lost, so [`flush`](BufferedProducer::flush) must be called before dropping it.
+#[derive(Debug)]
+pub struct BufferedProducer {
+    /// Producer used to flush the buffered messages.
+    producer: Producer,
+
+    /// Encoded messages waiting to be flushed.
+    buffer: Vec<Vec<Vec<u8>>>,
+
+    /// Max number of messages buffered before they are flushed.
+    max_size: usize,
+
+    /// Max time between flushes.
+    flush_interval: Duration,
+
+    /// Instant of the latest flush.
+    last_flush: Instant,
+}
+
+impl BufferedProducer {
+    /// Get [`Producer`].
+    pub fn get_producer(&self) -> &Producer {
+        &self.producer
+    }
+
+    /// Get the number of messages waiting to be flushed.
+    pub fn get_buffered_count(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Build a new [`BufferedProducer`] instance.
+    ///
+    /// # Arguments:
+    /// - **producer**: The [`Producer`] used to flush the buffered messages. Its interceptors and trace propagation are applied when a message is buffered.
+    /// - **max_size**: The max number of messages buffered before they are flushed. It must be greater than `0`.
+    /// - **flush_interval**: The max time between flushes.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the new [`BufferedProducer`] instance. If *max_size* is `0`, a [`RedsumerError`] is returned.
+    pub fn new(
+        producer: Producer,
+        max_size: usize,
+        flush_interval: Duration,
+    ) -> RedsumerResult<BufferedProducer> {
+        if max_size.eq(&0) {
+            return Err(RedsumerError::from((
+                ErrorKind::ClientError,
+                "Invalid buffer size",
+                "Buffer size must be greater than zero".to_string(),
+            )));
+        }
+
+        Ok(BufferedProducer {
+            producer,
+            buffer: Vec::with_capacity(max_size),
+            max_size,
+            flush_interval,
+            last_flush: Instant::now(),
+        })
+    }
+
+    /// Verify if the buffer must be flushed, because it is full or the flush interval elapsed.
+    fn is_flush_due(&self) -> bool {
+        !self.buffer.is_empty()
+            && (self.buffer.len().ge(&self.max_size)
+                || self.last_flush.elapsed().ge(&self.flush_interval))
+    }
+
+    /// Buffer a new message from a map, flushing the buffer if it is due.
+    ///
+    /// # Arguments:
+    /// - **map**: A map with the message to be produced. It must implement the [`ToRedisArgs`] trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the messages produced if the buffer was flushed, otherwise an empty list. If the message can not be encoded or the flush fails, a [`RedsumerError`] is returned.
+    pub async fn produce_from_map<M>(&mut self, map: M) -> RedsumerResult<Vec<Id>>
+    where
+        M: ToRedisArgs,
+    {
+        let fields: Vec<Vec<u8>> = self.producer.encode(map)?;
+        self.buffer.push(fields);
+
+        self.flush_if_due().await
+    }
+
+    /// Buffer a new message from a list of items, flushing the buffer if it is due.
+    ///
+    /// # Arguments:
+    /// - **items**: A list of items with the message to be produced. Each item is a tuple with the field and the value. Both must implement the [`ToRedisArgs`] trait.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the messages produced if the buffer was flushed, otherwise an empty list. If the message can not be encoded or the flush fails, a [`RedsumerError`] is returned.
+    pub async fn produce_from_items<F, V>(&mut self, items: Vec<(F, V)>) -> RedsumerResult<Vec<Id>>
+    where
+        F: ToRedisArgs,
+        V: ToRedisArgs,
+    {
+        let fields: Vec<Vec<u8>> = self.producer.encode(items.as_slice())?;
+        self.buffer.push(fields);
+
+        self.flush_if_due().await
+    }
+
+    /// Flush the buffer if it is full or the flush interval elapsed since the latest flush.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the messages produced if the buffer was flushed, otherwise an empty list. If the flush fails, a [`RedsumerError`] is returned.
+    pub async fn flush_if_due(&mut self) -> RedsumerResult<Vec<Id>> {
+        match self.is_flush_due() {
+            true => self.flush().await,
+            false => Ok(Vec::new()),
+        }
+    }
+
+    /// Produce all the buffered messages in a single pipeline.
+    ///
+    /// If the flush fails, the messages are kept in the buffer to be flushed again. A failed flush may be partially produced, so flushing again may duplicate the messages produced before the error.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the produced messages, in the same order they were buffered. If the flush fails, a [`RedsumerError`] is returned.
+    pub async fn flush(&mut self) -> RedsumerResult<Vec<Id>> {
+        self.last_flush = Instant::now();
+        if self.buffer.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<Id> = self
+            .producer
+            .produce_encoded(self.buffer.to_owned())
+            .await?;
+        self.buffer.clear();
+
+        debug!("Total buffered messages flushed: {}", ids.len());
+
+        Ok(ids)
+    }
+}
+
+impl Drop for BufferedProducer {
+    fn drop(&mut self) {
+        if !self.buffer.is_empty() {
+            warn!(
+                "Buffered producer dropped with {} messages not flushed",
+                self.buffer.len()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_buffered_producer {
+    use super::*;
+    use crate::redsumer::producer::ProducerConfig;
+
+    /// Build a buffered producer without connecting to the Redis server.
+    fn buffered_producer(max_size: usize, flush_interval: Duration) -> BufferedProducer {
+        BufferedProducer::new(
+            Producer::unconnected(&ProducerConfig::new("stream")),
+            max_size,
+            flush_interval,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_buffered_producer_invalid_size() {
+        // Build a buffered producer with a zero buffer size:
+        let result: RedsumerResult<BufferedProducer> = BufferedProducer::new(
+            Producer::unconnected(&ProducerConfig::new("stream")),
+            0,
+            DEFAULT_FLUSH_INTERVAL,
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_producer_buffers_messages() {
+        // Buffer messages below the size trigger:
+        let mut producer: BufferedProducer = buffered_producer(3, Duration::from_secs(60));
+        let first: Vec<Id> = producer.produce_from_map(&[("field", "1")]).await.unwrap();
+        let second: Vec<Id> = producer
+            .produce_from_items(vec![("field", "2")])
+            .await
+            .unwrap();
+
+        // Verify the messages are not flushed:
+        assert!(first.is_empty());
+        assert!(second.is_empty());
+        assert_eq!(producer.get_buffered_count(), 2);
+        assert!(producer.flush_if_due().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_buffered_producer_flush_on_size() {
+        // Buffer messages up to the size trigger without a server:
+        let mut producer: BufferedProducer = buffered_producer(2, Duration::from_secs(60));
+        producer.produce_from_map(&[("field", "1")]).await.unwrap();
+        let result: RedsumerResult<Vec<Id>> = producer.produce_from_map(&[("field", "2")]).await;
+
+        // Verify the flush failed and the messages are kept:
+        assert!(result.unwrap_err().is_connection_error());
+        assert_eq!(producer.get_buffered_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_producer_flush_on_interval() {
+        // Buffer a message and wait for the flush interval:
+        let mut producer: BufferedProducer = buffered_producer(10, Duration::from_millis(10));
+        producer.produce_from_map(&[("field", "1")]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // Verify the flush is due:
+        assert!(producer.is_flush_due());
+        assert!(producer.flush_if_due().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_buffered_producer_flush_empty() {
+        // Flush an empty buffer:
+        let mut producer: BufferedProducer = buffered_producer(1, DEFAULT_FLUSH_INTERVAL);
+
+        // Verify the result:
+        assert!(producer.flush().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,307 @@
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+
+use redis::Client;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+#[allow(unused_imports)]
+use crate::core::{
+    checkpoint::CheckpointCommands,
+    client::{ClientArgs, RedisClientBuilder},
+    result::{RedsumerError, RedsumerResult},
+    streams::{consumer::BEGINNING_OF_TIME_ID, types::Id},
+};
+
+/// Default prefix of the keys where [`RedisCheckpointStore`] stores the checkpoints.
+pub const DEFAULT_CHECKPOINT_KEY_PREFIX: &str = "redsumer:checkpoint";
+
+/// Checkpoint field of the latest pending message ID.
+const LATEST_PENDING_MESSAGE_ID_FIELD: &str = "latest_pending_message_id";
+
+/// Checkpoint field of the next ID to claim.
+const NEXT_ID_TO_CLAIM_FIELD: &str = "next_id_to_claim";
+
+/// Checkpoint field of the last processed message ID.
+const LAST_PROCESSED_ID_FIELD: &str = "last_processed_id";
+
+/// Position of a [`Consumer`](crate::consumer::Consumer) in a stream: the cursors of its pending list scan and claim scan, and the ID of the last processed message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// The latest pending message ID to start reading from.
+    latest_pending_message_id: String,
+
+    /// The latest ID to start claiming from.
+    next_id_to_claim: String,
+
+    /// The ID of the last message processed by the application, if any.
+    last_processed_id: Option<Id>,
+}
+
+impl Checkpoint {
+    /// Get **latest pending message id**.
+    pub fn get_latest_pending_message_id(&self) -> &str {
+        &self.latest_pending_message_id
+    }
+
+    /// Get **next id to claim**.
+    pub fn get_next_id_to_claim(&self) -> &str {
+        &self.next_id_to_claim
+    }
+
+    /// Get **last processed id**.
+    pub fn get_last_processed_id(&self) -> Option<Id> {
+        self.last_processed_id
+    }
+
+    /// Build a new [`Checkpoint`] instance.
+    ///
+    /// # Arguments:
+    /// - **latest_pending_message_id**: The latest pending message ID to start reading from.
+    /// - **next_id_to_claim**: The latest ID to start claiming from.
+    /// - **last_processed_id**: The ID of the last message processed by the application, if any.
+    ///
+    /// # Returns:
+    /// A new instance of [`Checkpoint`].
+    pub fn new(
+        latest_pending_message_id: &str,
+        next_id_to_claim: &str,
+        last_processed_id: Option<Id>,
+    ) -> Self {
+        Checkpoint {
+            latest_pending_message_id: latest_pending_message_id.to_owned(),
+            next_id_to_claim: next_id_to_claim.to_owned(),
+            last_processed_id,
+        }
+    }
+
+    /// Get the checkpoint as a list of hash fields. The last processed ID is omitted if it is `None`.
+    fn to_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields: Vec<(&'static str, String)> = vec![
+            (
+                LATEST_PENDING_MESSAGE_ID_FIELD,
+                self.latest_pending_message_id.to_owned(),
+            ),
+            (NEXT_ID_TO_CLAIM_FIELD, self.next_id_to_claim.to_owned()),
+        ];
+        if let Some(id) = self.last_processed_id {
+            fields.push((LAST_PROCESSED_ID_FIELD, id.to_string()));
+        }
+
+        fields
+    }
+
+    /// Build a checkpoint from its hash fields. Missing cursors start at the beginning of the pending lists.
+    fn from_fields(fields: &HashMap<String, String>) -> RedsumerResult<Self> {
+        let cursor = |field: &str| -> String {
+            fields
+                .get(field)
+                .cloned()
+                .unwrap_or_else(|| BEGINNING_OF_TIME_ID.to_owned())
+        };
+
+        Ok(Checkpoint {
+            latest_pending_message_id: cursor(LATEST_PENDING_MESSAGE_ID_FIELD),
+            next_id_to_claim: cursor(NEXT_ID_TO_CLAIM_FIELD),
+            last_processed_id: fields
+                .get(LAST_PROCESSED_ID_FIELD)
+                .map(|id| id.parse::<Id>())
+                .transpose()?,
+        })
+    }
+}
+
+/// Cursors at the beginning of the pending lists, without processed messages.
+impl Default for Checkpoint {
+    fn default() -> Self {
+        Checkpoint::new(BEGINNING_OF_TIME_ID, BEGINNING_OF_TIME_ID, None)
+    }
+}
+
+/// A store of consumer checkpoints, so consumers can resume precisely after a restart. See [`Consumer::with_checkpoint_store`](crate::consumer::Consumer::with_checkpoint_store).
+///
+/// Checkpoints are stored by stream, consumers group and consumer name. Implementations are called from a blocking thread, so they can perform blocking I/O.
+pub trait CheckpointStore: Debug + Send + Sync {
+    /// Save the checkpoint of a consumer, replacing the previous one.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **group**: The consumers group name.
+    /// - **consumer**: The consumer name.
+    /// - **checkpoint**: The [`Checkpoint`] to save.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` if the checkpoint was saved. Otherwise, a [`RedsumerError`] is returned.
+    fn save(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        checkpoint: &Checkpoint,
+    ) -> RedsumerResult<()>;
+
+    /// Load the checkpoint of a consumer.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **group**: The consumers group name.
+    /// - **consumer**: The consumer name.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the [`Checkpoint`] of the consumer, or `None` if it was never saved. Otherwise, a [`RedsumerError`] is returned.
+    fn load(&self, stream: &str, group: &str, consumer: &str)
+        -> RedsumerResult<Option<Checkpoint>>;
+}
+
+/// A [`CheckpointStore`] that stores every checkpoint in a Redis hash, in the key `<prefix>:<stream>:<group>:<consumer>`.
+#[derive(Debug, Clone)]
+pub struct RedisCheckpointStore {
+    /// Redis client to interact with Redis server. It can be shared with producers and consumers.
+    client: Arc<Client>,
+
+    /// Prefix of the checkpoint keys.
+    prefix: String,
+}
+
+impl RedisCheckpointStore {
+    /// Get **prefix** of the checkpoint keys.
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Build a new [`RedisCheckpointStore`] instance with the [`DEFAULT_CHECKPOINT_KEY_PREFIX`].
+    ///
+    /// If connection string is invalid, a [`RedsumerError`] is returned.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`RedisCheckpointStore`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs) -> RedsumerResult<RedisCheckpointStore> {
+        Ok(Self::from_client(Arc::new(args.build()?)))
+    }
+
+    /// Build a new [`RedisCheckpointStore`] instance with the [`DEFAULT_CHECKPOINT_KEY_PREFIX`] from a shared [`Client`], like the one of the consumer.
+    ///
+    /// # Arguments:
+    /// - **client**: A shared Redis client.
+    ///
+    /// # Returns:
+    /// A new instance of [`RedisCheckpointStore`].
+    pub fn from_client(client: Arc<Client>) -> RedisCheckpointStore {
+        RedisCheckpointStore {
+            client,
+            prefix: DEFAULT_CHECKPOINT_KEY_PREFIX.to_owned(),
+        }
+    }
+
+    /// Set the *prefix* of the checkpoint keys.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_owned();
+        self
+    }
+
+    /// Get the key of the checkpoint of a consumer.
+    fn key(&self, stream: &str, group: &str, consumer: &str) -> String {
+        format!("{}:{stream}:{group}:{consumer}", self.prefix)
+    }
+}
+
+impl CheckpointStore for RedisCheckpointStore {
+    fn save(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        checkpoint: &Checkpoint,
+    ) -> RedsumerResult<()> {
+        let key: String = self.key(stream, group, consumer);
+        debug!("Saving checkpoint {key}: {:?}", checkpoint);
+
+        self.client
+            .as_ref()
+            .to_owned()
+            .save_checkpoint(&key, &checkpoint.to_fields())
+    }
+
+    fn load(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+    ) -> RedsumerResult<Option<Checkpoint>> {
+        let fields: HashMap<String, String> = self
+            .client
+            .as_ref()
+            .to_owned()
+            .load_checkpoint(self.key(stream, group, consumer))?;
+
+        match fields.is_empty() {
+            true => Ok(None),
+            false => Checkpoint::from_fields(&fields).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_checkpoint {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_fields() {
+        // Define a checkpoint:
+        let checkpoint: Checkpoint = Checkpoint::new("2-0", "1-0", Some(Id::new(3, 0)));
+
+        // Convert it to fields and back:
+        let fields: HashMap<String, String> = checkpoint
+            .to_fields()
+            .into_iter()
+            .map(|(field, value)| (field.to_owned(), value))
+            .collect();
+
+        // Verify the result:
+        assert_eq!(fields.len(), 3);
+        assert_eq!(Checkpoint::from_fields(&fields).unwrap(), checkpoint);
+    }
+
+    #[test]
+    fn test_checkpoint_from_partial_fields() {
+        // Define the fields of a checkpoint without cursors:
+        let fields: HashMap<String, String> =
+            HashMap::from([(LAST_PROCESSED_ID_FIELD.to_owned(), "5-1".to_owned())]);
+
+        // Build the checkpoint:
+        let checkpoint: Checkpoint = Checkpoint::from_fields(&fields).unwrap();
+
+        // Verify the result:
+        assert_eq!(checkpoint.get_latest_pending_message_id(), "0-0");
+        assert_eq!(checkpoint.get_next_id_to_claim(), "0-0");
+        assert_eq!(checkpoint.get_last_processed_id(), Some(Id::new(5, 1)));
+    }
+
+    #[test]
+    fn test_checkpoint_from_invalid_fields() {
+        // Define the fields of a checkpoint with an invalid ID:
+        let fields: HashMap<String, String> =
+            HashMap::from([(LAST_PROCESSED_ID_FIELD.to_owned(), "invalid".to_owned())]);
+
+        // Verify the result:
+        assert!(Checkpoint::from_fields(&fields).is_err());
+    }
+
+    #[test]
+    fn test_redis_checkpoint_store_key() {
+        // Define the store:
+        let store: RedisCheckpointStore = RedisCheckpointStore::from_client(Arc::new(
+            Client::open("redis://127.0.0.1:1/0").unwrap(),
+        ))
+        .prefix("app:checkpoint");
+
+        // Verify the result:
+        assert_eq!(store.get_prefix(), "app:checkpoint");
+        assert_eq!(
+            store.key("stream", "group", "consumer"),
+            "app:checkpoint:stream:group:consumer"
+        );
+    }
+}
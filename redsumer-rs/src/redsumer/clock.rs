@@ -0,0 +1,126 @@
+use std::{
+    fmt::Debug,
+    future::{ready, Future},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+#[allow(unused_imports)]
+use crate::core::{
+    blocking::sleep,
+    result::{RedsumerError, RedsumerResult},
+};
+
+/// A boxed future returned by [`Clock::sleep`].
+pub type ClockFuture = Pin<Box<dyn Future<Output = RedsumerResult<()>> + Send>>;
+
+/// A source of time for the client-side timing of a [`Consumer`](crate::consumer::Consumer): the idle backoff, the ack deadlines of the messages in flight, the auto-commit interval and the idle consumer cleanup schedule. See [`Consumer::with_clock`](crate::consumer::Consumer::with_clock).
+///
+/// It can be replaced by a [`ManualClock`] in tests, to simulate idle time and deadline expiry without real waits. Times tracked by the Redis server, like the idle time of pending messages or the expiration of heartbeats, are not affected.
+pub trait Clock: Debug + Send + Sync {
+    /// Get the current instant.
+    fn now(&self) -> Instant;
+
+    /// Wait for the given *duration*.
+    ///
+    /// # Arguments:
+    /// - **duration**: The time to wait.
+    ///
+    /// # Returns:
+    /// A [`ClockFuture`] resolved when the duration has elapsed. If the wait fails, a [`RedsumerError`] is returned.
+    fn sleep(&self, duration: Duration) -> ClockFuture;
+}
+
+/// The [`Clock`] of the system, used by default. It waits with the registered [`Runtime`](crate::runtime::Runtime), with Tokio, or in the current thread.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> ClockFuture {
+        Box::pin(sleep(duration))
+    }
+}
+
+/// A [`Clock`] that only moves forward when it is advanced, for deterministic tests. Waits advance it by their duration and return immediately. Clones share the current instant.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    /// The instant the clock was created at.
+    start: Instant,
+
+    /// The time the clock was advanced since it was created.
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    /// Build a new [`ManualClock`] instance, starting at the current instant of the system.
+    pub fn new() -> Self {
+        ManualClock {
+            start: Instant::now(),
+            elapsed: Arc::default(),
+        }
+    }
+
+    /// Get the time the clock was advanced since it was created.
+    pub fn get_elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Advance the clock by the given *duration*.
+    pub fn advance(&self, duration: Duration) {
+        let mut elapsed = self.elapsed.lock().unwrap_or_else(|e| e.into_inner());
+        *elapsed = elapsed.saturating_add(duration);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + self.get_elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> ClockFuture {
+        self.advance(duration);
+        Box::pin(ready(Ok(())))
+    }
+}
+
+#[cfg(test)]
+mod test_clock {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_manual_clock() {
+        // Create a manual clock and a clone:
+        let clock: ManualClock = ManualClock::new();
+        let clone: ManualClock = clock.to_owned();
+        let start: Instant = clock.now();
+
+        // Advance the clock and wait with the clone:
+        clock.advance(Duration::from_secs(30));
+        clone.sleep(Duration::from_secs(10)).await.unwrap();
+
+        // Verify the result:
+        assert_eq!(clock.get_elapsed(), Duration::from_secs(40));
+        assert_eq!(clone.now() - start, Duration::from_secs(40));
+    }
+
+    #[tokio::test]
+    async fn test_system_clock() {
+        // Wait with the system clock:
+        let start: Instant = SystemClock.now();
+        SystemClock.sleep(Duration::from_millis(10)).await.unwrap();
+
+        // Verify the result:
+        assert!(SystemClock.now() - start >= Duration::from_millis(10));
+    }
+}
@@ -1,26 +1,91 @@
-use redis::{streams::StreamId, Client};
-use tracing::{debug, info};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    future::Future,
+    hash::{Hash, Hasher},
+    ops::ControlFlow,
+    pin::Pin,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use futures_util::{
+    future::{select, Either},
+    stream::unfold,
+    Stream,
+};
+use redis::{
+    streams::{StreamId, StreamInfoGroup, StreamPendingReply},
+    Client, ErrorKind,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use time::OffsetDateTime;
+use tracing::{debug, info, info_span, instrument, warn, Span};
 
-use crate::core::streams::types::{LatestPendingMessageId, NextIdToClaim};
+use crate::core::streams::types::{
+    LatestPendingMessageId, NextIdToClaim, PendingAndClaimedMessages,
+};
 #[allow(unused_imports)]
 use crate::core::{
+    blocking::{run_blocking, sleep},
     client::{ClientArgs, RedisClientBuilder},
-    connection::VerifyConnection,
+    connection::{
+        ServerCapabilities, ServerCommands, ServerFeature, StreamConnection, VerifyConnection,
+    },
+    env::{env_lookup, get_var, prefixed, require_var, VarLookup},
     result::{RedsumerError, RedsumerResult},
     streams::{
-        consumer::{ConsumerCommands, BEGINNING_OF_TIME_ID},
-        types::{Id, LastDeliveredMilliseconds, TotalTimesDelivered},
+        consumer::{AckCommands, ConsumerCommands, BEGINNING_OF_TIME_ID},
+        types::{EntryId, Id, LastDeliveredMilliseconds, TotalTimesDelivered},
     },
 };
+use crate::redsumer::checkpoint::{Checkpoint, CheckpointStore};
+use crate::redsumer::clock::{Clock, SystemClock};
+use crate::redsumer::guard::MessageGuard;
+use crate::redsumer::handler::{
+    decode, HandleMessagesReply, HandlerOutcome, MessageHandler, MessageMeta,
+};
+use crate::redsumer::interceptor::Interceptor;
+use crate::redsumer::metrics::{
+    MetricsRecorder, NoopMetricsRecorder, Operation, RateMeter, SlowOperationThresholds,
+};
+use crate::redsumer::schema::{MessageSchema, SchemaViolation};
+
+/// Serialize and deserialize a [`Duration`] as a number of `milliseconds`.
+pub(crate) mod duration_milliseconds {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
 
 /// Options used to configure the consume operation when reading new messages from a Redis stream.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ReadNewMessagesOptions {
     /// The number of new messages to read from the stream.
     count: usize,
 
-    /// The block time in `seconds` to wait for new messages to arrive in the stream.
-    block: usize,
+    /// The block time to wait for new messages to arrive in the stream. It is serialized in `milliseconds`.
+    #[serde(with = "duration_milliseconds")]
+    block: Duration,
 }
 
 impl ReadNewMessagesOptions {
@@ -30,7 +95,7 @@ impl ReadNewMessagesOptions {
     }
 
     /// Get the block time to wait for new messages to arrive in the stream.
-    pub fn get_block(&self) -> usize {
+    pub fn get_block(&self) -> Duration {
         self.block
     }
 
@@ -38,23 +103,28 @@ impl ReadNewMessagesOptions {
     ///
     /// # Arguments:
     /// - **count**: The number of new messages to read from the stream.
-    /// - **block**: The block time in seconds to wait for new messages to arrive in the stream.
+    /// - **block**: The block time to wait for new messages to arrive in the stream. It is sent to Redis in `milliseconds`, and a zero duration does not block.
     ///
     /// # Returns:
     /// A new instance of [`ReadNewMessagesOptions`] with the given count and block time.
-    pub fn new(count: usize, block: usize) -> Self {
+    pub fn new(count: usize, block: Duration) -> Self {
         ReadNewMessagesOptions { count, block }
     }
 }
 
+/// Default options with [`DEFAULT_MESSAGES_COUNT`] and [`DEFAULT_BLOCK`].
+impl Default for ReadNewMessagesOptions {
+    fn default() -> Self {
+        ReadNewMessagesOptions::new(DEFAULT_MESSAGES_COUNT, DEFAULT_BLOCK)
+    }
+}
+
 /// Options used to configure the consume operation when reading pending messages from a Redis stream.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ReadPendingMessagesOptions {
     /// The number of pending messages to read from the stream.
     count: usize,
-
-    /// The latest pending message ID to start reading from.
-    latest_pending_message_id: String,
 }
 
 impl ReadPendingMessagesOptions {
@@ -63,29 +133,28 @@ impl ReadPendingMessagesOptions {
         self.count
     }
 
-    /// Get the latest pending message ID to start reading from.
-    fn get_latest_pending_message_id(&self) -> &str {
-        &self.latest_pending_message_id
-    }
-
     /// Create a new instance of [`ReadPendingMessagesOptions`].
     ///
     /// # Arguments:
     /// - **count**: The number of pending messages to read from the stream.
-    /// - **latest_pending_message_id**: The latest pending message ID to start reading from.
     ///
     /// # Returns:
-    /// A new instance of [`ReadPendingMessagesOptions`] with the given count and latest pending message ID.
+    /// A new instance of [`ReadPendingMessagesOptions`] with the given count.
     pub fn new(count: usize) -> Self {
-        ReadPendingMessagesOptions {
-            count,
-            latest_pending_message_id: BEGINNING_OF_TIME_ID.to_string(),
-        }
+        ReadPendingMessagesOptions { count }
+    }
+}
+
+/// Default options with [`DEFAULT_MESSAGES_COUNT`].
+impl Default for ReadPendingMessagesOptions {
+    fn default() -> Self {
+        ReadPendingMessagesOptions::new(DEFAULT_MESSAGES_COUNT)
     }
 }
 
 /// Options used to configure the consume operation when claiming messages from a Redis stream.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ClaimMessagesOptions {
     /// The number of messages to claim from the stream.
     count: usize,
@@ -93,8 +162,11 @@ pub struct ClaimMessagesOptions {
     /// The min idle time in `milliseconds` to claim the messages.
     min_idle_time: usize,
 
-    /// The latest ID to start claiming from.
-    next_id_to_claim: String,
+    /// Run the claim step on every Nth consume without new messages, instead of on all of them.
+    every: usize,
+
+    /// Offset the claim steps of every consumer by a phase derived from its name, so consumers of the same group claim on different consumes.
+    jitter: bool,
 }
 
 impl ClaimMessagesOptions {
@@ -108,9 +180,45 @@ impl ClaimMessagesOptions {
         self.min_idle_time
     }
 
-    /// Get the latest ID to start claiming from.
-    fn get_next_id_to_claim(&self) -> &str {
-        &self.next_id_to_claim
+    /// Get **every**, the number of consumes without new messages per claim.
+    pub fn get_every(&self) -> usize {
+        self.every
+    }
+
+    /// Verify if the claim steps are offset by a phase derived from the consumer name.
+    pub fn get_jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Set the claim step to run on every *every* consume without new messages, so a group of many consumers sends fewer `XAUTOCLAIM` commands. A value of `1`, the default, claims on every consume without new or pending messages.
+    pub fn every(mut self, every: usize) -> Self {
+        self.every = every;
+        self
+    }
+
+    /// Set whether the claim steps of every consumer are offset by a phase derived from its name, so consumers of the same group that claim every N consumes do not claim at the same time. It has no effect if the claim step runs on every consume.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Get the phase of the claim steps of *consumer_name*, between `0` and **every** exclusive. It is `0` without jitter.
+    fn get_phase(&self, consumer_name: &str) -> u64 {
+        let every: u64 = self.every.max(1) as u64;
+        match self.jitter {
+            true => {
+                let mut hasher: DefaultHasher = DefaultHasher::new();
+                consumer_name.hash(&mut hasher);
+                hasher.finish() % every
+            }
+            false => 0,
+        }
+    }
+
+    /// Verify if the claim step is due on the given *step*, the number of consumes of *consumer_name* without new messages before this one.
+    fn is_due(&self, consumer_name: &str, step: u64) -> bool {
+        let every: u64 = self.every.max(1) as u64;
+        (step + self.get_phase(consumer_name)) % every == 0
     }
 
     /// Create a new instance of [`ClaimMessagesOptions`].
@@ -120,18 +228,217 @@ impl ClaimMessagesOptions {
     /// - **min_idle_time**: The min idle time in milliseconds to claim the messages.
     ///
     /// # Returns:
-    /// A new instance of [`ClaimMessagesOptions`] with the given count, min idle time and latest pending message ID.
+    /// A new instance of [`ClaimMessagesOptions`] with the given count and min idle time, claiming on every consume without jitter.
     pub fn new(count: usize, min_idle_time: usize) -> Self {
         ClaimMessagesOptions {
             count,
             min_idle_time,
-            next_id_to_claim: BEGINNING_OF_TIME_ID.to_string(),
+            every: 1,
+            jitter: false,
+        }
+    }
+}
+
+/// Default options with [`DEFAULT_MESSAGES_COUNT`] and [`DEFAULT_MIN_IDLE_TIME`].
+impl Default for ClaimMessagesOptions {
+    fn default() -> Self {
+        ClaimMessagesOptions::new(DEFAULT_MESSAGES_COUNT, DEFAULT_MIN_IDLE_TIME)
+    }
+}
+
+/// Commands used to claim messages from other consumers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimMode {
+    /// Use `XPENDING` and `XCLAIM` if the server version detected when the consumer is created does not support `XAUTOCLAIM`. If the version can not be detected, `XAUTOCLAIM` is used.
+    #[default]
+    Auto,
+
+    /// Claim messages with `XAUTOCLAIM`, which requires Redis 6.2 or later. Creating the consumer fails if the server is older.
+    AutoClaim,
+
+    /// Claim messages with `XPENDING` and `XCLAIM`, for older Redis versions and compatible servers without `XAUTOCLAIM`. It takes one more round trip per claim.
+    Compatibility,
+}
+
+/// Parse a claim mode: `auto`, `auto_claim` or `compatibility`.
+impl FromStr for ClaimMode {
+    type Err = RedsumerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ClaimMode::Auto),
+            "auto_claim" => Ok(ClaimMode::AutoClaim),
+            "compatibility" => Ok(ClaimMode::Compatibility),
+            _ => Err(RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid claim mode",
+                format!("expected auto, auto_claim or compatibility, got: {s}"),
+            ))),
+        }
+    }
+}
+
+/// Position of the stream where a new consumers group starts consuming messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StartPosition {
+    /// Deliver all the messages in the stream, from the first one.
+    #[default]
+    Beginning,
+
+    /// Deliver only the messages added to the stream after the group is created. It is the `$` special ID of Redis.
+    OnlyNewMessages,
+
+    /// Deliver the messages with an ID greater than the given one.
+    FromId(EntryId),
+
+    /// Deliver the messages added to the stream after the given timestamp.
+    FromTimestamp(OffsetDateTime),
+}
+
+impl StartPosition {
+    /// Get the ID used by `XGROUP CREATE` to create the consumers group.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the ID. If the timestamp of [`StartPosition::FromTimestamp`] is before the Unix epoch, a [`RedsumerError`] is returned.
+    pub fn to_id(&self) -> RedsumerResult<String> {
+        match self {
+            StartPosition::Beginning => Ok(BEGINNING_OF_TIME_ID.to_owned()),
+            StartPosition::OnlyNewMessages => Ok("$".to_owned()),
+            StartPosition::FromId(id) => Ok(id.to_string()),
+            StartPosition::FromTimestamp(timestamp) => {
+                EntryId::from_timestamp(*timestamp).map(|id| id.to_string())
+            }
+        }
+    }
+}
+
+/// Behavior of [`run`](Consumer::run) and [`into_stream`](Consumer::into_stream) when consecutive consumes do not find messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleStrategy {
+    /// Consume again immediately. The only wait is the block time of [`ReadNewMessagesOptions`].
+    NoWait,
+
+    /// Wait a fixed duration after every consume without messages.
+    Fixed(Duration),
+
+    /// Wait *initial* after the first consume without messages, doubling the wait on every consecutive one up to *max*. The wait is reset when messages are found.
+    ExponentialBackoff {
+        /// Wait after the first consume without messages.
+        initial: Duration,
+
+        /// Max wait between consumes.
+        max: Duration,
+    },
+}
+
+impl IdleStrategy {
+    /// Get the wait after consecutive consumes without messages.
+    ///
+    /// # Arguments:
+    /// - **idle_consumes**: The number of consecutive consumes without messages, starting at `1`.
+    ///
+    /// # Returns:
+    /// The [`Duration`] to wait before the next consume.
+    pub fn get_delay(&self, idle_consumes: u32) -> Duration {
+        match self {
+            IdleStrategy::NoWait => Duration::ZERO,
+            IdleStrategy::Fixed(delay) => *delay,
+            IdleStrategy::ExponentialBackoff { initial, max } => initial
+                .saturating_mul(2u32.saturating_pow(idle_consumes.saturating_sub(1)))
+                .min(*max),
+        }
+    }
+}
+
+/// Exponential backoff from `100` milliseconds up to `5` seconds.
+impl Default for IdleStrategy {
+    fn default() -> Self {
+        IdleStrategy::ExponentialBackoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A callback fired for every message returned by the consume operation.
+pub type MessageCallback = Arc<dyn Fn(&StreamId) + Send + Sync>;
+
+/// A callback fired with the messages claimed from other consumers.
+pub type ClaimCallback = Arc<dyn Fn(&[StreamId]) + Send + Sync>;
+
+/// A callback fired when a consume attempt fails.
+pub type ErrorCallback = Arc<dyn Fn(&RedsumerError) + Send + Sync>;
+
+/// A callback fired with the message ID and the time elapsed since it was delivered, when a message is not acked within the ack deadline.
+pub type AckDeadlineCallback = Arc<dyn Fn(&str, Duration) + Send + Sync>;
+
+/// Optional lifecycle callbacks of a consumer, giving applications observability without modifying the consume loop.
+///
+/// Callbacks are called in the same task that runs the consume operation, so they should be cheap and must not block.
+#[derive(Clone, Default)]
+pub struct ConsumerCallbacks {
+    /// Callback fired for every consumed message, before it is returned to the caller to be processed.
+    on_message: Option<MessageCallback>,
+
+    /// Callback fired when messages are claimed from another consumer.
+    on_claim: Option<ClaimCallback>,
+
+    /// Callback fired when a consume attempt fails.
+    on_error: Option<ErrorCallback>,
+
+    /// Callback fired when a message is not acked within the ack deadline.
+    on_ack_deadline_exceeded: Option<AckDeadlineCallback>,
+}
+
+impl ConsumerCallbacks {
+    /// Fire the *on message* callback for every message.
+    fn message(&self, messages: &[StreamId]) {
+        if let Some(callback) = &self.on_message {
+            messages.iter().for_each(|message| callback(message));
+        }
+    }
+
+    /// Fire the *on claim* callback.
+    fn claim(&self, messages: &[StreamId]) {
+        if let Some(callback) = &self.on_claim {
+            callback(messages);
+        }
+    }
+
+    /// Fire the *on error* callback.
+    fn error(&self, error: &RedsumerError) {
+        if let Some(callback) = &self.on_error {
+            callback(error);
+        }
+    }
+
+    /// Fire the *on ack deadline exceeded* callback.
+    fn ack_deadline_exceeded(&self, id: &str, elapsed: Duration) {
+        if let Some(callback) = &self.on_ack_deadline_exceeded {
+            callback(id, elapsed);
         }
     }
 }
 
+impl Debug for ConsumerCallbacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsumerCallbacks")
+            .field("on_message", &self.on_message.is_some())
+            .field("on_claim", &self.on_claim.is_some())
+            .field("on_error", &self.on_error.is_some())
+            .field(
+                "on_ack_deadline_exceeded",
+                &self.on_ack_deadline_exceeded.is_some(),
+            )
+            .finish()
+    }
+}
+
 /// Define the configuration parameters to create a consumer instance.
-#[derive(Debug, Clone)]
+///
+/// It can be loaded from any format supported by [serde](https://docs.rs/serde). Missing options take the same default values used by [`ConsumerConfigBuilder`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsumerConfig {
     /// Stream name where messages will be consumed.
     stream_name: String,
@@ -143,13 +450,36 @@ pub struct ConsumerConfig {
     consumer_name: String,
 
     /// Options to configure the read new messages operation.
+    #[serde(default)]
     read_new_messages_options: ReadNewMessagesOptions,
 
     /// Options to configure the read pending messages operation.
+    #[serde(default)]
     read_pending_messages_options: ReadPendingMessagesOptions,
 
     /// Options to configure the claim messages operation.
+    #[serde(default)]
     claim_messages_options: ClaimMessagesOptions,
+
+    /// Create the stream if it does not exist when the consumers group is created.
+    #[serde(default)]
+    create_stream: bool,
+
+    /// Max time in `seconds` to wait for the stream to exist when the consumer is created.
+    #[serde(default)]
+    max_wait_seconds_for_stream: u64,
+
+    /// Read pending messages and claim messages in a single pipeline.
+    #[serde(default)]
+    pipeline_pending_and_claim: bool,
+
+    /// Commands used to claim messages from other consumers.
+    #[serde(default)]
+    claim_mode: ClaimMode,
+
+    /// Lifecycle callbacks of the consumer.
+    #[serde(skip)]
+    callbacks: ConsumerCallbacks,
 }
 
 impl ConsumerConfig {
@@ -183,6 +513,137 @@ impl ConsumerConfig {
         &self.claim_messages_options
     }
 
+    /// Get **create stream**.
+    pub fn get_create_stream(&self) -> bool {
+        self.create_stream
+    }
+
+    /// Get **max wait seconds for stream**.
+    pub fn get_max_wait_seconds_for_stream(&self) -> u64 {
+        self.max_wait_seconds_for_stream
+    }
+
+    /// Get **pipeline pending and claim**.
+    pub fn get_pipeline_pending_and_claim(&self) -> bool {
+        self.pipeline_pending_and_claim
+    }
+
+    /// Get **claim mode**.
+    pub fn get_claim_mode(&self) -> ClaimMode {
+        self.claim_mode
+    }
+
+    /// Get **callbacks**.
+    pub fn get_callbacks(&self) -> &ConsumerCallbacks {
+        &self.callbacks
+    }
+
+    /// Create a new [`ConsumerConfigBuilder`] instance to build a [`ConsumerConfig`] with chained setters and default options.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream where messages will be consumed.
+    /// - **group_name**: Consumers group name.
+    /// - **consumer_name**: Represents the consumer name within the specified consumers group, which must be ensured to be unique.
+    ///
+    /// # Returns:
+    /// A new [`ConsumerConfigBuilder`] instance.
+    pub fn builder(
+        stream_name: &str,
+        group_name: &str,
+        consumer_name: &str,
+    ) -> ConsumerConfigBuilder {
+        ConsumerConfigBuilder::new(stream_name, group_name, consumer_name)
+    }
+
+    /// Create a new [`ConsumerConfig`] instance from the environment variables of the process.
+    ///
+    /// The following variables are read, where `<PREFIX>` is the given *prefix* (e.g. `REDSUMER`):
+    ///
+    /// - `<PREFIX>_STREAM`: Stream name. It is mandatory.
+    /// - `<PREFIX>_GROUP`: Consumers group name. It is mandatory.
+    /// - `<PREFIX>_CONSUMER`: Consumer name. If it is not set, **HOSTNAME** is used, which matches the pod name in Kubernetes deployments.
+    /// - `<PREFIX>_NEW_MESSAGES_COUNT`, `<PREFIX>_BLOCK` (in `seconds`), `<PREFIX>_PENDING_MESSAGES_COUNT`, `<PREFIX>_CLAIMED_MESSAGES_COUNT` and `<PREFIX>_MIN_IDLE_TIME`: Optional values for the consume options. If they are not set, the [`ConsumerConfigBuilder`] defaults are used.
+    /// - `<PREFIX>_CREATE_STREAM`: Optional flag (`true` or `false`) to create the stream if it does not exist. It is `false` by default.
+    /// - `<PREFIX>_MAX_WAIT_SECONDS_FOR_STREAM`: Optional max time in `seconds` to wait for the stream to exist. It is `0` by default.
+    /// - `<PREFIX>_PIPELINE_PENDING_AND_CLAIM`: Optional flag (`true` or `false`) to read pending messages and claim messages in a single pipeline. It is `false` by default.
+    /// - `<PREFIX>_CLAIM_MODE`: Optional [`ClaimMode`] (`auto`, `auto_claim` or `compatibility`). It is `auto` by default.
+    /// - `<PREFIX>_CLAIM_EVERY`: Optional number of consumes without new messages per claim. It is `1` by default.
+    /// - `<PREFIX>_CLAIM_JITTER`: Optional flag (`true` or `false`) to offset the claim steps by a phase derived from the consumer name. It is `false` by default.
+    ///
+    /// # Arguments:
+    /// - **prefix**: Prefix of the variable names. If it is empty, variables are read without prefix.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a new [`ConsumerConfig`] instance. If a mandatory variable is missing or a value is invalid, a [`RedsumerError`] is returned.
+    pub fn from_env(prefix: &str) -> RedsumerResult<Self> {
+        Self::from_lookup(prefix, &env_lookup)
+    }
+
+    /// Create a new [`ConsumerConfig`] instance from the variables given by *lookup*.
+    fn from_lookup<L>(prefix: &str, lookup: &L) -> RedsumerResult<Self>
+    where
+        L: VarLookup,
+    {
+        let var = |name: &str| prefixed(prefix, name);
+
+        let consumer_name: String = match get_var::<String, _>(lookup, &var("CONSUMER"))? {
+            Some(name) => name,
+            None => require_var(lookup, "HOSTNAME")?,
+        };
+
+        let mut builder: ConsumerConfigBuilder = ConsumerConfigBuilder::new(
+            &require_var::<String, _>(lookup, &var("STREAM"))?,
+            &require_var::<String, _>(lookup, &var("GROUP"))?,
+            &consumer_name,
+        );
+
+        if let Some(count) = get_var(lookup, &var("NEW_MESSAGES_COUNT"))? {
+            builder = builder.new_messages_count(count);
+        }
+
+        if let Some(block) = get_var(lookup, &var("BLOCK"))? {
+            builder = builder.block(Duration::from_secs(block));
+        }
+
+        if let Some(count) = get_var(lookup, &var("PENDING_MESSAGES_COUNT"))? {
+            builder = builder.pending_messages_count(count);
+        }
+
+        if let Some(count) = get_var(lookup, &var("CLAIMED_MESSAGES_COUNT"))? {
+            builder = builder.claimed_messages_count(count);
+        }
+
+        if let Some(min_idle_time) = get_var(lookup, &var("MIN_IDLE_TIME"))? {
+            builder = builder.min_idle_time(min_idle_time);
+        }
+
+        if let Some(create_stream) = get_var(lookup, &var("CREATE_STREAM"))? {
+            builder = builder.create_stream(create_stream);
+        }
+
+        if let Some(seconds) = get_var(lookup, &var("MAX_WAIT_SECONDS_FOR_STREAM"))? {
+            builder = builder.max_wait_seconds_for_stream(seconds);
+        }
+
+        if let Some(value) = get_var(lookup, &var("PIPELINE_PENDING_AND_CLAIM"))? {
+            builder = builder.pipeline_pending_and_claim(value);
+        }
+
+        if let Some(mode) = get_var(lookup, &var("CLAIM_MODE"))? {
+            builder = builder.claim_mode(mode);
+        }
+
+        if let Some(every) = get_var(lookup, &var("CLAIM_EVERY"))? {
+            builder = builder.claim_every(every);
+        }
+
+        if let Some(jitter) = get_var(lookup, &var("CLAIM_JITTER"))? {
+            builder = builder.claim_jitter(jitter);
+        }
+
+        builder.build()
+    }
+
     /// Create a new [`ConsumerConfig`] instance.
     ///
     /// # Arguments:
@@ -211,89 +672,559 @@ impl ConsumerConfig {
             read_new_messages_options,
             read_pending_messages_options,
             claim_messages_options,
+            create_stream: false,
+            max_wait_seconds_for_stream: 0,
+            pipeline_pending_and_claim: false,
+            claim_mode: ClaimMode::default(),
+            callbacks: ConsumerCallbacks::default(),
         }
     }
 }
 
-/// Define the kind of messages that were consumed by a specific consumer.
+/// Default number of messages to read or claim in each step of the consume operation.
+pub const DEFAULT_MESSAGES_COUNT: usize = 10;
+
+/// Default block time to wait for new messages to arrive in the stream: `5` seconds, sent to Redis as `5000` milliseconds.
+pub const DEFAULT_BLOCK: Duration = Duration::from_secs(5);
+
+/// Default min idle time in `milliseconds` to claim messages from other consumers.
+pub const DEFAULT_MIN_IDLE_TIME: usize = 30_000;
+
+/// A builder to create a [`ConsumerConfig`] instance with chained setters.
+///
+/// Stream, group and consumer names are mandatory. The remaining parameters take the following default values:
+///
+/// - **new messages count**: [`DEFAULT_MESSAGES_COUNT`].
+/// - **block**: [`DEFAULT_BLOCK`].
+/// - **pending messages count**: [`DEFAULT_MESSAGES_COUNT`].
+/// - **claimed messages count**: [`DEFAULT_MESSAGES_COUNT`].
+/// - **min idle time**: [`DEFAULT_MIN_IDLE_TIME`] milliseconds.
+/// - **create stream**: `false`.
+/// - **max wait seconds for stream**: `0`, so the stream must exist when the consumer is created.
+/// - **pipeline pending and claim**: `false`.
+/// - **claim mode**: [`ClaimMode::Auto`].
+/// - **claim every**: `1`, so messages are claimed on every consume without new or pending messages.
+/// - **claim jitter**: `false`.
 #[derive(Debug, Clone)]
-enum MessagesKind {
-    /// The messages were obtained from the new messages list and have not been delivered before to any consumer.
-    New,
+pub struct ConsumerConfigBuilder {
+    /// Stream name where messages will be consumed.
+    stream_name: String,
 
-    /// The messages were read from the consumer pending list. They were delivered to a consumer before, but they were not acked yet and they were not claimed by another consumer.
-    Pending,
+    /// Group name where the consumer is registered.
+    group_name: String,
 
-    /// The messages were claimed by another consumer and they were not acked yet.
-    Claimed,
+    /// Consumer name within the specified consumers group.
+    consumer_name: String,
 
-    /// Messages were not obtained from stream. It means that there are no new, pending or claimed messages to be processed by a consumer in the specified group.
-    NotFound,
+    /// Options to configure the read new messages operation.
+    read_new_messages_options: ReadNewMessagesOptions,
+
+    /// Options to configure the read pending messages operation.
+    read_pending_messages_options: ReadPendingMessagesOptions,
+
+    /// Options to configure the claim messages operation.
+    claim_messages_options: ClaimMessagesOptions,
+
+    /// Create the stream if it does not exist when the consumers group is created.
+    create_stream: bool,
+
+    /// Max time in `seconds` to wait for the stream to exist when the consumer is created.
+    max_wait_seconds_for_stream: u64,
+
+    /// Read pending messages and claim messages in a single pipeline.
+    pipeline_pending_and_claim: bool,
+
+    /// Commands used to claim messages from other consumers.
+    claim_mode: ClaimMode,
+
+    /// Lifecycle callbacks of the consumer.
+    callbacks: ConsumerCallbacks,
 }
 
-impl MessagesKind {
-    /// Check if the messages are new.
-    fn are_new(&self) -> bool {
-        matches!(self, MessagesKind::New)
+impl ConsumerConfigBuilder {
+    /// Create a new [`ConsumerConfigBuilder`] instance with default options.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream where messages will be consumed.
+    /// - **group_name**: Consumers group name.
+    /// - **consumer_name**: Represents the consumer name within the specified consumers group, which must be ensured to be unique.
+    ///
+    /// # Returns:
+    /// A new [`ConsumerConfigBuilder`] instance.
+    pub fn new(stream_name: &str, group_name: &str, consumer_name: &str) -> Self {
+        ConsumerConfigBuilder {
+            stream_name: stream_name.to_owned(),
+            group_name: group_name.to_owned(),
+            consumer_name: consumer_name.to_owned(),
+            read_new_messages_options: ReadNewMessagesOptions::default(),
+            read_pending_messages_options: ReadPendingMessagesOptions::default(),
+            claim_messages_options: ClaimMessagesOptions::default(),
+            create_stream: false,
+            max_wait_seconds_for_stream: 0,
+            pipeline_pending_and_claim: false,
+            claim_mode: ClaimMode::default(),
+            callbacks: ConsumerCallbacks::default(),
+        }
     }
 
-    /// Check if the messages are pending.
-    fn are_pending(&self) -> bool {
-        matches!(self, MessagesKind::Pending)
+    /// Set the number of new messages to read from the stream.
+    pub fn new_messages_count(mut self, count: usize) -> Self {
+        self.read_new_messages_options.count = count;
+        self
     }
 
-    /// Check if the messages were claimed.
-    fn were_claimed(&self) -> bool {
-        matches!(self, MessagesKind::Claimed)
+    /// Set the block time to wait for new messages to arrive in the stream. A zero duration does not block.
+    pub fn block(mut self, block: Duration) -> Self {
+        self.read_new_messages_options.block = block;
+        self
     }
 
-    /// Check if the messages were not found.
-    fn not_found(&self) -> bool {
-        matches!(self, MessagesKind::NotFound)
+    /// Set the number of pending messages to read from the stream.
+    pub fn pending_messages_count(mut self, count: usize) -> Self {
+        self.read_pending_messages_options.count = count;
+        self
     }
-}
 
-/// A reply to consume messages from a Redis stream. It contains a list of stream IDs and the kind of messages.
-#[derive(Debug, Clone)]
-pub struct ConsumeMessagesReply {
-    /// A list of stream IDs.
-    messages: Vec<StreamId>,
+    /// Set the number of messages to claim from the stream.
+    pub fn claimed_messages_count(mut self, count: usize) -> Self {
+        self.claim_messages_options.count = count;
+        self
+    }
 
-    /// The kind of messages.
-    kind: MessagesKind,
-}
+    /// Set the min idle time in `milliseconds` to claim messages.
+    pub fn min_idle_time(mut self, min_idle_time: usize) -> Self {
+        self.claim_messages_options.min_idle_time = min_idle_time;
+        self
+    }
 
-impl ConsumeMessagesReply {
-    /// Get **messages**.
-    pub fn get_messages(&self) -> &Vec<StreamId> {
-        &self.messages
+    /// Set the claim step to run on every *every* consume without new messages, see [`ClaimMessagesOptions::every`].
+    pub fn claim_every(mut self, every: usize) -> Self {
+        self.claim_messages_options.every = every;
+        self
     }
 
-    /// Verify if the messages are new.
-    pub fn are_new(&self) -> bool {
-        self.kind.are_new()
+    /// Set whether the claim steps are offset by a phase derived from the consumer name, see [`ClaimMessagesOptions::jitter`].
+    pub fn claim_jitter(mut self, jitter: bool) -> Self {
+        self.claim_messages_options.jitter = jitter;
+        self
     }
 
-    /// Verify if the messages are pending in the consumer pending list.
-    pub fn are_pending(&self) -> bool {
-        self.kind.are_pending()
+    /// Replace the whole [`ReadNewMessagesOptions`].
+    pub fn read_new_messages_options(mut self, options: ReadNewMessagesOptions) -> Self {
+        self.read_new_messages_options = options;
+        self
     }
 
-    /// Verify if the messages were claimed by another consumer.
-    pub fn were_claimed(&self) -> bool {
-        self.kind.were_claimed()
+    /// Replace the whole [`ReadPendingMessagesOptions`].
+    pub fn read_pending_messages_options(mut self, options: ReadPendingMessagesOptions) -> Self {
+        self.read_pending_messages_options = options;
+        self
     }
 
-    /// Verify if the messages were not found.
-    pub fn not_found(&self) -> bool {
-        self.kind.not_found()
+    /// Replace the whole [`ClaimMessagesOptions`].
+    pub fn claim_messages_options(mut self, options: ClaimMessagesOptions) -> Self {
+        self.claim_messages_options = options;
+        self
+    }
+
+    /// Set whether the stream is created with `MKSTREAM` if it does not exist, so consumers can be deployed before the first message is produced.
+    pub fn create_stream(mut self, create_stream: bool) -> Self {
+        self.create_stream = create_stream;
+        self
+    }
+
+    /// Set the max time in `seconds` to wait for the stream to exist when the consumer is created. The stream is polled with exponential backoff, which smooths race conditions with producers at deploy time.
+    pub fn max_wait_seconds_for_stream(mut self, seconds: u64) -> Self {
+        self.max_wait_seconds_for_stream = seconds;
+        self
+    }
+
+    /// Set whether pending messages are read and messages are claimed in a single pipeline, saving a round trip per consume when no new messages are found. Messages claimed while pending messages are found are returned as pending messages in later consumes. The pipeline is not used when messages are claimed with `XCLAIM`, see [`claim_mode`](ConsumerConfigBuilder::claim_mode).
+    pub fn pipeline_pending_and_claim(mut self, pipeline_pending_and_claim: bool) -> Self {
+        self.pipeline_pending_and_claim = pipeline_pending_and_claim;
+        self
+    }
+
+    /// Set the [`ClaimMode`], to claim messages with `XPENDING` and `XCLAIM` on servers without `XAUTOCLAIM`, like Redis versions before 6.2.
+    pub fn claim_mode(mut self, claim_mode: ClaimMode) -> Self {
+        self.claim_mode = claim_mode;
+        self
+    }
+
+    /// Set a callback fired for every consumed message, before it is returned to be processed.
+    pub fn on_message<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&StreamId) + Send + Sync + 'static,
+    {
+        self.callbacks.on_message = Some(Arc::new(callback));
+        self
+    }
+
+    /// Set a callback fired when messages are claimed from another consumer.
+    pub fn on_claim<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&[StreamId]) + Send + Sync + 'static,
+    {
+        self.callbacks.on_claim = Some(Arc::new(callback));
+        self
+    }
+
+    /// Set a callback fired when a consume attempt fails.
+    pub fn on_error<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&RedsumerError) + Send + Sync + 'static,
+    {
+        self.callbacks.on_error = Some(Arc::new(callback));
+        self
+    }
+
+    /// Set a callback fired when a message is not acked within the ack deadline, see [`Consumer::with_ack_deadline`].
+    pub fn on_ack_deadline_exceeded<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str, Duration) + Send + Sync + 'static,
+    {
+        self.callbacks.on_ack_deadline_exceeded = Some(Arc::new(callback));
+        self
+    }
+
+    /// Build a new [`ConsumerConfig`] instance.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a new [`ConsumerConfig`] instance. If the stream, group or consumer name is empty, or the claim step runs every `0` consumes, a [`RedsumerError`] is returned.
+    pub fn build(self) -> RedsumerResult<ConsumerConfig> {
+        for (field, value) in [
+            ("stream name", &self.stream_name),
+            ("group name", &self.group_name),
+            ("consumer name", &self.consumer_name),
+        ] {
+            if value.is_empty() {
+                return Err(RedsumerError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "Invalid consumer configuration",
+                    format!("The {field} must not be empty"),
+                )));
+            }
+        }
+
+        if self.claim_messages_options.every.eq(&0) {
+            return Err(RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid consumer configuration",
+                "The claim step must run every 1 or more consumes".to_string(),
+            )));
+        }
+
+        let mut config: ConsumerConfig = ConsumerConfig::new(
+            &self.stream_name,
+            &self.group_name,
+            &self.consumer_name,
+            self.read_new_messages_options,
+            self.read_pending_messages_options,
+            self.claim_messages_options,
+        );
+        config.create_stream = self.create_stream;
+        config.max_wait_seconds_for_stream = self.max_wait_seconds_for_stream;
+        config.pipeline_pending_and_claim = self.pipeline_pending_and_claim;
+        config.claim_mode = self.claim_mode;
+        config.callbacks = self.callbacks;
+
+        Ok(config)
+    }
+}
+
+/// Define the kind of messages that were consumed by a specific consumer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessagesKind {
+    /// The messages were obtained from the new messages list and have not been delivered before to any consumer.
+    New,
+
+    /// The messages were read from the consumer pending list. They were delivered to a consumer before, but they were not acked yet and they were not claimed by another consumer.
+    Pending,
+
+    /// The messages were claimed by another consumer and they were not acked yet.
+    Claimed,
+
+    /// Messages were not obtained from stream. It means that there are no new, pending or claimed messages to be processed by a consumer in the specified group.
+    NotFound,
+}
+
+impl MessagesKind {
+    /// Check if the messages are new.
+    pub fn are_new(&self) -> bool {
+        matches!(self, MessagesKind::New)
+    }
+
+    /// Check if the messages are pending.
+    pub fn are_pending(&self) -> bool {
+        matches!(self, MessagesKind::Pending)
+    }
+
+    /// Check if the messages were claimed.
+    pub fn were_claimed(&self) -> bool {
+        matches!(self, MessagesKind::Claimed)
+    }
+
+    /// Check if the messages were not found.
+    pub fn not_found(&self) -> bool {
+        matches!(self, MessagesKind::NotFound)
+    }
+}
+
+/// A reply to consume messages from a Redis stream. It contains a list of stream IDs and the kind of messages.
+#[derive(Debug, Clone)]
+pub struct ConsumeMessagesReply {
+    /// A list of stream IDs.
+    messages: Vec<StreamId>,
+
+    /// The kind of messages.
+    kind: MessagesKind,
+
+    /// The tokens to ack the messages, issued by the consumer that consumed them.
+    ack_tokens: Vec<AckToken>,
+
+    /// Position of every ack token by message ID.
+    ack_token_index: HashMap<Id, usize>,
+}
+
+impl ConsumeMessagesReply {
+    /// Get **messages**.
+    pub fn get_messages(&self) -> &Vec<StreamId> {
+        &self.messages
+    }
+
+    /// Get **kind** of messages.
+    pub fn get_kind(&self) -> &MessagesKind {
+        &self.kind
+    }
+
+    /// Get the **ack tokens** of the messages, in the same order. They are issued by [`Consumer::consume`] and [`Consumer::claim_from`], and it is empty if the reply was built otherwise.
+    pub fn get_ack_tokens(&self) -> &[AckToken] {
+        &self.ack_tokens
+    }
+
+    /// Get the [`AckToken`] of a specific message by *id*, if it was issued.
+    ///
+    /// # Arguments:
+    /// - **id**: The ID of the message, as in [`StreamId::id`].
+    ///
+    /// # Returns:
+    /// The [`AckToken`] of the message, or [`None`] if the message is not in the reply or no token was issued.
+    pub fn get_ack_token(&self, id: &str) -> Option<&AckToken> {
+        let id: Id = id.parse().ok()?;
+        self.ack_token_index
+            .get(&id)
+            .map(|position| &self.ack_tokens[*position])
+    }
+
+    /// Issue an [`AckToken`] for every message of the reply, scoped to the given stream, consumers group and consumer.
+    pub(crate) fn issue_ack_tokens(
+        &mut self,
+        stream_name: &str,
+        group_name: &str,
+        consumer_name: &str,
+    ) {
+        self.ack_tokens = self
+            .messages
+            .iter()
+            .filter_map(|message| message.id.parse::<Id>().ok())
+            .map(|id| AckToken::new(id, stream_name, group_name, consumer_name))
+            .collect();
+        self.ack_token_index = self
+            .ack_tokens
+            .iter()
+            .enumerate()
+            .map(|(position, token)| (*token.get_id(), position))
+            .collect();
+    }
+
+    /// Verify if the messages are new.
+    pub fn are_new(&self) -> bool {
+        self.kind.are_new()
+    }
+
+    /// Verify if the messages are pending in the consumer pending list.
+    pub fn are_pending(&self) -> bool {
+        self.kind.are_pending()
+    }
+
+    /// Verify if the messages were claimed by another consumer.
+    pub fn were_claimed(&self) -> bool {
+        self.kind.were_claimed()
+    }
+
+    /// Verify if the messages were not found.
+    pub fn not_found(&self) -> bool {
+        self.kind.not_found()
+    }
+
+    /// Get the number of messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Verify if there are no messages.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Get an iterator over the messages.
+    pub fn iter(&self) -> std::slice::Iter<'_, StreamId> {
+        self.messages.iter()
+    }
+
+    /// Get the **new messages** of the reply, so handlers can apply a different policy to every kind of messages. It is empty if the messages are not new.
+    pub fn new_messages(&self) -> &[StreamId] {
+        self.get_messages_by_kind(MessagesKind::New)
+    }
+
+    /// Get the **pending messages** of the reply, read from the consumer pending list. It is empty if the messages are not pending.
+    pub fn pending_messages(&self) -> &[StreamId] {
+        self.get_messages_by_kind(MessagesKind::Pending)
+    }
+
+    /// Get the **claimed messages** of the reply, claimed from other consumers. It is empty if the messages were not claimed.
+    pub fn claimed_messages(&self) -> &[StreamId] {
+        self.get_messages_by_kind(MessagesKind::Claimed)
+    }
+
+    /// Validate the messages of the reply against a *schema*.
+    ///
+    /// # Arguments:
+    /// - **schema**: The [`MessageSchema`] every message must comply with.
+    ///
+    /// # Returns:
+    /// A [`HashMap`] with the [`SchemaViolation`] list of every message that does not comply with the schema, by message ID. It is empty if every message is valid.
+    pub fn validate(&self, schema: &MessageSchema) -> HashMap<String, Vec<SchemaViolation>> {
+        self.messages
+            .iter()
+            .filter_map(|message| {
+                let violations: Vec<SchemaViolation> = schema.validate(message);
+                (!violations.is_empty()).then(|| (message.id.to_owned(), violations))
+            })
+            .collect()
+    }
+
+    /// Get the messages of the reply if they are of the given *kind*. Otherwise, an empty slice is returned.
+    fn get_messages_by_kind(&self, kind: MessagesKind) -> &[StreamId] {
+        match self.kind.eq(&kind) {
+            true => &self.messages,
+            false => &[],
+        }
+    }
+}
+
+/// Iterate over the messages of a [`ConsumeMessagesReply`], taking ownership of them.
+impl IntoIterator for ConsumeMessagesReply {
+    type Item = StreamId;
+    type IntoIter = std::vec::IntoIter<StreamId>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.messages.into_iter()
+    }
+}
+
+/// Iterate over the messages of a borrowed [`ConsumeMessagesReply`].
+impl<'a> IntoIterator for &'a ConsumeMessagesReply {
+    type Item = &'a StreamId;
+    type IntoIter = std::slice::Iter<'a, StreamId>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.messages.iter()
     }
 }
 
 /// Convert a tuple into a [`ConsumeMessagesReply`] instance.
 impl From<(Vec<StreamId>, MessagesKind)> for ConsumeMessagesReply {
     fn from((messages, kind): (Vec<StreamId>, MessagesKind)) -> Self {
-        ConsumeMessagesReply { messages, kind }
+        ConsumeMessagesReply {
+            messages,
+            kind,
+            ack_tokens: Vec::new(),
+            ack_token_index: HashMap::new(),
+        }
+    }
+}
+
+/// A token to ack a consumed message, issued by the consumer that consumed it. It ties the message ID to its stream, consumers group and consumer, so [`Consumer::ack`] rejects IDs that were consumed from another stream or group.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AckToken {
+    /// The ID of the message.
+    id: Id,
+
+    /// The name of the stream the message was consumed from.
+    stream_name: String,
+
+    /// The name of the consumers group the message was consumed by.
+    group_name: String,
+
+    /// The name of the consumer the message was delivered to.
+    consumer_name: String,
+}
+
+impl AckToken {
+    /// Build a new [`AckToken`] instance. Tokens are only issued by consumers.
+    pub(crate) fn new(id: Id, stream_name: &str, group_name: &str, consumer_name: &str) -> Self {
+        AckToken {
+            id,
+            stream_name: stream_name.to_owned(),
+            group_name: group_name.to_owned(),
+            consumer_name: consumer_name.to_owned(),
+        }
+    }
+
+    /// Get **id** of the message.
+    pub fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    /// Get **stream name** the message was consumed from.
+    pub fn get_stream_name(&self) -> &str {
+        &self.stream_name
+    }
+
+    /// Get **group name** the message was consumed by.
+    pub fn get_group_name(&self) -> &str {
+        &self.group_name
+    }
+
+    /// Get **consumer name** the message was delivered to.
+    pub fn get_consumer_name(&self) -> &str {
+        &self.consumer_name
+    }
+
+    /// Verify if the token was issued for the stream, consumers group and consumer of the given *config*.
+    pub(crate) fn verify(&self, config: &ConsumerConfig) -> RedsumerResult<()> {
+        match self.stream_name.eq(config.get_stream_name()) {
+            true => self.verify_consumer(config.get_group_name(), config.get_consumer_name()),
+            false => Err(self.invalid()),
+        }
+    }
+
+    /// Verify if the token was issued for the given consumers group and consumer, in any stream.
+    pub(crate) fn verify_consumer(
+        &self,
+        group_name: &str,
+        consumer_name: &str,
+    ) -> RedsumerResult<()> {
+        match self.group_name.eq(group_name) && self.consumer_name.eq(consumer_name) {
+            true => Ok(()),
+            false => Err(self.invalid()),
+        }
+    }
+
+    /// Build the error returned when the token is verified by a consumer it was not issued for.
+    fn invalid(&self) -> RedsumerError {
+        RedsumerError::from((
+            ErrorKind::ClientError,
+            "Invalid ack token",
+            format!(
+                "The ack token of message {} was issued for stream {}, group {} and consumer {}",
+                self.id, self.stream_name, self.group_name, self.consumer_name,
+            ),
+        ))
     }
 }
 
@@ -377,453 +1308,5278 @@ impl From<bool> for AckMessageReply {
     }
 }
 
-/// A consumer implementation of Redis Streams. The consumer is responsible for consuming messages from a stream. It can read new messages,  pending messages or claim messages from other consumers according to their min idle time.
-#[derive(Debug, Clone)]
-pub struct Consumer {
-    /// Redis client to interact with Redis server.
-    client: Client,
+/// A reply to ack a set of messages, with the specific messages that were acked and that were not.
+#[derive(Debug, Clone, Default)]
+pub struct AckManyReply {
+    /// The IDs of the messages that were acked.
+    acked: Vec<Id>,
 
-    /// Consumer configuration parameters.
-    config: ConsumerConfig,
+    /// The IDs of the messages that were not acked, since they were not pending in the consumers group.
+    not_acked: Vec<Id>,
 }
 
-impl Consumer {
-    /// Get [`Client`].
-    fn get_client(&self) -> &Client {
-        &self.client
+impl AckManyReply {
+    /// Get **acked** message IDs.
+    pub fn get_acked(&self) -> &[Id] {
+        &self.acked
     }
 
-    /// Get *config*.
-    pub fn get_config(&self) -> &ConsumerConfig {
-        &self.config
+    /// Get **not acked** message IDs. It is recommended to verify if another consumer has claimed or acked these messages before trying to process them again.
+    pub fn get_not_acked(&self) -> &[Id] {
+        &self.not_acked
     }
 
-    /// Update the latest pending message ID to start reading from.
-    fn update_latest_pending_message_id(&mut self, id: &str) {
-        self.config
-            .read_pending_messages_options
-            .latest_pending_message_id = id.to_owned();
+    /// Verify if all the messages were acked.
+    pub fn all_acked(&self) -> bool {
+        self.not_acked.is_empty()
     }
+}
 
-    /// Update the next ID to claim.
-    fn update_next_id_to_claim(&mut self, id: &str) {
-        self.config.claim_messages_options.next_id_to_claim = id.to_owned();
-    }
+/// Convert a list of IDs and their ack results into a [`AckManyReply`] instance.
+impl From<(&[Id], Vec<bool>)> for AckManyReply {
+    fn from((ids, acked): (&[Id], Vec<bool>)) -> Self {
+        let mut reply: AckManyReply = AckManyReply::default();
 
-    /// Build a new [`Consumer`] instance.
-    ///
-    ///  Before creating a new consumer, the following validations are performed:
-    ///
-    /// - If connection string is invalid, a [`RedsumerError`] is returned.
-    /// - If connection to Redis server can not be established, a [`RedsumerError`] is returned.
-    /// - If the stream does not exist, a [`RedsumerError`] is returned: The stream must exist before creating a new consumer.
-    ///  - If the consumers group does not exist, it is created based on the *stream_name*, *group_name* and the given *initial_stream_id*. If an error occurs during the creation process, a [`RedsumerError`] is returned.
-    ///
-    /// # Arguments:
-    /// - **args**: Client arguments to build a new [`Client`] instance.
-    /// - **config**: Consumer configuration parameters.
-    /// - **initial_stream_id**: The ID of the message to start consuming.
-    ///
-    ///  # Returns:
-    /// - A [`RedsumerResult`] containing a [`Consumer`] instance. Otherwise, a [`RedsumerError`] is returned.
-    pub fn new(
-        args: ClientArgs,
-        config: ConsumerConfig,
-        initial_stream_id: Option<String>,
-    ) -> RedsumerResult<Self> {
-        debug!(
-            "Creating a new consumer instance by: {:?} and {:?}",
-            args, config
-        );
+        for (id, acked) in ids.iter().zip(acked) {
+            match acked {
+                true => reply.acked.push(*id),
+                false => reply.not_acked.push(*id),
+            }
+        }
 
-        let mut client: Client = args.build()?;
-        client.ping()?;
+        reply
+    }
+}
 
-        client.verify_if_stream_exists(config.get_stream_name())?;
-        client.create_consumer_group(
-            config.get_stream_name(),
-            config.get_group_name(),
-            initial_stream_id.unwrap_or(BEGINNING_OF_TIME_ID.to_string()),
-        )?;
+/// A reply to remove a consumer from the consumers group.
+#[derive(Debug, Clone)]
+pub struct RemoveConsumerReply {
+    /// The number of pending messages the consumer had before it was removed.
+    pending_messages: usize,
+}
 
-        info!("Consumer was created successfully and it is ready to be used");
+impl RemoveConsumerReply {
+    /// Get **pending messages**. These messages are no longer pending for any consumer, so they will not be claimed again.
+    pub fn get_pending_messages(&self) -> usize {
+        self.pending_messages
+    }
+}
 
-        Ok(Self { client, config })
+/// Convert a number of pending messages into a [`RemoveConsumerReply`] instance.
+impl From<usize> for RemoveConsumerReply {
+    fn from(pending_messages: usize) -> Self {
+        RemoveConsumerReply { pending_messages }
     }
+}
 
-    /// Consume messages from stream according to the following steps:
-    ///
-    /// 1. Consumer tries to get new messages. If new messages are found, they are returned as a result.
-    /// 2. If new messages are not found, consumer tries to get pending messages. If pending messages are found, they are returned as a result.
-    /// 3. If pending messages are not found, consumer tries to claim messages from other consumers according to *min_idle_time_milliseconds*. If claimed messages are found, they are returned as a result.
-    /// 4. If new, pending or claimed messages are not found, an empty list is returned as a result.
-    ///
-    ///  # Arguments:
-    ///  *No arguments*
-    ///
-    ///  # Returns:
-    ///  - A [`RedsumerResult`] containing a list of [`ConsumeMessagesReply`] if new, pending or claimed messages are found, otherwise an empty list is returned. If an error occurs, a [`RedsumerError`] is returned.
-    pub async fn consume(&mut self) -> RedsumerResult<ConsumeMessagesReply> {
-        debug!(
-            "Consuming messages from stream {}",
-            self.get_config().get_stream_name()
-        );
+/// The lag of a consumers group, as reported by `XINFO GROUPS`. It is useful for autoscaling and dashboards.
+#[derive(Debug, Clone)]
+pub struct GroupLag {
+    /// The number of entries in the stream that are still waiting to be delivered to the group.
+    lag: Option<usize>,
 
-        debug!(
-            "Processing new messages by: {:?}",
-            self.get_config().get_read_new_messages_options()
-        );
+    /// The logical read counter of the last entry delivered to the group.
+    entries_read: Option<usize>,
 
-        let new_messages: Vec<StreamId> = self.get_client().to_owned().read_new_messages(
-            &self.get_config().get_stream_name(),
-            &self.get_config().get_group_name(),
-            &self.get_config().get_consumer_name(),
-            self.get_config()
-                .get_read_new_messages_options()
-                .get_count(),
-            self.get_config()
-                .get_read_new_messages_options()
-                .get_block(),
-        )?;
-        if new_messages.len().gt(&0) {
-            debug!("Total new messages found: {}", new_messages.len());
-            return Ok((new_messages, MessagesKind::New).into());
+    /// The number of messages delivered to the group but not acknowledged yet.
+    pending: usize,
+}
+
+impl GroupLag {
+    /// Get **lag**. It is `None` when Redis can not determine it, for example with Redis < 7.0 or after deleting entries from the stream.
+    pub fn get_lag(&self) -> Option<usize> {
+        self.lag
+    }
+
+    /// Get **entries read**. It is `None` with Redis < 7.0.
+    pub fn get_entries_read(&self) -> Option<usize> {
+        self.entries_read
+    }
+
+    /// Get **pending** messages of the whole group.
+    pub fn get_pending(&self) -> usize {
+        self.pending
+    }
+}
+
+/// Convert a [`StreamInfoGroup`] into a [`GroupLag`] instance.
+impl From<StreamInfoGroup> for GroupLag {
+    fn from(info: StreamInfoGroup) -> Self {
+        GroupLag {
+            lag: info.lag,
+            entries_read: info.entries_read,
+            pending: info.pending,
         }
+    }
+}
 
-        debug!(
-            "Processing pending messages by: {:?}",
-            self.get_config().get_read_pending_messages_options()
-        );
+/// Action taken by the consumer when a message is not acked within the ack deadline, see [`Consumer::with_ack_deadline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AckDeadlineAction {
+    /// Renew the ownership of the message, resetting its idle time so it is not claimed by other consumers.
+    Renew,
 
-        let (pending_messages, latest_pending_message_id): (Vec<StreamId>, LatestPendingMessageId) =
-            self.get_client().to_owned().read_pending_messages(
-                &self.get_config().get_stream_name(),
-                &self.get_config().get_group_name(),
-                &self.get_config().get_consumer_name(),
-                self.get_config()
-                    .get_read_pending_messages_options()
-                    .get_latest_pending_message_id(),
-                self.get_config()
-                    .get_read_pending_messages_options()
-                    .get_count(),
-            )?;
+    /// Log a warning and fire the *on ack deadline exceeded* callback of the [`ConsumerConfig`].
+    Warn,
+}
 
-        debug!("Updating latest pending message ID to: {latest_pending_message_id}",);
+/// Filter of the pending messages taken over from a specific consumer with [`Consumer::claim_from`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClaimFilter {
+    /// Claim the messages with the given IDs, if they are pending for the consumer.
+    Ids(Vec<Id>),
 
-        self.update_latest_pending_message_id(&latest_pending_message_id);
-        if pending_messages.len().gt(&0) {
-            debug!("Total pending messages found: {}", pending_messages.len());
-            return Ok((pending_messages, MessagesKind::Pending).into());
+    /// Claim up to *count* pending messages of the consumer, from the oldest, with an idle time greater than or equal to *min_idle_time* in milliseconds.
+    Idle { min_idle_time: usize, count: usize },
+}
+
+/// Ack deadline of the messages in flight.
+#[derive(Debug, Clone, Copy)]
+struct AckDeadline {
+    /// Max time between the delivery of a message and its ack.
+    deadline: Duration,
+
+    /// Action taken when the deadline is exceeded.
+    action: AckDeadlineAction,
+}
+
+/// Schedule of the idle consumer cleanup run by a [`Consumer`].
+#[derive(Debug, Clone)]
+struct IdleConsumerCleanup {
+    /// The min idle time of the consumers to remove.
+    min_idle_time: Duration,
+
+    /// Interval between cleanups.
+    interval: Duration,
+
+    /// Instant of the latest cleanup.
+    last_run: Instant,
+}
+
+/// Statistics of the messages consumed and acked by a [`Consumer`] since it was created, see [`Consumer::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsumerStats {
+    /// Total new messages consumed.
+    new_messages: u64,
+
+    /// Total pending messages consumed.
+    pending_messages: u64,
+
+    /// Total messages claimed from other consumers.
+    claimed_messages: u64,
+
+    /// Total messages acked successfully.
+    acks: u64,
+
+    /// Total failed acks.
+    failed_acks: u64,
+
+    /// Total attempts to claim messages from other consumers.
+    claim_cycles: u64,
+
+    /// Description of the latest error while consuming or acking messages.
+    last_error: Option<String>,
+}
+
+impl ConsumerStats {
+    /// Get the total new messages consumed.
+    pub fn get_new_messages(&self) -> u64 {
+        self.new_messages
+    }
+
+    /// Get the total pending messages consumed.
+    pub fn get_pending_messages(&self) -> u64 {
+        self.pending_messages
+    }
+
+    /// Get the total messages claimed from other consumers.
+    pub fn get_claimed_messages(&self) -> u64 {
+        self.claimed_messages
+    }
+
+    /// Get the total messages consumed, whatever their kind.
+    pub fn get_consumed_messages(&self) -> u64 {
+        self.new_messages + self.pending_messages + self.claimed_messages
+    }
+
+    /// Get the total messages acked successfully.
+    pub fn get_acks(&self) -> u64 {
+        self.acks
+    }
+
+    /// Get the total failed acks.
+    pub fn get_failed_acks(&self) -> u64 {
+        self.failed_acks
+    }
+
+    /// Get the total attempts to claim messages from other consumers, including the ones where no messages were claimed.
+    pub fn get_claim_cycles(&self) -> u64 {
+        self.claim_cycles
+    }
+
+    /// Get the description of the latest error while consuming or acking messages, if any.
+    pub fn get_last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Add the messages of a consume reply to the counter of their kind.
+    fn record_consumed(&mut self, kind: &MessagesKind, total: usize) {
+        let total: u64 = total as u64;
+        match kind {
+            MessagesKind::New => self.new_messages += total,
+            MessagesKind::Pending => self.pending_messages += total,
+            MessagesKind::Claimed => self.claimed_messages += total,
+            MessagesKind::NotFound => {}
         }
+    }
+}
 
-        debug!(
-            "Processing claimed messages by: {:?}",
-            self.get_config().get_claim_messages_options()
-        );
+/// Cursors of the pending messages read and the messages claimed by a [`Consumer`].
+#[derive(Debug, Clone)]
+struct ConsumerCursors {
+    /// The latest pending message ID to start reading from.
+    latest_pending_message_id: String,
 
-        let (claimed_messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) =
-            self.get_client().to_owned().claim_pending_messages(
-                &self.get_config().get_stream_name(),
-                &self.get_config().get_group_name(),
-                &self.get_config().get_consumer_name(),
-                self.get_config()
-                    .get_claim_messages_options()
-                    .get_min_idle_time(),
-                self.get_config()
-                    .get_claim_messages_options()
-                    .get_next_id_to_claim(),
-                self.get_config().get_claim_messages_options().get_count(),
-            )?;
+    /// The latest ID to start claiming from.
+    next_id_to_claim: String,
+}
 
-        debug!("Updating next ID to claim to: {next_id_to_claim}",);
+/// Cursors at the beginning of the pending lists.
+impl Default for ConsumerCursors {
+    fn default() -> Self {
+        ConsumerCursors {
+            latest_pending_message_id: BEGINNING_OF_TIME_ID.to_string(),
+            next_id_to_claim: BEGINNING_OF_TIME_ID.to_string(),
+        }
+    }
+}
 
-        self.update_next_id_to_claim(&next_id_to_claim);
-        if claimed_messages.len().gt(&0) {
-            debug!("Total claimed messages found: {}", claimed_messages.len());
-            return Ok((claimed_messages, MessagesKind::Claimed).into());
+/// A snapshot of the cursors of a [`Consumer`], to persist them and continue the pending list scan and the claim scan where they were left after a restart. See [`Consumer::snapshot`] and [`Consumer::restore`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsumerSnapshot {
+    /// The latest pending message ID to start reading from.
+    latest_pending_message_id: String,
+
+    /// The latest ID to start claiming from.
+    next_id_to_claim: String,
+}
+
+impl ConsumerSnapshot {
+    /// Get **latest pending message id**.
+    pub fn get_latest_pending_message_id(&self) -> &str {
+        &self.latest_pending_message_id
+    }
+
+    /// Get **next id to claim**.
+    pub fn get_next_id_to_claim(&self) -> &str {
+        &self.next_id_to_claim
+    }
+
+    /// Build a new [`ConsumerSnapshot`] instance.
+    ///
+    /// # Arguments:
+    /// - **latest_pending_message_id**: The latest pending message ID to start reading from.
+    /// - **next_id_to_claim**: The latest ID to start claiming from.
+    ///
+    /// # Returns:
+    /// A new instance of [`ConsumerSnapshot`].
+    pub fn new(latest_pending_message_id: &str, next_id_to_claim: &str) -> Self {
+        ConsumerSnapshot {
+            latest_pending_message_id: latest_pending_message_id.to_owned(),
+            next_id_to_claim: next_id_to_claim.to_owned(),
         }
+    }
+}
 
-        debug!("No messages found");
+/// Convert the cursors of a [`Consumer`] into a [`ConsumerSnapshot`].
+impl From<ConsumerCursors> for ConsumerSnapshot {
+    fn from(cursors: ConsumerCursors) -> Self {
+        ConsumerSnapshot {
+            latest_pending_message_id: cursors.latest_pending_message_id,
+            next_id_to_claim: cursors.next_id_to_claim,
+        }
+    }
+}
 
-        Ok((Vec::new(), MessagesKind::NotFound).into())
+/// Processing position of a [`Consumer`] with auto-commit, see [`with_auto_commit`](Consumer::with_auto_commit).
+#[derive(Debug)]
+struct AutoCommit {
+    /// Interval between commits.
+    interval: Duration,
+
+    /// Delivered messages not committed yet, ordered by ID, with a flag set when they are processed.
+    delivered: BTreeMap<Id, bool>,
+
+    /// Instant of the latest commit.
+    last_commit: Instant,
+}
+
+impl AutoCommit {
+    /// Build a new [`AutoCommit`] instance without delivered messages, whose interval starts *now*.
+    fn new(interval: Duration, now: Instant) -> Self {
+        AutoCommit {
+            interval,
+            delivered: BTreeMap::new(),
+            last_commit: now,
+        }
     }
 
-    /// Verify if a specific message by *id* is still in consumer pending list.
+    /// Track the given messages as delivered. Messages with an invalid ID are not tracked.
+    fn track(&mut self, messages: &[StreamId]) {
+        messages
+            .iter()
+            .filter_map(|message| message.id.parse::<Id>().ok())
+            .for_each(|id| {
+                self.delivered.entry(id).or_insert(false);
+            });
+    }
+
+    /// Mark a delivered message as processed. It returns `false` if the message is not tracked.
+    fn mark_processed(&mut self, id: &Id) -> bool {
+        match self.delivered.get_mut(id) {
+            Some(processed) => {
+                *processed = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Verify if the interval since the latest commit has elapsed *now*.
+    fn is_due(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.last_commit)
+            .ge(&self.interval)
+    }
+
+    /// Take the IDs of the processed messages up to the highest contiguously processed ID, which can be committed, and restart the interval *now*.
+    fn take_committable(&mut self, now: Instant) -> Vec<Id> {
+        self.last_commit = now;
+
+        let committable: Vec<Id> = self
+            .delivered
+            .iter()
+            .take_while(|(_, processed)| **processed)
+            .map(|(id, _)| *id)
+            .collect();
+        committable.iter().for_each(|id| {
+            self.delivered.remove(id);
+        });
+
+        committable
+    }
+
+    /// Track the given IDs again as processed, after a failed commit.
+    fn restore(&mut self, ids: &[Id]) {
+        self.delivered.extend(ids.iter().map(|id| (*id, true)));
+    }
+}
+
+/// Messages taken from an [`AutoCommit`] by a commit in progress. If the commit is cancelled before it finishes, they are restored when this is dropped, so they are committed again on the next commit.
+struct PendingCommit<'a> {
+    /// Processing position the messages were taken from.
+    auto_commit: &'a Mutex<AutoCommit>,
+
+    /// IDs of the messages being committed.
+    ids: &'a [Id],
+
+    /// Flag set when the commit finished, successfully or not.
+    finished: bool,
+}
+
+impl<'a> PendingCommit<'a> {
+    /// Build a new [`PendingCommit`] instance for the given *ids*.
+    fn new(auto_commit: &'a Mutex<AutoCommit>, ids: &'a [Id]) -> Self {
+        PendingCommit {
+            auto_commit,
+            ids,
+            finished: false,
+        }
+    }
+
+    /// Mark the commit as finished, so the messages are not restored.
+    fn finish(&mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for PendingCommit<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            warn!(
+                "Commit of {} messages was cancelled, they will be committed again",
+                self.ids.len()
+            );
+            self.auto_commit
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .restore(self.ids);
+        }
+    }
+}
+
+/// State changed by a read cycle, applied to the consumer that started it when the read finishes, along with the result of the read.
+///
+/// Only the state that is not shared between clones is returned. The pending and claim cursors, the server capabilities and the statistics are shared, so the read updates them directly.
+#[derive(Debug)]
+struct ReadCycleOutcome {
+    /// Flag set if the read initialized a consumer built with [`new_lazy`](Consumer::new_lazy).
+    initialized: bool,
+
+    /// Result of the read.
+    result: RedsumerResult<ConsumeMessagesReply>,
+}
+
+/// Outcome of a read cycle, or the error of the blocking thread where it runs.
+type ReadOutcome = RedsumerResult<ReadCycleOutcome>;
+
+/// A read cycle of [`consume`](Consumer::consume) running in a blocking thread.
+type ReadFuture = Pin<Box<dyn Future<Output = ReadOutcome> + Send>>;
+
+/// The read cycle started by a [`consume`](Consumer::consume) call that has not returned its outcome yet, because the call is still running or its future was dropped.
+///
+/// It is kept in the consumer, so a cancelled call is resumed by the next one instead of losing the messages read in the meantime. It is not shared between clones: a clone starts without a read cycle.
+struct ReadCycle {
+    /// The running read cycle, if any. The mutex only makes it `Sync`, since it is accessed through `&mut`.
+    future: Mutex<Option<ReadFuture>>,
+}
+
+impl ReadCycle {
+    /// Get the running read cycle.
+    fn get_mut(&mut self) -> &mut Option<ReadFuture> {
+        self.future.get_mut().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Verify if a read cycle is running.
+    fn is_running(&self) -> bool {
+        self.future
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+    }
+}
+
+impl Default for ReadCycle {
+    fn default() -> Self {
+        ReadCycle {
+            future: Mutex::new(None),
+        }
+    }
+}
+
+/// Clones start without a read cycle.
+impl Clone for ReadCycle {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Debug for ReadCycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ReadCycle")
+            .field("running", &self.is_running())
+            .finish()
+    }
+}
+
+/// A consumer implementation of Redis Streams. The consumer is responsible for consuming messages from a stream. It can read new messages,  pending messages or claim messages from other consumers according to their min idle time.
+///
+/// # Concurrency
+///
+/// [`Consumer`] is cheap to clone, and clones can be moved to other tasks or threads. Clones share the pause flag, the pending and claim cursors, the messages in flight and the statistics, so they behave as the same consumer of the group: a cursor moved by a clone is seen by the others, and [`reset_cursors`](Consumer::reset_cursors) or [`pause`](Consumer::pause) on a clone affects all of them. Since clones share the consumer name, concurrent consumes may get the same pending messages, which must be acked once. To process messages in parallel without overlap, build consumers with different names instead.
+#[derive(Debug, Clone)]
+pub struct Consumer<C = Client> {
+    /// Connection to interact with Redis server. It can be shared with other producers and consumers.
+    client: Arc<C>,
+
+    /// Consumer configuration parameters.
+    config: ConsumerConfig,
+
+    /// Flag to pause the consumption of messages. It is shared between clones of the consumer.
+    paused: Arc<AtomicBool>,
+
+    /// Cursors of the pending messages and the claimed messages. They are shared between clones of the consumer.
+    cursors: Arc<Mutex<ConsumerCursors>>,
+
+    /// Recorder of the consumer metrics.
+    metrics: Arc<dyn MetricsRecorder>,
+
+    /// Interceptors called after consuming messages and before acking them.
+    interceptors: Vec<Arc<dyn Interceptor>>,
+
+    /// Position where the consumers group is recreated if it is deleted at runtime. If it is `None`, the group is not recreated.
+    group_recovery: Option<StartPosition>,
+
+    /// Position where the consumers group is created on the first consume, for consumers built with [`new_lazy`](Consumer::new_lazy). It is `None` once the consumer is initialized.
+    lazy_start_position: Option<StartPosition>,
+
+    /// Time to live of the consumer heartbeat sent on every consume. If it is `None`, heartbeats are not sent.
+    heartbeat_ttl: Option<Duration>,
+
+    /// Schedule of the idle consumer cleanup. If it is `None`, the cleanup is not run.
+    idle_consumer_cleanup: Option<IdleConsumerCleanup>,
+
+    /// Wait applied by [`run`](Consumer::run) and [`into_stream`](Consumer::into_stream) when no messages are found.
+    idle_strategy: IdleStrategy,
+
+    /// IDs of the messages returned by [`consume`](Consumer::consume) and not acked yet, with the instant they were delivered. It is shared between clones of the consumer.
+    in_flight: Arc<Mutex<HashMap<String, Instant>>>,
+
+    /// Max number of unacked messages in flight. If it is `None`, the number is not limited.
+    max_in_flight: Option<usize>,
+
+    /// Ack deadline of the messages in flight. If it is `None`, deadlines are not tracked.
+    ack_deadline: Option<AckDeadline>,
+
+    /// Statistics of the consumer. They are shared between clones of the consumer.
+    stats: Arc<Mutex<ConsumerStats>>,
+
+    /// Rolling window rate calculator of the consumed messages. If it is `None`, the rate is not measured.
+    rate_meter: Option<Arc<RateMeter>>,
+
+    /// Latency thresholds to report slow consume and ack operations.
+    slow_thresholds: Arc<SlowOperationThresholds>,
+
+    /// Processing position of the messages acked in batches. If it is `None`, auto-commit is disabled. It is shared between clones of the consumer.
+    auto_commit: Option<Arc<Mutex<AutoCommit>>>,
+
+    /// Store where the checkpoints of the consumer are saved. If it is `None`, checkpoints can not be saved.
+    checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+
+    /// Capabilities of the Redis server, detected when the consumer is created or initialized. It is shared between clones of the consumer.
+    capabilities: Arc<OnceLock<ServerCapabilities>>,
+
+    /// Read cycle of a [`consume`](Consumer::consume) call that has not returned yet. It is not shared between clones of the consumer.
+    read_cycle: ReadCycle,
+
+    /// Number of consumes without new messages, to claim on every N of them. It is shared between clones of the consumer.
+    claim_steps: Arc<AtomicU64>,
+
+    /// Source of time of the idle backoff, the ack deadlines, the auto-commit interval and the idle consumer cleanup.
+    clock: Arc<dyn Clock>,
+}
+
+impl Consumer {
+    /// Build a new [`Consumer`] instance.
     ///
-    ///  If the message is not still in consumer pending list, it is recommended to verify if another consumer has claimed the message before trying to process it again.
+    ///  Before creating a new consumer, the following validations are performed:
+    ///
+    /// - If connection string is invalid, a [`RedsumerError`] is returned.
+    /// - If connection to Redis server can not be established, a [`RedsumerError`] is returned.
+    /// - If the stream does not exist, a [`RedsumerError`] is returned: The stream must exist before creating a new consumer, unless *create stream* is enabled in the [`ConsumerConfig`]. In that case, the stream is created along with the consumers group. If *max wait seconds for stream* is greater than zero, the stream is polled with exponential backoff until it exists or the time elapses.
+    ///  - If the consumers group does not exist, it is created based on the *stream_name*, *group_name* and the given *start_position*. If an error occurs during the creation process, a [`RedsumerError`] is returned. If the group already exists, *start_position* is ignored.
+    /// - The [`ServerCapabilities`] are detected from the server version. If [`ClaimMode::AutoClaim`] is configured and the server does not support `XAUTOCLAIM`, a [`RedsumerError`] is returned.
     ///
     /// # Arguments:
-    /// - **id**: Stream message id.
+    /// - **args**: Client arguments to build a new [`Client`] instance. If a namespace is set, the stream name of *config* is prefixed with it.
+    /// - **config**: Consumer configuration parameters.
+    /// - **start_position**: The [`StartPosition`] of the stream where a new consumers group starts consuming messages.
     ///
     ///  # Returns:
-    ///  - A [`RedsumerResult`] containing a [`IsStillMineReply`] if successful. If an error occurs, a [`RedsumerError`] is returned.
-    pub fn is_still_mine(&self, id: &Id) -> RedsumerResult<IsStillMineReply> {
-        self.get_client()
-            .to_owned()
-            .is_still_mine(
-                self.get_config().get_stream_name(),
-                self.get_config().get_group_name(),
-                self.get_config().get_consumer_name(),
-                id,
-            )
-            .map(IsStillMineReply::from)
+    /// - A [`RedsumerResult`] containing a [`Consumer`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(
+        args: ClientArgs,
+        mut config: ConsumerConfig,
+        start_position: StartPosition,
+    ) -> RedsumerResult<Self> {
+        debug!(
+            "Creating a new consumer instance by: {:?} and {:?}",
+            args, config
+        );
+
+        config.stream_name = args.get_key(config.get_stream_name());
+        Self::from_client(Arc::new(args.build()?), config, start_position)
     }
 
-    /// Ack a message by *id*.
-    ///
-    ///  If the message is acked, it is removed from the consumer pending list. Otherwise, it is recommended to verify if another consumer has claimed the message before trying to process it again.
-    ///  
-    /// # Arguments:
-    /// - **id**: Stream message id.
-    ///
-    /// # Returns:
-    ///  - A [`RedsumerResult`] containing a [`AckMessageReply`] if successful. If an error occurs, a [`RedsumerError`] is returned.
-    pub async fn ack(&self, id: &Id) -> RedsumerResult<AckMessageReply> {
-        self.get_client()
-            .to_owned()
-            .ack(
-                self.get_config().get_stream_name(),
-                self.get_config().get_group_name(),
-                &[id],
-            )
-            .map(AckMessageReply::from)
+    /// Build a new [`Consumer`] instance without connecting to the Redis server.
+    ///
+    /// Only the client arguments are validated, so the consumer can be built while Redis is not reachable yet, for example during application wiring. The connection, the stream verification and the consumers group creation described in [`new`](Consumer::new) are deferred to the first call to [`consume`](Consumer::consume). If they fail, the error is returned by [`consume`](Consumer::consume) and they are retried in the next call.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance.
+    /// - **config**: Consumer configuration parameters.
+    /// - **start_position**: The [`StartPosition`] of the stream where a new consumers group starts consuming messages.
+    ///
+    ///  # Returns:
+    /// - A [`RedsumerResult`] containing a [`Consumer`] instance. If the connection string is invalid, a [`RedsumerError`] is returned.
+    pub fn new_lazy(
+        args: ClientArgs,
+        mut config: ConsumerConfig,
+        start_position: StartPosition,
+    ) -> RedsumerResult<Self> {
+        debug!(
+            "Creating a new lazy consumer instance by: {:?} and {:?}",
+            args, config
+        );
+
+        config.stream_name = args.get_key(config.get_stream_name());
+
+        Ok(Self {
+            client: Arc::new(args.build()?),
+            config,
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: Some(start_position),
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: ReadCycle::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        })
+    }
+}
+
+impl<C> Consumer<C>
+where
+    C: StreamConnection,
+{
+    /// Get the connection.
+    pub(crate) fn get_client(&self) -> &C {
+        &self.client
+    }
+
+    /// Get the shared connection, to build other producers and consumers connected to the same Redis server.
+    pub fn get_shared_client(&self) -> Arc<C> {
+        self.client.to_owned()
+    }
+
+    /// Get *config*.
+    pub fn get_config(&self) -> &ConsumerConfig {
+        &self.config
+    }
+
+    /// Get the [`ServerCapabilities`] detected when the consumer was created. For consumers built with [`new_lazy`](Consumer::new_lazy), they are detected on the first consume, and every feature is assumed to be supported before.
+    pub fn get_capabilities(&self) -> ServerCapabilities {
+        self.capabilities.get().copied().unwrap_or_default()
+    }
+
+    /// Get [`MetricsRecorder`].
+    fn get_metrics(&self) -> &dyn MetricsRecorder {
+        self.metrics.as_ref()
+    }
+
+    /// Set the [`MetricsRecorder`] called at key points of the consumer operations. By default, metrics are discarded.
+    ///
+    /// # Arguments:
+    /// - **recorder**: The metrics recorder to use.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.metrics = recorder;
+        self
+    }
+
+    /// Register an [`Interceptor`] called after consuming messages and before acking them. Interceptors are called in the same order they were registered.
+    ///
+    /// # Arguments:
+    /// - **interceptor**: The interceptor to register.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given interceptor.
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Enable the recovery of the consumers group when it is deleted at runtime.
+    ///
+    /// If the stream or the consumers group are deleted while the consumer is running, Redis replies with a `NOGROUP` error. With recovery enabled, [`consume`](Consumer::consume) detects it, recreates the stream and the consumers group at the given position and retries the read once. By default, recovery is disabled and the error is returned to the caller, as a [`RedsumerError::StreamNotFound`] when the stream itself does not exist anymore, like an expired stream.
+    ///
+    /// # Arguments:
+    /// - **start_position**: The [`StartPosition`] where the consumers group is recreated.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with group recovery enabled.
+    pub fn with_group_recovery(mut self, start_position: StartPosition) -> Self {
+        self.group_recovery = Some(start_position);
+        self
+    }
+
+    /// Enable the consumer heartbeat.
+    ///
+    /// On every call to [`consume`](Consumer::consume), a key with the given time to live is set for this consumer, so operators can distinguish idle consumers from dead ones with [`Admin::get_dead_consumers`](crate::admin::Admin::get_dead_consumers). The time to live must be greater than the block time plus the time to process a batch of messages.
+    ///
+    /// # Arguments:
+    /// - **ttl**: The time to live of the heartbeat.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with heartbeats enabled.
+    pub fn with_heartbeat(mut self, ttl: Duration) -> Self {
+        self.heartbeat_ttl = Some(ttl);
+        self
+    }
+
+    /// Enable the periodic cleanup of idle consumers in the consumers group.
+    ///
+    /// Every *interval*, [`consume`](Consumer::consume) removes the consumers of the group that are idle beyond *min_idle_time* and have no pending messages, as [`Admin::remove_idle_consumers`](crate::admin::Admin::remove_idle_consumers) does. The cleanup runs after reading messages, so this consumer is never idle at that point. Errors are logged and they do not affect the consume result.
+    ///
+    /// # Arguments:
+    /// - **min_idle_time**: The min idle time of the consumers to remove.
+    /// - **interval**: The interval between cleanups.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the idle consumer cleanup enabled.
+    pub fn with_idle_consumer_cleanup(
+        mut self,
+        min_idle_time: Duration,
+        interval: Duration,
+    ) -> Self {
+        self.idle_consumer_cleanup = Some(IdleConsumerCleanup {
+            min_idle_time,
+            interval,
+            last_run: self.clock.now(),
+        });
+        self
+    }
+
+    /// Set the [`Clock`] of the idle backoff, the ack deadlines of the messages in flight, the auto-commit interval and the idle consumer cleanup schedule. By default, it is the [`SystemClock`].
+    ///
+    /// A [`ManualClock`](crate::clock::ManualClock) makes these deterministic in tests: idle waits return immediately, and deadlines expire when the clock is advanced. The auto-commit interval and the idle consumer cleanup schedule restart at the current instant of the new clock. Times tracked by the Redis server, like the min idle time to claim messages or the expiration of heartbeats, are not affected.
+    ///
+    /// # Arguments:
+    /// - **clock**: The source of time.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        let now: Instant = clock.now();
+        if let Some(cleanup) = self.idle_consumer_cleanup.as_mut() {
+            cleanup.last_run = now;
+        }
+        if let Some(auto_commit) = &self.auto_commit {
+            auto_commit
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .last_commit = now;
+        }
+
+        self.clock = clock;
+        self
+    }
+
+    /// Set the [`IdleStrategy`] applied by [`run`](Consumer::run) and [`into_stream`](Consumer::into_stream) when consecutive consumes do not find messages, to avoid a busy loop against Redis while the stream is quiet. By default, it is an exponential backoff from `100` milliseconds up to `5` seconds. It does not affect [`consume`](Consumer::consume).
+    ///
+    /// # Arguments:
+    /// - **strategy**: The idle strategy to apply.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given idle strategy.
+    pub fn with_idle_strategy(mut self, strategy: IdleStrategy) -> Self {
+        self.idle_strategy = strategy;
+        self
+    }
+
+    /// Limit the number of messages handed to the application and not acked yet.
+    ///
+    /// The IDs of the messages returned by [`consume`](Consumer::consume) are tracked until they are acked with [`ack`](Consumer::ack). Once *max* messages are in flight, [`consume`](Consumer::consume) returns an empty list without reading from the stream, and it resumes fetching when acks arrive. A single batch is not split, so the number of messages in flight can exceed *max* by up to one batch. It protects slow handlers from an unbounded growth of the pending list. By default, the number is not limited.
+    ///
+    /// # Arguments:
+    /// - **max**: The max number of unacked messages in flight.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given limit.
+    pub fn with_max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = Some(max);
+        self
+    }
+
+    /// Measure the rate of the messages consumed in a rolling *window*, retrievable with [`current_rate`](Consumer::current_rate). By default, the rate is not measured.
+    ///
+    /// # Arguments:
+    /// - **window**: The length of the rolling window.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given rate window.
+    pub fn with_rate_window(mut self, window: Duration) -> Self {
+        self.rate_meter = Some(Arc::new(RateMeter::new(window)));
+        self
+    }
+
+    /// Report consume and ack operations slower than the given *thresholds* with a structured warning and [`MetricsRecorder::record_slow_operation`]. By default, slow operations are not reported.
+    ///
+    /// # Arguments:
+    /// - **thresholds**: The latency thresholds by operation.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given thresholds.
+    pub fn with_slow_thresholds(mut self, thresholds: SlowOperationThresholds) -> Self {
+        self.slow_thresholds = Arc::new(thresholds);
+        self
+    }
+
+    /// Ack messages in batches on a timer instead of one by one, trading redelivery granularity for far fewer `XACK` round trips.
+    ///
+    /// Every message returned by [`consume`](Consumer::consume) is tracked until it is marked with [`mark_processed`](Consumer::mark_processed). Every *interval*, on the next [`consume`](Consumer::consume) or on an explicit [`commit`](Consumer::commit), the messages up to the highest contiguously processed ID are acked with a single `XACK`. A message that is not processed holds back the commit of the following ones, so they are delivered again if the consumer crashes. Call [`commit`](Consumer::commit) before shutting down to ack the last processed messages. By default, auto-commit is disabled.
+    ///
+    /// # Arguments:
+    /// - **interval**: The interval between commits.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with auto-commit enabled.
+    pub fn with_auto_commit(mut self, interval: Duration) -> Self {
+        self.auto_commit = Some(Arc::new(Mutex::new(AutoCommit::new(
+            interval,
+            self.clock.now(),
+        ))));
+        self
+    }
+
+    /// Mark a consumed message as processed, so it is acked on the next commit, see [`with_auto_commit`](Consumer::with_auto_commit).
+    ///
+    /// # Arguments:
+    /// - **token**: The [`AckToken`] of the message, see [`ConsumeMessagesReply::get_ack_tokens`].
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `true` if the message is tracked by auto-commit. Otherwise, `false` is returned, like when auto-commit is disabled or the message was already committed. If the token was issued for another stream, consumers group or consumer, a [`RedsumerError`] is returned.
+    pub fn mark_processed(&self, token: &AckToken) -> RedsumerResult<bool> {
+        token.verify(self.get_config())?;
+
+        Ok(self.auto_commit.as_ref().is_some_and(|auto_commit| {
+            auto_commit
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .mark_processed(token.get_id())
+        }))
+    }
+
+    /// Get the number of consumed messages not committed yet by auto-commit, processed or not. It is `0` if auto-commit is disabled.
+    pub fn get_uncommitted_count(&self) -> usize {
+        self.auto_commit.as_ref().map_or(0, |auto_commit| {
+            auto_commit
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .delivered
+                .len()
+        })
+    }
+
+    /// Verify if auto-commit is enabled and its interval has elapsed.
+    fn is_commit_due(&self) -> bool {
+        self.auto_commit.as_ref().is_some_and(|auto_commit| {
+            auto_commit
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .is_due(self.clock.now())
+        })
+    }
+
+    /// Ack the processed messages up to the highest contiguously processed ID with a single `XACK`, see [`with_auto_commit`](Consumer::with_auto_commit). Only the messages marked with their [`AckToken`] in [`mark_processed`](Consumer::mark_processed) are acked. It is called on every [`consume`](Consumer::consume) once the interval has elapsed, and it can be called at any time, like before shutting down.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] with the number of messages acked. It is `0` if auto-commit is disabled or there are no messages to commit. If an error occurs, the messages are committed again on the next commit and a [`RedsumerError`] is returned. If the returned future is dropped before it completes, the messages are committed again on the next commit too.
+    pub async fn commit(&self) -> RedsumerResult<usize> {
+        let auto_commit: &Arc<Mutex<AutoCommit>> = match &self.auto_commit {
+            Some(auto_commit) => auto_commit,
+            None => return Ok(0),
+        };
+
+        let ids: Vec<Id> = auto_commit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take_committable(self.clock.now());
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let started_at: Instant = Instant::now();
+
+        let mut client: C = self.get_client().to_owned();
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        let group_name: String = self.get_config().get_group_name().to_owned();
+        let owned_ids: Vec<Id> = ids.to_owned();
+
+        // If this future is dropped before the ack finishes, the messages are committed again on the next commit:
+        let mut pending: PendingCommit = PendingCommit::new(auto_commit, &ids);
+        let result: RedsumerResult<usize> =
+            run_blocking(move || client.ack_many(&stream_name, &group_name, &owned_ids)).await;
+        pending.finish();
+
+        match &result {
+            Ok(acked) => {
+                debug!("{acked} messages were committed up to {:?}", ids.last());
+
+                let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+                ids.iter().for_each(|id| {
+                    in_flight.remove(&id.to_string());
+                });
+                drop(in_flight);
+
+                self.update_stats(|stats| stats.acks += *acked as u64);
+                self.record_ack_latency(started_at.elapsed())
+            }
+            Err(e) => {
+                auto_commit
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .restore(&ids);
+
+                self.update_stats(|stats| {
+                    stats.failed_acks += 1;
+                    stats.last_error = Some(e.to_string());
+                });
+                self.get_metrics().record_error(
+                    Operation::Ack,
+                    self.get_config().get_stream_name(),
+                    e,
+                )
+            }
+        }
+
+        result
+    }
+
+    /// Get the rate of the messages consumed in the rolling window set with [`with_rate_window`](Consumer::with_rate_window), in messages per second. It is `None` if the rate is not measured.
+    pub fn current_rate(&self) -> Option<f64> {
+        self.rate_meter.as_ref().map(|meter| meter.current_rate())
+    }
+
+    /// Get the number of messages returned by [`consume`](Consumer::consume) and not acked yet.
+    pub fn get_in_flight_count(&self) -> usize {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    /// Verify if the max number of unacked messages in flight is reached.
+    fn is_in_flight_limit_reached(&self) -> bool {
+        self.max_in_flight
+            .is_some_and(|max| self.get_in_flight_count().ge(&max))
+    }
+
+    /// Track the given messages as in flight, and as delivered if auto-commit is enabled.
+    fn track_in_flight(&self, messages: &[StreamId]) {
+        if let Some(auto_commit) = &self.auto_commit {
+            auto_commit
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .track(messages);
+        }
+
+        if self.max_in_flight.is_some() || self.ack_deadline.is_some() {
+            let delivered_at: Instant = self.clock.now();
+            self.in_flight
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .extend(
+                    messages
+                        .iter()
+                        .map(|message| (message.id.to_owned(), delivered_at)),
+                );
+        }
+    }
+
+    /// Stop tracking a message as in flight, without ack. It is still tracked as delivered if auto-commit is enabled.
+    pub(crate) fn untrack_in_flight(&self, id: &str) {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id);
+    }
+
+    /// Set the ack deadline of the messages returned by [`consume`](Consumer::consume).
+    ///
+    /// The instant every message is delivered to the application is recorded until it is acked with [`ack`](Consumer::ack). When a message is not acked within the *deadline*, the given [`AckDeadlineAction`] is taken by [`check_ack_deadlines`](Consumer::check_ack_deadlines), which is called on every [`consume`](Consumer::consume) and can also be called periodically from another task with a clone of the consumer while a batch is processed. The *deadline* must be lower than the min idle time used by other consumers to claim messages, so messages are renewed before they are claimed. By default, deadlines are not tracked.
+    ///
+    /// # Arguments:
+    /// - **deadline**: The max time between the delivery of a message and its ack.
+    /// - **action**: The [`AckDeadlineAction`] taken when the deadline is exceeded.
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given ack deadline.
+    pub fn with_ack_deadline(mut self, deadline: Duration, action: AckDeadlineAction) -> Self {
+        self.ack_deadline = Some(AckDeadline { deadline, action });
+        self
+    }
+
+    /// Get the IDs of the messages in flight that exceeded the ack deadline, with the time elapsed since they were delivered.
+    fn get_overdue_messages(&self, deadline: Duration) -> Vec<(String, Duration)> {
+        let now: Instant = self.clock.now();
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(id, delivered_at)| (id.to_owned(), now.saturating_duration_since(*delivered_at)))
+            .filter(|(_, elapsed)| elapsed.ge(&deadline))
+            .collect()
+    }
+
+    /// Take the [`AckDeadlineAction`] on the messages in flight that exceeded the ack deadline, see [`with_ack_deadline`](Consumer::with_ack_deadline).
+    ///
+    /// - With [`AckDeadlineAction::Renew`], the ownership of the messages is renewed and their deadline starts again. Messages that are not pending for this consumer anymore are not tracked anymore, and the *on ack deadline exceeded* callback is fired for them.
+    /// - With [`AckDeadlineAction::Warn`], a warning is logged and the *on ack deadline exceeded* callback is fired for every message, and their deadline starts again.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the IDs of the messages that exceeded the ack deadline. If the ack deadline is not set, an empty list is returned. If the messages can not be renewed, a [`RedsumerError`] is returned.
+    pub async fn check_ack_deadlines(&self) -> RedsumerResult<Vec<String>> {
+        let ack_deadline: AckDeadline = match self.ack_deadline {
+            Some(ack_deadline) => ack_deadline,
+            None => return Ok(Vec::new()),
+        };
+
+        let overdue: Vec<(String, Duration)> = self.get_overdue_messages(ack_deadline.deadline);
+        if overdue.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<String> = overdue.iter().map(|(id, _)| id.to_owned()).collect();
+        let renewed: Vec<String> = match ack_deadline.action {
+            AckDeadlineAction::Renew => {
+                let mut client: C = self.get_client().to_owned();
+                let stream_name: String = self.get_config().get_stream_name().to_owned();
+                let group_name: String = self.get_config().get_group_name().to_owned();
+                let consumer_name: String = self.get_config().get_consumer_name().to_owned();
+                let min_idle_time: usize = ack_deadline.deadline.as_millis() as usize;
+                let ids: Vec<String> = ids.to_owned();
+                run_blocking(move || {
+                    client.renew_messages(
+                        &stream_name,
+                        &group_name,
+                        &consumer_name,
+                        min_idle_time,
+                        &ids,
+                    )
+                })
+                .await?
+            }
+            AckDeadlineAction::Warn => ids.to_owned(),
+        };
+
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        for (id, elapsed) in overdue.iter() {
+            match renewed.contains(id) {
+                true if ack_deadline.action.eq(&AckDeadlineAction::Renew) => {
+                    debug!("Message {} renewed after {:?} without ack", id, elapsed);
+                }
+                true => {
+                    warn!("Message {} not acked after {:?}", id, elapsed);
+                    self.get_config()
+                        .get_callbacks()
+                        .ack_deadline_exceeded(id, *elapsed);
+                }
+                false => {
+                    warn!(
+                        "Message {} not acked after {:?} is not pending for this consumer anymore",
+                        id, elapsed
+                    );
+                    in_flight.remove(id);
+                    self.get_config()
+                        .get_callbacks()
+                        .ack_deadline_exceeded(id, *elapsed);
+                    continue;
+                }
+            }
+
+            if let Some(delivered_at) = in_flight.get_mut(id) {
+                *delivered_at = self.clock.now();
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Run the idle consumer cleanup if it is enabled and its interval elapsed.
+    fn cleanup_idle_consumers(&mut self) {
+        let min_idle_time: Duration = match self.idle_consumer_cleanup.as_mut() {
+            Some(cleanup)
+                if self
+                    .clock
+                    .now()
+                    .saturating_duration_since(cleanup.last_run)
+                    .ge(&cleanup.interval) =>
+            {
+                cleanup.last_run = self.clock.now();
+                cleanup.min_idle_time
+            }
+            _ => return,
+        };
+
+        match self.get_client().to_owned().remove_idle_consumers(
+            self.get_config().get_stream_name(),
+            self.get_config().get_group_name(),
+            min_idle_time,
+        ) {
+            Ok(removed) if !removed.is_empty() => {
+                info!("Idle consumers removed: {:?}", removed)
+            }
+            Ok(_) => debug!("No idle consumers found"),
+            Err(e) => warn!("Error removing idle consumers: {:?}", e),
+        }
+    }
+
+    /// Send the consumer heartbeat if it is enabled.
+    fn send_heartbeat(&self) -> RedsumerResult<()> {
+        match self.heartbeat_ttl {
+            Some(ttl) => self.get_client().to_owned().send_heartbeat(
+                self.get_config().get_stream_name(),
+                self.get_config().get_group_name(),
+                self.get_config().get_consumer_name(),
+                ttl,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the ID after which the next pending messages of the consumer are read. It moves forward while the pending list of the consumer is read, and it goes back to `0-0` when the end of the list is reached.
+    pub fn get_latest_pending_message_id(&self) -> String {
+        self.get_cursors().latest_pending_message_id
+    }
+
+    /// Get the ID from which the next messages are claimed from other consumers. It moves forward while the pending list of the consumers group is scanned, and it goes back to `0-0` when the end of the list is reached.
+    pub fn get_next_id_to_claim(&self) -> String {
+        self.get_cursors().next_id_to_claim
+    }
+
+    /// Save and load the checkpoints of the consumer in the given *store*, so it can resume precisely after a restart, even when its cursors live outside the consumers group. See [`save_checkpoint`](Consumer::save_checkpoint) and [`load_checkpoint`](Consumer::load_checkpoint).
+    ///
+    /// # Arguments:
+    /// - **store**: The [`CheckpointStore`], like a [`RedisCheckpointStore`](crate::checkpoint::RedisCheckpointStore).
+    ///
+    /// # Returns:
+    /// The [`Consumer`] instance with the given checkpoint store.
+    pub fn with_checkpoint_store(mut self, store: Arc<dyn CheckpointStore>) -> Self {
+        self.checkpoint_store = Some(store);
+        self
+    }
+
+    /// Get the checkpoint store, or an error if it is not set.
+    fn get_checkpoint_store(&self) -> RedsumerResult<Arc<dyn CheckpointStore>> {
+        self.checkpoint_store.to_owned().ok_or_else(|| {
+            RedsumerError::from((
+                ErrorKind::ClientError,
+                "Checkpoint store not set",
+                self.get_config().get_consumer_name().to_owned(),
+            ))
+        })
+    }
+
+    /// Save a [`Checkpoint`] with the current cursors of the consumer and the ID of the last processed message in the checkpoint store, see [`with_checkpoint_store`](Consumer::with_checkpoint_store).
+    ///
+    /// # Arguments:
+    /// - **last_processed_id**: The ID of the last message processed by the application, if any.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] with the saved [`Checkpoint`]. If the checkpoint store is not set or an error occurs, a [`RedsumerError`] is returned.
+    pub async fn save_checkpoint(
+        &self,
+        last_processed_id: Option<Id>,
+    ) -> RedsumerResult<Checkpoint> {
+        let store: Arc<dyn CheckpointStore> = self.get_checkpoint_store()?;
+
+        let snapshot: ConsumerSnapshot = self.snapshot();
+        let checkpoint: Checkpoint = Checkpoint::new(
+            snapshot.get_latest_pending_message_id(),
+            snapshot.get_next_id_to_claim(),
+            last_processed_id,
+        );
+
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        let group_name: String = self.get_config().get_group_name().to_owned();
+        let consumer_name: String = self.get_config().get_consumer_name().to_owned();
+
+        run_blocking(move || {
+            store.save(&stream_name, &group_name, &consumer_name, &checkpoint)?;
+            Ok(checkpoint)
+        })
+        .await
+    }
+
+    /// Load the [`Checkpoint`] of the consumer from the checkpoint store and [`restore`](Consumer::restore) its cursors, so the pending list scan and the claim scan continue where they were left. See [`with_checkpoint_store`](Consumer::with_checkpoint_store).
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] with the loaded [`Checkpoint`], or `None` if it was never saved, in which case the cursors are not changed. If the checkpoint store is not set, a cursor of the checkpoint is not valid or an error occurs, a [`RedsumerError`] is returned.
+    pub async fn load_checkpoint(&self) -> RedsumerResult<Option<Checkpoint>> {
+        let store: Arc<dyn CheckpointStore> = self.get_checkpoint_store()?;
+
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        let group_name: String = self.get_config().get_group_name().to_owned();
+        let consumer_name: String = self.get_config().get_consumer_name().to_owned();
+
+        let checkpoint: Option<Checkpoint> =
+            run_blocking(move || store.load(&stream_name, &group_name, &consumer_name)).await?;
+
+        if let Some(checkpoint) = &checkpoint {
+            self.restore(&ConsumerSnapshot::new(
+                checkpoint.get_latest_pending_message_id(),
+                checkpoint.get_next_id_to_claim(),
+            ))?;
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Get a snapshot of the cursors.
+    fn get_cursors(&self) -> ConsumerCursors {
+        self.cursors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .to_owned()
+    }
+
+    /// Get a snapshot of the statistics of the consumer: the new, pending and claimed messages consumed, the acks, the failed acks, the claim cycles and the latest error. They are counted since the consumer was created and are shared between its clones.
+    ///
+    /// # Returns:
+    /// - A [`ConsumerStats`] instance.
+    pub fn stats(&self) -> ConsumerStats {
+        self.update_stats(|_| {})
+    }
+
+    /// Apply *update* to the statistics of the consumer and return a snapshot of them.
+    fn update_stats<F>(&self, update: F) -> ConsumerStats
+    where
+        F: FnOnce(&mut ConsumerStats),
+    {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        update(&mut stats);
+        stats.to_owned()
+    }
+
+    /// Take a [`ConsumerSnapshot`] of the cursors of the pending messages and the claimed messages, to persist them and [`restore`](Consumer::restore) them after a restart, so the consumer does not scan the pending list from `0-0` again.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`ConsumerSnapshot`] with the current cursors.
+    pub fn snapshot(&self) -> ConsumerSnapshot {
+        self.get_cursors().into()
+    }
+
+    /// Restore the cursors of the pending messages and the claimed messages from a [`ConsumerSnapshot`], taken with [`snapshot`](Consumer::snapshot). The cursors are shared between clones of the consumer, so they are restored for all of them.
+    ///
+    /// # Arguments:
+    /// - **snapshot**: The [`ConsumerSnapshot`] to restore.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` if the cursors were restored. If a cursor is not a valid stream entry ID, the cursors are not changed and a [`RedsumerError`] is returned.
+    pub fn restore(&self, snapshot: &ConsumerSnapshot) -> RedsumerResult<()> {
+        snapshot.get_latest_pending_message_id().parse::<Id>()?;
+        snapshot.get_next_id_to_claim().parse::<Id>()?;
+
+        info!(
+            "Restoring cursors of consumer {}: latest pending message ID {} and next ID to claim {}",
+            self.get_config().get_consumer_name(),
+            snapshot.get_latest_pending_message_id(),
+            snapshot.get_next_id_to_claim()
+        );
+
+        self.update_latest_pending_message_id(snapshot.get_latest_pending_message_id());
+        self.update_next_id_to_claim(snapshot.get_next_id_to_claim());
+
+        Ok(())
+    }
+
+    /// Reset the cursors of the pending messages and the claimed messages to `0-0`, so the next consumes read the pending list from the beginning, without recreating the consumer.
+    ///
+    /// It is useful when old pending messages are not read again because the cursors moved past them. The cursors are shared between clones of the consumer, so they are reset for all of them.
+    pub fn reset_cursors(&self) {
+        info!(
+            "Resetting cursors of consumer {}: latest pending message ID {} and next ID to claim {}",
+            self.get_config().get_consumer_name(),
+            self.get_latest_pending_message_id(),
+            self.get_next_id_to_claim()
+        );
+
+        self.update_latest_pending_message_id(BEGINNING_OF_TIME_ID);
+        self.update_next_id_to_claim(BEGINNING_OF_TIME_ID);
+    }
+
+    /// Update the latest pending message ID to start reading from.
+    fn update_latest_pending_message_id(&self, id: &str) {
+        self.cursors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .latest_pending_message_id = id.to_owned();
+    }
+
+    /// Update the next ID to claim.
+    fn update_next_id_to_claim(&self, id: &str) {
+        self.cursors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .next_id_to_claim = id.to_owned();
+    }
+
+    /// Build a new [`Consumer`] instance from a shared [`Client`], like the one of a [`Producer`](crate::producer::Producer) connected to the same Redis server, so both do not maintain separate clients.
+    ///
+    /// Any other [`StreamConnection`] can be given instead of a [`Client`], like a pooled connection, a cluster client or a `MockRedisConnection` in tests.
+    ///
+    /// The same validations described in [`new`](Consumer::new) are performed.
+    ///
+    /// # Arguments:
+    /// - **client**: A shared Redis client or connection.
+    /// - **config**: Consumer configuration parameters.
+    /// - **start_position**: The [`StartPosition`] of the stream where a new consumers group starts consuming messages.
+    ///
+    ///  # Returns:
+    /// - A [`RedsumerResult`] containing a [`Consumer`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn from_client(
+        client: Arc<C>,
+        config: ConsumerConfig,
+        start_position: StartPosition,
+    ) -> RedsumerResult<Self> {
+        debug!(
+            "Creating a new consumer instance from a shared client by: {:?}",
+            config
+        );
+
+        let capabilities: ServerCapabilities =
+            Self::prepare(&mut client.as_ref().to_owned(), &config, &start_position)?;
+
+        info!("Consumer was created successfully and it is ready to be used");
+
+        Ok(Self {
+            client,
+            config,
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::new(OnceLock::from(capabilities)),
+            read_cycle: ReadCycle::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Verify the connection and the stream, create the consumers group at *start_position* if it does not exist, and detect the [`ServerCapabilities`].
+    fn prepare(
+        client: &mut C,
+        config: &ConsumerConfig,
+        start_position: &StartPosition,
+    ) -> RedsumerResult<ServerCapabilities> {
+        client.ping()?;
+
+        match config.get_create_stream() {
+            true => client.create_consumer_group_and_stream(
+                config.get_stream_name(),
+                config.get_group_name(),
+                start_position.to_id()?,
+            )?,
+            false => {
+                match config.get_max_wait_seconds_for_stream() {
+                    0 => client.verify_if_stream_exists(config.get_stream_name())?,
+                    seconds => client
+                        .wait_for_stream(config.get_stream_name(), Duration::from_secs(seconds))?,
+                };
+                client.create_consumer_group(
+                    config.get_stream_name(),
+                    config.get_group_name(),
+                    start_position.to_id()?,
+                )?
+            }
+        };
+
+        let capabilities: ServerCapabilities = client.get_server_capabilities();
+        if config.get_claim_mode().eq(&ClaimMode::AutoClaim) {
+            capabilities.require(ServerFeature::AutoClaim)?;
+        }
+
+        Ok(capabilities)
+    }
+
+    /// Initialize a consumer built with [`new_lazy`](Consumer::new_lazy). It does nothing if the consumer is already initialized.
+    fn initialize(&mut self) -> RedsumerResult<()> {
+        if let Some(start_position) = self.lazy_start_position.to_owned() {
+            let capabilities: ServerCapabilities = Self::prepare(
+                &mut self.get_client().to_owned(),
+                self.get_config(),
+                &start_position,
+            )?;
+            let _ = self.capabilities.set(capabilities);
+            self.lazy_start_position = None;
+
+            info!("Lazy consumer was initialized successfully");
+        }
+
+        Ok(())
+    }
+
+    /// Pause the consumption of messages.
+    ///
+    /// While the consumer is paused, [`consume`](Consumer::consume) returns an empty reply without reading from the stream. The consumers group registration is kept, so pending messages remain assigned to this consumer until they are claimed by another one. The pause state is shared between clones of the consumer, so a clone can be used as a handle to pause it from another task.
+    pub fn pause(&self) {
+        info!("Pausing consumer {}", self.get_config().get_consumer_name());
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Resume the consumption of messages after a [`pause`](Consumer::pause).
+    pub fn resume(&self) {
+        info!(
+            "Resuming consumer {}",
+            self.get_config().get_consumer_name()
+        );
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Verify if the consumption of messages is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Consume messages from stream according to the following steps:
+    ///
+    /// 1. Consumer tries to get new messages. If new messages are found, they are returned as a result.
+    /// 2. If new messages are not found, consumer tries to get pending messages. If pending messages are found, they are returned as a result.
+    /// 3. If pending messages are not found, consumer tries to claim messages from other consumers according to *min_idle_time_milliseconds*. If claimed messages are found, they are returned as a result.
+    /// 4. If new, pending or claimed messages are not found, an empty list is returned as a result.
+    ///
+    /// If the consumer is paused, or the max number of unacked messages in flight is reached (see [`with_max_in_flight`](Consumer::with_max_in_flight)), an empty list is returned without reading from the stream.
+    ///
+    /// # Cancellation safety:
+    ///
+    /// This method is cancel safe, so it can be used as a branch of `tokio::select!` or wrapped in a timeout. The read runs in a blocking thread and its future is kept in the consumer, so if the returned future is dropped before it completes:
+    ///
+    /// - The read is not interrupted, and the cursors it moves are kept.
+    /// - The next call on the same consumer resumes the read instead of starting a new one, and returns its messages. Clones start without it, since they are other instances.
+    /// - A commit in progress is cancelled and its messages are committed again on the next commit.
+    /// - If the consumer is dropped instead, the messages read by the cancelled call stay in its pending list, so they are returned as pending messages later.
+    ///
+    ///  # Arguments:
+    ///  *No arguments*
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] containing a list of [`ConsumeMessagesReply`] if new, pending or claimed messages are found, otherwise an empty list is returned. If an error occurs, a [`RedsumerError`] is returned.
+    #[instrument(
+        name = "consume",
+        skip_all,
+        fields(
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.consumer.group.name = %self.get_config().get_group_name(),
+            messaging.consumer.name = %self.get_config().get_consumer_name(),
+            messaging.operation.type = "receive",
+            messaging.batch.message_count,
+        )
+    )]
+    pub async fn consume(&mut self) -> RedsumerResult<ConsumeMessagesReply> {
+        let started_at: Instant = Instant::now();
+
+        if self.read_cycle.is_running() {
+            debug!("Resuming the read cycle of a cancelled consume");
+        } else {
+            if self.is_paused() {
+                debug!("Consumer is paused, no messages will be consumed");
+                return Ok((Vec::new(), MessagesKind::NotFound).into());
+            }
+
+            if let Err(e) = self.check_ack_deadlines().await {
+                warn!("Error checking the ack deadlines: {:?}", e);
+            }
+
+            if self.is_commit_due() {
+                if let Err(e) = self.commit().await {
+                    warn!("Error committing the processed messages: {:?}", e);
+                }
+            }
+
+            if self.is_in_flight_limit_reached() {
+                debug!(
+                    "Max unacked messages in flight reached ({}), no messages will be consumed",
+                    self.get_in_flight_count()
+                );
+                return Ok((Vec::new(), MessagesKind::NotFound).into());
+            }
+
+            let read: ReadFuture = self.start_read_cycle();
+            *self.read_cycle.get_mut() = Some(read);
+        }
+
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        let group_name: String = self.get_config().get_group_name().to_owned();
+
+        // The read cycle is kept in the consumer until it finishes, so if this future is dropped, the next call resumes it:
+        let outcome: ReadOutcome = match self.read_cycle.get_mut() {
+            Some(read) => read.await,
+            None => return Ok((Vec::new(), MessagesKind::NotFound).into()),
+        };
+        self.read_cycle.get_mut().take();
+
+        let read: RedsumerResult<ConsumeMessagesReply> = match outcome {
+            Ok(outcome) => {
+                if outcome.initialized {
+                    self.lazy_start_position = None;
+                }
+                outcome.result
+            }
+            Err(e) => Err(e),
+        };
+
+        let result: RedsumerResult<ConsumeMessagesReply> = read.and_then(|mut reply| {
+            for interceptor in self.interceptors.iter() {
+                interceptor.after_consume(
+                    &stream_name,
+                    &group_name,
+                    &reply.kind,
+                    &mut reply.messages,
+                )?;
+            }
+            Ok(reply)
+        });
+
+        self.cleanup_idle_consumers();
+
+        match result {
+            Ok(mut reply) => {
+                reply.issue_ack_tokens(
+                    &stream_name,
+                    &group_name,
+                    self.get_config().get_consumer_name(),
+                );
+                self.track_in_flight(reply.get_messages());
+                self.update_stats(|stats| {
+                    stats.record_consumed(reply.get_kind(), reply.get_messages().len())
+                });
+                if let Some(meter) = &self.rate_meter {
+                    meter.record(reply.get_messages().len());
+                }
+                Span::current().record("messaging.batch.message_count", reply.get_messages().len());
+                self.slow_thresholds.check(
+                    Operation::Consume,
+                    &stream_name,
+                    started_at.elapsed(),
+                    self.get_metrics(),
+                );
+                self.get_metrics().record_consumed(
+                    &stream_name,
+                    &group_name,
+                    reply.get_kind(),
+                    reply.get_messages().len(),
+                );
+                if reply.were_claimed() {
+                    self.get_metrics().record_claimed(
+                        &stream_name,
+                        &group_name,
+                        reply.get_messages().len(),
+                    );
+                    self.get_config()
+                        .get_callbacks()
+                        .claim(reply.get_messages());
+                }
+                self.get_config()
+                    .get_callbacks()
+                    .message(reply.get_messages());
+                Ok(reply)
+            }
+            Err(e) => {
+                self.update_stats(|stats| stats.last_error = Some(e.to_string()));
+                self.get_metrics()
+                    .record_error(Operation::Consume, &stream_name, &e);
+                self.get_config().get_callbacks().error(&e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Start a read cycle in a blocking thread: initialize the consumer if needed, send its heartbeat and read messages.
+    ///
+    /// The read runs on a clone of the consumer, so only the state it changes is returned, and the consumer may be modified while the read of a cancelled call is running.
+    fn start_read_cycle(&self) -> ReadFuture {
+        let mut consumer: Consumer<C> = self.to_owned();
+        let command = move || {
+            let lazy: bool = consumer.lazy_start_position.is_some();
+            let result: RedsumerResult<ConsumeMessagesReply> = consumer
+                .initialize()
+                .and_then(|_| consumer.send_heartbeat())
+                .and_then(|_| consumer.read_messages_or_recover());
+            Ok(ReadCycleOutcome {
+                initialized: lazy && consumer.lazy_start_position.is_none(),
+                result,
+            })
+        };
+
+        // A blocking read waits in the blocking thread pool, so the async runtime keeps running other tasks:
+        Box::pin(run_blocking(command))
+    }
+
+    /// Consume messages like [`consume`](Consumer::consume), but give up if the whole consume cycle takes longer than *timeout*, so it can be composed with other timed work, like in `tokio::select!`.
+    ///
+    /// If the timeout is exceeded, an empty reply of kind [`MessagesKind::NotFound`] is returned, even if the stream has messages: the read of the cancelled cycle is not interrupted and keeps running in the background, holding a connection, until the server replies. Its messages are returned by the next consume on the same consumer, which waits for the read instead of starting a new one, see the cancellation safety of [`consume`](Consumer::consume). If the consumer is dropped instead, they stay in its pending list.
+    ///
+    /// # Arguments:
+    /// - **timeout**: The max time to wait for the consume cycle.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the [`ConsumeMessagesReply`] of the consume cycle, or an empty reply if the timeout was exceeded. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn consume_with_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> RedsumerResult<ConsumeMessagesReply> {
+        let consume = std::pin::pin!(self.consume());
+        let timer = std::pin::pin!(sleep(timeout));
+
+        match select(consume, timer).await {
+            Either::Left((result, _)) => result,
+            Either::Right((Ok(()), _)) => {
+                debug!("Consume cycle exceeded the timeout of {:?}", timeout);
+                Ok((Vec::new(), MessagesKind::NotFound).into())
+            }
+            Either::Right((Err(e), _)) => Err(e),
+        }
+    }
+
+    /// Record the *latency* of an ack operation, reporting it if it is slower than its threshold.
+    fn record_ack_latency(&self, latency: Duration) {
+        self.get_metrics().record_ack_latency(
+            self.get_config().get_stream_name(),
+            self.get_config().get_group_name(),
+            latency,
+        );
+        self.slow_thresholds.check(
+            Operation::Ack,
+            self.get_config().get_stream_name(),
+            latency,
+            self.get_metrics(),
+        );
+    }
+
+    /// Verify if the claim step is due in this consume, according to the *every* and *jitter* of the [`ClaimMessagesOptions`], counting the consume as one without new messages.
+    fn is_claim_due(&self) -> bool {
+        let step: u64 = self.claim_steps.fetch_add(1, Ordering::SeqCst);
+        self.get_config()
+            .get_claim_messages_options()
+            .is_due(self.get_config().get_consumer_name(), step)
+    }
+
+    /// Read messages from the stream. If the consumers group does not exist and recovery is enabled, the group is recreated and the read is retried once.
+    fn read_messages_or_recover(&mut self) -> RedsumerResult<ConsumeMessagesReply> {
+        match (self.read_messages(), self.group_recovery.to_owned()) {
+            (Err(e), Some(start_position)) if e.code() == Some("NOGROUP") => {
+                warn!(
+                    "Consumers group {} of stream {} does not exist, recreating it: {:?}",
+                    self.get_config().get_group_name(),
+                    self.get_config().get_stream_name(),
+                    e
+                );
+
+                self.get_client()
+                    .to_owned()
+                    .create_consumer_group_and_stream(
+                        self.get_config().get_stream_name(),
+                        self.get_config().get_group_name(),
+                        start_position.to_id()?,
+                    )?;
+
+                self.update_latest_pending_message_id(BEGINNING_OF_TIME_ID);
+                self.update_next_id_to_claim(BEGINNING_OF_TIME_ID);
+
+                self.read_messages()
+            }
+            (Err(e), None) if e.code() == Some("NOGROUP") => Err(self.report_missing_stream(e)),
+            (result, _) => result,
+        }
+    }
+
+    /// Classify a `NOGROUP` *error*: if the stream does not exist anymore, because it expired or it was deleted, a [`RedsumerError::StreamNotFound`] is returned. Otherwise, the error is returned as it is.
+    fn report_missing_stream(&self, error: RedsumerError) -> RedsumerError {
+        match self
+            .get_client()
+            .to_owned()
+            .verify_if_stream_exists(self.get_config().get_stream_name())
+        {
+            Err(e @ RedsumerError::StreamNotFound(_)) => {
+                warn!(
+                    "Stream {} does not exist, it expired or it was deleted",
+                    self.get_config().get_stream_name()
+                );
+                e
+            }
+            _ => error,
+        }
+    }
+
+    /// Read new, pending or claimed messages from the stream, in that order, updating the consumer cursors.
+    fn read_messages(&mut self) -> RedsumerResult<ConsumeMessagesReply> {
+        debug!(
+            "Consuming messages from stream {}",
+            self.get_config().get_stream_name()
+        );
+
+        debug!(
+            "Processing new messages by: {:?}",
+            self.get_config().get_read_new_messages_options()
+        );
+
+        let new_messages: Vec<StreamId> = self.get_client().to_owned().read_new_messages(
+            &self.get_config().get_stream_name(),
+            &self.get_config().get_group_name(),
+            &self.get_config().get_consumer_name(),
+            self.get_config()
+                .get_read_new_messages_options()
+                .get_count(),
+            self.get_config()
+                .get_read_new_messages_options()
+                .get_block()
+                .as_millis() as usize,
+        )?;
+        if new_messages.len().gt(&0) {
+            debug!("Total new messages found: {}", new_messages.len());
+            return Ok((new_messages, MessagesKind::New).into());
+        }
+
+        let claim_due: bool = self.is_claim_due();
+
+        if claim_due
+            && self.get_config().get_pipeline_pending_and_claim()
+            && self
+                .get_config()
+                .get_read_pending_messages_options()
+                .get_count()
+                .gt(&0)
+            && self
+                .get_config()
+                .get_claim_messages_options()
+                .get_count()
+                .gt(&0)
+            && !self.uses_compatibility_claim()
+        {
+            return self.read_pending_and_claimed_messages();
+        }
+
+        debug!(
+            "Processing pending messages by: {:?}",
+            self.get_config().get_read_pending_messages_options()
+        );
+
+        let (pending_messages, latest_pending_message_id): (Vec<StreamId>, LatestPendingMessageId) =
+            self.get_client().to_owned().read_pending_messages(
+                &self.get_config().get_stream_name(),
+                &self.get_config().get_group_name(),
+                &self.get_config().get_consumer_name(),
+                self.get_latest_pending_message_id(),
+                self.get_config()
+                    .get_read_pending_messages_options()
+                    .get_count(),
+            )?;
+
+        debug!("Updating latest pending message ID to: {latest_pending_message_id}",);
+
+        self.update_latest_pending_message_id(&latest_pending_message_id);
+        if pending_messages.len().gt(&0) {
+            debug!("Total pending messages found: {}", pending_messages.len());
+            return Ok((pending_messages, MessagesKind::Pending).into());
+        }
+
+        if !claim_due {
+            debug!("Claim step is not due in this consume, no messages found");
+            return Ok((Vec::new(), MessagesKind::NotFound).into());
+        }
+
+        debug!(
+            "Processing claimed messages by: {:?}",
+            self.get_config().get_claim_messages_options()
+        );
+
+        let _span = info_span!(
+            "claim",
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.consumer.group.name = %self.get_config().get_group_name(),
+            messaging.consumer.name = %self.get_config().get_consumer_name(),
+            messaging.operation.type = "receive",
+        )
+        .entered();
+
+        self.update_stats(|stats| stats.claim_cycles += 1);
+
+        let mut client: C = self.get_client().to_owned();
+        let (claimed_messages, next_id_to_claim): (Vec<StreamId>, NextIdToClaim) =
+            match self.uses_compatibility_claim() {
+                true => client.claim_pending_messages_with_xclaim(
+                    &self.get_config().get_stream_name(),
+                    &self.get_config().get_group_name(),
+                    &self.get_config().get_consumer_name(),
+                    self.get_config()
+                        .get_claim_messages_options()
+                        .get_min_idle_time(),
+                    self.get_next_id_to_claim(),
+                    self.get_config().get_claim_messages_options().get_count(),
+                )?,
+                false => client.claim_pending_messages(
+                    &self.get_config().get_stream_name(),
+                    &self.get_config().get_group_name(),
+                    &self.get_config().get_consumer_name(),
+                    self.get_config()
+                        .get_claim_messages_options()
+                        .get_min_idle_time(),
+                    self.get_next_id_to_claim(),
+                    self.get_config().get_claim_messages_options().get_count(),
+                )?,
+            };
+
+        debug!("Updating next ID to claim to: {next_id_to_claim}",);
+
+        self.update_next_id_to_claim(&next_id_to_claim);
+        if claimed_messages.len().gt(&0) {
+            debug!("Total claimed messages found: {}", claimed_messages.len());
+            return Ok((claimed_messages, MessagesKind::Claimed).into());
+        }
+
+        debug!("No messages found");
+
+        Ok((Vec::new(), MessagesKind::NotFound).into())
+    }
+
+    /// Verify if messages are claimed with `XPENDING` and `XCLAIM` instead of `XAUTOCLAIM`, according to the [`ClaimMode`] and the [`ServerCapabilities`].
+    fn uses_compatibility_claim(&self) -> bool {
+        match self.get_config().get_claim_mode() {
+            ClaimMode::AutoClaim => false,
+            ClaimMode::Compatibility => true,
+            ClaimMode::Auto => !self.get_capabilities().supports(ServerFeature::AutoClaim),
+        }
+    }
+
+    /// Read pending messages and claim messages in a single pipeline, updating both consumer cursors. Pending messages are returned first, and claimed messages are returned only if no pending messages are found. Otherwise, they stay in the consumer pending list and are returned as pending messages in later consumes.
+    fn read_pending_and_claimed_messages(&mut self) -> RedsumerResult<ConsumeMessagesReply> {
+        debug!(
+            "Processing pending and claimed messages in a pipeline by: {:?} and {:?}",
+            self.get_config().get_read_pending_messages_options(),
+            self.get_config().get_claim_messages_options()
+        );
+
+        self.update_stats(|stats| stats.claim_cycles += 1);
+
+        let (
+            (pending_messages, latest_pending_message_id),
+            (claimed_messages, next_id_to_claim),
+        ): PendingAndClaimedMessages = self
+            .get_client()
+            .to_owned()
+            .read_pending_and_claim_messages(
+                &self.get_config().get_stream_name(),
+                &self.get_config().get_group_name(),
+                &self.get_config().get_consumer_name(),
+                self.get_latest_pending_message_id(),
+                self.get_config()
+                    .get_read_pending_messages_options()
+                    .get_count(),
+                self.get_config()
+                    .get_claim_messages_options()
+                    .get_min_idle_time(),
+                self.get_next_id_to_claim(),
+                self.get_config().get_claim_messages_options().get_count(),
+            )?;
+
+        debug!("Updating latest pending message ID to: {latest_pending_message_id} and next ID to claim to: {next_id_to_claim}");
+
+        self.update_latest_pending_message_id(&latest_pending_message_id);
+        self.update_next_id_to_claim(&next_id_to_claim);
+
+        if pending_messages.len().gt(&0) {
+            debug!("Total pending messages found: {}", pending_messages.len());
+            return Ok((pending_messages, MessagesKind::Pending).into());
+        }
+
+        if claimed_messages.len().gt(&0) {
+            debug!("Total claimed messages found: {}", claimed_messages.len());
+            return Ok((claimed_messages, MessagesKind::Claimed).into());
+        }
+
+        debug!("No messages found");
+
+        Ok((Vec::new(), MessagesKind::NotFound).into())
+    }
+
+    /// Consume until messages are found, waiting between consumes without messages according to the [`IdleStrategy`].
+    async fn consume_until_found(
+        &mut self,
+        idle_consumes: &mut u32,
+    ) -> RedsumerResult<ConsumeMessagesReply> {
+        loop {
+            let reply: ConsumeMessagesReply = self.consume().await?;
+            if !reply.not_found() {
+                *idle_consumes = 0;
+                return Ok(reply);
+            }
+
+            *idle_consumes = idle_consumes.saturating_add(1);
+            let delay: Duration = self.idle_strategy.get_delay(*idle_consumes);
+            debug!(
+                "No messages found in {} consecutive consumes, waiting {:?}",
+                idle_consumes, delay
+            );
+            self.clock.sleep(delay).await?;
+        }
+    }
+
+    /// Consume repeatedly until messages are found or the *deadline* is reached, like a long poll, which is useful to wait for a reply in request/response patterns built on streams.
+    ///
+    /// Every consume blocks for the configured block time of the new messages, see [`ReadNewMessagesOptions`], and it is cut short if the deadline is reached meanwhile. Between consumes without messages, the consumer waits according to its [`IdleStrategy`], see [`with_idle_strategy`](Consumer::with_idle_strategy), without waiting beyond the deadline. A consume cut short by the deadline is resumed by the next consume, see the cancellation safety of [`consume`](Consumer::consume).
+    ///
+    /// # Arguments:
+    /// - **deadline**: The instant to stop consuming if no messages are found. If it has already passed, no consume is made.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the first [`ConsumeMessagesReply`] with messages, or an empty reply of kind [`MessagesKind::NotFound`] if the deadline was reached. If a consume fails, a [`RedsumerError`] is returned.
+    pub async fn consume_until(
+        &mut self,
+        deadline: Instant,
+    ) -> RedsumerResult<ConsumeMessagesReply> {
+        let mut idle_consumes: u32 = 0;
+        loop {
+            let remaining: Duration = deadline.saturating_duration_since(self.clock.now());
+            if remaining.is_zero() {
+                debug!("Deadline reached after {idle_consumes} consumes without messages");
+                return Ok((Vec::new(), MessagesKind::NotFound).into());
+            }
+
+            let reply: ConsumeMessagesReply = self.consume_with_timeout(remaining).await?;
+            if !reply.not_found() {
+                return Ok(reply);
+            }
+
+            idle_consumes = idle_consumes.saturating_add(1);
+            let delay: Duration = self
+                .idle_strategy
+                .get_delay(idle_consumes)
+                .min(deadline.saturating_duration_since(self.clock.now()));
+            debug!(
+                "No messages found in {} consecutive consumes, waiting {:?}",
+                idle_consumes, delay
+            );
+            self.clock.sleep(delay).await?;
+        }
+    }
+
+    /// Consume messages in a loop and pass every batch found to the *handler*.
+    ///
+    /// Consumes without messages are not passed to the *handler*. Instead, the consumer waits according to its [`IdleStrategy`] before consuming again, see [`with_idle_strategy`](Consumer::with_idle_strategy). Messages are not acked automatically.
+    ///
+    /// # Arguments:
+    /// - **handler**: The function called with every [`ConsumeMessagesReply`] with messages. It returns [`ControlFlow::Break`] to stop the loop, or [`ControlFlow::Continue`] to keep consuming.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the unit type when the *handler* stops the loop. If the consume operation or the *handler* fails, the loop stops and a [`RedsumerError`] is returned.
+    pub async fn run<F, Fut>(&mut self, mut handler: F) -> RedsumerResult<()>
+    where
+        F: FnMut(ConsumeMessagesReply) -> Fut,
+        Fut: Future<Output = RedsumerResult<ControlFlow<()>>>,
+    {
+        let mut idle_consumes: u32 = 0;
+        loop {
+            let reply: ConsumeMessagesReply = self.consume_until_found(&mut idle_consumes).await?;
+            if handler(reply).await?.is_break() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handle the messages of a *reply* with a [`MessageHandler`], acking the messages it processed.
+    ///
+    /// The fields of every message are decoded into *T* with [`decode`], and the ownership of the messages is verified in a single round trip, see [`is_still_mine_many`](Consumer::is_still_mine_many). Then, every message that still belongs to the consumer is passed to the *handler* in order, and it is acked if the *handler* returns [`HandlerOutcome::Ack`]. Messages that can not be decoded are logged and kept in the consumer pending list. Messages without an [`AckToken`] in the reply are not handled.
+    ///
+    /// # Arguments:
+    /// - **reply**: The [`ConsumeMessagesReply`] returned by [`consume`](Consumer::consume) or [`claim_from`](Consumer::claim_from).
+    /// - **handler**: The [`MessageHandler`] of the messages.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a [`HandleMessagesReply`] with the IDs of the messages by result. If the ownership of the messages can not be verified or a message can not be acked, the remaining messages are not handled and a [`RedsumerError`] is returned.
+    pub async fn handle_messages<T, H>(
+        &self,
+        reply: &ConsumeMessagesReply,
+        handler: &H,
+    ) -> RedsumerResult<HandleMessagesReply>
+    where
+        T: DeserializeOwned,
+        H: MessageHandler<T>,
+    {
+        let mut handled: HandleMessagesReply = HandleMessagesReply::default();
+
+        let mut decoded: Vec<(T, &AckToken)> = Vec::new();
+        for token in reply.get_ack_tokens() {
+            let message: Option<&StreamId> = reply
+                .iter()
+                .find(|message| message.id.eq(&token.get_id().to_string()));
+            match message.map(decode::<T>) {
+                Some(Ok(msg)) => decoded.push((msg, token)),
+                Some(Err(e)) => {
+                    warn!("Message {} can not be decoded: {:?}", token.get_id(), e);
+                    handled.record_undecodable(*token.get_id());
+                }
+                None => continue,
+            }
+        }
+
+        if decoded.is_empty() {
+            return Ok(handled);
+        }
+
+        let ids: Vec<Id> = decoded.iter().map(|(_, token)| *token.get_id()).collect();
+        let ownership: HashMap<Id, IsStillMineReply> = self.is_still_mine_many(&ids)?;
+
+        for (msg, token) in decoded {
+            let id: Id = *token.get_id();
+            let owned: &IsStillMineReply = match ownership.get(&id) {
+                Some(owned) if owned.belongs_to_me() => owned,
+                _ => {
+                    debug!("Message {} does not belong to the consumer anymore", id);
+                    handled.record_not_owned(id);
+                    continue;
+                }
+            };
+
+            let meta: MessageMeta = MessageMeta::new(
+                token.to_owned(),
+                reply.get_kind().to_owned(),
+                owned.get_last_delivered_milliseconds(),
+                owned.get_total_times_delivered(),
+            );
+
+            match handler.handle(msg, &meta).await {
+                HandlerOutcome::Ack => match self.ack(token).await?.was_acked() {
+                    true => handled.record_acked(id),
+                    false => {
+                        warn!("Message {} was handled, but it was not acked", id);
+                        handled.record_retried(id);
+                    }
+                },
+                HandlerOutcome::Retry => handled.record_retried(id),
+            }
+        }
+
+        Ok(handled)
+    }
+
+    /// Consume messages in a loop and handle them with a [`MessageHandler`], see [`handle_messages`](Consumer::handle_messages).
+    ///
+    /// Consumes without messages are not handled. Instead, the consumer waits according to its [`IdleStrategy`] before consuming again, see [`with_idle_strategy`](Consumer::with_idle_strategy).
+    ///
+    /// # Arguments:
+    /// - **handler**: The [`MessageHandler`] of the messages.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] that is only returned if an error occurs: if the consume operation fails, the ownership of the messages can not be verified or a message can not be acked, the loop stops and a [`RedsumerError`] is returned.
+    pub async fn run_handler<T, H>(&mut self, handler: &H) -> RedsumerResult<()>
+    where
+        T: DeserializeOwned,
+        H: MessageHandler<T>,
+    {
+        let mut idle_consumes: u32 = 0;
+        loop {
+            let reply: ConsumeMessagesReply = self.consume_until_found(&mut idle_consumes).await?;
+            let handled: HandleMessagesReply = self.handle_messages(&reply, handler).await?;
+            debug!(
+                "Total messages handled: {} acked, {} retried, {} not owned, {} undecodable",
+                handled.get_acked().len(),
+                handled.get_retried().len(),
+                handled.get_not_owned().len(),
+                handled.get_undecodable().len()
+            );
+        }
+    }
+
+    /// Convert the consumer into an endless [`Stream`] of batches of messages.
+    ///
+    /// Consumes without messages are not yielded. Instead, the consumer waits according to its [`IdleStrategy`] before consuming again, see [`with_idle_strategy`](Consumer::with_idle_strategy). Errors are yielded and the stream goes on, so the caller decides whether to stop. Messages are not acked automatically.
+    ///
+    /// # Returns:
+    /// A [`Stream`] of [`RedsumerResult`] with a [`ConsumeMessagesReply`] with messages, or a [`RedsumerError`].
+    pub fn into_stream(self) -> impl Stream<Item = RedsumerResult<ConsumeMessagesReply>> + Send {
+        unfold(
+            (self, 0u32),
+            |(mut consumer, mut idle_consumes)| async move {
+                let result: RedsumerResult<ConsumeMessagesReply> =
+                    consumer.consume_until_found(&mut idle_consumes).await;
+                Some((result, (consumer, idle_consumes)))
+            },
+        )
+    }
+
+    /// Verify if a specific message by *id* is still in consumer pending list.
+    ///
+    ///  If the message is not still in consumer pending list, it is recommended to verify if another consumer has claimed the message before trying to process it again.
+    ///
+    /// # Arguments:
+    /// - **id**: Stream message id.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] containing a [`IsStillMineReply`] if successful. If an error occurs, a [`RedsumerError`] is returned.
+    #[instrument(
+        name = "is_still_mine",
+        skip_all,
+        fields(
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.consumer.group.name = %self.get_config().get_group_name(),
+            messaging.consumer.name = %self.get_config().get_consumer_name(),
+            messaging.operation.type = "process",
+            messaging.message.id = %id,
+        )
+    )]
+    pub fn is_still_mine(&self, id: &Id) -> RedsumerResult<IsStillMineReply> {
+        self.get_client()
+            .to_owned()
+            .is_still_mine(
+                self.get_config().get_stream_name(),
+                self.get_config().get_group_name(),
+                self.get_config().get_consumer_name(),
+                id,
+            )
+            .map(IsStillMineReply::from)
+    }
+
+    /// Verify if a set of messages by *ids* are still in consumer pending list, checking all of them in a single round trip instead of one per message. It is useful to verify a large batch before acking it.
+    ///
+    /// # Arguments:
+    /// - **ids**: Stream message ids.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] containing a map from each id to its [`IsStillMineReply`] if successful. If an error occurs, a [`RedsumerError`] is returned.
+    #[instrument(
+        name = "is_still_mine_many",
+        skip_all,
+        fields(
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.consumer.group.name = %self.get_config().get_group_name(),
+            messaging.consumer.name = %self.get_config().get_consumer_name(),
+            messaging.operation.type = "process",
+            messaging.batch.message_count = ids.len(),
+        )
+    )]
+    pub fn is_still_mine_many(&self, ids: &[Id]) -> RedsumerResult<HashMap<Id, IsStillMineReply>> {
+        let replies = self.get_client().to_owned().are_still_mine(
+            self.get_config().get_stream_name(),
+            self.get_config().get_group_name(),
+            self.get_config().get_consumer_name(),
+            ids,
+        )?;
+
+        Ok(ids
+            .iter()
+            .copied()
+            .zip(replies.into_iter().map(IsStillMineReply::from))
+            .collect())
+    }
+
+    /// Ack a message by its *token*, issued when the message was consumed.
+    ///
+    ///  If the message is acked, it is removed from the consumer pending list. Otherwise, it is recommended to verify if another consumer has claimed the message before trying to process it again.
+    ///
+    ///  The token must be issued for the stream, consumers group and consumer of this consumer, so IDs consumed from another stream or group can not be acked by accident.
+    ///  
+    /// # Arguments:
+    /// - **token**: The [`AckToken`] of the message, see [`ConsumeMessagesReply::get_ack_tokens`].
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`AckMessageReply`] if successful. If the token was issued for another stream, consumers group or consumer, or an error occurs, a [`RedsumerError`] is returned.
+    #[instrument(
+        name = "ack",
+        skip_all,
+        fields(
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.consumer.group.name = %self.get_config().get_group_name(),
+            messaging.consumer.name = %self.get_config().get_consumer_name(),
+            messaging.operation.type = "settle",
+            messaging.message.id = %token.get_id(),
+        )
+    )]
+    pub async fn ack(&self, token: &AckToken) -> RedsumerResult<AckMessageReply> {
+        let started_at: Instant = Instant::now();
+        let id: &Id = token.get_id();
+
+        let intercepted: RedsumerResult<()> = token.verify(self.get_config()).and_then(|_| {
+            self.interceptors.iter().try_for_each(|interceptor| {
+                interceptor.before_ack(
+                    self.get_config().get_stream_name(),
+                    self.get_config().get_group_name(),
+                    id,
+                )
+            })
+        });
+
+        let mut client: C = self.get_client().to_owned();
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        let group_name: String = self.get_config().get_group_name().to_owned();
+        let id: Id = id.to_owned();
+
+        let result: RedsumerResult<AckMessageReply> = match intercepted {
+            Ok(_) => run_blocking(move || client.ack(&stream_name, &group_name, &[id]))
+                .await
+                .map(AckMessageReply::from),
+            Err(e) => Err(e),
+        };
+
+        match &result {
+            Ok(_) => {
+                self.in_flight
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(&id.to_string());
+                self.update_stats(|stats| stats.acks += 1);
+                self.record_ack_latency(started_at.elapsed())
+            }
+            Err(e) => {
+                self.update_stats(|stats| {
+                    stats.failed_acks += 1;
+                    stats.last_error = Some(e.to_string());
+                });
+                self.get_metrics().record_error(
+                    Operation::Ack,
+                    self.get_config().get_stream_name(),
+                    e,
+                )
+            }
+        }
+
+        result
+    }
+
+    /// Ack a set of messages by their *tokens* in a single round trip, reporting which specific messages were acked, so the caller can retry or investigate the others precisely.
+    ///
+    /// The messages are acked with `XACK`, preceded by one `XPENDING` command per message in the same transaction, since `XACK` only replies with the number of acked messages. Every token is verified like in [`ack`](Consumer::ack), and no message is acked if any of them was issued for another stream, consumers group or consumer.
+    ///
+    /// # Arguments:
+    /// - **tokens**: The [`AckToken`]s of the messages, see [`ConsumeMessagesReply::get_ack_tokens`].
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`AckManyReply`] if successful. If a token was issued for another stream, consumers group or consumer, or an error occurs, a [`RedsumerError`] is returned.
+    #[instrument(
+        name = "ack_many",
+        skip_all,
+        fields(
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.consumer.group.name = %self.get_config().get_group_name(),
+            messaging.consumer.name = %self.get_config().get_consumer_name(),
+            messaging.operation.type = "settle",
+            messaging.batch.message_count = tokens.len(),
+        )
+    )]
+    pub async fn ack_many_detailed(&self, tokens: &[AckToken]) -> RedsumerResult<AckManyReply> {
+        let started_at: Instant = Instant::now();
+        let ids: Vec<Id> = tokens.iter().map(|token| *token.get_id()).collect();
+
+        let intercepted: RedsumerResult<()> = tokens
+            .iter()
+            .try_for_each(|token| token.verify(self.get_config()))
+            .and_then(|_| {
+                ids.iter().try_for_each(|id| {
+                    self.interceptors.iter().try_for_each(|interceptor| {
+                        interceptor.before_ack(
+                            self.get_config().get_stream_name(),
+                            self.get_config().get_group_name(),
+                            id,
+                        )
+                    })
+                })
+            });
+
+        let mut client: C = self.get_client().to_owned();
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        let group_name: String = self.get_config().get_group_name().to_owned();
+        let owned_ids: Vec<Id> = ids.to_vec();
+
+        let result: RedsumerResult<AckManyReply> = match intercepted {
+            Ok(_) => run_blocking(move || {
+                client.ack_many_detailed(&stream_name, &group_name, &owned_ids)
+            })
+            .await
+            .map(|acked| AckManyReply::from((ids.as_slice(), acked))),
+            Err(e) => Err(e),
+        };
+
+        match &result {
+            Ok(reply) => {
+                let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+                reply.get_acked().iter().for_each(|id| {
+                    in_flight.remove(&id.to_string());
+                });
+                drop(in_flight);
+
+                self.update_stats(|stats| stats.acks += reply.get_acked().len() as u64);
+                self.record_ack_latency(started_at.elapsed())
+            }
+            Err(e) => {
+                self.update_stats(|stats| {
+                    stats.failed_acks += 1;
+                    stats.last_error = Some(e.to_string());
+                });
+                self.get_metrics().record_error(
+                    Operation::Ack,
+                    self.get_config().get_stream_name(),
+                    e,
+                )
+            }
+        }
+
+        result
+    }
+
+    /// Ack every message in a consumed *reply* in a single round trip, for simple pipelines that process the whole batch before acking it. See [`ack_many_detailed`](Consumer::ack_many_detailed).
+    ///
+    /// # Arguments:
+    /// - **reply**: The [`ConsumeMessagesReply`] whose messages were processed.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`AckManyReply`] with the messages that were acked and that were not. If no [`AckToken`] was issued for a message in the reply, a token was issued for another consumer, or an error occurs, a [`RedsumerError`] is returned.
+    pub async fn ack_all(&self, reply: &ConsumeMessagesReply) -> RedsumerResult<AckManyReply> {
+        let tokens: Vec<AckToken> = reply
+            .iter()
+            .map(|message| {
+                reply.get_ack_token(&message.id).cloned().ok_or_else(|| {
+                    RedsumerError::from((
+                        ErrorKind::ClientError,
+                        "Missing ack token",
+                        format!("No ack token was issued for message {}", message.id),
+                    ))
+                })
+            })
+            .collect::<RedsumerResult<Vec<AckToken>>>()?;
+
+        self.ack_many_detailed(&tokens).await
+    }
+
+    /// Guard every message of a *reply* with a [`MessageGuard`], which renews the ownership of the message while it is processed and must be resolved with [`ack`](MessageGuard::ack) or [`nack`](MessageGuard::nack).
+    ///
+    /// Every guard renews the ownership of its message in a dedicated thread every *renew_interval*, which must be lower than the min idle time used by other consumers to claim messages. If it is zero, the ownership is not renewed. Messages without an [`AckToken`] in the reply are not guarded.
+    ///
+    /// # Arguments:
+    /// - **reply**: The [`ConsumeMessagesReply`] returned by [`consume`](Consumer::consume) or [`claim_from`](Consumer::claim_from).
+    /// - **renew_interval**: The time between renewals of the ownership of every message.
+    ///
+    /// # Returns:
+    /// A list of [`MessageGuard`], one per message, in the order of the reply.
+    pub fn guard_messages(
+        &self,
+        reply: ConsumeMessagesReply,
+        renew_interval: Duration,
+    ) -> Vec<MessageGuard<C>> {
+        let tokens: Vec<AckToken> = reply.get_ack_tokens().to_vec();
+
+        reply
+            .into_iter()
+            .filter_map(|message| {
+                let token: AckToken = tokens
+                    .iter()
+                    .find(|token| token.get_id().to_string().eq(&message.id))?
+                    .to_owned();
+                Some(MessageGuard::new(
+                    self.to_owned(),
+                    message,
+                    token,
+                    renew_interval,
+                ))
+            })
+            .collect()
+    }
+
+    /// Take over pending messages of a specific consumer by *name*, like a known dead consumer, instead of scanning the whole pending list of the consumers group as [`consume`](Consumer::consume) does.
+    ///
+    /// The pending messages of the consumer matching the *filter* are found with `XPENDING` and claimed with `XCLAIM`, so they are delivered to this consumer and must be acked as any other message. It requires Redis 6.2 or later. Messages claimed by another consumer in the meantime are not claimed again with [`ClaimFilter::Idle`], since their idle time is reset.
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the consumer that owns the messages.
+    /// - **filter**: The [`ClaimFilter`] of the messages to claim.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] containing a [`ConsumeMessagesReply`] with the claimed messages, or without messages if none was found. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn claim_from(
+        &self,
+        name: &str,
+        filter: ClaimFilter,
+    ) -> RedsumerResult<ConsumeMessagesReply> {
+        info!(
+            "Claiming pending messages of consumer {} by: {:?}",
+            name, filter
+        );
+
+        let mut client: C = self.get_client().to_owned();
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        let group_name: String = self.get_config().get_group_name().to_owned();
+        let consumer_name: String = self.get_config().get_consumer_name().to_owned();
+        let owner: String = name.to_owned();
+
+        let result: RedsumerResult<Vec<StreamId>> = run_blocking(move || {
+            let (ids, min_idle_time): (Vec<String>, usize) = match filter {
+                ClaimFilter::Ids(ids) => {
+                    let mut owned: Vec<String> = Vec::new();
+                    for id in ids.iter() {
+                        owned.extend(client.get_consumer_pending_ids(
+                            &stream_name,
+                            &group_name,
+                            &owner,
+                            0,
+                            id,
+                            id,
+                            1,
+                        )?);
+                    }
+                    (owned, 0)
+                }
+                ClaimFilter::Idle {
+                    min_idle_time,
+                    count,
+                } => (
+                    client.get_consumer_pending_ids(
+                        &stream_name,
+                        &group_name,
+                        &owner,
+                        min_idle_time,
+                        "-",
+                        "+",
+                        count,
+                    )?,
+                    min_idle_time,
+                ),
+            };
+
+            client.claim_messages(
+                &stream_name,
+                &group_name,
+                &consumer_name,
+                min_idle_time,
+                &ids,
+            )
+        })
+        .await;
+
+        match result {
+            Ok(messages) if messages.is_empty() => {
+                debug!("No messages claimed from consumer {}", name);
+                Ok((messages, MessagesKind::NotFound).into())
+            }
+            Ok(messages) => {
+                info!(
+                    "Total messages claimed from consumer {}: {}",
+                    name,
+                    messages.len()
+                );
+                self.track_in_flight(&messages);
+                self.update_stats(|stats| {
+                    stats.record_consumed(&MessagesKind::Claimed, messages.len())
+                });
+                self.get_metrics().record_claimed(
+                    self.get_config().get_stream_name(),
+                    self.get_config().get_group_name(),
+                    messages.len(),
+                );
+                self.get_config().get_callbacks().claim(&messages);
+
+                let mut reply: ConsumeMessagesReply = (messages, MessagesKind::Claimed).into();
+                reply.issue_ack_tokens(
+                    self.get_config().get_stream_name(),
+                    self.get_config().get_group_name(),
+                    self.get_config().get_consumer_name(),
+                );
+                Ok(reply)
+            }
+            Err(e) => {
+                self.update_stats(|stats| stats.last_error = Some(e.to_string()));
+                self.get_config().get_callbacks().error(&e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Remove a consumer by *name* from the consumers group. It is useful to clean up stale consumers left behind by crashed instances.
+    ///
+    /// The pending messages of the removed consumer are deleted from the group pending list, so they will not be claimed by another consumer. It is recommended to claim or transfer them before removing the consumer.
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the consumer to remove.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`RemoveConsumerReply`] if successful. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn remove_consumer(&self, name: &str) -> RedsumerResult<RemoveConsumerReply> {
+        info!(
+            "Removing consumer {name} from group {}",
+            self.get_config().get_group_name()
+        );
+
+        self.get_client()
+            .to_owned()
+            .remove_consumer(
+                self.get_config().get_stream_name(),
+                self.get_config().get_group_name(),
+                name,
+            )
+            .map(RemoveConsumerReply::from)
+    }
+
+    /// Set the position of the consumers group in the stream, wrapping the `XGROUP SETID` command.
+    ///
+    /// The next new messages delivered to any consumer of the group are the ones after *id*, which allows controlled replays of the stream for the whole group. Pending messages are not affected.
+    ///
+    /// # Arguments:
+    /// - **id**: The new last delivered ID of the group. Use [`EntryId::default`] to replay the stream from the beginning.
+    /// - **entries_read**: An optional value for the group entries read counter, used by Redis >= 7.0 to compute the group lag.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] with no value if successful. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn set_group_position(
+        &self,
+        id: &Id,
+        entries_read: Option<usize>,
+    ) -> RedsumerResult<()> {
+        info!(
+            "Setting position of group {} to {id}",
+            self.get_config().get_group_name()
+        );
+
+        self.get_client().to_owned().set_consumer_group_id(
+            self.get_config().get_stream_name(),
+            self.get_config().get_group_name(),
+            id,
+            entries_read,
+        )
+    }
+
+    /// Get the lag of the consumers group, reading the `lag` and `entries-read` fields of `XINFO GROUPS`, which are available since Redis 7.0.
+    ///
+    /// If the server is older than Redis 7.0, a [`RedsumerError`] is returned without querying it.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`GroupLag`] if successful. If the group does not exist or an error occurs, a [`RedsumerError`] is returned.
+    pub async fn get_lag(&self) -> RedsumerResult<GroupLag> {
+        self.get_capabilities().require(ServerFeature::GroupLag)?;
+
+        self.get_client()
+            .to_owned()
+            .get_consumer_group_info(
+                self.get_config().get_stream_name(),
+                self.get_config().get_group_name(),
+            )
+            .map(GroupLag::from)
+    }
+
+    /// Get the number of pending messages of the whole consumers group, using the `XPENDING` summary.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the number of messages delivered to any consumer of the group and not acknowledged yet. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn get_pending_count(&self) -> RedsumerResult<usize> {
+        self.get_client()
+            .to_owned()
+            .get_pending_messages_summary(
+                self.get_config().get_stream_name(),
+                self.get_config().get_group_name(),
+            )
+            .map(|reply| reply.count())
+    }
+
+    /// Get the number of pending messages of this consumer, using the `XPENDING` summary.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the number of messages delivered to this consumer and not acknowledged yet. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn get_consumer_pending_count(&self) -> RedsumerResult<usize> {
+        let reply: StreamPendingReply = self.get_client().to_owned().get_pending_messages_summary(
+            self.get_config().get_stream_name(),
+            self.get_config().get_group_name(),
+        )?;
+
+        match reply {
+            StreamPendingReply::Empty => Ok(0),
+            StreamPendingReply::Data(data) => Ok(data
+                .consumers
+                .iter()
+                .find(|c| c.name.eq(self.get_config().get_consumer_name()))
+                .map(|c| c.pending)
+                .unwrap_or(0)),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Consumer {
+    /// Build a consumer without connecting to the Redis server, for the tests of other modules.
+    pub(crate) fn unconnected(config: ConsumerConfig) -> Consumer {
+        Consumer {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            config,
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: Default::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_read_new_messages_options {
+    use std::time::Duration;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_new_read_new_messages_options() {
+        // Define count and block:
+        let count: usize = 10;
+        let block: Duration = Duration::from_millis(3);
+
+        // Create new ReadNewMessagesOptions instance:
+        let options: ReadNewMessagesOptions = ReadNewMessagesOptions::new(count, block);
+
+        // Verify the result:
+        assert_eq!(options.get_count(), count);
+        assert_eq!(options.get_block(), block);
+    }
+}
+
+#[cfg(test)]
+mod test_read_pending_messages_options {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_new_read_pending_messages_options() {
+        // Define count:
+        let count: usize = 10;
+
+        // Create new ReadPendingMessagesOptions instance:
+        let options: ReadPendingMessagesOptions = ReadPendingMessagesOptions::new(count);
+
+        // Verify the result:
+        assert_eq!(options.get_count(), count);
+    }
+}
+
+#[cfg(test)]
+mod test_claim_messages_options {
+    use super::ClaimMessagesOptions;
+
+    #[test]
+    fn test_new_claim_messages_options() {
+        // Define count and min idle time:
+        let count: usize = 10;
+        let min_idle_time: usize = 1000;
+
+        // Create new ClaimMessagesOptions instance:
+        let options: ClaimMessagesOptions = ClaimMessagesOptions::new(count, min_idle_time);
+
+        // Verify the result:
+        assert_eq!(options.get_count(), count);
+        assert_eq!(options.get_min_idle_time(), min_idle_time);
+        assert_eq!(options.get_every(), 1);
+        assert!(!options.get_jitter());
+    }
+
+    #[test]
+    fn test_claim_messages_options_every() {
+        // Define options to claim every 3 consumes, with and without jitter:
+        let options: ClaimMessagesOptions = ClaimMessagesOptions::default().every(3);
+        let jittered: ClaimMessagesOptions = options.to_owned().jitter(true);
+
+        // Verify the result:
+        assert_eq!(
+            (0..6)
+                .map(|step| options.is_due("consumer", step))
+                .collect::<Vec<bool>>(),
+            vec![true, false, false, true, false, false]
+        );
+        for consumer in ["consumer-1", "consumer-2", "consumer-3"] {
+            let phase: u64 = jittered.get_phase(consumer);
+            assert!(phase < 3);
+            assert_eq!(jittered.get_phase(consumer), phase);
+            for step in 0..6 {
+                assert_eq!(jittered.is_due(consumer, step), (step + phase) % 3 == 0);
+            }
+        }
+        assert_eq!(
+            ClaimMessagesOptions::default()
+                .jitter(true)
+                .get_phase("consumer"),
+            0
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_config {
+    use std::time::Duration;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_new_consumer_config() {
+        // Define stream name, group name and consumer name:
+        let stream_name: &str = "stream";
+        let group_name: &str = "group";
+        let consumer_name: &str = "consumer";
+
+        // Define count, block, min idle time and initial stream id:
+        let count: usize = 10;
+        let block: Duration = Duration::from_millis(3);
+        let min_idle_time: usize = 1000;
+
+        // Create new ReadNewMessagesOptions instance:
+        let read_new_messages_options: ReadNewMessagesOptions =
+            ReadNewMessagesOptions::new(count, block);
+
+        // Create new ReadPendingMessagesOptions instance:
+        let read_pending_messages_options: ReadPendingMessagesOptions =
+            ReadPendingMessagesOptions::new(count);
+
+        // Create new ClaimMessagesOptions instance:
+        let claim_messages_options: ClaimMessagesOptions =
+            ClaimMessagesOptions::new(count, min_idle_time);
+
+        // Create new ConsumerConfig instance:
+        let config: ConsumerConfig = ConsumerConfig::new(
+            stream_name,
+            group_name,
+            consumer_name,
+            read_new_messages_options,
+            read_pending_messages_options,
+            claim_messages_options,
+        );
+
+        // Verify the result:
+        assert_eq!(config.get_stream_name(), stream_name);
+        assert_eq!(config.get_group_name(), group_name);
+        assert_eq!(config.get_consumer_name(), consumer_name);
+
+        assert_eq!(config.get_read_new_messages_options().get_count(), count);
+        assert_eq!(config.get_read_new_messages_options().get_block(), block);
+
+        assert_eq!(
+            config.get_read_pending_messages_options().get_count(),
+            count
+        );
+
+        assert_eq!(config.get_claim_messages_options().get_count(), count);
+        assert_eq!(
+            config.get_claim_messages_options().get_min_idle_time(),
+            min_idle_time
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_config_builder {
+    use std::time::Duration;
+
+    use super::{DEFAULT_BLOCK, DEFAULT_MESSAGES_COUNT, DEFAULT_MIN_IDLE_TIME};
+    use crate::prelude::*;
+
+    #[test]
+    fn test_consumer_config_builder_defaults() {
+        // Build a new ConsumerConfig instance with default options:
+        let config: RedsumerResult<ConsumerConfig> =
+            ConsumerConfig::builder("stream", "group", "consumer").build();
+
+        // Verify the result:
+        assert!(config.is_ok());
+        let config: ConsumerConfig = config.unwrap();
+
+        assert_eq!(config.get_stream_name(), "stream");
+        assert_eq!(config.get_group_name(), "group");
+        assert_eq!(config.get_consumer_name(), "consumer");
+
+        assert_eq!(
+            config.get_read_new_messages_options().get_count(),
+            DEFAULT_MESSAGES_COUNT
+        );
+        assert_eq!(
+            config.get_read_new_messages_options().get_block(),
+            DEFAULT_BLOCK
+        );
+        assert_eq!(
+            config.get_read_pending_messages_options().get_count(),
+            DEFAULT_MESSAGES_COUNT
+        );
+        assert_eq!(
+            config.get_claim_messages_options().get_count(),
+            DEFAULT_MESSAGES_COUNT
+        );
+        assert_eq!(
+            config.get_claim_messages_options().get_min_idle_time(),
+            DEFAULT_MIN_IDLE_TIME
+        );
+        assert!(!config.get_create_stream());
+        assert_eq!(config.get_claim_mode(), ClaimMode::Auto);
+    }
+
+    #[test]
+    fn test_consumer_config_builder_setters() {
+        // Build a new ConsumerConfig instance with chained setters:
+        let config: ConsumerConfig = ConsumerConfigBuilder::new("stream", "group", "consumer")
+            .new_messages_count(3)
+            .block(Duration::from_millis(1))
+            .pending_messages_count(2)
+            .claimed_messages_count(1)
+            .min_idle_time(500)
+            .create_stream(true)
+            .max_wait_seconds_for_stream(30)
+            .claim_mode(ClaimMode::Compatibility)
+            .build()
+            .unwrap();
+
+        // Verify the result:
+        assert_eq!(config.get_read_new_messages_options().get_count(), 3);
+        assert_eq!(
+            config.get_read_new_messages_options().get_block(),
+            Duration::from_millis(1)
+        );
+        assert_eq!(config.get_read_pending_messages_options().get_count(), 2);
+        assert_eq!(config.get_claim_messages_options().get_count(), 1);
+        assert_eq!(config.get_claim_messages_options().get_min_idle_time(), 500);
+        assert!(config.get_create_stream());
+        assert_eq!(config.get_max_wait_seconds_for_stream(), 30);
+        assert_eq!(config.get_claim_mode(), ClaimMode::Compatibility);
+    }
+
+    #[test]
+    fn test_consumer_config_builder_options() {
+        // Build a new ConsumerConfig instance replacing whole options:
+        let config: ConsumerConfig = ConsumerConfigBuilder::new("stream", "group", "consumer")
+            .read_new_messages_options(ReadNewMessagesOptions::new(7, Duration::from_millis(2)))
+            .read_pending_messages_options(ReadPendingMessagesOptions::new(6))
+            .claim_messages_options(ClaimMessagesOptions::new(5, 100))
+            .build()
+            .unwrap();
+
+        // Verify the result:
+        assert_eq!(config.get_read_new_messages_options().get_count(), 7);
+        assert_eq!(
+            config.get_read_new_messages_options().get_block(),
+            Duration::from_millis(2)
+        );
+        assert_eq!(config.get_read_pending_messages_options().get_count(), 6);
+        assert_eq!(config.get_claim_messages_options().get_count(), 5);
+        assert_eq!(config.get_claim_messages_options().get_min_idle_time(), 100);
+    }
+
+    #[test]
+    fn test_consumer_config_builder_empty_name() {
+        // Build a new ConsumerConfig instance with an empty group name:
+        let config: RedsumerResult<ConsumerConfig> =
+            ConsumerConfig::builder("stream", "", "consumer").build();
+
+        // Verify the result:
+        assert!(config.is_err());
+        assert_eq!(config.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+    }
+
+    #[test]
+    fn test_consumer_config_builder_claim_every() {
+        // Build new ConsumerConfig instances claiming every 5 consumes and every 0 consumes:
+        let config: ConsumerConfig = ConsumerConfig::builder("stream", "group", "consumer")
+            .claim_every(5)
+            .claim_jitter(true)
+            .build()
+            .unwrap();
+        let invalid: RedsumerResult<ConsumerConfig> =
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .claim_every(0)
+                .build();
+
+        // Verify the result:
+        assert_eq!(config.get_claim_messages_options().get_every(), 5);
+        assert!(config.get_claim_messages_options().get_jitter());
+        assert_eq!(invalid.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+    }
+
+    #[test]
+    fn test_consumer_claim_steps() {
+        // Create a new consumer claiming every 2 consumes, and a clone:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .claim_every(2)
+                .build()
+                .unwrap(),
+        );
+        let clone: Consumer = consumer.to_owned();
+
+        // Verify the result, the clone shares the claim steps:
+        assert!(consumer.is_claim_due());
+        assert!(!clone.is_claim_due());
+        assert!(consumer.is_claim_due());
+        assert!(!consumer.is_claim_due());
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_config_from_env {
+    use std::collections::HashMap;
+
+    use super::{DEFAULT_BLOCK, DEFAULT_MESSAGES_COUNT};
+    use crate::prelude::*;
+
+    /// Build a lookup function from a list of variables.
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        move |k: &str| vars.get(k).cloned()
+    }
+
+    #[test]
+    fn test_consumer_config_from_env_ok() {
+        // Create a new ConsumerConfig instance from variables:
+        let config: ConsumerConfig = ConsumerConfig::from_lookup(
+            "REDSUMER",
+            &lookup(&[
+                ("REDSUMER_STREAM", "stream"),
+                ("REDSUMER_GROUP", "group"),
+                ("REDSUMER_CONSUMER", "consumer"),
+                ("REDSUMER_NEW_MESSAGES_COUNT", "3"),
+                ("REDSUMER_CLAIMED_MESSAGES_COUNT", "1"),
+                ("REDSUMER_MIN_IDLE_TIME", "1000"),
+                ("REDSUMER_CREATE_STREAM", "true"),
+                ("REDSUMER_MAX_WAIT_SECONDS_FOR_STREAM", "10"),
+                ("REDSUMER_CLAIM_MODE", "compatibility"),
+                ("REDSUMER_CLAIM_EVERY", "4"),
+                ("REDSUMER_CLAIM_JITTER", "true"),
+            ]),
+        )
+        .unwrap();
+
+        // Verify the result:
+        assert_eq!(config.get_stream_name(), "stream");
+        assert_eq!(config.get_group_name(), "group");
+        assert_eq!(config.get_consumer_name(), "consumer");
+        assert_eq!(config.get_read_new_messages_options().get_count(), 3);
+        assert_eq!(
+            config.get_read_new_messages_options().get_block(),
+            DEFAULT_BLOCK
+        );
+        assert_eq!(
+            config.get_read_pending_messages_options().get_count(),
+            DEFAULT_MESSAGES_COUNT
+        );
+        assert_eq!(config.get_claim_messages_options().get_count(), 1);
+        assert_eq!(
+            config.get_claim_messages_options().get_min_idle_time(),
+            1000
+        );
+        assert!(config.get_create_stream());
+        assert_eq!(config.get_max_wait_seconds_for_stream(), 10);
+        assert_eq!(config.get_claim_mode(), ClaimMode::Compatibility);
+        assert_eq!(config.get_claim_messages_options().get_every(), 4);
+        assert!(config.get_claim_messages_options().get_jitter());
+    }
+
+    #[test]
+    fn test_consumer_config_from_env_block() {
+        // Create a new ConsumerConfig instance with a block time:
+        let config: ConsumerConfig = ConsumerConfig::from_lookup(
+            "REDSUMER",
+            &lookup(&[
+                ("REDSUMER_STREAM", "stream"),
+                ("REDSUMER_GROUP", "group"),
+                ("REDSUMER_CONSUMER", "consumer"),
+                ("REDSUMER_BLOCK", "2"),
+            ]),
+        )
+        .unwrap();
+
+        // Verify the result:
+        assert_eq!(
+            config.get_read_new_messages_options().get_block(),
+            std::time::Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_consumer_config_from_env_hostname() {
+        // Create a new ConsumerConfig instance without consumer name:
+        let config: ConsumerConfig = ConsumerConfig::from_lookup(
+            "APP",
+            &lookup(&[
+                ("APP_STREAM", "stream"),
+                ("APP_GROUP", "group"),
+                ("HOSTNAME", "pod-1"),
+            ]),
+        )
+        .unwrap();
+
+        // Verify the result:
+        assert_eq!(config.get_consumer_name(), "pod-1");
+    }
+
+    #[test]
+    fn test_consumer_config_from_env_errors() {
+        // Verify a missing group:
+        assert!(ConsumerConfig::from_lookup(
+            "REDSUMER",
+            &lookup(&[("REDSUMER_STREAM", "stream"), ("HOSTNAME", "pod-1")])
+        )
+        .is_err());
+
+        // Verify an invalid count:
+        let result: RedsumerResult<ConsumerConfig> = ConsumerConfig::from_lookup(
+            "REDSUMER",
+            &lookup(&[
+                ("REDSUMER_STREAM", "stream"),
+                ("REDSUMER_GROUP", "group"),
+                ("REDSUMER_CONSUMER", "consumer"),
+                ("REDSUMER_BLOCK", "-1"),
+            ]),
+        );
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+
+        // Verify an invalid claim mode:
+        let result: RedsumerResult<ConsumerConfig> = ConsumerConfig::from_lookup(
+            "REDSUMER",
+            &lookup(&[
+                ("REDSUMER_STREAM", "stream"),
+                ("REDSUMER_GROUP", "group"),
+                ("REDSUMER_CONSUMER", "consumer"),
+                ("REDSUMER_CLAIM_MODE", "xclaim"),
+            ]),
+        );
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_config_serde {
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+
+    use super::{DEFAULT_BLOCK, DEFAULT_MIN_IDLE_TIME};
+    use crate::prelude::*;
+
+    #[test]
+    fn test_consumer_config_serialize() {
+        // Create new ConsumerConfig instance:
+        let config: ConsumerConfig = ConsumerConfig::new(
+            "stream",
+            "group",
+            "consumer",
+            ReadNewMessagesOptions::new(3, Duration::from_millis(2)),
+            ReadPendingMessagesOptions::new(2),
+            ClaimMessagesOptions::new(1, 1000),
+        );
+
+        // Serialize the config:
+        let value: Value = serde_json::to_value(&config).unwrap();
+
+        // Verify the internal cursors are not serialized:
+        assert_eq!(
+            value,
+            json!({
+                "stream_name": "stream",
+                "group_name": "group",
+                "consumer_name": "consumer",
+                "read_new_messages_options": {"count": 3, "block": 2},
+                "read_pending_messages_options": {"count": 2},
+                "claim_messages_options": {
+                    "count": 1,
+                    "min_idle_time": 1000,
+                    "every": 1,
+                    "jitter": false
+                },
+                "create_stream": false,
+                "max_wait_seconds_for_stream": 0,
+                "pipeline_pending_and_claim": false,
+                "claim_mode": "auto"
+            })
+        );
+    }
+
+    #[test]
+    fn test_consumer_config_deserialize_with_defaults() {
+        // Deserialize the config:
+        let config: ConsumerConfig = serde_json::from_value(json!({
+            "stream_name": "stream",
+            "group_name": "group",
+            "consumer_name": "consumer",
+            "read_new_messages_options": {"count": 3},
+            "claim_messages_options": {"count": 1}
+        }))
+        .unwrap();
+
+        // Verify the result:
+        assert_eq!(config.get_stream_name(), "stream");
+        assert_eq!(config.get_read_new_messages_options().get_count(), 3);
+        assert_eq!(
+            config.get_read_new_messages_options().get_block(),
+            DEFAULT_BLOCK
+        );
+        assert_eq!(config.get_claim_messages_options().get_count(), 1);
+        assert_eq!(
+            config.get_claim_messages_options().get_min_idle_time(),
+            DEFAULT_MIN_IDLE_TIME
+        );
+        assert!(!config.get_create_stream());
+        assert_eq!(config.get_claim_mode(), ClaimMode::Auto);
+    }
+}
+
+#[cfg(test)]
+mod test_messages_kind {
+    use super::MessagesKind;
+
+    #[test]
+    fn test_messages_kind() {
+        // Create new MessagesKind instances:
+        let new_messages: MessagesKind = MessagesKind::New;
+        let pending_messages: MessagesKind = MessagesKind::Pending;
+        let claimed_messages: MessagesKind = MessagesKind::Claimed;
+        let not_found_messages: MessagesKind = MessagesKind::NotFound;
+
+        // Verify the result:
+        assert!(new_messages.are_new());
+        assert!(!new_messages.are_pending());
+        assert!(!new_messages.were_claimed());
+        assert!(!new_messages.not_found());
+
+        assert!(!pending_messages.are_new());
+        assert!(pending_messages.are_pending());
+        assert!(!pending_messages.were_claimed());
+        assert!(!pending_messages.not_found());
+
+        assert!(!claimed_messages.are_new());
+        assert!(!claimed_messages.are_pending());
+        assert!(claimed_messages.were_claimed());
+        assert!(!claimed_messages.not_found());
+
+        assert!(!not_found_messages.are_new());
+        assert!(!not_found_messages.are_pending());
+        assert!(!not_found_messages.were_claimed());
+        assert!(not_found_messages.not_found());
+    }
+}
+
+#[cfg(test)]
+mod test_consume_messages_reply {
+    use std::collections::HashMap;
+
+    use super::MessagesKind;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_consume_messages_reply_validate() {
+        // Define a valid and an invalid message:
+        let messages: Vec<StreamId> = vec![
+            StreamId {
+                id: "1-0".to_string(),
+                map: HashMap::from([("count".to_string(), Value::BulkString(b"1".to_vec()))]),
+            },
+            StreamId {
+                id: "2-0".to_string(),
+                ..Default::default()
+            },
+        ];
+        let reply: ConsumeMessagesReply = ConsumeMessagesReply::from((messages, MessagesKind::New));
+
+        // Validate the messages:
+        let invalid: HashMap<String, Vec<SchemaViolation>> =
+            reply.validate(&MessageSchema::new().required("count", FieldType::Integer));
+
+        // Verify the result:
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid["2-0"][0].get_kind(), ViolationKind::MissingField);
+    }
+
+    #[test]
+    fn test_consume_messages_reply() {
+        // Define messages and kind:
+        let messages: Vec<StreamId> = vec![StreamId::default()];
+        let kind: MessagesKind = MessagesKind::New;
+
+        // Create new ConsumeMessagesReply instance:
+        let reply: ConsumeMessagesReply = ConsumeMessagesReply::from((messages, kind));
+
+        // Verify the result:
+        assert!(reply.get_messages().len().eq(&1));
+        assert!(reply.are_new());
+        assert!(!reply.are_pending());
+        assert!(!reply.were_claimed());
+        assert!(!reply.not_found());
+    }
+
+    #[test]
+    fn test_consume_messages_reply_iterator() {
+        // Define messages:
+        let messages: Vec<StreamId> = vec![
+            StreamId {
+                id: "1-0".to_string(),
+                ..Default::default()
+            },
+            StreamId {
+                id: "2-0".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        // Create new ConsumeMessagesReply instance:
+        let reply: ConsumeMessagesReply = ConsumeMessagesReply::from((messages, MessagesKind::New));
+
+        // Verify the result:
+        assert_eq!(reply.len(), 2);
+        assert!(!reply.is_empty());
+        assert_eq!(
+            reply.iter().map(|m| m.id.as_str()).collect::<Vec<&str>>(),
+            vec!["1-0", "2-0"]
+        );
+
+        let mut borrowed: Vec<&str> = Vec::new();
+        for message in &reply {
+            borrowed.push(&message.id);
+        }
+        assert_eq!(borrowed, vec!["1-0", "2-0"]);
+
+        let owned: Vec<String> = reply.into_iter().map(|m| m.id).collect();
+        assert_eq!(owned, vec!["1-0", "2-0"]);
+    }
+
+    #[test]
+    fn test_consume_messages_reply_empty() {
+        // Create new ConsumeMessagesReply instance without messages:
+        let reply: ConsumeMessagesReply =
+            ConsumeMessagesReply::from((Vec::new(), MessagesKind::NotFound));
+
+        // Verify the result:
+        assert_eq!(reply.len(), 0);
+        assert!(reply.is_empty());
+        assert_eq!(reply.into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_consume_messages_reply_by_kind() {
+        // Create new ConsumeMessagesReply instance with claimed messages:
+        let reply: ConsumeMessagesReply = ConsumeMessagesReply::from((
+            vec![StreamId::default(), StreamId::default()],
+            MessagesKind::Claimed,
+        ));
+
+        // Verify the result:
+        assert!(reply.new_messages().is_empty());
+        assert!(reply.pending_messages().is_empty());
+        assert_eq!(reply.claimed_messages().len(), 2);
+    }
+}
+
+#[cfg(test)]
+mod test_is_still_mine_reply {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_is_still_mine_reply() {
+        // Define is still mine, last delivered milliseconds and total times delivered:
+        let is_still_mine: bool = true;
+        let last_delivered_milliseconds: Option<LastDeliveredMilliseconds> = Some(1000);
+        let total_times_delivered: Option<TotalTimesDelivered> = Some(787);
+
+        // Create new IsStillMineReply instance:
+        let reply: IsStillMineReply = IsStillMineReply::from((
+            is_still_mine,
+            last_delivered_milliseconds,
+            total_times_delivered,
+        ));
+
+        // Verify the result:
+        assert!(reply.belongs_to_me());
+
+        assert!(reply.get_last_delivered_milliseconds().is_some());
+        assert!(reply
+            .get_last_delivered_milliseconds()
+            .eq(&last_delivered_milliseconds));
+
+        assert!(reply.get_total_times_delivered().is_some());
+        assert!(reply.get_total_times_delivered().eq(&total_times_delivered));
+    }
+}
+
+#[cfg(test)]
+mod test_ack_message_reply {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_ack_message_reply() {
+        // Define was acked:
+        let was_acked: bool = true;
+
+        // Create new AckMessageReply instance:
+        let reply: AckMessageReply = AckMessageReply::from(was_acked);
+
+        // Verify the result:
+        assert!(reply.was_acked());
+    }
+}
+
+#[cfg(test)]
+mod test_remove_consumer_reply {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_remove_consumer_reply() {
+        // Create new RemoveConsumerReply instance:
+        let reply: RemoveConsumerReply = RemoveConsumerReply::from(3);
+
+        // Verify the result:
+        assert_eq!(reply.get_pending_messages(), 3);
+    }
+}
+
+#[cfg(test)]
+mod test_group_lag {
+    use redis::streams::StreamInfoGroup;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_group_lag_from_stream_info_group() {
+        // Define the group information:
+        let info: StreamInfoGroup = StreamInfoGroup {
+            name: "group".to_string(),
+            consumers: 2,
+            pending: 3,
+            last_delivered_id: "10-0".to_string(),
+            entries_read: Some(10),
+            lag: Some(4),
+        };
+
+        // Create new GroupLag instance:
+        let lag: GroupLag = GroupLag::from(info);
+
+        // Verify the result:
+        assert_eq!(lag.get_lag(), Some(4));
+        assert_eq!(lag.get_entries_read(), Some(10));
+        assert_eq!(lag.get_pending(), 3);
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_pause {
+    use std::sync::{atomic::AtomicBool, Arc};
+
+    use redis::Client;
+
+    use super::BEGINNING_OF_TIME_ID;
+    use crate::prelude::*;
+
+    /// Build a consumer without connecting to the Redis server.
+    fn consumer() -> Consumer {
+        Consumer {
+            client: Arc::new(Client::open("redis://fakehost/0").unwrap()),
+            config: ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: Default::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consumer_pause_and_resume() {
+        // Create a new consumer and a handle:
+        let mut consumer: Consumer = consumer();
+        let handle: Consumer = consumer.clone();
+        assert!(!consumer.is_paused());
+
+        // Pause the consumer from the handle:
+        handle.pause();
+        assert!(consumer.is_paused());
+
+        // Consume without reaching the server:
+        let reply: ConsumeMessagesReply = consumer.consume().await.unwrap();
+        assert!(reply.get_messages().is_empty());
+        assert!(reply.not_found());
+
+        // Resume the consumer:
+        handle.resume();
+        assert!(!consumer.is_paused());
+    }
+
+    #[test]
+    fn test_consumer_reset_cursors() {
+        // Create a new consumer with moved cursors:
+        let consumer: Consumer = consumer();
+        consumer.update_latest_pending_message_id("5-0");
+        consumer.update_next_id_to_claim("7-1");
+        assert_eq!(consumer.get_latest_pending_message_id(), "5-0");
+        assert_eq!(consumer.get_next_id_to_claim(), "7-1");
+
+        // Reset the cursors:
+        consumer.reset_cursors();
+
+        // Verify the result:
+        assert_eq!(consumer.get_latest_pending_message_id(), "0-0");
+        assert_eq!(consumer.get_next_id_to_claim(), "0-0");
+    }
+
+    #[test]
+    fn test_consumer_clones_share_cursors() {
+        // Create a new consumer and a clone:
+        let consumer: Consumer = consumer();
+        let clone: Consumer = consumer.clone();
+        assert_eq!(clone.get_latest_pending_message_id(), BEGINNING_OF_TIME_ID);
+        assert_eq!(clone.get_next_id_to_claim(), BEGINNING_OF_TIME_ID);
+
+        // Move the cursors of the consumer:
+        consumer.update_latest_pending_message_id("5-0");
+        consumer.update_next_id_to_claim("7-1");
+
+        // Verify the result:
+        assert_eq!(clone.get_latest_pending_message_id(), "5-0");
+        assert_eq!(clone.get_next_id_to_claim(), "7-1");
+
+        // Reset the cursors of the clone:
+        clone.reset_cursors();
+
+        // Verify the result:
+        assert_eq!(
+            consumer.get_latest_pending_message_id(),
+            BEGINNING_OF_TIME_ID
+        );
+        assert_eq!(consumer.get_next_id_to_claim(), BEGINNING_OF_TIME_ID);
+    }
+
+    #[test]
+    fn test_consumer_report_missing_stream_unreachable() {
+        // Define a NOGROUP error:
+        let error: RedsumerError = RedsumerError::from((
+            redis::ErrorKind::ResponseError,
+            "NOGROUP No such key or consumer group",
+        ));
+
+        // Classify the error without reaching the server:
+        let reported: RedsumerError = consumer().report_missing_stream(error);
+
+        // Verify the original error is kept when the stream can not be verified:
+        assert!(!matches!(reported, RedsumerError::StreamNotFound(_)));
+        assert_eq!(reported.kind(), redis::ErrorKind::ResponseError);
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_metrics {
+    use std::sync::{atomic::AtomicBool, Arc, Mutex};
+
+    use redis::Client;
+
+    use crate::prelude::*;
+
+    /// A recorder that keeps the failed operations.
+    #[derive(Debug, Default)]
+    struct ErrorsRecorder {
+        errors: Mutex<Vec<Operation>>,
+    }
+
+    impl MetricsRecorder for ErrorsRecorder {
+        fn record_error(&self, operation: Operation, _stream: &str, _error: &RedsumerError) {
+            self.errors.lock().unwrap().push(operation);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consumer_metrics_record_error() {
+        // Create a new consumer with an unreachable server:
+        let recorder: Arc<ErrorsRecorder> = Arc::new(ErrorsRecorder::default());
+        let mut consumer: Consumer = Consumer {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            config: ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: Default::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        }
+        .with_metrics(recorder.clone());
+
+        // Consume and ack messages:
+        assert!(consumer.consume().await.is_err());
+        assert!(consumer
+            .ack(&AckToken::new(
+                EntryId::new(1, 0),
+                "stream",
+                "group",
+                "consumer"
+            ))
+            .await
+            .is_err());
+
+        // Verify the result:
+        assert_eq!(
+            *recorder.errors.lock().unwrap(),
+            vec![Operation::Consume, Operation::Ack]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_stats {
+    use crate::prelude::*;
+
+    use super::ConsumerStats;
+
+    #[test]
+    fn test_consumer_stats_record_consumed() {
+        // Define the stats:
+        let mut stats: ConsumerStats = ConsumerStats::default();
+
+        // Record consumed messages:
+        stats.record_consumed(&MessagesKind::New, 3);
+        stats.record_consumed(&MessagesKind::Pending, 2);
+        stats.record_consumed(&MessagesKind::Claimed, 1);
+        stats.record_consumed(&MessagesKind::NotFound, 0);
+
+        // Verify the result:
+        assert_eq!(stats.get_new_messages(), 3);
+        assert_eq!(stats.get_pending_messages(), 2);
+        assert_eq!(stats.get_claimed_messages(), 1);
+        assert_eq!(stats.get_consumed_messages(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_consumer_stats_errors() {
+        // Create a new consumer with an unreachable server:
+        let mut consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+
+        // Verify the initial stats:
+        assert_eq!(consumer.stats(), ConsumerStats::default());
+
+        // Consume and ack messages:
+        assert!(consumer.consume().await.is_err());
+        assert!(consumer
+            .ack(&AckToken::new(
+                EntryId::new(1, 0),
+                "stream",
+                "group",
+                "consumer"
+            ))
+            .await
+            .is_err());
+
+        // Verify the result:
+        let stats: ConsumerStats = consumer.to_owned().stats();
+        assert_eq!(stats.get_consumed_messages(), 0);
+        assert_eq!(stats.get_acks(), 0);
+        assert_eq!(stats.get_failed_acks(), 1);
+        assert_eq!(stats.get_claim_cycles(), 0);
+        assert!(stats.get_last_error().is_some());
+    }
+
+    #[test]
+    fn test_consumer_current_rate() {
+        // Create a new consumer:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+
+        // Verify the rate is not measured by default:
+        assert!(consumer.current_rate().is_none());
+
+        // Verify the result with a rate window:
+        let consumer: Consumer = consumer.with_rate_window(std::time::Duration::from_secs(60));
+        assert_eq!(consumer.current_rate(), Some(0.0));
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_claim_from {
+    use crate::prelude::*;
+
+    #[tokio::test]
+    async fn test_consumer_claim_from_error() {
+        // Create a new consumer with an unreachable server:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+
+        // Claim the messages of a dead consumer:
+        let result: RedsumerResult<ConsumeMessagesReply> = consumer
+            .claim_from(
+                "dead-consumer",
+                ClaimFilter::Idle {
+                    min_idle_time: 60000,
+                    count: 10,
+                },
+            )
+            .await;
+
+        // Verify the result:
+        assert!(result.is_err());
+        assert!(consumer.stats().get_last_error().is_some());
+        assert_eq!(consumer.get_in_flight_count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_interceptor {
+    use std::sync::{atomic::AtomicBool, Arc};
+
+    use redis::{Client, ErrorKind};
+
+    use crate::prelude::*;
+
+    /// An interceptor that rejects every ack.
+    #[derive(Debug)]
+    struct RejectAckInterceptor;
+
+    impl Interceptor for RejectAckInterceptor {
+        fn before_ack(&self, _stream: &str, _group: &str, _id: &Id) -> RedsumerResult<()> {
+            Err(RedsumerError::from((ErrorKind::ClientError, "Rejected")))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consumer_interceptor_before_ack() {
+        // Create a new consumer with an interceptor:
+        let consumer: Consumer = Consumer {
+            client: Arc::new(Client::open("redis://fakehost/0").unwrap()),
+            config: ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: Default::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        }
+        .with_interceptor(Arc::new(RejectAckInterceptor));
+
+        // Ack a message:
+        let result: RedsumerResult<AckMessageReply> = consumer
+            .ack(&AckToken::new(
+                EntryId::new(1, 0),
+                "stream",
+                "group",
+                "consumer",
+            ))
+            .await;
+
+        // Verify the ack was aborted before reaching the server:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_callbacks {
+    use std::sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use redis::Client;
+
+    use crate::prelude::*;
+
+    #[tokio::test]
+    async fn test_consumer_callbacks_on_error() {
+        // Define a callback that counts errors:
+        let errors: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let counter: Arc<AtomicUsize> = errors.clone();
+
+        // Create a new consumer with an unreachable server:
+        let mut consumer: Consumer = Consumer {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            config: ConsumerConfig::builder("stream", "group", "consumer")
+                .on_error(move |_| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                })
+                .build()
+                .unwrap(),
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: Default::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        };
+
+        // Consume messages:
+        assert!(consumer.consume().await.is_err());
+
+        // Verify the result:
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+    }
+}
+
+#[cfg(test)]
+mod test_idle_strategy {
+    use std::{
+        ops::ControlFlow,
+        sync::{atomic::AtomicBool, Arc},
+        time::{Duration, Instant},
+    };
+
+    use futures_util::StreamExt;
+    use redis::Client;
+    use tokio::time::timeout;
+
+    use super::IdleStrategy;
+    use crate::prelude::*;
+
+    /// Build a paused consumer without connecting to the Redis server.
+    fn paused_consumer() -> Consumer {
+        Consumer {
+            client: Arc::new(Client::open("redis://fakehost/0").unwrap()),
+            config: ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            paused: Arc::new(AtomicBool::new(true)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::Fixed(Duration::from_millis(5)),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: Default::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    #[test]
+    fn test_idle_strategy_delay() {
+        // Define the strategies:
+        let backoff: IdleStrategy = IdleStrategy::ExponentialBackoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(500),
+        };
+
+        // Verify the result:
+        assert_eq!(IdleStrategy::NoWait.get_delay(3), Duration::ZERO);
+        assert_eq!(
+            IdleStrategy::Fixed(Duration::from_millis(50)).get_delay(3),
+            Duration::from_millis(50)
+        );
+        assert_eq!(backoff.get_delay(1), Duration::from_millis(100));
+        assert_eq!(backoff.get_delay(2), Duration::from_millis(200));
+        assert_eq!(backoff.get_delay(3), Duration::from_millis(400));
+        assert_eq!(backoff.get_delay(4), Duration::from_millis(500));
+        assert_eq!(backoff.get_delay(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_run_waits_while_idle() {
+        // Create a paused consumer, so every consume finds no messages:
+        let mut consumer: Consumer = paused_consumer();
+
+        // Run the consumer for a while:
+        let result = timeout(
+            Duration::from_millis(50),
+            consumer.run(|_| async { Ok(ControlFlow::Break(())) }),
+        )
+        .await;
+
+        // Verify the handler was never called:
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_into_stream_waits_while_idle() {
+        // Convert a paused consumer into a stream:
+        let mut stream = Box::pin(
+            paused_consumer()
+                .with_idle_strategy(IdleStrategy::ExponentialBackoff {
+                    initial: Duration::from_millis(1),
+                    max: Duration::from_millis(10),
+                })
+                .into_stream(),
+        );
+
+        // Verify no batch is yielded:
+        assert!(timeout(Duration::from_millis(50), stream.next())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_consume_until_manual_clock() {
+        // Create a paused consumer with a manual clock:
+        let clock: ManualClock = ManualClock::new();
+        let mut consumer: Consumer = paused_consumer()
+            .with_idle_strategy(IdleStrategy::Fixed(Duration::from_secs(1)))
+            .with_clock(Arc::new(clock.to_owned()));
+
+        // Consume until a deadline one minute ahead:
+        let reply: ConsumeMessagesReply = timeout(
+            Duration::from_secs(5),
+            consumer.consume_until(clock.now() + Duration::from_secs(60)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        // Verify the result, the idle waits advanced the clock up to the deadline:
+        assert!(reply.not_found());
+        assert_eq!(clock.get_elapsed(), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_consume_until_deadline() {
+        // Create a paused consumer, so every consume finds no messages:
+        let mut consumer: Consumer = paused_consumer();
+
+        // Consume until a deadline, and until a deadline that has already passed:
+        let started_at: Instant = Instant::now();
+        let reply: ConsumeMessagesReply = consumer
+            .consume_until(started_at + Duration::from_millis(30))
+            .await
+            .unwrap();
+        let elapsed: Duration = started_at.elapsed();
+        let expired: ConsumeMessagesReply = consumer.consume_until(started_at).await.unwrap();
+
+        // Verify the result:
+        assert!(reply.not_found());
+        assert!(elapsed >= Duration::from_millis(30));
+        assert!(elapsed < Duration::from_secs(1));
+        assert!(expired.not_found());
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_max_in_flight {
+    use std::sync::{atomic::AtomicBool, Arc};
+
+    use redis::{streams::StreamId, Client};
+
+    use super::IdleStrategy;
+    use crate::prelude::*;
+
+    /// Build a consumer with a max number of messages in flight without connecting to the Redis server.
+    fn consumer(max: usize) -> Consumer {
+        Consumer {
+            client: Arc::new(Client::open("redis://fakehost/0").unwrap()),
+            config: ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: Default::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        }
+        .with_max_in_flight(max)
+    }
+
+    /// Build a message with the given *id*.
+    fn message(id: &str) -> StreamId {
+        StreamId {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consumer_max_in_flight_reached() {
+        // Create a new consumer and a handle:
+        let mut consumer: Consumer = consumer(2);
+        let handle: Consumer = consumer.clone();
+
+        // Track messages in flight up to the limit:
+        consumer.track_in_flight(&[message("1-0"), message("2-0"), message("2-0")]);
+        assert_eq!(handle.get_in_flight_count(), 2);
+        assert!(consumer.is_in_flight_limit_reached());
+
+        // Consume without reaching the server:
+        let reply: ConsumeMessagesReply = consumer.consume().await.unwrap();
+        assert!(reply.not_found());
+    }
+
+    #[test]
+    fn test_consumer_max_in_flight_not_reached() {
+        // Create a new consumer:
+        let consumer: Consumer = consumer(2);
+
+        // Track a message in flight:
+        consumer.track_in_flight(&[message("1-0")]);
+
+        // Verify the result:
+        assert_eq!(consumer.get_in_flight_count(), 1);
+        assert!(!consumer.is_in_flight_limit_reached());
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_ack_deadline {
+    use std::{
+        sync::{atomic::AtomicBool, Arc, Mutex},
+        time::Duration,
+    };
+
+    use redis::{streams::StreamId, Client};
+
+    use super::IdleStrategy;
+    use crate::prelude::*;
+
+    /// Build a consumer without connecting to the Redis server, recording the messages that exceeded the ack deadline.
+    fn consumer(exceeded: Arc<Mutex<Vec<String>>>) -> Consumer {
+        Consumer {
+            client: Arc::new(Client::open("redis://fakehost/0").unwrap()),
+            config: ConsumerConfig::builder("stream", "group", "consumer")
+                .on_ack_deadline_exceeded(move |id, _| {
+                    exceeded.lock().unwrap().push(id.to_string())
+                })
+                .build()
+                .unwrap(),
+            paused: Arc::new(AtomicBool::new(false)),
+            cursors: Arc::default(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            interceptors: Vec::new(),
+            group_recovery: None,
+            lazy_start_position: None,
+            heartbeat_ttl: None,
+            idle_consumer_cleanup: None,
+            idle_strategy: IdleStrategy::default(),
+            in_flight: Arc::default(),
+            max_in_flight: None,
+            ack_deadline: None,
+            stats: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            auto_commit: None,
+            checkpoint_store: None,
+            capabilities: Arc::default(),
+            read_cycle: Default::default(),
+            claim_steps: Arc::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Build a message with the given *id*.
+    fn message(id: &str) -> StreamId {
+        StreamId {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consumer_ack_deadline_warn() {
+        // Create a new consumer with an ack deadline:
+        let exceeded: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let consumer: Consumer = consumer(exceeded.to_owned())
+            .with_ack_deadline(Duration::from_millis(10), AckDeadlineAction::Warn);
+
+        // Track a message in flight:
+        consumer.track_in_flight(&[message("1-0")]);
+
+        // Verify the deadline is not exceeded yet:
+        assert!(consumer.check_ack_deadlines().await.unwrap().is_empty());
+
+        // Verify the deadline is exceeded:
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let overdue: Vec<String> = consumer.check_ack_deadlines().await.unwrap();
+        assert_eq!(overdue, vec!["1-0".to_string()]);
+        assert_eq!(*exceeded.lock().unwrap(), vec!["1-0".to_string()]);
+
+        // Verify the deadline starts again:
+        assert!(consumer.check_ack_deadlines().await.unwrap().is_empty());
+        assert_eq!(consumer.get_in_flight_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_consumer_ack_deadline_renew_error() {
+        // Create a new consumer with an ack deadline:
+        let exceeded: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let consumer: Consumer = consumer(exceeded.to_owned())
+            .with_ack_deadline(Duration::ZERO, AckDeadlineAction::Renew);
+
+        // Track a message in flight:
+        consumer.track_in_flight(&[message("1-0")]);
+
+        // Verify the message can not be renewed without a server:
+        assert!(consumer.check_ack_deadlines().await.is_err());
+        assert!(exceeded.lock().unwrap().is_empty());
+        assert_eq!(consumer.get_in_flight_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_consumer_without_ack_deadline() {
+        // Create a new consumer without an ack deadline:
+        let consumer: Consumer = consumer(Arc::new(Mutex::new(Vec::new())));
+
+        // Verify messages are not tracked:
+        consumer.track_in_flight(&[message("1-0")]);
+        assert_eq!(consumer.get_in_flight_count(), 0);
+        assert!(consumer.check_ack_deadlines().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_consumer_ack_deadline_manual_clock() {
+        // Create a new consumer with an ack deadline of one minute and a manual clock:
+        let exceeded: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let clock: ManualClock = ManualClock::new();
+        let consumer: Consumer = consumer(exceeded.to_owned())
+            .with_ack_deadline(Duration::from_secs(60), AckDeadlineAction::Warn)
+            .with_clock(Arc::new(clock.to_owned()));
+
+        // Track a message in flight and advance the clock before the deadline:
+        consumer.track_in_flight(&[message("1-0")]);
+        clock.advance(Duration::from_secs(59));
+
+        // Verify the deadline is not exceeded yet:
+        assert!(consumer.check_ack_deadlines().await.unwrap().is_empty());
+
+        // Verify the deadline is exceeded, without waiting:
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(
+            consumer.check_ack_deadlines().await.unwrap(),
+            vec!["1-0".to_string()]
+        );
+        assert_eq!(*exceeded.lock().unwrap(), vec!["1-0".to_string()]);
+    }
+}
+
+#[cfg(test)]
+mod test_start_position {
+    use time::macros::datetime;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_start_position_to_id() {
+        // Verify the IDs:
+        assert_eq!(StartPosition::default().to_id().unwrap(), "0-0");
+        assert_eq!(StartPosition::OnlyNewMessages.to_id().unwrap(), "$");
+        assert_eq!(
+            StartPosition::FromId(EntryId::new(10, 2)).to_id().unwrap(),
+            "10-2"
+        );
+        assert_eq!(
+            StartPosition::FromTimestamp(datetime!(2023-11-14 22:13:20.123 UTC))
+                .to_id()
+                .unwrap(),
+            "1700000000123-0"
+        );
+        assert!(
+            StartPosition::FromTimestamp(datetime!(1969-12-31 23:59:59 UTC))
+                .to_id()
+                .is_err()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_lazy_consumer {
+    use crate::prelude::*;
+
+    #[tokio::test]
+    async fn test_lazy_consumer_without_server() {
+        // Build a new lazy consumer with an unreachable server:
+        let consumer: RedsumerResult<Consumer> = Consumer::new_lazy(
+            ClientArgs::new(None, "127.0.0.1", 1, 0, CommunicationProtocol::RESP2),
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
+        );
+
+        // Verify the consumer is built without connecting:
+        assert!(consumer.is_ok());
+        let mut consumer: Consumer = consumer.unwrap();
+
+        // Verify the connection error is deferred to the first consume:
+        assert!(consumer.consume().await.is_err());
+        assert!(consumer.lazy_start_position.is_some());
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_from_client {
+    use std::sync::Arc;
+
+    use redis::{cmd, Client, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_consumer_from_unreachable_client() {
+        // Define a shared client:
+        let client: Arc<Client> = Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap());
+
+        // Create a new consumer:
+        let result: RedsumerResult<Consumer> = Consumer::from_client(
+            client,
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
+        );
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_lazy_consumer_get_shared_client() {
+        // Build a new lazy consumer:
+        let consumer: Consumer = Consumer::new_lazy(
+            ClientArgs::new(None, "127.0.0.1", 1, 0, CommunicationProtocol::RESP2),
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
+        )
+        .unwrap();
+
+        // Verify the shared client is the same in every clone:
+        assert!(Arc::ptr_eq(
+            &consumer.get_shared_client(),
+            &consumer.clone().get_shared_client()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_consumer_from_mock_connection() {
+        // Define a mock connection:
+        let connection: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new(cmd("EXISTS").arg("stream"), Ok(1)),
+            MockCmd::new(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg("stream")
+                    .arg("group")
+                    .arg("0-0"),
+                Ok("OK"),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Ok(Value::BulkString(
+                    b"# Server\r\nredis_version:7.2.4\r\n".to_vec(),
+                )),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XACK").arg("stream").arg("group").arg("1-0"),
+                Ok(Value::Int(1)),
+            ),
+        ]);
+
+        // Create a new consumer on the mock connection:
+        let consumer: Consumer<MockRedisConnection> = Consumer::from_client(
+            Arc::new(connection),
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
+        )
+        .unwrap();
+
+        // Ack a message:
+        let reply: AckMessageReply = consumer
+            .ack(&AckToken::new(Id::new(1, 0), "stream", "group", "consumer"))
+            .await
+            .unwrap();
+
+        // Verify the result:
+        assert!(reply.was_acked());
+        assert_eq!(consumer.stats().get_acks(), 1);
+        assert_eq!(
+            consumer.get_capabilities().get_version(),
+            Some(ServerVersion::new(7, 2, 4))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_ack_token {
+    use super::*;
+
+    #[test]
+    fn test_consume_messages_reply_issue_ack_tokens() {
+        // Define a reply with messages:
+        let messages: Vec<StreamId> = vec![
+            StreamId {
+                id: "1-0".to_string(),
+                map: HashMap::new(),
+            },
+            StreamId {
+                id: "2-0".to_string(),
+                map: HashMap::new(),
+            },
+        ];
+        let mut reply: ConsumeMessagesReply = (messages, MessagesKind::New).into();
+        assert!(reply.get_ack_tokens().is_empty());
+
+        // Issue the ack tokens:
+        reply.issue_ack_tokens("stream", "group", "consumer");
+
+        // Verify the result:
+        assert_eq!(
+            reply.get_ack_tokens(),
+            &[
+                AckToken::new(Id::new(1, 0), "stream", "group", "consumer"),
+                AckToken::new(Id::new(2, 0), "stream", "group", "consumer"),
+            ]
+        );
+        assert_eq!(
+            reply.get_ack_token("2-0").map(|token| token.get_id()),
+            Some(&Id::new(2, 0))
+        );
+        assert!(reply.get_ack_token("3-0").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_consumer_ack_foreign_token() {
+        // Create a new consumer with an unreachable server:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+
+        // Ack messages with tokens issued for another stream, group and consumer:
+        for token in [
+            AckToken::new(Id::new(1, 0), "other", "group", "consumer"),
+            AckToken::new(Id::new(1, 0), "stream", "other", "consumer"),
+            AckToken::new(Id::new(1, 0), "stream", "group", "other"),
+        ] {
+            let result: RedsumerResult<AckMessageReply> = consumer.ack(&token).await;
+
+            // Verify the ack was rejected before reaching the server:
+            assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+        }
+        assert_eq!(consumer.stats().get_failed_acks(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_consumer_ack_many_detailed_foreign_token() {
+        // Create a new consumer with an unreachable server:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+
+        // Ack a batch with a token issued for another stream:
+        let tokens: Vec<AckToken> = vec![
+            AckToken::new(Id::new(1, 0), "stream", "group", "consumer"),
+            AckToken::new(Id::new(2, 0), "other", "group", "consumer"),
+        ];
+        let result: RedsumerResult<AckManyReply> = consumer.ack_many_detailed(&tokens).await;
+
+        // Verify the whole batch was rejected before reaching the server:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+        assert_eq!(consumer.stats().get_failed_acks(), 1);
+    }
+
+    #[test]
+    fn test_ack_token_verify_consumer() {
+        // Define a token:
+        let token: AckToken = AckToken::new(Id::new(1, 0), "stream", "group", "consumer");
+
+        // Verify the result:
+        assert!(token.verify_consumer("group", "consumer").is_ok());
+        assert!(token.verify_consumer("other", "consumer").is_err());
+        assert!(token.verify_consumer("group", "other").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_ack_many_reply {
+    use super::*;
+
+    #[test]
+    fn test_ack_many_reply_from_results() {
+        // Define the ids and the ack results:
+        let ids: Vec<Id> = vec![Id::new(1, 0), Id::new(2, 0), Id::new(3, 0)];
+        let acked: Vec<bool> = vec![true, false, true];
+
+        // Build the reply:
+        let reply: AckManyReply = AckManyReply::from((ids.as_slice(), acked));
+
+        // Verify the result:
+        assert_eq!(reply.get_acked(), &[Id::new(1, 0), Id::new(3, 0)]);
+        assert_eq!(reply.get_not_acked(), &[Id::new(2, 0)]);
+        assert!(!reply.all_acked());
+    }
+
+    #[tokio::test]
+    async fn test_consumer_ack_all_empty_reply() {
+        // Create a new consumer with an unreachable server:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+
+        // Ack a reply without messages, which does not reach the server:
+        let reply: ConsumeMessagesReply =
+            ConsumeMessagesReply::from((Vec::new(), MessagesKind::NotFound));
+        let result: RedsumerResult<AckManyReply> = consumer.ack_all(&reply).await;
+
+        // Verify the result:
+        assert!(result.unwrap().get_acked().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_consumer_ack_all_missing_token() {
+        // Create a new consumer with an unreachable server:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+
+        // Ack a reply whose messages were not consumed, so no ack token was issued:
+        let reply: ConsumeMessagesReply = ConsumeMessagesReply::from((
+            vec![StreamId {
+                id: "1-0".to_owned(),
+                map: HashMap::new(),
+            }],
+            MessagesKind::New,
+        ));
+        let result: RedsumerResult<AckManyReply> = consumer.ack_all(&reply).await;
+
+        // Verify the ack was rejected before reaching the server:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+
+    #[test]
+    fn test_ack_many_reply_all_acked() {
+        // Build a reply without messages:
+        let reply: AckManyReply = AckManyReply::default();
+
+        // Verify the result:
+        assert!(reply.get_acked().is_empty());
+        assert!(reply.all_acked());
+    }
+}
+
+#[cfg(test)]
+mod test_consumer_auto_commit {
+    use std::time::{Duration, Instant};
+
+    use redis::streams::StreamId;
+
+    use std::sync::Mutex;
+
+    use super::{AutoCommit, PendingCommit};
+    use crate::prelude::*;
+
+    /// Build a message with the given *id*.
+    fn message(id: &str) -> StreamId {
+        StreamId {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_auto_commit_contiguous_position() {
+        // Track delivered messages:
+        let mut auto_commit: AutoCommit = AutoCommit::new(Duration::from_secs(1), Instant::now());
+        auto_commit.track(&[
+            message("1-0"),
+            message("2-0"),
+            message("3-0"),
+            message("4-0"),
+        ]);
+
+        // Process every message except the third one:
+        for id in ["1-0", "2-0", "4-0"] {
+            assert!(auto_commit.mark_processed(&id.parse().unwrap()));
+        }
+        assert!(!auto_commit.mark_processed(&"5-0".parse().unwrap()));
+
+        // Verify the result:
+        assert_eq!(
+            auto_commit.take_committable(Instant::now()),
+            vec![Id::new(1, 0), Id::new(2, 0)]
+        );
+        assert!(auto_commit.take_committable(Instant::now()).is_empty());
+
+        // Process the third message:
+        auto_commit.mark_processed(&Id::new(3, 0));
+
+        // Verify the result:
+        assert_eq!(
+            auto_commit.take_committable(Instant::now()),
+            vec![Id::new(3, 0), Id::new(4, 0)]
+        );
+        assert!(auto_commit.delivered.is_empty());
+    }
+
+    #[test]
+    fn test_auto_commit_restore() {
+        // Track and process a message:
+        let mut auto_commit: AutoCommit = AutoCommit::new(Duration::ZERO, Instant::now());
+        auto_commit.track(&[message("1-0")]);
+        auto_commit.mark_processed(&Id::new(1, 0));
+        assert!(auto_commit.is_due(Instant::now()));
+
+        // Take and restore the committable messages, as after a failed commit:
+        let ids: Vec<Id> = auto_commit.take_committable(Instant::now());
+        auto_commit.restore(&ids);
+
+        // Verify the result:
+        assert_eq!(
+            auto_commit.take_committable(Instant::now()),
+            vec![Id::new(1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_pending_commit_cancelled() {
+        // Track and process two messages:
+        let auto_commit: Mutex<AutoCommit> =
+            Mutex::new(AutoCommit::new(Duration::ZERO, Instant::now()));
+        auto_commit
+            .lock()
+            .unwrap()
+            .track(&[message("1-0"), message("2-0")]);
+        auto_commit.lock().unwrap().mark_processed(&Id::new(1, 0));
+        auto_commit.lock().unwrap().mark_processed(&Id::new(2, 0));
+
+        // Take the committable messages and drop their commits, finishing only the second one:
+        let first: Vec<Id> = auto_commit.lock().unwrap().take_committable(Instant::now());
+        drop(PendingCommit::new(&auto_commit, &first));
+        let second: Vec<Id> = auto_commit.lock().unwrap().take_committable(Instant::now());
+        PendingCommit::new(&auto_commit, &second).finish();
+
+        // Verify the result:
+        assert_eq!(first, vec![Id::new(1, 0), Id::new(2, 0)]);
+        assert_eq!(second, first);
+        assert!(auto_commit
+            .lock()
+            .unwrap()
+            .take_committable(Instant::now())
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_consumer_auto_commit() {
+        // Create a new consumer with auto-commit:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        )
+        .with_auto_commit(Duration::from_secs(1));
+
+        // Track a delivered message without processing it:
+        consumer.track_in_flight(&[message("1-0")]);
+
+        // Verify the result, without reaching the server:
+        assert_eq!(consumer.get_uncommitted_count(), 1);
+        assert!(!consumer.is_commit_due());
+        assert_eq!(consumer.commit().await.unwrap(), 0);
+        assert!(consumer
+            .mark_processed(&AckToken::new(Id::new(1, 0), "stream", "group", "consumer"))
+            .unwrap());
+        assert_eq!(
+            consumer
+                .mark_processed(&AckToken::new(Id::new(1, 0), "other", "group", "consumer"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::ClientError
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consumer_without_auto_commit() {
+        // Create a new consumer without auto-commit:
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+
+        // Verify the result:
+        assert!(!consumer
+            .mark_processed(&AckToken::new(Id::new(1, 0), "stream", "group", "consumer"))
+            .unwrap());
+        assert_eq!(consumer.get_uncommitted_count(), 0);
+        assert_eq!(consumer.commit().await.unwrap(), 0);
     }
 }
 
 #[cfg(test)]
-mod test_read_new_messages_options {
+mod test_consumer_checkpoint {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
     use crate::prelude::*;
 
-    #[test]
-    fn test_new_read_new_messages_options() {
-        // Define count and block:
-        let count: usize = 10;
-        let block: usize = 3;
+    /// An in-memory checkpoint store.
+    #[derive(Debug, Default)]
+    struct MemoryCheckpointStore {
+        checkpoints: Mutex<HashMap<String, Checkpoint>>,
+    }
 
-        // Create new ReadNewMessagesOptions instance:
-        let options: ReadNewMessagesOptions = ReadNewMessagesOptions::new(count, block);
+    impl CheckpointStore for MemoryCheckpointStore {
+        fn save(
+            &self,
+            stream: &str,
+            group: &str,
+            consumer: &str,
+            checkpoint: &Checkpoint,
+        ) -> RedsumerResult<()> {
+            self.checkpoints.lock().unwrap().insert(
+                format!("{stream}:{group}:{consumer}"),
+                checkpoint.to_owned(),
+            );
+            Ok(())
+        }
+
+        fn load(
+            &self,
+            stream: &str,
+            group: &str,
+            consumer: &str,
+        ) -> RedsumerResult<Option<Checkpoint>> {
+            Ok(self
+                .checkpoints
+                .lock()
+                .unwrap()
+                .get(&format!("{stream}:{group}:{consumer}"))
+                .cloned())
+        }
+    }
+
+    /// Build a consumer without connecting to the Redis server.
+    fn consumer() -> Consumer {
+        Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        )
+    }
+
+    /// Build a consumer with the given checkpoint *store*.
+    fn consumer_with(store: Arc<MemoryCheckpointStore>) -> Consumer {
+        consumer().with_checkpoint_store(store)
+    }
+
+    #[tokio::test]
+    async fn test_consumer_checkpoint_roundtrip() {
+        // Create a consumer with a checkpoint store and move its cursors:
+        let store: Arc<MemoryCheckpointStore> = Arc::default();
+        let consumer: Consumer = consumer_with(store.clone());
+        consumer.update_latest_pending_message_id("5-0");
+        consumer.update_next_id_to_claim("3-0");
+
+        // Save a checkpoint:
+        let saved: Checkpoint = consumer.save_checkpoint(Some(Id::new(4, 0))).await.unwrap();
+        assert_eq!(saved.get_last_processed_id(), Some(Id::new(4, 0)));
+
+        // Load the checkpoint in a new consumer:
+        let restarted: Consumer = consumer_with(store);
+        let loaded: Option<Checkpoint> = restarted.load_checkpoint().await.unwrap();
 
         // Verify the result:
-        assert_eq!(options.get_count(), count);
-        assert_eq!(options.get_block(), block);
+        assert_eq!(loaded, Some(saved));
+        assert_eq!(restarted.get_latest_pending_message_id(), "5-0");
+        assert_eq!(restarted.get_next_id_to_claim(), "3-0");
     }
-}
 
-#[cfg(test)]
-mod test_read_pending_messages_options {
-    use super::BEGINNING_OF_TIME_ID;
-    use crate::prelude::*;
+    #[tokio::test]
+    async fn test_consumer_checkpoint_not_found() {
+        // Create a consumer with an empty checkpoint store:
+        let consumer: Consumer = consumer_with(Arc::default());
 
-    #[test]
-    fn test_new_read_pending_messages_options() {
-        // Define count:
-        let count: usize = 10;
+        // Verify the result:
+        assert!(consumer.load_checkpoint().await.unwrap().is_none());
+        assert_eq!(consumer.get_latest_pending_message_id(), "0-0");
+    }
 
-        // Create new ReadPendingMessagesOptions instance:
-        let options: ReadPendingMessagesOptions = ReadPendingMessagesOptions::new(count);
+    #[tokio::test]
+    async fn test_consumer_checkpoint_without_store() {
+        // Create a consumer without checkpoint store:
+        let consumer: Consumer = consumer();
 
         // Verify the result:
-        assert_eq!(options.get_count(), count);
         assert_eq!(
-            options.get_latest_pending_message_id(),
-            BEGINNING_OF_TIME_ID
+            consumer.save_checkpoint(None).await.unwrap_err().kind(),
+            ErrorKind::ClientError
         );
+        assert!(consumer.load_checkpoint().await.is_err());
     }
 }
 
 #[cfg(test)]
-mod test_claim_messages_options {
-    use super::BEGINNING_OF_TIME_ID;
+mod test_consumer_snapshot {
     use crate::prelude::*;
 
+    /// Build a consumer without connecting to the Redis server.
+    fn consumer() -> Consumer {
+        Consumer::unconnected(
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+        )
+    }
+
     #[test]
-    fn test_new_claim_messages_options() {
-        // Define count and min idle time:
-        let count: usize = 10;
-        let min_idle_time: usize = 1000;
+    fn test_consumer_snapshot_and_restore() {
+        // Create a consumer and move its cursors:
+        let running: Consumer = consumer();
+        running.update_latest_pending_message_id("5-0");
+        running.update_next_id_to_claim("3-1");
 
-        // Create new ClaimMessagesOptions instance:
-        let options: ClaimMessagesOptions = ClaimMessagesOptions::new(count, min_idle_time);
+        // Take a snapshot and serialize it:
+        let snapshot: ConsumerSnapshot = running.snapshot();
+        let serialized: String = serde_json::to_string(&snapshot).unwrap();
+
+        // Restore the snapshot in a new consumer:
+        let restarted: Consumer = consumer();
+        let deserialized: ConsumerSnapshot = serde_json::from_str(&serialized).unwrap();
+        restarted.restore(&deserialized).unwrap();
 
         // Verify the result:
-        assert_eq!(options.get_count(), count);
-        assert_eq!(options.get_min_idle_time(), min_idle_time);
-        assert_eq!(options.get_next_id_to_claim(), BEGINNING_OF_TIME_ID);
+        assert_eq!(snapshot, ConsumerSnapshot::new("5-0", "3-1"));
+        assert_eq!(restarted.get_latest_pending_message_id(), "5-0");
+        assert_eq!(restarted.get_next_id_to_claim(), "3-1");
+    }
+
+    #[test]
+    fn test_consumer_restore_invalid_snapshot() {
+        // Create a consumer:
+        let consumer: Consumer = consumer();
+
+        // Restore an invalid snapshot:
+        let result: RedsumerResult<()> = consumer.restore(&ConsumerSnapshot::new("5-0", "invalid"));
+
+        // Verify the result:
+        assert!(result.is_err());
+        assert_eq!(consumer.get_latest_pending_message_id(), "0-0");
     }
 }
 
 #[cfg(test)]
-mod test_consumer_config {
-    use super::BEGINNING_OF_TIME_ID;
+mod test_consumer_claim_mode {
+    use std::sync::Arc;
+
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
     use crate::prelude::*;
 
+    /// Build a consumer on a mock connection with the given *claim_mode* and the *commands* expected after its creation.
+    fn consumer(claim_mode: ClaimMode, commands: Vec<MockCmd>) -> Consumer<MockRedisConnection> {
+        let mut expected: Vec<MockCmd> = vec![
+            MockCmd::new(cmd("EXISTS").arg("stream"), Ok(1)),
+            MockCmd::new(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg("stream")
+                    .arg("group")
+                    .arg("0-0"),
+                Ok("OK"),
+            ),
+        ];
+        expected.extend(commands);
+
+        Consumer::from_client(
+            Arc::new(MockRedisConnection::new(expected)),
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .claim_mode(claim_mode)
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
+        )
+        .unwrap()
+    }
+
+    /// Build the `INFO server` reply of a server with the given *version*.
+    fn info(version: &str) -> MockCmd {
+        MockCmd::new::<_, Value>(
+            cmd("INFO").arg("server"),
+            Ok(Value::BulkString(
+                format!("# Server\r\nredis_version:{version}\r\n").into_bytes(),
+            )),
+        )
+    }
+
     #[test]
-    fn test_new_consumer_config() {
-        // Define stream name, group name and consumer name:
-        let stream_name: &str = "stream";
-        let group_name: &str = "group";
-        let consumer_name: &str = "consumer";
+    fn test_claim_mode_explicit() {
+        // Build consumers with an explicit claim mode on a server of unknown version:
+        let auto_claim: Consumer<MockRedisConnection> = consumer(ClaimMode::AutoClaim, vec![]);
+        let compatibility: Consumer<MockRedisConnection> =
+            consumer(ClaimMode::Compatibility, vec![info("7.2.4")]);
 
-        // Define count, block, min idle time and initial stream id:
-        let count: usize = 10;
-        let block: usize = 3;
-        let min_idle_time: usize = 1000;
+        // Verify the result:
+        assert!(!auto_claim.uses_compatibility_claim());
+        assert!(compatibility.uses_compatibility_claim());
+    }
 
-        // Create new ReadNewMessagesOptions instance:
-        let read_new_messages_options: ReadNewMessagesOptions =
-            ReadNewMessagesOptions::new(count, block);
+    #[test]
+    fn test_claim_mode_auto_detects_old_server() {
+        // Build a consumer connected to Redis 6.0:
+        let consumer: Consumer<MockRedisConnection> =
+            consumer(ClaimMode::Auto, vec![info("6.0.9")]);
 
-        // Create new ReadPendingMessagesOptions instance:
-        let read_pending_messages_options: ReadPendingMessagesOptions =
-            ReadPendingMessagesOptions::new(count);
+        // Verify the server version is detected once and shared between clones:
+        assert!(consumer.uses_compatibility_claim());
+        assert!(consumer.clone().uses_compatibility_claim());
+    }
 
-        // Create new ClaimMessagesOptions instance:
-        let claim_messages_options: ClaimMessagesOptions =
-            ClaimMessagesOptions::new(count, min_idle_time);
+    #[test]
+    fn test_claim_mode_auto_claim_unsupported() {
+        // Define a mock connection to Redis 6.0:
+        let connection: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new(cmd("EXISTS").arg("stream"), Ok(1)),
+            MockCmd::new(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg("stream")
+                    .arg("group")
+                    .arg("0-0"),
+                Ok("OK"),
+            ),
+            info("6.0.9"),
+        ]);
 
-        // Create new ConsumerConfig instance:
-        let config: ConsumerConfig = ConsumerConfig::new(
-            stream_name,
-            group_name,
-            consumer_name,
-            read_new_messages_options,
-            read_pending_messages_options,
-            claim_messages_options,
+        // Create a consumer that requires XAUTOCLAIM:
+        let result: RedsumerResult<Consumer<MockRedisConnection>> = Consumer::from_client(
+            Arc::new(connection),
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .claim_mode(ClaimMode::AutoClaim)
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
         );
 
         // Verify the result:
-        assert_eq!(config.get_stream_name(), stream_name);
-        assert_eq!(config.get_group_name(), group_name);
-        assert_eq!(config.get_consumer_name(), consumer_name);
+        assert_eq!(result.err().map(|e| e.kind()), Some(ErrorKind::ClientError));
+    }
 
-        assert_eq!(config.get_read_new_messages_options().get_count(), count);
-        assert_eq!(config.get_read_new_messages_options().get_block(), block);
+    #[tokio::test]
+    async fn test_get_lag_unsupported() {
+        // Build a consumer connected to Redis 6.2:
+        let consumer: Consumer<MockRedisConnection> =
+            consumer(ClaimMode::Auto, vec![info("6.2.14")]);
 
+        // Verify the lag is rejected without querying the server:
         assert_eq!(
-            config.get_read_pending_messages_options().get_count(),
-            count
-        );
-        assert_eq!(
-            config
-                .get_read_pending_messages_options()
-                .get_latest_pending_message_id(),
-            BEGINNING_OF_TIME_ID
+            consumer.get_lag().await.unwrap_err().kind(),
+            ErrorKind::ClientError
         );
+    }
 
-        assert_eq!(config.get_claim_messages_options().get_count(), count);
-        assert_eq!(
-            config.get_claim_messages_options().get_min_idle_time(),
-            min_idle_time
-        );
-        assert_eq!(
-            config.get_claim_messages_options().get_next_id_to_claim(),
-            BEGINNING_OF_TIME_ID
-        );
+    #[test]
+    fn test_claim_mode_auto_detection_error() {
+        // Build a consumer whose server does not reply to INFO:
+        let consumer: Consumer<MockRedisConnection> = consumer(ClaimMode::Auto, vec![]);
+
+        // Verify XAUTOCLAIM is used:
+        assert!(!consumer.uses_compatibility_claim());
     }
 }
 
 #[cfg(test)]
-mod test_messages_kind {
-    use super::MessagesKind;
+mod test_consumer_consume_with_timeout {
+    use std::{
+        sync::Arc,
+        thread,
+        time::{Duration, Instant},
+    };
 
-    #[test]
-    fn test_messages_kind() {
-        // Create new MessagesKind instances:
-        let new_messages: MessagesKind = MessagesKind::New;
-        let pending_messages: MessagesKind = MessagesKind::Pending;
-        let claimed_messages: MessagesKind = MessagesKind::Claimed;
-        let not_found_messages: MessagesKind = MessagesKind::NotFound;
+    use redis::{
+        cmd,
+        streams::{StreamId, StreamReadOptions},
+        ConnectionLike, RedisResult, Value,
+    };
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use crate::prelude::*;
+
+    /// An interceptor that filters out all the consumed messages.
+    #[derive(Debug)]
+    struct DropMessages;
+
+    impl Interceptor for DropMessages {
+        fn after_consume(
+            &self,
+            _stream: &str,
+            _group: &str,
+            _kind: &MessagesKind,
+            messages: &mut Vec<StreamId>,
+        ) -> RedsumerResult<()> {
+            messages.clear();
+            Ok(())
+        }
+    }
+
+    /// A mock connection that waits before replying every command.
+    #[derive(Clone)]
+    struct SlowConnection {
+        inner: MockRedisConnection,
+        delay: Duration,
+    }
+
+    impl ConnectionLike for SlowConnection {
+        fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
+            thread::sleep(self.delay);
+            self.inner.req_packed_command(cmd)
+        }
+
+        fn req_packed_commands(
+            &mut self,
+            cmd: &[u8],
+            offset: usize,
+            count: usize,
+        ) -> RedisResult<Vec<Value>> {
+            thread::sleep(self.delay);
+            self.inner.req_packed_commands(cmd, offset, count)
+        }
+
+        fn get_db(&self) -> i64 {
+            self.inner.get_db()
+        }
+
+        fn check_connection(&mut self) -> bool {
+            true
+        }
+
+        fn is_open(&self) -> bool {
+            true
+        }
+    }
+
+    /// Build a consumer on a connection that waits *delay* before replying the given *commands*, which are expected after the consumer creation.
+    fn consumer(delay: Duration, commands: Vec<MockCmd>) -> Consumer<SlowConnection> {
+        let connection: SlowConnection = SlowConnection {
+            inner: MockRedisConnection::new(
+                [
+                    MockCmd::new(cmd("EXISTS").arg("stream"), Ok(1)),
+                    MockCmd::new(
+                        cmd("XGROUP")
+                            .arg("CREATE")
+                            .arg("stream")
+                            .arg("group")
+                            .arg("0-0"),
+                        Ok("OK"),
+                    ),
+                ]
+                .into_iter()
+                .chain(commands),
+            ),
+            delay: Duration::ZERO,
+        };
+
+        let mut consumer: Consumer<SlowConnection> = Consumer::from_client(
+            Arc::new(connection),
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .claim_mode(ClaimMode::AutoClaim)
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
+        )
+        .unwrap();
+
+        Arc::get_mut(&mut consumer.client).unwrap().delay = delay;
+        consumer
+    }
+
+    /// Build the `INFO` mock command sent when the consumer is created.
+    fn info() -> MockCmd {
+        MockCmd::new::<_, Value>(
+            cmd("INFO").arg("server"),
+            Ok(Value::BulkString(
+                b"# Server\r\nredis_version:7.2.4\r\n".to_vec(),
+            )),
+        )
+    }
+
+    /// Build the `XREADGROUP` mock command that reads the new message `1-0`.
+    fn read_new_message() -> MockCmd {
+        MockCmd::new::<_, Value>(
+            cmd("XREADGROUP")
+                .arg(
+                    StreamReadOptions::default()
+                        .group("group", "consumer")
+                        .count(DEFAULT_MESSAGES_COUNT)
+                        .block(DEFAULT_BLOCK.as_millis() as usize),
+                )
+                .arg("STREAMS")
+                .arg(&["stream"])
+                .arg(&[">"]),
+            Ok(Value::Array(vec![Value::Map(vec![(
+                Value::SimpleString("stream".to_string()),
+                Value::Array(vec![Value::Map(vec![(
+                    Value::SimpleString("1-0".to_string()),
+                    Value::Array(vec![Value::SimpleString("code".to_string()), Value::Int(1)]),
+                )])]),
+            )])])),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_consume_with_timeout_exceeded() {
+        // Build a consumer whose server replies after the timeout:
+        let mut consumer: Consumer<SlowConnection> =
+            consumer(Duration::from_millis(500), Vec::new());
+
+        // Consume messages with a timeout:
+        let reply: ConsumeMessagesReply = consumer
+            .consume_with_timeout(Duration::from_millis(20))
+            .await
+            .unwrap();
 
         // Verify the result:
-        assert!(new_messages.are_new());
-        assert!(!new_messages.are_pending());
-        assert!(!new_messages.were_claimed());
-        assert!(!new_messages.not_found());
+        assert!(reply.not_found());
+    }
 
-        assert!(!pending_messages.are_new());
-        assert!(pending_messages.are_pending());
-        assert!(!pending_messages.were_claimed());
-        assert!(!pending_messages.not_found());
+    #[tokio::test]
+    async fn test_consume_with_timeout_not_exceeded() {
+        // Build a paused consumer, which replies without reading from the stream:
+        let mut consumer: Consumer<SlowConnection> =
+            consumer(Duration::from_millis(500), Vec::new());
+        consumer.pause();
 
-        assert!(!claimed_messages.are_new());
-        assert!(!claimed_messages.are_pending());
-        assert!(claimed_messages.were_claimed());
-        assert!(!claimed_messages.not_found());
+        // Consume messages with a timeout:
+        let reply: ConsumeMessagesReply = consumer
+            .consume_with_timeout(Duration::from_secs(5))
+            .await
+            .unwrap();
 
-        assert!(!not_found_messages.are_new());
-        assert!(!not_found_messages.are_pending());
-        assert!(!not_found_messages.were_claimed());
-        assert!(not_found_messages.not_found());
+        // Verify the result:
+        assert!(reply.not_found());
+        assert!(consumer.is_paused());
     }
-}
 
-#[cfg(test)]
-mod test_consume_messages_reply {
-    use super::MessagesKind;
-    use crate::prelude::*;
+    #[tokio::test]
+    async fn test_consume_resumes_cancelled_cycle() {
+        // Build a consumer whose server replies a new message after the timeout, only once:
+        let mut consumer: Consumer<SlowConnection> =
+            consumer(Duration::from_millis(200), vec![info(), read_new_message()]);
 
-    #[test]
-    fn test_consume_messages_reply() {
-        // Define messages and kind:
-        let messages: Vec<StreamId> = vec![StreamId::default()];
-        let kind: MessagesKind = MessagesKind::New;
+        // Cancel a consume cycle with a timeout, and consume again:
+        let cancelled: ConsumeMessagesReply = consumer
+            .consume_with_timeout(Duration::from_millis(20))
+            .await
+            .unwrap();
+        let resumed: ConsumeMessagesReply = consumer.consume().await.unwrap();
 
-        // Create new ConsumeMessagesReply instance:
-        let reply: ConsumeMessagesReply = ConsumeMessagesReply::from((messages, kind));
+        // Verify the result, the message read by the cancelled cycle is returned by the next one:
+        assert!(cancelled.not_found());
+        assert!(resumed.are_new());
+        assert_eq!(resumed.get_messages().len(), 1);
+        assert_eq!(resumed.get_messages()[0].id, "1-0");
+        assert_eq!(consumer.stats().get_new_messages(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_consume_resumes_cancelled_cycle_keeps_changes() {
+        // Build a consumer whose server replies a new message after the timeout, only once:
+        let mut consumer: Consumer<SlowConnection> =
+            consumer(Duration::from_millis(200), vec![info(), read_new_message()]);
+
+        // Cancel a consume cycle with a timeout, change the consumer and consume again:
+        let cancelled: ConsumeMessagesReply = consumer
+            .consume_with_timeout(Duration::from_millis(20))
+            .await
+            .unwrap();
+        consumer = consumer
+            .with_interceptor(Arc::new(DropMessages))
+            .with_max_in_flight(5);
+        let resumed: ConsumeMessagesReply = consumer.consume().await.unwrap();
+
+        // Verify the result, the changes made while the read was running are kept and applied to its messages:
+        assert!(cancelled.not_found());
+        assert!(resumed.get_messages().is_empty());
+        assert_eq!(consumer.interceptors.len(), 1);
+        assert_eq!(consumer.max_in_flight, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_consume_until_messages_found() {
+        // Build a consumer whose server replies a new message:
+        let mut consumer: Consumer<SlowConnection> =
+            consumer(Duration::ZERO, vec![info(), read_new_message()]);
+
+        // Consume until a message is found:
+        let reply: ConsumeMessagesReply = consumer
+            .consume_until(Instant::now() + Duration::from_secs(5))
+            .await
+            .unwrap();
 
         // Verify the result:
-        assert!(reply.get_messages().len().eq(&1));
         assert!(reply.are_new());
-        assert!(!reply.are_pending());
-        assert!(!reply.were_claimed());
-        assert!(!reply.not_found());
+        assert_eq!(reply.get_messages()[0].id, "1-0");
+        assert_eq!(
+            reply.get_ack_tokens(),
+            &[AckToken::new(Id::new(1, 0), "stream", "group", "consumer")]
+        );
     }
 }
 
 #[cfg(test)]
-mod test_is_still_mine_reply {
+mod test_consumer_handle_messages {
+    use std::{collections::HashMap, sync::Arc};
+
+    use redis::{cmd, pipe, streams::StreamId, Pipeline, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+    use serde::Deserialize;
+
     use crate::prelude::*;
 
-    #[test]
-    fn test_is_still_mine_reply() {
-        // Define is still mine, last delivered milliseconds and total times delivered:
-        let is_still_mine: bool = true;
-        let last_delivered_milliseconds: Option<LastDeliveredMilliseconds> = Some(1000);
-        let total_times_delivered: Option<TotalTimesDelivered> = Some(787);
+    #[derive(Debug, Deserialize)]
+    struct Reading {
+        value: i64,
+    }
 
-        // Create new IsStillMineReply instance:
-        let reply: IsStillMineReply = IsStillMineReply::from((
-            is_still_mine,
-            last_delivered_milliseconds,
-            total_times_delivered,
-        ));
+    /// A handler that acks the readings with a positive value and retries the others.
+    struct PositiveReadings;
 
-        // Verify the result:
-        assert!(reply.belongs_to_me());
+    impl MessageHandler<Reading> for PositiveReadings {
+        async fn handle(&self, msg: Reading, meta: &MessageMeta) -> HandlerOutcome {
+            assert_eq!(meta.get_stream_name(), "stream");
+            assert_eq!(meta.get_total_times_delivered(), Some(1));
+            match msg.value.gt(&0) {
+                true => HandlerOutcome::Ack,
+                false => HandlerOutcome::Retry,
+            }
+        }
+    }
 
-        assert!(reply.get_last_delivered_milliseconds().is_some());
-        assert!(reply
-            .get_last_delivered_milliseconds()
-            .eq(&last_delivered_milliseconds));
+    /// Build a message with the given *value* field.
+    fn message(id: &str, value: &str) -> StreamId {
+        StreamId {
+            id: id.to_string(),
+            map: HashMap::from([(
+                "value".to_string(),
+                Value::BulkString(value.as_bytes().to_vec()),
+            )]),
+        }
+    }
 
-        assert!(reply.get_total_times_delivered().is_some());
-        assert!(reply.get_total_times_delivered().eq(&total_times_delivered));
+    /// Build a `XPENDING` reply with a single entry owned by *consumer*.
+    fn entry(id: &str, consumer: &str) -> Value {
+        Value::Array(vec![Value::Array(vec![
+            Value::BulkString(id.as_bytes().to_vec()),
+            Value::BulkString(consumer.as_bytes().to_vec()),
+            Value::Int(1000),
+            Value::Int(1),
+        ])])
     }
-}
 
-#[cfg(test)]
-mod test_ack_message_reply {
-    use crate::prelude::*;
+    #[tokio::test]
+    async fn test_consumer_handle_messages() {
+        // Define a mock connection:
+        let mut pipeline: Pipeline = pipe();
+        for id in ["1-0", "2-0", "3-0"] {
+            pipeline.add_command(
+                cmd("XPENDING")
+                    .arg("stream")
+                    .arg("group")
+                    .arg(id)
+                    .arg(id)
+                    .arg(1)
+                    .arg("consumer")
+                    .to_owned(),
+            );
+        }
+        let connection: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new(cmd("EXISTS").arg("stream"), Ok(1)),
+            MockCmd::new(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg("stream")
+                    .arg("group")
+                    .arg("0-0"),
+                Ok("OK"),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Ok(Value::BulkString(
+                    b"# Server\r\nredis_version:7.2.4\r\n".to_vec(),
+                )),
+            ),
+            MockCmd::with_values(
+                pipeline,
+                Ok(vec![
+                    entry("1-0", "consumer"),
+                    entry("2-0", "consumer"),
+                    entry("3-0", "other"),
+                ]),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XACK").arg("stream").arg("group").arg("1-0"),
+                Ok(Value::Int(1)),
+            ),
+        ]);
 
-    #[test]
-    fn test_ack_message_reply() {
-        // Define was acked:
-        let was_acked: bool = true;
+        // Create a new consumer on the mock connection:
+        let consumer: Consumer<MockRedisConnection> = Consumer::from_client(
+            Arc::new(connection),
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
+        )
+        .unwrap();
 
-        // Create new AckMessageReply instance:
-        let reply: AckMessageReply = AckMessageReply::from(was_acked);
+        // Define a reply with messages:
+        let mut reply: ConsumeMessagesReply = ConsumeMessagesReply::from((
+            vec![
+                message("1-0", "10"),
+                message("2-0", "-5"),
+                message("3-0", "7"),
+                message("4-0", "high"),
+            ],
+            MessagesKind::New,
+        ));
+        reply.issue_ack_tokens("stream", "group", "consumer");
+
+        // Handle the messages:
+        let handled: HandleMessagesReply = consumer
+            .handle_messages(&reply, &PositiveReadings)
+            .await
+            .unwrap();
 
         // Verify the result:
-        assert!(reply.was_acked());
+        assert_eq!(handled.get_acked(), &[Id::new(1, 0)]);
+        assert_eq!(handled.get_retried(), &[Id::new(2, 0)]);
+        assert_eq!(handled.get_not_owned(), &[Id::new(3, 0)]);
+        assert_eq!(handled.get_undecodable(), &[Id::new(4, 0)]);
+        assert_eq!(consumer.stats().get_acks(), 1);
     }
 }
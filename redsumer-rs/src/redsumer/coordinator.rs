@@ -0,0 +1,358 @@
+use std::time::Duration;
+
+use redis::Client;
+use time::OffsetDateTime;
+use tracing::{debug, info};
+
+#[allow(unused_imports)]
+use crate::core::{
+    client::{ClientArgs, RedisClientBuilder},
+    connection::VerifyConnection,
+    coordination::CoordinationCommands,
+    result::{RedsumerError, RedsumerResult},
+};
+
+/// Default time to live of the coordinator membership and leases.
+pub const DEFAULT_LEASE_TTL: Duration = Duration::from_secs(30);
+
+/// Prefix of the keys where the coordinator state is stored.
+const COORDINATOR_KEY_PREFIX: &str = "redsumer:coordinator";
+
+/// Coordinator configuration parameters.
+#[derive(Debug, Clone)]
+pub struct CoordinatorConfig {
+    /// Name of the coordination domain. Instances with the same name share the shards.
+    name: String,
+
+    /// Unique ID of this instance, for example the pod name.
+    instance_id: String,
+
+    /// Shards to assign between instances, for example the names of sharded streams or claim ranges.
+    shards: Vec<String>,
+
+    /// Time to live of the membership and leases of this instance.
+    lease_ttl: Duration,
+}
+
+impl CoordinatorConfig {
+    /// Get **name**.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get **instance id**.
+    pub fn get_instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// Get **shards**.
+    pub fn get_shards(&self) -> &[String] {
+        &self.shards
+    }
+
+    /// Get **lease ttl**.
+    pub fn get_lease_ttl(&self) -> Duration {
+        self.lease_ttl
+    }
+
+    /// Create a new [`CoordinatorConfig`] instance with a lease time to live of [`DEFAULT_LEASE_TTL`].
+    ///
+    /// # Arguments:
+    /// - **name**: Name of the coordination domain. Instances with the same name share the shards.
+    /// - **instance_id**: Unique ID of this instance. In a microservices architecture, for example, it is recommended to use the pod name.
+    /// - **shards**: Shards to assign between instances. All the instances must be configured with the same shards.
+    ///
+    /// # Returns:
+    /// A new [`CoordinatorConfig`] instance.
+    pub fn new(name: &str, instance_id: &str, shards: &[&str]) -> Self {
+        CoordinatorConfig {
+            name: name.to_owned(),
+            instance_id: instance_id.to_owned(),
+            shards: shards.iter().map(|s| s.to_string()).collect(),
+            lease_ttl: DEFAULT_LEASE_TTL,
+        }
+    }
+
+    /// Set the time to live of the membership and leases. It must be greater than the interval between rebalances.
+    pub fn lease_ttl(mut self, lease_ttl: Duration) -> Self {
+        self.lease_ttl = lease_ttl;
+        self
+    }
+}
+
+/// The result of a rebalance of a [`Coordinator`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Assignment {
+    /// Shards owned by this instance after the rebalance.
+    shards: Vec<String>,
+
+    /// Live instances of the coordination domain.
+    members: Vec<String>,
+
+    /// Shards acquired in the rebalance.
+    acquired: Vec<String>,
+
+    /// Shards released in the rebalance.
+    released: Vec<String>,
+}
+
+impl Assignment {
+    /// Get **shards** owned by this instance.
+    pub fn get_shards(&self) -> &[String] {
+        &self.shards
+    }
+
+    /// Get live **members**, sorted by instance ID.
+    pub fn get_members(&self) -> &[String] {
+        &self.members
+    }
+
+    /// Get **acquired** shards.
+    pub fn get_acquired(&self) -> &[String] {
+        &self.acquired
+    }
+
+    /// Get **released** shards.
+    pub fn get_released(&self) -> &[String] {
+        &self.released
+    }
+
+    /// Verify if the assignment of this instance changed in the rebalance.
+    pub fn has_changed(&self) -> bool {
+        !self.acquired.is_empty() || !self.released.is_empty()
+    }
+}
+
+/// Get the shards assigned to *member*, distributing them in round-robin between the sorted *members*.
+fn assign_shards(shards: &[String], members: &[String], member: &str) -> Vec<String> {
+    match members.iter().position(|m| m.eq(member)) {
+        Some(index) => shards
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (i % members.len()).eq(&index))
+            .map(|(_, shard)| shard.to_owned())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// A coordinator that assigns shards to the live instances of a deployment and rebalances them when instances join or leave, like partition assignment in Kafka consumer groups.
+///
+/// Instances register themselves in a membership set stored in Redis, which expires after the lease time to live unless it is renewed. On every [`rebalance`](Coordinator::rebalance), shards are distributed between the live instances and each instance holds a lease per owned shard, so a shard is never owned by two instances at the same time: a new owner only acquires a shard once its previous owner releases it or its lease expires.
+///
+/// [`rebalance`](Coordinator::rebalance) must be called periodically, with an interval lower than the lease time to live, and only the shards in the returned [`Assignment`] must be processed.
+#[derive(Debug, Clone)]
+pub struct Coordinator {
+    /// Redis client to interact with Redis server.
+    client: Client,
+
+    /// Coordinator configuration parameters.
+    config: CoordinatorConfig,
+
+    /// Shards owned by this instance.
+    owned_shards: Vec<String>,
+}
+
+impl Coordinator {
+    /// Get [`Client`].
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get *config*.
+    pub fn get_config(&self) -> &CoordinatorConfig {
+        &self.config
+    }
+
+    /// Get the shards owned by this instance since the latest rebalance.
+    pub fn get_owned_shards(&self) -> &[String] {
+        &self.owned_shards
+    }
+
+    /// Get the key of the membership set.
+    fn get_members_key(&self) -> String {
+        format!(
+            "{COORDINATOR_KEY_PREFIX}:{}:members",
+            self.get_config().get_name()
+        )
+    }
+
+    /// Get the key of the lease of a shard.
+    fn get_lease_key(&self, shard: &str) -> String {
+        format!(
+            "{COORDINATOR_KEY_PREFIX}:{}:lease:{shard}",
+            self.get_config().get_name()
+        )
+    }
+
+    /// Build a new [`Coordinator`] instance.
+    ///
+    /// Before creating a new coordinator, the following validations are performed:
+    ///
+    /// - If connection string is invalid, a [`RedsumerError`] is returned.
+    /// - If connection to Redis server can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// The instance does not join the coordination domain until the first [`rebalance`](Coordinator::rebalance).
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance. If a namespace is set, the name of the coordination domain is prefixed with it.
+    /// - **config**: Coordinator configuration parameters.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`Coordinator`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs, mut config: CoordinatorConfig) -> RedsumerResult<Coordinator> {
+        debug!(
+            "Creating a new coordinator instance by: {:?} and {:?}",
+            args, config
+        );
+
+        config.name = args.get_key(config.get_name());
+
+        let mut client: Client = args.build()?;
+        client.ping()?;
+
+        info!("Coordinator instance created successfully and it is ready to be used");
+
+        Ok(Coordinator {
+            client,
+            config,
+            owned_shards: Vec::new(),
+        })
+    }
+
+    /// Renew the membership of this instance and rebalance the shards between the live instances.
+    ///
+    /// Shards that are no longer assigned to this instance are released, and the assigned ones are acquired or renewed. A shard assigned to this instance that is still leased by another one is acquired in a later rebalance.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the [`Assignment`] of this instance. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn rebalance(&mut self) -> RedsumerResult<Assignment> {
+        let mut client: Client = self.get_client().to_owned();
+        let instance_id: &str = self.get_config().get_instance_id();
+        let lease_ttl: Duration = self.get_config().get_lease_ttl();
+
+        let now: i64 = (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64;
+        let mut members: Vec<String> = client.renew_membership(
+            self.get_members_key(),
+            instance_id,
+            now,
+            now + lease_ttl.as_millis() as i64,
+        )?;
+        members.sort();
+
+        let assigned: Vec<String> =
+            assign_shards(self.get_config().get_shards(), &members, instance_id);
+
+        let mut released: Vec<String> = Vec::new();
+        for shard in self.get_owned_shards() {
+            if !assigned.contains(shard) {
+                client.release_lease(self.get_lease_key(shard), instance_id)?;
+                released.push(shard.to_owned());
+            }
+        }
+
+        let mut shards: Vec<String> = Vec::new();
+        let mut acquired: Vec<String> = Vec::new();
+        for shard in assigned {
+            if client.acquire_lease(self.get_lease_key(&shard), instance_id, lease_ttl)? {
+                if !self.get_owned_shards().contains(&shard) {
+                    acquired.push(shard.to_owned());
+                }
+                shards.push(shard);
+            } else {
+                debug!("Shard {shard} is still leased by another instance");
+            }
+        }
+
+        if !acquired.is_empty() || !released.is_empty() {
+            info!("Shards rebalanced, acquired: {acquired:?}, released: {released:?}");
+        }
+
+        self.owned_shards = shards.to_owned();
+
+        Ok(Assignment {
+            shards,
+            members,
+            acquired,
+            released,
+        })
+    }
+
+    /// Leave the coordination domain, releasing all the shards owned by this instance, so they are rebalanced to the remaining instances without waiting for the leases to expire.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] with no value if successful. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn leave(&mut self) -> RedsumerResult<()> {
+        let mut client: Client = self.get_client().to_owned();
+        let instance_id: &str = self.get_config().get_instance_id();
+
+        for shard in self.get_owned_shards() {
+            client.release_lease(self.get_lease_key(shard), instance_id)?;
+        }
+        client.leave_membership(self.get_members_key(), instance_id)?;
+
+        info!("Instance {instance_id} left the coordination domain");
+
+        self.owned_shards.clear();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_assign_shards {
+    use super::*;
+
+    /// Build a list of owned strings.
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_assign_shards_between_members() {
+        // Define the shards and members:
+        let shards: Vec<String> = strings(&["s0", "s1", "s2", "s3", "s4"]);
+        let members: Vec<String> = strings(&["a", "b"]);
+
+        // Verify the result:
+        assert_eq!(
+            assign_shards(&shards, &members, "a"),
+            strings(&["s0", "s2", "s4"])
+        );
+        assert_eq!(
+            assign_shards(&shards, &members, "b"),
+            strings(&["s1", "s3"])
+        );
+    }
+
+    #[test]
+    fn test_assign_shards_to_unknown_member() {
+        // Define the shards and members:
+        let shards: Vec<String> = strings(&["s0", "s1"]);
+        let members: Vec<String> = strings(&["a"]);
+
+        // Verify the result:
+        assert!(assign_shards(&shards, &members, "b").is_empty());
+        assert_eq!(assign_shards(&shards, &members, "a"), shards);
+    }
+
+    #[test]
+    fn test_coordinator_config() {
+        // Create a new config:
+        let config: CoordinatorConfig =
+            CoordinatorConfig::new("orders", "pod-1", &["orders-0", "orders-1"])
+                .lease_ttl(Duration::from_secs(10));
+
+        // Verify the result:
+        assert_eq!(config.get_name(), "orders");
+        assert_eq!(config.get_instance_id(), "pod-1");
+        assert_eq!(config.get_shards(), strings(&["orders-0", "orders-1"]));
+        assert_eq!(config.get_lease_ttl(), Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use redis::{from_redis_value, streams::StreamId};
+use serde::{Deserialize, Serialize};
+
+#[allow(unused_imports)]
+use crate::core::{
+    result::{RedsumerError, RedsumerResult},
+    streams::types::Id,
+};
+
+/// A stream message in a JSON Lines dump, where every line is a JSON document like `{"id":"1526919030474-55","fields":{"field":"value"}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DumpedMessage {
+    /// Original *ID* of the message.
+    id: Id,
+
+    /// Fields and values of the message.
+    fields: BTreeMap<String, String>,
+}
+
+impl DumpedMessage {
+    /// Get original *ID* of the message.
+    pub fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    /// Get **fields** of the message.
+    pub fn get_fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    /// Create a new [`DumpedMessage`] instance.
+    ///
+    /// # Arguments:
+    /// - **id**: Original *ID* of the message.
+    /// - **fields**: Fields and values of the message.
+    ///
+    /// # Returns:
+    /// A new [`DumpedMessage`] instance.
+    pub fn new(id: Id, fields: BTreeMap<String, String>) -> Self {
+        DumpedMessage { id, fields }
+    }
+}
+
+/// Convert a [`StreamId`] to a [`DumpedMessage`]. If a field value is not a valid UTF-8 string, a [`RedsumerError`] is returned.
+impl TryFrom<&StreamId> for DumpedMessage {
+    type Error = RedsumerError;
+
+    fn try_from(message: &StreamId) -> RedsumerResult<Self> {
+        Ok(DumpedMessage {
+            id: message.id.parse()?,
+            fields: message
+                .map
+                .iter()
+                .map(|(field, value)| Ok((field.to_owned(), from_redis_value::<String>(value)?)))
+                .collect::<RedsumerResult<_>>()?,
+        })
+    }
+}
+
+/// Mode to set the *ID* of the messages imported from a dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportIdMode {
+    /// Messages are produced with their original *ID*. The target stream must be empty or its latest *ID* must be lower than the first imported one.
+    Preserve,
+
+    /// Messages are produced with new *IDs* generated by Redis, keeping their original order.
+    #[default]
+    Remap,
+}
+
+/// Summary of the messages imported from a dump.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Number of messages imported.
+    imported: usize,
+
+    /// *ID* of the latest message produced in the target stream.
+    last_id: Option<Id>,
+}
+
+impl ImportSummary {
+    /// Get the number of **imported** messages.
+    pub fn get_imported(&self) -> usize {
+        self.imported
+    }
+
+    /// Get the **last id** produced in the target stream. It is `None` if no message was imported.
+    pub fn get_last_id(&self) -> Option<&Id> {
+        self.last_id.as_ref()
+    }
+
+    /// Register a message produced with *id*.
+    pub(crate) fn register(&mut self, id: Id) {
+        self.imported += 1;
+        self.last_id = Some(id);
+    }
+}
+
+#[cfg(test)]
+mod test_dumped_message {
+    use redis::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_dumped_message_serde() {
+        // Define the message:
+        let mut fields: BTreeMap<String, String> = BTreeMap::new();
+        fields.insert("field".to_string(), "value".to_string());
+        let message: DumpedMessage = DumpedMessage::new(Id::new(1526919030474, 55), fields);
+
+        // Serialize the message:
+        let line: String = serde_json::to_string(&message).unwrap();
+
+        // Verify the result:
+        assert_eq!(
+            line,
+            r#"{"id":"1526919030474-55","fields":{"field":"value"}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<DumpedMessage>(&line).unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn test_dumped_message_from_stream_id() {
+        // Define the stream message:
+        let mut message: StreamId = StreamId {
+            id: "1-2".to_string(),
+            ..Default::default()
+        };
+        message
+            .map
+            .insert("field".to_string(), Value::BulkString(b"value".to_vec()));
+
+        // Convert the message:
+        let dumped: DumpedMessage = DumpedMessage::try_from(&message).unwrap();
+
+        // Verify the result:
+        assert_eq!(dumped.get_id(), &Id::new(1, 2));
+        assert_eq!(dumped.get_fields().get("field").unwrap(), "value");
+    }
+
+    #[test]
+    fn test_import_summary_register() {
+        // Register two messages:
+        let mut summary: ImportSummary = ImportSummary::default();
+        summary.register(Id::new(1, 0));
+        summary.register(Id::new(2, 0));
+
+        // Verify the result:
+        assert_eq!(summary.get_imported(), 2);
+        assert_eq!(summary.get_last_id(), Some(&Id::new(2, 0)));
+    }
+}
@@ -0,0 +1,674 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use redis::{streams::StreamId, Client};
+use tracing::{debug, info, warn};
+
+#[allow(unused_imports)]
+use crate::core::{
+    blocking::run_blocking,
+    client::{namespaced_key, strip_namespace, ClientArgs, RedisClientBuilder},
+    connection::VerifyConnection,
+    result::{RedsumerError, RedsumerResult},
+    streams::{
+        consumer::{AckCommands, ConsumerCommands, BEGINNING_OF_TIME_ID},
+        discovery::DiscoveryCommands,
+        types::Id,
+    },
+};
+use crate::redsumer::consumer::{AckMessageReply, AckToken, ReadNewMessagesOptions, StartPosition};
+
+/// Default interval between two scans of the streams of a [`DynamicConsumer`].
+pub const DEFAULT_RESCAN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A change in the streams consumed by a [`DynamicConsumer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MembershipEvent {
+    /// A new stream matching the pattern was found, and it is consumed from now on.
+    Added(String),
+
+    /// A stream is no longer found, and it is not consumed anymore.
+    Removed(String),
+}
+
+/// A callback fired for every change in the streams consumed by a [`DynamicConsumer`].
+pub type MembershipCallback = Arc<dyn Fn(&MembershipEvent) + Send + Sync>;
+
+/// Get the changes between the *current* streams and the *discovered* ones: first the added streams and then the removed ones, in ascending order.
+fn get_membership_events(
+    current: &BTreeSet<String>,
+    discovered: &BTreeSet<String>,
+) -> Vec<MembershipEvent> {
+    discovered
+        .difference(current)
+        .map(|key| MembershipEvent::Added(key.to_owned()))
+        .chain(
+            current
+                .difference(discovered)
+                .map(|key| MembershipEvent::Removed(key.to_owned())),
+        )
+        .collect()
+}
+
+/// A reply of the consume operation of a [`DynamicConsumer`], with the new messages of every stream.
+#[derive(Debug, Clone, Default)]
+pub struct DynamicConsumeReply {
+    /// Name of the stream and new messages, for every stream where messages were found.
+    streams: Vec<(String, Vec<StreamId>)>,
+
+    /// Tokens to ack the messages, in the same order as the streams and their messages.
+    ack_tokens: Vec<AckToken>,
+
+    /// Position of every ack token by stream name and message ID.
+    ack_token_index: HashMap<String, HashMap<Id, usize>>,
+}
+
+impl DynamicConsumeReply {
+    /// Build a new [`DynamicConsumeReply`] instance, issuing an [`AckToken`] for every message, scoped to its stream and to the given consumers group and consumer.
+    fn new(streams: Vec<(String, Vec<StreamId>)>, group_name: &str, consumer_name: &str) -> Self {
+        let ack_tokens: Vec<AckToken> = streams
+            .iter()
+            .flat_map(|(stream_name, messages)| {
+                messages
+                    .iter()
+                    .filter_map(|message| message.id.parse::<Id>().ok())
+                    .map(move |id| AckToken::new(id, stream_name, group_name, consumer_name))
+            })
+            .collect();
+
+        let mut ack_token_index: HashMap<String, HashMap<Id, usize>> = HashMap::new();
+        for (position, token) in ack_tokens.iter().enumerate() {
+            ack_token_index
+                .entry(token.get_stream_name().to_owned())
+                .or_default()
+                .insert(*token.get_id(), position);
+        }
+
+        DynamicConsumeReply {
+            streams,
+            ack_tokens,
+            ack_token_index,
+        }
+    }
+
+    /// Get the name and the new messages of every stream where messages were found.
+    pub fn get_streams(&self) -> &[(String, Vec<StreamId>)] {
+        &self.streams
+    }
+
+    /// Get the **ack tokens** of the messages, in the same order as the streams and their messages.
+    pub fn get_ack_tokens(&self) -> &[AckToken] {
+        &self.ack_tokens
+    }
+
+    /// Get the [`AckToken`] of a specific message by *stream name* and *id*, if it was issued.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream of the message.
+    /// - **id**: The ID of the message, as in [`StreamId::id`].
+    ///
+    /// # Returns:
+    /// The [`AckToken`] of the message, or [`None`] if the message is not in the reply.
+    pub fn get_ack_token(&self, stream_name: &str, id: &str) -> Option<&AckToken> {
+        let id: Id = id.parse().ok()?;
+        self.ack_token_index
+            .get(stream_name)
+            .and_then(|index| index.get(&id))
+            .map(|position| &self.ack_tokens[*position])
+    }
+
+    /// Get the total number of messages found in all the streams.
+    pub fn get_messages_count(&self) -> usize {
+        self.streams
+            .iter()
+            .map(|(_, messages)| messages.len())
+            .sum()
+    }
+
+    /// Verify if no messages were found.
+    pub fn is_empty(&self) -> bool {
+        self.get_messages_count().eq(&0)
+    }
+}
+
+/// A consumer of all the streams whose name matches a glob pattern, like `meters:*:events`, in a single consumers group.
+///
+/// The streams are discovered with [`Admin::discover_streams`](crate::admin::Admin::discover_streams) semantics and re-scanned periodically: new streams are added to the `XREADGROUP` of the consumer, with the consumers group created at the start position, and removed streams are dropped. Every change fires the membership callback.
+///
+/// The messages delivered to the consumer before a restart, which are still in its pending list, are read again before new messages when a stream is added, including the first scan. Pending messages of other consumers are not claimed, so they must be recovered with a [`Consumer`](crate::consumer::Consumer) of the stream if needed.
+#[derive(Clone)]
+pub struct DynamicConsumer {
+    /// Redis client to interact with Redis server.
+    client: Client,
+
+    /// Namespace prepended to the keys of the streams.
+    namespace: Option<String>,
+
+    /// Glob pattern of the stream names.
+    pattern: String,
+
+    /// Name of the consumers group.
+    group_name: String,
+
+    /// Name of the consumer.
+    consumer_name: String,
+
+    /// Position where the consumers group of a new stream starts consuming messages.
+    start_position: StartPosition,
+
+    /// Options to read new messages from the streams.
+    read_new_messages_options: ReadNewMessagesOptions,
+
+    /// Interval between two scans of the streams.
+    rescan_interval: Duration,
+
+    /// Keys of the streams consumed.
+    streams: BTreeSet<String>,
+
+    /// Keys of the streams whose pending messages are still being read, with the ID of the last pending message read.
+    pending_ids: BTreeMap<String, String>,
+
+    /// When the streams were scanned for the last time. If it is `None`, the streams are scanned in the next consume.
+    last_scan: Option<Instant>,
+
+    /// Callback fired for every change in the streams consumed.
+    on_membership_change: Option<MembershipCallback>,
+}
+
+impl Debug for DynamicConsumer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicConsumer")
+            .field("client", &self.client)
+            .field("namespace", &self.namespace)
+            .field("pattern", &self.pattern)
+            .field("group_name", &self.group_name)
+            .field("consumer_name", &self.consumer_name)
+            .field("start_position", &self.start_position)
+            .field("read_new_messages_options", &self.read_new_messages_options)
+            .field("rescan_interval", &self.rescan_interval)
+            .field("streams", &self.streams)
+            .field("pending_ids", &self.pending_ids)
+            .field("on_membership_change", &self.on_membership_change.is_some())
+            .finish()
+    }
+}
+
+impl DynamicConsumer {
+    /// Get [`Client`].
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get **pattern**.
+    pub fn get_pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Get **group name**.
+    pub fn get_group_name(&self) -> &str {
+        &self.group_name
+    }
+
+    /// Get **consumer name**.
+    pub fn get_consumer_name(&self) -> &str {
+        &self.consumer_name
+    }
+
+    /// Get **rescan interval**.
+    pub fn get_rescan_interval(&self) -> Duration {
+        self.rescan_interval
+    }
+
+    /// Get the names of the streams consumed, in ascending order.
+    pub fn get_streams(&self) -> Vec<String> {
+        self.streams
+            .iter()
+            .map(|key| strip_namespace(self.namespace.as_deref(), key.to_owned()))
+            .collect()
+    }
+
+    /// Verify if the streams must be scanned again.
+    fn is_rescan_due(&self) -> bool {
+        self.last_scan
+            .is_none_or(|last_scan| last_scan.elapsed().ge(&self.rescan_interval))
+    }
+
+    /// Set the interval between two scans of the streams. By default, it is [`DEFAULT_RESCAN_INTERVAL`].
+    pub fn with_rescan_interval(mut self, interval: Duration) -> Self {
+        self.rescan_interval = interval;
+        self
+    }
+
+    /// Set the options to read new messages from the streams. The count is applied to every stream.
+    pub fn with_read_new_messages_options(mut self, options: ReadNewMessagesOptions) -> Self {
+        self.read_new_messages_options = options;
+        self
+    }
+
+    /// Set a callback fired for every change in the streams consumed.
+    pub fn on_membership_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&MembershipEvent) + Send + Sync + 'static,
+    {
+        self.on_membership_change = Some(Arc::new(callback));
+        self
+    }
+
+    /// Build a new [`DynamicConsumer`] instance.
+    ///
+    /// If connection string is invalid or connection to Redis server can not be established, a [`RedsumerError`] is returned. The streams are scanned for the first time in the first call to [`consume`](DynamicConsumer::consume).
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance. If a namespace is set, the pattern is matched inside the namespace.
+    /// - **pattern**: The glob pattern of the stream names, like `meters:*:events`.
+    /// - **group_name**: The name of the consumers group.
+    /// - **consumer_name**: The name of the consumer.
+    /// - **start_position**: The [`StartPosition`] where the consumers group of a new stream starts consuming messages.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`DynamicConsumer`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(
+        args: &ClientArgs,
+        pattern: &str,
+        group_name: &str,
+        consumer_name: &str,
+        start_position: StartPosition,
+    ) -> RedsumerResult<DynamicConsumer> {
+        debug!(
+            "Creating a new dynamic consumer instance by: {:?} and {pattern}",
+            args
+        );
+
+        let mut client: Client = args.build()?;
+        client.ping()?;
+
+        info!("Dynamic consumer instance created successfully and it is ready to be used");
+
+        Ok(DynamicConsumer {
+            client,
+            namespace: args.get_namespace().map(str::to_owned),
+            pattern: pattern.to_owned(),
+            group_name: group_name.to_owned(),
+            consumer_name: consumer_name.to_owned(),
+            start_position,
+            read_new_messages_options: ReadNewMessagesOptions::default(),
+            rescan_interval: DEFAULT_RESCAN_INTERVAL,
+            streams: BTreeSet::new(),
+            pending_ids: BTreeMap::new(),
+            last_scan: None,
+            on_membership_change: None,
+        })
+    }
+
+    /// Scan the streams matching the pattern, adding the new ones and dropping the removed ones. The consumers group is created in every new stream, if it does not exist, and the pending messages of the consumer in the new streams are read in the next consume.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the changes in the streams consumed, with the stream names. If an error occurs, a [`RedsumerError`] is returned and the streams consumed are not changed.
+    pub async fn rescan(&mut self) -> RedsumerResult<Vec<MembershipEvent>> {
+        let mut client: Client = self.get_client().to_owned();
+        let pattern: String = namespaced_key(self.namespace.as_deref(), self.get_pattern());
+        let discovered: BTreeSet<String> = run_blocking(move || client.discover_streams(&pattern))
+            .await?
+            .into_iter()
+            .collect();
+
+        let events: Vec<MembershipEvent> = get_membership_events(&self.streams, &discovered);
+
+        let since_id: String = self.start_position.to_id()?;
+        for event in events.iter() {
+            if let MembershipEvent::Added(key) = event {
+                let mut client: Client = self.get_client().to_owned();
+                let key: String = key.to_owned();
+                let group_name: String = self.get_group_name().to_owned();
+                let since_id: String = since_id.to_owned();
+                run_blocking(move || client.create_consumer_group(&key, &group_name, &since_id))
+                    .await?;
+            }
+        }
+
+        for event in events.iter() {
+            match event {
+                MembershipEvent::Added(key) => {
+                    self.pending_ids
+                        .insert(key.to_owned(), BEGINNING_OF_TIME_ID.to_owned());
+                }
+                MembershipEvent::Removed(key) => {
+                    self.pending_ids.remove(key);
+                }
+            }
+        }
+
+        self.streams = discovered;
+        self.last_scan = Some(Instant::now());
+
+        let events: Vec<MembershipEvent> = events
+            .into_iter()
+            .map(|event| match event {
+                MembershipEvent::Added(key) => {
+                    MembershipEvent::Added(strip_namespace(self.namespace.as_deref(), key))
+                }
+                MembershipEvent::Removed(key) => {
+                    MembershipEvent::Removed(strip_namespace(self.namespace.as_deref(), key))
+                }
+            })
+            .collect();
+
+        for event in events.iter() {
+            info!(
+                "Streams of pattern {} changed: {:?}",
+                self.get_pattern(),
+                event
+            );
+            if let Some(callback) = &self.on_membership_change {
+                callback(event);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Consume messages from all the streams matching the pattern, scanning them again when the rescan interval elapsed.
+    ///
+    /// The pending messages of the consumer in the streams added by a scan are read first, without blocking, so the messages delivered before a restart are processed again. Once there are no pending messages left, new messages are read.
+    ///
+    /// If a stream is deleted between two scans, the read fails, and the streams are scanned again in the next call.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`DynamicConsumeReply`] with the new messages of every stream. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn consume(&mut self) -> RedsumerResult<DynamicConsumeReply> {
+        if self.is_rescan_due() {
+            self.rescan().await?;
+        }
+
+        if self.streams.is_empty() {
+            debug!("No streams match the pattern {}", self.get_pattern());
+            return Ok(DynamicConsumeReply::default());
+        }
+
+        let result: RedsumerResult<Vec<(String, Vec<StreamId>)>> =
+            match self.read_pending_messages().await {
+                Ok(streams) if streams.is_empty() => self.read_new_messages().await,
+                result => result,
+            };
+
+        match result {
+            Ok(streams) => Ok(DynamicConsumeReply::new(
+                streams
+                    .into_iter()
+                    .map(|(key, messages)| {
+                        (strip_namespace(self.namespace.as_deref(), key), messages)
+                    })
+                    .collect(),
+                self.get_group_name(),
+                self.get_consumer_name(),
+            )),
+            Err(e) => {
+                warn!(
+                    "Error consuming streams of pattern {}, they will be scanned again: {:?}",
+                    self.get_pattern(),
+                    e
+                );
+                self.last_scan = None;
+                Err(e)
+            }
+        }
+    }
+
+    /// Read the pending messages of the consumer in the streams where they are still being read, after the last pending message read in every stream. The streams without pending messages left are not read again.
+    async fn read_pending_messages(&mut self) -> RedsumerResult<Vec<(String, Vec<StreamId>)>> {
+        if self.pending_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut client: Client = self.get_client().to_owned();
+        let keys: Vec<String> = self.pending_ids.keys().cloned().collect();
+        let ids: Vec<String> = self.pending_ids.values().cloned().collect();
+        let group_name: String = self.get_group_name().to_owned();
+        let consumer_name: String = self.get_consumer_name().to_owned();
+        let count: usize = self.read_new_messages_options.get_count();
+        let streams: Vec<(String, Vec<StreamId>)> = run_blocking(move || {
+            client.read_pending_messages_from_streams(
+                &keys,
+                &ids,
+                &group_name,
+                &consumer_name,
+                count,
+            )
+        })
+        .await?;
+
+        self.pending_ids = streams
+            .iter()
+            .filter_map(|(key, messages)| {
+                messages
+                    .last()
+                    .map(|message| (key.to_owned(), message.id.to_owned()))
+            })
+            .collect();
+
+        Ok(streams
+            .into_iter()
+            .filter(|(_, messages)| !messages.is_empty())
+            .collect())
+    }
+
+    /// Read new messages from all the streams consumed, waiting for them up to the block time of the read options.
+    async fn read_new_messages(&self) -> RedsumerResult<Vec<(String, Vec<StreamId>)>> {
+        let mut client: Client = self.get_client().to_owned();
+        let keys: Vec<String> = self.streams.iter().cloned().collect();
+        let group_name: String = self.get_group_name().to_owned();
+        let consumer_name: String = self.get_consumer_name().to_owned();
+        let count: usize = self.read_new_messages_options.get_count();
+        let block: Duration = self.read_new_messages_options.get_block();
+        let command = move || {
+            client.read_new_messages_from_streams(
+                &keys,
+                &group_name,
+                &consumer_name,
+                count,
+                block.as_millis() as usize,
+            )
+        };
+
+        // A blocking read waits in the blocking thread pool, so the async runtime keeps running other tasks:
+        run_blocking(command).await
+    }
+
+    /// Ack a message by its *token*, issued when the message was consumed. The message is acked in the stream of the token.
+    ///
+    /// The token must be issued for the consumers group and consumer of this consumer, so IDs consumed by another consumer can not be acked by accident.
+    ///
+    /// # Arguments:
+    /// - **token**: The [`AckToken`] of the message, see [`DynamicConsumeReply::get_ack_tokens`].
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing an [`AckMessageReply`] if successful. If the token was issued for another consumers group or consumer, or an error occurs, a [`RedsumerError`] is returned.
+    pub async fn ack(&self, token: &AckToken) -> RedsumerResult<AckMessageReply> {
+        token.verify_consumer(self.get_group_name(), self.get_consumer_name())?;
+
+        let mut client: Client = self.get_client().to_owned();
+        let key: String = namespaced_key(self.namespace.as_deref(), token.get_stream_name());
+        let group_name: String = self.get_group_name().to_owned();
+        let id: Id = *token.get_id();
+
+        run_blocking(move || client.ack(&key, &group_name, &[id]))
+            .await
+            .map(AckMessageReply::from)
+    }
+}
+
+#[cfg(test)]
+mod test_membership_events {
+    use super::*;
+
+    #[test]
+    fn test_get_membership_events() {
+        // Define the current and the discovered streams:
+        let current: BTreeSet<String> = ["meters:1", "meters:2"]
+            .iter()
+            .map(|key| key.to_string())
+            .collect();
+        let discovered: BTreeSet<String> = ["meters:2", "meters:3"]
+            .iter()
+            .map(|key| key.to_string())
+            .collect();
+
+        // Verify the result:
+        assert_eq!(
+            get_membership_events(&current, &discovered),
+            vec![
+                MembershipEvent::Added("meters:3".to_string()),
+                MembershipEvent::Removed("meters:1".to_string()),
+            ]
+        );
+        assert!(get_membership_events(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_dynamic_consume_reply() {
+        // Define a reply:
+        let reply: DynamicConsumeReply = DynamicConsumeReply {
+            streams: vec![
+                ("meters:1".to_string(), vec![StreamId::default()]),
+                (
+                    "meters:2".to_string(),
+                    vec![StreamId::default(), StreamId::default()],
+                ),
+            ],
+            ack_tokens: Vec::new(),
+            ack_token_index: HashMap::new(),
+        };
+
+        // Verify the result:
+        assert_eq!(reply.get_streams().len(), 2);
+        assert_eq!(reply.get_messages_count(), 3);
+        assert!(!reply.is_empty());
+        assert!(DynamicConsumeReply::default().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_dynamic_consumer {
+    use super::*;
+
+    /// Build a dynamic consumer without connecting to the Redis server.
+    fn consumer() -> DynamicConsumer {
+        DynamicConsumer {
+            client: Client::open("redis://127.0.0.1:1/0").unwrap(),
+            namespace: Some("tenant".to_string()),
+            pattern: "meters:*".to_string(),
+            group_name: "group".to_string(),
+            consumer_name: "consumer".to_string(),
+            start_position: StartPosition::Beginning,
+            read_new_messages_options: ReadNewMessagesOptions::default(),
+            rescan_interval: DEFAULT_RESCAN_INTERVAL,
+            streams: BTreeSet::from(["tenant:meters:1".to_string()]),
+            pending_ids: BTreeMap::new(),
+            last_scan: None,
+            on_membership_change: None,
+        }
+    }
+
+    #[test]
+    fn test_dynamic_consumer_streams() {
+        // Build a dynamic consumer:
+        let consumer: DynamicConsumer = consumer().with_rescan_interval(Duration::from_secs(5));
+
+        // Verify the result:
+        assert_eq!(consumer.get_streams(), vec!["meters:1"]);
+        assert_eq!(consumer.get_rescan_interval(), Duration::from_secs(5));
+        assert!(consumer.is_rescan_due());
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_consumer_consume_unreachable() {
+        // Build a dynamic consumer:
+        let mut consumer: DynamicConsumer = consumer();
+
+        // Consume without reaching the server:
+        let result: RedsumerResult<DynamicConsumeReply> = consumer.consume().await;
+
+        // Verify the streams are kept:
+        assert!(result.unwrap_err().is_connection_error());
+        assert_eq!(consumer.get_streams(), vec!["meters:1"]);
+        assert!(consumer.is_rescan_due());
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_consumer_read_pending_unreachable() {
+        // Build a dynamic consumer whose pending messages are not read yet:
+        let mut consumer: DynamicConsumer = consumer();
+        consumer.last_scan = Some(Instant::now());
+        consumer.pending_ids = BTreeMap::from([(
+            "tenant:meters:1".to_string(),
+            BEGINNING_OF_TIME_ID.to_string(),
+        )]);
+
+        // Consume without reaching the server:
+        let result: RedsumerResult<DynamicConsumeReply> = consumer.consume().await;
+
+        // Verify the pending messages are read again in the next consume:
+        assert!(result.unwrap_err().is_connection_error());
+        assert_eq!(
+            consumer
+                .pending_ids
+                .get("tenant:meters:1")
+                .map(String::as_str),
+            Some(BEGINNING_OF_TIME_ID)
+        );
+        assert!(consumer.is_rescan_due());
+    }
+
+    #[test]
+    fn test_dynamic_consume_reply_ack_tokens() {
+        // Define the messages of two streams:
+        let message = |id: &str| StreamId {
+            id: id.to_string(),
+            map: Default::default(),
+        };
+        let reply: DynamicConsumeReply = DynamicConsumeReply::new(
+            vec![
+                ("meters:1".to_string(), vec![message("1-0"), message("2-0")]),
+                ("meters:2".to_string(), vec![message("1-0")]),
+            ],
+            "group",
+            "consumer",
+        );
+
+        // Verify the result:
+        assert_eq!(reply.get_ack_tokens().len(), 3);
+        assert_eq!(
+            reply.get_ack_token("meters:2", "1-0"),
+            Some(&AckToken::new(
+                Id::new(1, 0),
+                "meters:2",
+                "group",
+                "consumer"
+            ))
+        );
+        assert!(reply.get_ack_token("meters:2", "2-0").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_consumer_ack_foreign_token() {
+        // Build a dynamic consumer:
+        let consumer: DynamicConsumer = consumer();
+
+        // Ack a message with a token issued for another consumer:
+        let token: AckToken = AckToken::new(Id::new(1, 0), "meters:1", "group", "other");
+        let result: RedsumerResult<AckMessageReply> = consumer.ack(&token).await;
+
+        // Verify the ack was rejected before reaching the server:
+        assert_eq!(result.unwrap_err().kind(), redis::ErrorKind::ClientError);
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use redis::{from_redis_value, streams::StreamId, RedisWrite, ToRedisArgs, Value};
+use time::OffsetDateTime;
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+
+/// Reserved message field for the application message ID.
+pub const MESSAGE_ID_HEADER: &str = "x-message-id";
+
+/// Reserved message field for the correlation ID.
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// Reserved message field for the content type of the payload.
+pub const CONTENT_TYPE_HEADER: &str = "x-content-type";
+
+/// Reserved message field for the schema version of the payload.
+pub const SCHEMA_VERSION_HEADER: &str = "x-schema-version";
+
+/// Reserved message field for the Unix time in milliseconds when the message was produced.
+pub const PRODUCED_AT_HEADER: &str = "x-produced-at";
+
+/// All the reserved message fields of the [`Headers`].
+pub const RESERVED_HEADERS: [&str; 5] = [
+    MESSAGE_ID_HEADER,
+    CORRELATION_ID_HEADER,
+    CONTENT_TYPE_HEADER,
+    SCHEMA_VERSION_HEADER,
+    PRODUCED_AT_HEADER,
+];
+
+/// Standard metadata of a message, written in reserved fields alongside the business payload.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Headers {
+    /// Application message ID. It is independent of the stream entry ID.
+    message_id: Option<String>,
+
+    /// ID to correlate messages of the same flow.
+    correlation_id: Option<String>,
+
+    /// Content type of the payload, for example `application/json`.
+    content_type: Option<String>,
+
+    /// Schema version of the payload.
+    schema_version: Option<String>,
+
+    /// Unix time in milliseconds when the message was produced.
+    produced_at: Option<i64>,
+}
+
+impl Headers {
+    /// Get **message id**.
+    pub fn get_message_id(&self) -> Option<&str> {
+        self.message_id.as_deref()
+    }
+
+    /// Get **correlation id**.
+    pub fn get_correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
+    /// Get **content type**.
+    pub fn get_content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Get **schema version**.
+    pub fn get_schema_version(&self) -> Option<&str> {
+        self.schema_version.as_deref()
+    }
+
+    /// Get **produced at** timestamp in UTC.
+    pub fn get_produced_at(&self) -> Option<OffsetDateTime> {
+        self.produced_at.and_then(|milliseconds| {
+            OffsetDateTime::from_unix_timestamp_nanos(i128::from(milliseconds) * 1_000_000).ok()
+        })
+    }
+
+    /// Get the headers from the reserved fields of a message.
+    ///
+    /// # Arguments:
+    /// - **message**: A consumed message.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the [`Headers`]. Missing fields are `None`. If a reserved field can not be read as a string, or the *produced at* field is not an integer, a [`RedsumerError`] is returned.
+    pub fn from_message(message: &StreamId) -> RedsumerResult<Self> {
+        let get = |field: &str| -> RedsumerResult<Option<String>> {
+            match message.map.get(field) {
+                Some(value) => Ok(Some(from_redis_value::<String>(value)?)),
+                None => Ok(None),
+            }
+        };
+
+        let produced_at: Option<i64> = match get(PRODUCED_AT_HEADER)? {
+            Some(value) => Some(value.parse::<i64>().map_err(|e| {
+                RedsumerError::DeserializationError(format!(
+                    "Invalid message header {PRODUCED_AT_HEADER}: {e}"
+                ))
+            })?),
+            None => None,
+        };
+
+        Ok(Headers {
+            message_id: get(MESSAGE_ID_HEADER)?,
+            correlation_id: get(CORRELATION_ID_HEADER)?,
+            content_type: get(CONTENT_TYPE_HEADER)?,
+            schema_version: get(SCHEMA_VERSION_HEADER)?,
+            produced_at,
+        })
+    }
+
+    /// Get the headers as a list of message fields. Missing headers are omitted.
+    fn to_fields(&self) -> Vec<(&'static str, String)> {
+        [
+            (MESSAGE_ID_HEADER, self.message_id.to_owned()),
+            (CORRELATION_ID_HEADER, self.correlation_id.to_owned()),
+            (CONTENT_TYPE_HEADER, self.content_type.to_owned()),
+            (SCHEMA_VERSION_HEADER, self.schema_version.to_owned()),
+            (PRODUCED_AT_HEADER, self.produced_at.map(|p| p.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| value.map(|v| (field, v)))
+        .collect()
+    }
+}
+
+/// A message with standard [`Headers`] and a business payload.
+///
+/// On the producer side, an [`Envelope`] can be produced with [`produce_from_map`](crate::producer::Producer::produce_from_map) when its payload implements [`ToRedisArgs`], since the headers are written as reserved fields before the payload fields. On the consumer side, an [`Envelope`] can be built from a consumed message, splitting the headers from the payload fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope<P> {
+    /// Message headers.
+    headers: Headers,
+
+    /// Business payload.
+    payload: P,
+}
+
+impl<P> Envelope<P> {
+    /// Get **headers**.
+    pub fn get_headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    /// Get **payload**.
+    pub fn get_payload(&self) -> &P {
+        &self.payload
+    }
+
+    /// Take the **payload**, consuming the envelope.
+    pub fn into_payload(self) -> P {
+        self.payload
+    }
+
+    /// Build a new [`Envelope`] instance with *produced at* set to the current time.
+    ///
+    /// # Arguments:
+    /// - **payload**: The business payload.
+    ///
+    /// # Returns:
+    /// A new [`Envelope`] instance.
+    pub fn new(payload: P) -> Self {
+        let produced_at: i64 =
+            (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64;
+
+        Envelope {
+            headers: Headers {
+                produced_at: Some(produced_at),
+                ..Headers::default()
+            },
+            payload,
+        }
+    }
+
+    /// Set the application message ID.
+    pub fn message_id(mut self, message_id: &str) -> Self {
+        self.headers.message_id = Some(message_id.to_owned());
+        self
+    }
+
+    /// Set the correlation ID.
+    pub fn correlation_id(mut self, correlation_id: &str) -> Self {
+        self.headers.correlation_id = Some(correlation_id.to_owned());
+        self
+    }
+
+    /// Set the content type of the payload.
+    pub fn content_type(mut self, content_type: &str) -> Self {
+        self.headers.content_type = Some(content_type.to_owned());
+        self
+    }
+
+    /// Set the schema version of the payload.
+    pub fn schema_version(mut self, schema_version: &str) -> Self {
+        self.headers.schema_version = Some(schema_version.to_owned());
+        self
+    }
+}
+
+impl<P> ToRedisArgs for Envelope<P>
+where
+    P: ToRedisArgs,
+{
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        self.headers.to_fields().write_redis_args(out);
+        self.payload.write_redis_args(out);
+    }
+}
+
+/// Build an [`Envelope`] from a consumed message. The payload contains all the fields of the message except the reserved headers.
+impl TryFrom<&StreamId> for Envelope<HashMap<String, Value>> {
+    type Error = RedsumerError;
+
+    fn try_from(message: &StreamId) -> Result<Self, Self::Error> {
+        Ok(Envelope {
+            headers: Headers::from_message(message)?,
+            payload: message
+                .map
+                .iter()
+                .filter(|(field, _)| !RESERVED_HEADERS.contains(&field.as_str()))
+                .map(|(field, value)| (field.to_owned(), value.to_owned()))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_envelope {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_envelope_to_redis_args() {
+        // Build a new envelope:
+        let envelope: Envelope<Vec<(&str, &str)>> = Envelope::new(vec![("key", "value")])
+            .message_id("message-1")
+            .correlation_id("flow-1")
+            .content_type("text/plain")
+            .schema_version("1");
+
+        // Verify the result:
+        let args: Vec<Vec<u8>> = envelope.to_redis_args();
+        assert_eq!(args.len(), 12);
+        assert_eq!(args[0], MESSAGE_ID_HEADER.as_bytes());
+        assert_eq!(args[1], b"message-1");
+        assert_eq!(args[8], PRODUCED_AT_HEADER.as_bytes());
+        assert_eq!(args[10], b"key");
+        assert_eq!(args[11], b"value");
+        assert!(envelope.get_headers().get_produced_at().is_some());
+    }
+
+    #[test]
+    fn test_envelope_from_message() {
+        // Define a consumed message:
+        let message: StreamId = StreamId {
+            id: "1-0".to_string(),
+            map: HashMap::from([
+                ("key".to_string(), Value::BulkString(b"value".to_vec())),
+                (
+                    CORRELATION_ID_HEADER.to_string(),
+                    Value::BulkString(b"flow-1".to_vec()),
+                ),
+                (
+                    PRODUCED_AT_HEADER.to_string(),
+                    Value::BulkString(b"1700000000123".to_vec()),
+                ),
+            ]),
+        };
+
+        // Build the envelope:
+        let envelope: Envelope<HashMap<String, Value>> = Envelope::try_from(&message).unwrap();
+
+        // Verify the result:
+        assert_eq!(envelope.get_headers().get_correlation_id(), Some("flow-1"));
+        assert!(envelope.get_headers().get_message_id().is_none());
+        assert_eq!(
+            envelope.get_headers().get_produced_at(),
+            Some(datetime!(2023-11-14 22:13:20.123 UTC))
+        );
+        assert_eq!(
+            envelope.into_payload(),
+            HashMap::from([("key".to_string(), Value::BulkString(b"value".to_vec()))])
+        );
+    }
+
+    #[test]
+    fn test_envelope_from_message_invalid_header() {
+        // Define a consumed message with an invalid header:
+        let message: StreamId = StreamId {
+            id: "1-0".to_string(),
+            map: HashMap::from([(
+                PRODUCED_AT_HEADER.to_string(),
+                Value::BulkString(b"yesterday".to_vec()),
+            )]),
+        };
+
+        // Verify the result:
+        assert!(Headers::from_message(&message).is_err());
+    }
+}
@@ -0,0 +1,431 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread::spawn,
+    time::Duration,
+};
+
+use redis::{streams::StreamId, Client};
+use tracing::{debug, warn};
+
+#[allow(unused_imports)]
+use crate::core::{
+    blocking::run_blocking,
+    connection::StreamConnection,
+    result::{RedsumerError, RedsumerResult},
+    streams::consumer::ConsumerCommands,
+};
+use crate::redsumer::consumer::{AckMessageReply, AckToken, Consumer, ConsumerConfig};
+
+/// Action taken when a [`MessageGuard`] is dropped without [`ack`](MessageGuard::ack) or [`nack`](MessageGuard::nack).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GuardDropAction {
+    /// Log a warning. The message is kept in the consumer pending list, so it is read again by the next pending read of the consumer, or claimed by another consumer once it is idle long enough.
+    #[default]
+    Warn,
+
+    /// Log a warning and release the message, as [`nack`](MessageGuard::nack) does, so it can be claimed by other consumers right away.
+    Requeue,
+}
+
+/// A signal sent to the renewal thread of a [`MessageGuard`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GuardSignal {
+    /// Release the message and stop renewing it.
+    Release,
+}
+
+/// The names of the stream, consumers group and consumer of a guarded message, with the idle time set when it is released.
+#[derive(Debug, Clone)]
+struct GuardScope {
+    /// The name of the stream.
+    stream_name: String,
+
+    /// The name of the consumers group.
+    group_name: String,
+
+    /// The name of the consumer.
+    consumer_name: String,
+
+    /// The idle time in milliseconds set to the message when it is released: the min idle time used to claim messages.
+    release_idle_time: usize,
+}
+
+/// Release a message, so it can be claimed by other consumers right away.
+fn release<C>(client: &mut C, scope: &GuardScope, id: &str) -> RedsumerResult<bool>
+where
+    C: StreamConnection,
+{
+    client
+        .release_messages(
+            &scope.stream_name,
+            &scope.group_name,
+            &scope.consumer_name,
+            scope.release_idle_time,
+            &[id],
+        )
+        .map(|released| !released.is_empty())
+}
+
+/// Renew the ownership of a message every *interval* until the guard stops it, or releases the message. If the *interval* is zero, the ownership is not renewed.
+fn renew<C>(
+    mut client: C,
+    scope: GuardScope,
+    id: String,
+    interval: Duration,
+    owned: Arc<AtomicBool>,
+    signal: Receiver<GuardSignal>,
+) where
+    C: StreamConnection,
+{
+    loop {
+        let received: Result<GuardSignal, RecvTimeoutError> = match interval.is_zero() {
+            true => signal.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            false => signal.recv_timeout(interval),
+        };
+
+        match received {
+            Err(RecvTimeoutError::Timeout) => {
+                match client.renew_messages(
+                    &scope.stream_name,
+                    &scope.group_name,
+                    &scope.consumer_name,
+                    0,
+                    &[&id],
+                ) {
+                    Ok(renewed) if renewed.is_empty() => {
+                        warn!(
+                            "Message {} is not pending for this consumer anymore, its ownership is not renewed",
+                            id
+                        );
+                        owned.store(false, Ordering::Relaxed);
+                        return;
+                    }
+                    Ok(_) => debug!("Ownership of message {} renewed", id),
+                    Err(e) => warn!("Error renewing the ownership of message {}: {:?}", id, e),
+                }
+            }
+            Ok(GuardSignal::Release) => {
+                if let Err(e) = release(&mut client, &scope, &id) {
+                    warn!("Error releasing message {}: {:?}", id, e);
+                }
+                return;
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+/// An ownership guard of a consumed message, see [`Consumer::guard_messages`].
+///
+/// While the guard is alive, the ownership of the message is renewed in a dedicated thread every renew interval, resetting its idle time so it is not claimed by other consumers while it is processed. The guard is resolved with [`ack`](MessageGuard::ack) or [`nack`](MessageGuard::nack). If it is dropped without resolution, a warning is logged and the [`GuardDropAction`] is taken, so messages that were never acked are visible.
+#[derive(Debug)]
+pub struct MessageGuard<C = Client>
+where
+    C: StreamConnection,
+{
+    /// The consumer that consumed the message.
+    consumer: Consumer<C>,
+
+    /// The guarded message.
+    message: StreamId,
+
+    /// The token to ack the message.
+    token: AckToken,
+
+    /// The scope of the message, used to release it.
+    scope: GuardScope,
+
+    /// Whether the message is still owned by the consumer, as seen by the last renewal.
+    owned: Arc<AtomicBool>,
+
+    /// The sender of signals to the renewal thread. Dropping it stops the renewal.
+    signal: Option<Sender<GuardSignal>>,
+
+    /// Action taken when the guard is dropped without resolution.
+    drop_action: GuardDropAction,
+}
+
+impl<C> MessageGuard<C>
+where
+    C: StreamConnection,
+{
+    /// Build a new [`MessageGuard`] instance, starting the renewal of the ownership of the message every *renew_interval*.
+    pub(crate) fn new(
+        consumer: Consumer<C>,
+        message: StreamId,
+        token: AckToken,
+        renew_interval: Duration,
+    ) -> Self {
+        let config: &ConsumerConfig = consumer.get_config();
+        let scope: GuardScope = GuardScope {
+            stream_name: config.get_stream_name().to_owned(),
+            group_name: config.get_group_name().to_owned(),
+            consumer_name: config.get_consumer_name().to_owned(),
+            release_idle_time: config.get_claim_messages_options().get_min_idle_time(),
+        };
+
+        let owned: Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
+        let (sender, receiver) = channel::<GuardSignal>();
+        let client: C = consumer.get_client().to_owned();
+        let renewal_scope: GuardScope = scope.to_owned();
+        let renewal_id: String = message.id.to_owned();
+        let renewal_owned: Arc<AtomicBool> = owned.to_owned();
+        spawn(move || {
+            renew(
+                client,
+                renewal_scope,
+                renewal_id,
+                renew_interval,
+                renewal_owned,
+                receiver,
+            )
+        });
+
+        MessageGuard {
+            consumer,
+            message,
+            token,
+            scope,
+            owned,
+            signal: Some(sender),
+            drop_action: GuardDropAction::default(),
+        }
+    }
+
+    /// Set the [`GuardDropAction`] taken when the guard is dropped without resolution. It is [`GuardDropAction::Warn`] by default.
+    ///
+    /// # Arguments:
+    /// - **action**: The action taken when the guard is dropped without resolution.
+    ///
+    /// # Returns:
+    /// The [`MessageGuard`] instance with the given drop action.
+    pub fn with_drop_action(mut self, action: GuardDropAction) -> Self {
+        self.drop_action = action;
+        self
+    }
+
+    /// Get the guarded **message**.
+    pub fn get_message(&self) -> &StreamId {
+        &self.message
+    }
+
+    /// Get the **token** to ack the message.
+    pub fn get_token(&self) -> &AckToken {
+        &self.token
+    }
+
+    /// Get the **drop action** of the guard.
+    pub fn get_drop_action(&self) -> GuardDropAction {
+        self.drop_action
+    }
+
+    /// Verify if the message is still owned by the consumer, as seen by the last renewal. Once a renewal finds the message is not pending for the consumer anymore, for example because it was claimed by another consumer, the ownership is not renewed anymore.
+    pub fn is_owned(&self) -> bool {
+        self.owned.load(Ordering::Relaxed)
+    }
+
+    /// Ack the message, see [`Consumer::ack`]. The renewal of the ownership is stopped.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] containing an [`AckMessageReply`] if successful. If an error occurs, a [`RedsumerError`] is returned, and the message is kept in the consumer pending list.
+    pub async fn ack(mut self) -> RedsumerResult<AckMessageReply> {
+        self.signal.take();
+        self.consumer.ack(&self.token).await
+    }
+
+    /// Release the message without ack, so it can be claimed by other consumers right away: its idle time is set to the min idle time of the [`ClaimMessagesOptions`](crate::consumer::ClaimMessagesOptions) of the consumer. The renewal of the ownership is stopped, and the message is not tracked as in flight anymore.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `true` if the message was released, or `false` if it was not pending for the consumer anymore. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn nack(mut self) -> RedsumerResult<bool> {
+        self.signal.take();
+        self.consumer.untrack_in_flight(&self.message.id);
+
+        let mut client: C = self.consumer.get_client().to_owned();
+        let scope: GuardScope = self.scope.to_owned();
+        let id: String = self.message.id.to_owned();
+        run_blocking(move || release(&mut client, &scope, &id)).await
+    }
+}
+
+impl<C> Drop for MessageGuard<C>
+where
+    C: StreamConnection,
+{
+    fn drop(&mut self) {
+        let signal: Sender<GuardSignal> = match self.signal.take() {
+            Some(signal) => signal,
+            None => return,
+        };
+
+        warn!(
+            "Message {} dropped without ack or nack, drop action: {:?}",
+            self.message.id, self.drop_action
+        );
+
+        if self.drop_action.eq(&GuardDropAction::Requeue) {
+            self.consumer.untrack_in_flight(&self.message.id);
+            if signal.send(GuardSignal::Release).is_err() {
+                warn!(
+                    "Message {} can not be released, its renewal is stopped",
+                    self.message.id
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_message_guard {
+    use std::{collections::HashMap, time::Instant};
+
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+    use crate::redsumer::consumer::{ConsumeMessagesReply, MessagesKind, StartPosition};
+
+    /// Build a consumer on a mock connection with the given *commands*, after the commands sent to create it.
+    fn consumer(commands: Vec<MockCmd>) -> Consumer<MockRedisConnection> {
+        let mut mocks: Vec<MockCmd> = vec![
+            MockCmd::new(cmd("EXISTS").arg("stream"), Ok(1)),
+            MockCmd::new(
+                cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg("stream")
+                    .arg("group")
+                    .arg("0-0"),
+                Ok("OK"),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Ok(Value::BulkString(
+                    b"# Server\r\nredis_version:7.2.4\r\n".to_vec(),
+                )),
+            ),
+        ];
+        mocks.extend(commands);
+
+        Consumer::from_client(
+            Arc::new(MockRedisConnection::new(mocks)),
+            ConsumerConfig::builder("stream", "group", "consumer")
+                .min_idle_time(1000)
+                .build()
+                .unwrap(),
+            StartPosition::Beginning,
+        )
+        .unwrap()
+    }
+
+    /// Build a reply with the message `1-0` and its ack token.
+    fn reply() -> ConsumeMessagesReply {
+        let message: StreamId = StreamId {
+            id: "1-0".to_string(),
+            map: HashMap::new(),
+        };
+        let mut reply: ConsumeMessagesReply = (vec![message], MessagesKind::New).into();
+        reply.issue_ack_tokens("stream", "group", "consumer");
+        reply
+    }
+
+    #[tokio::test]
+    async fn test_message_guard_ack() {
+        // Guard a message without renewals:
+        let consumer: Consumer<MockRedisConnection> = consumer(vec![MockCmd::new::<_, Value>(
+            cmd("XACK").arg("stream").arg("group").arg("1-0"),
+            Ok(Value::Int(1)),
+        )]);
+        let mut guards: Vec<MessageGuard<MockRedisConnection>> =
+            consumer.guard_messages(reply(), Duration::ZERO);
+
+        // Verify the guard:
+        assert_eq!(guards.len(), 1);
+        let guard: MessageGuard<MockRedisConnection> = guards.remove(0);
+        assert_eq!(guard.get_message().id, "1-0");
+        assert_eq!(guard.get_token().get_stream_name(), "stream");
+        assert_eq!(guard.get_drop_action(), GuardDropAction::Warn);
+        assert!(guard.is_owned());
+
+        // Ack the message:
+        let reply: AckMessageReply = guard.ack().await.unwrap();
+
+        // Verify the result:
+        assert!(reply.was_acked());
+        assert_eq!(consumer.stats().get_acks(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_message_guard_nack() {
+        // Guard a message without renewals:
+        let consumer: Consumer<MockRedisConnection> = consumer(vec![MockCmd::new::<_, Value>(
+            cmd("XCLAIM")
+                .arg("stream")
+                .arg("group")
+                .arg("consumer")
+                .arg(0)
+                .arg(&["1-0"])
+                .arg("IDLE")
+                .arg(1000)
+                .arg("JUSTID"),
+            Ok(Value::Array(vec![Value::BulkString(b"1-0".to_vec())])),
+        )]);
+        let guard: MessageGuard<MockRedisConnection> = consumer
+            .guard_messages(reply(), Duration::ZERO)
+            .remove(0)
+            .with_drop_action(GuardDropAction::Requeue);
+
+        // Release the message:
+        let released: bool = guard.nack().await.unwrap();
+
+        // Verify the result:
+        assert!(released);
+    }
+
+    #[tokio::test]
+    async fn test_message_guard_lost_ownership() {
+        // Guard a message whose renewal finds it is not pending anymore:
+        let consumer: Consumer<MockRedisConnection> = consumer(vec![MockCmd::new::<_, Value>(
+            cmd("XCLAIM")
+                .arg("stream")
+                .arg("group")
+                .arg("consumer")
+                .arg(0)
+                .arg(&["1-0"])
+                .arg("JUSTID"),
+            Ok(Value::Array(vec![])),
+        )]);
+        let guard: MessageGuard<MockRedisConnection> = consumer
+            .guard_messages(reply(), Duration::from_millis(10))
+            .remove(0);
+
+        // Wait for the renewal:
+        let deadline: Instant = Instant::now() + Duration::from_secs(5);
+        while guard.is_owned() && Instant::now().lt(&deadline) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Verify the result:
+        assert!(!guard.is_owned());
+    }
+
+    #[test]
+    fn test_guard_messages_without_tokens() {
+        // Define a reply without ack tokens:
+        let consumer: Consumer<MockRedisConnection> = consumer(Vec::new());
+        let reply: ConsumeMessagesReply = (
+            vec![StreamId {
+                id: "1-0".to_string(),
+                map: HashMap::new(),
+            }],
+            MessagesKind::New,
+        )
+            .into();
+
+        // Verify the result:
+        assert!(consumer.guard_messages(reply, Duration::ZERO).is_empty());
+    }
+}
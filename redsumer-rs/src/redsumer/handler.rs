@@ -0,0 +1,452 @@
+use std::{future::Future, io::Cursor, str::FromStr};
+
+use redis::{from_redis_value, streams::StreamId};
+use serde::{
+    de::{
+        value::{Error as DecodeError, MapDeserializer},
+        DeserializeOwned, Error as _, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use serde_json::{de::IoRead, Deserializer as JsonDeserializer, Error as JsonError};
+
+#[allow(unused_imports)]
+use crate::core::{
+    result::{RedsumerError, RedsumerResult},
+    streams::types::{Id, LastDeliveredMilliseconds, TotalTimesDelivered},
+};
+use crate::redsumer::consumer::{AckToken, MessagesKind};
+
+/// The outcome of a [`MessageHandler`] for a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandlerOutcome {
+    /// The message was processed, so it is acked.
+    Ack,
+
+    /// The message was not processed and it is kept in the consumer pending list, so it is read again by the next pending read of the consumer, or claimed by another consumer once it is idle long enough.
+    Retry,
+}
+
+/// Metadata of a message passed to a [`MessageHandler`] with its decoded fields.
+#[derive(Debug, Clone)]
+pub struct MessageMeta {
+    /// The token to ack the message, with its ID, stream, consumers group and consumer.
+    token: AckToken,
+
+    /// The kind of the message.
+    kind: MessagesKind,
+
+    /// The milliseconds since the message was last delivered, as seen by the ownership check.
+    last_delivered_milliseconds: Option<LastDeliveredMilliseconds>,
+
+    /// The number of times the message was delivered, as seen by the ownership check.
+    total_times_delivered: Option<TotalTimesDelivered>,
+}
+
+impl MessageMeta {
+    /// Build a new [`MessageMeta`] instance.
+    pub(crate) fn new(
+        token: AckToken,
+        kind: MessagesKind,
+        last_delivered_milliseconds: Option<LastDeliveredMilliseconds>,
+        total_times_delivered: Option<TotalTimesDelivered>,
+    ) -> Self {
+        MessageMeta {
+            token,
+            kind,
+            last_delivered_milliseconds,
+            total_times_delivered,
+        }
+    }
+
+    /// Get **id** of the message.
+    pub fn get_id(&self) -> &Id {
+        self.token.get_id()
+    }
+
+    /// Get **stream name** the message was consumed from.
+    pub fn get_stream_name(&self) -> &str {
+        self.token.get_stream_name()
+    }
+
+    /// Get **token** to ack the message.
+    pub fn get_token(&self) -> &AckToken {
+        &self.token
+    }
+
+    /// Get **kind** of the message.
+    pub fn get_kind(&self) -> &MessagesKind {
+        &self.kind
+    }
+
+    /// Get **last delivered milliseconds**.
+    pub fn get_last_delivered_milliseconds(&self) -> Option<LastDeliveredMilliseconds> {
+        self.last_delivered_milliseconds
+    }
+
+    /// Get **total times delivered**, useful to give up on messages that failed too many times.
+    pub fn get_total_times_delivered(&self) -> Option<TotalTimesDelivered> {
+        self.total_times_delivered
+    }
+}
+
+/// A handler of the messages of a stream, decoded into *T*. See [`Consumer::handle_messages`](crate::consumer::Consumer::handle_messages) and [`Consumer::run_handler`](crate::consumer::Consumer::run_handler).
+///
+/// It can be implemented with `async fn handle`.
+pub trait MessageHandler<T>: Send + Sync
+where
+    T: DeserializeOwned,
+{
+    /// Handle a message.
+    ///
+    /// # Arguments:
+    /// - **msg**: The fields of the message, decoded with [`decode`].
+    /// - **meta**: The [`MessageMeta`] of the message.
+    ///
+    /// # Returns:
+    /// The [`HandlerOutcome`] for the message.
+    fn handle(&self, msg: T, meta: &MessageMeta) -> impl Future<Output = HandlerOutcome> + Send;
+}
+
+/// A reply to handle the messages of a [`ConsumeMessagesReply`](crate::consumer::ConsumeMessagesReply) with a [`MessageHandler`], with the IDs of the messages by result.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HandleMessagesReply {
+    /// The IDs of the messages handled and acked.
+    acked: Vec<Id>,
+
+    /// The IDs of the messages handled with [`HandlerOutcome::Retry`], or handled with [`HandlerOutcome::Ack`] but not acked since they were claimed by another consumer meanwhile.
+    retried: Vec<Id>,
+
+    /// The IDs of the messages not handled since they do not belong to the consumer anymore.
+    not_owned: Vec<Id>,
+
+    /// The IDs of the messages not handled since they can not be decoded.
+    undecodable: Vec<Id>,
+}
+
+impl HandleMessagesReply {
+    /// Get the IDs of the messages handled and **acked**.
+    pub fn get_acked(&self) -> &[Id] {
+        &self.acked
+    }
+
+    /// Get the IDs of the messages **retried**: handled with [`HandlerOutcome::Retry`], or handled with [`HandlerOutcome::Ack`] but not acked since they were claimed by another consumer meanwhile.
+    pub fn get_retried(&self) -> &[Id] {
+        &self.retried
+    }
+
+    /// Get the IDs of the messages not handled since they are **not owned** by the consumer anymore.
+    pub fn get_not_owned(&self) -> &[Id] {
+        &self.not_owned
+    }
+
+    /// Get the IDs of the messages not handled since they are **undecodable**. They are kept in the consumer pending list.
+    pub fn get_undecodable(&self) -> &[Id] {
+        &self.undecodable
+    }
+
+    /// Record the ID of a message acked.
+    pub(crate) fn record_acked(&mut self, id: Id) {
+        self.acked.push(id);
+    }
+
+    /// Record the ID of a message retried.
+    pub(crate) fn record_retried(&mut self, id: Id) {
+        self.retried.push(id);
+    }
+
+    /// Record the ID of a message not owned by the consumer.
+    pub(crate) fn record_not_owned(&mut self, id: Id) {
+        self.not_owned.push(id);
+    }
+
+    /// Record the ID of a message that can not be decoded.
+    pub(crate) fn record_undecodable(&mut self, id: Id) {
+        self.undecodable.push(id);
+    }
+}
+
+/// A field value of a message, deserialized from its text: numbers and booleans are parsed, and sequences, maps and structs are parsed as JSON.
+struct FieldValue(String);
+
+impl FieldValue {
+    /// Parse the field value into *T*.
+    fn parse<T>(&self) -> Result<T, DecodeError>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.0
+            .parse::<T>()
+            .map_err(|e| DecodeError::custom(format!("invalid field value {:?}: {e}", self.0)))
+    }
+
+    /// Deserialize the field value as JSON with the given *deserialize* function.
+    fn decode_json<V, F>(self, deserialize: F) -> Result<V, DecodeError>
+    where
+        F: FnOnce(&mut JsonDeserializer<IoRead<Cursor<Vec<u8>>>>) -> Result<V, JsonError>,
+    {
+        let mut deserializer: JsonDeserializer<IoRead<Cursor<Vec<u8>>>> =
+            JsonDeserializer::from_reader(Cursor::new(self.0.into_bytes()));
+        deserialize(&mut deserializer)
+            .and_then(|value| deserializer.end().map(|_| value))
+            .map_err(DecodeError::custom)
+    }
+}
+
+/// Deserialize a parsed field value with the given visitor method.
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+/// Deserialize a field value as JSON with the given deserializer method.
+macro_rules! deserialize_json {
+    ($($method:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.decode_json(|deserializer| deserializer.$method(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for FieldValue {
+    type Error = DecodeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.decode_json(|deserializer| deserializer.deserialize_tuple(len, visitor))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.decode_json(|deserializer| deserializer.deserialize_tuple_struct(name, len, visitor))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.decode_json(|deserializer| deserializer.deserialize_struct(name, fields, visitor))
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    deserialize_json! {
+        deserialize_seq,
+        deserialize_map,
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DecodeError> for FieldValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// Decode the fields of a consumed *message* into *T*, like a struct whose fields are named as the message fields.
+///
+/// Field values are read as text: numbers and booleans are parsed, enums are matched by variant name, and sequences, maps and nested structs are parsed as JSON. Fields of the message that are not in *T*, like the headers of an [`Envelope`](crate::envelope::Envelope), are ignored unless *T* denies unknown fields.
+///
+/// # Arguments:
+/// - **message**: A consumed message.
+///
+/// # Returns:
+/// A [`RedsumerResult`] with the decoded message. If a field value is not text or the fields do not match *T*, a [`RedsumerError::DeserializationError`] is returned.
+pub fn decode<T>(message: &StreamId) -> RedsumerResult<T>
+where
+    T: DeserializeOwned,
+{
+    let fields: Vec<(String, FieldValue)> = message
+        .map
+        .iter()
+        .map(|(field, value)| Ok((field.to_owned(), FieldValue(from_redis_value(value)?))))
+        .collect::<RedsumerResult<Vec<(String, FieldValue)>>>()?;
+
+    T::deserialize(MapDeserializer::new(fields.into_iter())).map_err(|e: DecodeError| {
+        RedsumerError::DeserializationError(format!(
+            "Invalid fields of message {}: {e}",
+            message.id
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test_decode {
+    use std::collections::HashMap;
+
+    use redis::Value;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Unit {
+        Kwh,
+        Wh,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Reading {
+        meter: String,
+        value: f64,
+        count: u32,
+        valid: bool,
+        unit: Unit,
+        tags: Vec<String>,
+        note: Option<String>,
+    }
+
+    /// Build a message with the given fields.
+    fn message(fields: &[(&str, &str)]) -> StreamId {
+        StreamId {
+            id: "1-0".to_string(),
+            map: fields
+                .iter()
+                .map(|(field, value)| {
+                    (
+                        field.to_string(),
+                        Value::BulkString(value.as_bytes().to_vec()),
+                    )
+                })
+                .collect::<HashMap<String, Value>>(),
+        }
+    }
+
+    #[test]
+    fn test_decode_ok() {
+        // Define a message:
+        let message: StreamId = message(&[
+            ("meter", "123"),
+            ("value", "1.5"),
+            ("count", "3"),
+            ("valid", "true"),
+            ("unit", "kwh"),
+            ("tags", r#"["a","b"]"#),
+            ("rd-message-id", "abc"),
+        ]);
+
+        // Decode the message:
+        let reading: Reading = decode(&message).unwrap();
+
+        // Verify the result:
+        assert_eq!(
+            reading,
+            Reading {
+                meter: "123".to_string(),
+                value: 1.5,
+                count: 3,
+                valid: true,
+                unit: Unit::Kwh,
+                tags: vec!["a".to_string(), "b".to_string()],
+                note: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_fields() {
+        // Define messages with invalid and missing fields:
+        let invalid: StreamId = message(&[
+            ("meter", "123"),
+            ("value", "high"),
+            ("count", "3"),
+            ("valid", "true"),
+            ("unit", "wh"),
+            ("tags", "[]"),
+        ]);
+        let missing: StreamId = message(&[("meter", "123")]);
+
+        // Verify the result:
+        for message in [invalid, missing] {
+            assert!(matches!(
+                decode::<Reading>(&message),
+                Err(RedsumerError::DeserializationError(_))
+            ));
+        }
+    }
+}
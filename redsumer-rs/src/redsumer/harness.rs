@@ -0,0 +1,192 @@
+use redis::{cmd, Client, ErrorKind};
+use testcontainers::{
+    core::{IntoContainerPort, WaitFor},
+    runners::AsyncRunner,
+    ContainerAsync, GenericImage, TestcontainersError,
+};
+use time::OffsetDateTime;
+use tracing::{error, info};
+
+#[allow(unused_imports)]
+use crate::core::{
+    client::{ClientArgs, CommunicationProtocol, RedisClientBuilder},
+    result::{RedsumerError, RedsumerResult},
+};
+use crate::redsumer::{
+    consumer::{Consumer, ConsumerConfig, StartPosition},
+    producer::{Producer, ProducerConfig},
+};
+
+/// Default Redis image used by [`RedisTestHarness::start`].
+pub const DEFAULT_REDIS_IMAGE: &str = "redis:7-alpine";
+
+/// Port of the Redis server inside the container.
+const REDIS_PORT: u16 = 6379;
+
+/// Message logged by the Redis server once it is ready to accept connections.
+const READY_MESSAGE: &str = "Ready to accept connections";
+
+/// Convert an error of the Redis container into a [`RedsumerError`].
+fn container_error(e: TestcontainersError) -> RedsumerError {
+    error!("Error running Redis test container: {:?}", e);
+    RedsumerError::from((
+        ErrorKind::ClientError,
+        "Redis test container failed",
+        e.to_string(),
+    ))
+}
+
+/// An integration test harness that runs a throwaway Redis server in a Docker container, with [testcontainers](https://docs.rs/testcontainers).
+///
+/// The container is published on a random local port, so tests do not depend on a Redis server running at a fixed port. A stream and a consumers group with unique names are created, and preconfigured [`Producer`] and [`Consumer`] instances can be built from them. The container is removed by [`cleanup`](RedisTestHarness::cleanup) or when the harness is dropped, which must happen inside a Tokio runtime.
+///
+/// It requires a Docker daemon and it is only available with the `test-harness` feature.
+#[derive(Debug)]
+pub struct RedisTestHarness {
+    /// The Redis container.
+    container: ContainerAsync<GenericImage>,
+
+    /// Client arguments to connect to the Redis container.
+    args: ClientArgs,
+
+    /// Name of the stream created for the test.
+    stream_name: String,
+
+    /// Name of the consumers group created for the test.
+    group_name: String,
+}
+
+impl RedisTestHarness {
+    /// Get **client args** to connect to the Redis container.
+    pub fn get_client_args(&self) -> &ClientArgs {
+        &self.args
+    }
+
+    /// Get **stream name**.
+    pub fn get_stream_name(&self) -> &str {
+        &self.stream_name
+    }
+
+    /// Get **group name**.
+    pub fn get_group_name(&self) -> &str {
+        &self.group_name
+    }
+
+    /// Start a new Redis container with the [`DEFAULT_REDIS_IMAGE`].
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a new [`RedisTestHarness`] instance. If the container can not be started or the Redis server is not ready, a [`RedsumerError`] is returned.
+    pub async fn start() -> RedsumerResult<Self> {
+        Self::start_with_image(DEFAULT_REDIS_IMAGE).await
+    }
+
+    /// Start a new Redis container with the given image.
+    ///
+    /// # Arguments:
+    /// - **image**: The Docker image of the Redis server, as `<name>:<tag>`. If the tag is missing, `latest` is used.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a new [`RedisTestHarness`] instance. If the container can not be started or the Redis server is not ready, a [`RedsumerError`] is returned.
+    pub async fn start_with_image(image: &str) -> RedsumerResult<Self> {
+        let (name, tag): (&str, &str) = image.rsplit_once(':').unwrap_or((image, "latest"));
+
+        let container: ContainerAsync<GenericImage> = GenericImage::new(name, tag)
+            .with_exposed_port(REDIS_PORT.tcp())
+            .with_wait_for(WaitFor::message_on_stdout(READY_MESSAGE))
+            .start()
+            .await
+            .map_err(container_error)?;
+
+        let host: String = container
+            .get_host()
+            .await
+            .map_err(container_error)?
+            .to_string();
+        let port: u16 = container
+            .get_host_port_ipv4(REDIS_PORT)
+            .await
+            .map_err(container_error)?;
+        let suffix: i128 = OffsetDateTime::now_utc().unix_timestamp_nanos();
+
+        let harness: RedisTestHarness = RedisTestHarness {
+            container,
+            args: ClientArgs::new(None, &host, port, 0, CommunicationProtocol::RESP2),
+            stream_name: format!("redsumer-test-stream-{suffix}"),
+            group_name: format!("redsumer-test-group-{suffix}"),
+        };
+
+        let mut client: Client = harness.get_client_args().build()?;
+        cmd("XGROUP")
+            .arg("CREATE")
+            .arg(harness.get_stream_name())
+            .arg(harness.get_group_name())
+            .arg("0")
+            .arg("MKSTREAM")
+            .query::<()>(&mut client)?;
+
+        info!(
+            "Redis test harness started in container {} on {host}:{port}",
+            harness.container.id()
+        );
+
+        Ok(harness)
+    }
+
+    /// Build a new [`Producer`] of the test stream.
+    pub fn producer(&self) -> RedsumerResult<Producer> {
+        Producer::new(
+            self.get_client_args(),
+            &ProducerConfig::new(self.get_stream_name()),
+        )
+    }
+
+    /// Build a new [`Consumer`] of the test stream and consumers group with default options.
+    ///
+    /// # Arguments:
+    /// - **consumer_name**: The name of the consumer.
+    pub fn consumer(&self, consumer_name: &str) -> RedsumerResult<Consumer> {
+        Consumer::new(
+            self.get_client_args().to_owned(),
+            ConsumerConfig::builder(self.get_stream_name(), self.get_group_name(), consumer_name)
+                .build()?,
+            StartPosition::Beginning,
+        )
+    }
+
+    /// Remove the Redis container right away, instead of when the harness is dropped.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the unit type. If the container can not be removed, a [`RedsumerError`] is returned.
+    pub async fn cleanup(self) -> RedsumerResult<()> {
+        self.container.rm().await.map_err(container_error)
+    }
+}
+
+#[cfg(test)]
+mod test_redis_test_harness {
+    use super::*;
+    use crate::redsumer::consumer::ConsumeMessagesReply;
+
+    #[tokio::test]
+    #[ignore = "requires a Docker daemon, run in CI with --ignored"]
+    async fn test_redis_test_harness_produce_and_consume() {
+        // Start a new harness:
+        let harness: RedisTestHarness = RedisTestHarness::start().await.unwrap();
+
+        // Produce a message:
+        let producer: Producer = harness.producer().unwrap();
+        producer
+            .produce_from_items(vec![("key", "value")])
+            .await
+            .unwrap();
+
+        // Consume the message:
+        let mut consumer: Consumer = harness.consumer("consumer").unwrap();
+        let reply: ConsumeMessagesReply = consumer.consume().await.unwrap();
+
+        // Verify the result:
+        assert!(reply.are_new());
+        assert_eq!(reply.get_messages().len(), 1);
+        harness.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,151 @@
+use redis::{streams::StreamId, Client};
+use time::OffsetDateTime;
+use tracing::{debug, info};
+
+#[allow(unused_imports)]
+use crate::core::{
+    client::{ClientArgs, RedisClientBuilder},
+    connection::VerifyConnection,
+    result::{RedsumerError, RedsumerResult},
+    streams::{
+        reader::{ReaderCommands, RANGE_END, RANGE_START},
+        types::Id,
+    },
+};
+
+/// Max number of messages read per request by [`StreamInspector::read_between`].
+const READ_BETWEEN_PAGE_SIZE: usize = 100;
+
+/// An inspection interface of a Redis stream. It reads stream contents without registering a consumers group, so the pending lists of the existing groups are not affected, which is useful for operators and test suites.
+///
+/// If a [replica](ClientArgs::with_replica) is set in the [`ClientArgs`], the stream is read from the replica, so heavy inspection workloads do not load the primary.
+#[derive(Debug, Clone)]
+pub struct StreamInspector {
+    /// Redis client to interact with Redis server.
+    client: Client,
+
+    /// Stream name to inspect.
+    stream_name: String,
+}
+
+impl StreamInspector {
+    /// Get [`Client`].
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get **stream name**.
+    pub fn get_stream_name(&self) -> &str {
+        &self.stream_name
+    }
+
+    /// Build a new [`StreamInspector`] instance.
+    ///
+    /// Before creating a new inspector, the following validations are performed:
+    ///
+    /// - If connection string is invalid, a [`RedsumerError`] is returned.
+    /// - If connection to Redis server, or to its replica if it is set, can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance.
+    /// - **stream_name**: Stream name to inspect. If a namespace is set in *args*, it is prefixed with it.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`StreamInspector`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs, stream_name: &str) -> RedsumerResult<StreamInspector> {
+        debug!(
+            "Creating a new stream inspector instance by: {:?} and {stream_name}",
+            args
+        );
+
+        let mut client: Client = args.build_replica()?;
+        client.ping()?;
+
+        info!("Stream inspector instance created successfully and it is ready to be used");
+
+        Ok(StreamInspector {
+            client,
+            stream_name: args.get_key(stream_name),
+        })
+    }
+
+    /// Peek messages of the stream between *start* and *end*, both inclusive, in ascending order, using `XRANGE`. Messages are not delivered to any consumers group.
+    ///
+    /// # Arguments:
+    /// - **start**: The ID to start reading from. If it is `None`, messages are read from the beginning of the stream.
+    /// - **end**: The ID to end reading at. If it is `None`, messages are read until the end of the stream.
+    /// - **count**: The max number of messages to read.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the messages found. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn peek(
+        &self,
+        start: Option<&Id>,
+        end: Option<&Id>,
+        count: usize,
+    ) -> RedsumerResult<Vec<StreamId>> {
+        let start: String = start.map_or(RANGE_START.to_owned(), |id| id.to_string());
+        let end: String = end.map_or(RANGE_END.to_owned(), |id| id.to_string());
+
+        debug!(
+            "Peeking messages of stream {} from {start} to {end}",
+            self.get_stream_name()
+        );
+
+        self.get_client()
+            .to_owned()
+            .read_range(self.get_stream_name(), start, end, count)
+    }
+
+    /// Peek the latest *count* messages of the stream, from the latest to the oldest, using `XREVRANGE`. It is useful for dashboards and debugging, to know what was produced last. Messages are not delivered to any consumers group.
+    ///
+    /// # Arguments:
+    /// - **count**: The max number of messages to read.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the messages found, in descending order by ID. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn peek_latest(&self, count: usize) -> RedsumerResult<Vec<StreamId>> {
+        debug!(
+            "Peeking the latest {count} messages of stream {}",
+            self.get_stream_name()
+        );
+
+        self.get_client().to_owned().read_rev_range(
+            self.get_stream_name(),
+            RANGE_END,
+            RANGE_START,
+            count,
+        )
+    }
+
+    /// Read all the messages of the stream produced between *from* and *to*, both inclusive, paging through the range with `XRANGE`. It is useful for investigations and backfills scoped to a time window. Messages are not delivered to any consumers group.
+    ///
+    /// Timestamps are converted to stream IDs with millisecond precision, so *from* matches the first message of its millisecond and *to* matches the last one of its millisecond.
+    ///
+    /// # Arguments:
+    /// - **from**: The timestamp to start reading from.
+    /// - **to**: The timestamp to end reading at.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the messages found, in ascending order by ID. If a timestamp is before the Unix epoch or an error occurs, a [`RedsumerError`] is returned.
+    pub async fn read_between(
+        &self,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> RedsumerResult<Vec<StreamId>> {
+        let start: Id = Id::from_timestamp(from)?;
+        let end: Id = Id::new(Id::from_timestamp(to)?.get_milliseconds(), u64::MAX);
+
+        debug!(
+            "Reading messages of stream {} between {from} and {to}",
+            self.get_stream_name()
+        );
+
+        self.get_client().to_owned().read_range_pages(
+            self.get_stream_name(),
+            start,
+            end,
+            READ_BETWEEN_PAGE_SIZE,
+        )
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Debug;
+
+use redis::streams::StreamId;
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+use crate::core::streams::types::Id;
+use crate::redsumer::consumer::MessagesKind;
+
+/// The fields of a message to produce, as encoded field and value pairs.
+pub type MessageFields = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// An interceptor to layer cross-cutting concerns, like enrichment, validation or auditing, on producer and consumer operations.
+///
+/// Interceptors are registered with `with_interceptor()` in [`Producer`](crate::producer::Producer) and [`Consumer`](crate::consumer::Consumer), and they are called in the same order they were registered. All methods have a default implementation that does nothing, so only the relevant hooks need to be implemented. If a hook returns an error, the operation is aborted and the error is returned to the caller.
+pub trait Interceptor: Debug + Send + Sync {
+    /// Called before producing a message. The message fields can be modified or extended.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **fields**: The fields of the message to produce.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` to continue with the operation. Otherwise, a [`RedsumerError`] to abort it.
+    fn before_produce(&self, _stream: &str, _fields: &mut MessageFields) -> RedsumerResult<()> {
+        Ok(())
+    }
+
+    /// Called after consuming messages, before they are returned to the caller. Messages can be modified or filtered out.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **group**: The consumers group name.
+    /// - **kind**: The kind of the consumed messages.
+    /// - **messages**: The consumed messages.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` to continue with the operation. Otherwise, a [`RedsumerError`] to abort it.
+    fn after_consume(
+        &self,
+        _stream: &str,
+        _group: &str,
+        _kind: &MessagesKind,
+        _messages: &mut Vec<StreamId>,
+    ) -> RedsumerResult<()> {
+        Ok(())
+    }
+
+    /// Called before acking a message.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **group**: The consumers group name.
+    /// - **id**: The ID of the message to ack.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` to continue with the operation. Otherwise, a [`RedsumerError`] to abort it.
+    fn before_ack(&self, _stream: &str, _group: &str, _id: &Id) -> RedsumerResult<()> {
+        Ok(())
+    }
+}
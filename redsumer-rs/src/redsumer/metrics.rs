@@ -0,0 +1,361 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tracing::warn;
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+use crate::redsumer::consumer::MessagesKind;
+
+/// Operations reported to a [`MetricsRecorder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// Produce a message in a stream.
+    Produce,
+
+    /// Consume messages from a stream.
+    Consume,
+
+    /// Ack a message.
+    Ack,
+}
+
+impl Operation {
+    /// Get the operation name, useful as a metric label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Produce => "produce",
+            Operation::Consume => "consume",
+            Operation::Ack => "ack",
+        }
+    }
+}
+
+/// A hook to instrument producers and consumers with the metrics stack of choice.
+///
+/// [`Producer`](crate::producer::Producer) and [`Consumer`](crate::consumer::Consumer) call the recorder at key points of their operations. All methods have an empty default implementation, so only the relevant metrics need to be implemented. Methods are called in the same task that runs the operation, so they should be cheap and must not block.
+pub trait MetricsRecorder: Debug + Send + Sync {
+    /// Record the messages obtained by a consume operation.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **group**: The consumers group name.
+    /// - **kind**: The kind of the consumed messages.
+    /// - **count**: The number of consumed messages.
+    fn record_consumed(&self, _stream: &str, _group: &str, _kind: &MessagesKind, _count: usize) {}
+
+    /// Record the messages claimed from other consumers by a consume operation.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **group**: The consumers group name.
+    /// - **count**: The number of claimed messages.
+    fn record_claimed(&self, _stream: &str, _group: &str, _count: usize) {}
+
+    /// Record the latency of a produce operation, including retries.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **latency**: The elapsed time of the operation.
+    fn record_produce_latency(&self, _stream: &str, _latency: Duration) {}
+
+    /// Record the latency of an ack operation.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **group**: The consumers group name.
+    /// - **latency**: The elapsed time of the operation.
+    fn record_ack_latency(&self, _stream: &str, _group: &str, _latency: Duration) {}
+
+    /// Record an error returned by an operation.
+    ///
+    /// # Arguments:
+    /// - **operation**: The failed [`Operation`].
+    /// - **stream**: The stream name.
+    /// - **error**: The returned [`RedsumerError`].
+    fn record_error(&self, _operation: Operation, _stream: &str, _error: &RedsumerError) {}
+
+    /// Record an operation slower than its threshold in [`SlowOperationThresholds`].
+    ///
+    /// # Arguments:
+    /// - **operation**: The slow [`Operation`].
+    /// - **stream**: The stream name.
+    /// - **latency**: The elapsed time of the operation.
+    fn record_slow_operation(&self, _operation: Operation, _stream: &str, _latency: Duration) {}
+}
+
+/// A [`MetricsRecorder`] that discards all metrics. It is used by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetricsRecorder;
+
+impl MetricsRecorder for NoopMetricsRecorder {}
+
+/// Latency thresholds to detect slow operations, so latency regressions are visible without a full metrics stack.
+///
+/// When an operation exceeds its threshold, a structured warning is emitted with `tracing` and [`MetricsRecorder::record_slow_operation`] is called. Operations without a threshold are never reported. The latency of a consume operation includes the time a blocking read waits for new messages.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlowOperationThresholds {
+    /// Latency thresholds by operation.
+    thresholds: HashMap<Operation, Duration>,
+}
+
+impl SlowOperationThresholds {
+    /// Build a new [`SlowOperationThresholds`] instance without thresholds.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A new [`SlowOperationThresholds`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the latency *threshold* of an *operation*.
+    ///
+    /// # Arguments:
+    /// - **operation**: The [`Operation`] to watch.
+    /// - **threshold**: The latency above which the operation is reported as slow.
+    ///
+    /// # Returns:
+    /// The [`SlowOperationThresholds`] instance with the given threshold.
+    pub fn with_threshold(mut self, operation: Operation, threshold: Duration) -> Self {
+        self.thresholds.insert(operation, threshold);
+        self
+    }
+
+    /// Get the latency threshold of an *operation*. It is `None` if the operation is not watched.
+    pub fn get_threshold(&self, operation: Operation) -> Option<Duration> {
+        self.thresholds.get(&operation).copied()
+    }
+
+    /// Report an *operation* on *stream* as slow if its *latency* exceeds its threshold.
+    ///
+    /// # Returns:
+    /// `true` if the operation was reported as slow.
+    pub(crate) fn check(
+        &self,
+        operation: Operation,
+        stream: &str,
+        latency: Duration,
+        recorder: &dyn MetricsRecorder,
+    ) -> bool {
+        match self.get_threshold(operation) {
+            Some(threshold) if latency > threshold => {
+                warn!(
+                    command = operation.as_str(),
+                    stream,
+                    duration_ms = latency.as_millis() as u64,
+                    threshold_ms = threshold.as_millis() as u64,
+                    "Slow {} operation on stream {stream}: {latency:?}",
+                    operation.as_str(),
+                );
+                recorder.record_slow_operation(operation, stream, latency);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A rolling window rate calculator, to report the throughput of producers and consumers in messages per second without a full metrics stack.
+///
+/// Messages are recorded with the instant they were processed, and the ones older than the window are discarded.
+#[derive(Debug)]
+pub struct RateMeter {
+    /// Length of the rolling window.
+    window: Duration,
+
+    /// Instants and counts of the messages recorded within the window, from the oldest to the newest.
+    samples: Mutex<VecDeque<(Instant, usize)>>,
+}
+
+impl RateMeter {
+    /// Build a new [`RateMeter`] instance.
+    ///
+    /// # Arguments:
+    /// - **window**: The length of the rolling window. It must be greater than `0`, otherwise the rate is always `0`.
+    ///
+    /// # Returns:
+    /// A new [`RateMeter`] instance without messages.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Get the length of the rolling window.
+    pub fn get_window(&self) -> Duration {
+        self.window
+    }
+
+    /// Record *count* messages processed now.
+    pub fn record(&self, count: usize) {
+        self.record_at(Instant::now(), count);
+    }
+
+    /// Get the rate of the messages recorded within the window, in messages per second.
+    pub fn current_rate(&self) -> f64 {
+        self.rate_at(Instant::now())
+    }
+
+    /// Record *count* messages processed at *now*, discarding the ones older than the window.
+    fn record_at(&self, now: Instant, count: usize) {
+        if count.eq(&0) {
+            return;
+        }
+
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        samples.push_back((now, count));
+        while samples
+            .front()
+            .is_some_and(|(instant, _)| now.saturating_duration_since(*instant) > self.window)
+        {
+            samples.pop_front();
+        }
+    }
+
+    /// Get the rate at *now* of the messages recorded within the window, in messages per second.
+    fn rate_at(&self, now: Instant) -> f64 {
+        if self.window.is_zero() {
+            return 0.0;
+        }
+
+        let total: usize = self
+            .samples
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(instant, _)| now.saturating_duration_since(*instant) <= self.window)
+            .map(|(_, count)| count)
+            .sum();
+
+        total as f64 / self.window.as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+mod test_operation {
+    use super::*;
+
+    #[test]
+    fn test_operation_as_str() {
+        // Verify the operation names:
+        assert_eq!(Operation::Produce.as_str(), "produce");
+        assert_eq!(Operation::Consume.as_str(), "consume");
+        assert_eq!(Operation::Ack.as_str(), "ack");
+    }
+}
+
+#[cfg(test)]
+mod test_rate_meter {
+    use super::*;
+
+    #[test]
+    fn test_rate_meter_current_rate() {
+        // Define the rate meter:
+        let meter: RateMeter = RateMeter::new(Duration::from_secs(10));
+        let start: Instant = Instant::now();
+
+        // Record messages:
+        meter.record_at(start, 30);
+        meter.record_at(start + Duration::from_secs(5), 20);
+        meter.record_at(start + Duration::from_secs(6), 0);
+
+        // Verify the result:
+        assert_eq!(meter.get_window(), Duration::from_secs(10));
+        assert_eq!(meter.rate_at(start + Duration::from_secs(5)), 5.0);
+        assert_eq!(meter.rate_at(start + Duration::from_secs(12)), 2.0);
+        assert_eq!(meter.rate_at(start + Duration::from_secs(20)), 0.0);
+    }
+
+    #[test]
+    fn test_rate_meter_discards_old_messages() {
+        // Define the rate meter:
+        let meter: RateMeter = RateMeter::new(Duration::from_secs(1));
+        let start: Instant = Instant::now();
+
+        // Record messages:
+        meter.record_at(start, 10);
+        meter.record_at(start + Duration::from_secs(2), 4);
+
+        // Verify the result:
+        assert_eq!(meter.samples.lock().unwrap().len(), 1);
+        assert_eq!(meter.rate_at(start + Duration::from_secs(2)), 4.0);
+    }
+
+    #[test]
+    fn test_rate_meter_zero_window() {
+        // Define the rate meter:
+        let meter: RateMeter = RateMeter::new(Duration::ZERO);
+
+        // Record messages:
+        meter.record(10);
+
+        // Verify the result:
+        assert_eq!(meter.current_rate(), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod test_slow_operation_thresholds {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// A recorder that counts the slow operations.
+    #[derive(Debug, Default)]
+    struct SlowCounter {
+        slow: AtomicUsize,
+    }
+
+    impl MetricsRecorder for SlowCounter {
+        fn record_slow_operation(&self, _operation: Operation, _stream: &str, _latency: Duration) {
+            self.slow.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_slow_operation_thresholds_check() {
+        // Define the thresholds and the recorder:
+        let thresholds: SlowOperationThresholds = SlowOperationThresholds::new()
+            .with_threshold(Operation::Ack, Duration::from_millis(100));
+        let recorder: SlowCounter = SlowCounter::default();
+
+        // Check fast, slow and unwatched operations:
+        let fast: bool = thresholds.check(
+            Operation::Ack,
+            "stream",
+            Duration::from_millis(50),
+            &recorder,
+        );
+        let slow: bool = thresholds.check(
+            Operation::Ack,
+            "stream",
+            Duration::from_millis(150),
+            &recorder,
+        );
+        let unwatched: bool = thresholds.check(
+            Operation::Produce,
+            "stream",
+            Duration::from_secs(10),
+            &recorder,
+        );
+
+        // Verify the result:
+        assert!(!fast);
+        assert!(slow);
+        assert!(!unwatched);
+        assert_eq!(recorder.slow.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            thresholds.get_threshold(Operation::Ack),
+            Some(Duration::from_millis(100))
+        );
+        assert!(thresholds.get_threshold(Operation::Consume).is_none());
+    }
+}
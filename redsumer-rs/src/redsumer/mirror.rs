@@ -0,0 +1,311 @@
+use redis::{
+    streams::{StreamAddOptions, StreamId},
+    Client, Commands,
+};
+use tracing::{debug, info};
+
+#[allow(unused_imports)]
+use crate::core::{
+    client::{ClientArgs, RedisClientBuilder},
+    connection::VerifyConnection,
+    result::{RedsumerError, RedsumerResult},
+    streams::{
+        producer::{message_to_items, ProducerCommands},
+        reader::{ReaderCommands, RANGE_END, RANGE_START},
+        types::Id,
+    },
+};
+use crate::redsumer::consumer::DEFAULT_MESSAGES_COUNT;
+
+/// Get the position of a mirror, which is the ID of the latest message of the destination stream, or `0-0` if it is empty.
+fn get_position<C>(destination: &mut C, destination_stream: &str) -> RedsumerResult<Id>
+where
+    C: Commands,
+{
+    match destination
+        .read_rev_range(destination_stream, RANGE_END, RANGE_START, 1)?
+        .first()
+    {
+        Some(latest) => latest.id.parse(),
+        None => Ok(Id::default()),
+    }
+}
+
+/// Read the messages of the source stream after *position* and produce them in the destination stream with the same IDs.
+///
+/// # Returns:
+/// A [`RedsumerResult`] with the ID of the latest message mirrored, or `None` if no messages were found.
+fn mirror_messages<S, D>(
+    source: &mut S,
+    destination: &mut D,
+    source_stream: &str,
+    destination_stream: &str,
+    position: &Id,
+    count: usize,
+    block: usize,
+) -> RedsumerResult<Option<Id>>
+where
+    S: Commands,
+    D: Commands,
+{
+    let messages: Vec<StreamId> = source.read_messages(&source_stream, position, count, block)?;
+
+    let mut latest: Option<Id> = None;
+    for message in messages.iter() {
+        let id: Id = destination.produce_from_items_with_id(
+            destination_stream,
+            &message.id.parse()?,
+            &message_to_items(message)?,
+            &StreamAddOptions::default(),
+        )?;
+        latest = Some(id);
+    }
+
+    Ok(latest)
+}
+
+/// A mirror that tails a stream in a source Redis server and republishes its messages in a stream of a destination Redis server, like another host or cluster. It is intended for migrations and disaster recovery replication.
+///
+/// Messages are produced in the destination stream with their original IDs, so the position of the mirror is the ID of the latest message of the destination stream. It is durable and survives restarts of the mirror: a new mirror resumes from the latest message mirrored, and a message can not be mirrored twice. Therefore, the destination stream must only be written by the mirror.
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    /// Redis client of the source server.
+    source: Client,
+
+    /// Redis client of the destination server.
+    destination: Client,
+
+    /// Stream name to mirror in the source server.
+    source_stream: String,
+
+    /// Stream name to produce messages in the destination server.
+    destination_stream: String,
+
+    /// ID of the latest message mirrored.
+    position: Id,
+
+    /// Max number of messages to mirror in each call.
+    count: usize,
+
+    /// Time in milliseconds to wait for new messages to arrive in the source stream.
+    block: usize,
+}
+
+impl Mirror {
+    /// Get **source stream** name.
+    pub fn get_source_stream(&self) -> &str {
+        &self.source_stream
+    }
+
+    /// Get **destination stream** name.
+    pub fn get_destination_stream(&self) -> &str {
+        &self.destination_stream
+    }
+
+    /// Get **position**, the ID of the latest message mirrored. It is `0-0` if no messages were mirrored yet.
+    pub fn get_position(&self) -> &Id {
+        &self.position
+    }
+
+    /// Set the max number of messages to mirror in each call. By default, it is [`DEFAULT_MESSAGES_COUNT`].
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Set the time in `milliseconds` to wait for new messages to arrive in the source stream. By default, it is `0` and calls do not block.
+    pub fn block(mut self, block: usize) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// Build a new [`Mirror`] instance.
+    ///
+    /// Before creating a new mirror, the following validations are performed:
+    ///
+    /// - If connection strings are invalid, a [`RedsumerError`] is returned.
+    /// - If connection to any Redis server can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// The position of the mirror is loaded from the latest message of the destination stream.
+    ///
+    /// # Arguments:
+    /// - **source**: Client arguments of the source Redis server.
+    /// - **source_stream**: Stream name to mirror in the source server.
+    /// - **destination**: Client arguments of the destination Redis server.
+    /// - **destination_stream**: Stream name to produce messages in the destination server.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`Mirror`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(
+        source: &ClientArgs,
+        source_stream: &str,
+        destination: &ClientArgs,
+        destination_stream: &str,
+    ) -> RedsumerResult<Mirror> {
+        debug!(
+            "Creating a new mirror instance from {:?} and {source_stream} to {:?} and {destination_stream}",
+            source, destination
+        );
+
+        let mut source: Client = source.build()?;
+        source.ping()?;
+
+        let mut destination: Client = destination.build()?;
+        destination.ping()?;
+
+        let position: Id = get_position(&mut destination, destination_stream)?;
+
+        info!("Mirror instance created successfully at position {position}");
+
+        Ok(Mirror {
+            source,
+            destination,
+            source_stream: source_stream.to_owned(),
+            destination_stream: destination_stream.to_owned(),
+            position,
+            count: DEFAULT_MESSAGES_COUNT,
+            block: 0,
+        })
+    }
+
+    /// Mirror the messages of the source stream after the position, and move the position to the latest message mirrored. It must be called in a loop to keep the destination stream in sync.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] with the ID of the latest message mirrored, or `None` if no messages arrive before the block time elapses. If an error occurs, a [`RedsumerError`] is returned and the position is reloaded from the destination stream, so the next call resumes from the latest message mirrored.
+    pub async fn sync(&mut self) -> RedsumerResult<Option<Id>> {
+        let mut destination: Client = self.destination.to_owned();
+
+        let result: RedsumerResult<Option<Id>> = mirror_messages(
+            &mut self.source.to_owned(),
+            &mut destination,
+            &self.source_stream,
+            &self.destination_stream,
+            &self.position,
+            self.count,
+            self.block,
+        );
+
+        match result {
+            Ok(Some(latest)) => {
+                debug!("Updating mirror position to: {latest}");
+                self.position = latest;
+                Ok(Some(latest))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                if let Ok(position) = get_position(&mut destination, &self.destination_stream) {
+                    self.position = position;
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_mirror_messages {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_get_position_of_empty_stream() {
+        // Create a mock connection:
+        let mut conn: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREVRANGE")
+                    .arg("mirror")
+                    .arg("+")
+                    .arg("-")
+                    .arg("COUNT")
+                    .arg(1),
+                Ok(Value::Array(vec![])),
+            )]);
+
+        // Verify the result:
+        assert_eq!(get_position(&mut conn, "mirror").unwrap(), Id::default());
+    }
+
+    #[test]
+    fn test_mirror_messages_ok() {
+        // Create a mock connection to the source server:
+        let mut source: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREAD")
+                    .arg("COUNT")
+                    .arg(10)
+                    .arg("STREAMS")
+                    .arg("stream")
+                    .arg("1-0"),
+                Ok(Value::Array(vec![Value::Array(vec![
+                    Value::BulkString(b"stream".to_vec()),
+                    Value::Array(vec![Value::Array(vec![
+                        Value::BulkString(b"2-0".to_vec()),
+                        Value::Array(vec![
+                            Value::BulkString(b"key".to_vec()),
+                            Value::BulkString(b"value".to_vec()),
+                        ]),
+                    ])]),
+                ])])),
+            )]);
+
+        // Create a mock connection to the destination server:
+        let mut destination: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XADD").arg("mirror").arg("2-0").arg("key").arg("value"),
+                Ok(Value::BulkString(b"2-0".to_vec())),
+            )]);
+
+        // Mirror the messages:
+        let result: RedsumerResult<Option<Id>> = mirror_messages(
+            &mut source,
+            &mut destination,
+            "stream",
+            "mirror",
+            &Id::new(1, 0),
+            10,
+            0,
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), Some(Id::new(2, 0)));
+    }
+
+    #[test]
+    fn test_mirror_messages_empty() {
+        // Create a mock connection to the source server:
+        let mut source: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("XREAD")
+                    .arg("BLOCK")
+                    .arg(100)
+                    .arg("COUNT")
+                    .arg(10)
+                    .arg("STREAMS")
+                    .arg("stream")
+                    .arg("1-0"),
+                Ok(Value::Nil),
+            )]);
+
+        // Create a mock connection to the destination server:
+        let mut destination: MockRedisConnection = MockRedisConnection::new(vec![]);
+
+        // Mirror the messages:
+        let result: RedsumerResult<Option<Id>> = mirror_messages(
+            &mut source,
+            &mut destination,
+            "stream",
+            "mirror",
+            &Id::new(1, 0),
+            10,
+            100,
+        );
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), None);
+    }
+}
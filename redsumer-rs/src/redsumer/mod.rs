@@ -1,2 +1,31 @@
+pub mod admin;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod buffer;
+pub mod checkpoint;
+pub mod clock;
 pub mod consumer;
+pub mod coordinator;
+pub mod dump;
+pub mod dynamic;
+pub mod envelope;
+pub mod guard;
+pub mod handler;
+#[cfg(feature = "test-harness")]
+pub mod harness;
+pub mod inspector;
+pub mod interceptor;
+pub mod metrics;
+pub mod mirror;
+pub mod notifier;
+pub mod priority;
 pub mod producer;
+pub mod propagation;
+pub mod reader;
+pub mod replayer;
+pub mod schema;
+pub mod scripting;
+pub mod testing;
+pub mod traits;
+pub mod validation;
+pub mod versioning;
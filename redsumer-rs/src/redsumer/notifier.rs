@@ -0,0 +1,237 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread::spawn,
+    time::{Duration, Instant},
+};
+
+use redis::{cmd, from_redis_value, Client, Connection, ErrorKind, PushInfo, PushKind};
+use tracing::{debug, info, warn};
+
+#[allow(unused_imports)]
+use crate::core::{
+    client::{ClientArgs, CommunicationProtocol, RedisClientBuilder},
+    result::{RedsumerError, RedsumerResult},
+};
+
+/// Keyspace event published by Redis when a message is added to a stream.
+const XADD_EVENT: &str = "xadd";
+
+/// Read timeout of the notifier connection, to stop listening soon after the notifier is dropped.
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// An event received by a [`StreamNotifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifierEvent {
+    /// New messages were added to the stream.
+    MessagesAdded,
+
+    /// The connection to the Redis server was lost. No more events are received, so a new notifier must be created once the server is reachable again.
+    Disconnected,
+
+    /// No events were received before the timeout elapsed.
+    Timeout,
+}
+
+/// Get the keyspace notifications channel of a stream.
+fn keyspace_channel(db: i64, stream_name: &str) -> String {
+    format!("__keyspace@{db}__:{stream_name}")
+}
+
+/// Convert a RESP3 push message into a [`NotifierEvent`]. Push messages unrelated to new messages or connection health are ignored.
+fn to_event(push: &PushInfo) -> Option<NotifierEvent> {
+    match push.kind {
+        PushKind::Message => match push.data.get(1).map(from_redis_value::<String>) {
+            Some(Ok(event)) if event.eq(XADD_EVENT) => Some(NotifierEvent::MessagesAdded),
+            _ => None,
+        },
+        PushKind::Disconnection => Some(NotifierEvent::Disconnected),
+        _ => None,
+    }
+}
+
+/// Read push messages from *connection* until *running* is unset or the connection is lost. Push messages are forwarded by the connection to its push sender.
+fn listen(mut connection: Connection, sender: Sender<PushInfo>, running: Arc<AtomicBool>) {
+    while running.load(Ordering::Relaxed) {
+        match connection.recv_response() {
+            Err(e) if e.is_timeout() => continue,
+            Err(e) if e.is_io_error() || e.is_connection_dropped() => {
+                warn!("Notifier connection lost: {:?}", e);
+                let _ = sender.send(PushInfo {
+                    kind: PushKind::Disconnection,
+                    data: Vec::new(),
+                });
+                break;
+            }
+            _ => continue,
+        }
+    }
+
+    debug!("Notifier stopped listening push messages");
+}
+
+/// A notifier of new messages in a Redis stream, driven by RESP3 push messages instead of polling.
+///
+/// It subscribes to the keyspace notifications of the stream in a dedicated connection, so consumers can wait for new messages and react to connection losses without sending read commands in a loop. For example, when [`Consumer::consume`](crate::consumer::Consumer::consume) finds no messages, a consumer can call [`wait`](StreamNotifier::wait) before consuming again.
+///
+/// The client arguments must use the [`CommunicationProtocol::RESP3`] protocol, and keyspace notifications for stream events must be enabled in the Redis server, for example with `CONFIG SET notify-keyspace-events Kt`. Notifications are delivered at most once, so consumers must keep reading pending and claimed messages periodically, and set a timeout when waiting.
+#[derive(Debug)]
+pub struct StreamNotifier {
+    /// Stream name to watch.
+    stream_name: String,
+
+    /// Receiver of the push messages of the notifier connection.
+    receiver: Receiver<PushInfo>,
+
+    /// Flag to stop listening push messages when the notifier is dropped.
+    running: Arc<AtomicBool>,
+}
+
+impl StreamNotifier {
+    /// Get **stream name**.
+    pub fn get_stream_name(&self) -> &str {
+        &self.stream_name
+    }
+
+    /// Build a new [`StreamNotifier`] instance.
+    ///
+    /// Before creating a new notifier, the following validations are performed:
+    ///
+    /// - If the protocol is not [`CommunicationProtocol::RESP3`], a [`RedsumerError`] is returned.
+    /// - If connection string is invalid, a [`RedsumerError`] is returned.
+    /// - If connection to Redis server can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance.
+    /// - **stream_name**: Stream name to watch. If a namespace is set in *args*, it is prefixed with it.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`StreamNotifier`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs, stream_name: &str) -> RedsumerResult<StreamNotifier> {
+        debug!(
+            "Creating a new stream notifier instance by: {:?} and {stream_name}",
+            args
+        );
+
+        if args.get_protocol().ne(&CommunicationProtocol::RESP3) {
+            return Err(RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid protocol",
+                "RESP3 is required to receive push messages".to_string(),
+            )));
+        }
+
+        let stream_name: &str = &args.get_key(stream_name);
+        let client: Client = args.build()?;
+        let mut connection: Connection = client.get_connection()?;
+        connection.set_read_timeout(Some(READ_TIMEOUT))?;
+
+        let (sender, receiver) = channel::<PushInfo>();
+        connection.set_push_sender(sender.to_owned());
+        connection.send_packed_command(
+            &cmd("SUBSCRIBE")
+                .arg(keyspace_channel(args.get_db(), stream_name))
+                .get_packed_command(),
+        )?;
+
+        let running: Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
+        let listener_running: Arc<AtomicBool> = running.to_owned();
+        spawn(move || listen(connection, sender, listener_running));
+
+        info!("Stream notifier instance created successfully and it is ready to be used");
+
+        Ok(StreamNotifier {
+            stream_name: stream_name.to_owned(),
+            receiver,
+            running,
+        })
+    }
+
+    /// Wait until new messages are added to the stream, the connection is lost or the *timeout* elapses.
+    ///
+    /// # Arguments:
+    /// - **timeout**: The max time to wait for an event.
+    ///
+    /// # Returns:
+    ///  - The [`NotifierEvent`] received.
+    pub async fn wait(&self, timeout: Duration) -> NotifierEvent {
+        let deadline: Instant = Instant::now() + timeout;
+
+        loop {
+            let remaining: Duration = deadline.saturating_duration_since(Instant::now());
+
+            match self.receiver.recv_timeout(remaining) {
+                Ok(push) => {
+                    if let Some(event) = to_event(&push) {
+                        debug!("Stream notifier event received: {event:?}");
+                        return event;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => return NotifierEvent::Timeout,
+                Err(RecvTimeoutError::Disconnected) => return NotifierEvent::Disconnected,
+            }
+        }
+    }
+}
+
+impl Drop for StreamNotifier {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod test_stream_notifier {
+    use redis::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_keyspace_channel() {
+        // Verify the result:
+        assert_eq!(keyspace_channel(2, "stream"), "__keyspace@2__:stream");
+    }
+
+    #[test]
+    fn test_to_event() {
+        // Define the push messages:
+        let push = |kind: PushKind, event: &str| PushInfo {
+            kind,
+            data: vec![
+                Value::BulkString(b"__keyspace@0__:stream".to_vec()),
+                Value::BulkString(event.as_bytes().to_vec()),
+            ],
+        };
+
+        // Verify the result:
+        assert_eq!(
+            to_event(&push(PushKind::Message, "xadd")),
+            Some(NotifierEvent::MessagesAdded)
+        );
+        assert_eq!(to_event(&push(PushKind::Message, "xtrim")), None);
+        assert_eq!(to_event(&push(PushKind::Subscribe, "xadd")), None);
+        assert_eq!(
+            to_event(&PushInfo {
+                kind: PushKind::Disconnection,
+                data: Vec::new(),
+            }),
+            Some(NotifierEvent::Disconnected)
+        );
+    }
+
+    #[test]
+    fn test_stream_notifier_requires_resp3() {
+        // Define the client arguments:
+        let args: ClientArgs =
+            ClientArgs::new(None, "localhost", 6379, 0, CommunicationProtocol::RESP2);
+
+        // Create a new notifier:
+        let result: RedsumerResult<StreamNotifier> = StreamNotifier::new(&args, "stream");
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+    }
+}
@@ -0,0 +1,345 @@
+use redis::ErrorKind;
+use tracing::debug;
+
+#[allow(unused_imports)]
+use crate::core::{
+    result::{RedsumerError, RedsumerResult},
+    streams::types::Id,
+};
+use crate::redsumer::consumer::{
+    AckMessageReply, AckToken, ConsumeMessagesReply, Consumer, MessagesKind,
+};
+
+/// Order in which a [`PriorityConsumer`] tries its streams on every consume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scheduling {
+    /// Streams are always tried from the highest priority to the lowest one, so lower priority streams are only consumed when the higher priority ones are empty.
+    #[default]
+    Priority,
+
+    /// Streams take turns, ignoring their priorities: every consume starts after the stream where messages were found the last time, so a busy stream can not starve the others.
+    RoundRobin,
+
+    /// Streams take turns in proportion to their priorities, used as weights: a stream with priority `3` gets three turns for every turn of a stream with priority `1`. A priority of `0` counts as `1`.
+    WeightedRoundRobin,
+}
+
+/// Get the turns of streams with the given *weights*, interleaved with the smooth weighted round robin algorithm, so a stream does not take all its turns in a row.
+fn get_weighted_turns(weights: &[u32]) -> Vec<usize> {
+    let total: i64 = weights.iter().map(|weight| i64::from(*weight)).sum();
+    let mut current: Vec<i64> = vec![0; weights.len()];
+    let mut turns: Vec<usize> = Vec::with_capacity(total as usize);
+
+    for _ in 0..total {
+        for (current, weight) in current.iter_mut().zip(weights.iter()) {
+            *current += i64::from(*weight);
+        }
+
+        let next: usize = current.iter().enumerate().fold(0, |max, (index, value)| {
+            match value.gt(&current[max]) {
+                true => index,
+                false => max,
+            }
+        });
+        current[next] -= total;
+        turns.push(next);
+    }
+
+    turns
+}
+
+/// A stream consumed by a [`PriorityConsumer`] with its priority.
+#[derive(Debug, Clone)]
+struct PriorityLane {
+    /// Priority of the stream. Streams with a higher value are consumed first.
+    priority: u32,
+
+    /// Consumer of the stream.
+    consumer: Consumer,
+}
+
+/// A reply of the consume operation of a [`PriorityConsumer`].
+#[derive(Debug, Clone)]
+pub struct PriorityConsumeReply {
+    /// Name of the stream where messages were found, if any.
+    stream_name: Option<String>,
+
+    /// Priority of the stream where messages were found, if any.
+    priority: Option<u32>,
+
+    /// Reply of the consumer of the stream.
+    reply: ConsumeMessagesReply,
+}
+
+impl PriorityConsumeReply {
+    /// Get the name of the stream where messages were found. If no messages were found in any stream, `None` is returned.
+    pub fn get_stream_name(&self) -> Option<&str> {
+        self.stream_name.as_deref()
+    }
+
+    /// Get the priority of the stream where messages were found. If no messages were found in any stream, `None` is returned.
+    pub fn get_priority(&self) -> Option<u32> {
+        self.priority
+    }
+
+    /// Get the [`ConsumeMessagesReply`] of the stream where messages were found.
+    pub fn get_reply(&self) -> &ConsumeMessagesReply {
+        &self.reply
+    }
+}
+
+/// A consumer of several streams with priorities, to implement lanes like urgent and bulk messages.
+///
+/// Every call to [`consume`](PriorityConsumer::consume) tries the streams from the highest priority to the lowest one, and returns the messages of the first stream where new, pending or claimed messages are found. So lower priority streams are only consumed when the higher priority ones are empty. To share the consumer fairly between the streams instead, set a round robin [`Scheduling`].
+///
+/// Every stream is consumed by its own [`Consumer`], with its own configuration. Since streams are tried in order, a block time in a high priority stream delays the lower priority ones, so it is recommended to block only in the lowest priority stream.
+#[derive(Debug, Clone, Default)]
+pub struct PriorityConsumer {
+    /// Streams consumed, sorted from the highest priority to the lowest one.
+    lanes: Vec<PriorityLane>,
+
+    /// Order in which the streams are tried.
+    scheduling: Scheduling,
+
+    /// Position in the turns of the stream tried first in the next consume.
+    next_turn: usize,
+}
+
+impl PriorityConsumer {
+    /// Build a new [`PriorityConsumer`] instance without streams.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    /// A new [`PriorityConsumer`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a stream with its *priority*. Streams with a higher value are consumed first. Streams with the same priority are consumed in the order they were added.
+    ///
+    /// # Arguments:
+    /// - **priority**: The priority of the stream.
+    /// - **consumer**: The [`Consumer`] of the stream.
+    ///
+    /// # Returns:
+    /// The [`PriorityConsumer`] instance with the stream added.
+    pub fn with_stream(mut self, priority: u32, consumer: Consumer) -> Self {
+        let position: usize = self
+            .lanes
+            .iter()
+            .position(|lane| lane.priority.lt(&priority))
+            .unwrap_or(self.lanes.len());
+        self.lanes
+            .insert(position, PriorityLane { priority, consumer });
+        self
+    }
+
+    /// Set the order in which the streams are tried on every consume. By default, it is [`Scheduling::Priority`].
+    pub fn with_scheduling(mut self, scheduling: Scheduling) -> Self {
+        self.scheduling = scheduling;
+        self.next_turn = 0;
+        self
+    }
+
+    /// Get **scheduling**.
+    pub fn get_scheduling(&self) -> Scheduling {
+        self.scheduling
+    }
+
+    /// Get the turns of the streams, as positions in the lanes, according to the scheduling.
+    fn get_turns(&self) -> Vec<usize> {
+        match self.scheduling {
+            Scheduling::Priority | Scheduling::RoundRobin => (0..self.lanes.len()).collect(),
+            Scheduling::WeightedRoundRobin => get_weighted_turns(
+                &self
+                    .lanes
+                    .iter()
+                    .map(|lane| lane.priority.max(1))
+                    .collect::<Vec<u32>>(),
+            ),
+        }
+    }
+
+    /// Get the name and the priority of the streams, sorted from the highest priority to the lowest one.
+    pub fn get_streams(&self) -> Vec<(&str, u32)> {
+        self.lanes
+            .iter()
+            .map(|lane| (lane.consumer.get_config().get_stream_name(), lane.priority))
+            .collect()
+    }
+
+    /// Get the [`Consumer`] of the stream *stream_name*, if it is consumed.
+    pub fn get_consumer(&self, stream_name: &str) -> Option<&Consumer> {
+        self.lanes
+            .iter()
+            .map(|lane| &lane.consumer)
+            .find(|consumer| consumer.get_config().get_stream_name().eq(stream_name))
+    }
+
+    /// Consume messages from the first stream where messages are found, trying the streams in the order of the [`Scheduling`].
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing a [`PriorityConsumeReply`] with the messages and the stream where they were found. If no messages are found in any stream, the reply has no stream and its messages are empty. If an error occurs in any stream, a [`RedsumerError`] is returned and the next streams are not consumed.
+    pub async fn consume(&mut self) -> RedsumerResult<PriorityConsumeReply> {
+        let turns: Vec<usize> = self.get_turns();
+        let mut tried: Vec<bool> = vec![false; self.lanes.len()];
+
+        for offset in 0..turns.len() {
+            let turn: usize = (self.next_turn + offset) % turns.len();
+            let index: usize = turns[turn];
+            if tried[index] {
+                continue;
+            }
+            tried[index] = true;
+
+            let lane: &mut PriorityLane = &mut self.lanes[index];
+            let reply: ConsumeMessagesReply = lane.consumer.consume().await?;
+
+            if !reply.not_found() {
+                if self.scheduling.ne(&Scheduling::Priority) {
+                    self.next_turn = (turn + 1) % turns.len();
+                }
+
+                debug!(
+                    "Messages found in stream {} with priority {}",
+                    lane.consumer.get_config().get_stream_name(),
+                    lane.priority
+                );
+
+                return Ok(PriorityConsumeReply {
+                    stream_name: Some(lane.consumer.get_config().get_stream_name().to_owned()),
+                    priority: Some(lane.priority),
+                    reply,
+                });
+            }
+        }
+
+        Ok(PriorityConsumeReply {
+            stream_name: None,
+            priority: None,
+            reply: (Vec::new(), MessagesKind::NotFound).into(),
+        })
+    }
+
+    /// Ack a message by its *token*, with the consumer of the stream the token was issued for.
+    ///
+    /// # Arguments:
+    /// - **token**: The [`AckToken`] of the message, issued by [`consume`](PriorityConsumer::consume).
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing an [`AckMessageReply`] if successful. If the stream is not consumed or an error occurs, a [`RedsumerError`] is returned.
+    pub async fn ack(&self, token: &AckToken) -> RedsumerResult<AckMessageReply> {
+        let stream_name: &str = token.get_stream_name();
+        match self.get_consumer(stream_name) {
+            Some(consumer) => consumer.ack(token).await,
+            None => Err(RedsumerError::from((
+                ErrorKind::ClientError,
+                "Unknown stream",
+                format!("The stream {stream_name} is not consumed by the priority consumer"),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_priority_consumer {
+    use super::*;
+    use crate::redsumer::consumer::ConsumerConfig;
+
+    /// Build a paused consumer of *stream_name*, which consumes without reaching the server.
+    fn consumer(stream_name: &str) -> Consumer {
+        let consumer: Consumer = Consumer::unconnected(
+            ConsumerConfig::builder(stream_name, "group", "consumer")
+                .build()
+                .unwrap(),
+        );
+        consumer.pause();
+        consumer
+    }
+
+    #[test]
+    fn test_priority_consumer_streams_order() {
+        // Build a priority consumer:
+        let consumer: PriorityConsumer = PriorityConsumer::new()
+            .with_stream(1, consumer("bulk"))
+            .with_stream(10, consumer("urgent"))
+            .with_stream(5, consumer("normal"))
+            .with_stream(10, consumer("urgent-2"));
+
+        // Verify the result:
+        assert_eq!(
+            consumer.get_streams(),
+            vec![("urgent", 10), ("urgent-2", 10), ("normal", 5), ("bulk", 1)]
+        );
+        assert!(consumer.get_consumer("normal").is_some());
+        assert!(consumer.get_consumer("unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_priority_consumer_consume_not_found() {
+        // Build a priority consumer of paused streams:
+        let mut consumer: PriorityConsumer = PriorityConsumer::new()
+            .with_stream(10, consumer("urgent"))
+            .with_stream(1, consumer("bulk"));
+
+        // Consume messages:
+        let reply: PriorityConsumeReply = consumer.consume().await.unwrap();
+
+        // Verify the result:
+        assert!(reply.get_stream_name().is_none());
+        assert!(reply.get_priority().is_none());
+        assert!(reply.get_reply().not_found());
+    }
+
+    #[test]
+    fn test_get_weighted_turns() {
+        // Verify the turns are interleaved in proportion to the weights:
+        assert_eq!(get_weighted_turns(&[3, 1]), vec![0, 0, 1, 0]);
+        assert_eq!(get_weighted_turns(&[1, 1, 1]), vec![0, 1, 2]);
+        assert_eq!(get_weighted_turns(&[5, 1, 1]), vec![0, 0, 1, 0, 2, 0, 0]);
+        assert!(get_weighted_turns(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_priority_consumer_turns() {
+        // Build a priority consumer:
+        let consumer: PriorityConsumer = PriorityConsumer::new()
+            .with_stream(2, consumer("urgent"))
+            .with_stream(0, consumer("bulk"));
+
+        // Verify the turns of every scheduling:
+        assert_eq!(consumer.get_scheduling(), Scheduling::Priority);
+        assert_eq!(consumer.get_turns(), vec![0, 1]);
+        assert_eq!(
+            consumer
+                .to_owned()
+                .with_scheduling(Scheduling::RoundRobin)
+                .get_turns(),
+            vec![0, 1]
+        );
+        assert_eq!(
+            consumer
+                .with_scheduling(Scheduling::WeightedRoundRobin)
+                .get_turns(),
+            vec![0, 1, 0]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_priority_consumer_ack_unknown_stream() {
+        // Build a priority consumer:
+        let consumer: PriorityConsumer = PriorityConsumer::new().with_stream(1, consumer("bulk"));
+
+        // Ack a message of an unknown stream:
+        let token: AckToken = AckToken::new(Id::new(1, 0), "unknown", "group", "consumer");
+        let result: RedsumerResult<AckMessageReply> = consumer.ack(&token).await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+}
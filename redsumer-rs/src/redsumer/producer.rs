@@ -1,19 +1,276 @@
-use redis::{Client, ToRedisArgs};
-use tracing::{debug, info};
+use std::{
+    io::BufRead,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use redis::{
+    streams::{StreamAddOptions, StreamTrimOptions, StreamTrimStrategy, StreamTrimmingMode},
+    Client, ErrorKind, ToRedisArgs,
+};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tracing::{debug, error, info, instrument, warn, Span};
 
 #[allow(unused_imports)]
 use crate::core::{
+    blocking::run_blocking,
     client::{ClientArgs, ClientCredentials, RedisClientBuilder},
-    connection::VerifyConnection,
+    connection::{
+        ServerCapabilities, ServerCommands, ServerFeature, StreamConnection, VerifyConnection,
+    },
     result::{RedsumerError, RedsumerResult},
     streams::{producer::ProducerCommands, types::Id},
 };
+use crate::redsumer::dump::{DumpedMessage, ImportIdMode, ImportSummary};
+use crate::redsumer::envelope::SCHEMA_VERSION_HEADER;
+use crate::redsumer::interceptor::{Interceptor, MessageFields};
+use crate::redsumer::metrics::{
+    MetricsRecorder, NoopMetricsRecorder, Operation, RateMeter, SlowOperationThresholds,
+};
+use crate::redsumer::propagation::TraceContextPropagator;
+use crate::redsumer::validation::Validator;
+
+/// Policy to trim the stream every time a new message is produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrimmingPolicy {
+    /// Evict the oldest entries as long as the stream length exceeds *max_entries*.
+    MaxLen {
+        /// Maximum number of entries to keep in the stream.
+        max_entries: usize,
+
+        /// If `true`, the `~` modifier is used and Redis trims the stream only when a whole node can be removed, which is more efficient.
+        approximate: bool,
+    },
+
+    /// Evict the entries with an ID lower than *min_id*.
+    MinId {
+        /// Minimum ID to keep in the stream.
+        min_id: Id,
+
+        /// If `true`, the `~` modifier is used and Redis trims the stream only when a whole node can be removed, which is more efficient.
+        approximate: bool,
+    },
+}
+
+impl TrimmingPolicy {
+    /// Get the [`StreamTrimmingMode`] of the policy.
+    fn get_mode(approximate: bool) -> StreamTrimmingMode {
+        match approximate {
+            true => StreamTrimmingMode::Approx,
+            false => StreamTrimmingMode::Exact,
+        }
+    }
+
+    /// Convert the policy into a [`StreamTrimStrategy`].
+    fn to_trim_strategy(&self) -> StreamTrimStrategy {
+        match self {
+            TrimmingPolicy::MaxLen {
+                max_entries,
+                approximate,
+            } => StreamTrimStrategy::maxlen(Self::get_mode(*approximate), *max_entries),
+            TrimmingPolicy::MinId {
+                min_id,
+                approximate,
+            } => StreamTrimStrategy::minid(Self::get_mode(*approximate), min_id.to_string()),
+        }
+    }
+
+    /// Convert the policy into the [`StreamTrimOptions`] of the `XTRIM` command.
+    fn to_trim_options(&self) -> StreamTrimOptions {
+        match self {
+            TrimmingPolicy::MaxLen {
+                max_entries,
+                approximate,
+            } => StreamTrimOptions::maxlen(Self::get_mode(*approximate), *max_entries),
+            TrimmingPolicy::MinId {
+                min_id,
+                approximate,
+            } => StreamTrimOptions::minid(Self::get_mode(*approximate), min_id.to_string()),
+        }
+    }
+}
+
+/// Policy to trim the stream periodically, once every *N* produced messages, with a separate `XTRIM` command.
+///
+/// Unlike [`TrimmingPolicy`], which is applied by every `XADD`, it keeps retention enforcement close to the write path at a lower cost, without a separate cron.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrimPolicy {
+    /// Number of produced messages between trims.
+    every_n_messages: usize,
+
+    /// Strategy used to trim the stream.
+    strategy: TrimmingPolicy,
+}
+
+impl TrimPolicy {
+    /// Get **every n messages**.
+    pub fn get_every_n_messages(&self) -> usize {
+        self.every_n_messages
+    }
+
+    /// Get **strategy**.
+    pub fn get_strategy(&self) -> &TrimmingPolicy {
+        &self.strategy
+    }
+
+    /// Build a new [`TrimPolicy`] instance.
+    ///
+    /// # Arguments:
+    /// - **every_n_messages**: The number of produced messages between trims. It must be greater than `0`.
+    /// - **strategy**: The [`TrimmingPolicy`] used to trim the stream, like `MAXLEN` or `MINID`.
+    ///
+    /// # Returns:
+    /// A new instance of [`TrimPolicy`].
+    pub fn new(every_n_messages: usize, strategy: TrimmingPolicy) -> Self {
+        TrimPolicy {
+            every_n_messages,
+            strategy,
+        }
+    }
+
+    /// Verify if the stream must be trimmed after producing *produced* messages, when the total number of messages produced before was *previous*.
+    fn is_due(&self, previous: usize, produced: usize) -> bool {
+        self.every_n_messages.gt(&0)
+            && (previous + produced) / self.every_n_messages > previous / self.every_n_messages
+    }
+}
+
+/// Policy to confirm the replication of every produced message with `WAIT`, for workloads that can not tolerate losing acknowledged messages on failover.
+///
+/// `WAIT` does not make Redis strongly consistent: a message acknowledged by fewer replicas than requested is still produced, and the reply reports the replication level reached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WaitPolicy {
+    /// Number of replicas that must acknowledge every produced message.
+    replicas: usize,
+
+    /// Max time to wait for the replicas. If it is zero, the producer waits until the replicas acknowledge the message.
+    #[serde(with = "crate::redsumer::consumer::duration_milliseconds")]
+    timeout: Duration,
+}
+
+impl WaitPolicy {
+    /// Get **replicas**.
+    pub fn get_replicas(&self) -> usize {
+        self.replicas
+    }
+
+    /// Get **timeout**.
+    pub fn get_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Build a new [`WaitPolicy`] instance.
+    ///
+    /// # Arguments:
+    /// - **replicas**: The number of replicas that must acknowledge every produced message. It must be greater than `0`.
+    /// - **timeout**: The max time to wait for the replicas. If it is zero, the producer waits until the replicas acknowledge the message.
+    ///
+    /// # Returns:
+    /// A new instance of [`WaitPolicy`].
+    pub fn new(replicas: usize, timeout: Duration) -> Self {
+        WaitPolicy { replicas, timeout }
+    }
+}
+
+/// Codec used by default to encode message field values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldCodec {
+    /// Field values are written as they are given.
+    #[default]
+    Raw,
+
+    /// Field values are written as JSON documents, so they can be read with [`ValueHandler::to_json_value`](crate::core::value::ValueHandler::to_json_value). Values that already are a JSON document, like `{"id":1}`, `42` or `true`, are written as they are, and any other value is written as a JSON string.
+    Json,
+}
+
+impl FieldCodec {
+    /// Encode the values of the given message *args*, given as pairs of field and value. The field names are written as they are given.
+    ///
+    /// # Arguments:
+    /// - **args**: The encoded fields and values of the message.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the args with encoded values. If a value can not be encoded, like a binary value with the [`FieldCodec::Json`] codec, a [`RedsumerError`] is returned.
+    fn encode(&self, mut args: Vec<Vec<u8>>) -> RedsumerResult<Vec<Vec<u8>>> {
+        if self.eq(&FieldCodec::Raw) {
+            return Ok(args);
+        }
+
+        for value in args.iter_mut().skip(1).step_by(2) {
+            if serde_json::from_slice::<IgnoredAny>(value).is_ok() {
+                continue;
+            }
+
+            let text: &str = std::str::from_utf8(value).map_err(|e| {
+                error!("Error encoding field value as JSON: {:?}", e);
+                RedsumerError::from((
+                    ErrorKind::TypeError,
+                    "Invalid message",
+                    format!("Field value is not a valid UTF-8 string: {e}"),
+                ))
+            })?;
+            *value = JsonValue::String(text.to_owned()).to_string().into_bytes();
+        }
+
+        Ok(args)
+    }
+}
+
+/// Default number of retries when a message can not be produced because of a connection error.
+pub const DEFAULT_MAX_RETRIES: usize = 0;
+
+/// Default time to wait before retrying a message that can not be produced because of a connection error.
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(100);
 
 /// Define the configuration parameters to create a producer instance.
-#[derive(Debug, Clone)]
+///
+/// It can be loaded from any format supported by [serde](https://docs.rs/serde). Missing options take the same default values used by [`ProducerConfigBuilder`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProducerConfig {
-    // Stream name where messages will be produced.
+    /// Stream name where messages will be produced.
     stream_name: String,
+
+    /// Policy to trim the stream when a new message is produced.
+    #[serde(default)]
+    trimming_policy: Option<TrimmingPolicy>,
+
+    /// Policy to trim the stream periodically, once every *N* produced messages.
+    #[serde(default)]
+    trim_policy: Option<TrimPolicy>,
+
+    /// If `true`, the stream is not created when it does not exist.
+    #[serde(default)]
+    nomkstream: bool,
+
+    /// Time to live of the stream in seconds, refreshed every time a message is produced. If it is not set, the stream never expires.
+    #[serde(default)]
+    stream_ttl_seconds: Option<u64>,
+
+    /// Codec used by default to encode message field values.
+    #[serde(default)]
+    field_codec: FieldCodec,
+
+    /// Number of times to retry producing a message after a retryable error, like a connection error. Retries may produce a message twice, see [`ProducerConfigBuilder::max_retries`].
+    #[serde(default)]
+    max_retries: usize,
+
+    /// Time to wait before every retry. It is serialized in `milliseconds`.
+    #[serde(
+        default = "default_retry_delay",
+        with = "crate::redsumer::consumer::duration_milliseconds"
+    )]
+    retry_delay: Duration,
+
+    /// Policy to confirm the replication of every produced message. If it is not set, the producer does not wait for replicas.
+    #[serde(default)]
+    wait_policy: Option<WaitPolicy>,
 }
 
 impl ProducerConfig {
@@ -22,7 +279,94 @@ impl ProducerConfig {
         &self.stream_name
     }
 
-    /// Create a new [`ProducerConfig`] instance.
+    /// Get **trimming policy**.
+    pub fn get_trimming_policy(&self) -> Option<&TrimmingPolicy> {
+        self.trimming_policy.as_ref()
+    }
+
+    /// Get **trim policy**.
+    pub fn get_trim_policy(&self) -> Option<&TrimPolicy> {
+        self.trim_policy.as_ref()
+    }
+
+    /// Get **nomkstream**.
+    pub fn get_nomkstream(&self) -> bool {
+        self.nomkstream
+    }
+
+    /// Get **stream ttl seconds**.
+    pub fn get_stream_ttl_seconds(&self) -> Option<u64> {
+        self.stream_ttl_seconds
+    }
+
+    /// Get **field codec**.
+    pub fn get_field_codec(&self) -> FieldCodec {
+        self.field_codec
+    }
+
+    /// Get **max retries**.
+    pub fn get_max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    /// Get **retry delay**.
+    pub fn get_retry_delay(&self) -> Duration {
+        self.retry_delay
+    }
+
+    /// Get **wait policy**.
+    pub fn get_wait_policy(&self) -> Option<&WaitPolicy> {
+        self.wait_policy.as_ref()
+    }
+
+    /// Get the server features required by the configuration: `NOMKSTREAM` and the `MINID` trimming strategy.
+    fn get_required_features(&self) -> Vec<ServerFeature> {
+        let mut features: Vec<ServerFeature> = Vec::new();
+
+        if self.get_nomkstream() {
+            features.push(ServerFeature::NoMkStream);
+        }
+
+        let strategies = self
+            .get_trimming_policy()
+            .into_iter()
+            .chain(self.get_trim_policy().map(TrimPolicy::get_strategy));
+        for strategy in strategies {
+            if matches!(strategy, TrimmingPolicy::MinId { .. }) {
+                features.push(ServerFeature::TrimMinId);
+            }
+        }
+
+        features
+    }
+
+    /// Build the `XADD` options according to the configuration.
+    fn get_add_options(&self) -> StreamAddOptions {
+        let mut options: StreamAddOptions = StreamAddOptions::default();
+
+        if self.get_nomkstream() {
+            options = options.nomkstream();
+        }
+
+        if let Some(policy) = self.get_trimming_policy() {
+            options = options.trim(policy.to_trim_strategy());
+        }
+
+        options
+    }
+
+    /// Create a new [`ProducerConfigBuilder`] instance to build a [`ProducerConfig`] with chained setters and default options.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream where messages will be produced.
+    ///
+    /// # Returns:
+    /// A new [`ProducerConfigBuilder`] instance.
+    pub fn builder(stream_name: &str) -> ProducerConfigBuilder {
+        ProducerConfigBuilder::new(stream_name)
+    }
+
+    /// Create a new [`ProducerConfig`] instance with default options.
     ///
     /// # Arguments:
     /// - **stream_name**: The name of the stream where messages will be produced.
@@ -32,7 +376,145 @@ impl ProducerConfig {
     pub fn new(stream_name: &str) -> Self {
         ProducerConfig {
             stream_name: stream_name.to_owned(),
+            trimming_policy: None,
+            trim_policy: None,
+            nomkstream: false,
+            stream_ttl_seconds: None,
+            field_codec: FieldCodec::default(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            wait_policy: None,
+        }
+    }
+}
+
+/// Default retry delay of a deserialized [`ProducerConfig`].
+fn default_retry_delay() -> Duration {
+    DEFAULT_RETRY_DELAY
+}
+
+/// A builder to create a [`ProducerConfig`] instance with chained setters.
+///
+/// The stream name is mandatory. By default, the stream is not trimmed, it is created if it does not exist, field values are written as they are given and failed messages are not retried.
+#[derive(Debug, Clone)]
+pub struct ProducerConfigBuilder {
+    /// Producer configuration being built.
+    config: ProducerConfig,
+}
+
+impl ProducerConfigBuilder {
+    /// Create a new [`ProducerConfigBuilder`] instance with default options.
+    ///
+    /// # Arguments:
+    /// - **stream_name**: The name of the stream where messages will be produced.
+    ///
+    /// # Returns:
+    /// A new [`ProducerConfigBuilder`] instance.
+    pub fn new(stream_name: &str) -> Self {
+        ProducerConfigBuilder {
+            config: ProducerConfig::new(stream_name),
+        }
+    }
+
+    /// Set the policy to trim the stream when a new message is produced.
+    pub fn trimming_policy(mut self, policy: TrimmingPolicy) -> Self {
+        self.config.trimming_policy = Some(policy);
+        self
+    }
+
+    /// Set the policy to trim the stream periodically, once every *N* produced messages.
+    pub fn trim_policy(mut self, policy: TrimPolicy) -> Self {
+        self.config.trim_policy = Some(policy);
+        self
+    }
+
+    /// Set the *NOMKSTREAM* flag. If `true`, producing in a stream that does not exist fails instead of creating it.
+    pub fn nomkstream(mut self, nomkstream: bool) -> Self {
+        self.config.nomkstream = nomkstream;
+        self
+    }
+
+    /// Set the time to live of the stream in seconds. It is set with `EXPIRE` and refreshed every time a message is produced, so ephemeral streams, like per-session or per-tenant streams, are deleted by Redis once they are no longer written.
+    pub fn stream_ttl_seconds(mut self, seconds: u64) -> Self {
+        self.config.stream_ttl_seconds = Some(seconds);
+        self
+    }
+
+    /// Set the codec used by default to encode message field values. It is applied to the messages produced with [`Producer::produce_from_map`], [`Producer::produce_from_items`] and the bulk produce methods, before the interceptors. The trace context fields and the schema version header are written as they are.
+    pub fn field_codec(mut self, codec: FieldCodec) -> Self {
+        self.config.field_codec = codec;
+        self
+    }
+
+    /// Set the number of times to retry producing a message after a retryable error, like a connection error. See [`RedsumerError::is_retryable`].
+    ///
+    /// Retries are not idempotent: a command that fails after it was sent, like on a read timeout or a dropped connection, may have been applied by the server, and retrying an `XADD` with an auto-generated ID (`*`) produces the message again. Consumers must tolerate duplicates when retries are enabled. Messages imported with [`ImportIdMode::Preserve`] keep their explicit ID, so a retry of an applied message fails instead of duplicating it.
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.config.max_retries = max_retries;
+        self
+    }
+
+    /// Set the time to wait before every retry, so a server that is failing over or restarting is not flooded with commands. If it is zero, commands are retried right away.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.config.retry_delay = delay;
+        self
+    }
+
+    /// Set the policy to confirm the replication of every message produced with [`Producer::produce_from_map`] or [`Producer::produce_from_items`], issuing `WAIT` after `XADD`.
+    pub fn wait_policy(mut self, policy: WaitPolicy) -> Self {
+        self.config.wait_policy = Some(policy);
+        self
+    }
+
+    /// Build a new [`ProducerConfig`] instance.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a new [`ProducerConfig`] instance. If the stream name is empty, the trim policy runs every `0` messages, the stream time to live is `0` or the wait policy requests `0` replicas, a [`RedsumerError`] is returned.
+    pub fn build(self) -> RedsumerResult<ProducerConfig> {
+        if self.config.get_stream_name().is_empty() {
+            return Err(RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid producer configuration",
+                "The stream name must not be empty".to_string(),
+            )));
+        }
+
+        if self
+            .config
+            .get_trim_policy()
+            .is_some_and(|policy| policy.get_every_n_messages().eq(&0))
+        {
+            return Err(RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid producer configuration",
+                "The trim policy must run every one or more messages".to_string(),
+            )));
+        }
+
+        if self.config.get_stream_ttl_seconds().eq(&Some(0)) {
+            return Err(RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid producer configuration",
+                "The stream time to live must be greater than zero".to_string(),
+            )));
+        }
+
+        if self
+            .config
+            .get_wait_policy()
+            .is_some_and(|policy| policy.get_replicas().eq(&0))
+        {
+            return Err(RedsumerError::from((
+                ErrorKind::InvalidClientConfig,
+                "Invalid producer configuration",
+                "The wait policy must request one or more replicas".to_string(),
+            )));
         }
+
+        Ok(self.config)
     }
 }
 
@@ -41,6 +523,12 @@ impl ProducerConfig {
 pub struct ProduceMessageReply {
     /// *ID* of the produced message.
     id: Id,
+
+    /// Number of replicas requested by the [`WaitPolicy`], if it is configured.
+    requested_replicas: Option<usize>,
+
+    /// Number of replicas that acknowledged the message, if a [`WaitPolicy`] is configured.
+    acknowledged_replicas: Option<usize>,
 }
 
 impl ProduceMessageReply {
@@ -48,49 +536,139 @@ impl ProduceMessageReply {
     pub fn get_id(&self) -> &Id {
         &self.id
     }
+
+    /// Get the number of **acknowledged replicas**. It is `None` if no [`WaitPolicy`] is configured.
+    pub fn get_acknowledged_replicas(&self) -> Option<usize> {
+        self.acknowledged_replicas
+    }
+
+    /// Verify if the replication level requested by the [`WaitPolicy`] was reached. It is `None` if no [`WaitPolicy`] is configured.
+    pub fn replication_reached(&self) -> Option<bool> {
+        self.requested_replicas
+            .zip(self.acknowledged_replicas)
+            .map(|(requested, acknowledged)| acknowledged.ge(&requested))
+    }
 }
 
-/// Convert a `ID` to a [`ProduceMessageReply`] instance.
+/// Convert an [`Id`] to a [`ProduceMessageReply`] instance.
 impl From<Id> for ProduceMessageReply {
     fn from(id: Id) -> Self {
-        ProduceMessageReply { id }
+        ProduceMessageReply {
+            id,
+            requested_replicas: None,
+            acknowledged_replicas: None,
+        }
+    }
+}
+
+/// Convert an [`Id`] with the number of requested and acknowledged replicas to a [`ProduceMessageReply`] instance.
+impl From<(Id, usize, usize)> for ProduceMessageReply {
+    fn from((id, requested_replicas, acknowledged_replicas): (Id, usize, usize)) -> Self {
+        ProduceMessageReply {
+            id,
+            requested_replicas: Some(requested_replicas),
+            acknowledged_replicas: Some(acknowledged_replicas),
+        }
+    }
+}
+
+/// Summary of the messages produced in chunks with [`Producer::produce_stream`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkProduceSummary {
+    /// Number of messages produced.
+    produced: usize,
+
+    /// Indices of the chunks that could not be produced, starting at `0`.
+    failed_chunks: Vec<usize>,
+
+    /// *ID* of the latest message produced.
+    last_id: Option<Id>,
+}
+
+impl BulkProduceSummary {
+    /// Get the number of **produced** messages.
+    pub fn get_produced(&self) -> usize {
+        self.produced
+    }
+
+    /// Get the indices of the **failed chunks**, starting at `0`.
+    pub fn get_failed_chunks(&self) -> &[usize] {
+        &self.failed_chunks
+    }
+
+    /// Get the **last id** produced in the stream. It is `None` if no message was produced.
+    pub fn get_last_id(&self) -> Option<&Id> {
+        self.last_id.as_ref()
+    }
+
+    /// Verify if all the chunks were produced.
+    pub fn is_complete(&self) -> bool {
+        self.failed_chunks.is_empty()
+    }
+
+    /// Register a chunk produced with *ids*.
+    fn register(&mut self, ids: Vec<Id>) {
+        self.produced += ids.len();
+        if let Some(id) = ids.last() {
+            self.last_id = Some(id.to_owned());
+        }
+    }
+
+    /// Register a chunk by *index* that could not be produced.
+    fn register_failure(&mut self, index: usize) {
+        self.failed_chunks.push(index);
     }
 }
 
 /// A producer implementation of Redis Streams. This struct is responsible for producing messages in a stream.
 #[derive(Debug, Clone)]
-pub struct Producer {
-    /// Redis client to interact with Redis server.
-    client: Client,
+pub struct Producer<C = Client> {
+    /// Connection to interact with Redis server. It can be shared with other producers and consumers.
+    client: Arc<C>,
 
     /// Producer configuration parameters.
     config: ProducerConfig,
-}
 
-impl Producer {
-    /// Get [`Client`].
-    fn get_client(&self) -> &Client {
-        &self.client
-    }
+    /// Recorder of the producer metrics.
+    metrics: Arc<dyn MetricsRecorder>,
 
-    /// Get *stream name*.
-    pub fn get_config(&self) -> &ProducerConfig {
-        &self.config
-    }
+    /// Optional propagator to inject the trace context in produced messages.
+    trace_propagator: Option<Arc<dyn TraceContextPropagator>>,
+
+    /// Interceptors called before producing a message.
+    interceptors: Vec<Arc<dyn Interceptor>>,
+
+    /// Validators called before producing a message, after the interceptors.
+    validators: Vec<Arc<dyn Validator>>,
+
+    /// Optional schema version written in the [`SCHEMA_VERSION_HEADER`] field of every produced message.
+    schema_version: Option<String>,
 
+    /// Number of messages produced, to run the periodic [`TrimPolicy`]. It is shared between clones of the producer.
+    produced: Arc<AtomicUsize>,
+
+    /// Rolling window rate calculator of the produced messages. If it is `None`, the rate is not measured.
+    rate_meter: Option<Arc<RateMeter>>,
+
+    /// Latency thresholds to report slow produce operations.
+    slow_thresholds: Arc<SlowOperationThresholds>,
+
+    /// Capabilities of the Redis server, detected when the producer is created.
+    capabilities: ServerCapabilities,
+}
+
+impl Producer {
     /// Build a new [`Producer`] instance.
     ///
     /// Before creating a new producer, the following validations are performed:
     ///
     /// - If connection string is invalid, a [`RedsumerError`] is returned.
     /// - If connection to Redis server can not be established, a [`RedsumerError`] is returned.
+    /// - If the server does not support a feature required by *config*, like `NOMKSTREAM` or the `MINID` trimming strategy before Redis 6.2, a [`RedsumerError`] is returned. See [`ServerCapabilities`].
     ///
     /// # Arguments:
-    /// - **credentials**: Optional [`ClientCredentials`] to authenticate in Redis.
-    /// - **host**: Redis host.
-    /// - **port**: Redis port.
-    /// - **db**: Redis database.
-    /// - **stream_name**: Stream name to produce messages.
+    /// - **args**: Client arguments to build a new [`Client`] instance. If a namespace is set, the stream name of *config* is prefixed with it.
+    /// - **config**: Producer configuration parameters.
     ///
     ///  # Returns:
     ///  - A [`RedsumerResult`] with the new [`Producer`] instance. Otherwise, a [`RedsumerError`] is returned.
@@ -100,66 +678,694 @@ impl Producer {
             args, config
         );
 
-        let mut client: Client = args.build()?;
-        client.ping()?;
+        let mut config: ProducerConfig = config.to_owned();
+        config.stream_name = args.get_key(config.get_stream_name());
+        Self::from_client(Arc::new(args.build()?), &config)
+    }
+}
+
+impl<C> Producer<C>
+where
+    C: StreamConnection,
+{
+    /// Get the connection.
+    fn get_client(&self) -> &C {
+        &self.client
+    }
 
-        info!("Producer instance created successfully and it is ready to be used");
+    /// Get the shared connection, to build other producers and consumers connected to the same Redis server.
+    pub fn get_shared_client(&self) -> Arc<C> {
+        self.client.to_owned()
+    }
 
-        Ok(Producer {
-            client,
-            config: config.to_owned(),
-        })
+    /// Get *stream name*.
+    pub fn get_config(&self) -> &ProducerConfig {
+        &self.config
     }
 
-    /// Produce a new message in the stream from a map.
-    ///
-    ///  This method produces a new message in the stream setting the *ID* as "*", which means that Redis will generate a new *ID* for the message automatically with the current timestamp. If stream does not exist, it will be created.
+    /// Get the [`ServerCapabilities`] detected when the producer was created.
+    pub fn get_capabilities(&self) -> ServerCapabilities {
+        self.capabilities
+    }
+
+    /// Get [`MetricsRecorder`].
+    fn get_metrics(&self) -> &dyn MetricsRecorder {
+        self.metrics.as_ref()
+    }
+
+    /// Set the [`MetricsRecorder`] called at key points of the producer operations. By default, metrics are discarded.
     ///
     /// # Arguments:
-    /// - **map**: A map with the message to be produced. It must implement the [`ToRedisArgs`] trait.
+    /// - **recorder**: The metrics recorder to use.
     ///
-    ///  # Returns:
-    /// - A [`RedsumerResult`] with a [`ProduceMessageReply`] instance. Otherwise, a [`RedsumerError`] is returned.
-    pub async fn produce_from_map<M>(&self, map: M) -> RedsumerResult<ProduceMessageReply>
-    where
-        M: ToRedisArgs,
-    {
-        self.get_client()
-            .to_owned()
-            .produce_from_map(self.get_config().get_stream_name(), map)
-            .map(ProduceMessageReply::from)
+    /// # Returns:
+    /// The [`Producer`] instance with the given recorder.
+    pub fn with_metrics(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.metrics = recorder;
+        self
     }
 
-    /// Produce a new message in the stream from a list of items.
+    /// Measure the rate of the messages produced in a rolling *window*, retrievable with [`current_rate`](Producer::current_rate). By default, the rate is not measured.
+    ///
+    /// # Arguments:
+    /// - **window**: The length of the rolling window.
     ///
-    /// This method produces a new message in the stream setting the *ID* as "*", which means that Redis will generate a new *ID* for the message automatically with the current timestamp. If stream does not exist, it will be created.
+    /// # Returns:
+    /// The [`Producer`] instance with the given rate window.
+    pub fn with_rate_window(mut self, window: Duration) -> Self {
+        self.rate_meter = Some(Arc::new(RateMeter::new(window)));
+        self
+    }
+
+    /// Report produce operations slower than the given *thresholds* with a structured warning and [`MetricsRecorder::record_slow_operation`]. By default, slow operations are not reported.
     ///
     /// # Arguments:
-    /// - **items**: A list of items with the message to be produced. Each item is a tuple with the field and the value. Both must implement the [`ToRedisArgs`] trait.
+    /// - **thresholds**: The latency thresholds by operation. Only the [`Operation::Produce`] threshold is used.
     ///
     /// # Returns:
-    /// - A [`RedsumerResult`] with a [`ProduceMessageReply`] instance. Otherwise, a [`RedsumerError`] is returned.
-    pub async fn produce_from_items<F, V>(
-        &self,
-        items: Vec<(F, V)>,
-    ) -> RedsumerResult<ProduceMessageReply>
-    where
-        F: ToRedisArgs,
-        V: ToRedisArgs,
-    {
-        self.get_client()
-            .to_owned()
-            .produce_from_items(self.get_config().get_stream_name(), items.as_slice())
-            .map(ProduceMessageReply::from)
+    /// The [`Producer`] instance with the given thresholds.
+    pub fn with_slow_thresholds(mut self, thresholds: SlowOperationThresholds) -> Self {
+        self.slow_thresholds = Arc::new(thresholds);
+        self
     }
-}
 
-#[cfg(test)]
-mod test_producer_config {
-    use super::*;
+    /// Get the rate of the messages produced in the rolling window set with [`with_rate_window`](Producer::with_rate_window), in messages per second. It is `None` if the rate is not measured.
+    pub fn current_rate(&self) -> Option<f64> {
+        self.rate_meter.as_ref().map(|meter| meter.current_rate())
+    }
 
-    #[test]
-    fn test_producer_config_new() {
+    /// Set the [`TraceContextPropagator`] used to add the W3C `traceparent` and `tracestate` fields to every produced message. By default, the trace context is not propagated.
+    ///
+    /// # Arguments:
+    /// - **propagator**: The trace context propagator to use.
+    ///
+    /// # Returns:
+    /// The [`Producer`] instance with the given propagator.
+    pub fn with_trace_propagator(mut self, propagator: Arc<dyn TraceContextPropagator>) -> Self {
+        self.trace_propagator = Some(propagator);
+        self
+    }
+
+    /// Register an [`Interceptor`] called before producing every message. Interceptors are called in the same order they were registered.
+    ///
+    /// # Arguments:
+    /// - **interceptor**: The interceptor to register.
+    ///
+    /// # Returns:
+    /// The [`Producer`] instance with the given interceptor.
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Write a schema *version* in the [`SCHEMA_VERSION_HEADER`] field of every produced message, so consumers can pick the deserializer of its payload format with a [`VersionedDeserializer`](crate::versioning::VersionedDeserializer). It is written after the message fields, so it takes precedence over the schema version of an [`Envelope`](crate::envelope::Envelope).
+    ///
+    /// # Arguments:
+    /// - **version**: The schema version of the produced messages.
+    ///
+    /// # Returns:
+    /// The [`Producer`] instance with the given schema version.
+    pub fn with_schema_version(mut self, version: &str) -> Self {
+        self.schema_version = Some(version.to_owned());
+        self
+    }
+
+    /// Register a [`Validator`] called before producing every message, after the interceptors. Validators are called in the same order they were registered, and a message rejected by any of them is not produced.
+    ///
+    /// # Arguments:
+    /// - **validator**: The validator to register.
+    ///
+    /// # Returns:
+    /// The [`Producer`] instance with the given validator.
+    pub fn with_validator(mut self, validator: Arc<dyn Validator>) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Validate the encoded *args* of a message with the registered validators.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` if the message is valid or there are no validators. If the message fields are not given in pairs or a validator rejects the message, a [`RedsumerError`] is returned.
+    fn validate(&self, args: &[Vec<u8>]) -> RedsumerResult<()> {
+        if self.validators.is_empty() {
+            return Ok(());
+        }
+
+        if args.len() % 2 != 0 {
+            return Err(RedsumerError::from((
+                ErrorKind::TypeError,
+                "Invalid message",
+                "Message fields and values must be given in pairs".to_string(),
+            )));
+        }
+
+        let fields: MessageFields = args
+            .chunks_exact(2)
+            .map(|pair| (pair[0].to_owned(), pair[1].to_owned()))
+            .collect();
+
+        self.validators.iter().try_for_each(|validator| {
+            validator.validate(self.get_config().get_stream_name(), &fields)
+        })
+    }
+
+    /// Encode a message and pass its fields through the registered interceptors.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `None` if there are no interceptors, so the message can be produced as it is. Otherwise, `Some` with the intercepted fields. If the message fields are not given in pairs or an interceptor fails, a [`RedsumerError`] is returned.
+    fn intercept<M>(&self, message: M) -> RedsumerResult<Option<MessageFields>>
+    where
+        M: ToRedisArgs,
+    {
+        if self.interceptors.is_empty() {
+            return Ok(None);
+        }
+
+        let args: Vec<Vec<u8>> = message.to_redis_args();
+        if args.len() % 2 != 0 {
+            return Err(RedsumerError::from((
+                ErrorKind::TypeError,
+                "Invalid message",
+                "Message fields and values must be given in pairs".to_string(),
+            )));
+        }
+
+        let mut fields: MessageFields = args
+            .chunks_exact(2)
+            .map(|pair| (pair[0].to_owned(), pair[1].to_owned()))
+            .collect();
+
+        for interceptor in self.interceptors.iter() {
+            interceptor.before_produce(self.get_config().get_stream_name(), &mut fields)?;
+        }
+
+        Ok(Some(fields))
+    }
+
+    /// Get the trace context fields and the schema version header to add to a produced message.
+    fn get_header_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields: Vec<(&'static str, String)> = self
+            .trace_propagator
+            .as_ref()
+            .and_then(|propagator| propagator.inject())
+            .map(|context| context.to_fields())
+            .unwrap_or_default();
+
+        if let Some(version) = &self.schema_version {
+            fields.push((SCHEMA_VERSION_HEADER, version.to_owned()));
+        }
+
+        fields
+    }
+
+    /// Encode a *message* with the trace context fields and the schema version header into the arguments of a produce command, applying the [`FieldCodec`], the interceptors and the validators.
+    pub(crate) fn encode<M>(&self, message: M) -> RedsumerResult<Vec<Vec<u8>>>
+    where
+        M: ToRedisArgs,
+    {
+        let header_fields: Vec<(&str, String)> = self.get_header_fields();
+        let message: Vec<Vec<u8>> = self
+            .get_config()
+            .get_field_codec()
+            .encode(message.to_redis_args())?;
+
+        let args: Vec<Vec<u8>> = match self.intercept((&message, &header_fields))? {
+            Some(fields) => fields.to_redis_args(),
+            None => (&message, &header_fields).to_redis_args(),
+        };
+        self.validate(&args)?;
+
+        Ok(args)
+    }
+
+    /// Produce a message from its encoded *fields*, retrying it when a retryable error occurs, and wait for its replication if a [`WaitPolicy`] is configured. The command is run with [`run_blocking`], so it does not stall the async runtime.
+    async fn produce_fields(&self, fields: Vec<Vec<u8>>) -> RedsumerResult<ProduceMessageReply> {
+        let producer: Producer<C> = self.to_owned();
+
+        run_blocking(move || {
+            let options: StreamAddOptions = producer.get_config().get_add_options();
+            let stream_name: &str = producer.get_config().get_stream_name();
+
+            match producer.get_config().get_wait_policy() {
+                Some(policy) => {
+                    let (id, acknowledged): (Id, usize) = producer.with_retries(|client| {
+                        client.produce_and_wait(
+                            stream_name,
+                            &fields,
+                            &options,
+                            policy.get_replicas(),
+                            policy.get_timeout(),
+                        )
+                    })?;
+
+                    if acknowledged.lt(&policy.get_replicas()) {
+                        warn!(
+                            "Message {id} acknowledged by {acknowledged} of {} replicas",
+                            policy.get_replicas()
+                        );
+                    }
+
+                    Ok(ProduceMessageReply::from((
+                        id,
+                        policy.get_replicas(),
+                        acknowledged,
+                    )))
+                }
+                None => producer
+                    .with_retries(|client| client.produce_from_map(stream_name, &fields, &options))
+                    .map(ProduceMessageReply::from),
+            }
+        })
+        .await
+    }
+
+    /// Produce a chunk of messages from their encoded *fields* in a single pipeline, retrying it when a retryable error occurs. The command is run with [`run_blocking`], so it does not stall the async runtime.
+    async fn produce_chunk(&self, fields: Vec<Vec<Vec<u8>>>) -> RedsumerResult<Vec<Id>> {
+        let producer: Producer<C> = self.to_owned();
+
+        run_blocking(move || {
+            let options: StreamAddOptions = producer.get_config().get_add_options();
+            producer.with_retries(|client| {
+                client.produce_batch(producer.get_config().get_stream_name(), &fields, &options)
+            })
+        })
+        .await
+    }
+
+    /// Produce a chunk of encoded messages in a single pipeline, recording its latency or its error.
+    pub(crate) async fn produce_encoded(
+        &self,
+        fields: Vec<Vec<Vec<u8>>>,
+    ) -> RedsumerResult<Vec<Id>> {
+        let started_at: Instant = Instant::now();
+
+        let result: RedsumerResult<Vec<Id>> = self.produce_chunk(fields).await;
+        match &result {
+            Ok(ids) => {
+                self.record_produce_latency(started_at.elapsed());
+                self.trim_if_due(ids.len()).await;
+                self.refresh_stream_ttl().await;
+            }
+            Err(e) => self.get_metrics().record_error(
+                Operation::Produce,
+                self.get_config().get_stream_name(),
+                e,
+            ),
+        }
+
+        result
+    }
+
+    /// Register *produced* messages and trim the stream if the periodic [`TrimPolicy`] is due. Errors are logged and they do not affect the produce result.
+    async fn trim_if_due(&self, produced: usize) {
+        if let Some(meter) = &self.rate_meter {
+            meter.record(produced);
+        }
+
+        let policy: TrimPolicy = match self.get_config().get_trim_policy() {
+            Some(policy) => policy.to_owned(),
+            None => return,
+        };
+
+        let previous: usize = self.produced.fetch_add(produced, Ordering::SeqCst);
+        if !policy.is_due(previous, produced) {
+            return;
+        }
+
+        let mut client: C = self.get_client().to_owned();
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        match run_blocking(move || {
+            client.trim_stream(&stream_name, &policy.get_strategy().to_trim_options())
+        })
+        .await
+        {
+            Ok(trimmed) => debug!(
+                "Stream {} trimmed by the periodic trim policy: {} messages evicted",
+                self.get_config().get_stream_name(),
+                trimmed
+            ),
+            Err(e) => warn!(
+                "Error trimming stream {}: {:?}",
+                self.get_config().get_stream_name(),
+                e
+            ),
+        }
+    }
+
+    /// Refresh the time to live of the stream, if it is configured. Errors are logged and they do not affect the produce result.
+    async fn refresh_stream_ttl(&self) {
+        let seconds: u64 = match self.get_config().get_stream_ttl_seconds() {
+            Some(seconds) => seconds,
+            None => return,
+        };
+
+        let mut client: C = self.get_client().to_owned();
+        let stream_name: String = self.get_config().get_stream_name().to_owned();
+        if let Err(e) = run_blocking(move || client.expire_stream(&stream_name, seconds)).await {
+            warn!(
+                "Error refreshing the time to live of stream {}: {:?}",
+                self.get_config().get_stream_name(),
+                e
+            );
+        }
+    }
+
+    /// Record the *latency* of a produce operation, reporting it if it is slower than its threshold.
+    fn record_produce_latency(&self, latency: Duration) {
+        self.get_metrics()
+            .record_produce_latency(self.get_config().get_stream_name(), latency);
+        self.slow_thresholds.check(
+            Operation::Produce,
+            self.get_config().get_stream_name(),
+            latency,
+            self.get_metrics(),
+        );
+    }
+
+    /// Record the latency or the error of a produce operation started at *started_at*. The ID of the produced message is recorded in the current span.
+    fn record_produce(&self, started_at: Instant, result: Result<&Id, &RedsumerError>) {
+        match result {
+            Ok(id) => {
+                Span::current().record("messaging.message.id", id.to_string());
+                self.record_produce_latency(started_at.elapsed());
+            }
+            Err(e) => self.get_metrics().record_error(
+                Operation::Produce,
+                self.get_config().get_stream_name(),
+                e,
+            ),
+        }
+    }
+
+    /// Run a command with a new copy of the connection, retrying it up to *max_retries* times when a retryable error occurs, waiting the *retry_delay* before every retry. It must be run in a blocking thread, see [`run_blocking`].
+    ///
+    /// The command may have been applied before the error, so a retried `XADD` with an auto-generated ID can duplicate the message, see [`ProducerConfigBuilder::max_retries`].
+    fn with_retries<T, F>(&self, mut command: F) -> RedsumerResult<T>
+    where
+        F: FnMut(&mut C) -> RedsumerResult<T>,
+    {
+        let mut attempt: usize = 0;
+
+        loop {
+            match command(&mut self.get_client().to_owned()) {
+                Err(e) if attempt.lt(&self.get_config().get_max_retries()) && e.is_retryable() => {
+                    attempt += 1;
+                    warn!("Retrying command after retryable error (attempt {attempt}): {e:?}");
+                    thread::sleep(self.get_config().get_retry_delay());
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Build a new [`Producer`] instance from a shared [`Client`], like the one of a [`Consumer`](crate::consumer::Consumer) connected to the same Redis server, so both do not maintain separate clients.
+    ///
+    /// Any other [`StreamConnection`] can be given instead of a [`Client`], like a pooled connection, a cluster client or a `MockRedisConnection` in tests.
+    ///
+    /// If connection to Redis server can not be established, or the server does not support a feature required by *config*, a [`RedsumerError`] is returned.
+    ///
+    /// # Arguments:
+    /// - **client**: A shared Redis client or connection.
+    /// - **config**: Producer configuration parameters.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`Producer`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn from_client(client: Arc<C>, config: &ProducerConfig) -> RedsumerResult<Self> {
+        debug!(
+            "Creating a new producer instance from a shared client by: {:?}",
+            config
+        );
+
+        let mut connection: C = client.as_ref().to_owned();
+        connection.ping()?;
+
+        let capabilities: ServerCapabilities = connection.get_server_capabilities();
+        for feature in config.get_required_features() {
+            capabilities.require(feature)?;
+        }
+
+        info!("Producer instance created successfully and it is ready to be used");
+
+        Ok(Self {
+            client,
+            config: config.to_owned(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            trace_propagator: None,
+            interceptors: Vec::new(),
+            validators: Vec::new(),
+            schema_version: None,
+            produced: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            capabilities,
+        })
+    }
+
+    /// Produce a new message in the stream from a map.
+    ///
+    ///  This method produces a new message in the stream setting the *ID* as "*", which means that Redis will generate a new *ID* for the message automatically with the current timestamp. If stream does not exist, it will be created unless *nomkstream* is enabled in [`ProducerConfig`]. The stream is trimmed according to the configured [`TrimmingPolicy`].
+    ///
+    /// # Arguments:
+    /// - **map**: A map with the message to be produced. It must implement the [`ToRedisArgs`] trait.
+    ///
+    ///  # Returns:
+    /// - A [`RedsumerResult`] with a [`ProduceMessageReply`] instance. Otherwise, a [`RedsumerError`] is returned.
+    #[instrument(
+        name = "produce",
+        skip_all,
+        fields(
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.operation.type = "send",
+            messaging.message.id,
+        )
+    )]
+    pub async fn produce_from_map<M>(&self, map: M) -> RedsumerResult<ProduceMessageReply>
+    where
+        M: ToRedisArgs,
+    {
+        let started_at: Instant = Instant::now();
+
+        let result: RedsumerResult<ProduceMessageReply> = match self.encode(map) {
+            Ok(fields) => self.produce_fields(fields).await,
+            Err(e) => Err(e),
+        };
+        self.record_produce(started_at, result.as_ref().map(ProduceMessageReply::get_id));
+        if result.is_ok() {
+            self.trim_if_due(1).await;
+            self.refresh_stream_ttl().await;
+        }
+
+        result
+    }
+
+    /// Produce a new message in the stream from a list of items.
+    ///
+    /// This method produces a new message in the stream setting the *ID* as "*", which means that Redis will generate a new *ID* for the message automatically with the current timestamp. If stream does not exist, it will be created unless *nomkstream* is enabled in [`ProducerConfig`]. The stream is trimmed according to the configured [`TrimmingPolicy`].
+    ///
+    /// # Arguments:
+    /// - **items**: A list of items with the message to be produced. Each item is a tuple with the field and the value. Both must implement the [`ToRedisArgs`] trait.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with a [`ProduceMessageReply`] instance. Otherwise, a [`RedsumerError`] is returned.
+    #[instrument(
+        name = "produce",
+        skip_all,
+        fields(
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.operation.type = "send",
+            messaging.message.id,
+        )
+    )]
+    pub async fn produce_from_items<F, V>(
+        &self,
+        items: Vec<(F, V)>,
+    ) -> RedsumerResult<ProduceMessageReply>
+    where
+        F: ToRedisArgs,
+        V: ToRedisArgs,
+    {
+        let started_at: Instant = Instant::now();
+
+        let result: RedsumerResult<ProduceMessageReply> = match self.encode(items.as_slice()) {
+            Ok(fields) => self.produce_fields(fields).await,
+            Err(e) => Err(e),
+        };
+        self.record_produce(started_at, result.as_ref().map(ProduceMessageReply::get_id));
+        if result.is_ok() {
+            self.trim_if_due(1).await;
+            self.refresh_stream_ttl().await;
+        }
+
+        result
+    }
+
+    /// Produce the messages of an iterator in chunks, pipelining every chunk in a single round trip. It is useful for ETL-style bulk loads.
+    ///
+    /// Every message is produced as [`produce_from_map`](Producer::produce_from_map) does, with the trace context fields and the interceptors. If a chunk can not be produced, its index is registered in the summary and the next chunks are still produced. A failed chunk may be partially produced, and retrying it after a retryable error may duplicate the messages produced before the error.
+    ///
+    /// # Arguments:
+    /// - **messages**: An iterator of messages, each one with its fields and values. They must implement the [`ToRedisArgs`] trait.
+    /// - **chunk_size**: The max number of messages produced in every pipeline. It must be greater than `0`.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with a [`BulkProduceSummary`]. If *chunk_size* is `0`, a [`RedsumerError`] is returned.
+    #[instrument(
+        name = "produce_stream",
+        skip_all,
+        fields(
+            messaging.system = "redis",
+            messaging.destination.name = %self.get_config().get_stream_name(),
+            messaging.operation.type = "send",
+        )
+    )]
+    pub async fn produce_stream<I, M>(
+        &self,
+        messages: I,
+        chunk_size: usize,
+    ) -> RedsumerResult<BulkProduceSummary>
+    where
+        I: IntoIterator<Item = M>,
+        M: ToRedisArgs,
+    {
+        if chunk_size.eq(&0) {
+            return Err(RedsumerError::from((
+                ErrorKind::ClientError,
+                "Invalid chunk size",
+                "Chunk size must be greater than zero".to_string(),
+            )));
+        }
+
+        let mut messages = messages.into_iter();
+        let mut summary: BulkProduceSummary = BulkProduceSummary::default();
+
+        for index in 0.. {
+            let chunk: Vec<M> = messages.by_ref().take(chunk_size).collect();
+            if chunk.is_empty() {
+                break;
+            }
+
+            let result: RedsumerResult<Vec<Id>> = match chunk
+                .into_iter()
+                .map(|message| self.encode(message))
+                .collect::<RedsumerResult<Vec<Vec<Vec<u8>>>>>()
+            {
+                Ok(fields) => self.produce_encoded(fields).await,
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(ids) => summary.register(ids),
+                Err(e) => {
+                    warn!("Error producing chunk {index}: {:?}", e);
+                    summary.register_failure(index);
+                }
+            }
+        }
+
+        info!(
+            "Total messages produced in stream {}: {}, failed chunks: {:?}",
+            self.get_config().get_stream_name(),
+            summary.get_produced(),
+            summary.get_failed_chunks()
+        );
+
+        Ok(summary)
+    }
+
+    /// Import the messages of a JSON Lines dump into the stream, where every line is a [`DumpedMessage`]. Empty lines are skipped.
+    ///
+    /// Messages are produced as they are in the dump, so interceptors and trace propagation are not applied. If an error occurs, the import is stopped and the messages imported before the error are kept in the stream.
+    ///
+    /// # Arguments:
+    /// - **reader**: A buffered reader of the dump, like a file or the standard input.
+    /// - **mode**: An [`ImportIdMode`] to preserve the original *IDs* of the messages or to remap them to new ones.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with an [`ImportSummary`]. If a line is not a valid [`DumpedMessage`] or a message can not be produced, a [`RedsumerError`] is returned.
+    pub async fn import_from_reader<R>(
+        &self,
+        reader: R,
+        mode: ImportIdMode,
+    ) -> RedsumerResult<ImportSummary>
+    where
+        R: BufRead,
+    {
+        let mut summary: ImportSummary = ImportSummary::default();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line: String = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let message: DumpedMessage = serde_json::from_str(&line).map_err(|e| {
+                RedsumerError::DeserializationError(format!("Invalid dump line {}: {e}", index + 1))
+            })?;
+
+            // Every message is produced with run_blocking, so a large dump does not stall the async runtime:
+            let started_at: Instant = Instant::now();
+            let producer: Producer<C> = self.to_owned();
+            let result: RedsumerResult<Id> = run_blocking(move || {
+                let options: StreamAddOptions = producer.get_config().get_add_options();
+                let stream_name: &str = producer.get_config().get_stream_name();
+                let items: Vec<(&String, &String)> = message.get_fields().iter().collect();
+
+                producer.with_retries(|client| match mode {
+                    ImportIdMode::Preserve => client.produce_from_items_with_id(
+                        stream_name,
+                        message.get_id(),
+                        items.as_slice(),
+                        &options,
+                    ),
+                    ImportIdMode::Remap => {
+                        client.produce_from_items(stream_name, items.as_slice(), &options)
+                    }
+                })
+            })
+            .await;
+            self.record_produce(started_at, result.as_ref());
+
+            summary.register(result?);
+        }
+
+        info!(
+            "Total messages imported into stream {}: {}",
+            self.get_config().get_stream_name(),
+            summary.get_imported()
+        );
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+impl Producer {
+    /// Build a producer without connecting to the Redis server, for the tests of other modules.
+    pub(crate) fn unconnected(config: &ProducerConfig) -> Producer {
+        Producer {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            config: config.to_owned(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            trace_propagator: None,
+            interceptors: Vec::new(),
+            validators: Vec::new(),
+            schema_version: None,
+            produced: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            capabilities: ServerCapabilities::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_producer_config {
+    use super::*;
+
+    #[test]
+    fn test_producer_config_new() {
         // Define the stream name.
         let stream_name: &str = "stream_name";
 
@@ -171,14 +1377,460 @@ mod test_producer_config {
     }
 }
 
+#[cfg(test)]
+mod test_producer_config_builder {
+    use redis::ToRedisArgs;
+
+    use super::*;
+    use crate::core::streams::types::EntryId;
+
+    #[test]
+    fn test_producer_config_builder_defaults() {
+        // Build a new producer configuration with default options.
+        let config: ProducerConfig = ProducerConfig::builder("stream_name").build().unwrap();
+
+        // Verify the result.
+        assert_eq!(config.get_stream_name(), "stream_name");
+        assert!(config.get_trimming_policy().is_none());
+        assert!(!config.get_nomkstream());
+        assert_eq!(config.get_field_codec(), FieldCodec::Raw);
+        assert_eq!(config.get_max_retries(), DEFAULT_MAX_RETRIES);
+        assert_eq!(config.get_retry_delay(), DEFAULT_RETRY_DELAY);
+        assert!(config.get_add_options().to_redis_args().is_empty());
+    }
+
+    #[test]
+    fn test_producer_config_builder_maxlen() {
+        // Build a new producer configuration with chained setters.
+        let config: ProducerConfig = ProducerConfigBuilder::new("stream_name")
+            .trimming_policy(TrimmingPolicy::MaxLen {
+                max_entries: 1000,
+                approximate: true,
+            })
+            .nomkstream(true)
+            .field_codec(FieldCodec::Json)
+            .max_retries(3)
+            .retry_delay(Duration::from_millis(250))
+            .build()
+            .unwrap();
+
+        // Verify the result.
+        assert!(config.get_nomkstream());
+        assert_eq!(config.get_field_codec(), FieldCodec::Json);
+        assert_eq!(config.get_max_retries(), 3);
+        assert_eq!(config.get_retry_delay(), Duration::from_millis(250));
+        assert_eq!(
+            config.get_add_options().to_redis_args(),
+            vec![
+                b"NOMKSTREAM".to_vec(),
+                b"MAXLEN".to_vec(),
+                b"~".to_vec(),
+                b"1000".to_vec()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_producer_config_builder_minid() {
+        // Build a new producer configuration with a MINID trimming policy.
+        let config: ProducerConfig = ProducerConfig::builder("stream_name")
+            .trimming_policy(TrimmingPolicy::MinId {
+                min_id: EntryId::new(1000, 0),
+                approximate: false,
+            })
+            .build()
+            .unwrap();
+
+        // Verify the result.
+        assert_eq!(
+            config.get_add_options().to_redis_args(),
+            vec![b"MINID".to_vec(), b"=".to_vec(), b"1000-0".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_producer_config_builder_empty_stream_name() {
+        // Build a new producer configuration with an empty stream name.
+        let config: RedsumerResult<ProducerConfig> = ProducerConfig::builder("").build();
+
+        // Verify the result.
+        assert!(config.is_err());
+        assert_eq!(config.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+    }
+}
+
+#[cfg(test)]
+mod test_producer_config_serde {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::core::streams::types::EntryId;
+
+    #[test]
+    fn test_producer_config_serde_round_trip() {
+        // Build a new producer configuration.
+        let config: ProducerConfig = ProducerConfig::builder("stream_name")
+            .trimming_policy(TrimmingPolicy::MinId {
+                min_id: EntryId::new(1000, 0),
+                approximate: true,
+            })
+            .field_codec(FieldCodec::Json)
+            .build()
+            .unwrap();
+
+        // Serialize the configuration.
+        let value: Value = serde_json::to_value(&config).unwrap();
+
+        // Verify the result.
+        assert_eq!(
+            value,
+            json!({
+                "stream_name": "stream_name",
+                "trimming_policy": {"min_id": {"min_id": "1000-0", "approximate": true}},
+                "trim_policy": null,
+                "nomkstream": false,
+                "stream_ttl_seconds": null,
+                "field_codec": "json",
+                "max_retries": 0,
+                "retry_delay": 100,
+                "wait_policy": null
+            })
+        );
+
+        let deserialized: ProducerConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            deserialized.get_trimming_policy(),
+            config.get_trimming_policy()
+        );
+        assert_eq!(deserialized.get_field_codec(), FieldCodec::Json);
+    }
+
+    #[test]
+    fn test_producer_config_deserialize_with_defaults() {
+        // Deserialize a producer configuration with only the stream name.
+        let config: ProducerConfig =
+            serde_json::from_value(json!({"stream_name": "stream_name"})).unwrap();
+
+        // Verify the result.
+        assert_eq!(config.get_stream_name(), "stream_name");
+        assert!(config.get_trimming_policy().is_none());
+        assert!(config.get_trim_policy().is_none());
+        assert!(!config.get_nomkstream());
+        assert!(config.get_stream_ttl_seconds().is_none());
+        assert_eq!(config.get_field_codec(), FieldCodec::Raw);
+        assert_eq!(config.get_max_retries(), DEFAULT_MAX_RETRIES);
+        assert_eq!(config.get_retry_delay(), DEFAULT_RETRY_DELAY);
+        assert!(config.get_wait_policy().is_none());
+    }
+}
+
+#[cfg(test)]
+mod test_wait_policy {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_producer_config_builder_wait_policy() {
+        // Build producer configurations with wait policies:
+        let config: RedsumerResult<ProducerConfig> = ProducerConfig::builder("stream_name")
+            .wait_policy(WaitPolicy::new(2, Duration::from_millis(500)))
+            .build();
+        let invalid: RedsumerResult<ProducerConfig> = ProducerConfig::builder("stream_name")
+            .wait_policy(WaitPolicy::new(0, Duration::from_millis(500)))
+            .build();
+
+        // Verify the result:
+        let config: ProducerConfig = config.unwrap();
+        let policy: &WaitPolicy = config.get_wait_policy().unwrap();
+        assert_eq!(policy.get_replicas(), 2);
+        assert_eq!(policy.get_timeout(), Duration::from_millis(500));
+        assert_eq!(invalid.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+    }
+
+    #[test]
+    fn test_wait_policy_serde() {
+        // Deserialize a producer configuration with a wait policy:
+        let config: ProducerConfig = serde_json::from_value(json!({
+            "stream_name": "stream_name",
+            "wait_policy": {"replicas": 1, "timeout": 250}
+        }))
+        .unwrap();
+
+        // Verify the result:
+        assert_eq!(
+            config.get_wait_policy(),
+            Some(&WaitPolicy::new(1, Duration::from_millis(250)))
+        );
+    }
+
+    #[test]
+    fn test_produce_message_reply_replication() {
+        // Build replies with and without replication confirmation:
+        let reached: ProduceMessageReply = ProduceMessageReply::from((Id::new(1, 0), 2, 2));
+        let missed: ProduceMessageReply = ProduceMessageReply::from((Id::new(1, 0), 2, 1));
+        let unconfirmed: ProduceMessageReply = ProduceMessageReply::from(Id::new(1, 0));
+
+        // Verify the result:
+        assert_eq!(reached.get_acknowledged_replicas(), Some(2));
+        assert_eq!(reached.replication_reached(), Some(true));
+        assert_eq!(missed.replication_reached(), Some(false));
+        assert!(unconfirmed.get_acknowledged_replicas().is_none());
+        assert!(unconfirmed.replication_reached().is_none());
+    }
+}
+
+#[cfg(test)]
+mod test_field_codec {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_field_codec_raw() {
+        // Encode a message with the raw codec:
+        let args: Vec<Vec<u8>> = FieldCodec::Raw
+            .encode(vec![b"name".to_vec(), b"meter".to_vec()])
+            .unwrap();
+
+        // Verify the result:
+        assert_eq!(args, vec![b"name".to_vec(), b"meter".to_vec()]);
+    }
+
+    #[test]
+    fn test_field_codec_json() {
+        // Encode a message with the JSON codec:
+        let args: Vec<Vec<u8>> = FieldCodec::Json
+            .encode(vec![
+                b"name".to_vec(),
+                b"meter".to_vec(),
+                b"value".to_vec(),
+                b"42".to_vec(),
+                b"payload".to_vec(),
+                br#"{"id":1}"#.to_vec(),
+            ])
+            .unwrap();
+
+        // Verify the result, the values are JSON documents and the fields are kept:
+        assert_eq!(
+            args,
+            vec![
+                b"name".to_vec(),
+                br#""meter""#.to_vec(),
+                b"value".to_vec(),
+                b"42".to_vec(),
+                b"payload".to_vec(),
+                br#"{"id":1}"#.to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_codec_json_binary_value() {
+        // Encode a message with a binary value with the JSON codec:
+        let result: RedsumerResult<Vec<Vec<u8>>> =
+            FieldCodec::Json.encode(vec![b"blob".to_vec(), vec![0xff, 0xfe]]);
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::TypeError);
+    }
+
+    #[tokio::test]
+    async fn test_produce_from_items_with_json_codec() {
+        // Define the config and a mock connection:
+        let config: ProducerConfig = ProducerConfig::builder("stream")
+            .field_codec(FieldCodec::Json)
+            .build()
+            .unwrap();
+        let connection: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Ok(Value::BulkString(
+                    b"# Server\r\nredis_version:7.2.4\r\n".to_vec(),
+                )),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XADD")
+                    .arg("stream")
+                    .arg(config.get_add_options())
+                    .arg("*")
+                    .arg("name")
+                    .arg(r#""meter""#)
+                    .arg("value")
+                    .arg("42"),
+                Ok(Value::BulkString(b"1-0".to_vec())),
+            ),
+        ]);
+
+        // Create a new producer on the mock connection:
+        let producer: Producer<MockRedisConnection> =
+            Producer::from_client(Arc::new(connection), &config).unwrap();
+
+        // Produce a message:
+        let reply: ProduceMessageReply = producer
+            .produce_from_items(vec![("name", "meter"), ("value", "42")])
+            .await
+            .unwrap();
+
+        // Verify the result:
+        assert_eq!(reply.get_id(), &Id::new(1, 0));
+    }
+}
+
+#[cfg(test)]
+mod test_producer_retries {
+    use super::*;
+
+    #[test]
+    fn test_producer_retry_delay() {
+        // Define a producer with retries on an unreachable server:
+        let producer: Producer = Producer {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            config: ProducerConfig::builder("stream")
+                .max_retries(2)
+                .retry_delay(Duration::from_millis(50))
+                .build()
+                .unwrap(),
+            metrics: Arc::new(NoopMetricsRecorder),
+            trace_propagator: None,
+            interceptors: Vec::new(),
+            validators: Vec::new(),
+            schema_version: None,
+            produced: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            capabilities: ServerCapabilities::default(),
+        };
+
+        // Run a command that always fails with a retryable error:
+        let started_at: Instant = Instant::now();
+        let mut attempts: usize = 0;
+        let result: RedsumerResult<String> = producer.with_retries(|client| {
+            attempts += 1;
+            client.ping()
+        });
+
+        // Verify the result, the command was retried twice waiting the retry delay:
+        assert!(result.unwrap_err().is_retryable());
+        assert_eq!(attempts, 3);
+        assert!(started_at.elapsed().ge(&Duration::from_millis(100)));
+    }
+}
+
+#[cfg(test)]
+mod test_producer_config_stream_ttl {
+    use super::*;
+
+    #[test]
+    fn test_producer_config_builder_stream_ttl() {
+        // Build producer configurations with a stream time to live:
+        let config: RedsumerResult<ProducerConfig> = ProducerConfig::builder("stream_name")
+            .stream_ttl_seconds(3600)
+            .build();
+        let invalid: RedsumerResult<ProducerConfig> = ProducerConfig::builder("stream_name")
+            .stream_ttl_seconds(0)
+            .build();
+
+        // Verify the result:
+        assert_eq!(config.unwrap().get_stream_ttl_seconds(), Some(3600));
+        assert_eq!(invalid.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+    }
+}
+
+#[cfg(test)]
+mod test_trim_policy {
+    use redis::ToRedisArgs;
+
+    use super::*;
+
+    #[test]
+    fn test_trim_policy_is_due() {
+        // Define a policy that trims every three messages:
+        let policy: TrimPolicy = TrimPolicy::new(
+            3,
+            TrimmingPolicy::MaxLen {
+                max_entries: 100,
+                approximate: true,
+            },
+        );
+
+        // Verify the result:
+        assert!(!policy.is_due(0, 1));
+        assert!(!policy.is_due(1, 1));
+        assert!(policy.is_due(2, 1));
+        assert!(!policy.is_due(3, 2));
+        assert!(policy.is_due(4, 5));
+        assert!(!TrimPolicy::new(0, policy.get_strategy().to_owned()).is_due(0, 1));
+    }
+
+    #[test]
+    fn test_trim_policy_options() {
+        // Define the strategies:
+        let maxlen: TrimmingPolicy = TrimmingPolicy::MaxLen {
+            max_entries: 100,
+            approximate: true,
+        };
+        let minid: TrimmingPolicy = TrimmingPolicy::MinId {
+            min_id: Id::new(1000, 0),
+            approximate: false,
+        };
+
+        // Verify the result:
+        assert_eq!(
+            maxlen.to_trim_options().to_redis_args(),
+            vec![b"MAXLEN".to_vec(), b"~".to_vec(), b"100".to_vec()]
+        );
+        assert_eq!(
+            minid.to_trim_options().to_redis_args(),
+            vec![b"MINID".to_vec(), b"=".to_vec(), b"1000-0".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_producer_config_builder_trim_policy() {
+        // Build producer configurations with trim policies:
+        let config: RedsumerResult<ProducerConfig> = ProducerConfig::builder("stream_name")
+            .trim_policy(TrimPolicy::new(
+                10,
+                TrimmingPolicy::MaxLen {
+                    max_entries: 100,
+                    approximate: true,
+                },
+            ))
+            .build();
+        let invalid: RedsumerResult<ProducerConfig> = ProducerConfig::builder("stream_name")
+            .trim_policy(TrimPolicy::new(
+                0,
+                TrimmingPolicy::MaxLen {
+                    max_entries: 100,
+                    approximate: true,
+                },
+            ))
+            .build();
+
+        // Verify the result:
+        assert_eq!(
+            config
+                .unwrap()
+                .get_trim_policy()
+                .unwrap()
+                .get_every_n_messages(),
+            10
+        );
+        assert_eq!(invalid.unwrap_err().kind(), ErrorKind::InvalidClientConfig);
+    }
+}
+
 #[cfg(test)]
 mod test_produce_messages_reply {
     use super::*;
+    use crate::core::streams::types::EntryId;
 
     #[test]
     fn test_produce_message_reply_from() {
         // Define the message ID.
-        let id: Id = "1234567890".to_string();
+        let id: Id = EntryId::new(1234567890, 0);
 
         // Create a new produce message reply.
         let reply: ProduceMessageReply = ProduceMessageReply::from(id.to_owned());
@@ -187,3 +1839,366 @@ mod test_produce_messages_reply {
         assert_eq!(reply.get_id(), &id);
     }
 }
+
+#[cfg(test)]
+mod test_producer_interceptor {
+    use super::*;
+
+    /// An interceptor that adds a field to every message.
+    #[derive(Debug)]
+    struct SourceInterceptor;
+
+    impl Interceptor for SourceInterceptor {
+        fn before_produce(&self, _stream: &str, fields: &mut MessageFields) -> RedsumerResult<()> {
+            fields.push((b"source".to_vec(), b"test".to_vec()));
+            Ok(())
+        }
+    }
+
+    /// Build a producer without connecting to the Redis server.
+    fn producer() -> Producer {
+        Producer {
+            client: Arc::new(Client::open("redis://fakehost/0").unwrap()),
+            config: ProducerConfig::new("stream"),
+            metrics: Arc::new(NoopMetricsRecorder),
+            trace_propagator: None,
+            interceptors: Vec::new(),
+            validators: Vec::new(),
+            schema_version: None,
+            produced: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            capabilities: ServerCapabilities::default(),
+        }
+    }
+
+    #[test]
+    fn test_producer_intercept_without_interceptors() {
+        // Verify the message is not encoded:
+        assert!(producer().intercept(&[("key", "value")]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_producer_intercept_ok() {
+        // Create a producer with an interceptor:
+        let producer: Producer = producer().with_interceptor(Arc::new(SourceInterceptor));
+
+        // Intercept a message:
+        let fields: Option<MessageFields> = producer.intercept(&[("key", "value")]).unwrap();
+
+        // Verify the result:
+        assert_eq!(
+            fields,
+            Some(vec![
+                (b"key".to_vec(), b"value".to_vec()),
+                (b"source".to_vec(), b"test".to_vec()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_producer_intercept_odd_fields() {
+        // Create a producer with an interceptor:
+        let producer: Producer = producer().with_interceptor(Arc::new(SourceInterceptor));
+
+        // Verify the result:
+        assert!(producer.intercept(&["key"]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_producer_validator {
+    use super::*;
+    use crate::redsumer::validation::RequiredFieldsValidator;
+
+    /// Build a producer without connecting to the Redis server.
+    fn producer() -> Producer {
+        Producer {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            config: ProducerConfig::new("stream"),
+            metrics: Arc::new(NoopMetricsRecorder),
+            trace_propagator: None,
+            interceptors: Vec::new(),
+            validators: Vec::new(),
+            schema_version: None,
+            produced: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            capabilities: ServerCapabilities::default(),
+        }
+    }
+
+    #[test]
+    fn test_producer_encode_valid_message() {
+        // Create a producer with a validator:
+        let producer: Producer =
+            producer().with_validator(Arc::new(RequiredFieldsValidator::new(&["id"])));
+
+        // Encode a valid message:
+        let args: RedsumerResult<Vec<Vec<u8>>> = producer.encode(&[("id", "1")]);
+
+        // Verify the result:
+        assert_eq!(args.unwrap(), vec![b"id".to_vec(), b"1".to_vec()]);
+    }
+
+    #[test]
+    fn test_producer_encode_schema_version() {
+        // Create a producer with a schema version:
+        let producer: Producer = producer().with_schema_version("2");
+
+        // Encode a message:
+        let args: RedsumerResult<Vec<Vec<u8>>> = producer.encode(&[("id", "1")]);
+
+        // Verify the result:
+        assert_eq!(
+            args.unwrap(),
+            vec![
+                b"id".to_vec(),
+                b"1".to_vec(),
+                SCHEMA_VERSION_HEADER.as_bytes().to_vec(),
+                b"2".to_vec()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_producer_rejects_invalid_message() {
+        // Create a producer with a validator:
+        let producer: Producer =
+            producer().with_validator(Arc::new(RequiredFieldsValidator::new(&["id"])));
+
+        // Produce an invalid message, which does not reach the server:
+        let result: RedsumerResult<ProduceMessageReply> =
+            producer.produce_from_items(vec![("kind", "created")]).await;
+
+        // Verify the result:
+        assert!(matches!(
+            result.unwrap_err(),
+            RedsumerError::ValidationError(_)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_producer_import {
+    use super::*;
+
+    /// Build a producer without connecting to the Redis server.
+    fn producer() -> Producer {
+        Producer {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            config: ProducerConfig::new("stream"),
+            metrics: Arc::new(NoopMetricsRecorder),
+            trace_propagator: None,
+            interceptors: Vec::new(),
+            validators: Vec::new(),
+            schema_version: None,
+            produced: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            capabilities: ServerCapabilities::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_empty_dump() {
+        // Import a dump with empty lines only:
+        let result: RedsumerResult<ImportSummary> = producer()
+            .import_from_reader("\n  \n".as_bytes(), ImportIdMode::Preserve)
+            .await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), ImportSummary::default());
+    }
+
+    #[tokio::test]
+    async fn test_import_invalid_line() {
+        // Import a dump with an invalid line:
+        let result: RedsumerResult<ImportSummary> = producer()
+            .import_from_reader("\n{\"id\":\"invalid\"}\n".as_bytes(), ImportIdMode::Remap)
+            .await;
+
+        // Verify the result:
+        let error: RedsumerError = result.unwrap_err();
+        assert!(matches!(error, RedsumerError::DeserializationError(_)));
+        assert!(error.to_string().contains("line 2"));
+    }
+
+    #[tokio::test]
+    async fn test_import_unreachable_server() {
+        // Import a valid dump without reaching the server:
+        let result: RedsumerResult<ImportSummary> = producer()
+            .import_from_reader(
+                "{\"id\":\"1-0\",\"fields\":{\"key\":\"value\"}}\n".as_bytes(),
+                ImportIdMode::Preserve,
+            )
+            .await;
+
+        // Verify the result:
+        assert!(result.unwrap_err().is_connection_error());
+    }
+}
+
+#[cfg(test)]
+mod test_producer_from_client {
+    use redis::{cmd, Value};
+    use redis_test::{MockCmd, MockRedisConnection};
+
+    use super::*;
+
+    #[test]
+    fn test_producer_from_unreachable_client() {
+        // Define a shared client:
+        let client: Arc<Client> = Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap());
+
+        // Create a new producer:
+        let result: RedsumerResult<Producer> =
+            Producer::from_client(client, &ProducerConfig::new("stream"));
+
+        // Verify the result:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_producer_get_shared_client() {
+        // Define a producer with a shared client:
+        let client: Arc<Client> = Arc::new(Client::open("redis://fakehost/0").unwrap());
+        let producer: Producer = Producer {
+            client: client.to_owned(),
+            config: ProducerConfig::new("stream"),
+            metrics: Arc::new(NoopMetricsRecorder),
+            trace_propagator: None,
+            interceptors: Vec::new(),
+            validators: Vec::new(),
+            schema_version: None,
+            produced: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            capabilities: ServerCapabilities::default(),
+        };
+
+        // Verify the result:
+        assert!(Arc::ptr_eq(&producer.get_shared_client(), &client));
+    }
+
+    #[tokio::test]
+    async fn test_producer_from_mock_connection() {
+        // Define the config and a mock connection:
+        let config: ProducerConfig = ProducerConfig::new("stream");
+        let connection: MockRedisConnection = MockRedisConnection::new(vec![
+            MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Ok(Value::BulkString(
+                    b"# Server\r\nredis_version:7.2.4\r\n".to_vec(),
+                )),
+            ),
+            MockCmd::new::<_, Value>(
+                cmd("XADD")
+                    .arg("stream")
+                    .arg(config.get_add_options())
+                    .arg("*")
+                    .arg("field")
+                    .arg("value"),
+                Ok(Value::BulkString(b"1-0".to_vec())),
+            ),
+        ]);
+
+        // Create a new producer on the mock connection:
+        let producer: Producer<MockRedisConnection> =
+            Producer::from_client(Arc::new(connection), &config).unwrap();
+
+        // Produce a message:
+        let reply: ProduceMessageReply = producer
+            .produce_from_items(vec![("field", "value")])
+            .await
+            .unwrap();
+
+        // Verify the result:
+        assert_eq!(reply.get_id(), &Id::new(1, 0));
+    }
+
+    #[test]
+    fn test_producer_unsupported_feature() {
+        // Define a config with NOMKSTREAM and a mock connection to Redis 6.0:
+        let config: ProducerConfig = ProducerConfig::builder("stream")
+            .nomkstream(true)
+            .build()
+            .unwrap();
+        let connection: MockRedisConnection =
+            MockRedisConnection::new(vec![MockCmd::new::<_, Value>(
+                cmd("INFO").arg("server"),
+                Ok(Value::BulkString(
+                    b"# Server\r\nredis_version:6.0.9\r\n".to_vec(),
+                )),
+            )]);
+
+        // Create a new producer on the mock connection:
+        let result: RedsumerResult<Producer<MockRedisConnection>> =
+            Producer::from_client(Arc::new(connection), &config);
+
+        // Verify the result:
+        assert_eq!(result.err().map(|e| e.kind()), Some(ErrorKind::ClientError));
+    }
+}
+
+#[cfg(test)]
+mod test_bulk_produce_summary {
+    use super::*;
+
+    /// Build a producer without connecting to the Redis server.
+    fn producer() -> Producer {
+        Producer {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            config: ProducerConfig::new("stream"),
+            metrics: Arc::new(NoopMetricsRecorder),
+            trace_propagator: None,
+            interceptors: Vec::new(),
+            validators: Vec::new(),
+            schema_version: None,
+            produced: Arc::default(),
+            rate_meter: None,
+            slow_thresholds: Arc::default(),
+            capabilities: ServerCapabilities::default(),
+        }
+    }
+
+    #[test]
+    fn test_bulk_produce_summary_register() {
+        // Register two chunks and a failure:
+        let mut summary: BulkProduceSummary = BulkProduceSummary::default();
+        summary.register(vec![Id::new(1, 0), Id::new(1, 1)]);
+        summary.register_failure(1);
+        summary.register(vec![Id::new(2, 0)]);
+
+        // Verify the result:
+        assert_eq!(summary.get_produced(), 3);
+        assert_eq!(summary.get_failed_chunks(), &[1]);
+        assert_eq!(summary.get_last_id(), Some(&Id::new(2, 0)));
+        assert!(!summary.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_produce_stream_invalid_chunk_size() {
+        // Produce with a zero chunk size:
+        let result: RedsumerResult<BulkProduceSummary> = producer()
+            .produce_stream(vec![vec![("field", "value")]], 0)
+            .await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+
+    #[tokio::test]
+    async fn test_produce_stream_failed_chunks() {
+        // Produce three messages in chunks of two without a server:
+        let messages = (0..3).map(|i| vec![("field", i)]);
+        let result: RedsumerResult<BulkProduceSummary> =
+            producer().produce_stream(messages, 2).await;
+
+        // Verify every chunk failed:
+        let summary: BulkProduceSummary = result.unwrap();
+        assert_eq!(summary.get_produced(), 0);
+        assert_eq!(summary.get_failed_chunks(), &[0, 1]);
+        assert!(summary.get_last_id().is_none());
+    }
+}
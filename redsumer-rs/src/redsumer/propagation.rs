@@ -0,0 +1,165 @@
+use std::fmt::Debug;
+
+use redis::{from_redis_value, streams::StreamId};
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+
+/// Message field used to propagate the W3C `traceparent` header.
+pub const TRACEPARENT_FIELD: &str = "traceparent";
+
+/// Message field used to propagate the W3C `tracestate` header.
+pub const TRACESTATE_FIELD: &str = "tracestate";
+
+/// A W3C trace context, propagated through message fields between producer and consumer services.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// The `traceparent` header, with the `<version>-<trace-id>-<parent-id>-<trace-flags>` format.
+    traceparent: String,
+
+    /// The optional `tracestate` header with vendor specific data.
+    tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Get **traceparent**.
+    pub fn get_traceparent(&self) -> &str {
+        &self.traceparent
+    }
+
+    /// Get **tracestate**.
+    pub fn get_tracestate(&self) -> Option<&str> {
+        self.tracestate.as_deref()
+    }
+
+    /// Build a new [`TraceContext`] instance.
+    ///
+    /// # Arguments:
+    /// - **traceparent**: The `traceparent` header.
+    /// - **tracestate**: The optional `tracestate` header.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with a new [`TraceContext`] instance. If *traceparent* does not have the `<version>-<trace-id>-<parent-id>-<trace-flags>` format, a [`RedsumerError`] is returned.
+    pub fn new(traceparent: &str, tracestate: Option<&str>) -> RedsumerResult<Self> {
+        let parts: Vec<&str> = traceparent.split('-').collect();
+
+        let is_valid: bool = parts.len().eq(&4)
+            && parts
+                .iter()
+                .zip([2, 32, 16, 2])
+                .all(|(part, len)| part.len().eq(&len))
+            && parts
+                .iter()
+                .all(|part| part.chars().all(|c| c.is_ascii_hexdigit()));
+
+        if !is_valid {
+            return Err(RedsumerError::DeserializationError(format!(
+                "Invalid traceparent: {traceparent}"
+            )));
+        }
+
+        Ok(TraceContext {
+            traceparent: traceparent.to_owned(),
+            tracestate: tracestate.map(str::to_owned),
+        })
+    }
+
+    /// Get the message fields to propagate the trace context.
+    pub fn to_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields: Vec<(&'static str, String)> =
+            vec![(TRACEPARENT_FIELD, self.traceparent.to_owned())];
+
+        if let Some(tracestate) = &self.tracestate {
+            fields.push((TRACESTATE_FIELD, tracestate.to_owned()));
+        }
+
+        fields
+    }
+}
+
+/// A hook to inject the trace context of the current span in produced messages.
+///
+/// It is implemented by the user with the tracing stack of choice, for example with the OpenTelemetry text map propagator, and set in the [`Producer`](crate::producer::Producer) with `with_trace_propagator()`.
+pub trait TraceContextPropagator: Debug + Send + Sync {
+    /// Get the trace context of the current span.
+    ///
+    /// # Returns:
+    /// `Some` with the [`TraceContext`] to add to the produced message, or `None` if there is no active trace.
+    fn inject(&self) -> Option<TraceContext>;
+}
+
+/// A trait to extract the trace context from consumed messages.
+pub trait TraceContextExt {
+    /// Get the trace context propagated in the message fields.
+    ///
+    /// # Returns:
+    /// `Some` with the [`TraceContext`] if the message contains a valid `traceparent` field, otherwise `None`.
+    fn get_trace_context(&self) -> Option<TraceContext>;
+}
+
+impl TraceContextExt for StreamId {
+    fn get_trace_context(&self) -> Option<TraceContext> {
+        let traceparent: String = from_redis_value(self.map.get(TRACEPARENT_FIELD)?).ok()?;
+        let tracestate: Option<String> = self
+            .map
+            .get(TRACESTATE_FIELD)
+            .and_then(|v| from_redis_value(v).ok());
+
+        TraceContext::new(&traceparent, tracestate.as_deref()).ok()
+    }
+}
+
+#[cfg(test)]
+mod test_trace_context {
+    use std::collections::HashMap;
+
+    use redis::Value;
+
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_trace_context_new() {
+        // Build valid trace contexts:
+        let context: TraceContext =
+            TraceContext::new(TRACEPARENT, Some("congo=t61rcWkgMzE")).unwrap();
+
+        // Verify the result:
+        assert_eq!(context.get_traceparent(), TRACEPARENT);
+        assert_eq!(context.get_tracestate(), Some("congo=t61rcWkgMzE"));
+        assert_eq!(
+            context.to_fields(),
+            vec![
+                (TRACEPARENT_FIELD, TRACEPARENT.to_string()),
+                (TRACESTATE_FIELD, "congo=t61rcWkgMzE".to_string())
+            ]
+        );
+
+        // Verify invalid trace contexts:
+        assert!(TraceContext::new("00-abc-def-01", None).is_err());
+        assert!(TraceContext::new("invalid", None).is_err());
+    }
+
+    #[test]
+    fn test_trace_context_from_message() {
+        // Define a message with trace context:
+        let message: StreamId = StreamId {
+            id: "1-0".to_string(),
+            map: HashMap::from([
+                ("key".to_string(), Value::BulkString(b"value".to_vec())),
+                (
+                    TRACEPARENT_FIELD.to_string(),
+                    Value::BulkString(TRACEPARENT.as_bytes().to_vec()),
+                ),
+            ]),
+        };
+
+        // Verify the result:
+        assert_eq!(
+            message.get_trace_context(),
+            Some(TraceContext::new(TRACEPARENT, None).unwrap())
+        );
+        assert!(StreamId::default().get_trace_context().is_none());
+    }
+}
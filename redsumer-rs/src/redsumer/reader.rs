@@ -0,0 +1,139 @@
+use redis::{streams::StreamId, Client};
+use tracing::{debug, info};
+
+#[allow(unused_imports)]
+use crate::core::{
+    client::{ClientArgs, RedisClientBuilder},
+    connection::VerifyConnection,
+    result::{RedsumerError, RedsumerResult},
+    streams::{
+        reader::{ReaderCommands, RANGE_END, RANGE_START},
+        types::Id,
+    },
+};
+use crate::redsumer::consumer::{StartPosition, DEFAULT_MESSAGES_COUNT};
+
+/// A plain reader of a Redis stream. It reads messages with `XREAD` and keeps its own cursor, without a consumers group or pending list, so messages do not need to be acknowledged.
+///
+/// It is intended for lightweight tailing use cases, like log followers and debugging tools. Every reader receives all the messages of the stream, and the cursor is lost when the reader is dropped.
+#[derive(Debug, Clone)]
+pub struct StreamReader {
+    /// Redis client to interact with Redis server.
+    client: Client,
+
+    /// Stream name to read.
+    stream_name: String,
+
+    /// ID of the latest message read.
+    cursor: Id,
+
+    /// Max number of messages to read in each call.
+    count: usize,
+
+    /// Time in milliseconds to wait for new messages to arrive in the stream.
+    block: usize,
+}
+
+impl StreamReader {
+    /// Get [`Client`].
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get **stream name**.
+    pub fn get_stream_name(&self) -> &str {
+        &self.stream_name
+    }
+
+    /// Get **cursor**, the ID of the latest message read. The next read returns messages with a greater ID.
+    pub fn get_cursor(&self) -> &Id {
+        &self.cursor
+    }
+
+    /// Set the max number of messages to read in each call. By default, it is [`DEFAULT_MESSAGES_COUNT`].
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Set the time in `milliseconds` to wait for new messages to arrive in the stream. By default, it is `0` and reads do not block.
+    pub fn block(mut self, block: usize) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// Build a new [`StreamReader`] instance.
+    ///
+    /// Before creating a new reader, the following validations are performed:
+    ///
+    /// - If connection string is invalid, a [`RedsumerError`] is returned.
+    /// - If connection to Redis server can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// With [`StartPosition::OnlyNewMessages`], the cursor is set to the latest message of the stream when the reader is created.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance.
+    /// - **stream_name**: Stream name to read. If a namespace is set in *args*, it is prefixed with it.
+    /// - **start_position**: The [`StartPosition`] of the stream where the reader starts reading messages.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`StreamReader`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(
+        args: &ClientArgs,
+        stream_name: &str,
+        start_position: StartPosition,
+    ) -> RedsumerResult<StreamReader> {
+        debug!(
+            "Creating a new stream reader instance by: {:?} and {stream_name}",
+            args
+        );
+
+        let stream_name: &str = &args.get_key(stream_name);
+        let mut client: Client = args.build()?;
+        client.ping()?;
+
+        let cursor: Id = match start_position {
+            StartPosition::OnlyNewMessages => match client
+                .read_rev_range(stream_name, RANGE_END, RANGE_START, 1)?
+                .first()
+            {
+                Some(latest) => latest.id.parse()?,
+                None => Id::default(),
+            },
+            start_position => start_position.to_id()?.parse()?,
+        };
+
+        info!("Stream reader instance created successfully and it is ready to be used");
+
+        Ok(StreamReader {
+            client,
+            stream_name: stream_name.to_owned(),
+            cursor,
+            count: DEFAULT_MESSAGES_COUNT,
+            block: 0,
+        })
+    }
+
+    /// Read the messages of the stream after the cursor, and move the cursor to the latest message read.
+    ///
+    /// # Arguments:
+    /// *No arguments*
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the messages found, in ascending order by ID. If no messages arrive before the block time elapses, an empty list is returned. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn read(&mut self) -> RedsumerResult<Vec<StreamId>> {
+        let messages: Vec<StreamId> = self.get_client().to_owned().read_messages(
+            &self.get_stream_name(),
+            self.get_cursor(),
+            self.count,
+            self.block,
+        )?;
+
+        if let Some(latest) = messages.last() {
+            self.cursor = latest.id.parse()?;
+            debug!("Updating stream reader cursor to: {}", self.cursor);
+        }
+
+        Ok(messages)
+    }
+}
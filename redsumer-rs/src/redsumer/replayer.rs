@@ -0,0 +1,326 @@
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use redis::{
+    streams::{StreamAddOptions, StreamId},
+    Client,
+};
+use tracing::{debug, info};
+
+#[allow(unused_imports)]
+use crate::core::{
+    client::{namespaced_key, ClientArgs, RedisClientBuilder},
+    connection::VerifyConnection,
+    result::{RedsumerError, RedsumerResult},
+    streams::{
+        consumer::ConsumerCommands,
+        producer::{message_to_items, ProducerCommands},
+        reader::{next_id, ReaderCommands},
+        types::Id,
+    },
+};
+
+/// Default number of messages read per request by a [`Replayer`].
+pub const DEFAULT_REPLAY_PAGE_SIZE: usize = 100;
+
+/// The progress of a replay.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayProgress {
+    /// Number of messages replayed.
+    replayed: usize,
+
+    /// ID of the latest message replayed.
+    last_id: Option<Id>,
+}
+
+impl ReplayProgress {
+    /// Get the number of **replayed** messages.
+    pub fn get_replayed(&self) -> usize {
+        self.replayed
+    }
+
+    /// Get the **last id** replayed. It is `None` if no message was replayed.
+    pub fn get_last_id(&self) -> Option<&Id> {
+        self.last_id.as_ref()
+    }
+}
+
+/// A callback fired with the progress of a replay, after every page of messages.
+pub type ProgressCallback = Arc<dyn Fn(&ReplayProgress) + Send + Sync>;
+
+/// A replayer of historical messages of a Redis stream, to reprocess them after a bug fix or to backfill a new service.
+///
+/// Messages can be replayed in three ways:
+///
+/// - **Into a consumers group**: the group position is moved back with `XGROUP SETID`, so the messages are delivered again to its consumers as new messages.
+/// - **Into a replay stream**: the messages of a range are republished in a dedicated stream, with the same fields and new IDs.
+/// - **Into a handler**: the messages of a range are passed to a function in the current task.
+///
+/// Replays of ranges can be rate limited and report their progress with a callback.
+#[derive(Clone)]
+pub struct Replayer {
+    /// Redis client to interact with Redis server.
+    client: Client,
+
+    /// Stream name to replay.
+    stream_name: String,
+
+    /// Namespace prepended to the keys of the replay streams.
+    namespace: Option<String>,
+
+    /// Number of messages read per request.
+    page_size: usize,
+
+    /// Max number of messages replayed per second. If it is `None`, the replay is not rate limited.
+    max_rate: Option<usize>,
+
+    /// Callback fired with the progress of the replay.
+    on_progress: Option<ProgressCallback>,
+}
+
+impl Debug for Replayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Replayer")
+            .field("client", &self.client)
+            .field("stream_name", &self.stream_name)
+            .field("namespace", &self.namespace)
+            .field("page_size", &self.page_size)
+            .field("max_rate", &self.max_rate)
+            .field("on_progress", &self.on_progress.is_some())
+            .finish()
+    }
+}
+
+impl Replayer {
+    /// Get [`Client`].
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get **stream name**.
+    pub fn get_stream_name(&self) -> &str {
+        &self.stream_name
+    }
+
+    /// Set the number of messages read per request. By default, it is [`DEFAULT_REPLAY_PAGE_SIZE`].
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Set the max number of messages replayed per second, to avoid overloading Redis or the downstream services.
+    pub fn max_rate(mut self, messages_per_second: usize) -> Self {
+        self.max_rate = Some(messages_per_second);
+        self
+    }
+
+    /// Set a callback fired with the progress of the replay, after every page of messages.
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ReplayProgress) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Build a new [`Replayer`] instance.
+    ///
+    /// Before creating a new replayer, the following validations are performed:
+    ///
+    /// - If connection string is invalid, a [`RedsumerError`] is returned.
+    /// - If connection to Redis server can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance.
+    /// - **stream_name**: Stream name to replay. If a namespace is set in *args*, it is prefixed with it, like the replay streams.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`Replayer`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs, stream_name: &str) -> RedsumerResult<Replayer> {
+        debug!(
+            "Creating a new replayer instance by: {:?} and {stream_name}",
+            args
+        );
+
+        let mut client: Client = args.build()?;
+        client.ping()?;
+
+        info!("Replayer instance created successfully and it is ready to be used");
+
+        Ok(Replayer {
+            client,
+            stream_name: args.get_key(stream_name),
+            namespace: args.get_namespace().map(str::to_owned),
+            page_size: DEFAULT_REPLAY_PAGE_SIZE,
+            max_rate: None,
+            on_progress: None,
+        })
+    }
+
+    /// Replay the messages of the stream into a consumers group, moving the group position back with `XGROUP SETID`. The messages with an ID greater than *after* are delivered again to the consumers of the group as new messages.
+    ///
+    /// Rate limiting and progress reporting do not apply, since messages are delivered by the consumers of the group.
+    ///
+    /// # Arguments:
+    /// - **group_name**: The name of the consumers group.
+    /// - **after**: The ID after which messages are delivered again. Use [`Id::default`] to replay the stream from the beginning.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] with no value if successful. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn replay_to_group(&self, group_name: &str, after: &Id) -> RedsumerResult<()> {
+        info!(
+            "Replaying stream {} into group {group_name} after {after}",
+            self.get_stream_name()
+        );
+
+        self.get_client().to_owned().set_consumer_group_id(
+            self.get_stream_name(),
+            group_name,
+            after,
+            None,
+        )
+    }
+
+    /// Replay the messages of the stream between *start* and *end*, both inclusive, into a dedicated replay stream. Messages are produced with the same fields and new IDs.
+    ///
+    /// # Arguments:
+    /// - **target_stream**: The name of the replay stream.
+    /// - **start**: The ID to start replaying from.
+    /// - **end**: The ID to end replaying at.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the final [`ReplayProgress`]. If an error occurs, a [`RedsumerError`] is returned and the messages replayed before the error are kept in the replay stream.
+    pub async fn replay_to_stream(
+        &self,
+        target_stream: &str,
+        start: &Id,
+        end: &Id,
+    ) -> RedsumerResult<ReplayProgress> {
+        info!(
+            "Replaying stream {} into stream {target_stream} from {start} to {end}",
+            self.get_stream_name()
+        );
+
+        let mut client: Client = self.get_client().to_owned();
+        let target_stream: String = namespaced_key(self.namespace.as_deref(), target_stream);
+
+        self.replay(start, end, |message| {
+            client
+                .produce_from_items(
+                    &target_stream,
+                    &message_to_items(message)?,
+                    &StreamAddOptions::default(),
+                )
+                .map(|_| ())
+        })
+    }
+
+    /// Replay the messages of the stream between *start* and *end*, both inclusive, into a handler, in ascending order by ID.
+    ///
+    /// # Arguments:
+    /// - **start**: The ID to start replaying from.
+    /// - **end**: The ID to end replaying at.
+    /// - **handler**: The function called with every message. If it returns an error, the replay is stopped.
+    ///
+    /// # Returns:
+    ///  - A [`RedsumerResult`] containing the final [`ReplayProgress`]. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn replay_with<F>(
+        &self,
+        start: &Id,
+        end: &Id,
+        handler: F,
+    ) -> RedsumerResult<ReplayProgress>
+    where
+        F: FnMut(&StreamId) -> RedsumerResult<()>,
+    {
+        info!(
+            "Replaying stream {} into a handler from {start} to {end}",
+            self.get_stream_name()
+        );
+
+        self.replay(start, end, handler)
+    }
+
+    /// Page through the messages between *start* and *end*, passing them to *handler* with the configured rate limit.
+    fn replay<F>(&self, start: &Id, end: &Id, mut handler: F) -> RedsumerResult<ReplayProgress>
+    where
+        F: FnMut(&StreamId) -> RedsumerResult<()>,
+    {
+        let mut client: Client = self.get_client().to_owned();
+        let mut progress: ReplayProgress = ReplayProgress::default();
+        let mut next: Option<Id> = Some(*start);
+        let started_at: Instant = Instant::now();
+
+        while let Some(start) = next.filter(|id| id.le(end)) {
+            let page: Vec<StreamId> =
+                client.read_range(self.get_stream_name(), start, end, self.page_size)?;
+
+            for message in page.iter() {
+                if let Some(max_rate) = self.max_rate.filter(|rate| rate.gt(&0)) {
+                    let expected: Duration =
+                        Duration::from_secs_f64(progress.replayed as f64 / max_rate as f64);
+                    if let Some(wait) = expected.checked_sub(started_at.elapsed()) {
+                        sleep(wait);
+                    }
+                }
+
+                handler(message)?;
+
+                progress.replayed += 1;
+                progress.last_id = Some(message.id.parse()?);
+            }
+
+            if let Some(callback) = &self.on_progress {
+                callback(&progress);
+            }
+
+            next = match page.len().ge(&self.page_size) {
+                true => progress.last_id.as_ref().and_then(next_id),
+                false => None,
+            };
+        }
+
+        info!("Total messages replayed: {}", progress.replayed);
+
+        Ok(progress)
+    }
+}
+
+#[cfg(test)]
+mod test_replayer {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replay_with_unreachable_server() {
+        // Build a replayer without connecting to the Redis server:
+        let replayer: Replayer = Replayer {
+            client: Client::open("redis://127.0.0.1:1/0").unwrap(),
+            stream_name: "stream".to_string(),
+            namespace: None,
+            page_size: DEFAULT_REPLAY_PAGE_SIZE,
+            max_rate: None,
+            on_progress: None,
+        }
+        .page_size(2)
+        .max_rate(10);
+
+        // Replay messages into a handler:
+        let mut handled: usize = 0;
+        let result: RedsumerResult<ReplayProgress> = replayer
+            .replay_with(&Id::new(1, 0), &Id::new(2, 0), |_| {
+                handled += 1;
+                Ok(())
+            })
+            .await;
+
+        // Verify the result:
+        assert!(result.is_err());
+        assert_eq!(handled, 0);
+        assert_eq!(replayer.page_size, 2);
+        assert_eq!(replayer.max_rate, Some(10));
+    }
+}
@@ -0,0 +1,293 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use redis::{from_redis_value, streams::StreamId, Value};
+use serde_json::Value as JsonValue;
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+
+/// Expected type of a message field, checked on the string representation of its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// A valid UTF-8 string.
+    String,
+
+    /// A signed integer of 64 bits.
+    Integer,
+
+    /// A floating point number.
+    Float,
+
+    /// A boolean: `true`, `false`, `1` or `0`.
+    Boolean,
+
+    /// A valid JSON document.
+    Json,
+}
+
+impl FieldType {
+    /// Verify if a Redis *value* matches the field type.
+    fn matches(&self, value: &Value) -> bool {
+        let raw: String = match from_redis_value::<String>(value) {
+            Ok(raw) => raw,
+            Err(_) => return false,
+        };
+
+        match self {
+            FieldType::String => true,
+            FieldType::Integer => raw.parse::<i64>().is_ok(),
+            FieldType::Float => raw.parse::<f64>().is_ok(),
+            FieldType::Boolean => matches!(raw.as_str(), "true" | "false" | "1" | "0"),
+            FieldType::Json => serde_json::from_str::<JsonValue>(&raw).is_ok(),
+        }
+    }
+}
+
+/// Kind of a [`SchemaViolation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// A required field is missing.
+    MissingField,
+
+    /// A field does not match the expected type.
+    InvalidType(FieldType),
+}
+
+/// A field of a message that does not comply with a [`MessageSchema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Name of the field.
+    field: String,
+
+    /// Kind of violation.
+    kind: ViolationKind,
+}
+
+impl SchemaViolation {
+    /// Get **field**.
+    pub fn get_field(&self) -> &str {
+        &self.field
+    }
+
+    /// Get **kind** of violation.
+    pub fn get_kind(&self) -> ViolationKind {
+        self.kind
+    }
+}
+
+impl Display for SchemaViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.kind {
+            ViolationKind::MissingField => write!(f, "field {} is missing", self.field),
+            ViolationKind::InvalidType(expected) => {
+                write!(f, "field {} is not of type {:?}", self.field, expected)
+            }
+        }
+    }
+}
+
+/// Declaration of an expected field of a [`MessageSchema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldSchema {
+    /// Name of the field.
+    name: String,
+
+    /// Expected type of the field.
+    field_type: FieldType,
+
+    /// Whether the field must be present.
+    required: bool,
+}
+
+/// A lightweight declarative schema of the fields of a message, to validate consumed messages and get every violation at once instead of the first deserialization error.
+///
+/// Fields not declared in the schema are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageSchema {
+    /// Declared fields.
+    fields: Vec<FieldSchema>,
+}
+
+impl MessageSchema {
+    /// Build a new empty [`MessageSchema`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a required field.
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the field.
+    /// - **field_type**: The expected type of the field.
+    ///
+    /// # Returns:
+    /// The [`MessageSchema`] instance with the given field.
+    pub fn required(self, name: &str, field_type: FieldType) -> Self {
+        self.field(name, field_type, true)
+    }
+
+    /// Declare an optional field, whose type is checked only when it is present.
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the field.
+    /// - **field_type**: The expected type of the field.
+    ///
+    /// # Returns:
+    /// The [`MessageSchema`] instance with the given field.
+    pub fn optional(self, name: &str, field_type: FieldType) -> Self {
+        self.field(name, field_type, false)
+    }
+
+    /// Declare a field, replacing a previous declaration with the same name.
+    fn field(mut self, name: &str, field_type: FieldType, required: bool) -> Self {
+        self.fields.retain(|field| field.name.ne(name));
+        self.fields.push(FieldSchema {
+            name: name.to_owned(),
+            field_type,
+            required,
+        });
+        self
+    }
+
+    /// Validate a *message* against the schema.
+    ///
+    /// # Arguments:
+    /// - **message**: A consumed message.
+    ///
+    /// # Returns:
+    /// A list of [`SchemaViolation`], in the same order the fields were declared. It is empty if the message complies with the schema.
+    pub fn validate(&self, message: &StreamId) -> Vec<SchemaViolation> {
+        self.fields
+            .iter()
+            .filter_map(|field| {
+                let kind: ViolationKind = match message.map.get(&field.name) {
+                    None if field.required => ViolationKind::MissingField,
+                    None => return None,
+                    Some(value) if field.field_type.matches(value) => return None,
+                    Some(_) => ViolationKind::InvalidType(field.field_type),
+                };
+
+                Some(SchemaViolation {
+                    field: field.name.to_owned(),
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    /// Verify that a *message* complies with the schema.
+    ///
+    /// # Arguments:
+    /// - **message**: A consumed message.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` if the message complies with the schema. Otherwise, a [`RedsumerError::ValidationError`] listing every violation.
+    pub fn check(&self, message: &StreamId) -> RedsumerResult<()> {
+        let violations: Vec<SchemaViolation> = self.validate(message);
+
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(RedsumerError::ValidationError(format!(
+                "Message {} does not comply with the schema: {}",
+                message.id,
+                violations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_message_schema {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// Build a message from string pairs.
+    fn message(pairs: &[(&str, &str)]) -> StreamId {
+        StreamId {
+            id: "1-0".to_string(),
+            map: pairs
+                .iter()
+                .map(|(field, value)| {
+                    (
+                        field.to_string(),
+                        Value::BulkString(value.as_bytes().to_vec()),
+                    )
+                })
+                .collect::<HashMap<String, Value>>(),
+        }
+    }
+
+    /// Build a schema with a field of every type.
+    fn schema() -> MessageSchema {
+        MessageSchema::new()
+            .required("name", FieldType::String)
+            .required("count", FieldType::Integer)
+            .required("ratio", FieldType::Float)
+            .optional("active", FieldType::Boolean)
+            .optional("payload", FieldType::Json)
+    }
+
+    #[test]
+    fn test_message_schema_valid_message() {
+        // Define a valid message:
+        let message: StreamId = message(&[
+            ("name", "sensor"),
+            ("count", "3"),
+            ("ratio", "0.5"),
+            ("active", "true"),
+            ("payload", r#"{"a": 1}"#),
+            ("extra", "ignored"),
+        ]);
+
+        // Verify the result:
+        assert!(schema().validate(&message).is_empty());
+        assert!(schema().check(&message).is_ok());
+    }
+
+    #[test]
+    fn test_message_schema_violations() {
+        // Define an invalid message:
+        let message: StreamId = message(&[
+            ("count", "three"),
+            ("ratio", "0.5"),
+            ("payload", "{not-json"),
+        ]);
+
+        // Validate the message:
+        let violations: Vec<SchemaViolation> = schema().validate(&message);
+
+        // Verify the result:
+        assert_eq!(violations.len(), 3);
+        assert_eq!(violations[0].get_field(), "name");
+        assert_eq!(violations[0].get_kind(), ViolationKind::MissingField);
+        assert_eq!(violations[1].get_field(), "count");
+        assert_eq!(
+            violations[1].get_kind(),
+            ViolationKind::InvalidType(FieldType::Integer)
+        );
+        assert_eq!(
+            violations[2].get_kind(),
+            ViolationKind::InvalidType(FieldType::Json)
+        );
+        assert_eq!(
+            schema().check(&message).unwrap_err().to_string(),
+            "message validation failed: Message 1-0 does not comply with the schema: field name is missing, field count is not of type Integer, field payload is not of type Json"
+        );
+    }
+
+    #[test]
+    fn test_message_schema_replaces_field() {
+        // Declare the same field twice:
+        let schema: MessageSchema = MessageSchema::new()
+            .required("count", FieldType::Integer)
+            .optional("count", FieldType::String);
+
+        // Verify the result:
+        assert!(schema.validate(&message(&[])).is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use redis::{Client, ErrorKind, FromRedisValue, ToRedisArgs};
+use tracing::{debug, info};
+
+#[allow(unused_imports)]
+use crate::core::{
+    blocking::run_blocking,
+    client::{ClientArgs, RedisClientBuilder},
+    connection::VerifyConnection,
+    result::{RedsumerError, RedsumerResult},
+    scripting::{LuaScript, ScriptCommands},
+};
+
+/// A registry of Lua scripts for custom atomic operations on streams, like moving a message between streams or acking and producing at once.
+///
+/// Scripts are registered by name and run with `EVALSHA` through the same connection handling used by producers and consumers. If a script is not in the script cache of the server, like after a restart or a failover, it is loaded with `SCRIPT LOAD` and run again. Clones share the registered scripts.
+#[derive(Debug, Clone)]
+pub struct ScriptRegistry {
+    /// Redis client to interact with Redis server. It can be shared with producers and consumers.
+    client: Arc<Client>,
+
+    /// Registered scripts by name.
+    scripts: Arc<RwLock<HashMap<String, LuaScript>>>,
+}
+
+impl ScriptRegistry {
+    /// Get [`Client`].
+    fn get_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Build a new [`ScriptRegistry`] instance.
+    ///
+    /// If connection string is invalid or connection to Redis server can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// # Arguments:
+    /// - **args**: Client arguments to build a new [`Client`] instance.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`ScriptRegistry`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn new(args: &ClientArgs) -> RedsumerResult<ScriptRegistry> {
+        debug!("Creating a new script registry instance by: {:?}", args);
+
+        Self::from_client(Arc::new(args.build()?))
+    }
+
+    /// Build a new [`ScriptRegistry`] instance from a shared [`Client`], like the one of a [`Producer`](crate::producer::Producer) or a [`Consumer`](crate::consumer::Consumer).
+    ///
+    /// If connection to Redis server can not be established, a [`RedsumerError`] is returned.
+    ///
+    /// # Arguments:
+    /// - **client**: A shared Redis client.
+    ///
+    ///  # Returns:
+    ///  - A [`RedsumerResult`] with the new [`ScriptRegistry`] instance. Otherwise, a [`RedsumerError`] is returned.
+    pub fn from_client(client: Arc<Client>) -> RedsumerResult<ScriptRegistry> {
+        client.as_ref().to_owned().ping()?;
+
+        info!("Script registry instance created successfully and it is ready to be used");
+
+        Ok(ScriptRegistry {
+            client,
+            scripts: Arc::default(),
+        })
+    }
+
+    /// Register a script by *name*, replacing the script registered before with the same name. The script is not sent to the server until it is run or [`load_all`](ScriptRegistry::load_all) is called.
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the script.
+    /// - **source**: The Lua source code of the script.
+    ///
+    /// # Returns:
+    /// The SHA1 digest of the script.
+    pub fn register(&self, name: &str, source: &str) -> String {
+        let script: LuaScript = LuaScript::new(source);
+        let hash: String = script.get_hash().to_owned();
+
+        debug!("Registering script {name}: {hash}");
+        self.scripts
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_owned(), script);
+
+        hash
+    }
+
+    /// Get a registered script by *name*.
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the script.
+    ///
+    /// # Returns:
+    /// The [`LuaScript`] registered with *name*, or `None` if there is no script with that name.
+    pub fn get_script(&self, name: &str) -> Option<LuaScript> {
+        self.scripts
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    /// Load every registered script in the script cache of the server with `SCRIPT LOAD`. It is optional, since scripts are loaded the first time they are run, but it avoids a round trip per script on the hot path.
+    ///
+    /// # Arguments:
+    /// - No arguments.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with the number of scripts loaded. If an error occurs, a [`RedsumerError`] is returned.
+    pub async fn load_all(&self) -> RedsumerResult<usize> {
+        let scripts: Vec<LuaScript> = self
+            .scripts
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+
+        let mut client: Client = self.get_client().to_owned();
+        run_blocking(move || {
+            for script in scripts.iter() {
+                client.load_script(script)?;
+            }
+
+            Ok(scripts.len())
+        })
+        .await
+    }
+
+    /// Run a registered script by *name* with `EVALSHA`, loading it first if it is not in the script cache of the server.
+    ///
+    /// # Arguments:
+    /// - **name**: The name of the script.
+    /// - **keys**: The keys accessed by the script, available as *KEYS*. Each one must implement the [`ToRedisArgs`] trait.
+    /// - **args**: The arguments of the script, available as *ARGV*. Each one must implement the [`ToRedisArgs`] trait.
+    ///
+    /// # Returns:
+    /// - A [`RedsumerResult`] with the reply of the script. If no script is registered with *name* or an error occurs, a [`RedsumerError`] is returned.
+    pub async fn invoke<K, A, T>(&self, name: &str, keys: &[K], args: &[A]) -> RedsumerResult<T>
+    where
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+        T: FromRedisValue + Send + 'static,
+    {
+        let script: LuaScript = self.get_script(name).ok_or_else(|| {
+            RedsumerError::from((
+                ErrorKind::ClientError,
+                "Script not registered",
+                name.to_owned(),
+            ))
+        })?;
+
+        let keys: Vec<Vec<u8>> = keys.iter().flat_map(|key| key.to_redis_args()).collect();
+        let args: Vec<Vec<u8>> = args.iter().flat_map(|arg| arg.to_redis_args()).collect();
+
+        let mut client: Client = self.get_client().to_owned();
+        run_blocking(move || client.run_script(&script, &keys, &args)).await
+    }
+}
+
+#[cfg(test)]
+impl ScriptRegistry {
+    /// Build a new [`ScriptRegistry`] instance with a client of an unreachable server, to test the operations that do not reach it.
+    pub(crate) fn unconnected() -> ScriptRegistry {
+        ScriptRegistry {
+            client: Arc::new(Client::open("redis://127.0.0.1:1/0").unwrap()),
+            scripts: Arc::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_script_registry {
+    use super::*;
+
+    #[test]
+    fn test_script_registry_register() {
+        // Create a new registry:
+        let registry: ScriptRegistry = ScriptRegistry::unconnected();
+
+        // Register a script and replace it in a clone:
+        let hash: String = registry.register("echo", "return ARGV[1]");
+        let replaced: String = registry.clone().register("echo", "return KEYS[1]");
+
+        // Verify the result:
+        assert_eq!(hash, LuaScript::new("return ARGV[1]").get_hash());
+        assert_ne!(hash, replaced);
+        assert_eq!(
+            registry.get_script("echo").unwrap().get_source(),
+            "return KEYS[1]"
+        );
+        assert!(registry.get_script("unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_script_registry_invoke_unknown_script() {
+        // Create a new registry:
+        let registry: ScriptRegistry = ScriptRegistry::unconnected();
+
+        // Run a script that is not registered:
+        let result: RedsumerResult<String> =
+            registry.invoke::<&str, &str, _>("unknown", &[], &[]).await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::ClientError);
+    }
+
+    #[tokio::test]
+    async fn test_script_registry_load_all_without_scripts() {
+        // Create a new registry:
+        let registry: ScriptRegistry = ScriptRegistry::unconnected();
+
+        // Load the registered scripts, which does not reach the server:
+        let result: RedsumerResult<usize> = registry.load_all().await;
+
+        // Verify the result:
+        assert_eq!(result.unwrap(), 0);
+    }
+}
@@ -0,0 +1,456 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Instant,
+};
+
+use redis::{streams::StreamId, ErrorKind, ToRedisArgs, Value};
+use time::OffsetDateTime;
+
+#[allow(unused_imports)]
+use crate::core::{
+    result::{RedsumerError, RedsumerResult},
+    streams::types::{EntryId, Id},
+};
+use crate::redsumer::{
+    consumer::{
+        AckMessageReply, AckToken, ConsumeMessagesReply, ConsumerConfig, IsStillMineReply,
+        MessagesKind,
+    },
+    producer::ProduceMessageReply,
+    traits::{MessageSink, MessageSource},
+};
+
+/// A message delivered to a consumer and not acked yet.
+#[derive(Debug, Clone)]
+struct PendingEntry {
+    /// The consumer that owns the message.
+    consumer: String,
+
+    /// The last time the message was delivered.
+    delivered_at: Instant,
+
+    /// The number of times the message was delivered.
+    deliveries: usize,
+}
+
+/// The state of a consumers group.
+#[derive(Debug, Clone, Default)]
+struct GroupState {
+    /// The ID of the last message delivered to the group.
+    last_delivered_id: EntryId,
+
+    /// The pending entries list of the group.
+    pending: BTreeMap<EntryId, PendingEntry>,
+}
+
+/// The state of an in-memory stream.
+#[derive(Debug, Default)]
+struct StreamState {
+    /// The messages of the stream, ordered by ID.
+    entries: BTreeMap<EntryId, HashMap<String, Value>>,
+
+    /// The ID of the last produced message.
+    last_id: EntryId,
+
+    /// The consumers groups of the stream.
+    groups: HashMap<String, GroupState>,
+}
+
+impl StreamState {
+    /// Get the next ID to produce a message, ensuring it is greater than the last one.
+    fn next_id(&mut self) -> EntryId {
+        let milliseconds: u64 =
+            (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000).max(0) as u64;
+
+        self.last_id = match milliseconds.gt(&self.last_id.get_milliseconds()) {
+            true => EntryId::new(milliseconds, 0),
+            false => EntryId::new(
+                self.last_id.get_milliseconds(),
+                self.last_id.get_sequence() + 1,
+            ),
+        };
+
+        self.last_id
+    }
+
+    /// Get a message of the stream as a [`StreamId`].
+    fn get_message(&self, id: &EntryId) -> Option<StreamId> {
+        self.entries.get(id).map(|map| StreamId {
+            id: id.to_string(),
+            map: map.to_owned(),
+        })
+    }
+}
+
+/// An in-memory implementation of a stream, for fast and deterministic unit tests of handler logic without a Redis server.
+///
+/// It implements [`MessageSink`] to produce messages, and it creates [`InMemoryConsumer`] instances that implement [`MessageSource`]. Messages are ordered by ID and each consumers group keeps its own pending entries list, so new, pending and claimed messages are delivered with the same steps used by [`Consumer::consume`](crate::consumer::Consumer::consume). Stream trimming, message deletion and blocking reads are not supported. Clones share the same stream.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryStream {
+    /// The shared state of the stream.
+    state: Arc<Mutex<StreamState>>,
+}
+
+impl InMemoryStream {
+    /// Lock the state of the stream.
+    fn lock(&self) -> MutexGuard<'_, StreamState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Build a new empty [`InMemoryStream`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of messages in the stream.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Verify if the stream has no messages.
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Get the number of pending messages of a consumers group.
+    pub fn pending_count(&self, group_name: &str) -> usize {
+        self.lock()
+            .groups
+            .get(group_name)
+            .map(|group| group.pending.len())
+            .unwrap_or(0)
+    }
+
+    /// Build a new [`InMemoryConsumer`] instance. If the consumers group does not exist, it is created to deliver all the messages of the stream.
+    ///
+    /// # Arguments:
+    /// - **config**: Consumer configuration parameters. Block times are ignored.
+    ///
+    /// # Returns:
+    /// A new [`InMemoryConsumer`] instance.
+    pub fn consumer(&self, config: ConsumerConfig) -> InMemoryConsumer {
+        self.lock()
+            .groups
+            .entry(config.get_group_name().to_owned())
+            .or_default();
+
+        InMemoryConsumer {
+            stream: self.clone(),
+            config,
+            latest_pending_message_id: EntryId::default(),
+        }
+    }
+
+    /// Add a message to the stream.
+    fn add<M>(&self, message: M) -> RedsumerResult<Id>
+    where
+        M: ToRedisArgs,
+    {
+        let args: Vec<Vec<u8>> = message.to_redis_args();
+        if args.is_empty() || args.len() % 2 != 0 {
+            return Err(RedsumerError::from((
+                ErrorKind::ResponseError,
+                "wrong number of arguments for 'xadd' command",
+            )));
+        }
+
+        let map: HashMap<String, Value> = args
+            .chunks_exact(2)
+            .map(|pair| {
+                (
+                    String::from_utf8_lossy(&pair[0]).into_owned(),
+                    Value::BulkString(pair[1].to_owned()),
+                )
+            })
+            .collect();
+
+        let mut state: MutexGuard<'_, StreamState> = self.lock();
+        let id: EntryId = state.next_id();
+        state.entries.insert(id, map);
+
+        Ok(id)
+    }
+}
+
+impl MessageSink for InMemoryStream {
+    async fn produce_from_map<M>(&self, map: M) -> RedsumerResult<ProduceMessageReply>
+    where
+        M: ToRedisArgs + Send,
+    {
+        self.add(map).map(ProduceMessageReply::from)
+    }
+
+    async fn produce_from_items<F, V>(
+        &self,
+        items: Vec<(F, V)>,
+    ) -> RedsumerResult<ProduceMessageReply>
+    where
+        F: ToRedisArgs + Send,
+        V: ToRedisArgs + Send,
+    {
+        self.add(items).map(ProduceMessageReply::from)
+    }
+}
+
+/// An in-memory consumer of an [`InMemoryStream`]. See [`InMemoryStream::consumer`].
+#[derive(Debug, Clone)]
+pub struct InMemoryConsumer {
+    /// The stream to consume messages from.
+    stream: InMemoryStream,
+
+    /// Consumer configuration parameters.
+    config: ConsumerConfig,
+
+    /// The ID of the latest pending message read by the consumer.
+    latest_pending_message_id: EntryId,
+}
+
+impl InMemoryConsumer {
+    /// Get *config*.
+    pub fn get_config(&self) -> &ConsumerConfig {
+        &self.config
+    }
+
+    /// Read new, pending or claimed messages from the stream, in that order.
+    fn read_messages(&mut self) -> ConsumeMessagesReply {
+        let consumer: String = self.config.get_consumer_name().to_owned();
+        let now: Instant = Instant::now();
+
+        let mut guard: MutexGuard<'_, StreamState> = self.stream.lock();
+        let state: &mut StreamState = &mut guard;
+        let group: &mut GroupState = state
+            .groups
+            .entry(self.config.get_group_name().to_owned())
+            .or_default();
+
+        let new_ids: Vec<EntryId> = state
+            .entries
+            .range(group.last_delivered_id..)
+            .map(|(id, _)| *id)
+            .filter(|id| id.gt(&group.last_delivered_id))
+            .take(self.config.get_read_new_messages_options().get_count())
+            .collect();
+        if let Some(last) = new_ids.last() {
+            group.last_delivered_id = *last;
+            for id in new_ids.iter() {
+                group.pending.insert(
+                    *id,
+                    PendingEntry {
+                        consumer: consumer.to_owned(),
+                        delivered_at: now,
+                        deliveries: 1,
+                    },
+                );
+            }
+            let messages: Vec<StreamId> = new_ids
+                .iter()
+                .filter_map(|id| state.get_message(id))
+                .collect();
+            return (messages, MessagesKind::New).into();
+        }
+
+        let pending_ids: Vec<EntryId> = group
+            .pending
+            .iter()
+            .filter(|(id, entry)| {
+                id.gt(&&self.latest_pending_message_id) && entry.consumer.eq(&consumer)
+            })
+            .map(|(id, _)| *id)
+            .take(self.config.get_read_pending_messages_options().get_count())
+            .collect();
+        self.latest_pending_message_id = pending_ids.last().copied().unwrap_or_default();
+        if !pending_ids.is_empty() {
+            for id in pending_ids.iter() {
+                if let Some(entry) = group.pending.get_mut(id) {
+                    entry.delivered_at = now;
+                    entry.deliveries += 1;
+                }
+            }
+            let messages: Vec<StreamId> = pending_ids
+                .iter()
+                .filter_map(|id| state.get_message(id))
+                .collect();
+            return (messages, MessagesKind::Pending).into();
+        }
+
+        let min_idle_time: u128 =
+            self.config.get_claim_messages_options().get_min_idle_time() as u128;
+        let claimed_ids: Vec<EntryId> = group
+            .pending
+            .iter()
+            .filter(|(_, entry)| {
+                entry.consumer.ne(&consumer)
+                    && now
+                        .duration_since(entry.delivered_at)
+                        .as_millis()
+                        .ge(&min_idle_time)
+            })
+            .map(|(id, _)| *id)
+            .take(self.config.get_claim_messages_options().get_count())
+            .collect();
+        if !claimed_ids.is_empty() {
+            for id in claimed_ids.iter() {
+                if let Some(entry) = group.pending.get_mut(id) {
+                    entry.consumer = consumer.to_owned();
+                    entry.delivered_at = now;
+                    entry.deliveries += 1;
+                }
+            }
+            let messages: Vec<StreamId> = claimed_ids
+                .iter()
+                .filter_map(|id| state.get_message(id))
+                .collect();
+            return (messages, MessagesKind::Claimed).into();
+        }
+
+        (Vec::new(), MessagesKind::NotFound).into()
+    }
+}
+
+impl MessageSource for InMemoryConsumer {
+    async fn consume(&mut self) -> RedsumerResult<ConsumeMessagesReply> {
+        let mut reply: ConsumeMessagesReply = self.read_messages();
+        reply.issue_ack_tokens(
+            self.config.get_stream_name(),
+            self.config.get_group_name(),
+            self.config.get_consumer_name(),
+        );
+        Ok(reply)
+    }
+
+    fn is_still_mine(&self, id: &Id) -> RedsumerResult<IsStillMineReply> {
+        let state: MutexGuard<'_, StreamState> = self.stream.lock();
+
+        let entry: Option<&PendingEntry> = state
+            .groups
+            .get(self.config.get_group_name())
+            .and_then(|group| group.pending.get(id))
+            .filter(|entry| entry.consumer.eq(self.config.get_consumer_name()));
+
+        Ok(match entry {
+            Some(entry) => IsStillMineReply::from((
+                true,
+                Some(entry.delivered_at.elapsed().as_millis() as usize),
+                Some(entry.deliveries),
+            )),
+            None => IsStillMineReply::from((false, None, None)),
+        })
+    }
+
+    async fn ack(&self, token: &AckToken) -> RedsumerResult<AckMessageReply> {
+        token.verify(&self.config)?;
+
+        let mut state: MutexGuard<'_, StreamState> = self.stream.lock();
+
+        let was_acked: bool = state
+            .groups
+            .get_mut(self.config.get_group_name())
+            .and_then(|group| group.pending.remove(token.get_id()))
+            .is_some();
+
+        Ok(AckMessageReply::from(was_acked))
+    }
+}
+
+#[cfg(test)]
+mod test_in_memory_stream {
+    use super::*;
+
+    /// Build a consumer configuration for tests.
+    fn config(consumer_name: &str) -> ConsumerConfig {
+        ConsumerConfig::builder("stream", "group", consumer_name)
+            .new_messages_count(2)
+            .min_idle_time(0)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_stream_produce() {
+        // Define an in-memory stream:
+        let stream: InMemoryStream = InMemoryStream::new();
+
+        // Produce messages:
+        let first: ProduceMessageReply =
+            stream.produce_from_items(vec![("key", "1")]).await.unwrap();
+        let second: ProduceMessageReply = stream.produce_from_map(&[("key", "2")]).await.unwrap();
+
+        // Verify the result:
+        assert_eq!(stream.len(), 2);
+        assert!(first.get_id() < second.get_id());
+        assert!(stream
+            .produce_from_items(Vec::<(&str, &str)>::new())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_stream_consume() {
+        // Define an in-memory stream with messages:
+        let stream: InMemoryStream = InMemoryStream::new();
+        for value in ["1", "2", "3"] {
+            stream
+                .produce_from_items(vec![("key", value)])
+                .await
+                .unwrap();
+        }
+
+        // Consume new messages:
+        let mut consumer: InMemoryConsumer = stream.consumer(config("consumer"));
+
+        let reply: ConsumeMessagesReply = consumer.consume().await.unwrap();
+        assert!(reply.are_new());
+        assert_eq!(reply.get_messages().len(), 2);
+
+        let reply: ConsumeMessagesReply = consumer.consume().await.unwrap();
+        assert!(reply.are_new());
+        assert_eq!(reply.get_messages().len(), 1);
+
+        // Consume pending messages:
+        let reply: ConsumeMessagesReply = consumer.consume().await.unwrap();
+        assert!(reply.are_pending());
+        assert_eq!(reply.get_messages().len(), 3);
+
+        // Ack a message:
+        let token: &AckToken = &reply.get_ack_tokens()[0];
+        assert!(consumer
+            .is_still_mine(token.get_id())
+            .unwrap()
+            .belongs_to_me());
+        assert!(consumer.ack(token).await.unwrap().was_acked());
+        assert!(!consumer.ack(token).await.unwrap().was_acked());
+        assert_eq!(stream.pending_count("group"), 2);
+
+        // Ack with a token issued by another consumer:
+        let other: InMemoryConsumer = stream.consumer(config("other"));
+        assert!(other.ack(token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_stream_claim() {
+        // Define an in-memory stream with messages:
+        let stream: InMemoryStream = InMemoryStream::new();
+        stream.produce_from_items(vec![("key", "1")]).await.unwrap();
+
+        // Deliver the message to the first consumer:
+        let mut first: InMemoryConsumer = stream.consumer(config("first"));
+        let reply: ConsumeMessagesReply = first.consume().await.unwrap();
+        let id: Id = reply.get_messages()[0].id.parse().unwrap();
+
+        // Claim the message by the second consumer:
+        let mut second: InMemoryConsumer = stream.consumer(config("second"));
+        let reply: ConsumeMessagesReply = second.consume().await.unwrap();
+
+        // Verify the result:
+        assert!(reply.were_claimed());
+        assert!(!first.is_still_mine(&id).unwrap().belongs_to_me());
+        assert_eq!(
+            second
+                .is_still_mine(&id)
+                .unwrap()
+                .get_total_times_delivered(),
+            Some(2)
+        );
+    }
+}
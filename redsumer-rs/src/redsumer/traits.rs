@@ -0,0 +1,152 @@
+use std::future::Future;
+
+use redis::ToRedisArgs;
+
+#[allow(unused_imports)]
+use crate::core::{
+    connection::StreamConnection,
+    result::{RedsumerError, RedsumerResult},
+    streams::types::Id,
+};
+use crate::redsumer::{
+    consumer::{AckMessageReply, AckToken, ConsumeMessagesReply, Consumer, IsStillMineReply},
+    producer::{ProduceMessageReply, Producer},
+};
+
+/// A source of stream messages. It is implemented by [`Consumer`], so application code can depend on this trait and unit tests can use in-memory doubles without a Redis server.
+pub trait MessageSource {
+    /// Consume messages from the source. See [`Consumer::consume`].
+    fn consume(&mut self) -> impl Future<Output = RedsumerResult<ConsumeMessagesReply>> + Send;
+
+    /// Verify if a specific message by *id* is still in the consumer pending list. See [`Consumer::is_still_mine`].
+    fn is_still_mine(&self, id: &Id) -> RedsumerResult<IsStillMineReply>;
+
+    /// Ack a message by its *token*. See [`Consumer::ack`].
+    fn ack(&self, token: &AckToken)
+        -> impl Future<Output = RedsumerResult<AckMessageReply>> + Send;
+}
+
+/// A sink of stream messages. It is implemented by [`Producer`], so application code can depend on this trait and unit tests can use in-memory doubles without a Redis server.
+pub trait MessageSink {
+    /// Produce a new message from a map. See [`Producer::produce_from_map`].
+    fn produce_from_map<M>(
+        &self,
+        map: M,
+    ) -> impl Future<Output = RedsumerResult<ProduceMessageReply>> + Send
+    where
+        M: ToRedisArgs + Send;
+
+    /// Produce a new message from a list of items. See [`Producer::produce_from_items`].
+    fn produce_from_items<F, V>(
+        &self,
+        items: Vec<(F, V)>,
+    ) -> impl Future<Output = RedsumerResult<ProduceMessageReply>> + Send
+    where
+        F: ToRedisArgs + Send,
+        V: ToRedisArgs + Send;
+}
+
+impl<C> MessageSource for Consumer<C>
+where
+    C: StreamConnection,
+{
+    fn consume(&mut self) -> impl Future<Output = RedsumerResult<ConsumeMessagesReply>> + Send {
+        Consumer::consume(self)
+    }
+
+    fn is_still_mine(&self, id: &Id) -> RedsumerResult<IsStillMineReply> {
+        Consumer::is_still_mine(self, id)
+    }
+
+    fn ack(
+        &self,
+        token: &AckToken,
+    ) -> impl Future<Output = RedsumerResult<AckMessageReply>> + Send {
+        Consumer::ack(self, token)
+    }
+}
+
+impl<C> MessageSink for Producer<C>
+where
+    C: StreamConnection,
+{
+    fn produce_from_map<M>(
+        &self,
+        map: M,
+    ) -> impl Future<Output = RedsumerResult<ProduceMessageReply>> + Send
+    where
+        M: ToRedisArgs + Send,
+    {
+        Producer::produce_from_map(self, map)
+    }
+
+    fn produce_from_items<F, V>(
+        &self,
+        items: Vec<(F, V)>,
+    ) -> impl Future<Output = RedsumerResult<ProduceMessageReply>> + Send
+    where
+        F: ToRedisArgs + Send,
+        V: ToRedisArgs + Send,
+    {
+        Producer::produce_from_items(self, items)
+    }
+}
+
+#[cfg(test)]
+mod test_message_sink {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// An in-memory sink that keeps the encoded messages.
+    #[derive(Debug, Default)]
+    struct MemorySink {
+        messages: Mutex<Vec<Vec<Vec<u8>>>>,
+    }
+
+    impl MessageSink for MemorySink {
+        async fn produce_from_map<M>(&self, map: M) -> RedsumerResult<ProduceMessageReply>
+        where
+            M: ToRedisArgs + Send,
+        {
+            let mut messages = self.messages.lock().unwrap();
+            messages.push(map.to_redis_args());
+
+            Ok(ProduceMessageReply::from(Id::new(messages.len() as u64, 0)))
+        }
+
+        async fn produce_from_items<F, V>(
+            &self,
+            items: Vec<(F, V)>,
+        ) -> RedsumerResult<ProduceMessageReply>
+        where
+            F: ToRedisArgs + Send,
+            V: ToRedisArgs + Send,
+        {
+            self.produce_from_map(items).await
+        }
+    }
+
+    /// Application code that depends on the trait.
+    async fn publish<S: MessageSink>(sink: &S, user: &str) -> RedsumerResult<Id> {
+        sink.produce_from_items(vec![("user", user)])
+            .await
+            .map(|reply| *reply.get_id())
+    }
+
+    #[tokio::test]
+    async fn test_message_sink_double() {
+        // Define an in-memory sink:
+        let sink: MemorySink = MemorySink::default();
+
+        // Publish messages:
+        assert_eq!(publish(&sink, "a").await.unwrap(), Id::new(1, 0));
+        assert_eq!(publish(&sink, "b").await.unwrap(), Id::new(2, 0));
+
+        // Verify the result:
+        assert_eq!(
+            sink.messages.lock().unwrap()[1],
+            vec![b"user".to_vec(), b"b".to_vec()]
+        );
+    }
+}
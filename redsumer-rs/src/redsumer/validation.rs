@@ -0,0 +1,161 @@
+use std::fmt::Debug;
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+use crate::redsumer::interceptor::MessageFields;
+
+/// A validator of the messages to produce, to reject malformed events before they are shipped downstream.
+///
+/// Validators are registered with [`Producer::with_validator`](crate::producer::Producer::with_validator) and they are called before `XADD`, after the interceptors, in the same order they were registered. If a validator rejects a message, it is not produced and a [`RedsumerError::ValidationError`] is returned to the caller.
+pub trait Validator: Debug + Send + Sync {
+    /// Validate a message to produce.
+    ///
+    /// # Arguments:
+    /// - **stream**: The stream name.
+    /// - **fields**: The fields of the message to produce.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with `()` if the message is valid. Otherwise, a [`RedsumerError::ValidationError`] describing why it was rejected.
+    fn validate(&self, stream: &str, fields: &MessageFields) -> RedsumerResult<()>;
+}
+
+/// A [`Validator`] that rejects messages without a set of required fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredFieldsValidator {
+    /// Names of the fields every message must have.
+    fields: Vec<String>,
+}
+
+impl RequiredFieldsValidator {
+    /// Get **fields**.
+    pub fn get_fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Build a new [`RequiredFieldsValidator`] instance.
+    ///
+    /// # Arguments:
+    /// - **fields**: The names of the fields every message must have.
+    ///
+    /// # Returns:
+    /// A new instance of [`RequiredFieldsValidator`].
+    pub fn new(fields: &[&str]) -> Self {
+        RequiredFieldsValidator {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+        }
+    }
+}
+
+impl Validator for RequiredFieldsValidator {
+    fn validate(&self, stream: &str, fields: &MessageFields) -> RedsumerResult<()> {
+        let missing: Vec<&str> = self
+            .fields
+            .iter()
+            .filter(|required| {
+                !fields
+                    .iter()
+                    .any(|(field, _)| field.eq(required.as_bytes()))
+            })
+            .map(String::as_str)
+            .collect();
+
+        match missing.is_empty() {
+            true => Ok(()),
+            false => Err(RedsumerError::ValidationError(format!(
+                "Message to stream {stream} is missing required fields: {}",
+                missing.join(", ")
+            ))),
+        }
+    }
+}
+
+/// A [`Validator`] that rejects messages whose fields and values exceed a max size in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxSizeValidator {
+    /// Max size of a message in bytes, adding up its fields and values.
+    max_bytes: usize,
+}
+
+impl MaxSizeValidator {
+    /// Get **max bytes**.
+    pub fn get_max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Build a new [`MaxSizeValidator`] instance.
+    ///
+    /// # Arguments:
+    /// - **max_bytes**: The max size of a message in bytes, adding up its fields and values.
+    ///
+    /// # Returns:
+    /// A new instance of [`MaxSizeValidator`].
+    pub fn new(max_bytes: usize) -> Self {
+        MaxSizeValidator { max_bytes }
+    }
+}
+
+impl Validator for MaxSizeValidator {
+    fn validate(&self, stream: &str, fields: &MessageFields) -> RedsumerResult<()> {
+        let size: usize = fields
+            .iter()
+            .map(|(field, value)| field.len() + value.len())
+            .sum();
+
+        match size.gt(&self.max_bytes) {
+            true => Err(RedsumerError::ValidationError(format!(
+                "Message to stream {stream} has {size} bytes, max allowed: {}",
+                self.max_bytes
+            ))),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_validators {
+    use super::*;
+
+    /// Build the fields of a message from string pairs.
+    fn fields(pairs: &[(&str, &str)]) -> MessageFields {
+        pairs
+            .iter()
+            .map(|(field, value)| (field.as_bytes().to_vec(), value.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_required_fields_validator() {
+        // Define the validator:
+        let validator: RequiredFieldsValidator = RequiredFieldsValidator::new(&["id", "kind"]);
+
+        // Validate a complete and an incomplete message:
+        let valid: RedsumerResult<()> =
+            validator.validate("stream", &fields(&[("id", "1"), ("kind", "created")]));
+        let invalid: RedsumerResult<()> = validator.validate("stream", &fields(&[("id", "1")]));
+
+        // Verify the result:
+        assert!(valid.is_ok());
+        assert_eq!(
+            invalid.unwrap_err().to_string(),
+            "message validation failed: Message to stream stream is missing required fields: kind"
+        );
+    }
+
+    #[test]
+    fn test_max_size_validator() {
+        // Define the validator:
+        let validator: MaxSizeValidator = MaxSizeValidator::new(8);
+
+        // Validate a small and an oversized message:
+        let valid: RedsumerResult<()> = validator.validate("stream", &fields(&[("id", "123456")]));
+        let invalid: RedsumerResult<()> =
+            validator.validate("stream", &fields(&[("id", "1234567")]));
+
+        // Verify the result:
+        assert!(valid.is_ok());
+        assert!(matches!(
+            invalid.unwrap_err(),
+            RedsumerError::ValidationError(_)
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::Arc,
+};
+
+use redis::streams::StreamId;
+use tracing::{debug, error};
+
+#[allow(unused_imports)]
+use crate::core::result::{RedsumerError, RedsumerResult};
+use crate::redsumer::envelope::{Headers, SCHEMA_VERSION_HEADER};
+
+/// A function to deserialize a consumed message of a specific schema version.
+pub type DeserializerFn<T> = Arc<dyn Fn(&StreamId) -> RedsumerResult<T> + Send + Sync>;
+
+/// A registry of deserializers by schema version, to consume messages of several payload formats during a rolling deploy.
+///
+/// The schema version of a message is read from the [`SCHEMA_VERSION_HEADER`] field, written by [`Envelope::schema_version`](crate::envelope::Envelope::schema_version) or by every message of a producer with [`Producer::with_schema_version`](crate::producer::Producer::with_schema_version). Clones share the registered deserializers.
+pub struct VersionedDeserializer<T> {
+    /// Registered deserializers by schema version.
+    deserializers: HashMap<String, DeserializerFn<T>>,
+
+    /// Deserializer of the messages without schema version. If it is `None`, those messages are rejected.
+    unversioned: Option<DeserializerFn<T>>,
+}
+
+impl<T> VersionedDeserializer<T> {
+    /// Build a new [`VersionedDeserializer`] instance without deserializers.
+    pub fn new() -> Self {
+        VersionedDeserializer {
+            deserializers: HashMap::new(),
+            unversioned: None,
+        }
+    }
+
+    /// Get the registered **versions**, in no particular order.
+    pub fn get_versions(&self) -> Vec<&str> {
+        self.deserializers.keys().map(String::as_str).collect()
+    }
+
+    /// Register the deserializer of a schema *version*, replacing the deserializer registered before for the same version.
+    ///
+    /// # Arguments:
+    /// - **version**: The schema version.
+    /// - **deserializer**: A function to deserialize the messages of the schema version.
+    ///
+    /// # Returns:
+    /// The [`VersionedDeserializer`] instance with the given deserializer.
+    pub fn register<F>(mut self, version: &str, deserializer: F) -> Self
+    where
+        F: Fn(&StreamId) -> RedsumerResult<T> + Send + Sync + 'static,
+    {
+        self.deserializers
+            .insert(version.to_owned(), Arc::new(deserializer));
+        self
+    }
+
+    /// Set the deserializer of the messages without schema version, like the ones produced before the header was introduced.
+    ///
+    /// # Arguments:
+    /// - **deserializer**: A function to deserialize the messages without schema version.
+    ///
+    /// # Returns:
+    /// The [`VersionedDeserializer`] instance with the given deserializer.
+    pub fn unversioned<F>(mut self, deserializer: F) -> Self
+    where
+        F: Fn(&StreamId) -> RedsumerResult<T> + Send + Sync + 'static,
+    {
+        self.unversioned = Some(Arc::new(deserializer));
+        self
+    }
+
+    /// Deserialize a consumed *message* with the deserializer of its schema version.
+    ///
+    /// # Arguments:
+    /// - **message**: A consumed message.
+    ///
+    /// # Returns:
+    /// A [`RedsumerResult`] with the deserialized message. If there is no deserializer for the schema version of the message, a [`RedsumerError::DeserializationError`] is returned. Errors of the deserializer are returned as they are.
+    pub fn deserialize(&self, message: &StreamId) -> RedsumerResult<T> {
+        let headers: Headers = Headers::from_message(message)?;
+
+        let deserializer: Option<&DeserializerFn<T>> = match headers.get_schema_version() {
+            Some(version) => self.deserializers.get(version),
+            None => self.unversioned.as_ref(),
+        };
+
+        match deserializer {
+            Some(deserializer) => {
+                debug!(
+                    "Deserializing message {} with schema version {:?}",
+                    message.id,
+                    headers.get_schema_version()
+                );
+                deserializer(message)
+            }
+            None => {
+                error!(
+                    "No deserializer for message {} with schema version {:?}",
+                    message.id,
+                    headers.get_schema_version()
+                );
+                Err(RedsumerError::DeserializationError(format!(
+                    "Unsupported {SCHEMA_VERSION_HEADER} of message {}: {}",
+                    message.id,
+                    headers.get_schema_version().unwrap_or("none")
+                )))
+            }
+        }
+    }
+}
+
+impl<T> Default for VersionedDeserializer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for VersionedDeserializer<T> {
+    fn clone(&self) -> Self {
+        VersionedDeserializer {
+            deserializers: self.deserializers.clone(),
+            unversioned: self.unversioned.clone(),
+        }
+    }
+}
+
+impl<T> Debug for VersionedDeserializer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("VersionedDeserializer")
+            .field("versions", &self.get_versions())
+            .field("unversioned", &self.unversioned.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test_versioned_deserializer {
+    use redis::{from_redis_value, Value};
+
+    use super::*;
+
+    /// Build a message with an optional schema version and an amount in cents (v1) or in units (v2).
+    fn message(version: Option<&str>, amount: &str) -> StreamId {
+        let mut map: HashMap<String, Value> = HashMap::from([(
+            "amount".to_string(),
+            Value::BulkString(amount.as_bytes().to_vec()),
+        )]);
+        if let Some(version) = version {
+            map.insert(
+                SCHEMA_VERSION_HEADER.to_string(),
+                Value::BulkString(version.as_bytes().to_vec()),
+            );
+        }
+
+        StreamId {
+            id: "1-0".to_string(),
+            map,
+        }
+    }
+
+    /// Build a deserializer of the amount in cents.
+    fn deserializer() -> VersionedDeserializer<i64> {
+        VersionedDeserializer::new()
+            .register("1", |message| {
+                Ok(from_redis_value::<i64>(&message.map["amount"])?)
+            })
+            .register("2", |message| {
+                Ok(from_redis_value::<i64>(&message.map["amount"])? * 100)
+            })
+    }
+
+    #[test]
+    fn test_versioned_deserializer_by_version() {
+        // Define the deserializer:
+        let deserializer: VersionedDeserializer<i64> = deserializer();
+
+        // Verify the result:
+        assert_eq!(
+            deserializer
+                .deserialize(&message(Some("1"), "250"))
+                .unwrap(),
+            250
+        );
+        assert_eq!(
+            deserializer.deserialize(&message(Some("2"), "3")).unwrap(),
+            300
+        );
+        assert_eq!(deserializer.get_versions().len(), 2);
+    }
+
+    #[test]
+    fn test_versioned_deserializer_unsupported_version() {
+        // Define the deserializer:
+        let deserializer: VersionedDeserializer<i64> = deserializer();
+
+        // Deserialize messages without deserializer:
+        let unknown: RedsumerResult<i64> = deserializer.deserialize(&message(Some("3"), "1"));
+        let unversioned: RedsumerResult<i64> = deserializer.deserialize(&message(None, "1"));
+
+        // Verify the result:
+        assert!(matches!(
+            unknown.unwrap_err(),
+            RedsumerError::DeserializationError(_)
+        ));
+        assert!(matches!(
+            unversioned.unwrap_err(),
+            RedsumerError::DeserializationError(_)
+        ));
+    }
+
+    #[test]
+    fn test_versioned_deserializer_unversioned() {
+        // Define the deserializer with a fallback for messages without schema version:
+        let deserializer: VersionedDeserializer<i64> = deserializer().unversioned(|_| Ok(0));
+
+        // Verify the result:
+        assert_eq!(deserializer.deserialize(&message(None, "1")).unwrap(), 0);
+    }
+}